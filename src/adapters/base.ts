@@ -6,6 +6,44 @@
 
 import type { LaunchRequestArguments, AttachRequestArguments } from '../dap/protocol.js';
 
+/**
+ * Hot code reload mechanisms:
+ * - js-debug: drop edited CommonJS modules from require.cache and load them again
+ * - debugpy: importlib.reload() the modules loaded from the edited files
+ */
+export type HotReloadKind = 'js-debug' | 'debugpy';
+
+/**
+ * Exception type selection (--break-on-exception "type:Name"):
+ * - condition: this filter, with a condition that tests the thrown value's type
+ * - exceptionOptions: DAP exceptionOptions whose path starts with this category
+ */
+export type ExceptionTypeSupport =
+  | { kind: 'condition'; filter: string; condition: (type: string) => string }
+  | { kind: 'exceptionOptions'; category: string };
+
+/**
+ * Side-effect-free evaluation (--eval-safe): how to ask the adapter to refuse expressions
+ * that could change program state, and how it reports a refusal
+ */
+export interface SafeEvaluation {
+  context: 'watch' | 'repl' | 'hover' | 'clipboard';
+  /** Rewrite the expression (e.g., add a format specifier or wrap it in a command) */
+  expression?: (expression: string) => string;
+  /** Matches the error (or result) of an expression rejected for side effects */
+  rejected: RegExp;
+  /**
+   * Recognizes expressions that change state but that the adapter would still evaluate,
+   * so they are refused before they reach it
+   */
+  mutates?: (expression: string) => boolean;
+  /**
+   * Extract the value from the adapter's response, for rewrites that return command output
+   * @throws Error if the output reports an error
+   */
+  parseResult?: (output: string) => { result: string; type?: string };
+}
+
 export interface LaunchOptions {
   program: string;
   args?: string[];
@@ -14,6 +52,32 @@ export interface LaunchOptions {
   stopAtEntry?: boolean;
   /** Source map path overrides for js-debug (TypeScript/bundled code) */
   sourceMapOverrides?: Record<string, string>;
+  /** Run a module instead of a program file (python -m); program is ignored when set */
+  module?: string;
+  /** Interpreter for the debuggee (e.g., a virtualenv's python) */
+  interpreter?: string;
+  /** Extra arguments for the runtime (e.g., node --import tsx), before the program */
+  runtimeArgs?: string[];
+  /** Runtime to launch instead of the default (e.g., npm); program is ignored when set */
+  runtimeExecutable?: string;
+  /** Globs js-debug skips when stepping and in stack traces (node); replaces the defaults */
+  skipFiles?: string[];
+  /** Only stop and step in user code (default: true for .NET, false for Python) */
+  justMyCode?: boolean;
+  /** Python: debug greenlet-based code (gevent, eventlet) */
+  gevent?: boolean;
+  /** Python: enable Django template debugging even when runserver isn't detected */
+  django?: boolean;
+  /** .NET: require source files to match the PDB checksum (default: true) */
+  requireExactSource?: boolean;
+  /** .NET: symbol servers to load missing PDBs from (adapters with symbolSupport 'launch') */
+  symbolServers?: string[];
+  /** .NET: where those PDBs are cached */
+  symbolCache?: string;
+  /** .NET: download sources through the PDBs' SourceLink maps */
+  sourceLink?: boolean;
+  /** Start the program through a runInTerminal request (adapters with terminalLaunch) */
+  runInTerminal?: boolean;
 }
 
 export interface AttachOptions {
@@ -68,6 +132,64 @@ export interface AdapterConfig {
 
   /** Exception breakpoint filters supported by this adapter */
   exceptionFilters?: string[];
+
+  /** Supports instruction address breakpoints (--break-addr) */
+  instructionBreakpoints?: boolean;
+
+  /**
+   * How --break-on-exception "type:Name" selects exception types. Adapters without it
+   * only support filter ids.
+   */
+  exceptionTypes?: ExceptionTypeSupport;
+
+  /**
+   * Exception filters that stop only on crashes (unhandled exceptions), used by
+   * --until-crash. Native debuggers stop on fatal signals without any filter.
+   */
+  crashFilters?: string[];
+
+  /**
+   * Native debugger that stops on fatal signals (SIGSEGV, SIGABRT, ...); those stops are
+   * reported as signal_received with registers and every thread's stack
+   */
+  nativeSignals?: boolean;
+
+  /**
+   * How to evaluate without side effects (--eval-safe). Adapters without it can't
+   * guarantee side-effect-free evaluation.
+   */
+  safeEvaluation?: SafeEvaluation;
+
+  /**
+   * How edited code is applied to a running debuggee (serve mode session.reload).
+   * Adapters without it don't support hot code reload.
+   */
+  hotReload?: HotReloadKind;
+
+  /**
+   * How symbol servers and SourceLink (--symbol-server, --source-link) are supported:
+   * - launch: the adapter downloads PDBs and sources itself, as set in launchConfig
+   * - prefetch: debug-run puts PDBs next to the program's assemblies before launch and
+   *   downloads SourceLink sources as stacks show them
+   * Adapters without it support neither.
+   */
+  symbolSupport?: 'launch' | 'prefetch';
+
+  /**
+   * Can start the program through a runInTerminal request (LaunchOptions.runInTerminal),
+   * which is how --sandbox confines the program without the adapter. How the started
+   * program connects back to the adapter:
+   * - pipe: a Unix domain socket in the temp directory
+   * - loopback: a TCP connection to localhost
+   */
+  terminalLaunch?: 'pipe' | 'loopback';
+
+  /**
+   * Alternative debugger backends selectable with --backend
+   * (e.g., the dotnet adapter can run on netcoredbg or vsdbg).
+   * When omitted, the adapter chooses automatically in detect().
+   */
+  backends?: Record<string, AdapterConfig>;
 }
 
 /**
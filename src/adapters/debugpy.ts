@@ -59,6 +59,41 @@ async function checkPipDebugpy(pythonCmd: string): Promise<boolean> {
   }
 }
 
+export type PythonDevServer = 'django' | 'flask';
+
+/**
+ * Detect a Django or Flask development server launch
+ */
+export function detectDevServer(options: LaunchOptions): PythonDevServer | null {
+  const args = options.args || [];
+  if (path.basename(options.program || '') === 'manage.py' && args[0] === 'runserver') {
+    return 'django';
+  }
+  if (options.module === 'django' && args[0] === 'runserver') {
+    return 'django';
+  }
+  if (options.module === 'flask' && args.includes('run')) {
+    return 'flask';
+  }
+  return null;
+}
+
+/**
+ * Program arguments for a dev server launch. Auto-reloaders run the app in a worker
+ * subprocess while the parent only watches files, so breakpoints would bind in the
+ * wrong process; reloading is disabled unless the arguments ask for it.
+ */
+export function devServerArgs(server: PythonDevServer, args: string[]): string[] {
+  if (server === 'django') {
+    return args.includes('--noreload') ? args : [...args, '--noreload'];
+  }
+  // flask run: also disable the Werkzeug debugger, which intercepts exceptions
+  const extra = ['--no-reload', '--no-debugger'].filter(
+    (flag) => !args.includes(flag) && !args.includes(flag.replace('--no-', '--'))
+  );
+  return [...args, ...extra];
+}
+
 export const debugpyAdapter: AdapterConfig = {
   id: 'debugpy',
   name: 'debugpy',
@@ -136,15 +171,48 @@ Make sure Python 3 is installed: https://www.python.org/downloads/
       name: 'Python Launch',
       type: 'debugpy',
       request: 'launch',
-      program: path.resolve(options.program),
       args: options.args || [],
-      cwd: options.cwd || path.dirname(path.resolve(options.program)),
       env: options.env || {},
       stopOnEntry: options.stopAtEntry || false,
-      console: 'internalConsole',
-      justMyCode: false, // Show all code, not just user code
+      console: options.runInTerminal ? 'integratedTerminal' : 'internalConsole',
+      justMyCode: options.justMyCode ?? false, // Show all code, not just user code
     };
 
+    if (options.module) {
+      // python -m package.module, resolved from the working directory
+      config.module = options.module;
+      config.cwd = options.cwd || process.cwd();
+    } else {
+      config.program = path.resolve(options.program);
+      config.cwd = options.cwd || path.dirname(path.resolve(options.program));
+    }
+
+    // Django/Flask dev servers: debug the process that actually serves requests
+    const devServer = detectDevServer(options);
+    if (devServer) {
+      config.args = devServerArgs(devServer, options.args || []);
+      config.subProcess = true;
+      if (devServer === 'django') {
+        config.django = true;
+      } else {
+        config.jinja = true;
+      }
+    }
+    if (options.django) {
+      config.django = true;
+    }
+
+    // gevent/eventlet: trace greenlet switches, or breakpoints in greenlets never bind
+    if (options.gevent) {
+      config.gevent = true;
+    }
+
+    // Run the debuggee with the project's interpreter (the adapter itself keeps using
+    // the python that has debugpy installed)
+    if (options.interpreter) {
+      config.python = options.interpreter;
+    }
+
     // If using VS Code bundled debugpy, add its path to PYTHONPATH
     if (cachedSource === 'vscode' && cachedDebugpyPath) {
       const debugpyParentDir = path.dirname(cachedDebugpyPath);
@@ -174,4 +242,9 @@ Make sure Python 3 is installed: https://www.python.org/downloads/
     'uncaught', // Break on uncaught exceptions
     'userUnhandled', // Break on user-unhandled exceptions
   ],
+  exceptionTypes: { kind: 'exceptionOptions', category: 'Python Exceptions' },
+  crashFilters: ['uncaught'],
+  hotReload: 'debugpy',
+  // The launcher connects back to the adapter on localhost
+  terminalLaunch: 'loopback',
 };
@@ -10,23 +10,32 @@ export * from './vsdbg.js';
 export * from './debugpy.js';
 export * from './node.js';
 export * from './lldb.js';
+export * from './rust.js';
+export * from './stdio.js';
 
 import * as path from 'node:path';
 import type { AdapterConfig, LaunchOptions, AttachOptions } from './base.js';
 import { netcoredbgAdapter } from './netcoredbg.js';
-import { vsdbgAdapter } from './vsdbg.js';
+import { vsdbgAdapter, vsdbgSymbolOptions } from './vsdbg.js';
 import { debugpyAdapter } from './debugpy.js';
 import { nodeAdapter } from './node.js';
 import { lldbAdapter } from './lldb.js';
+import { rustAdapter } from './rust.js';
 import { findVsdbg } from '../util/vscode-adapters.js';
 import { isNetcoredbgInstalled, getNetcoredbgPath } from '../util/adapter-installer.js';
+import { isVsdaAvailable } from '../util/vsda-signer.js';
+import { logger } from '../util/logger.js';
 import { commandExists } from './base.js';
 
 /**
  * Smart .NET adapter that tries multiple debuggers in order:
- * 1. vsdbg from VS Code C# extension
+ * 1. vsdbg from VS Code C# extension (only when VS Code's handshake signer is present)
  * 2. netcoredbg (bundled)
  * 3. netcoredbg (system PATH)
+ *
+ * vsdbg is licensed for use with Microsoft's IDEs only and authenticates its client
+ * with a handshake signed by VS Code's vsda module. Without vsda (CI images, containers,
+ * machines without VS Code) netcoredbg is selected instead. Use --backend to override.
  */
 let dotnetCachedPath: string | null = null;
 
@@ -43,16 +52,23 @@ const dotnetAdapter: AdapterConfig = {
   },
 
   detect: async () => {
-    // Try vsdbg first (from VS Code)
+    // Try vsdbg first (from VS Code), if its licensing handshake can be satisfied
     const vsdbgPath = findVsdbg();
-    if (vsdbgPath) {
+    if (vsdbgPath && isVsdaAvailable()) {
       dotnetCachedPath = vsdbgPath;
+      logger.info('adapter', 'selected vsdbg backend', { path: vsdbgPath });
       return vsdbgPath;
     }
+    if (vsdbgPath) {
+      logger.info('adapter', 'vsdbg found but VS Code handshake signer is unavailable', {
+        path: vsdbgPath,
+      });
+    }
 
     // Try bundled netcoredbg
     if (isNetcoredbgInstalled()) {
       dotnetCachedPath = getNetcoredbgPath();
+      logger.info('adapter', 'selected netcoredbg backend', { path: dotnetCachedPath });
       return dotnetCachedPath;
     }
 
@@ -60,9 +76,16 @@ const dotnetAdapter: AdapterConfig = {
     const systemPath = await commandExists('netcoredbg');
     if (systemPath) {
       dotnetCachedPath = systemPath;
+      logger.info('adapter', 'selected netcoredbg backend', { path: systemPath });
       return systemPath;
     }
 
+    // Last resort: vsdbg without a signed handshake (may print a license warning)
+    if (vsdbgPath) {
+      dotnetCachedPath = vsdbgPath;
+      return vsdbgPath;
+    }
+
     return null;
   },
 
@@ -92,13 +115,11 @@ const dotnetAdapter: AdapterConfig = {
     env: options.env || {},
     stopAtEntry: options.stopAtEntry || false,
     console: 'internalConsole',
-    // vsdbg-specific options (ignored by netcoredbg)
-    justMyCode: true,
+    justMyCode: options.justMyCode ?? true,
     enableStepFiltering: true,
-    symbolOptions: {
-      searchMicrosoftSymbolServer: false,
-      searchNuGetOrgSymbolServer: false,
-    },
+    requireExactSource: options.requireExactSource ?? true,
+    // vsdbg-specific options (ignored by netcoredbg)
+    ...vsdbgSymbolOptions(options),
   }),
 
   attachConfig: (options: AttachOptions) => ({
@@ -109,6 +130,22 @@ const dotnetAdapter: AdapterConfig = {
   }),
 
   exceptionFilters: ['all', 'user-unhandled'],
+  exceptionTypes: { kind: 'exceptionOptions', category: 'CLR Exceptions' },
+  crashFilters: ['user-unhandled'],
+  // Only vsdbg understands the "nse" format specifier
+  get safeEvaluation() {
+    return path.basename(dotnetCachedPath ?? '').startsWith('vsdbg')
+      ? vsdbgAdapter.safeEvaluation
+      : undefined;
+  },
+  get symbolSupport(): AdapterConfig['symbolSupport'] {
+    return path.basename(dotnetCachedPath ?? '').startsWith('vsdbg') ? 'launch' : 'prefetch';
+  },
+
+  backends: {
+    netcoredbg: netcoredbgAdapter,
+    vsdbg: vsdbgAdapter,
+  },
 };
 
 const adapters: Map<string, AdapterConfig> = new Map([
@@ -130,21 +167,43 @@ const adapters: Map<string, AdapterConfig> = new Map([
   ['javascript', nodeAdapter],
   ['js', nodeAdapter],
 
-  // LLDB (C/C++/Rust/Swift)
+  // LLDB (C/C++/Swift)
   ['lldb', lldbAdapter],
   ['codelldb', lldbAdapter],
   ['cpp', lldbAdapter],
   ['c', lldbAdapter],
-  ['rust', lldbAdapter],
+
+  // Rust (LLDB or GDB, with Rust pretty-printers)
+  ['rust', rustAdapter],
 ]);
 
 /**
- * Get an adapter configuration by name
+ * Get a registered adapter configuration by name. "stdio:<command>" adapters run an arbitrary
+ * command, so only the CLI builds them (see stdio.ts).
  */
 export function getAdapter(name: string): AdapterConfig | undefined {
   return adapters.get(name.toLowerCase());
 }
 
+/**
+ * Select a specific backend of an adapter (e.g., dotnet + "vsdbg")
+ *
+ * @throws Error if the adapter has no selectable backends or the backend is unknown
+ */
+export function getAdapterBackend(adapter: AdapterConfig, backend: string): AdapterConfig {
+  if (!adapter.backends) {
+    throw new Error(`Adapter "${adapter.name}" does not support --backend`);
+  }
+  const selected = adapter.backends[backend.toLowerCase()];
+  if (!selected) {
+    throw new Error(
+      `Unknown backend "${backend}" for adapter "${adapter.name}". ` +
+        `Available backends: ${Object.keys(adapter.backends).join(', ')}`
+    );
+  }
+  return selected;
+}
+
 /**
  * Get all available adapter names (primary names only, not aliases)
  */
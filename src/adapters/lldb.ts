@@ -39,6 +39,38 @@ function findHomebrewLLDB(): string | null {
 let cachedPath: string | null = null;
 let cachedType: 'lldb-dap' | 'codelldb' | null = null;
 
+/**
+ * Which LLDB adapter flavor detect() found (null before detection)
+ */
+export function getLldbAdapterType(): 'lldb-dap' | 'codelldb' | null {
+  return cachedType;
+}
+
+/**
+ * Extract the value and type from lldb's expression command output ("(int) $0 = 42")
+ *
+ * @throws Error if lldb reported an error
+ */
+export function parseLldbExpressionOutput(output: string): { result: string; type?: string } {
+  const trimmed = output.trim();
+  if (trimmed.startsWith('error:')) {
+    throw new Error(trimmed);
+  }
+  const match = trimmed.match(/^\((.+?)\) \S+ = ([\s\S]*)$/);
+  return match ? { result: match[2], type: match[1] } : { result: trimmed };
+}
+
+/**
+ * Whether a C, C++ or Rust expression assigns (=, +=, ...) or increments/decrements. lldb
+ * interprets those without JIT, so --allow-jit false doesn't stop them.
+ */
+export function isLldbMutation(expression: string): boolean {
+  // Operators inside string and character literals don't count
+  const code = expression.replace(/"(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*'/g, '""');
+  // Not ==, !=, <=, >=, Rust's => or ..=
+  return /\+\+|--|<<=|>>=|(?:^|[^=!<>.])=(?![=>])/.test(code);
+}
+
 export const lldbAdapter: AdapterConfig = {
   id: 'lldb',
   name: 'lldb',
@@ -149,4 +181,17 @@ Options:
     'objc_catch', // Break on Objective-C @catch
     'swift_throw', // Break on Swift throw
   ],
+  instructionBreakpoints: true,
+  nativeSignals: true,
+  // The expression command without JIT can't run code in the target (function calls), but
+  // still assigns, so assignments are refused first.
+  // lldb-dap's repl runs backtick-prefixed input as a command; CodeLLDB's runs all input.
+  safeEvaluation: {
+    context: 'repl',
+    expression: (expression) =>
+      `${cachedType === 'codelldb' ? '' : '`'}expression --allow-jit false -- ${expression}`,
+    rejected: /can't be interpreted or run|\bJIT\b/i,
+    mutates: isLldbMutation,
+    parseResult: parseLldbExpressionOutput,
+  },
 };
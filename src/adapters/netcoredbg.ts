@@ -71,6 +71,9 @@ Install netcoredbg:
     env: options.env || {},
     stopAtEntry: options.stopAtEntry || false,
     console: 'internalConsole',
+    justMyCode: options.justMyCode ?? true,
+    enableStepFiltering: true,
+    requireExactSource: options.requireExactSource ?? true,
   }),
 
   attachConfig: (options: AttachOptions) => ({
@@ -84,4 +87,8 @@ Install netcoredbg:
     'all', // Break on all exceptions
     'user-unhandled', // Break on user-unhandled exceptions
   ],
+  exceptionTypes: { kind: 'exceptionOptions', category: 'CLR Exceptions' },
+  crashFilters: ['user-unhandled'],
+  // Only loads PDBs embedded in or next to an assembly
+  symbolSupport: 'prefetch',
 };
@@ -5,11 +5,13 @@
  * https://github.com/microsoft/vscode-js-debug
  */
 
+import * as fs from 'node:fs';
 import * as path from 'node:path';
 import type { AdapterConfig, LaunchOptions, AttachOptions } from './base.js';
 import { commandExists } from './base.js';
 import { findJsDebug } from '../util/vscode-adapters.js';
 import { isJsDebugInstalled, getJsDebugPath } from '../util/adapter-installer.js';
+import { logger } from '../util/logger.js';
 
 // Cache the detected path and type
 let cachedPath: string | null = null;
@@ -18,6 +20,97 @@ let cachedType: 'js-debug' | 'node' | null = null;
 // Default port for js-debug DAP server
 const JSDEBUG_PORT = 8177;
 
+const TYPESCRIPT_EXTENSIONS = ['.ts', '.mts', '.cts', '.tsx'];
+
+/** Code skipped when stepping and in stack traces unless --skip-files says otherwise */
+export const DEFAULT_SKIP_FILES = ['<node_internals>/**', '**/node_modules/**'];
+
+/**
+ * Turn --skip-files globs into js-debug skipFiles patterns. js-debug matches them against
+ * absolute paths, so a relative glob ("node_modules/**") matches at any depth.
+ */
+export function normalizeSkipFiles(globs: string[]): string[] {
+  return globs.map((glob) => {
+    const negated = glob.startsWith('!');
+    const pattern = negated ? glob.slice(1) : glob;
+    const anchored =
+      pattern.startsWith('<') || pattern.startsWith('**') || path.isAbsolute(pattern);
+    const normalized = anchored ? pattern : `**/${pattern.replace(/^\.\//, '')}`;
+    return negated ? `!${normalized}` : normalized;
+  });
+}
+
+/**
+ * Check if a program is a TypeScript source file that node can't run as-is
+ */
+export function isTypeScriptProgram(program: string): boolean {
+  return TYPESCRIPT_EXTENSIONS.includes(path.extname(program).toLowerCase());
+}
+
+/**
+ * Find an installed TypeScript loader (tsx preferred, then ts-node), searching
+ * node_modules from the given directory upward
+ */
+export function findTypeScriptLoader(fromDir: string): 'tsx' | 'ts-node' | null {
+  let current = path.resolve(fromDir);
+  while (true) {
+    for (const loader of ['tsx', 'ts-node'] as const) {
+      if (fs.existsSync(path.join(current, 'node_modules', loader, 'package.json'))) {
+        return loader;
+      }
+    }
+    const parent = path.dirname(current);
+    if (parent === current) {
+      return null;
+    }
+    current = parent;
+  }
+}
+
+/**
+ * Runtime arguments that let node execute a .ts program in-process (so js-debug
+ * debugs the program itself rather than a loader's child process)
+ */
+export function typeScriptRuntimeArgs(
+  loader: 'tsx' | 'ts-node' | null,
+  nodeVersion: string = process.versions.node
+): string[] {
+  if (loader === 'tsx') {
+    return ['--import', 'tsx'];
+  }
+  if (loader === 'ts-node') {
+    return ['--require', 'ts-node/register'];
+  }
+
+  // No loader installed: fall back to node's built-in type stripping
+  const [major, minor] = nodeVersion.split('.').map((part) => parseInt(part, 10));
+  if (major > 23 || (major === 23 && minor >= 6)) {
+    return []; // Enabled by default
+  }
+  if (major > 22 || (major === 22 && minor >= 6)) {
+    return ['--experimental-strip-types'];
+  }
+  return [];
+}
+
+/**
+ * Resolve runtime arguments for a launch: explicit --runtime-args win, otherwise
+ * TypeScript programs get a loader automatically
+ */
+function resolveRuntimeArgs(options: LaunchOptions): string[] | undefined {
+  if (options.runtimeArgs && options.runtimeArgs.length > 0) {
+    return options.runtimeArgs;
+  }
+  if (!isTypeScriptProgram(options.program)) {
+    return undefined;
+  }
+
+  const loader = findTypeScriptLoader(path.dirname(path.resolve(options.program)));
+  const args = typeScriptRuntimeArgs(loader);
+  logger.info('adapter', 'running TypeScript program', { loader: loader ?? 'node', args });
+  return args.length > 0 ? args : undefined;
+}
+
 export const nodeAdapter: AdapterConfig = {
   id: 'pwa-node',
   name: 'node',
@@ -85,28 +178,59 @@ Alternative options:
   2. Ensure Node.js is installed: https://nodejs.org
 `.trim(),
 
-  launchConfig: (options: LaunchOptions) => ({
-    name: 'Node.js Launch',
-    type: 'pwa-node',
-    request: 'launch',
-    program: path.resolve(options.program),
-    args: options.args || [],
-    cwd: options.cwd || path.dirname(path.resolve(options.program)),
-    env: options.env || {},
-    stopOnEntry: options.stopAtEntry || false,
-    console: 'internalConsole',
-    // js-debug specific options
-    skipFiles: ['<node_internals>/**'],
-    resolveSourceMapLocations: ['**', '!**/node_modules/**'],
-    // Disable child process auto-attach to avoid multi-session complexity
-    autoAttachChildProcesses: false,
-    // Wait for source maps to load before running
-    pauseForSourceMap: true,
-    // Source map path overrides for TypeScript/bundled code
-    ...(options.sourceMapOverrides && {
-      sourceMapPathOverrides: options.sourceMapOverrides,
-    }),
-  }),
+  launchConfig: (options: LaunchOptions) => {
+    if (options.runtimeExecutable) {
+      // Launch through another runtime (e.g., "npm run start"); the real program is a
+      // descendant process, so child processes must be auto-attached
+      return {
+        name: 'Node.js Launch',
+        type: 'pwa-node',
+        request: 'launch',
+        runtimeExecutable: options.runtimeExecutable,
+        runtimeArgs: options.runtimeArgs || [],
+        args: options.args || [],
+        cwd: options.cwd || process.cwd(),
+        env: options.env || {},
+        stopOnEntry: options.stopAtEntry || false,
+        console: options.runInTerminal ? 'integratedTerminal' : 'internalConsole',
+        sourceMaps: true,
+        skipFiles: normalizeSkipFiles(options.skipFiles ?? DEFAULT_SKIP_FILES),
+        resolveSourceMapLocations: ['**', '!**/node_modules/**'],
+        autoAttachChildProcesses: true,
+        pauseForSourceMap: true,
+        ...(options.sourceMapOverrides && {
+          sourceMapPathOverrides: options.sourceMapOverrides,
+        }),
+      };
+    }
+
+    const runtimeArgs = resolveRuntimeArgs(options);
+    return {
+      name: 'Node.js Launch',
+      type: 'pwa-node',
+      request: 'launch',
+      program: path.resolve(options.program),
+      args: options.args || [],
+      cwd: options.cwd || path.dirname(path.resolve(options.program)),
+      env: options.env || {},
+      stopOnEntry: options.stopAtEntry || false,
+      console: options.runInTerminal ? 'integratedTerminal' : 'internalConsole',
+      // Loader/runtime flags (e.g., --import tsx for TypeScript sources)
+      ...(runtimeArgs && { runtimeArgs }),
+      // js-debug specific options
+      sourceMaps: true,
+      skipFiles: normalizeSkipFiles(options.skipFiles ?? DEFAULT_SKIP_FILES),
+      resolveSourceMapLocations: ['**', '!**/node_modules/**'],
+      // Disable child process auto-attach to avoid multi-session complexity
+      autoAttachChildProcesses: false,
+      // Wait for source maps to load before running
+      pauseForSourceMap: true,
+      // Source map path overrides for TypeScript/bundled code
+      ...(options.sourceMapOverrides && {
+        sourceMapPathOverrides: options.sourceMapOverrides,
+      }),
+    };
+  },
 
   attachConfig: (options: AttachOptions) => ({
     name: 'Node.js Attach',
@@ -121,4 +245,18 @@ Alternative options:
     'all', // Break on all exceptions
     'uncaught', // Break on uncaught exceptions only
   ],
+  // js-debug evaluates filter conditions with the thrown value bound to `error`
+  exceptionTypes: {
+    kind: 'condition',
+    filter: 'all',
+    condition: (type) =>
+      `error?.name === ${JSON.stringify(type)} || ` +
+      `error?.constructor?.name === ${JSON.stringify(type)}`,
+  },
+  crashFilters: ['uncaught'],
+  // js-debug evaluates hovers with V8's throwOnSideEffect
+  safeEvaluation: { context: 'hover', rejected: /^EvalError: Possible side-effect/ },
+  hotReload: 'js-debug',
+  // The bootloader reaches js-debug through a named pipe in the temp directory
+  terminalLaunch: 'pipe',
 };
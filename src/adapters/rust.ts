@@ -0,0 +1,238 @@
+/**
+ * Rust Debug Adapter Configuration
+ *
+ * Debugs Rust binaries with either LLDB (lldb-dap / CodeLLDB) or GDB's built-in
+ * DAP interpreter (GDB 14+), and loads the pretty-printers shipped with the Rust
+ * toolchain so Vec, String, HashMap, Option, etc. render as values instead of
+ * raw pointer/capacity/length structs.
+ *
+ * Pretty-printers live in `$(rustc --print sysroot)/lib/rustlib/etc`. MSVC targets
+ * embed natvis files in the PDB, which CodeLLDB and vsdbg pick up automatically.
+ */
+
+import { execSync } from 'node:child_process';
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+import type { AdapterConfig, LaunchOptions, AttachOptions } from './base.js';
+import { commandExists } from './base.js';
+import { lldbAdapter, getLldbAdapterType } from './lldb.js';
+import { logger } from '../util/logger.js';
+
+let sysrootCache: string | null | undefined;
+
+/**
+ * Directory containing the Rust toolchain's debugger scripts, or null if rustc
+ * is unavailable or the scripts are missing
+ */
+export function getRustDebuggerScriptsDir(): string | null {
+  if (sysrootCache === undefined) {
+    try {
+      const sysroot = execSync('rustc --print sysroot', {
+        encoding: 'utf-8',
+        stdio: ['ignore', 'pipe', 'ignore'],
+      }).trim();
+      const etcDir = path.join(sysroot, 'lib', 'rustlib', 'etc');
+      sysrootCache = fs.existsSync(etcDir) ? etcDir : null;
+    } catch {
+      sysrootCache = null;
+    }
+    if (!sysrootCache) {
+      logger.info('adapter', 'Rust pretty-printers not found (is rustc on PATH?)');
+    }
+  }
+  return sysrootCache;
+}
+
+/**
+ * LLDB commands that register the Rust type summaries/synthetic providers
+ * (the same setup rust-lldb performs)
+ */
+export function rustLldbInitCommands(scriptsDir: string): string[] {
+  return [
+    `command script import "${path.join(scriptsDir, 'lldb_lookup.py')}"`,
+    `command source -s 0 "${path.join(scriptsDir, 'lldb_commands')}"`,
+  ];
+}
+
+/**
+ * GDB arguments that allow auto-loading the Rust pretty-printers referenced by
+ * the binary's .debug_gdb_scripts section (the same setup rust-gdb performs)
+ */
+export function rustGdbArgs(scriptsDir: string): string[] {
+  return ['--directory', scriptsDir, '-iex', `add-auto-load-safe-path ${scriptsDir}`];
+}
+
+/**
+ * Add Rust formatter setup to an LLDB launch/attach configuration
+ */
+function withRustFormatters<T extends Record<string, unknown>>(config: T): T {
+  if (getLldbAdapterType() === 'codelldb') {
+    // CodeLLDB bundles its own Rust formatters
+    return { ...config, sourceLanguages: ['rust'] };
+  }
+
+  const scriptsDir = getRustDebuggerScriptsDir();
+  if (!scriptsDir) {
+    return config;
+  }
+  return { ...config, initCommands: rustLldbInitCommands(scriptsDir) };
+}
+
+/**
+ * Rust on LLDB: the lldb adapter plus Rust formatters
+ */
+export const rustLldbAdapter: AdapterConfig = {
+  ...lldbAdapter,
+  name: 'rust-lldb',
+
+  get command() {
+    return lldbAdapter.command;
+  },
+
+  get args() {
+    return lldbAdapter.args;
+  },
+
+  detect: () => lldbAdapter.detect(),
+
+  launchConfig: (options: LaunchOptions) => withRustFormatters(lldbAdapter.launchConfig(options)),
+
+  attachConfig: (options: AttachOptions) => withRustFormatters(lldbAdapter.attachConfig(options)),
+};
+
+let gdbCachedPath: string | null = null;
+
+/**
+ * Rust on GDB via its native DAP interpreter (`gdb -i dap`, GDB 14+)
+ */
+export const rustGdbAdapter: AdapterConfig = {
+  id: 'gdb',
+  name: 'rust-gdb',
+
+  get command() {
+    return gdbCachedPath || 'gdb';
+  },
+
+  get args() {
+    const scriptsDir = getRustDebuggerScriptsDir();
+    return ['--interpreter=dap', ...(scriptsDir ? rustGdbArgs(scriptsDir) : [])];
+  },
+
+  detect: async () => {
+    const gdbPath = await commandExists('gdb');
+    if (!gdbPath) {
+      return null;
+    }
+
+    // The DAP interpreter first shipped in GDB 14
+    try {
+      const version = execSync(`"${gdbPath}" --version`, {
+        encoding: 'utf-8',
+        stdio: ['ignore', 'pipe', 'ignore'],
+      });
+      const major = parseInt(version.match(/(\d+)\.\d+/)?.[1] ?? '0', 10);
+      if (major < 14) {
+        logger.warn('adapter', `GDB ${major} found, but DAP support requires GDB 14 or newer`);
+        return null;
+      }
+    } catch {
+      return null;
+    }
+
+    gdbCachedPath = gdbPath;
+    return gdbPath;
+  },
+
+  installHint: `
+GDB 14 or newer is required for the gdb backend (it provides "gdb -i dap").
+
+  - Ubuntu 24.04+/Debian 13+: apt install gdb
+  - Fedora: dnf install gdb
+  - macOS: brew install gdb (requires codesigning)
+
+Rust pretty-printers are loaded from "rustc --print sysroot", so keep rustc on PATH.
+`.trim(),
+
+  launchConfig: (options: LaunchOptions) => ({
+    name: 'GDB Launch',
+    type: 'gdb',
+    request: 'launch',
+    program: path.resolve(options.program),
+    args: options.args || [],
+    cwd: options.cwd || path.dirname(path.resolve(options.program)),
+    env: options.env || {},
+    stopAtBeginningOfMainSubprogram: options.stopAtEntry || false,
+  }),
+
+  attachConfig: (options: AttachOptions) => ({
+    name: 'GDB Attach',
+    type: 'gdb',
+    request: 'attach',
+    pid: options.pid,
+  }),
+
+  exceptionFilters: ['throw', 'rethrow', 'catch'],
+  instructionBreakpoints: true,
+  nativeSignals: true,
+};
+
+let rustSelected: AdapterConfig = rustLldbAdapter;
+
+/**
+ * Smart Rust adapter: prefers LLDB, falls back to GDB. Use --backend to override.
+ */
+export const rustAdapter: AdapterConfig = {
+  get id() {
+    return rustSelected.id;
+  },
+  name: 'rust',
+
+  get command() {
+    return rustSelected.command;
+  },
+
+  get args() {
+    return rustSelected.args;
+  },
+
+  detect: async () => {
+    for (const backend of [rustLldbAdapter, rustGdbAdapter]) {
+      const detected = await backend.detect();
+      if (detected) {
+        rustSelected = backend;
+        logger.info('adapter', `selected ${backend.name} backend`, { path: detected });
+        return detected;
+      }
+    }
+    return null;
+  },
+
+  installHint: `
+No Rust-capable debugger found. Install one of:
+
+  1. LLDB (recommended): brew install llvm, or apt install lldb
+     (or the CodeLLDB VS Code extension)
+
+  2. GDB 14+: apt install gdb
+
+Select one explicitly with --backend lldb or --backend gdb.
+`.trim(),
+
+  launchConfig: (options: LaunchOptions) => rustSelected.launchConfig(options),
+
+  attachConfig: (options: AttachOptions) => rustSelected.attachConfig(options),
+
+  get exceptionFilters() {
+    return rustSelected.exceptionFilters;
+  },
+  instructionBreakpoints: true,
+  nativeSignals: true,
+  get safeEvaluation() {
+    return rustSelected.safeEvaluation;
+  },
+
+  backends: {
+    lldb: rustLldbAdapter,
+    gdb: rustGdbAdapter,
+  },
+};
@@ -0,0 +1,148 @@
+/**
+ * Inline stdio Adapters (-a "stdio:<command>")
+ *
+ * Runs any command as a DAP server over stdin/stdout, so experimental or in-house adapters
+ * can be used without adding them to the registry. The launch request is generic (program,
+ * args, cwd, env, stopOnEntry) unless a --launch-template file gives the adapter's own
+ * fields, with ${program}, ${args}, ${cwd}, ${env} and ${stopOnEntry} filled in per session.
+ */
+
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+import type { AdapterConfig, LaunchOptions, AttachOptions } from './base.js';
+import { commandExists } from './base.js';
+import type { LaunchRequestArguments } from '../dap/protocol.js';
+import { splitCommandLine } from '../util/npm-scripts.js';
+
+export const STDIO_SCHEME = 'stdio:';
+
+export type LaunchTemplate = Record<string, unknown>;
+
+const PLACEHOLDER_PATTERN = /\$\{(\w+)\}/g;
+
+const TEMPLATE_VARIABLES = ['program', 'args', 'cwd', 'env', 'stopOnEntry'];
+
+/**
+ * Whether an adapter name is a stdio: command line
+ */
+export function isStdioAdapter(name: string): boolean {
+  return name.toLowerCase().startsWith(STDIO_SCHEME);
+}
+
+function isPlainObject(value: unknown): value is Record<string, unknown> {
+  return typeof value === 'object' && value !== null && !Array.isArray(value);
+}
+
+/** Every placeholder name used in a template's strings */
+function placeholders(value: unknown): string[] {
+  if (typeof value === 'string') {
+    return [...value.matchAll(PLACEHOLDER_PATTERN)].map((match) => match[1]);
+  }
+  if (Array.isArray(value)) return value.flatMap(placeholders);
+  if (isPlainObject(value)) return Object.values(value).flatMap(placeholders);
+  return [];
+}
+
+/**
+ * Read a --launch-template file, which must hold a JSON object
+ *
+ * @throws Error if the file can't be read, isn't a JSON object, or uses an unknown placeholder
+ */
+export function loadLaunchTemplate(file: string): LaunchTemplate {
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(fs.readFileSync(file, 'utf-8'));
+  } catch (error) {
+    const reason = error instanceof Error ? error.message : String(error);
+    throw new Error(`Invalid --launch-template "${file}": ${reason}`);
+  }
+  if (!isPlainObject(parsed)) {
+    throw new Error(`Invalid --launch-template "${file}": expected a JSON object`);
+  }
+  const unknown = placeholders(parsed).find((name) => !TEMPLATE_VARIABLES.includes(name));
+  if (unknown !== undefined) {
+    throw new Error(
+      `Invalid --launch-template "${file}": unknown placeholder \${${unknown}} ` +
+        `(use ${TEMPLATE_VARIABLES.map((name) => `\${${name}}`).join(', ')})`
+    );
+  }
+  return parsed;
+}
+
+/**
+ * Fill in a template's placeholders. A string that is only a placeholder takes the value
+ * itself ("${args}" becomes the array); one inside other text is replaced by its text.
+ */
+export function fillLaunchTemplate(
+  template: LaunchTemplate,
+  variables: Record<string, unknown>
+): LaunchRequestArguments {
+  const fill = (value: unknown): unknown => {
+    if (typeof value === 'string') {
+      const whole = value.match(/^\$\{(\w+)\}$/);
+      if (whole) return variables[whole[1]];
+      return value.replace(PLACEHOLDER_PATTERN, (_, name: string) => {
+        const variable = variables[name];
+        return Array.isArray(variable) ? variable.join(' ') : String(variable ?? '');
+      });
+    }
+    if (Array.isArray(value)) return value.map(fill);
+    if (isPlainObject(value)) {
+      return Object.fromEntries(Object.entries(value).map(([key, child]) => [key, fill(child)]));
+    }
+    return value;
+  };
+  return { request: 'launch', ...(fill(template) as LaunchRequestArguments) };
+}
+
+/**
+ * Build an adapter for "stdio:<command line>", or undefined if no command is given
+ */
+export function createStdioAdapter(
+  name: string,
+  template?: LaunchTemplate
+): AdapterConfig | undefined {
+  const [command, ...args] = splitCommandLine(name.slice(STDIO_SCHEME.length));
+  if (!command) return undefined;
+
+  // A relative path is run from where debug-run was started, not the program's cwd
+  const executable = /[\\/]/.test(command) ? path.resolve(command) : command;
+  const id = path.basename(command, path.extname(command));
+
+  return {
+    id,
+    name,
+    command: executable,
+    args,
+
+    detect: async () => {
+      if (executable === command) return commandExists(command);
+      return fs.existsSync(executable) ? executable : null;
+    },
+
+    installHint: `Adapter command "${command}" not found. A stdio: adapter runs a DAP server from a command line, e.g. -a "stdio:./my-adapter --flag".`,
+
+    launchConfig: (options: LaunchOptions) => {
+      const variables = {
+        program: path.resolve(options.program),
+        args: options.args || [],
+        cwd: options.cwd || process.cwd(),
+        env: options.env || {},
+        stopOnEntry: options.stopAtEntry || false,
+      };
+      if (template) {
+        return fillLaunchTemplate(template, variables);
+      }
+      return { name: `${id} Launch`, type: id, request: 'launch', ...variables };
+    },
+
+    attachConfig: (options: AttachOptions) => ({
+      name: `${id} Attach`,
+      type: id,
+      request: 'attach',
+      processId: options.pid,
+      ...(options.host && { host: options.host }),
+      ...(options.port && { port: options.port }),
+    }),
+  };
+}
@@ -8,10 +8,27 @@
 import * as path from 'node:path';
 import type { AdapterConfig, LaunchOptions, AttachOptions } from './base.js';
 import { findVsdbg } from '../util/vscode-adapters.js';
+import { isVsdaAvailable } from '../util/vsda-signer.js';
+import { logger } from '../util/logger.js';
 
 // Cache the detected path
 let cachedPath: string | null = null;
 
+/**
+ * vsdbg's symbol and SourceLink options: only the given symbol servers are searched
+ */
+export function vsdbgSymbolOptions(options: LaunchOptions): Record<string, unknown> {
+  return {
+    symbolOptions: {
+      searchMicrosoftSymbolServer: false,
+      searchNuGetOrgSymbolServer: false,
+      ...(options.symbolServers?.length && { searchPaths: options.symbolServers }),
+      ...(options.symbolCache && { cachePath: options.symbolCache }),
+    },
+    ...(options.sourceLink && { sourceLinkOptions: { '*': { enabled: true } } }),
+  };
+}
+
 export const vsdbgAdapter: AdapterConfig = {
   id: 'coreclr',
   name: 'vsdbg',
@@ -24,6 +41,13 @@ export const vsdbgAdapter: AdapterConfig = {
 
   detect: async () => {
     cachedPath = findVsdbg();
+    if (cachedPath && !isVsdaAvailable()) {
+      // vsdbg only fully trusts clients that sign its handshake with VS Code's vsda module
+      logger.warn(
+        'adapter',
+        'VS Code handshake signer (vsda) not found; vsdbg may refuse to run or print a license warning. Consider --backend netcoredbg'
+      );
+    }
     return cachedPath;
   },
 
@@ -48,13 +72,11 @@ Or install the C# Dev Kit extension for additional features.
     env: options.env || {},
     stopAtEntry: options.stopAtEntry || false,
     console: 'internalConsole',
-    // vsdbg-specific options
-    justMyCode: true,
+    justMyCode: options.justMyCode ?? true,
     enableStepFiltering: true,
-    symbolOptions: {
-      searchMicrosoftSymbolServer: false,
-      searchNuGetOrgSymbolServer: false,
-    },
+    requireExactSource: options.requireExactSource ?? true,
+    // vsdbg-specific options
+    ...vsdbgSymbolOptions(options),
     logging: {
       moduleLoad: false,
       programOutput: true,
@@ -73,4 +95,13 @@ Or install the C# Dev Kit extension for additional features.
   }),
 
   exceptionFilters: ['all', 'user-unhandled'],
+  exceptionTypes: { kind: 'exceptionOptions', category: 'CLR Exceptions' },
+  crashFilters: ['user-unhandled'],
+  symbolSupport: 'launch',
+  // The "nse" (no side effects) format specifier
+  safeEvaluation: {
+    context: 'watch',
+    expression: (expression) => `${expression}, nse`,
+    rejected: /causes side effects/i,
+  },
 };
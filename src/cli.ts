@@ -6,11 +6,32 @@
 
 import * as fs from 'node:fs';
 import * as path from 'node:path';
+import { constants as osConstants } from 'node:os';
 import { createRequire } from 'node:module';
+import * as readline from 'node:readline/promises';
+import { PassThrough } from 'node:stream';
 import { Command, Option } from 'commander';
-import { getAdapter, getAdapterNames } from './adapters/index.js';
-import { DebugSession } from './session/manager.js';
-import { OutputFormatter } from './output/formatter.js';
+import {
+  createStdioAdapter,
+  getAdapter,
+  getAdapterNames,
+  getAdapterBackend,
+  isStdioAdapter,
+  loadLaunchTemplate,
+  type AdapterConfig,
+  type LaunchTemplate,
+} from './adapters/index.js';
+import {
+  DebugSession,
+  ON_EXIT_ACTIONS,
+  SIGINT_MODES,
+  parseOnHitAction,
+  type AdapterTrafficObserver,
+  type OnExitAction,
+  type OnHitAction,
+  type SigintMode,
+} from './session/manager.js';
+import { OutputFormatter, type ProgramStreams } from './output/formatter.js';
 import {
   installNetcoredbg,
   isNetcoredbgInstalled,
@@ -24,19 +45,142 @@ import { launchTestRunner, cleanupTestRunner, type TestRunnerResult } from './ut
 const require = createRequire(import.meta.url);
 const packageJson = require('../package.json');
 const VERSION = packageJson.version;
-import { validateAllBreakpoints } from './session/breakpoints.js';
+import { parseBreakpointSpec, validateAllBreakpoints } from './session/breakpoints.js';
+import { breakpointsFromDiff } from './session/diff-breakpoints.js';
+import { parseStructuralQuery, resolveStructuralBreakpoints } from './session/ast-breakpoints.js';
+import { expandGlobBreakpoints, type BreakpointExpansion } from './session/glob-breakpoints.js';
+import { resolveThrowBreakpoints } from './session/throw-breakpoints.js';
+import { validateExceptionBreakpointSpecs } from './session/exception-breakpoints.js';
+import { parseMemoryWatch, type MemoryWatchSpec } from './session/memory-watch.js';
+import { parseAggregateSpec, type AggregateSpec } from './session/aggregate.js';
+import { buildLaunchOverrides, type LaunchOverrides } from './session/launch-overrides.js';
+import { parseStopCommand } from './session/stop-command.js';
+import { expandDefines, parseDefines, undefinedPlaceholders } from './session/defines.js';
+import { parseTriggerSignal, triggerHint, type SessionTrigger } from './session/trigger.js';
+import { resolveSymbolServer } from './session/dotnet-symbols.js';
+import { parseArmPattern } from './session/output-arming.js';
+import { hitWindowLabels, parseHitWindows, type HitWindow } from './session/hit-windows.js';
+import { MIXED_MODE_ADAPTERS, type MixedModeConfig } from './session/mixed-mode.js';
+import {
+  EVALUATE_CONTEXTS,
+  hasEvaluationContexts,
+  parseAssignment,
+  type EvaluateContext,
+} from './session/variables.js';
+import {
+  formatBreakableLinesReport,
+  parseLineRange,
+  queryBreakableLines,
+  type LineRange,
+} from './session/breakpoint-locations.js';
+import {
+  formatVerifyReport,
+  parseVerifySpecs,
+  verifyBreakpoints,
+  type PendingBreakpoint,
+} from './session/verify.js';
+import { formatCapabilitiesReport, queryCapabilities } from './session/capabilities.js';
+import { formatConformanceReport, runConformance } from './session/conformance.js';
+import {
+  SAMPLE_PROJECTS,
+  generateSample,
+  getSampleProject,
+  sampleBreakpoints,
+  sampleCommand,
+  type GeneratedSample,
+} from './samples/generate.js';
 import { parseSourceMapOverrides, getPresetNames } from './sourcemaps/overrides.js';
 import { diagnoseSourceMaps, formatDiagnoseReport } from './sourcemaps/diagnose.js';
+import { formatSuggestReport, suggestBreakpoints } from './suggest/from-trace.js';
+import { configureLogger, logger, type LogFormat } from './util/logger.js';
+import {
+  isProcessAlive,
+  sweepOrphanedProcesses,
+  terminateTrackedProcessesSync,
+} from './util/process-tree.js';
+import {
+  parseCpuLimit,
+  parseFdLimit,
+  parseMemoryLimit,
+  unsupportedResourceLimits,
+  type ResourceLimits,
+} from './util/resource-limits.js';
+import { detectPythonInterpreter } from './util/python-env.js';
+import { resolveNpmScript, type NpmScriptLaunch } from './util/npm-scripts.js';
+import { buildCargoTarget, type CargoTarget } from './util/cargo.js';
+import {
+  attachDenyRules,
+  configDefaultsFor,
+  loadConfigFiles,
+  type AttachDenyRule,
+  type ConfigDefault,
+} from './util/config.js';
+import { attachRefusals, attachTarget, describeAttachTarget } from './util/attach-guard.js';
+import {
+  discoverInspectors,
+  formatInspectorTargets,
+  matchInspectors,
+  type InspectorTarget,
+} from './util/node-inspector.js';
+import { wslDistroName } from './util/wsl.js';
+import { checkSandbox, defaultHiddenPaths, type SandboxPolicy } from './util/sandbox.js';
+import type { TomlValue } from './util/toml.js';
+import { ResultStore, STORE_QUERIES } from './store/result-store.js';
+import { OtlpExporter, otlpOptionsFromEnv } from './output/otlp.js';
+import { DEFAULT_RESULTS_DIR, ResultRun } from './output/result-files.js';
+import { SessionArtifacts } from './output/artifacts.js';
+import { SessionTimeline } from './output/timeline.js';
+import { ValueExport, valueExportFormat } from './output/value-export.js';
+import {
+  BaselineRecorder,
+  compareToBaseline,
+  formatBaselineDiff,
+  readBaseline,
+  writeBaseline,
+  type Baseline,
+} from './output/baseline.js';
+import { DEFAULT_REDACT_NAMES, Redactor } from './output/redaction.js';
+import { HtmlReport } from './output/html-report.js';
+import { GitHubAnnotations } from './output/gh-annotations.js';
+import { StatusLine } from './output/status-line.js';
+import type { TerminationInfo } from './output/events.js';
+import { DebugServer, WEBSOCKET_PATH } from './server/server.js';
+import { buildOpenApiSpec } from './server/rest.js';
+import { ProjectStateStore } from './server/project-state.js';
+import { TOKEN_ENV } from './server/auth.js';
+import {
+  DEFAULT_KEEP_SESSIONS,
+  parseSavedSessionState,
+  type SavedSessionState,
+  type SessionParams,
+} from './server/sessions.js';
 
 export interface CliOptions {
   adapter: string;
+  backend?: string;
   program?: string;
   args?: string[];
   cwd?: string;
   breakpoint: string[];
+  /** -b glob patterns and the breakpoints they expanded to (filled in before the session) */
+  breakpointExpansions?: BreakpointExpansion[];
+  breakpointsFromDiff?: string;
+  breakAst?: string[];
+  breakThrows?: string[];
   logpoint: string[];
+  breakAddr?: string[];
+  nativeBreak?: string[];
   eval: string[];
   assert: string[];
+  define?: string[];
+  continueIf?: string[];
+  set?: string[];
+  onHit?: string;
+  onExit?: OnExitAction;
+  modules?: boolean;
+  requestStats?: boolean;
+  watchMemory?: string[];
+  aggregate?: string[];
   breakOnException?: string[];
   timeout?: string;
   captureLocals?: boolean;
@@ -45,15 +189,68 @@ export interface CliOptions {
   captureEachStep?: boolean;
   attach?: boolean;
   pid?: number;
+  /** --discover, with its optional filter */
+  discover?: string | boolean;
+  confirmAttach?: boolean;
+  yes?: boolean;
+  allowOtherUser?: boolean;
+  /** --wsl, with its optional distribution name */
+  wsl?: string | boolean;
   trace?: boolean;
   traceInto?: boolean;
   traceLimit?: number;
   traceUntil?: string;
   diffVars?: boolean;
   evalAfterStep?: boolean;
+  evalSafe?: boolean;
+  evalContext?: EvaluateContext;
+  evalExpand?: number;
+  provenance?: boolean;
+  captureBudget?: string;
+  maxHits?: number;
+  skipToHit?: number;
+  activeAfter?: string[];
+  activeUntil?: string[];
+  armOnOutput?: string;
+  includeSource?: number;
+  captureArgs?: boolean;
+  heapSnapshot?: string | boolean;
+  onStopExec?: string;
+  triggerFile?: string;
+  triggerSignal?: string;
+  sigintMode?: SigintMode;
+  limitMem?: string;
+  limitCpu?: string;
+  limitFds?: string;
+  limitsBestEffort?: boolean;
+  sandbox?: boolean;
+  sandboxWrite?: string[];
+  sandboxHide?: string[];
+  sandboxNetwork?: boolean;
+  sandboxAdapter?: boolean;
+  untilCrash?: boolean;
+  sampleExceptions?: string | boolean;
   output?: string;
+  outputFormat?: 'ndjson' | 'html' | 'gh-annotations';
+  name?: string;
+  outDir?: string;
+  artifacts?: string;
+  quietProgram?: boolean;
+  status?: boolean;
+  propagateExitCode?: boolean;
+  resultFd?: number;
+  store?: string;
+  otlp?: string | boolean;
+  timeline?: string;
+  exportValues?: string;
+  expect?: string;
+  expectUpdate?: boolean;
   include?: string[];
   exclude?: string[];
+  redact?: boolean;
+  redactName?: string[];
+  redactEnv?: string[];
+  redactValue?: string[];
   // Test runner options
   testProject?: string;
   testFilter?: string;
@@ -70,6 +267,35 @@ export interface CliOptions {
   stackLimit?: number;
   // Source map options
   sourceMapOverrides?: string;
+  // Raw launch/attach request fields
+  launchArg?: string[];
+  launchJson?: string[];
+  launchTemplate?: string;
+  // Python options
+  module?: string;
+  pythonInterp?: string;
+  gevent?: boolean;
+  django?: boolean;
+  // Node options
+  runtimeArgs?: string[];
+  npmScript?: string;
+  skipFiles?: string[] | false;
+  // .NET options
+  justMyCode?: 'on' | 'off';
+  requireExactSource?: 'on' | 'off';
+  symbolServer?: string[];
+  sourceLink?: boolean;
+  // Rust options
+  cargoBin?: string;
+  cargoExample?: string;
+  features?: string[];
+  stepFilter?: string[];
+  // Diagnostic logging options
+  verbose?: number;
+  logFile?: string;
+  logFormat?: LogFormat;
+  /** false with --no-config (skip config files) */
+  config?: boolean;
 }
 
 export function parseTimeout(value: string): number {
@@ -93,44 +319,268 @@ export function parseTimeout(value: string): number {
   }
 }
 
+/**
+ * A config file value as an option value: booleans for switches (and on/off choices),
+ * arrays for options that take several values, and strings or numbers run through the
+ * option's own parsing
+ *
+ * @throws Error if the command has no such option or the value doesn't fit it
+ */
+export function configOptionValue(
+  command: Command,
+  key: string,
+  value: TomlValue
+): { attribute: string; value: unknown } {
+  const option = command.options.find((o) => o.long === `--${key}` && !o.negate);
+  const negated = command.options.find((o) => o.long === `--no-${key}`);
+  if (key === 'adapter' || key === 'config') {
+    throw new Error(`"${key}" can't be set in a config file`);
+  }
+  if (!option && !negated) {
+    throw new Error(`unknown option "${key}"`);
+  }
+
+  if (!option || option.isBoolean()) {
+    if (typeof value !== 'boolean') {
+      throw new Error(`"${key}" is a switch; expected true or false`);
+    }
+    return { attribute: (option ?? negated)!.attributeName(), value };
+  }
+  if (typeof value === 'boolean') {
+    // "skip-files = false" is --no-skip-files, "just-my-code = false" is --just-my-code off
+    if (!value && negated) {
+      return { attribute: negated.attributeName(), value: false };
+    }
+    if (option.argChoices?.includes('on') && option.argChoices.includes('off')) {
+      return { attribute: option.attributeName(), value: value ? 'on' : 'off' };
+    }
+    throw new Error(`"${key}" takes a value, not ${value}`);
+  }
+
+  const items = Array.isArray(value) ? value : [value];
+  if (items.length > 1 && !option.variadic) {
+    throw new Error(`"${key}" takes one value, not a list`);
+  }
+  let parsed: unknown;
+  for (const item of items) {
+    if (typeof item !== 'string' && typeof item !== 'number') {
+      throw new Error(`"${key}" expects strings or numbers`);
+    }
+    const raw = String(item);
+    try {
+      parsed = option.parseArg
+        ? option.parseArg(raw, parsed)
+        : option.variadic
+          ? [...((parsed as string[] | undefined) ?? []), raw]
+          : raw;
+    } catch (error) {
+      throw new Error(`"${key}": ${error instanceof Error ? error.message : error}`);
+    }
+  }
+  return { attribute: option.attributeName(), value: parsed };
+}
+
+/**
+ * Fill in options not given on the command line from the user and project config files
+ */
+function applyConfigDefaults(command: Command, adapterName: string): void {
+  const adapter = lookupAdapter(adapterName);
+  // An unknown adapter is reported when the session starts
+  if (!adapter || command.getOptionValue('config') === false) return;
+
+  let defaults: Map<string, ConfigDefault>;
+  try {
+    defaults = configDefaultsFor(loadConfigFiles(), adapter);
+  } catch (error) {
+    console.error(`Error: ${error instanceof Error ? error.message : error}`);
+    process.exit(1);
+  }
+
+  for (const [key, { value, source }] of defaults) {
+    let option: { attribute: string; value: unknown };
+    try {
+      option = configOptionValue(command, key, value);
+    } catch (error) {
+      console.error(`Error: ${source}: ${error instanceof Error ? error.message : error}`);
+      process.exit(1);
+    }
+    const given = command.getOptionValueSource(option.attribute);
+    if (given === 'cli' || given === 'env') continue;
+    command.setOptionValueWithSource(option.attribute, option.value, 'config');
+    logger.debug('cli', 'option from config file', { option: key, source });
+  }
+}
+
 export function createCli(): Command {
   const program = new Command();
 
   program
     .name('debug-run')
     .description('CLI tool enabling AI agents to programmatically debug code via DAP')
-    .version(VERSION);
+    .version(VERSION)
+    // Subcommands have their own -a, -t, -b, --args, --cwd, ...: options after a subcommand
+    // name are its options, not the main command's
+    .enablePositionalOptions()
+    // Diagnostic logging options (apply to all subcommands; give them before the subcommand)
+    .option(
+      '-v, --verbose',
+      'Increase diagnostic verbosity on stderr (-v phases, -vv details, -vvv DAP traffic)',
+      (_value: string, previous: number) => previous + 1,
+      0
+    )
+    .option('--log-file <path>', 'Write diagnostic logs to a file instead of stderr')
+    .addOption(
+      new Option('--log-format <format>', 'Diagnostic log format')
+        .choices(['text', 'json'])
+        .default('text')
+    )
+    .hook('preAction', (command) => {
+      const opts = command.opts<Pick<CliOptions, 'verbose' | 'logFile' | 'logFormat'>>();
+      try {
+        configureLogger({
+          verbosity: opts.verbose,
+          file: opts.logFile,
+          format: opts.logFormat,
+        });
+      } catch (error) {
+        const message = error instanceof Error ? error.message : String(error);
+        console.error(`Error: Cannot open log file: ${message}`);
+        process.exit(1);
+      }
+    });
 
   // Main debug command (default)
   program
     .argument('[program]', 'Program to debug')
-    .option('-a, --adapter <name>', `Debug adapter to use (${getAdapterNames().join(', ')})`)
+    .option(
+      '-a, --adapter <name>',
+      `Debug adapter to use (${getAdapterNames().join(', ')}, or stdio:<command>)`
+    )
+    .option(
+      '--backend <name>',
+      'Debugger backend (dotnet: netcoredbg, vsdbg; rust: lldb, gdb; default: auto)'
+    )
     .option('--args <args...>', 'Arguments to pass to the program')
     .option('--cwd <path>', 'Working directory for the program')
+    .option('-m, --module <name>', 'Run a Python module instead of a file (python -m <name>)')
+    .option(
+      '--python-interp <path>',
+      'Python interpreter for the debuggee (default: auto-detect venv, poetry or conda)'
+    )
+    .option('--gevent', 'python: debug greenlet-based servers (gevent, eventlet)')
+    .option('--django', 'python: enable Django template debugging (automatic for runserver)')
+    .option(
+      '--runtime-args <args...>',
+      'Arguments for the node runtime before the program (default for .ts: --import tsx)'
+    )
+    .option(
+      '--npm-script <name>',
+      'Debug a package.json script (npm/yarn/pnpm) instead of a program (implies -a node)'
+    )
+    .option(
+      '--cargo-bin <name>',
+      'Build a cargo binary target and debug the executable cargo reports (implies -a rust)'
+    )
+    .option('--cargo-example <name>', 'Build a cargo example and debug it (implies -a rust)')
+    .option(
+      '--features <features...>',
+      'Cargo features to build --cargo-bin/--cargo-example with (e.g., "tls,metrics")'
+    )
+    .option(
+      '--skip-files <glob...>',
+      'node: code to skip when stepping and leave out of stacks (default: "<node_internals>/**" "node_modules/**")'
+    )
+    .option('--no-skip-files', 'node: step into and report every frame, including Node internals')
+    .addOption(
+      new Option(
+        '--just-my-code <mode>',
+        'dotnet, python: only stop and step in user code (default: on for dotnet, off for python)'
+      ).choices(['on', 'off'])
+    )
+    .addOption(
+      new Option(
+        '--require-exact-source <mode>',
+        "dotnet: only bind breakpoints when the source file matches the build's PDB (default: on)"
+      ).choices(['on', 'off'])
+    )
+    .option(
+      '--symbol-server <url...>',
+      'dotnet: load PDBs the program is missing from symbol servers (a URL, "microsoft" or "nuget")'
+    )
+    .option(
+      '--source-link',
+      "dotnet: download dependency sources through their PDBs' SourceLink (stacks show real files)"
+    )
     .option(
       '-b, --breakpoint <spec...>',
-      'Breakpoint specifications (e.g., "file.ts:45" or "file.ts:45?condition")',
+      'Breakpoint specifications (e.g., "file.ts:45", "file.ts:45?condition", "file.ts:45#label" or "src/**/pricing*.rs:fn:calculate_*")',
       []
     )
+    .option(
+      '--breakpoints-from-diff <rev>',
+      'Break on the changed code since a git revision (e.g., HEAD~1 or main)'
+    )
+    .option(
+      '--break-ast <query...>',
+      'Break at a point in a function by structure instead of line (e.g., "fn:calculate_discount:first-return", "loop-body:process_order")'
+    )
+    .option(
+      '--break-throws <path...>',
+      'Break on every throw, raise or panic! statement in these files or directories (e.g., src/services/)'
+    )
     .option('-e, --eval <expr...>', 'Expressions to evaluate when breakpoints are hit', [])
     .option(
       '--assert <expr...>',
       'Invariant expressions that must remain truthy; stops on first violation',
       []
     )
+    .option(
+      '--define <name=value...>',
+      'Fill ${NAME} in breakpoints, evaluations and assertions (e.g., ORDER_ID=ORD-002)'
+    )
+    .option(
+      '--continue-if <expr...>',
+      'Resume without capturing a breakpoint hit when any of these expressions is truthy'
+    )
+    .option(
+      '--set <assignment...>',
+      'Assign variables after capturing each breakpoint hit (e.g., "order.total=0")'
+    )
+    .option(
+      '--on-hit <action>',
+      'After capturing a breakpoint hit: restart-frame (re-run the function with --set applied) or goto:<file>:<line> (skip ahead)'
+    )
+    .addOption(
+      new Option(
+        '--on-exit <action>',
+        'When the program exits: restart (relaunch it, keeping the adapter and breakpoints)'
+      ).choices([...ON_EXIT_ACTIONS])
+    )
     .option(
       '-l, --logpoint <spec...>',
       'Logpoint specifications (e.g., "file.ts:45|log message with {expr}")',
       []
     )
+    .option(
+      '--break-addr <address...>',
+      'Instruction address breakpoints for native adapters (e.g., "0x55aa113e", "0x55aa113e#2")'
+    )
+    .option(
+      '--native-break <spec...>',
+      'Also attach lldb and break in native extension code (e.g., "src/fastsum.c:42"); each hit includes the Python stack (-a python)'
+    )
     .option(
       '--break-on-exception <filter...>',
-      'Break on exceptions (e.g., "all", "user-unhandled", "uncaught")'
+      'Break on exceptions (e.g., "all", "uncaught", "type:ValidationError", "all?error.code")'
     )
     .option('-t, --timeout <duration>', 'Session timeout (e.g., 30s, 5000ms, 2m)', '60s')
     .option('--capture-locals', 'Capture local variables at breakpoints', true)
     .option('--no-capture-locals', 'Disable capturing local variables')
     .option('--pretty', 'Pretty print JSON output', false)
+    .option(
+      '--no-config',
+      'Ignore option defaults in ~/.config/debug-run/config.toml and .debug-run.toml'
+    )
     .option(
       '-s, --steps <count>',
       'Number of steps to execute after hitting a breakpoint (step over)',
@@ -143,6 +593,10 @@ export function createCli(): Command {
       'Use stepIn instead of stepOver in trace mode (follow into function calls)',
       false
     )
+    .option(
+      '--step-filter <namespace...>',
+      'When a step lands in one of these namespaces or types, step back out (e.g., "Serilog" "Npgsql")'
+    )
     .option(
       '--trace-limit <count>',
       'Maximum steps in trace mode before stopping (default: 500)',
@@ -160,17 +614,247 @@ export function createCli(): Command {
       'Step once before evaluating expressions (useful for evaluating variables being assigned on the breakpoint line)',
       false
     )
+    .option(
+      '--eval-safe',
+      'Evaluate --eval and --assert expressions without side effects; rejected ones are flagged',
+      false
+    )
+    .addOption(
+      new Option(
+        '--eval-context <context>',
+        'DAP context for --eval expressions (override per expression with a prefix, e.g. "repl:order")'
+      ).choices([...EVALUATE_CONTEXTS])
+    )
+    .option(
+      '--eval-expand <depth>',
+      'Expand structured --eval results this many levels deep, like locals (default: 0)',
+      (val: string) => parseInt(val, 10)
+    )
+    .option(
+      '--provenance',
+      "Tag each captured variable with its frame, scope, variablesReference path and evaluateName"
+    )
+    .option(
+      '--capture-budget <duration>',
+      'Time each stop may spend capturing locals and --eval results (e.g., 2s); values not fetched in time are marked truncated'
+    )
+    .option(
+      '--max-hits <count>',
+      'End the session after this many breakpoint hits (session_end lists the hit order)',
+      (val: string) => parseInt(val, 10)
+    )
+    .option(
+      '--skip-to-hit <n>',
+      'Resume the first n-1 breakpoint hits without capturing them, e.g. to get back to a stop from an earlier run',
+      (val: string) => parseInt(val, 10)
+    )
+    .option(
+      '--active-after <label=counter@count...>',
+      'Capture the labelled breakpoint only from the count-th hit of the counter breakpoint on (e.g., parse=iteration@10)'
+    )
+    .option(
+      '--active-until <label=counter@count...>',
+      'Capture the labelled breakpoint only up to the count-th hit of the counter breakpoint (e.g., parse=iteration@20)'
+    )
+    .option(
+      '--arm-on-output <pattern>',
+      'Set breakpoints only once the program prints a line matching this regex (e.g., "Server listening on"), skipping its warm-up'
+    )
+    .option(
+      '--propagate-exit-code',
+      "Exit with the program's exit code (128 + signal number if a signal killed it)",
+      false
+    )
+    .option(
+      '--include-source <lines>',
+      'Include this many source lines around each stop location in stop events',
+      (val: string) => parseInt(val, 10)
+    )
+    .option(
+      '--capture-args',
+      "Attach each stack frame's argument values to it (a few more adapter requests per frame)"
+    )
+    .option(
+      '--heap-snapshot [dir]',
+      'Write a V8 heap snapshot at each breakpoint hit, with a summary in the event (node)'
+    )
+    .option(
+      '--on-stop-exec <command>',
+      'Run a command at each breakpoint hit and attach its output, with {name} filled in from captured values (e.g., "./capture_db_state.sh {order_id}")'
+    )
+    .option(
+      '--trigger-file <file>',
+      'Launch with breakpoints bound but hold the program at entry until this file exists'
+    )
+    .option(
+      '--trigger-signal <signal>',
+      'Hold the launched program at entry until debug-run receives this signal (USR1, USR2, HUP)'
+    )
+    .option(
+      '--watch-memory <spec...>',
+      'Record bytes at an address at every stop and on writes (e.g., "&counter:8"), logging each change'
+    )
+    .option(
+      '--aggregate <spec...>',
+      'Report statistics of an expression across breakpoint hits instead of each hit (e.g., "item.price:min,max,mean")'
+    )
+    .option(
+      '--modules',
+      'List loaded modules (DLLs, shared libraries) and whether their symbols loaded in session_end'
+    )
+    .option(
+      '--request-stats',
+      'Report the count and round-trip time of DAP requests per command in session_end'
+    )
+    .option(
+      '--limit-mem <size>',
+      'Memory limit for the launched program (e.g., 512M, 2G; Linux and Windows)'
+    )
+    .option(
+      '--limit-cpu <duration>',
+      'CPU time limit for the launched program (e.g., 120s, 5m; Linux and Windows)'
+    )
+    .option('--limit-fds <count>', 'Open file descriptor limit for the launched program (Linux)')
+    .option(
+      '--limits-best-effort',
+      'Keep running without the --limit-* limits when they cannot be applied, instead of failing'
+    )
+    .option(
+      '--sandbox',
+      'Run the launched program with a read-only file system, hidden credentials and no network (Linux: bwrap, macOS: sandbox-exec)'
+    )
+    .option(
+      '--sandbox-write <path...>',
+      'With --sandbox: paths the program may write to besides the temp directory (e.g., . for the working directory)'
+    )
+    .option(
+      '--sandbox-hide <path...>',
+      "With --sandbox: paths the program can't read, instead of the default credential stores (~/.ssh, ~/.aws, ...)"
+    )
+    .option('--sandbox-network', 'With --sandbox: allow network access')
+    .option('--sandbox-adapter', 'With --sandbox: run the debug adapter in the sandbox too')
+    .option(
+      '--until-crash',
+      'Run without breakpoints until the program crashes, then report threads, locals and output',
+      false
+    )
+    .option(
+      '--sample-exceptions [interval]',
+      'Attach and only record exceptions, resuming at once, with a report every interval (default: 60s)'
+    )
     .option('-o, --output <file>', 'Write events to file instead of stdout')
-    .option('--include <types...>', 'Only emit these event types (e.g., breakpoint_hit error)')
+    .addOption(
+      new Option(
+        '--output-format <format>',
+        'ndjson: the event stream; html: a standalone report written when the session ends; gh-annotations: GitHub Actions workflow commands'
+      )
+        .choices(['ndjson', 'html', 'gh-annotations'])
+        .default('ndjson')
+    )
+    .option(
+      '--quiet-program',
+      "Write the program's output to stderr instead of as program_output events on stdout",
+      false
+    )
+    .option('--no-status', 'Hide the live status line (startup phase, adapter progress) on stderr')
+    .option(
+      '--result-fd <fd>',
+      "Write events to this file descriptor (e.g., 3 with 3>results.ndjson); the program's output stays on stdout/stderr",
+      (value: string) => parseInt(value, 10)
+    )
+    .option(
+      '--artifacts <dir>',
+      'Write the DAP trace, program output, adapter stderr and stop snapshots to a session folder in <dir>'
+    )
+    .option(
+      '--name <name>',
+      'Name this run; events go to a timestamped file in --out-dir (also names --artifacts folders)'
+    )
+    .option(
+      '--out-dir <dir>',
+      `Write events to <name>-<timestamp>.ndjson here, with a "latest" link and index.json (default: ${DEFAULT_RESULTS_DIR} with --name)`
+    )
+    .addOption(
+      new Option(
+        '--sigint-mode <mode>',
+        'Ctrl+C behavior: snapshot (pause and capture, then forward SIGINT, then tear down), forward (forward SIGINT, then tear down) or stop'
+      )
+        .choices([...SIGINT_MODES])
+        .default('snapshot')
+    )
+    .option(
+      '--store <db>',
+      'Append the session, stops, variables and evaluations to a SQLite database (see "query")'
+    )
+    .option(
+      '--otlp [endpoint]',
+      'Export session phase spans and stop events via OTLP/HTTP (default: OTEL_EXPORTER_OTLP_ENDPOINT or localhost:4318)'
+    )
+    .option(
+      '--timeline <file>',
+      'Write a Chrome trace of the session (phases, pauses, breakpoints, stops, output) for chrome://tracing or Perfetto'
+    )
+    .option(
+      '--export-values <file>',
+      'Write every captured local and evaluation as a table row (session, hit, label, name, type, value) to a .csv or .parquet file'
+    )
+    .option(
+      '--expect <file>',
+      'Compare the captured locals and evaluations with a baseline JSON file and fail with a diff if they differ'
+    )
+    .option('--expect-update', 'Write this run as the --expect baseline instead of comparing')
+    .option(
+      '--include <types...>',
+      'Only emit these event types (e.g., breakpoint_hit error) and sections (stacks, variables, evaluations, source, output, threads, modules)'
+    )
     .option(
       '--exclude <types...>',
-      'Suppress these event types (e.g., program_output exception_thrown)'
+      'Suppress these event types (e.g., program_output exception_thrown) and sections (e.g., modules,threads)'
+    )
+    .option(
+      '--redact',
+      'Mask variables and env vars named like password/secret/token/api key in events, output and artifacts'
+    )
+    .option(
+      '--redact-name <regex...>',
+      'Mask variables and env vars whose name matches, instead of the defaults (implies --redact)'
+    )
+    .option(
+      '--redact-env <name...>',
+      'Mask the values of these env vars wherever they appear (implies --redact)'
+    )
+    .option(
+      '--redact-value <regex...>',
+      'Mask text matching a regex in any captured value or output (implies --redact)'
     )
     .addOption(new Option('--env <key=value...>', 'Environment variables for the program'))
+    .option(
+      '--launch-arg <key=value...>',
+      'Set a field of the DAP launch/attach request (e.g., sourceMaps=true, env.DEBUG=app:*); JSON values are parsed'
+    )
+    .option(
+      '--launch-json <file...>',
+      'Deep-merge a JSON object from a file into the DAP launch/attach request'
+    )
+    .option(
+      '--launch-template <file>',
+      'JSON launch request for a stdio: adapter, with ${program}, ${args}, ${cwd}, ${env} and ${stopOnEntry} filled in'
+    )
     .option('--attach', 'Attach to a running process instead of launching', false)
     .option('--pid <processId>', 'Process ID to attach to (requires --attach)', (val: string) =>
       parseInt(val, 10)
     )
+    .option(
+      '--discover [match]',
+      'node: find processes started with --inspect and attach to one (implies --attach); match narrows by port, pid or script'
+    )
+    .option('--confirm-attach', 'Ask before attaching, naming the process, its owner and the host')
+    .option('--yes', 'Answer --confirm-attach ahead of time (required without a terminal)')
+    .option('--allow-other-user', 'Attach to a process owned by another user (refused by default)')
+    .option(
+      '--wsl [distro]',
+      'Windows: run the adapter and program inside WSL (default distribution if omitted), translating paths'
+    )
     .option(
       '--test-project <path>',
       'Run dotnet test with VSTEST_HOST_DEBUG=1 and auto-attach (for NUnit/xUnit/MSTest)'
@@ -240,7 +924,8 @@ export function createCli(): Command {
           compact?: boolean;
           stackLimit?: number;
           sourceMapOverrides?: string;
-        }
+        },
+        command: Command
       ) => {
         // Handle test runner mode
         if (options.testProject) {
@@ -249,14 +934,20 @@ export function createCli(): Command {
             // Default to vsdbg for .NET tests
             options.adapter = 'vsdbg';
           }
+          applyConfigDefaults(command, options.adapter);
           await runTestDebugSession({ ...options, program: programPath, adapter: options.adapter });
           return;
         }
 
+        if (options.discover !== undefined) {
+          options.attach = true;
+          options.adapter ??= 'node';
+        }
+
         // Validate attach mode
         if (options.attach) {
-          if (!options.pid) {
-            console.error('Error: --pid is required when using --attach');
+          if (!options.pid && options.discover === undefined) {
+            console.error('Error: --pid is required when using --attach (or -a node --discover)');
             console.error(
               'Usage: debug-run --attach --pid <processId> -a <adapter> -b <breakpoint>'
             );
@@ -264,25 +955,116 @@ export function createCli(): Command {
           }
         } else {
           // Launch mode requires a program
-          if (!programPath) {
+          if (
+            !programPath &&
+            !options.module &&
+            !options.npmScript &&
+            !options.cargoBin &&
+            !options.cargoExample
+          ) {
             console.error(
-              'Error: <program> argument is required (or use --attach --pid, or --test-project)'
+              'Error: <program> argument is required (or use --module, --attach --pid, or --test-project)'
             );
             console.error('Usage: debug-run <program> -a <adapter> -b <breakpoint>');
             process.exit(1);
           }
         }
 
+        if (options.npmScript && !options.adapter) {
+          options.adapter = 'node';
+        }
+        if ((options.cargoBin || options.cargoExample) && !options.adapter) {
+          options.adapter = 'rust';
+        }
+
         if (!options.adapter) {
           console.error('Error: --adapter is required');
           console.error(`Available adapters: ${getAdapterNames().join(', ')}`);
           process.exit(1);
         }
+        applyConfigDefaults(command, options.adapter);
+
+        if (options.breakpointsFromDiff) {
+          let specs: string[];
+          try {
+            specs = breakpointsFromDiff(options.breakpointsFromDiff, {
+              cwd: options.cwd,
+              adapter: getAdapter(options.adapter)?.name,
+            });
+          } catch (error) {
+            console.error(`Error: ${error instanceof Error ? error.message : error}`);
+            process.exit(1);
+          }
+          if (specs.length === 0) {
+            console.error(
+              `Error: No changed executable lines since ${options.breakpointsFromDiff}`
+            );
+            process.exit(1);
+          }
+          logger.info('cli', 'derived breakpoints from diff', {
+            revision: options.breakpointsFromDiff,
+            breakpoints: specs,
+          });
+          options.breakpoint = [...(options.breakpoint || []), ...specs];
+        }
+
+        if (options.breakAst?.length) {
+          let specs: string[];
+          try {
+            const queries = options.breakAst.map((query) => parseStructuralQuery(query));
+            specs = resolveStructuralBreakpoints(queries, {
+              cwd: options.cwd,
+              adapter: getAdapter(options.adapter)?.name,
+            });
+          } catch (error) {
+            console.error(`Error: ${error instanceof Error ? error.message : error}`);
+            process.exit(1);
+          }
+          logger.info('cli', 'derived breakpoints from structural queries', {
+            queries: options.breakAst,
+            breakpoints: specs,
+          });
+          options.breakpoint = [...(options.breakpoint || []), ...specs];
+        }
+
+        if (options.breakThrows?.length) {
+          let specs: string[];
+          try {
+            specs = resolveThrowBreakpoints(options.breakThrows, {
+              cwd: options.cwd,
+              adapter: getAdapter(options.adapter)?.name,
+            });
+          } catch (error) {
+            console.error(`Error: ${error instanceof Error ? error.message : error}`);
+            process.exit(1);
+          }
+          logger.info('cli', 'derived breakpoints from throw sites', {
+            paths: options.breakThrows,
+            breakpoints: specs,
+          });
+          options.breakpoint = [...(options.breakpoint || []), ...specs];
+        }
+
+        if (options.breakpoint?.length) {
+          let expanded: ReturnType<typeof expandGlobBreakpoints>;
+          try {
+            expanded = expandGlobBreakpoints(options.breakpoint, { cwd: options.cwd });
+          } catch (error) {
+            console.error(`Error: ${error instanceof Error ? error.message : error}`);
+            process.exit(1);
+          }
+          if (expanded.expansions.length > 0) {
+            logger.info('cli', 'expanded glob breakpoints', { expansions: expanded.expansions });
+            options.breakpoint = expanded.breakpoints;
+            options.breakpointExpansions = expanded.expansions;
+          }
+        }
 
         // Validate breakpoint and logpoint formats before starting session
         const breakpointErrors = validateAllBreakpoints(
-          options.breakpoint || [],
-          options.logpoint || []
+          [...(options.breakpoint || []), ...(options.nativeBreak || [])],
+          options.logpoint || [],
+          options.breakAddr || []
         );
         if (breakpointErrors.length > 0) {
           for (const error of breakpointErrors) {
@@ -325,19 +1107,301 @@ export function createCli(): Command {
       }
     );
 
-  // Add diagnose-sources subcommand (Phase 3)
+  // Add query subcommand for --store databases
   program
-    .command('diagnose-sources [directory]')
-    .description('Scan directory for source maps and diagnose path resolution issues')
+    .command('query <db> <query> [expression]')
+    .description(
+      `Run a canned query against a --store database (${Object.keys(STORE_QUERIES).join(', ')})`
+    )
     .option(
-      '--source-map-overrides <jsonOrPreset>',
-      'Source map path overrides (preset name or JSON object)'
+      '--last <runs>',
+      'Only consider the most recent N runs',
+      (val: string) => parseInt(val, 10),
+      10
     )
-    .option('--include-node-modules', 'Include node_modules in scan', false)
-    .option('--verbose', 'Show detailed per-file diagnostics', false)
-    .option('--json', 'Output as JSON instead of human-readable format', false)
     .action(
-      async (
+      (db: string, query: string, expression: string | undefined, options: { last: number }) => {
+        runStoreQuery(db, query, { expression, last: options.last });
+      }
+    );
+
+  // Add breakpoint-locations subcommand
+  program
+    .command('breakpoint-locations <range>')
+    .description('List the breakable lines in a range (file:start-end) as the adapter sees them')
+    .requiredOption(
+      '-a, --adapter <name>',
+      `Debug adapter to use (${getAdapterNames().join(', ')})`
+    )
+    .option(
+      '--backend <name>',
+      'Debugger backend (dotnet: netcoredbg, vsdbg; rust: lldb, gdb; default: auto)'
+    )
+    .option('--program <path>', 'Program to launch (default: the file in the range)')
+    .option('--args <args...>', 'Arguments to pass to the program')
+    .option('--cwd <path>', 'Working directory for the program')
+    .option('-t, --timeout <duration>', 'Request timeout (e.g., 30s, 5000ms, 2m)', '30s')
+    .option('--json', 'Output as JSON instead of human-readable format', false)
+    .action(
+      async (
+        range: string,
+        options: {
+          adapter: string;
+          backend?: string;
+          program?: string;
+          args?: string[];
+          cwd?: string;
+          timeout: string;
+          json: boolean;
+        }
+      ) => {
+        await runBreakpointLocations(range, options);
+      }
+    );
+
+  // Add verify subcommand
+  program
+    .command('verify')
+    .description(
+      'Launch the program only until its breakpoints bind, then report each one and why it did not'
+    )
+    .requiredOption(
+      '-a, --adapter <name>',
+      `Debug adapter to use (${getAdapterNames().join(', ')})`
+    )
+    .option(
+      '--backend <name>',
+      'Debugger backend (dotnet: netcoredbg, vsdbg; rust: lldb, gdb; default: auto)'
+    )
+    .requiredOption('--program <path>', 'Program to launch')
+    .requiredOption('-b, --breakpoint <spec...>', 'Breakpoint specifications to verify')
+    .option('--args <args...>', 'Arguments to pass to the program')
+    .option('--cwd <path>', 'Working directory for the program')
+    .option(
+      '--source-map-overrides <jsonOrPreset>',
+      'Source map path overrides (preset name or JSON object)'
+    )
+    .option('-t, --timeout <duration>', 'How long to wait for the breakpoints to bind', '5s')
+    .option('--json', 'Output as JSON instead of human-readable format', false)
+    .action(
+      async (options: {
+        adapter: string;
+        backend?: string;
+        program: string;
+        breakpoint: string[];
+        args?: string[];
+        cwd?: string;
+        sourceMapOverrides?: string;
+        timeout: string;
+        json: boolean;
+      }) => {
+        await runVerify(options);
+      }
+    );
+
+  // Add capabilities subcommand
+  program
+    .command('capabilities')
+    .description(
+      'Start an adapter, initialize it and list the DAP capabilities it reports (and which options need them)'
+    )
+    .requiredOption(
+      '-a, --adapter <name>',
+      `Debug adapter to use (${getAdapterNames().join(', ')})`
+    )
+    .option(
+      '--backend <name>',
+      'Debugger backend (dotnet: netcoredbg, vsdbg; rust: lldb, gdb; default: auto)'
+    )
+    .option('--cwd <path>', 'Working directory for the adapter')
+    .option('-t, --timeout <duration>', 'How long to wait for the adapter to initialize', '10s')
+    .option('--json', 'Output as JSON instead of human-readable format', false)
+    .action(
+      async (options: {
+        adapter: string;
+        backend?: string;
+        cwd?: string;
+        timeout: string;
+        json: boolean;
+      }) => {
+        await runCapabilities(options);
+      }
+    );
+
+  // Add conformance subcommand
+  program
+    .command('conformance')
+    .description(
+      'Debug a bundled sample program with an adapter, once per DAP feature, and report which features work'
+    )
+    .requiredOption(
+      '-a, --adapter <name>',
+      `Debug adapter to use (${getAdapterNames().join(', ')})`
+    )
+    .option(
+      '--backend <name>',
+      'Debugger backend (dotnet: netcoredbg, vsdbg; rust: lldb, gdb; default: auto)'
+    )
+    .option(
+      '--language <name>',
+      'Sample to debug (javascript, python, csharp, c, rust; default: the adapter\'s)'
+    )
+    .option('--feature <name...>', 'Only check these features (default: all)')
+    .option('-t, --timeout <duration>', 'How long each check may wait for a stop or exit', '30s')
+    .option('--json', 'Output as JSON instead of human-readable format', false)
+    .action(
+      async (options: {
+        adapter: string;
+        backend?: string;
+        language?: string;
+        feature?: string[];
+        timeout: string;
+        json: boolean;
+      }) => {
+        await runConformanceCommand(options);
+      }
+    );
+
+  // Add samples subcommand
+  const samples = program.command('samples').description('Sample apps to try debug-run on');
+  samples
+    .command('generate <language> [dir]')
+    .description(
+      `Write a ready-to-build sample app with its good breakpoint lines marked and a .debug-run.toml (${Object.keys(SAMPLE_PROJECTS).join(', ')})`
+    )
+    .option('--force', 'Overwrite files that already exist', false)
+    .action((language: string, dir: string | undefined, options: { force: boolean }) => {
+      runSamplesGenerate(language, dir, options);
+    });
+
+  // Add suggest subcommand
+  program
+    .command('suggest')
+    .description('Suggest breakpoints from a pasted stack trace or exception log')
+    .requiredOption('--from-trace <file>', 'File containing the stack trace ("-" for stdin)')
+    .option('--root <dir>', 'Repository root to map frames against (default: cwd)')
+    .option(
+      '--limit <count>',
+      'Maximum breakpoints to suggest',
+      (val: string) => parseInt(val, 10),
+      3
+    )
+    .option('--json', 'Output as JSON instead of human-readable format', false)
+    .action((options: { fromTrace: string; root?: string; limit: number; json: boolean }) => {
+      let text: string;
+      try {
+        text = fs.readFileSync(options.fromTrace === '-' ? 0 : options.fromTrace, 'utf-8');
+      } catch (error) {
+        const reason = error instanceof Error ? error.message : error;
+        console.error(`Error: Cannot read ${options.fromTrace}: ${reason}`);
+        process.exit(1);
+      }
+
+      const result = suggestBreakpoints(text, { root: options.root, limit: options.limit });
+      if (options.json) {
+        console.log(JSON.stringify(result, null, 2));
+      } else {
+        console.log(formatSuggestReport(result));
+      }
+      if (result.breakpoints.length === 0) {
+        process.exit(1);
+      }
+    });
+
+  // Add serve subcommand
+  program
+    .command('serve')
+    .description(
+      'Run a local server that starts sessions and exposes them over WebSocket JSON-RPC and REST'
+    )
+    .option('--port <port>', 'Port to listen on', (val: string) => parseInt(val, 10), 7433)
+    .option('--host <host>', 'Interface to bind to', '127.0.0.1')
+    .option('--openapi', 'Print the OpenAPI document for the REST API and exit', false)
+    .option(
+      '--save-session <file>',
+      'On shutdown, save attach sessions to a file for "debug-run resume"'
+    )
+    .option(
+      '--history <N>',
+      'Keep the last N stops of each session for evaluate to refer to as $1, $2, ...',
+      (val: string) => parseInt(val, 10)
+    )
+    .option(
+      '--keep-sessions <N>',
+      `Ended sessions to keep for session.get and replay (default: ${DEFAULT_KEEP_SESSIONS})`,
+      (val: string) => parseInt(val, 10)
+    )
+    .option(
+      '--state-file <file>',
+      "Keep each project's breakpoints, watches and profiles in a file across restarts"
+    )
+    .option(
+      '--token <token>',
+      `Token clients must present (default: $${TOKEN_ENV}, else a new random one)`
+    )
+    .action(
+      async (options: {
+        port: number;
+        host: string;
+        openapi: boolean;
+        saveSession?: string;
+        history?: number;
+        keepSessions?: number;
+        stateFile?: string;
+        token?: string;
+      }) => {
+        if (options.openapi) {
+          console.log(JSON.stringify(buildOpenApiSpec(VERSION), null, 2));
+          return;
+        }
+        if (
+          options.history !== undefined &&
+          (!Number.isInteger(options.history) || options.history < 0)
+        ) {
+          console.error('Error: --history must be a non-negative number of stops');
+          process.exit(1);
+        }
+        if (
+          options.keepSessions !== undefined &&
+          (!Number.isInteger(options.keepSessions) || options.keepSessions < 0)
+        ) {
+          console.error('Error: --keep-sessions must be a non-negative number of sessions');
+          process.exit(1);
+        }
+        await runServer(options);
+      }
+    );
+
+  // Add resume subcommand
+  program
+    .command('resume <file>')
+    .description('Re-attach sessions saved by serve mode, restoring breakpoints and watches')
+    .option('--server <url>', 'Resume in a running debug-run server instead of the foreground')
+    .option('--session <id>', 'Saved session to resume (required if the file has several)')
+    .option('--token <token>', `The server's token (default: $${TOKEN_ENV})`)
+    .option('--pretty', 'Pretty print JSON output', false)
+    .action(
+      async (
+        file: string,
+        options: { server?: string; session?: string; token?: string; pretty: boolean }
+      ) => {
+        await runResume(file, options);
+      }
+    );
+
+  // Add diagnose-sources subcommand (Phase 3)
+  program
+    .command('diagnose-sources [directory]')
+    .description('Scan directory for source maps and diagnose path resolution issues')
+    .option(
+      '--source-map-overrides <jsonOrPreset>',
+      'Source map path overrides (preset name or JSON object)'
+    )
+    .option('--include-node-modules', 'Include node_modules in scan', false)
+    .option('--verbose', 'Show detailed per-file diagnostics', false)
+    .option('--json', 'Output as JSON instead of human-readable format', false)
+    .action(
+      async (
         directory: string | undefined,
         options: {
           sourceMapOverrides?: string;
@@ -386,18 +1450,60 @@ export function createCli(): Command {
 }
 
 /**
- * Run a debug session for .NET tests using the test runner.
- * Automatically launches dotnet test with VSTEST_HOST_DEBUG=1 and attaches.
+ * A registered adapter, or one built for a "stdio:<command>" name
  */
-async function runTestDebugSession(options: CliOptions & { env?: string[] }): Promise<void> {
-  // Validate adapter
-  const adapter = getAdapter(options.adapter);
+function lookupAdapter(name: string, template?: LaunchTemplate): AdapterConfig | undefined {
+  return isStdioAdapter(name) ? createStdioAdapter(name, template) : getAdapter(name);
+}
+
+/**
+ * Look up the requested adapter, applying --backend or --launch-template if given. Exits on
+ * error.
+ */
+function resolveAdapter(
+  options: Pick<CliOptions, 'adapter' | 'backend' | 'launchTemplate'>
+): AdapterConfig {
+  let template: LaunchTemplate | undefined;
+  if (options.launchTemplate) {
+    if (!isStdioAdapter(options.adapter)) {
+      console.error('Error: --launch-template requires a stdio: adapter (-a "stdio:<command>")');
+      process.exit(1);
+    }
+    try {
+      template = loadLaunchTemplate(options.launchTemplate);
+    } catch (error) {
+      console.error(`Error: ${error instanceof Error ? error.message : error}`);
+      process.exit(1);
+    }
+  }
+
+  const adapter = lookupAdapter(options.adapter, template);
   if (!adapter) {
     console.error(`Unknown adapter: ${options.adapter}`);
     console.error(`Available adapters: ${getAdapterNames().join(', ')}`);
     process.exit(1);
   }
 
+  if (!options.backend) {
+    return adapter;
+  }
+
+  try {
+    return getAdapterBackend(adapter, options.backend);
+  } catch (error) {
+    console.error(`Error: ${error instanceof Error ? error.message : error}`);
+    process.exit(1);
+  }
+}
+
+/**
+ * Run a debug session for .NET tests using the test runner.
+ * Automatically launches dotnet test with VSTEST_HOST_DEBUG=1 and attaches.
+ */
+async function runTestDebugSession(options: CliOptions & { env?: string[] }): Promise<void> {
+  // Validate adapter
+  const adapter = resolveAdapter(options);
+
   // Check if adapter is installed
   const adapterPath = await adapter.detect();
   if (!adapterPath) {
@@ -450,23 +1556,152 @@ async function runTestDebugSession(options: CliOptions & { env?: string[] }): Pr
   } finally {
     // Clean up the test runner process
     if (testRunner) {
-      cleanupTestRunner(testRunner.process);
+      await cleanupTestRunner(testRunner.process);
+    }
+  }
+}
+
+/**
+ * Refuse an attach the guardrails rule out (another user's process, the config denylist),
+ * and with --confirm-attach ask first. Exits unless the attach may go ahead.
+ */
+async function guardAttach(
+  options: CliOptions,
+  pid: number | undefined,
+  launchOverrides: LaunchOverrides | undefined
+): Promise<void> {
+  let denylist: AttachDenyRule[];
+  try {
+    denylist = attachDenyRules(loadConfigFiles());
+  } catch (error) {
+    console.error(`Error: ${error instanceof Error ? error.message : error}`);
+    process.exit(1);
+  }
+
+  const target = attachTarget(pid, launchOverrides);
+  const refusals = attachRefusals(target, {
+    denylist,
+    allowOtherUser: options.allowOtherUser,
+    uid: process.getuid?.(),
+  });
+  if (refusals.length > 0) {
+    for (const refusal of refusals) {
+      console.error(`Error: Refusing to attach: ${refusal}`);
+    }
+    process.exit(1);
+  }
+  logger.info('cli', 'attach target', { ...target });
+
+  if (!options.confirmAttach || options.yes) return;
+  if (!process.stdin.isTTY || !process.stderr.isTTY) {
+    console.error('Error: --confirm-attach has no terminal to ask on; pass --yes to attach');
+    process.exit(1);
+  }
+  const prompt = readline.createInterface({ input: process.stdin, output: process.stderr });
+  // Ctrl+C or Ctrl+D ends the question without an answer
+  const closed = new Promise<undefined>((resolve) =>
+    prompt.once('close', () => resolve(undefined))
+  );
+  prompt.on('SIGINT', () => prompt.close());
+  try {
+    const answer = await Promise.race([
+      prompt.question(
+        `Attach to ${describeAttachTarget(target)}? It will pause at breakpoints [y/N]: `
+      ),
+      closed,
+    ]);
+    if (!answer || !/^y(es)?$/i.test(answer.trim())) {
+      console.error('Error: Attach not confirmed');
+      process.exit(1);
+    }
+  } finally {
+    prompt.close();
+  }
+}
+
+/**
+ * Pick the Node process --discover attaches to: the only match, or on a terminal the one
+ * chosen from the list. Exits when there are none or several and no way to ask.
+ */
+async function chooseInspector(discover: string | boolean): Promise<InspectorTarget> {
+  const found = await discoverInspectors();
+  const match = typeof discover === 'string' ? discover : undefined;
+  const targets = match !== undefined ? matchInspectors(found, match) : found;
+
+  if (targets.length === 0) {
+    const which = match !== undefined ? ` matching "${match}"` : '';
+    console.error(`Error: No Node inspector found${which}`);
+    if (found.length > 0) {
+      console.error(`Found:\n${formatInspectorTargets(found)}`);
+    } else {
+      console.error('Start the program with --inspect (or --inspect=<port>) and try again');
+    }
+    process.exit(1);
+  }
+  if (targets.length === 1) {
+    const [target] = targets;
+    logger.info('cli', 'discovered node inspector', { port: target.port, pid: target.pid });
+    console.error(`Attaching to ${target.script} (${target.host}:${target.port})`);
+    return target;
+  }
+
+  console.error(`Found ${targets.length} Node inspectors:\n${formatInspectorTargets(targets)}`);
+  if (!process.stdin.isTTY || !process.stderr.isTTY) {
+    console.error('Error: Pick one with --discover <port|pid|script>');
+    process.exit(1);
+  }
+  const prompt = readline.createInterface({ input: process.stdin, output: process.stderr });
+  // Ctrl+C or Ctrl+D ends the question without an answer
+  const closed = new Promise<undefined>((resolve) =>
+    prompt.once('close', () => resolve(undefined))
+  );
+  prompt.on('SIGINT', () => prompt.close());
+  try {
+    for (;;) {
+      const answer = await Promise.race([
+        prompt.question(`Attach to [1-${targets.length}]: `),
+        closed,
+      ]);
+      if (answer === undefined) {
+        console.error('Error: No process chosen');
+        process.exit(1);
+      }
+      const choice = answer.trim();
+      const index = parseInt(choice, 10);
+      if (String(index) === choice && index >= 1 && index <= targets.length) {
+        return targets[index - 1];
+      }
     }
+  } finally {
+    prompt.close();
   }
 }
 
 async function runDebugSession(options: CliOptions & { env?: string[] }): Promise<void> {
   // Validate adapter
-  const adapter = getAdapter(options.adapter);
-  if (!adapter) {
-    console.error(`Unknown adapter: ${options.adapter}`);
-    console.error(`Available adapters: ${getAdapterNames().join(', ')}`);
+  const adapter = resolveAdapter(options);
+
+  // --define fills ${NAME} placeholders, so one config file or profile serves many runs
+  let defines: Record<string, string>;
+  try {
+    defines = parseDefines(options.define ?? []);
+  } catch (error) {
+    console.error(`Error: ${(error as Error).message}`);
     process.exit(1);
   }
+  options.breakpoint = options.breakpoint.map((spec) => expandDefines(spec, defines));
+  options.eval = options.eval.map((expression) => expandDefines(expression, defines));
+  options.assert = options.assert.map((expression) => expandDefines(expression, defines));
+  for (const name of undefinedPlaceholders(
+    [...options.breakpoint, ...options.eval, ...options.assert],
+    defines
+  )) {
+    logger.warn('cli', 'placeholder has no --define; left as-is', { placeholder: `\${${name}}` });
+  }
 
-  // Check if adapter is installed
+  // Check if adapter is installed (with --wsl it only has to be installed in the distribution)
   const adapterPath = await adapter.detect();
-  if (!adapterPath) {
+  if (!adapterPath && options.wsl === undefined) {
     console.error(`Adapter "${adapter.name}" is not installed.`);
     console.error(adapter.installHint);
     process.exit(1);
@@ -474,101 +1709,864 @@ async function runDebugSession(options: CliOptions & { env?: string[] }): Promis
 
   // Validate breakpoints (or exception breakpoints must be specified)
   const hasBreakpoints =
-    options.breakpoint.length > 0 || (options.logpoint && options.logpoint.length > 0);
+    options.breakpoint.length > 0 ||
+    (options.logpoint && options.logpoint.length > 0) ||
+    (options.breakAddr && options.breakAddr.length > 0) ||
+    (options.nativeBreak && options.nativeBreak.length > 0);
   const hasExceptionBreakpoints = options.breakOnException && options.breakOnException.length > 0;
 
+  // Crash triage: stop only on unhandled exceptions (native debuggers stop on fatal
+  // signals by themselves); --break-on-exception overrides the adapter's crash filters
+  if (options.untilCrash) {
+    if (hasBreakpoints) {
+      console.error('Error: --until-crash runs without --breakpoint, --logpoint or --break-addr');
+      process.exit(1);
+    }
+    if (!hasExceptionBreakpoints) {
+      options.breakOnException = adapter.crashFilters;
+    }
+  }
+
+  // Exception sampling: exception breakpoints only, so the service never waits on a user
+  // breakpoint; without --break-on-exception, the adapter's first filter (every throw)
+  let sampleExceptions: number | undefined;
+  if (options.sampleExceptions) {
+    if (!options.attach) {
+      console.error('Error: --sample-exceptions samples an attached service; add --attach');
+      process.exit(1);
+    }
+    if (hasBreakpoints || options.untilCrash || options.aggregate?.length) {
+      console.error(
+        'Error: --sample-exceptions sets only exception breakpoints; drop --breakpoint, ' +
+          '--logpoint, --break-addr, --aggregate and --until-crash'
+      );
+      process.exit(1);
+    }
+    const interval =
+      typeof options.sampleExceptions === 'string' ? options.sampleExceptions : '60s';
+    try {
+      sampleExceptions = parseTimeout(interval);
+    } catch {
+      sampleExceptions = 0;
+    }
+    if (sampleExceptions <= 0) {
+      console.error(
+        `Error: Invalid --sample-exceptions interval "${interval}". Use a duration like "30s" or "5m"`
+      );
+      process.exit(1);
+    }
+    if (!hasExceptionBreakpoints) {
+      const filter = adapter.exceptionFilters?.[0];
+      if (!filter) {
+        console.error(`Error: ${adapter.name} has no exception filters; use --break-on-exception`);
+        process.exit(1);
+      }
+      options.breakOnException = [filter];
+    }
+  }
+
   // In attach mode, breakpoints are optional (you might just want to break on exceptions)
-  if (!hasBreakpoints && !hasExceptionBreakpoints && !options.attach) {
+  if (!hasBreakpoints && !hasExceptionBreakpoints && !options.attach && !options.untilCrash) {
     console.error(
       'Error: At least one --breakpoint, --logpoint, or --break-on-exception is required'
     );
     process.exit(1);
   }
 
-  // Validate exception breakpoint filters against adapter's supported filters
-  if (hasExceptionBreakpoints && adapter.exceptionFilters) {
-    for (const filter of options.breakOnException!) {
-      if (!adapter.exceptionFilters.includes(filter)) {
-        console.error(
-          `Error: Adapter "${adapter.name}" does not support exception filter "${filter}"`
-        );
-        console.error(`Supported filters: ${adapter.exceptionFilters.join(', ')}`);
-        process.exit(1);
-      }
+  // --set specs must be target=value
+  for (const spec of options.set ?? []) {
+    try {
+      parseAssignment(spec);
+    } catch (error) {
+      console.error(`Error: ${error instanceof Error ? error.message : error}`);
+      process.exit(1);
     }
   }
 
-  // Parse environment variables
-  const env: Record<string, string> = {};
-  if (options.env) {
-    for (const item of options.env) {
-      const [key, ...valueParts] = item.split('=');
-      if (key && valueParts.length > 0) {
-        env[key] = valueParts.join('=');
-      }
-    }
+  // --watch-memory specs are <address expression>:<bytes>
+  let watchMemory: MemoryWatchSpec[] | undefined;
+  try {
+    watchMemory = options.watchMemory?.map(parseMemoryWatch);
+  } catch (error) {
+    console.error(`Error: ${error instanceof Error ? error.message : error}`);
+    process.exit(1);
   }
 
-  // Parse timeout
-  const timeout = parseTimeout(options.timeout || '60s');
+  // --aggregate specs are <expression>[:stat,stat...]
+  let aggregates: AggregateSpec[] | undefined;
+  try {
+    aggregates = options.aggregate?.map(parseAggregateSpec);
+  } catch (error) {
+    console.error(`Error: ${error instanceof Error ? error.message : error}`);
+    process.exit(1);
+  }
 
-  // Parse source map overrides
-  let sourceMapOverrides: Record<string, string> | undefined;
-  if (options.sourceMapOverrides) {
-    const workspaceFolder = options.cwd || process.cwd();
+  // --on-hit replaces the continue that stepping and tracing start from
+  let onHit: OnHitAction | undefined;
+  if (options.onHit) {
     try {
-      sourceMapOverrides = parseSourceMapOverrides(options.sourceMapOverrides, workspaceFolder);
+      onHit = parseOnHitAction(options.onHit);
     } catch (error) {
       console.error(`Error: ${error instanceof Error ? error.message : error}`);
-      console.error(`Available presets: ${getPresetNames().join(', ')}`);
+      process.exit(1);
+    }
+    if (options.trace || options.steps || options.evalAfterStep) {
+      console.error('Error: --on-hit cannot be combined with --trace, --steps or --eval-after-step');
       process.exit(1);
     }
   }
 
-  // Create output stream (file or stdout)
-  let outputStream: NodeJS.WritableStream = process.stdout;
-  let fileStream: fs.WriteStream | undefined;
-
+  if (options.onExit === 'restart' && options.attach) {
+    console.error("Error: --on-exit restart relaunches the program, which --attach didn't launch");
+    process.exit(1);
+  }
+  if (options.discover !== undefined && adapter.name !== 'node') {
+    console.error('Error: --discover finds Node inspector ports; use it with -a node');
+    process.exit(1);
+  }
+  if (options.discover !== undefined && options.pid) {
+    console.error('Error: --discover and --pid both choose the process to attach to; use one');
+    process.exit(1);
+  }
+
+  // Instruction breakpoints need a native debugger (lldb-dap, CodeLLDB or gdb)
+  if (options.breakAddr && options.breakAddr.length > 0 && !adapter.instructionBreakpoints) {
+    console.error(`Error: --break-addr is not supported by the ${adapter.name} adapter`);
+    console.error('Use a native adapter: lldb or rust');
+    process.exit(1);
+  }
+
+  // Mixed mode: lldb attaches to the launched program next to the adapter
+  let mixedMode: MixedModeConfig | undefined;
+  if (options.nativeBreak && options.nativeBreak.length > 0) {
+    if (!MIXED_MODE_ADAPTERS.includes(adapter.name)) {
+      console.error(`Error: --native-break is not supported by the ${adapter.name} adapter`);
+      console.error(`Mixed mode works with: ${MIXED_MODE_ADAPTERS.join(', ')}`);
+      process.exit(1);
+    }
+    if (options.attach || options.wsl !== undefined) {
+      console.error('Error: --native-break attaches lldb to a program launched on this machine');
+      process.exit(1);
+    }
+    const nativeAdapter = getAdapter('lldb')!;
+    if (!(await nativeAdapter.detect())) {
+      console.error('Error: --native-break needs lldb-dap or CodeLLDB');
+      console.error(nativeAdapter.installHint);
+      process.exit(1);
+    }
+    mixedMode = { adapter: nativeAdapter, breakpoints: options.nativeBreak };
+  }
+
+  // Side-effect-free evaluation depends on the adapter's evaluation modes
+  if (options.evalSafe && !adapter.safeEvaluation) {
+    console.error(`Error: --eval-safe is not supported by the ${adapter.name} adapter`);
+    console.error('Supported adapters: node, lldb, rust (lldb backend), dotnet (vsdbg backend)');
+    process.exit(1);
+  }
+  if (options.evalSafe && hasEvaluationContexts(options.eval, options.evalContext)) {
+    console.error('Error: --eval-safe picks the evaluation context; drop --eval-context and prefixes');
+    process.exit(1);
+  }
+  if (
+    options.evalExpand !== undefined &&
+    (!Number.isInteger(options.evalExpand) || options.evalExpand < 0)
+  ) {
+    console.error('Error: --eval-expand must be a non-negative number of levels');
+    process.exit(1);
+  }
+  if (
+    options.skipToHit !== undefined &&
+    (!Number.isInteger(options.skipToHit) || options.skipToHit < 1)
+  ) {
+    console.error('Error: --skip-to-hit must be a hit number (1 or more)');
+    process.exit(1);
+  }
+  let hitWindows: HitWindow[] | undefined;
+  if (options.activeAfter || options.activeUntil) {
+    try {
+      hitWindows = parseHitWindows(options.activeAfter, options.activeUntil);
+    } catch (error) {
+      console.error(`Error: ${(error as Error).message}`);
+      process.exit(1);
+    }
+    const labels = new Set(options.breakpoint.map((spec) => parseBreakpointSpec(spec).label));
+    for (const label of hitWindowLabels(hitWindows)) {
+      if (!labels.has(label)) {
+        console.error(
+          `Error: No breakpoint is labelled "${label}" (label one with -b "file:line#${label}")`
+        );
+        process.exit(1);
+      }
+    }
+    if (options.skipToHit !== undefined) {
+      console.error('Error: --skip-to-hit does not combine with --active-after/--active-until');
+      process.exit(1);
+    }
+  }
+  let armOnOutput: RegExp | undefined;
+  if (options.armOnOutput !== undefined) {
+    try {
+      armOnOutput = parseArmPattern(options.armOnOutput);
+    } catch (error) {
+      console.error(`Error: ${(error as Error).message}`);
+      process.exit(1);
+    }
+  }
+
+  // Validate exception breakpoint filters and types against what the adapter supports
+  if (hasExceptionBreakpoints) {
+    const exceptionErrors = validateExceptionBreakpointSpecs(options.breakOnException!, adapter);
+    if (exceptionErrors.length > 0) {
+      for (const error of exceptionErrors) {
+        console.error(`Error: ${error}`);
+      }
+      process.exit(1);
+    }
+  }
+
+  // Heap snapshots use js-debug's profiling requests
+  let heapSnapshotDir: string | undefined;
+  if (options.heapSnapshot) {
+    if (adapter.name !== 'node') {
+      console.error('Error: --heap-snapshot requires the node adapter (js-debug)');
+      process.exit(1);
+    }
+    heapSnapshotDir = path.resolve(
+      typeof options.heapSnapshot === 'string' ? options.heapSnapshot : '.'
+    );
+    fs.mkdirSync(heapSnapshotDir, { recursive: true });
+  }
+
+  let onStopExec: string[] | undefined;
+  if (options.onStopExec !== undefined) {
+    try {
+      onStopExec = parseStopCommand(options.onStopExec);
+    } catch (error) {
+      console.error(`Error: ${(error as Error).message}`);
+      process.exit(1);
+    }
+  }
+
+  // Hold the program at entry until a test harness has prepared its state
+  let trigger: SessionTrigger | undefined;
+  if (options.triggerFile !== undefined || options.triggerSignal !== undefined) {
+    if (options.attach) {
+      console.error('Error: --trigger-file and --trigger-signal hold a launched program at entry');
+      process.exit(1);
+    }
+    if (options.triggerSignal !== undefined && process.platform === 'win32') {
+      console.error('Error: --trigger-signal needs POSIX signals; use --trigger-file on Windows');
+      process.exit(1);
+    }
+    try {
+      trigger = {
+        ...(options.triggerFile !== undefined && { file: path.resolve(options.triggerFile) }),
+        ...(options.triggerSignal !== undefined && {
+          signal: parseTriggerSignal(options.triggerSignal),
+        }),
+      };
+    } catch (error) {
+      console.error(`Error: ${(error as Error).message}`);
+      process.exit(1);
+    }
+  }
+
+  // Parse resource limits for the launched program
+  let resourceLimits: ResourceLimits | undefined;
+  if (options.limitMem || options.limitCpu || options.limitFds) {
+    if (options.attach) {
+      console.error(
+        'Error: --limit-mem, --limit-cpu and --limit-fds only apply to launched programs'
+      );
+      process.exit(1);
+    }
+    try {
+      resourceLimits = {
+        ...(options.limitMem && { memoryBytes: parseMemoryLimit(options.limitMem) }),
+        ...(options.limitCpu && { cpuSeconds: parseCpuLimit(options.limitCpu) }),
+        ...(options.limitFds && { fileDescriptors: parseFdLimit(options.limitFds) }),
+      };
+    } catch (error) {
+      console.error(`Error: ${error instanceof Error ? error.message : error}`);
+      process.exit(1);
+    }
+    const unsupported = unsupportedResourceLimits(resourceLimits);
+    if (unsupported.length > 0) {
+      console.error(`Error: ${unsupported.join(', ')} not supported on ${process.platform}`);
+      process.exit(1);
+    }
+  } else if (options.limitsBestEffort) {
+    console.error('Error: --limits-best-effort needs --limit-mem, --limit-cpu or --limit-fds');
+    process.exit(1);
+  }
+
+  // Sandbox for the launched program
+  let sandbox: SandboxPolicy | undefined;
+  const sandboxTuning =
+    options.sandboxWrite || options.sandboxHide || options.sandboxNetwork || options.sandboxAdapter;
+  if (sandboxTuning && !options.sandbox) {
+    console.error(
+      'Error: --sandbox-write, --sandbox-hide, --sandbox-network and --sandbox-adapter need --sandbox'
+    );
+    process.exit(1);
+  }
+  if (options.sandbox) {
+    if (options.attach) {
+      console.error('Error: --sandbox only applies to launched programs');
+      process.exit(1);
+    }
+    const writable = (options.sandboxWrite ?? []).map((target) => path.resolve(target));
+    const missing = writable.find((target) => !fs.existsSync(target));
+    if (missing) {
+      console.error(`Error: --sandbox-write path not found: ${missing}`);
+      process.exit(1);
+    }
+    sandbox = {
+      writable,
+      hidden: options.sandboxHide?.map((target) => path.resolve(target)) ?? defaultHiddenPaths(),
+      network: options.sandboxNetwork === true,
+      adapter: options.sandboxAdapter === true,
+    };
+    if (
+      sandbox.adapter &&
+      !sandbox.network &&
+      process.platform === 'linux' &&
+      adapter.transport === 'socket'
+    ) {
+      console.error(
+        `Error: --sandbox-adapter without --sandbox-network cuts debug-run off from the ${adapter.name} adapter's socket on Linux`
+      );
+      process.exit(1);
+    }
+    try {
+      await checkSandbox(sandbox);
+    } catch (error) {
+      console.error(`Error: ${(error as Error).message}`);
+      process.exit(1);
+    }
+  }
+
+  // Parse environment variables
+  const env: Record<string, string> = {};
+  if (options.env) {
+    for (const item of options.env) {
+      const [key, ...valueParts] = item.split('=');
+      if (key && valueParts.length > 0) {
+        env[key] = valueParts.join('=');
+      }
+    }
+  }
+
+  // Python: --module and interpreter selection
+  let interpreter: string | undefined;
+  if (adapter.name === 'debugpy') {
+    if (options.pythonInterp) {
+      interpreter = path.resolve(options.pythonInterp);
+    } else {
+      // The program may live in another project than the directory it is run from
+      const dirs = [options.cwd || process.cwd()];
+      if (options.program) dirs.unshift(path.dirname(path.resolve(options.program)));
+      const detected = detectPythonInterpreter(dirs);
+      if (detected) {
+        logger.info('cli', `using ${detected.source} interpreter`, { path: detected.path });
+        interpreter = detected.path;
+      }
+    }
+  } else if (
+    options.module ||
+    options.pythonInterp ||
+    options.gevent ||
+    options.django
+  ) {
+    console.error(
+      'Error: --module, --python-interp, --gevent and --django are only supported by the python adapter'
+    );
+    process.exit(1);
+  }
+
+  if ((options.runtimeArgs || options.npmScript) && adapter.name !== 'node') {
+    console.error('Error: --runtime-args and --npm-script are only supported by the node adapter');
+    process.exit(1);
+  }
+
+  if (options.skipFiles !== undefined && adapter.name !== 'node') {
+    console.error('Error: --skip-files is only supported by the node adapter');
+    process.exit(1);
+  }
+
+  if (options.justMyCode && adapter.id !== 'coreclr' && adapter.name !== 'debugpy') {
+    console.error('Error: --just-my-code is only supported by the dotnet and python adapters');
+    process.exit(1);
+  }
+
+  if (options.requireExactSource && adapter.id !== 'coreclr') {
+    console.error('Error: --require-exact-source is only supported by the dotnet adapter');
+    process.exit(1);
+  }
+
+  let symbolServers: string[] | undefined;
+  if (options.symbolServer || options.sourceLink) {
+    if (adapter.id !== 'coreclr') {
+      console.error(
+        'Error: --symbol-server and --source-link are only supported by the dotnet adapter'
+      );
+      process.exit(1);
+    }
+    if (options.symbolServer && options.attach) {
+      console.error(
+        'Error: --symbol-server needs a launched program (its PDBs are loaded before it starts)'
+      );
+      process.exit(1);
+    }
+    if (options.sourceLink && options.wsl !== undefined) {
+      console.error('Error: --source-link is not supported with --wsl');
+      process.exit(1);
+    }
+    try {
+      symbolServers = options.symbolServer?.map(resolveSymbolServer);
+    } catch (error) {
+      console.error(`Error: ${(error as Error).message}`);
+      process.exit(1);
+    }
+  }
+
+  const cargoTarget = options.cargoBin ?? options.cargoExample;
+  if (cargoTarget !== undefined || options.features) {
+    if (options.cargoBin && options.cargoExample) {
+      console.error('Error: use either --cargo-bin or --cargo-example');
+      process.exit(1);
+    }
+    if (cargoTarget === undefined) {
+      console.error('Error: --features applies to --cargo-bin or --cargo-example');
+      process.exit(1);
+    }
+    if (options.program || options.attach) {
+      console.error(
+        'Error: --cargo-bin and --cargo-example build the program to launch; drop <program> and --attach'
+      );
+      process.exit(1);
+    }
+    if (!adapter.nativeSignals) {
+      console.error('Error: --cargo-bin and --cargo-example need a native adapter (rust, lldb)');
+      process.exit(1);
+    }
+  }
+
+  // Node: expand --npm-script into the program (or package manager) to launch
+  let program = options.program;
+  let programArgs = options.args;
+  let runtimeArgs = options.runtimeArgs;
+  let runtimeExecutable: string | undefined;
+  let cwd = options.cwd;
+  if (options.npmScript) {
+    let script: NpmScriptLaunch;
+    try {
+      script = resolveNpmScript(path.resolve(options.cwd || process.cwd()), options.npmScript);
+    } catch (error) {
+      console.error(`Error: ${error instanceof Error ? error.message : error}`);
+      process.exit(1);
+    }
+    logger.info('cli', `resolved ${script.packageManager} script "${script.name}"`, {
+      command: script.command,
+      expanded: script.program !== undefined,
+    });
+
+    program = script.program;
+    runtimeExecutable = script.runtimeExecutable;
+    runtimeArgs = [...(script.runtimeArgs ?? []), ...(options.runtimeArgs ?? [])];
+    programArgs = [...(script.args ?? []), ...(options.args ?? [])];
+    cwd = options.cwd ?? script.cwd;
+    for (const [key, value] of Object.entries(script.env ?? {})) {
+      env[key] ??= value;
+    }
+  }
+
+  // Rust: build the cargo target and debug the executable cargo reports
+  if (cargoTarget !== undefined) {
+    const target: CargoTarget = {
+      kind: options.cargoBin !== undefined ? 'bin' : 'example',
+      name: cargoTarget,
+      features: options.features?.flatMap((list) => list.split(/[\s,]+/)).filter(Boolean),
+    };
+    try {
+      program = buildCargoTarget(target, path.resolve(options.cwd || process.cwd()));
+    } catch (error) {
+      console.error(`Error: ${error instanceof Error ? error.message : error}`);
+      process.exit(1);
+    }
+    logger.info('cli', `built cargo ${target.kind} "${target.name}"`, { executable: program });
+  }
+
+  // Parse timeout
+  const timeout = parseTimeout(options.timeout || '60s');
+
+  let captureBudget: number | undefined;
+  if (options.captureBudget !== undefined) {
+    try {
+      captureBudget = parseTimeout(options.captureBudget);
+    } catch {
+      console.error(
+        `Error: Invalid --capture-budget "${options.captureBudget}". Use a duration like "2s" or "500ms"`
+      );
+      process.exit(1);
+    }
+  }
+
+  // --wsl runs the adapter in a distribution, so its processes aren't Windows processes
+  let wslDistro: string | undefined;
+  if (options.wsl !== undefined) {
+    if (process.platform !== 'win32') {
+      console.error('Error: --wsl runs the adapter in WSL from Windows');
+      console.error('Inside WSL or on Linux, run debug-run without --wsl');
+      process.exit(1);
+    }
+    if (resourceLimits) {
+      console.error('Error: --limit-mem, --limit-cpu and --limit-fds cannot be combined with --wsl');
+      process.exit(1);
+    }
+    try {
+      wslDistro = wslDistroName(typeof options.wsl === 'string' ? options.wsl : undefined);
+    } catch (error) {
+      console.error(`Error: ${error instanceof Error ? error.message : error}`);
+      process.exit(1);
+    }
+    logger.info('cli', 'running adapter in WSL', { distro: wslDistro });
+  }
+
+  const inspector =
+    options.discover !== undefined ? await chooseInspector(options.discover) : undefined;
+
+  // Parse source map overrides
+  let sourceMapOverrides: Record<string, string> | undefined;
+  if (options.sourceMapOverrides) {
+    const workspaceFolder = options.cwd || process.cwd();
+    try {
+      sourceMapOverrides = parseSourceMapOverrides(options.sourceMapOverrides, workspaceFolder);
+    } catch (error) {
+      console.error(`Error: ${error instanceof Error ? error.message : error}`);
+      console.error(`Available presets: ${getPresetNames().join(', ')}`);
+      process.exit(1);
+    }
+  }
+
+  // --launch-json files, then --launch-arg pairs, merged into the launch/attach request
+  let launchOverrides: LaunchOverrides | undefined;
+  if (options.launchJson || options.launchArg) {
+    try {
+      launchOverrides = buildLaunchOverrides(options.launchJson ?? [], options.launchArg ?? []);
+    } catch (error) {
+      console.error(`Error: ${error instanceof Error ? error.message : error}`);
+      process.exit(1);
+    }
+  }
+
+  // Attaching pauses a real process: check it against the guardrails first (the test host
+  // --test-project attaches to is one debug-run started)
+  if (options.attach && !options.testProject) {
+    await guardAttach(options, inspector?.pid ?? options.pid, launchOverrides);
+  }
+
+  // Names result files and the artifacts folder
+  const runName =
+    options.name ?? path.parse(options.program ?? options.module ?? program ?? 'session').name;
+
+  // Create output stream (file, file descriptor or stdout)
+  let outputStream: NodeJS.WritableStream = process.stdout;
+  let fileStream: fs.WriteStream | undefined;
+  let resultRun: ResultRun | undefined;
+  // Where the program's own output goes when it's kept out of the event stream
+  let programStreams: ProgramStreams | undefined;
+
+  const destinations = [
+    options.output && '--output',
+    options.resultFd !== undefined && '--result-fd',
+    (options.name || options.outDir) && '--name/--out-dir',
+  ].filter(Boolean);
+  if (destinations.length > 1) {
+    console.error(`Error: ${destinations.join(' and ')} cannot be combined`);
+    process.exit(1);
+  }
+  // Result files are NDJSON, indexed as they're written
+  const outputFormat = options.outputFormat ?? 'ndjson';
+  if (outputFormat !== 'ndjson' && (options.name || options.outDir)) {
+    console.error(
+      `Error: --output-format ${outputFormat} cannot be combined with --name/--out-dir`
+    );
+    process.exit(1);
+  }
+
   if (options.output) {
     fileStream = fs.createWriteStream(options.output);
     outputStream = fileStream;
+  } else if (options.resultFd !== undefined) {
+    const fd = options.resultFd;
+    if (!Number.isInteger(fd) || fd < 1) {
+      console.error('Error: --result-fd must be a file descriptor number (e.g., 3)');
+      process.exit(1);
+    }
+    try {
+      fs.fstatSync(fd);
+    } catch {
+      console.error(
+        `Error: file descriptor ${fd} is not open (redirect it, e.g. ${fd}>results.ndjson)`
+      );
+      process.exit(1);
+    }
+    fileStream = fs.createWriteStream('', { fd });
+    outputStream = fileStream;
+    programStreams = { stdout: process.stdout, stderr: process.stderr };
+  } else if (options.name || options.outDir) {
+    try {
+      resultRun = ResultRun.start(path.resolve(options.outDir ?? DEFAULT_RESULTS_DIR), runName);
+    } catch (error) {
+      console.error(`Error: ${error instanceof Error ? error.message : error}`);
+      process.exit(1);
+    }
+    outputStream = resultRun.stream;
+  }
+
+  if (options.quietProgram && !programStreams) {
+    programStreams = { stdout: process.stderr, stderr: process.stderr };
+  }
+
+  // With an HTML report, events are collected for it instead of written as they happen; with
+  // annotations, only the workflow commands for them are written
+  const htmlReport = options.outputFormat === 'html' ? new HtmlReport() : undefined;
+  const annotations =
+    options.outputFormat === 'gh-annotations' ? new GitHubAnnotations() : undefined;
+
+  // Mask secrets before any sink sees an event
+  let redactor: Redactor | undefined;
+  if (options.redact || options.redactName || options.redactEnv || options.redactValue) {
+    try {
+      redactor = new Redactor(
+        {
+          names: options.redactName ?? DEFAULT_REDACT_NAMES,
+          envNames: options.redactEnv,
+          values: options.redactValue,
+        },
+        { ...process.env, ...env }
+      );
+    } catch (error) {
+      console.error(`Error: ${error instanceof Error ? error.message : error}`);
+      process.exit(1);
+    }
+  }
+
+  // Create formatter with filtering options
+  const formatter = new OutputFormatter({
+    pretty: options.pretty,
+    stream: htmlReport || annotations ? new PassThrough().resume() : outputStream,
+    include: options.include,
+    exclude: options.exclude,
+    compact: options.compact,
+    stackLimit: options.stackLimit,
+    programStreams,
+    redactor,
+  });
+
+  if (resultRun) {
+    const run = resultRun;
+    formatter.onEvent((event) => run.observe(event));
+  }
+  if (htmlReport) {
+    formatter.onEvent((event) => htmlReport.observe(event));
+  }
+  if (annotations) {
+    formatter.onEvent((event) => {
+      const command = annotations.annotate(event);
+      if (command) outputStream.write(`${command}\n`);
+    });
+  }
+
+  // Show startup progress on a terminal, unless events or program output are written there too
+  const statusLine =
+    options.status !== false &&
+    process.stderr.isTTY &&
+    !(outputStream === process.stdout && process.stdout.isTTY) &&
+    programStreams?.stderr !== process.stderr
+      ? new StatusLine(process.stderr)
+      : undefined;
+
+  let termination: TerminationInfo | undefined;
+  formatter.onEvent((event) => {
+    if (event.type === 'session_end') {
+      termination = event.summary.termination;
+    }
+  });
+
+  // Record events to the result store, independent of output filtering
+  let store: ResultStore | undefined;
+  if (options.store) {
+    try {
+      store = ResultStore.open(options.store);
+    } catch (error) {
+      console.error(`Error: ${error instanceof Error ? error.message : error}`);
+      process.exit(1);
+    }
+    const resultStore = store;
+    formatter.onEvent((event) => {
+      try {
+        resultStore.record(event);
+      } catch (error) {
+        logger.warn('store', 'failed to record event', {
+          type: event.type,
+          error: error instanceof Error ? error.message : String(error),
+        });
+      }
+    });
   }
 
-  // Create formatter with filtering options
-  const formatter = new OutputFormatter({
-    pretty: options.pretty,
-    stream: outputStream,
-    include: options.include,
-    exclude: options.exclude,
-    compact: options.compact,
-    stackLimit: options.stackLimit,
-  });
+  // Write the DAP trace, raw output and stop snapshots to a session folder
+  let artifacts: SessionArtifacts | undefined;
+  if (options.artifacts) {
+    try {
+      artifacts = SessionArtifacts.create(path.resolve(options.artifacts), runName);
+    } catch (error) {
+      console.error(`Error: ${error instanceof Error ? error.message : error}`);
+      process.exit(1);
+    }
+    const bundle = artifacts;
+    formatter.onEvent((event) => bundle.observe(event));
+  }
+
+  // Export phase spans and stop events to an OpenTelemetry collector
+  let otlp: OtlpExporter | undefined;
+  let stopObservingSpans: (() => void) | undefined;
+  if (options.otlp) {
+    const exporter = new OtlpExporter(
+      otlpOptionsFromEnv(typeof options.otlp === 'string' ? options.otlp : undefined)
+    );
+    stopObservingSpans = logger.observeSpans(exporter);
+    formatter.onEvent((event) => exporter.recordEvent(event));
+    otlp = exporter;
+  }
+
+  // Record phases, pauses and stops for chrome://tracing or Perfetto
+  let timeline: SessionTimeline | undefined;
+  let stopTimeline: (() => void) | undefined;
+  if (options.timeline) {
+    const recorder = new SessionTimeline();
+    stopTimeline = logger.observeSpans(recorder);
+    formatter.onEvent((event) => recorder.recordEvent(event));
+    timeline = recorder;
+  }
+
+  if (trigger) {
+    const hint = triggerHint(trigger);
+    formatter.onEvent((event) => {
+      if (event.type === 'trigger_waiting') console.error(`Held at entry: ${hint} to start`);
+    });
+  }
+
+  // Flatten captured values into a table for pandas, DuckDB or a spreadsheet
+  let valueExport: ValueExport | undefined;
+  if (options.exportValues) {
+    try {
+      valueExportFormat(options.exportValues);
+    } catch (error) {
+      console.error(`Error: ${error instanceof Error ? error.message : error}`);
+      process.exit(1);
+    }
+    const values = new ValueExport(runName);
+    formatter.onEvent((event) => values.observe(event));
+    valueExport = values;
+  }
+
+  // Compare captured values with a checked-in baseline, like a regression test
+  let baseline: Baseline | undefined;
+  let baselineRecorder: BaselineRecorder | undefined;
+  if (options.expectUpdate && !options.expect) {
+    console.error('Error: --expect-update needs --expect <file> to write');
+    process.exit(1);
+  }
+  if (options.expect) {
+    const file = path.resolve(options.expect);
+    try {
+      baseline = options.expectUpdate && !fs.existsSync(file) ? { stops: [] } : readBaseline(file);
+    } catch (error) {
+      console.error(`Error: ${error instanceof Error ? error.message : error}`);
+      process.exit(1);
+    }
+    const recorder = new BaselineRecorder();
+    formatter.onEvent((event) => recorder.observe(event));
+    baselineRecorder = recorder;
+  }
+
+  // Kill process trees left behind by a debug-run that was itself killed (e.g., SIGKILL)
+  const swept = await sweepOrphanedProcesses();
+  if (swept > 0) {
+    logger.info('cli', 'cleaned up orphaned process trees from a previous run', { swept });
+  }
 
   // Create and run session
   const session = new DebugSession(
     {
       adapter,
-      program: options.program,
-      args: options.args,
-      cwd: options.cwd,
+      program,
+      module: options.module,
+      interpreter,
+      gevent: options.gevent,
+      django: options.django,
+      skipFiles: options.skipFiles === false ? [] : options.skipFiles,
+      justMyCode: options.justMyCode ? options.justMyCode === 'on' : undefined,
+      requireExactSource: options.requireExactSource
+        ? options.requireExactSource === 'on'
+        : undefined,
+      symbolServers,
+      sourceLink: options.sourceLink,
+      runtimeArgs,
+      runtimeExecutable,
+      args: programArgs,
+      cwd,
       env: Object.keys(env).length > 0 ? env : undefined,
       breakpoints: options.breakpoint,
+      breakpointExpansions: options.breakpointExpansions,
       logpoints: options.logpoint && options.logpoint.length > 0 ? options.logpoint : undefined,
+      instructionBreakpoints:
+        options.breakAddr && options.breakAddr.length > 0 ? options.breakAddr : undefined,
+      mixedMode,
       exceptionFilters: options.breakOnException,
       evaluations: options.eval.length > 0 ? options.eval : undefined,
       assertions: options.assert.length > 0 ? options.assert : undefined,
+      continueIf: options.continueIf,
+      assignments: options.set,
+      onHit,
+      onExit: options.onExit,
+      captureModules: options.modules,
+      requestStats: options.requestStats,
+      timeline,
+      watchMemory,
+      aggregates,
+      sampleExceptions,
       timeout,
       captureLocals: options.captureLocals,
       steps: options.steps,
       captureEachStep: options.captureEachStep,
       attach: options.attach,
-      pid: options.pid,
+      pid: inspector?.pid ?? options.pid,
+      attachPort: inspector?.port,
       trace: options.trace,
       traceInto: options.traceInto,
+      stepFilters: options.stepFilter,
       traceLimit: options.traceLimit,
       traceUntil: options.traceUntil,
       diffVars: options.diffVars,
       evalAfterStep: options.evalAfterStep,
+      evalSafe: options.evalSafe,
+      evalContext: options.evalContext,
+      evalExpand: options.evalExpand,
+      provenance: options.provenance,
+      captureBudget,
+      maxHits: options.maxHits,
+      skipToHit: options.skipToHit,
+      hitWindows,
+      armOnOutput,
+      includeSource: options.includeSource,
+      captureArgs: options.captureArgs,
+      heapSnapshotDir,
+      onStopExec,
+      trigger,
       // Token efficiency options
       expandServices: options.expandServices,
       showNullProps: options.showNullProps,
@@ -578,19 +2576,575 @@ async function runDebugSession(options: CliOptions & { env?: string[] }): Promis
       exceptionChainDepth: options.exceptionChainDepth,
       // Source map options
       sourceMapOverrides,
+      launchOverrides,
+      sigintMode: options.sigintMode,
+      resourceLimits,
+      limitsBestEffort: options.limitsBestEffort,
+      sandbox,
+      untilCrash: options.untilCrash,
+      wslDistro,
+      adapterTraffic: artifacts && redactor ? redactTraffic(artifacts, redactor) : artifacts,
+      statusLine,
     },
     formatter
   );
 
+  // Adapters run in their own process group, so Ctrl+C only reaches debug-run. Escalate
+  // per --sigint-mode (snapshot, forward SIGINT, tear down) and force-kill after teardown
+  let interruptedBy: NodeJS.Signals | undefined;
+  const interrupt = (signal: NodeJS.Signals) => {
+    void session.interrupt(signal === 'SIGTERM' ? 'SIGTERM' : 'SIGINT').then((action) => {
+      if (action === 'kill') {
+        terminateTrackedProcessesSync();
+        process.exit(signalExitCode(signal));
+      }
+      if (action === 'stop') {
+        interruptedBy = signal;
+      }
+    });
+  };
+  process.on('SIGINT', interrupt);
+  process.on('SIGTERM', interrupt);
+
+  let failed = false;
   try {
     await session.run();
   } catch {
-    process.exit(1);
+    failed = true;
   } finally {
+    process.off('SIGINT', interrupt);
+    process.off('SIGTERM', interrupt);
+    statusLine?.clear();
+    if (htmlReport) {
+      outputStream.write(htmlReport.render());
+    }
     // Close file stream if we created one
     if (fileStream) {
       fileStream.end();
     }
+    store?.close();
+  }
+
+  if (artifacts) {
+    await artifacts.close();
+    console.error(`Artifacts: ${path.relative(process.cwd(), artifacts.dir)}`);
+  }
+
+  if (resultRun) {
+    await resultRun.finish(failed || interruptedBy !== undefined);
+    console.error(`Results: ${path.relative(process.cwd(), resultRun.file)}`);
+  }
+
+  if (otlp) {
+    stopObservingSpans?.();
+    await otlp.flush();
+  }
+
+  if (timeline && options.timeline) {
+    stopTimeline?.();
+    try {
+      timeline.write(path.resolve(options.timeline));
+      console.error(`Timeline: ${options.timeline}`);
+    } catch (error) {
+      console.error(`Error: ${error instanceof Error ? error.message : error}`);
+      failed = true;
+    }
+  }
+
+  if (valueExport && options.exportValues) {
+    try {
+      valueExport.write(path.resolve(options.exportValues));
+      console.error(`Values: ${options.exportValues} (${valueExport.rows.length} rows)`);
+    } catch (error) {
+      console.error(`Error: ${error instanceof Error ? error.message : error}`);
+      failed = true;
+    }
+  }
+
+  if (baseline && baselineRecorder && options.expect) {
+    const { stops } = baselineRecorder;
+    if (options.expectUpdate) {
+      try {
+        writeBaseline(path.resolve(options.expect), stops, baseline.rules);
+        console.error(`Baseline: ${options.expect} (${stops.length} stops)`);
+      } catch (error) {
+        console.error(`Error: ${error instanceof Error ? error.message : error}`);
+        failed = true;
+      }
+    } else {
+      const differences = compareToBaseline(baseline, stops);
+      if (differences.length > 0) {
+        console.error(formatBaselineDiff(options.expect, differences));
+        failed = true;
+      } else {
+        console.error(`Expectation: ${stops.length} stops match ${options.expect}`);
+      }
+    }
+  }
+
+  if (interruptedBy) {
+    process.exit(signalExitCode(interruptedBy));
+  }
+  if (failed) {
+    process.exit(1);
+  }
+  if (options.propagateExitCode) {
+    process.exit(debuggeeExitCode(termination) ?? 0);
+  }
+}
+
+/**
+ * Pass the adapter traffic --artifacts records through the redactor
+ */
+function redactTraffic(
+  traffic: AdapterTrafficObserver,
+  redactor: Redactor
+): AdapterTrafficObserver {
+  return {
+    protocolMessage: (direction, message, session) =>
+      traffic.protocolMessage(direction, redactor.redactMessage(message), session),
+    adapterStderr: (data) => traffic.adapterStderr(redactor.redactText(data)),
+    adapterStdout: (data) => traffic.adapterStdout(redactor.redactText(data)),
+  };
+}
+
+/**
+ * Conventional exit code for a process ended by a signal (128 + signal number)
+ */
+function signalExitCode(signal: NodeJS.Signals): number {
+  return signal === 'SIGINT' ? 130 : 143;
+}
+
+/**
+ * Exit status mirroring the debuggee (--propagate-exit-code): its exit code, or the
+ * signal exit code when a signal killed it
+ */
+export function debuggeeExitCode(termination: TerminationInfo | undefined): number | undefined {
+  if (termination?.exitCode != null) {
+    return termination.exitCode;
+  }
+  const signal = termination?.signal as NodeJS.Signals | undefined;
+  const signalNumber = signal ? osConstants.signals[signal] : undefined;
+  return signalNumber !== undefined ? 128 + signalNumber : undefined;
+}
+
+/**
+ * Run the debug server until interrupted
+ */
+async function runServer(options: {
+  port: number;
+  host: string;
+  saveSession?: string;
+  history?: number;
+  keepSessions?: number;
+  stateFile?: string;
+  token?: string;
+}): Promise<void> {
+  let projectState: ProjectStateStore;
+  try {
+    projectState = new ProjectStateStore(options.stateFile);
+  } catch (error) {
+    console.error(`Error: ${error instanceof Error ? error.message : error}`);
+    process.exit(1);
+  }
+  const server = new DebugServer(VERSION, {
+    history: options.history,
+    keepSessions: options.keepSessions,
+    projectState,
+    token: options.token ?? process.env[TOKEN_ENV],
+  });
+  try {
+    const address = await server.listen(options.port, options.host);
+    const origin = `${options.host}:${address.port}`;
+    console.error(`debug-run server listening on ws://${origin}${WEBSOCKET_PATH}`);
+    console.error(`REST API: http://${origin}/sessions (spec: http://${origin}/openapi.json)`);
+    console.error(`Metrics: http://${origin}/metrics`);
+    console.error(`Token: ${server.token} (send "Authorization: Bearer <token>")`);
+  } catch (error) {
+    console.error(`Error: ${error instanceof Error ? error.message : error}`);
+    process.exit(1);
+  }
+
+  const shutdown = async () => {
+    // Save before stopping: stopping detaches the sessions
+    if (options.saveSession) {
+      const state = server.sessions.saveAll();
+      if (state.sessions.length > 0) {
+        fs.writeFileSync(options.saveSession, JSON.stringify(state, null, 2) + '\n');
+        console.error(
+          `Saved ${state.sessions.length} session(s) to ${options.saveSession}; ` +
+            `resume with: debug-run resume ${options.saveSession}`
+        );
+      }
+    }
+    await server.close();
+    process.exit(0);
+  };
+  process.once('SIGINT', shutdown);
+  process.once('SIGTERM', shutdown);
+  // Closing the terminal
+  process.once('SIGHUP', shutdown);
+}
+
+/**
+ * Re-attach saved sessions, in a server (--server) or as a foreground session
+ */
+async function runResume(
+  file: string,
+  options: { server?: string; session?: string; token?: string; pretty: boolean }
+): Promise<void> {
+  let state: SavedSessionState;
+  try {
+    state = parseSavedSessionState(JSON.parse(fs.readFileSync(file, 'utf-8')));
+  } catch (error) {
+    console.error(`Error: Cannot read ${file}: ${error instanceof Error ? error.message : error}`);
+    process.exit(1);
+  }
+
+  const sessions = options.session
+    ? state.sessions.filter((session) => session.sessionId === options.session)
+    : state.sessions;
+  if (sessions.length === 0) {
+    console.error(
+      options.session
+        ? `Error: No saved session ${options.session} in ${file}`
+        : `Error: ${file} contains no sessions`
+    );
+    process.exit(1);
+  }
+
+  const gone = sessions.filter((session) => !isProcessAlive(session.params.pid!));
+  for (const session of gone) {
+    console.error(
+      `Error: Process ${session.params.pid} (session ${session.sessionId}) has exited`
+    );
+  }
+  if (gone.length > 0) {
+    process.exit(1);
+  }
+
+  if (options.server) {
+    const response = await fetch(new URL('/sessions/resume', options.server), {
+      method: 'POST',
+      headers: {
+        'Content-Type': 'application/json',
+        Authorization: `Bearer ${options.token ?? process.env[TOKEN_ENV] ?? ''}`,
+      },
+      body: JSON.stringify({ ...state, sessions }),
+    }).catch((error: Error) => {
+      console.error(`Error: Cannot reach ${options.server}: ${error.message}`);
+      process.exit(1);
+    });
+    const body = await response.json();
+    if (!response.ok) {
+      console.error(`Error: ${body.error ?? response.statusText}`);
+      process.exit(1);
+    }
+    console.log(JSON.stringify(body, null, options.pretty ? 2 : undefined));
+    return;
+  }
+
+  if (sessions.length > 1) {
+    console.error(`Error: ${file} has ${sessions.length} sessions; pick one with --session`);
+    console.error(`Saved sessions: ${sessions.map((session) => session.sessionId).join(', ')}`);
+    process.exit(1);
+  }
+
+  await runDebugSession(cliOptionsFromSessionParams(sessions[0].params, options.pretty));
+}
+
+/**
+ * CLI options equivalent to serve-mode session parameters (for foreground resume)
+ */
+function cliOptionsFromSessionParams(params: SessionParams, pretty: boolean): CliOptions {
+  return {
+    adapter: params.adapter,
+    backend: params.backend,
+    cwd: params.cwd,
+    breakpoint: params.breakpoints ?? [],
+    logpoint: params.logpoints ?? [],
+    breakAddr: params.instructionBreakpoints,
+    eval: params.evaluations ?? [],
+    assert: params.assertions ?? [],
+    evalSafe: params.evalSafe,
+    evalContext: params.evalContext,
+    evalExpand: params.evalExpand,
+    provenance: params.provenance,
+    ...(params.captureBudgetMs !== undefined && { captureBudget: `${params.captureBudgetMs}ms` }),
+    breakOnException: params.exceptionFilters,
+    ...(params.timeoutMs !== undefined && { timeout: `${params.timeoutMs}ms` }),
+    captureLocals: params.captureLocals ?? true,
+    steps: params.steps,
+    trace: params.trace,
+    traceInto: params.traceInto,
+    traceLimit: params.traceLimit ?? 500,
+    traceUntil: params.traceUntil,
+    maxHits: params.maxHits,
+    skipToHit: params.skipToHit,
+    includeSource: params.includeSource,
+    captureArgs: params.captureArgs,
+    ...(params.defines && {
+      define: Object.entries(params.defines).map(([name, value]) => `${name}=${value}`),
+    }),
+    attach: true,
+    pid: params.pid,
+    pretty,
+  };
+}
+
+/**
+ * Launch a program stopped at entry and print which lines in a range are breakable
+ */
+async function runBreakpointLocations(
+  rangeSpec: string,
+  options: {
+    adapter: string;
+    backend?: string;
+    program?: string;
+    args?: string[];
+    cwd?: string;
+    timeout: string;
+    json: boolean;
+  }
+): Promise<void> {
+  const adapter = resolveAdapter(options);
+  if (!(await adapter.detect())) {
+    console.error(`Adapter "${adapter.name}" is not installed.`);
+    console.error(adapter.installHint);
+    process.exit(1);
+  }
+
+  const cwd = options.cwd ? path.resolve(options.cwd) : process.cwd();
+  let range: LineRange;
+  let timeout: number;
+  try {
+    range = parseLineRange(rangeSpec, cwd);
+    timeout = parseTimeout(options.timeout);
+  } catch (error) {
+    console.error(`Error: ${error instanceof Error ? error.message : error}`);
+    process.exit(1);
+  }
+  if (!fs.existsSync(range.file)) {
+    console.error(`Error: File not found: ${range.file}`);
+    process.exit(1);
+  }
+
+  try {
+    const report = await queryBreakableLines(adapter, range, {
+      program: options.program ? path.resolve(cwd, options.program) : range.file,
+      args: options.args,
+      cwd,
+      timeout,
+    });
+    if (options.json) {
+      console.log(JSON.stringify(report, null, 2));
+    } else {
+      console.log(formatBreakableLinesReport(report, cwd));
+    }
+    if (report.lines.length === 0) {
+      process.exit(1);
+    }
+  } catch (error) {
+    console.error(`Error: ${error instanceof Error ? error.message : error}`);
+    process.exit(1);
+  }
+}
+
+/**
+ * Launch a program until its breakpoints bind and print how each one fared
+ */
+async function runVerify(options: {
+  adapter: string;
+  backend?: string;
+  program: string;
+  breakpoint: string[];
+  args?: string[];
+  cwd?: string;
+  sourceMapOverrides?: string;
+  timeout: string;
+  json: boolean;
+}): Promise<void> {
+  const adapter = resolveAdapter(options);
+  if (!(await adapter.detect())) {
+    console.error(`Adapter "${adapter.name}" is not installed.`);
+    console.error(adapter.installHint);
+    process.exit(1);
+  }
+
+  const cwd = options.cwd ? path.resolve(options.cwd) : process.cwd();
+  let breakpoints: PendingBreakpoint[];
+  let timeout: number;
+  let sourceMapOverrides: Record<string, string> | undefined;
+  try {
+    breakpoints = parseVerifySpecs(options.breakpoint, { cwd });
+    timeout = parseTimeout(options.timeout);
+    if (options.sourceMapOverrides) {
+      sourceMapOverrides = parseSourceMapOverrides(options.sourceMapOverrides, cwd);
+    }
+  } catch (error) {
+    console.error(`Error: ${error instanceof Error ? error.message : error}`);
+    process.exit(1);
+  }
+
+  try {
+    const report = await verifyBreakpoints(adapter, breakpoints, {
+      program: path.resolve(cwd, options.program),
+      args: options.args,
+      cwd,
+      sourceMapOverrides,
+      timeout,
+    });
+    if (options.json) {
+      console.log(JSON.stringify(report, null, 2));
+    } else {
+      console.log(formatVerifyReport(report, cwd));
+    }
+    if (report.breakpoints.some((bp) => !bp.verified)) {
+      process.exit(1);
+    }
+  } catch (error) {
+    console.error(`Error: ${error instanceof Error ? error.message : error}`);
+    process.exit(1);
+  }
+}
+
+async function runCapabilities(options: {
+  adapter: string;
+  backend?: string;
+  cwd?: string;
+  timeout: string;
+  json: boolean;
+}): Promise<void> {
+  const adapter = resolveAdapter(options);
+  if (!(await adapter.detect())) {
+    console.error(`Adapter "${adapter.name}" is not installed.`);
+    console.error(adapter.installHint);
+    process.exit(1);
+  }
+
+  let timeout: number;
+  try {
+    timeout = parseTimeout(options.timeout);
+  } catch (error) {
+    console.error(`Error: ${error instanceof Error ? error.message : error}`);
+    process.exit(1);
+  }
+
+  try {
+    const report = await queryCapabilities(adapter, {
+      cwd: options.cwd ? path.resolve(options.cwd) : process.cwd(),
+      timeout,
+    });
+    console.log(options.json ? JSON.stringify(report, null, 2) : formatCapabilitiesReport(report));
+  } catch (error) {
+    console.error(`Error: ${error instanceof Error ? error.message : error}`);
+    process.exit(1);
+  }
+}
+
+/**
+ * Write a sample app and print how to build and debug it
+ */
+function runSamplesGenerate(
+  language: string,
+  dir: string | undefined,
+  options: { force: boolean }
+): void {
+  let sample: GeneratedSample;
+  try {
+    const { language: name } = getSampleProject(language);
+    sample = generateSample(name, path.resolve(dir ?? `${name}-sample`), options);
+  } catch (error) {
+    console.error(`Error: ${error instanceof Error ? error.message : error}`);
+    process.exit(1);
+  }
+
+  const { project, directory, files } = sample;
+  const relative = path.relative(process.cwd(), directory) || '.';
+  const breakpoints = sampleBreakpoints(project);
+  const width = Math.max(...breakpoints.map((bp) => bp.spec.length));
+  console.log(`Wrote the ${project.language} sample to ${relative}:`);
+  for (const file of files) console.log(`  ${file}`);
+  console.log('');
+  console.log('Build and debug it (breakpoints and evaluations come from .debug-run.toml):');
+  console.log(`  cd ${relative}`);
+  if (project.build) console.log(`  ${project.build}`);
+  console.log(`  ${sampleCommand(project)}`);
+  console.log('');
+  console.log('Good breakpoint lines:');
+  for (const bp of breakpoints) {
+    const isDefault = bp.description === project.defaultBreakpoint ? ' (in .debug-run.toml)' : '';
+    console.log(`  ${bp.spec.padEnd(width)}  ${bp.description}${isDefault}`);
+  }
+}
+
+/**
+ * Run the conformance matrix against an adapter and print the report, failing if any
+ * feature the adapter claims (or every adapter should have) didn't work
+ */
+async function runConformanceCommand(options: {
+  adapter: string;
+  backend?: string;
+  language?: string;
+  feature?: string[];
+  timeout: string;
+  json: boolean;
+}): Promise<void> {
+  const adapter = resolveAdapter(options);
+  if (!(await adapter.detect())) {
+    console.error(`Adapter "${adapter.name}" is not installed.`);
+    console.error(adapter.installHint);
+    process.exit(1);
+  }
+
+  let timeout: number;
+  try {
+    timeout = parseTimeout(options.timeout);
+  } catch (error) {
+    console.error(`Error: ${error instanceof Error ? error.message : error}`);
+    process.exit(1);
+  }
+
+  try {
+    const report = await runConformance(adapter, {
+      language: options.language,
+      features: options.feature,
+      timeout,
+    });
+    console.log(options.json ? JSON.stringify(report, null, 2) : formatConformanceReport(report));
+    if (report.results.some((result) => result.status === 'fail')) {
+      process.exit(1);
+    }
+  } catch (error) {
+    console.error(`Error: ${error instanceof Error ? error.message : error}`);
+    process.exit(1);
+  }
+}
+
+/**
+ * Run a canned query against a --store database and print one JSON row per line
+ */
+function runStoreQuery(
+  db: string,
+  query: string,
+  options: { expression?: string; last: number }
+): void {
+  if (!fs.existsSync(db)) {
+    console.error(`Error: Store not found: ${db}`);
+    process.exit(1);
+  }
+
+  let store: ResultStore | undefined;
+  try {
+    store = ResultStore.open(db);
+    for (const row of store.query(query, options)) {
+      console.log(JSON.stringify(row));
+    }
+  } catch (error) {
+    console.error(`Error: ${error instanceof Error ? error.message : error}`);
+    process.exit(1);
+  } finally {
+    store?.close();
   }
 }
 
@@ -11,6 +11,10 @@ import type {
   AttachRequestArguments,
   SetBreakpointsArguments,
   SetBreakpointsResponse,
+  SetInstructionBreakpointsArguments,
+  SetInstructionBreakpointsResponse,
+  BreakpointLocationsArguments,
+  BreakpointLocationsResponse,
   SetExceptionBreakpointsArguments,
   StackTraceArguments,
   StackTraceResponse,
@@ -38,6 +42,10 @@ export interface IDapClient extends EventEmitter {
   attach(args: AttachRequestArguments): Promise<void>;
   configurationDone(): Promise<void>;
   setBreakpoints(args: SetBreakpointsArguments): Promise<SetBreakpointsResponse>;
+  setInstructionBreakpoints(
+    args: SetInstructionBreakpointsArguments
+  ): Promise<SetInstructionBreakpointsResponse>;
+  breakpointLocations(args: BreakpointLocationsArguments): Promise<BreakpointLocationsResponse>;
   setExceptionBreakpoints(args: SetExceptionBreakpointsArguments): Promise<void>;
   threads(): Promise<ThreadsResponse>;
   stackTrace(args: StackTraceArguments): Promise<StackTraceResponse>;
@@ -53,6 +61,10 @@ export interface IDapClient extends EventEmitter {
   terminate(): Promise<void>;
   getCapabilities(): Capabilities;
   isConnected(): boolean;
+  /** Kill whatever remains of the adapter's process tree (debuggee, helper processes) */
+  reapProcessTree(): Promise<void>;
   /** Retrieve source content for virtual/generated sources (Phase 4) */
   source(args: SourceArguments): Promise<SourceResponse>;
+  /** Send an adapter-specific request (e.g., js-debug's startProfile) */
+  customRequest<T = unknown>(command: string, args?: unknown): Promise<T>;
 }
@@ -7,15 +7,30 @@
 import { spawn, type ChildProcess } from 'node:child_process';
 import { EventEmitter } from 'node:events';
 import { DapTransport } from './transport.js';
+import {
+  killProcessTree,
+  processGroupSpawnOptions,
+  trackProcess,
+  untrackProcess,
+} from '../util/process-tree.js';
 import { signHandshake } from '../util/vsda-signer.js';
+import { logger } from '../util/logger.js';
+import { TerminalLauncher, type CommandWrapper } from './terminal.js';
+import type { PathMapper } from './path-mapper.js';
 import type {
+  ProtocolMessage,
   Request,
+  RunInTerminalRequestArguments,
   InitializeRequestArguments,
   InitializeResponse,
   LaunchRequestArguments,
   AttachRequestArguments,
   SetBreakpointsArguments,
   SetBreakpointsResponse,
+  SetInstructionBreakpointsArguments,
+  SetInstructionBreakpointsResponse,
+  BreakpointLocationsArguments,
+  BreakpointLocationsResponse,
   SetExceptionBreakpointsArguments,
   StackTraceArguments,
   StackTraceResponse,
@@ -37,6 +52,12 @@ import type {
   ExitedEventBody,
   OutputEventBody,
   BreakpointEventBody,
+  ModuleEventBody,
+  ThreadEventBody,
+  ProgressStartEventBody,
+  ProgressUpdateEventBody,
+  ProgressEndEventBody,
+  ProcessEventBody,
   Event,
   SourceArguments,
   SourceResponse,
@@ -53,6 +74,10 @@ export interface DapClientOptions {
   env?: Record<string, string>;
   /** Request timeout in ms (default: 30000) */
   timeout?: number;
+  /** Translates paths for an adapter with a different view of the file system (--wsl) */
+  pathMapper?: PathMapper;
+  /** Runs the commands of runInTerminal requests inside this (--sandbox) */
+  terminalWrapper?: CommandWrapper;
 }
 
 export class DapClient extends EventEmitter {
@@ -61,10 +86,17 @@ export class DapClient extends EventEmitter {
   private options: DapClientOptions;
   private capabilities: Capabilities = {};
   private initialized: boolean = false;
+  private reaped: boolean = false;
+  /** Runs the debuggee for adapters that launch it through runInTerminal */
+  private terminal: TerminalLauncher;
 
   constructor(options: DapClientOptions) {
     super();
     this.options = options;
+    this.terminal = new TerminalLauncher(
+      (body) => this.emit('output', body),
+      options.terminalWrapper
+    );
   }
 
   /**
@@ -79,9 +111,11 @@ export class DapClient extends EventEmitter {
       cwd: this.options.cwd,
       env: { ...process.env, ...this.options.env },
       stdio: ['pipe', 'pipe', 'pipe'],
+      ...processGroupSpawnOptions(),
     });
+    trackProcess(this.process.pid, this.options.command);
 
-    this.transport = new DapTransport(this.process, this.options.timeout);
+    this.transport = new DapTransport(this.process, this.options.timeout, this.options.pathMapper);
 
     // Forward events
     this.transport.on('event:stopped', (body: StoppedEventBody) => {
@@ -104,6 +138,30 @@ export class DapClient extends EventEmitter {
       this.emit('breakpoint', body);
     });
 
+    this.transport.on('event:process', (body: ProcessEventBody) => {
+      this.emit('process', body);
+    });
+
+    this.transport.on('event:module', (body: ModuleEventBody) => {
+      this.emit('module', body);
+    });
+
+    this.transport.on('event:thread', (body: ThreadEventBody) => {
+      this.emit('thread', body);
+    });
+
+    this.transport.on('event:progressStart', (body: ProgressStartEventBody) => {
+      this.emit('progress', 'start', body);
+    });
+
+    this.transport.on('event:progressUpdate', (body: ProgressUpdateEventBody) => {
+      this.emit('progress', 'update', body);
+    });
+
+    this.transport.on('event:progressEnd', (body: ProgressEndEventBody) => {
+      this.emit('progress', 'end', body);
+    });
+
     this.transport.on('event:initialized', () => {
       this.emit('initialized');
     });
@@ -116,6 +174,15 @@ export class DapClient extends EventEmitter {
       this.emit('stderr', data);
     });
 
+    // Raw traffic for protocol traces (--artifacts)
+    this.transport.on('sent', (message: ProtocolMessage) => {
+      this.emit('protocolMessage', 'send', message);
+    });
+
+    this.transport.on('message', (message: ProtocolMessage) => {
+      this.emit('protocolMessage', 'receive', message);
+    });
+
     this.transport.on('exit', (code: number | null, signal: string | null) => {
       this.emit('exit', code, signal);
     });
@@ -128,6 +195,24 @@ export class DapClient extends EventEmitter {
     this.transport.on('reverseRequest:handshake', (request: Request) => {
       this.handleHandshakeRequest(request);
     });
+
+    this.transport.on('reverseRequest:runInTerminal', (request: Request) => {
+      void this.handleRunInTerminalRequest(request);
+    });
+  }
+
+  /**
+   * Run the command the adapter would have run in a terminal and report its PID
+   */
+  private async handleRunInTerminalRequest(request: Request): Promise<void> {
+    try {
+      const body = await this.terminal.launch(request.arguments as RunInTerminalRequestArguments);
+      this.sendReverseResponse(request, true, undefined, body);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      logger.warn('dap', 'runInTerminal failed', { error: message });
+      this.sendReverseResponse(request, false, message);
+    }
   }
 
   /**
@@ -202,7 +287,8 @@ export class DapClient extends EventEmitter {
       columnsStartAt1: true,
       supportsVariableType: true,
       supportsVariablePaging: true,
-      supportsRunInTerminalRequest: false,
+      supportsRunInTerminalRequest: true,
+      supportsProgressReporting: true,
       ...args,
     });
 
@@ -256,6 +342,32 @@ export class DapClient extends EventEmitter {
     return await this.transport!.sendRequest<SetBreakpointsResponse>('setBreakpoints', args);
   }
 
+  /**
+   * Set breakpoints on instruction addresses (replaces all instruction breakpoints)
+   */
+  async setInstructionBreakpoints(
+    args: SetInstructionBreakpointsArguments
+  ): Promise<SetInstructionBreakpointsResponse> {
+    this.ensureInitialized();
+    return await this.transport!.sendRequest<SetInstructionBreakpointsResponse>(
+      'setInstructionBreakpoints',
+      args
+    );
+  }
+
+  /**
+   * List the possible breakpoint locations in a source range
+   */
+  async breakpointLocations(
+    args: BreakpointLocationsArguments
+  ): Promise<BreakpointLocationsResponse> {
+    this.ensureInitialized();
+    return await this.transport!.sendRequest<BreakpointLocationsResponse>(
+      'breakpointLocations',
+      args
+    );
+  }
+
   /**
    * Set exception breakpoints
    */
@@ -361,6 +473,9 @@ export class DapClient extends EventEmitter {
     } catch {
       // Ignore errors during disconnect
     } finally {
+      // Reap before closing: once the adapter exits, its children are reparented
+      // and can no longer be found by walking the tree
+      await this.reapProcessTree();
       this.transport.close();
     }
   }
@@ -386,6 +501,14 @@ export class DapClient extends EventEmitter {
     return await this.transport!.sendRequest<SourceResponse>('source', args);
   }
 
+  /**
+   * Send an adapter-specific request
+   */
+  async customRequest<T = unknown>(command: string, args?: unknown): Promise<T> {
+    this.ensureInitialized();
+    return await this.transport!.sendRequest<T>(command, args);
+  }
+
   /**
    * Get the debug adapter capabilities
    */
@@ -400,6 +523,19 @@ export class DapClient extends EventEmitter {
     return this.transport?.isOpen() ?? false;
   }
 
+  /**
+   * Kill whatever remains of the adapter's process tree (debuggee, helper processes).
+   * Safe to call more than once.
+   */
+  async reapProcessTree(): Promise<void> {
+    await this.terminal.reap();
+    const pid = this.process?.pid;
+    if (!pid || this.reaped) return;
+    this.reaped = true;
+    await killProcessTree(pid);
+    untrackProcess(pid);
+  }
+
   private ensureConnected(): void {
     if (!this.transport) {
       throw new Error('Not connected. Call connect() first.');
@@ -0,0 +1,68 @@
+/**
+ * DAP Path Mapping
+ *
+ * Rewrites file paths in DAP traffic for an adapter that sees the file system differently
+ * from debug-run (one running inside WSL, for example). Paths in requests are translated to
+ * the adapter's form; the `path` of every source, module and frame it reports is translated
+ * back, so breakpoints, stack traces and source lookups all use local paths.
+ */
+
+import type { ProtocolMessage, Request } from './protocol.js';
+
+export interface PathMapper {
+  /** A local path as the adapter sees it (other strings returned unchanged) */
+  toAdapter(path: string): string;
+  /** A path the adapter reported, as a local path */
+  toLocal(path: string): string;
+}
+
+/** Requests whose arguments are code or values rather than paths */
+const CODE_REQUESTS = new Set(['evaluate', 'setExpression', 'setVariable', 'completions']);
+
+/** Request fields holding code or text that may happen to look like a path */
+const CODE_FIELDS = new Set(['condition', 'hitCondition', 'logMessage', 'expression']);
+
+function mapStrings(value: unknown, map: (text: string) => string): unknown {
+  if (typeof value === 'string') return map(value);
+  if (Array.isArray(value)) return value.map((item) => mapStrings(item, map));
+  if (value === null || typeof value !== 'object') return value;
+  return Object.fromEntries(
+    Object.entries(value).map(([key, item]) => [
+      key,
+      CODE_FIELDS.has(key) ? item : mapStrings(item, map),
+    ])
+  );
+}
+
+function mapPathFields(value: unknown, map: (path: string) => string): unknown {
+  if (Array.isArray(value)) return value.map((item) => mapPathFields(item, map));
+  if (value === null || typeof value !== 'object') return value;
+  return Object.fromEntries(
+    Object.entries(value).map(([key, item]) => [
+      key,
+      key === 'path' && typeof item === 'string' ? map(item) : mapPathFields(item, map),
+    ])
+  );
+}
+
+/**
+ * A message about to be sent, with the paths in a request's arguments in the adapter's form
+ * (responses to the adapter's reverse requests are sent as they are)
+ */
+export function mapOutgoingPaths(message: ProtocolMessage, mapper: PathMapper): ProtocolMessage {
+  if (message.type !== 'request') return message;
+  const request = message as Request;
+  if (request.arguments === undefined || CODE_REQUESTS.has(request.command)) return message;
+  return {
+    ...request,
+    arguments: mapStrings(request.arguments, (text) => mapper.toAdapter(text)),
+  };
+}
+
+/**
+ * A received response or event with every `path` field translated to a local path
+ */
+export function mapIncomingPaths(message: ProtocolMessage, mapper: PathMapper): ProtocolMessage {
+  if (message.type !== 'response' && message.type !== 'event') return message;
+  return mapPathFields(message, (path) => mapper.toLocal(path)) as ProtocolMessage;
+}
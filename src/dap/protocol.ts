@@ -39,17 +39,28 @@ export interface Capabilities {
   supportsConditionalBreakpoints?: boolean;
   supportsHitConditionalBreakpoints?: boolean;
   supportsEvaluateForHovers?: boolean;
+  supportsClipboardContext?: boolean;
   supportsExceptionOptions?: boolean;
+  supportsExceptionFilterOptions?: boolean;
+  exceptionBreakpointFilters?: ExceptionBreakpointsFilter[];
   supportsExceptionInfoRequest?: boolean;
   supportsValueFormattingOptions?: boolean;
   supportsStepBack?: boolean;
   supportsSetVariable?: boolean;
+  supportsSetExpression?: boolean;
   supportsRestartFrame?: boolean;
+  supportsRestartRequest?: boolean;
+  supportsTerminateRequest?: boolean;
   supportsGotoTargetsRequest?: boolean;
   supportsStepInTargetsRequest?: boolean;
   supportsCompletionsRequest?: boolean;
   supportsModulesRequest?: boolean;
   supportsLogPoints?: boolean;
+  supportsInstructionBreakpoints?: boolean;
+  supportsBreakpointLocationsRequest?: boolean;
+  supportsReadMemoryRequest?: boolean;
+  supportsDataBreakpoints?: boolean;
+  supportsDataBreakpointBytes?: boolean;
 }
 
 // Breakpoint types
@@ -70,12 +81,26 @@ export interface Breakpoint {
   column?: number;
   endLine?: number;
   endColumn?: number;
+  instructionReference?: string;
+  offset?: number;
 }
 
 export interface Source {
   name?: string;
   path?: string;
   sourceReference?: number;
+  /** js-debug deemphasizes sources matched by skipFiles */
+  presentationHint?: 'normal' | 'emphasize' | 'deemphasize';
+  /** Why the source is presented this way (e.g., "Skipped by skipFiles") */
+  origin?: string;
+  checksums?: Checksum[];
+}
+
+export type ChecksumAlgorithm = 'MD5' | 'SHA1' | 'SHA256' | 'timestamp';
+
+export interface Checksum {
+  algorithm: ChecksumAlgorithm;
+  checksum: string;
 }
 
 // Stack and variables
@@ -89,10 +114,13 @@ export interface StackFrame {
   endColumn?: number;
   moduleId?: number | string;
   presentationHint?: 'normal' | 'label' | 'subtle';
+  instructionPointerReference?: string;
 }
 
 export interface Scope {
   name: string;
+  /** e.g., 'arguments', 'locals', 'registers' */
+  presentationHint?: string;
   variablesReference: number;
   namedVariables?: number;
   indexedVariables?: number;
@@ -108,10 +136,23 @@ export interface Variable {
   name: string;
   value: string;
   type?: string;
+  presentationHint?: VariablePresentationHint;
   variablesReference: number;
   namedVariables?: number;
   indexedVariables?: number;
   evaluateName?: string;
+  memoryReference?: string;
+}
+
+export interface VariablePresentationHint {
+  /** e.g., 'property', 'method', 'class', 'data', 'virtual' */
+  kind?: string;
+  /** e.g., 'readOnly', 'constant', 'hasSideEffects', 'rawString' */
+  attributes?: string[];
+  /** e.g., 'public', 'private', 'internal' */
+  visibility?: string;
+  /** The value is only fetched when expanded (a getter) */
+  lazy?: boolean;
 }
 
 // Thread
@@ -162,9 +203,30 @@ export interface SetBreakpointsArguments {
   sourceModified?: boolean;
 }
 
+export interface BreakpointLocationsArguments {
+  source: Source;
+  line: number;
+  column?: number;
+  endLine?: number;
+  endColumn?: number;
+}
+
+export interface InstructionBreakpoint {
+  /** Memory reference of the instruction, e.g. an address like 0x55aa113e */
+  instructionReference: string;
+  offset?: number;
+  condition?: string;
+  hitCondition?: string;
+}
+
+export interface SetInstructionBreakpointsArguments {
+  breakpoints: InstructionBreakpoint[];
+}
+
 export interface SetExceptionBreakpointsArguments {
   filters: string[];
   filterOptions?: ExceptionFilterOptions[];
+  exceptionOptions?: ExceptionOptions[];
 }
 
 export interface ExceptionFilterOptions {
@@ -172,6 +234,18 @@ export interface ExceptionFilterOptions {
   condition?: string;
 }
 
+export type ExceptionBreakMode = 'never' | 'always' | 'unhandled' | 'userUnhandled';
+
+export interface ExceptionPathSegment {
+  negate?: boolean;
+  names: string[];
+}
+
+export interface ExceptionOptions {
+  path?: ExceptionPathSegment[];
+  breakMode: ExceptionBreakMode;
+}
+
 export interface StackTraceArguments {
   threadId: number;
   startFrame?: number;
@@ -228,6 +302,21 @@ export interface SetBreakpointsResponse {
   breakpoints: Breakpoint[];
 }
 
+export interface SetInstructionBreakpointsResponse {
+  breakpoints: Breakpoint[];
+}
+
+export interface BreakpointLocation {
+  line: number;
+  column?: number;
+  endLine?: number;
+  endColumn?: number;
+}
+
+export interface BreakpointLocationsResponse {
+  breakpoints: BreakpointLocation[];
+}
+
 export interface StackTraceResponse {
   stackFrames: StackFrame[];
   totalFrames?: number;
@@ -244,7 +333,9 @@ export interface VariablesResponse {
 export interface EvaluateResponse {
   result: string;
   type?: string;
+  presentationHint?: VariablePresentationHint;
   variablesReference: number;
+  memoryReference?: string;
   namedVariables?: number;
   indexedVariables?: number;
 }
@@ -268,6 +359,70 @@ export interface ContinueResponse {
   allThreadsContinued?: boolean;
 }
 
+export interface GotoTargetsArguments {
+  source: Source;
+  line: number;
+  column?: number;
+}
+
+export interface GotoTarget {
+  id: number;
+  label: string;
+  line: number;
+  column?: number;
+}
+
+export interface GotoTargetsResponse {
+  targets: GotoTarget[];
+}
+
+export interface ReadMemoryArguments {
+  memoryReference: string;
+  offset?: number;
+  count: number;
+}
+
+export interface ReadMemoryResponse {
+  address: string;
+  unreadableBytes?: number;
+  /** Base64-encoded bytes */
+  data?: string;
+}
+
+export interface DataBreakpointInfoArguments {
+  variablesReference?: number;
+  name: string;
+  frameId?: number;
+  /** Size of the region, with asAddress */
+  bytes?: number;
+  /** Treat name as a memory address */
+  asAddress?: boolean;
+}
+
+export interface DataBreakpointInfoResponse {
+  dataId: string | null;
+  description: string;
+  accessTypes?: DataBreakpointAccessType[];
+  canPersist?: boolean;
+}
+
+export type DataBreakpointAccessType = 'read' | 'write' | 'readWrite';
+
+export interface DataBreakpoint {
+  dataId: string;
+  accessType?: DataBreakpointAccessType;
+  condition?: string;
+  hitCondition?: string;
+}
+
+export interface SetDataBreakpointsArguments {
+  breakpoints: DataBreakpoint[];
+}
+
+export interface SetDataBreakpointsResponse {
+  breakpoints: Breakpoint[];
+}
+
 // Event bodies
 export interface StoppedEventBody {
   reason:
@@ -308,6 +463,79 @@ export interface OutputEventBody {
   data?: unknown;
 }
 
+export interface ProcessEventBody {
+  name: string;
+  systemProcessId?: number;
+  isLocalProcess?: boolean;
+  startMethod?: 'launch' | 'attach' | 'attachForSuspendedLaunch';
+}
+
+/** Reverse request: the adapter asks the client to run the debuggee in a terminal */
+export interface RunInTerminalRequestArguments {
+  kind?: 'integrated' | 'external';
+  title?: string;
+  cwd: string;
+  args: string[];
+  /** Variables to add; null removes a variable */
+  env?: Record<string, string | null>;
+  argsCanBeInterpretedByShell?: boolean;
+}
+
+export interface RunInTerminalResponseBody {
+  processId?: number;
+  shellProcessId?: number;
+}
+
+export interface ProgressStartEventBody {
+  progressId: string;
+  title: string;
+  requestId?: number;
+  cancellable?: boolean;
+  message?: string;
+  percentage?: number;
+}
+
+export interface ProgressUpdateEventBody {
+  progressId: string;
+  message?: string;
+  percentage?: number;
+}
+
+export interface ProgressEndEventBody {
+  progressId: string;
+  message?: string;
+}
+
+export type ProgressEventKind = 'start' | 'update' | 'end';
+
+export interface ThreadEventBody {
+  reason: 'started' | 'exited' | string;
+  threadId: number;
+}
+
+export interface Module {
+  id: number | string;
+  name: string;
+  path?: string;
+  isOptimized?: boolean;
+  isUserCode?: boolean;
+  version?: string;
+  symbolStatus?: string;
+  symbolFilePath?: string;
+  dateTimeStamp?: string;
+  addressRange?: string;
+}
+
+export interface ModuleEventBody {
+  reason: 'new' | 'changed' | 'removed';
+  module: Module;
+}
+
+export interface ModulesResponse {
+  modules: Module[];
+  totalModules?: number;
+}
+
 export interface BreakpointEventBody {
   reason: 'changed' | 'new' | 'removed';
   breakpoint: Breakpoint;
@@ -8,13 +8,28 @@
 import { spawn, type ChildProcess } from 'node:child_process';
 import { EventEmitter } from 'node:events';
 import { SocketDapTransport } from './socket-transport.js';
+import {
+  killProcessTree,
+  processGroupSpawnOptions,
+  trackProcess,
+  untrackProcess,
+} from '../util/process-tree.js';
+import { logger } from '../util/logger.js';
+import { TerminalLauncher, type CommandWrapper } from './terminal.js';
+import type { PathMapper } from './path-mapper.js';
 import type {
+  ProtocolMessage,
+  RunInTerminalRequestArguments,
   InitializeRequestArguments,
   InitializeResponse,
   LaunchRequestArguments,
   AttachRequestArguments,
   SetBreakpointsArguments,
   SetBreakpointsResponse,
+  SetInstructionBreakpointsArguments,
+  SetInstructionBreakpointsResponse,
+  BreakpointLocationsArguments,
+  BreakpointLocationsResponse,
   SetExceptionBreakpointsArguments,
   StackTraceArguments,
   StackTraceResponse,
@@ -36,6 +51,11 @@ import type {
   ExitedEventBody,
   OutputEventBody,
   BreakpointEventBody,
+  ProcessEventBody,
+  ThreadEventBody,
+  ProgressStartEventBody,
+  ProgressUpdateEventBody,
+  ProgressEndEventBody,
   Event,
   SourceArguments,
   SourceResponse,
@@ -58,6 +78,10 @@ export interface SocketDapClientOptions {
   timeout?: number;
   /** Delay before connecting (ms) - give server time to start */
   connectDelay?: number;
+  /** Translates paths for an adapter with a different view of the file system (--wsl) */
+  pathMapper?: PathMapper;
+  /** Runs the commands of runInTerminal requests inside this (--sandbox) */
+  terminalWrapper?: CommandWrapper;
 }
 
 export class SocketDapClient extends EventEmitter {
@@ -67,14 +91,21 @@ export class SocketDapClient extends EventEmitter {
   private options: SocketDapClientOptions;
   private capabilities: Capabilities = {};
   private initialized: boolean = false;
+  private reaped: boolean = false;
+  /** Runs the debuggee when js-debug launches it through runInTerminal */
+  private terminal: TerminalLauncher;
 
   // Store breakpoint configurations for child session
   private breakpointConfigs: SetBreakpointsArguments[] = [];
-  private exceptionFilters: string[] = [];
+  private exceptionBreakpoints: SetExceptionBreakpointsArguments | null = null;
 
   constructor(options: SocketDapClientOptions) {
     super();
     this.options = options;
+    this.terminal = new TerminalLauncher(
+      (body) => this.emit('output', body),
+      options.terminalWrapper
+    );
   }
 
   /**
@@ -90,7 +121,9 @@ export class SocketDapClient extends EventEmitter {
       cwd: this.options.cwd,
       env: { ...process.env, ...this.options.env },
       stdio: ['ignore', 'pipe', 'pipe'],
+      ...processGroupSpawnOptions(),
     });
+    trackProcess(this.process.pid, this.options.command);
 
     // Forward stderr for debugging
     this.process.stderr?.on('data', (chunk: Buffer) => {
@@ -100,6 +133,7 @@ export class SocketDapClient extends EventEmitter {
     // Forward stdout (server may output useful info)
     this.process.stdout?.on('data', (chunk: Buffer) => {
       this.emit('serverOutput', chunk.toString());
+      this.emit('stdout', chunk.toString());
     });
 
     this.process.on('exit', (code, signal) => {
@@ -120,6 +154,7 @@ export class SocketDapClient extends EventEmitter {
       host: this.options.host || 'localhost',
       port: this.options.port,
       requestTimeout: this.options.timeout,
+      pathMapper: this.options.pathMapper,
     });
 
     await this.transport.connect();
@@ -145,6 +180,22 @@ export class SocketDapClient extends EventEmitter {
       this.emit('breakpoint', body);
     });
 
+    this.transport.on('event:process', (body: ProcessEventBody) => {
+      this.emit('process', body);
+    });
+
+    this.transport.on('event:progressStart', (body: ProgressStartEventBody) => {
+      this.emit('progress', 'start', body);
+    });
+
+    this.transport.on('event:progressUpdate', (body: ProgressUpdateEventBody) => {
+      this.emit('progress', 'update', body);
+    });
+
+    this.transport.on('event:progressEnd', (body: ProgressEndEventBody) => {
+      this.emit('progress', 'end', body);
+    });
+
     this.transport.on('event:initialized', () => {
       this.emit('initialized');
     });
@@ -164,10 +215,7 @@ export class SocketDapClient extends EventEmitter {
         // We need to create a new connection for the child session
         const childConfig = request.arguments?.configuration || {};
 
-        if (process.env.DEBUG_DAP) {
-          console.error('[DAP] Creating child session for js-debug');
-          console.error('[DAP] Child config:', JSON.stringify(childConfig));
-        }
+        logger.debug('dap', 'creating child session for js-debug', { config: childConfig });
 
         try {
           // Create child session on a new connection
@@ -176,13 +224,9 @@ export class SocketDapClient extends EventEmitter {
           // Respond with success
           this.transport!.sendResponse(request.seq, 'startDebugging', true);
 
-          if (process.env.DEBUG_DAP) {
-            console.error('[DAP] Child session created successfully');
-          }
+          logger.debug('dap', 'child session created');
         } catch (error) {
-          if (process.env.DEBUG_DAP) {
-            console.error('[DAP] Child session creation failed:', error);
-          }
+          logger.warn('dap', 'child session creation failed', { error: String(error) });
           this.transport!.sendResponse(
             request.seq,
             'startDebugging',
@@ -194,11 +238,16 @@ export class SocketDapClient extends EventEmitter {
       }
     );
 
-    // Forward server output for debugging
-    this.on('serverOutput', (data: string) => {
-      if (process.env.DEBUG) {
-        console.error('[js-debug server]', data);
+    this.transport.on(
+      'reverseRequest:runInTerminal',
+      (request: { seq: number; arguments: RunInTerminalRequestArguments }) => {
+        void this.handleRunInTerminal(this.transport!, request);
       }
+    );
+
+    // Log server output for debugging
+    this.on('serverOutput', (data: string) => {
+      logger.debug('js-debug', data.trimEnd());
     });
 
     this.transport.on('exit', (code: number | null, signal: string | null) => {
@@ -208,6 +257,15 @@ export class SocketDapClient extends EventEmitter {
     this.transport.on('error', (error: Error) => {
       this.emit('error', error);
     });
+
+    // Raw traffic for protocol traces (--artifacts)
+    this.transport.on('sent', (message: ProtocolMessage) => {
+      this.emit('protocolMessage', 'send', message);
+    });
+
+    this.transport.on('message', (message: ProtocolMessage) => {
+      this.emit('protocolMessage', 'receive', message);
+    });
   }
 
   /**
@@ -229,7 +287,8 @@ export class SocketDapClient extends EventEmitter {
       columnsStartAt1: true,
       supportsVariableType: true,
       supportsVariablePaging: true,
-      supportsRunInTerminalRequest: false,
+      supportsRunInTerminalRequest: true,
+      supportsProgressReporting: true,
       ...args,
     });
 
@@ -281,14 +340,40 @@ export class SocketDapClient extends EventEmitter {
     return await this.transport!.sendRequest<SetBreakpointsResponse>('setBreakpoints', args);
   }
 
+  /**
+   * Set instruction breakpoints (js-debug doesn't support them, but the request is passed on)
+   */
+  async setInstructionBreakpoints(
+    args: SetInstructionBreakpointsArguments
+  ): Promise<SetInstructionBreakpointsResponse> {
+    this.ensureInitialized();
+    return await this.getActiveTransport().sendRequest<SetInstructionBreakpointsResponse>(
+      'setInstructionBreakpoints',
+      args
+    );
+  }
+
+  /**
+   * List the possible breakpoint locations in a source range
+   */
+  async breakpointLocations(
+    args: BreakpointLocationsArguments
+  ): Promise<BreakpointLocationsResponse> {
+    this.ensureInitialized();
+    return await this.getActiveTransport().sendRequest<BreakpointLocationsResponse>(
+      'breakpointLocations',
+      args
+    );
+  }
+
   /**
    * Set exception breakpoints
    */
   async setExceptionBreakpoints(args: SetExceptionBreakpointsArguments): Promise<void> {
     this.ensureInitialized();
 
-    // Store exception filters for child session
-    this.exceptionFilters = args.filters || [];
+    // Store exception breakpoints for child session
+    this.exceptionBreakpoints = args;
 
     await this.transport!.sendRequest('setExceptionBreakpoints', args);
   }
@@ -413,9 +498,10 @@ export class SocketDapClient extends EventEmitter {
     } catch {
       // Ignore errors during disconnect
     } finally {
+      // Kill the server process and anything it launched. Reap before closing: once the
+      // server exits, its children are reparented and can no longer be found
+      await this.reapProcessTree();
       this.transport.close();
-      // Kill the server process
-      this.process?.kill();
     }
   }
 
@@ -440,6 +526,14 @@ export class SocketDapClient extends EventEmitter {
     return await this.getActiveTransport().sendRequest<SourceResponse>('source', args);
   }
 
+  /**
+   * Send an adapter-specific request (to the child session when there is one)
+   */
+  async customRequest<T = unknown>(command: string, args?: unknown): Promise<T> {
+    this.ensureInitialized();
+    return await this.getActiveTransport().sendRequest<T>(command, args);
+  }
+
   /**
    * Get the debug adapter capabilities
    */
@@ -454,6 +548,36 @@ export class SocketDapClient extends EventEmitter {
     return this.transport?.isOpen() ?? false;
   }
 
+  /**
+   * Kill whatever remains of the adapter's process tree (debuggee, helper processes).
+   * Safe to call more than once.
+   */
+  async reapProcessTree(): Promise<void> {
+    await this.terminal.reap();
+    const pid = this.process?.pid;
+    if (!pid || this.reaped) return;
+    this.reaped = true;
+    await killProcessTree(pid);
+    untrackProcess(pid);
+  }
+
+  /**
+   * Run the command js-debug would have run in a terminal and report its PID
+   */
+  private async handleRunInTerminal(
+    transport: SocketDapTransport,
+    request: { seq: number; arguments: RunInTerminalRequestArguments }
+  ): Promise<void> {
+    try {
+      const body = await this.terminal.launch(request.arguments);
+      transport.sendResponse(request.seq, 'runInTerminal', true, body);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      logger.warn('dap', 'runInTerminal failed', { error: message });
+      transport.sendResponse(request.seq, 'runInTerminal', false, undefined, message);
+    }
+  }
+
   private ensureConnected(): void {
     if (!this.transport) {
       throw new Error('Not connected. Call connect() first.');
@@ -476,33 +600,26 @@ export class SocketDapClient extends EventEmitter {
       host: this.options.host || 'localhost',
       port: this.options.port,
       requestTimeout: this.options.timeout,
+      pathMapper: this.options.pathMapper,
     });
 
     await this.childTransport.connect();
 
-    if (process.env.DEBUG_DAP) {
-      console.error('[DAP child] Connected to js-debug');
-    }
+    logger.debug('dap', 'child connected to js-debug');
 
     // Forward events from child session to our main event emitter
     this.childTransport.on('event:stopped', (body: StoppedEventBody) => {
-      if (process.env.DEBUG_DAP) {
-        console.error('[DAP child] stopped event:', JSON.stringify(body));
-      }
+      logger.debug('dap', 'child stopped event', { body });
       this.emit('stopped', body);
     });
 
     this.childTransport.on('event:terminated', (body: TerminatedEventBody) => {
-      if (process.env.DEBUG_DAP) {
-        console.error('[DAP child] terminated event');
-      }
+      logger.debug('dap', 'child terminated event');
       this.emit('terminated', body);
     });
 
     this.childTransport.on('event:exited', (body: ExitedEventBody) => {
-      if (process.env.DEBUG_DAP) {
-        console.error('[DAP child] exited event:', body?.exitCode);
-      }
+      logger.debug('dap', 'child exited event', { exitCode: body?.exitCode });
       this.emit('exited', body);
     });
 
@@ -514,16 +631,48 @@ export class SocketDapClient extends EventEmitter {
     });
 
     this.childTransport.on('event:breakpoint', (body: BreakpointEventBody) => {
-      if (process.env.DEBUG_DAP) {
-        console.error('[DAP child] breakpoint event:', JSON.stringify(body));
-      }
+      logger.debug('dap', 'child breakpoint event', { body });
       this.emit('breakpoint', body);
     });
 
+    this.childTransport.on('event:process', (body: ProcessEventBody) => {
+      logger.debug('dap', 'child process event', { body });
+      this.emit('process', body);
+    });
+
+    this.childTransport.on('event:thread', (body: ThreadEventBody) => {
+      this.emit('thread', body);
+    });
+
+    this.childTransport.on('event:progressStart', (body: ProgressStartEventBody) => {
+      this.emit('progress', 'start', body);
+    });
+
+    this.childTransport.on('event:progressUpdate', (body: ProgressUpdateEventBody) => {
+      this.emit('progress', 'update', body);
+    });
+
+    this.childTransport.on('event:progressEnd', (body: ProgressEndEventBody) => {
+      this.emit('progress', 'end', body);
+    });
+
     this.childTransport.on('event', (event: Event) => {
-      if (process.env.DEBUG_DAP) {
-        console.error('[DAP child] event:', event.event);
+      logger.trace('dap', `child event ${event.event}`);
+    });
+
+    this.childTransport.on(
+      'reverseRequest:runInTerminal',
+      (request: { seq: number; arguments: RunInTerminalRequestArguments }) => {
+        void this.handleRunInTerminal(this.childTransport!, request);
       }
+    );
+
+    this.childTransport.on('sent', (message: ProtocolMessage) => {
+      this.emit('protocolMessage', 'send', message, 'child');
+    });
+
+    this.childTransport.on('message', (message: ProtocolMessage) => {
+      this.emit('protocolMessage', 'receive', message, 'child');
     });
 
     // Initialize the child session
@@ -536,13 +685,12 @@ export class SocketDapClient extends EventEmitter {
       columnsStartAt1: true,
       supportsVariableType: true,
       supportsVariablePaging: true,
-      supportsRunInTerminalRequest: false,
+      supportsRunInTerminalRequest: true,
+      supportsProgressReporting: true,
       supportsStartDebuggingRequest: true,
     });
 
-    if (process.env.DEBUG_DAP) {
-      console.error('[DAP child] Initialize response received, waiting for initialized event...');
-    }
+    logger.debug('dap', 'child initialize response received, waiting for initialized event');
 
     // Wait for the initialized event before setting breakpoints
     await new Promise<void>((resolve) => {
@@ -564,37 +712,27 @@ export class SocketDapClient extends EventEmitter {
       }, 500);
     });
 
-    if (process.env.DEBUG_DAP) {
-      console.error('[DAP child] Initialized');
-    }
+    logger.debug('dap', 'child initialized');
 
     // Re-set breakpoints on child session
     for (const bp of this.breakpointConfigs) {
-      if (process.env.DEBUG_DAP) {
-        console.error('[DAP child] Setting breakpoints for:', bp.source?.path);
-      }
+      logger.debug('dap', 'child setting breakpoints', { file: bp.source?.path });
       const response = await this.childTransport.sendRequest<SetBreakpointsResponse>(
         'setBreakpoints',
         bp
       );
-      if (process.env.DEBUG_DAP) {
-        console.error('[DAP child] Breakpoint response:', JSON.stringify(response, null, 2));
-      }
+      logger.debug('dap', 'child breakpoint response', { response });
     }
 
     // Re-set exception breakpoints if any
-    if (this.exceptionFilters.length > 0) {
-      await this.childTransport.sendRequest('setExceptionBreakpoints', {
-        filters: this.exceptionFilters,
-      });
+    if (this.exceptionBreakpoints) {
+      await this.childTransport.sendRequest('setExceptionBreakpoints', this.exceptionBreakpoints);
     }
 
     // Configuration done
     await this.childTransport.sendRequest('configurationDone');
 
-    if (process.env.DEBUG_DAP) {
-      console.error('[DAP child] Configuration done, launching with pendingTargetId');
-    }
+    logger.debug('dap', 'child configuration done, launching with pendingTargetId');
 
     // Launch with the child configuration (includes __pendingTargetId)
     await this.childTransport.sendRequest('launch', {
@@ -603,8 +741,6 @@ export class SocketDapClient extends EventEmitter {
       ...config,
     });
 
-    if (process.env.DEBUG_DAP) {
-      console.error('[DAP child] Launch complete');
-    }
+    logger.debug('dap', 'child launch complete');
   }
 }
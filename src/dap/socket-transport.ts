@@ -7,6 +7,8 @@
 import { Socket, connect } from 'node:net';
 import { EventEmitter } from 'node:events';
 import type { ProtocolMessage, Request, Response, Event } from './protocol.js';
+import { mapIncomingPaths, mapOutgoingPaths, type PathMapper } from './path-mapper.js';
+import { logger } from '../util/logger.js';
 
 const HEADER_DELIMITER = '\r\n\r\n';
 const CONTENT_LENGTH_HEADER = 'Content-Length: ';
@@ -22,6 +24,8 @@ export interface SocketTransportOptions {
   host: string;
   port: number;
   requestTimeout?: number;
+  /** Translates paths for an adapter with a different view of the file system */
+  pathMapper?: PathMapper;
 }
 
 export class SocketDapTransport extends EventEmitter {
@@ -174,14 +178,8 @@ export class SocketDapTransport extends EventEmitter {
       return;
     }
 
-    // Debug logging
-    if (process.env.DEBUG_DAP) {
-      const summary =
-        message.type === 'request'
-          ? `request:${(message as Request).command}`
-          : `response:${(message as Response).command}`;
-      console.error(`[DAP send] ${summary}`);
-    }
+    if (this.options.pathMapper) message = mapOutgoingPaths(message, this.options.pathMapper);
+    logger.trace('dap', 'send', { port: this.options.port, message });
 
     const json = JSON.stringify(message);
     const contentLength = Buffer.byteLength(json, 'utf-8');
@@ -233,16 +231,8 @@ export class SocketDapTransport extends EventEmitter {
   }
 
   private handleMessage(message: ProtocolMessage): void {
-    // Debug logging
-    if (process.env.DEBUG_DAP) {
-      const summary =
-        message.type === 'event'
-          ? `event:${(message as Event).event}`
-          : message.type === 'response'
-            ? `response:${(message as Response).command}:${(message as Response).success}`
-            : `request:${(message as Request).command}`;
-      console.error(`[DAP recv] ${summary}`);
-    }
+    if (this.options.pathMapper) message = mapIncomingPaths(message, this.options.pathMapper);
+    logger.trace('dap', 'recv', { port: this.options.port, message });
 
     this.emit('message', message);
 
@@ -254,13 +244,9 @@ export class SocketDapTransport extends EventEmitter {
         this.handleEvent(message as Event);
         break;
       case 'request':
-        // Log reverse requests in detail for debugging
-        if (process.env.DEBUG_DAP) {
-          console.error(
-            `[DAP reverse request] ${(message as Request).command}:`,
-            JSON.stringify((message as Request).arguments, null, 2)
-          );
-        }
+        logger.debug('dap', `reverse request ${(message as Request).command}`, {
+          arguments: (message as Request).arguments,
+        });
         this.emit('reverseRequest', message as Request);
         this.emit(`reverseRequest:${(message as Request).command}`, message as Request);
         break;
@@ -0,0 +1,244 @@
+/**
+ * runInTerminal Reverse Requests
+ *
+ * Some adapters launch the debuggee by asking the client to run a command in a terminal
+ * (js-debug with console: integratedTerminal, cppdbg). debug-run has no terminal to offer,
+ * so it runs the command itself: under a pseudo-terminal from script(1) on Linux and macOS,
+ * so the program still sees a TTY, and with plain pipes elsewhere. Its stdout and stderr are
+ * reported as output in those categories (under a pseudo-terminal both reach the terminal,
+ * so they arrive as stdout).
+ *
+ * The response carries the program's PID, and script(1)'s as the shell's: the program is
+ * started through sh, which writes its PID to a file before exec'ing it. A command line for
+ * the shell to interpret has no single program, and a sandboxed one has PIDs of its own, so
+ * only the shell's (or sandbox's) PID is reported for those.
+ */
+
+import { spawn, type ChildProcess } from 'node:child_process';
+import { randomUUID } from 'node:crypto';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import { commandExists } from '../adapters/base.js';
+import {
+  killProcessTree,
+  processGroupSpawnOptions,
+  trackProcess,
+  untrackProcess,
+} from '../util/process-tree.js';
+import { logger } from '../util/logger.js';
+import type {
+  OutputEventBody,
+  RunInTerminalRequestArguments,
+  RunInTerminalResponseBody,
+} from './protocol.js';
+
+/** How long to wait for a command under script(1) to report its PID */
+const PID_TIMEOUT_MS = 5000;
+
+export interface TerminalCommand {
+  command: string;
+  args: string[];
+  shell: boolean;
+}
+
+/**
+ * Runs a command inside another, such as a sandbox (--sandbox). env is what the command sets
+ * on top of our environment.
+ */
+export type CommandWrapper = (
+  command: string,
+  args: string[],
+  env?: Record<string, string | null>
+) => { command: string; args: string[] };
+
+/**
+ * Quote an argument for a POSIX shell
+ */
+export function quoteShellArg(arg: string): string {
+  return /^[\w@%+=:,./-]+$/.test(arg) ? arg : `'${arg.replace(/'/g, `'\\''`)}'`;
+}
+
+/**
+ * A command that writes its PID to a file and then becomes the given one (keeping the PID)
+ */
+export function reportPidArgs(args: string[], pidFile: string): string[] {
+  return ['sh', '-c', 'echo $$ >"$0"; exec "$@"', pidFile, ...args];
+}
+
+/**
+ * Wait for a PID written by reportPidArgs, until the command exits or PID_TIMEOUT_MS passes
+ */
+async function readReportedPid(pidFile: string, child: ChildProcess): Promise<number | undefined> {
+  const deadline = Date.now() + PID_TIMEOUT_MS;
+  for (;;) {
+    try {
+      const pid = parseInt(await fs.promises.readFile(pidFile, 'utf-8'), 10);
+      if (pid > 0) return pid;
+    } catch {
+      // Not written yet
+    }
+    if (child.exitCode !== null || child.signalCode !== null || Date.now() > deadline) {
+      return undefined;
+    }
+    await new Promise((resolve) => setTimeout(resolve, 10));
+  }
+}
+
+/**
+ * Work out how to spawn a runInTerminal command, under script(1) when usePty is set
+ */
+export function buildTerminalCommand(
+  request: Pick<RunInTerminalRequestArguments, 'args' | 'argsCanBeInterpretedByShell'>,
+  usePty: boolean,
+  platform: NodeJS.Platform = process.platform
+): TerminalCommand {
+  const { args } = request;
+  if (args.length === 0) {
+    throw new Error('runInTerminal request has no command');
+  }
+  const line = request.argsCanBeInterpretedByShell
+    ? args.join(' ')
+    : args.map(quoteShellArg).join(' ');
+
+  if (usePty && platform === 'linux') {
+    // -e passes the command's exit code through, -f flushes output as it's written
+    return { command: 'script', args: ['-qfec', line, '/dev/null'], shell: false };
+  }
+  if (usePty && platform === 'darwin') {
+    const command = request.argsCanBeInterpretedByShell ? ['sh', '-c', line] : args;
+    return { command: 'script', args: ['-q', '/dev/null', ...command], shell: false };
+  }
+  if (request.argsCanBeInterpretedByShell) {
+    return { command: line, args: [], shell: true };
+  }
+  return { command: args[0], args: args.slice(1), shell: false };
+}
+
+/**
+ * A runInTerminal command run inside a wrapper; a shell command line goes through sh -c
+ */
+export function wrapTerminalCommand(
+  terminal: TerminalCommand,
+  wrap: CommandWrapper,
+  env?: RunInTerminalRequestArguments['env']
+): TerminalCommand {
+  const { command, args } = terminal.shell
+    ? wrap('sh', ['-c', terminal.command], env)
+    : wrap(terminal.command, terminal.args, env);
+  return { command, args, shell: false };
+}
+
+/**
+ * The debuggee's environment: ours plus the request's, where null removes a variable
+ */
+export function buildTerminalEnv(
+  env: RunInTerminalRequestArguments['env'] = {}
+): NodeJS.ProcessEnv {
+  const result: NodeJS.ProcessEnv = { ...process.env };
+  for (const [name, value] of Object.entries(env)) {
+    if (value === null) {
+      delete result[name];
+    } else {
+      result[name] = value;
+    }
+  }
+  return result;
+}
+
+/**
+ * Runs the commands an adapter asks for and cleans them up with the session
+ */
+export class TerminalLauncher {
+  private processes = new Set<ChildProcess>();
+  private onOutput: (body: OutputEventBody) => void;
+  private usePty: Promise<boolean> | null = null;
+  private wrap?: CommandWrapper;
+
+  constructor(onOutput: (body: OutputEventBody) => void, wrap?: CommandWrapper) {
+    this.onOutput = onOutput;
+    this.wrap = wrap;
+  }
+
+  /**
+   * Start the command and report its PID (and script(1)'s) once it's running
+   */
+  async launch(request: RunInTerminalRequestArguments): Promise<RunInTerminalResponseBody> {
+    this.usePty ??= this.detectPty();
+    const usePty = await this.usePty;
+    // A sandbox has its own /tmp and PIDs, so only the wrapper's PID is known there
+    const pidFile =
+      usePty && !request.argsCanBeInterpretedByShell && !this.wrap
+        ? path.join(os.tmpdir(), `debug-run-${randomUUID()}.pid`)
+        : undefined;
+    const terminal = buildTerminalCommand(
+      pidFile ? { ...request, args: reportPidArgs(request.args, pidFile) } : request,
+      usePty
+    );
+    const { command, args, shell } = this.wrap
+      ? wrapTerminalCommand(terminal, this.wrap, request.env)
+      : terminal;
+
+    const child = spawn(command, args, {
+      cwd: request.cwd || undefined,
+      env: buildTerminalEnv(request.env),
+      stdio: ['ignore', 'pipe', 'pipe'],
+      shell,
+      ...processGroupSpawnOptions(),
+    });
+    await new Promise<void>((resolve, reject) => {
+      child.once('spawn', resolve);
+      child.once('error', reject);
+    });
+    trackProcess(child.pid, request.args[0]);
+    this.processes.add(child);
+    logger.debug('dap', 'started runInTerminal command', {
+      command: request.args.join(' '),
+      pid: child.pid,
+      pty: usePty,
+    });
+
+    const forward = (category: 'stdout' | 'stderr') => (chunk: Buffer) => {
+      // A pseudo-terminal turns every \n into \r\n
+      const output = usePty ? chunk.toString().replace(/\r\n/g, '\n') : chunk.toString();
+      this.onOutput({ category, output });
+    };
+    child.stdout?.on('data', forward('stdout'));
+    child.stderr?.on('data', forward('stderr'));
+    child.on('exit', () => {
+      this.processes.delete(child);
+      untrackProcess(child.pid);
+      if (pidFile) fs.rmSync(pidFile, { force: true });
+    });
+
+    if (pidFile) {
+      const processId = await readReportedPid(pidFile, child);
+      fs.rmSync(pidFile, { force: true });
+      return { processId, shellProcessId: child.pid };
+    }
+    if (request.argsCanBeInterpretedByShell || this.wrap) {
+      return { shellProcessId: child.pid };
+    }
+    return { processId: child.pid };
+  }
+
+  /**
+   * Kill every command still running (and anything it started)
+   */
+  async reap(): Promise<void> {
+    const children = [...this.processes];
+    this.processes.clear();
+    await Promise.all(
+      children.map(async (child) => {
+        if (child.pid === undefined) return;
+        await killProcessTree(child.pid);
+        untrackProcess(child.pid);
+      })
+    );
+  }
+
+  private async detectPty(): Promise<boolean> {
+    if (process.platform !== 'linux' && process.platform !== 'darwin') return false;
+    return (await commandExists('script')) !== null;
+  }
+}
@@ -11,6 +11,8 @@
 import type { ChildProcess } from 'node:child_process';
 import { EventEmitter } from 'node:events';
 import type { ProtocolMessage, Request, Response, Event } from './protocol.js';
+import { mapIncomingPaths, mapOutgoingPaths, type PathMapper } from './path-mapper.js';
+import { logger } from '../util/logger.js';
 
 const HEADER_DELIMITER = '\r\n\r\n';
 const CONTENT_LENGTH_HEADER = 'Content-Length: ';
@@ -29,11 +31,13 @@ export class DapTransport extends EventEmitter {
   private seq: number = 1;
   private requestTimeout: number;
   private closed: boolean = false;
+  private pathMapper?: PathMapper;
 
-  constructor(process: ChildProcess, requestTimeout: number = 30000) {
+  constructor(process: ChildProcess, requestTimeout: number = 30000, pathMapper?: PathMapper) {
     super();
     this.process = process;
     this.requestTimeout = requestTimeout;
+    this.pathMapper = pathMapper;
 
     if (!process.stdout || !process.stdin) {
       throw new Error('Process must have stdout and stdin');
@@ -125,13 +129,12 @@ export class DapTransport extends EventEmitter {
       return;
     }
 
+    if (this.pathMapper) message = mapOutgoingPaths(message, this.pathMapper);
     const json = JSON.stringify(message);
     const contentLength = Buffer.byteLength(json, 'utf-8');
     const header = `${CONTENT_LENGTH_HEADER}${contentLength}${HEADER_DELIMITER}`;
 
-    if (process.env.DEBUG_DAP) {
-      console.error(`[DAP TX] ${json}`);
-    }
+    logger.trace('dap', 'TX', { message });
 
     this.process.stdin.write(header + json);
     this.emit('sent', message);
@@ -184,9 +187,8 @@ export class DapTransport extends EventEmitter {
   }
 
   private handleMessage(message: ProtocolMessage): void {
-    if (process.env.DEBUG_DAP) {
-      console.error(`[DAP RX] ${JSON.stringify(message)}`);
-    }
+    if (this.pathMapper) message = mapIncomingPaths(message, this.pathMapper);
+    logger.trace('dap', 'RX', { message });
     this.emit('message', message);
 
     switch (message.type) {
@@ -0,0 +1,149 @@
+/**
+ * Session Artifacts (--artifacts)
+ *
+ * Writes everything needed to investigate a run after the fact into one session-stamped
+ * folder, ready to upload as a CI artifact bundle when a debugging job fails:
+ *
+ *   <dir>/<name>-<timestamp>/
+ *     events.ndjson                  every event, before --include/--exclude filtering
+ *     dap.ndjson                     every DAP message to and from the adapter
+ *     program.stdout.log             the program's stdout, as-is
+ *     program.stderr.log             the program's stderr, as-is
+ *     debug-console.log              the adapter's debug console output
+ *     adapter.stderr.log             the adapter's own stderr
+ *     adapter.stdout.log             the adapter's own stdout (socket adapters like js-debug)
+ *     stops/001-breakpoint_hit.json  one file per stop snapshot
+ *
+ * Files are only created once there is something to write to them.
+ */
+
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+import type { ProtocolMessage } from '../dap/protocol.js';
+import type { DebugEvent } from './events.js';
+import { formatRunTimestamp, sanitizeRunName } from './result-files.js';
+import { logger } from '../util/logger.js';
+
+/** Events that capture program state at a stop, each written to stops/ */
+const STOP_EVENT_TYPES: ReadonlySet<DebugEvent['type']> = new Set([
+  'breakpoint_hit',
+  'exception_thrown',
+  'step_completed',
+  'assertion_failed',
+  'trace_completed',
+  'interrupt_snapshot',
+  'crash_report',
+  'signal_received',
+]);
+
+const PROGRAM_OUTPUT_FILES = {
+  stdout: 'program.stdout.log',
+  stderr: 'program.stderr.log',
+  console: 'debug-console.log',
+} as const;
+
+export class SessionArtifacts {
+  readonly dir: string;
+  private streams = new Map<string, fs.WriteStream>();
+  private stopCount = 0;
+
+  private constructor(dir: string) {
+    this.dir = dir;
+  }
+
+  /**
+   * Create <parent>/<name>-<timestamp>/ (with a numeric suffix if it already exists)
+   */
+  static create(parent: string, name: string, now: Date = new Date()): SessionArtifacts {
+    fs.mkdirSync(parent, { recursive: true });
+    const base = path.join(parent, `${sanitizeRunName(name)}-${formatRunTimestamp(now)}`);
+    for (let n = 1; ; n++) {
+      const dir = n === 1 ? base : `${base}-${n}`;
+      try {
+        fs.mkdirSync(dir);
+        return new SessionArtifacts(dir);
+      } catch (error) {
+        if ((error as NodeJS.ErrnoException).code !== 'EEXIST') throw error;
+      }
+    }
+  }
+
+  /**
+   * Record a session event (register with OutputFormatter.onEvent)
+   */
+  observe(event: DebugEvent): void {
+    this.write('events.ndjson', JSON.stringify(event) + '\n');
+
+    if (event.type === 'program_output') {
+      this.write(PROGRAM_OUTPUT_FILES[event.category], event.output);
+    } else if (STOP_EVENT_TYPES.has(event.type)) {
+      this.stopCount++;
+      const file = `${String(this.stopCount).padStart(3, '0')}-${event.type}.json`;
+      this.writeFile(path.join('stops', file), JSON.stringify(event, null, 2) + '\n');
+    }
+  }
+
+  /**
+   * Record a DAP message sent to or received from the adapter
+   */
+  protocolMessage(
+    direction: 'send' | 'receive',
+    message: ProtocolMessage,
+    session?: string
+  ): void {
+    const record = {
+      ts: new Date().toISOString(),
+      direction,
+      ...(session && { session }),
+      message,
+    };
+    this.write('dap.ndjson', JSON.stringify(record) + '\n');
+  }
+
+  /**
+   * Record output the adapter wrote to its stderr
+   */
+  adapterStderr(data: string): void {
+    this.write('adapter.stderr.log', data);
+  }
+
+  /**
+   * Record output the adapter wrote to its stdout (when that isn't the DAP stream)
+   */
+  adapterStdout(data: string): void {
+    this.write('adapter.stdout.log', data);
+  }
+
+  /**
+   * Flush and close every file
+   */
+  async close(): Promise<void> {
+    const streams = [...this.streams.values()];
+    this.streams.clear();
+    await Promise.all(streams.map((stream) => new Promise<void>((resolve) => stream.end(resolve))));
+  }
+
+  private write(file: string, data: string): void {
+    let stream = this.streams.get(file);
+    if (!stream) {
+      stream = fs.createWriteStream(path.join(this.dir, file));
+      stream.on('error', (error) => {
+        logger.warn('artifacts', 'failed to write artifact', { file, error: error.message });
+      });
+      this.streams.set(file, stream);
+    }
+    stream.write(data);
+  }
+
+  private writeFile(file: string, data: string): void {
+    try {
+      fs.mkdirSync(path.dirname(path.join(this.dir, file)), { recursive: true });
+      fs.writeFileSync(path.join(this.dir, file), data);
+    } catch (error) {
+      logger.warn('artifacts', 'failed to write artifact', {
+        file,
+        error: error instanceof Error ? error.message : String(error),
+      });
+    }
+  }
+}
@@ -0,0 +1,286 @@
+/**
+ * Expected-Value Baselines (--expect baseline.json)
+ *
+ * Turns a debug session into a runtime regression test: the locals and evaluations captured
+ * at each stop are compared with a checked-in baseline, and any difference fails the run
+ * with a readable diff. Values are compared as text, the way --export-values writes them,
+ * except that numbers may differ by rounding and the baseline's rules can loosen the match
+ * for values that change between runs (floats, timestamps, IDs).
+ *
+ *   {
+ *     "rules": { "*.price": { "within": 0.01 }, "order.createdAt": "timestamp", "id": "ignore" },
+ *     "stops": [{ "event": "breakpoint_hit", "location": "src/cart.ts:42",
+ *                 "locals": { "total": "42.5" }, "evaluations": { "cart.items.length": "3" } }]
+ *   }
+ *
+ * --expect-update writes the session's values as the baseline, keeping its rules.
+ */
+
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+import type { DebugEvent } from './events.js';
+import { evaluationRows, flattenValue, isStopEvent, stopLocation } from './value-export.js';
+
+/**
+ * How a value may differ from the baseline: not compared at all, any date/time, or a
+ * number within an absolute or relative distance
+ */
+export type ToleranceRule = 'ignore' | 'timestamp' | { within: number } | { relative: number };
+
+export interface BaselineStop {
+  /** breakpoint_hit, exception_thrown, ... */
+  event: string;
+  /** file:line, the file relative to the working directory */
+  location: string;
+  label?: string;
+  /** Locals, flattened to their members (order.Total, items[0]) */
+  locals: Record<string, string>;
+  evaluations: Record<string, string>;
+}
+
+export interface Baseline {
+  /** Tolerance rules by value name; * matches any characters */
+  rules?: Record<string, ToleranceRule>;
+  stops: BaselineStop[];
+}
+
+export interface BaselineDifference {
+  /** The stop's number in the session (1, 2, ...) */
+  stop: number;
+  /** Where the stop happened, in the run or else in the baseline */
+  location: string;
+  /** What differs: the stop itself (missing, unexpected, elsewhere) or one of its values */
+  kind: 'stop' | 'local' | 'evaluation';
+  name?: string;
+  expected?: string;
+  actual?: string;
+  /** Extra explanation, e.g. a rule that would make the values match */
+  hint?: string;
+}
+
+/** Relative difference allowed between numbers without a rule (float rounding) */
+const NUMBER_EPSILON = 1e-9;
+
+const ISO_TIMESTAMP = /^\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}(:\d{2}(\.\d+)?)?(Z|[+-]\d{2}:?\d{2})?$/;
+
+/**
+ * Collects the values a baseline compares from a session's events (register with
+ * OutputFormatter.onEvent)
+ */
+export class BaselineRecorder {
+  readonly stops: BaselineStop[] = [];
+
+  constructor(private cwd: string = process.cwd()) {}
+
+  observe(event: DebugEvent): void {
+    if (!isStopEvent(event)) return;
+    const location = stopLocation(event);
+    const file = path.isAbsolute(location.file)
+      ? path.relative(this.cwd, location.file)
+      : location.file;
+    const stop: BaselineStop = {
+      event: event.type,
+      location: `${file.split(path.sep).join('/')}:${location.line}`,
+      ...('label' in event && event.label && { label: event.label }),
+      locals: {},
+      evaluations: {},
+    };
+    for (const [name, variable] of Object.entries(event.locals)) {
+      for (const row of flattenValue(name, variable)) stop.locals[row.name] = row.value;
+    }
+    const evaluations = 'evaluations' in event ? event.evaluations : undefined;
+    for (const [expression, evaluation] of Object.entries(evaluations ?? {})) {
+      for (const row of evaluationRows(expression, evaluation)) {
+        stop.evaluations[row.name] = row.value;
+      }
+    }
+    this.stops.push(stop);
+  }
+}
+
+function isRule(rule: unknown): rule is ToleranceRule {
+  if (rule === 'ignore' || rule === 'timestamp') return true;
+  if (typeof rule !== 'object' || rule === null) return false;
+  const [key, ...rest] = Object.keys(rule);
+  const distance = (rule as Record<string, unknown>)[key];
+  return (
+    rest.length === 0 &&
+    (key === 'within' || key === 'relative') &&
+    typeof distance === 'number' &&
+    distance >= 0
+  );
+}
+
+/**
+ * Read a baseline file
+ *
+ * @throws Error if it can't be read or isn't a baseline
+ */
+export function readBaseline(file: string): Baseline {
+  let data: unknown;
+  try {
+    data = JSON.parse(fs.readFileSync(file, 'utf8'));
+  } catch (error) {
+    const reason = (error as NodeJS.ErrnoException).code === 'ENOENT' ? 'not found' : error;
+    throw new Error(`Cannot read baseline ${file}: ${reason} (create it with --expect-update)`);
+  }
+  const baseline = data as Baseline;
+  if (typeof data !== 'object' || data === null || !Array.isArray(baseline.stops)) {
+    throw new Error(`Invalid baseline ${file}: expected an object with a "stops" array`);
+  }
+  for (const [pattern, rule] of Object.entries(baseline.rules ?? {})) {
+    if (!isRule(rule)) {
+      throw new Error(
+        `Invalid baseline rule "${pattern}" in ${file}: use "ignore", "timestamp", ` +
+          '{ "within": <number> } or { "relative": <number> }'
+      );
+    }
+  }
+  return baseline;
+}
+
+/**
+ * Write the recorded stops as the baseline, keeping the rules of the one it replaces
+ */
+export function writeBaseline(
+  file: string,
+  stops: BaselineStop[],
+  rules?: Baseline['rules']
+): void {
+  const baseline: Baseline = { ...(rules && { rules }), stops };
+  fs.writeFileSync(file, JSON.stringify(baseline, null, 2) + '\n');
+}
+
+function globPattern(pattern: string): RegExp {
+  const escaped = pattern.replace(/[.+?^${}()|[\]\\]/g, '\\$&').replace(/\*/g, '.*');
+  return new RegExp(`^${escaped}$`);
+}
+
+function toNumber(text: string): number | undefined {
+  const number = text.trim() === '' ? NaN : Number(text);
+  return Number.isFinite(number) ? number : undefined;
+}
+
+function isTimestamp(text: string): boolean {
+  return ISO_TIMESTAMP.test(text.trim()) && !Number.isNaN(Date.parse(text));
+}
+
+/**
+ * Whether a value matches its baseline under the rule for it (exact text, or numbers equal
+ * up to rounding, without one)
+ */
+export function valuesMatch(expected: string, actual: string, rule?: ToleranceRule): boolean {
+  if (rule === 'ignore' || expected === actual) return true;
+  if (rule === 'timestamp') return isTimestamp(expected) && isTimestamp(actual);
+  const a = toNumber(expected);
+  const b = toNumber(actual);
+  if (a === undefined || b === undefined) return false;
+  const difference = Math.abs(a - b);
+  if (rule && 'within' in rule) return difference <= rule.within;
+  const relative = rule && 'relative' in rule ? rule.relative : NUMBER_EPSILON;
+  return difference <= relative * Math.max(Math.abs(a), Math.abs(b));
+}
+
+/**
+ * Differences between the session's stops and the baseline, stop by stop in order
+ */
+export function compareToBaseline(baseline: Baseline, stops: BaselineStop[]): BaselineDifference[] {
+  const rules = Object.entries(baseline.rules ?? {}).map(
+    ([pattern, rule]) => [globPattern(pattern), rule] as const
+  );
+  const ruleFor = (name: string) => rules.find(([pattern]) => pattern.test(name))?.[1];
+  const differences: BaselineDifference[] = [];
+
+  const count = Math.max(baseline.stops.length, stops.length);
+  for (let index = 0; index < count; index++) {
+    const expected = baseline.stops[index];
+    const actual = stops[index];
+    const stop = index + 1;
+    const where = (entry: BaselineStop) => `${entry.event} at ${entry.location}`;
+    if (!actual || !expected) {
+      differences.push({
+        stop,
+        location: (actual ?? expected)!.location,
+        kind: 'stop',
+        ...(expected && { expected: where(expected) }),
+        ...(actual && { actual: where(actual) }),
+      });
+      continue;
+    }
+    if (
+      expected.event !== actual.event ||
+      expected.location !== actual.location ||
+      (expected.label ?? '') !== (actual.label ?? '')
+    ) {
+      differences.push({
+        stop,
+        location: actual.location,
+        kind: 'stop',
+        expected: where(expected) + (expected.label ? ` #${expected.label}` : ''),
+        actual: where(actual) + (actual.label ? ` #${actual.label}` : ''),
+      });
+      continue;
+    }
+
+    for (const kind of ['local', 'evaluation'] as const) {
+      const field = kind === 'local' ? 'locals' : 'evaluations';
+      const want = expected[field] ?? {};
+      const got = actual[field];
+      for (const name of new Set([...Object.keys(want), ...Object.keys(got)])) {
+        const rule = ruleFor(name);
+        if (rule === 'ignore') continue;
+        const wanted = Object.hasOwn(want, name) ? want[name] : undefined;
+        const value = Object.hasOwn(got, name) ? got[name] : undefined;
+        let hint: string | undefined;
+        if (wanted !== undefined && value !== undefined) {
+          if (valuesMatch(wanted, value, rule)) continue;
+          if (!rule && isTimestamp(wanted) && isTimestamp(value)) {
+            hint = `add the rule "${name}": "timestamp" to accept any time`;
+          }
+        }
+        differences.push({
+          stop,
+          location: actual.location,
+          kind,
+          name,
+          ...(wanted !== undefined && { expected: wanted }),
+          ...(value !== undefined && { actual: value }),
+          ...(hint && { hint }),
+        });
+      }
+    }
+  }
+  return differences;
+}
+
+/**
+ * The differences as a readable report, grouped by stop
+ */
+export function formatBaselineDiff(file: string, differences: BaselineDifference[]): string {
+  const noun = differences.length === 1 ? 'difference' : 'differences';
+  const lines = [`Expectation failed: ${differences.length} ${noun} from ${file}`];
+  let lastStop = 0;
+  for (const difference of differences) {
+    if (difference.kind === 'stop') {
+      const what =
+        difference.expected === undefined
+          ? 'unexpected stop'
+          : difference.actual === undefined
+            ? 'missing stop'
+            : 'different stop';
+      lines.push(`  stop ${difference.stop}: ${what}`);
+    } else {
+      if (difference.stop !== lastStop) {
+        lines.push(`  stop ${difference.stop} (${difference.location})`);
+      }
+      const label = difference.kind === 'evaluation' ? 'eval ' : '';
+      lines.push(`    ${label}${difference.name}`);
+    }
+    lastStop = difference.stop;
+    const indent = difference.kind === 'stop' ? '    ' : '      ';
+    lines.push(`${indent}expected: ${difference.expected ?? '(none)'}`);
+    lines.push(`${indent}actual:   ${difference.actual ?? '(none)'}`);
+    if (difference.hint) lines.push(`${indent}hint: ${difference.hint}`);
+  }
+  return lines.join('\n');
+}
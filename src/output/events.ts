@@ -15,6 +15,8 @@ export interface SourceLocation {
   sourceReference?: number;
   /** Name hint for generated sources */
   sourceName?: string;
+  /** Instruction address, for native frames (e.g., without line info) */
+  address?: string;
 }
 
 // Variable representation
@@ -27,6 +29,33 @@ export interface VariableValue {
   circular?: boolean;
   /** True if this is a reference to a previously-seen object with identical content */
   deduplicated?: boolean;
+  /** True if --capture-budget ran out before this value's children were fetched */
+  truncated?: boolean;
+  /** Where the value was read from (--provenance) */
+  provenance?: VariableProvenance;
+}
+
+/**
+ * Where a captured variable came from, for building follow-up requests. References are only
+ * valid while the program is stopped where the value was captured.
+ */
+export interface VariableProvenance {
+  /** Stack frame index, 0 being the frame the program stopped in */
+  frameIndex: number;
+  /** Scope the variable was read from (e.g., "Local: calculate", "Arguments") */
+  scope: string;
+  /** variablesReference of the scope, then of each container down to the variable */
+  referencePath: number[];
+  /** An expression for the variable, when the adapter provides one (DAP evaluateName) */
+  evaluateName?: string;
+}
+
+/** DAP's VariablePresentationHint */
+export interface ValuePresentationHint {
+  kind?: string;
+  attributes?: string[];
+  visibility?: string;
+  lazy?: boolean;
 }
 
 // Variable change for semantic diffing
@@ -38,6 +67,27 @@ export interface VariableChange {
 }
 
 // Stack frame in output
+/** Result of an --eval expression at a stop */
+export interface EvaluationResult {
+  result: string;
+  type?: string;
+  /** Set when the result has children; expand with --eval-expand or over serve */
+  variablesReference?: number;
+  namedVariables?: number;
+  indexedVariables?: number;
+  /** How the adapter suggests showing the result (kind, attributes such as readOnly) */
+  presentationHint?: ValuePresentationHint;
+  /** Address of the result's memory, for readMemory or --watch-memory */
+  memoryReference?: string;
+  /** The result's children, expanded --eval-expand levels deep like locals */
+  value?: unknown;
+  error?: string;
+  /** Set when --eval-safe rejected the expression because it could have side effects */
+  sideEffect?: boolean;
+  /** Set when --capture-budget ran out before the result (or its expansion) came back */
+  truncated?: boolean;
+}
+
 export interface StackFrameInfo {
   frameId: number;
   function: string;
@@ -49,6 +99,16 @@ export interface StackFrameInfo {
   sourceReference?: number;
   /** Name hint for generated sources */
   sourceName?: string;
+  /** Argument values by parameter name, as the adapter displays them (--capture-args) */
+  args?: Record<string, string>;
+}
+
+// Source lines around a stop location (--include-source)
+export interface SourceSnippet {
+  startLine: number;
+  endLine: number;
+  /** Lines formatted as "> 42 | code", with ">" marking the stopped line */
+  lines: string[];
 }
 
 // Base event type
@@ -67,10 +127,81 @@ export interface SessionStartEvent extends BaseEvent {
   cwd?: string;
   /** Process ID (attach mode) */
   pid?: number;
+  /** Inspector port (attach mode with --discover) */
+  port?: number;
   /** True if attaching to a running process */
   attach?: boolean;
 }
 
+/**
+ * One breakpoint hit in the order it happened
+ */
+export interface HitPathEntry {
+  /** 1-based position in the hit sequence */
+  hit: number;
+  /** Breakpoint ID reported by the adapter */
+  breakpointId?: number;
+  threadId: number;
+  location: SourceLocation;
+  timestamp: string;
+}
+
+/**
+ * Why a session ended
+ * - exited: the program ran to completion (or exited on its own)
+ * - crashed: the program crashed under --until-crash
+ * - assertion_failed: an --assert expression failed
+ * - max_hits: --max-hits breakpoint hits were captured
+ * - timeout: the session hit --timeout
+ * - interrupted: Ctrl+C or SIGTERM ended the session
+ * - stopped: the session was ended on request (serve mode)
+ * - adapter_exited: the debug adapter exited before the program did
+ * - error: the session failed (e.g., the adapter could not launch the program)
+ */
+export type TerminationReason =
+  | 'exited'
+  | 'crashed'
+  | 'assertion_failed'
+  | 'max_hits'
+  | 'timeout'
+  | 'interrupted'
+  | 'stopped'
+  | 'adapter_exited'
+  | 'error';
+
+export interface TerminationInfo {
+  reason: TerminationReason;
+  /** Exit code the adapter reported, null if the program didn't exit on its own */
+  exitCode: number | null;
+  /** Signal that killed the program, when the adapter reports one (native crashes) */
+  signal?: string;
+  timedOut: boolean;
+  /** True if debug-run terminated the program rather than it exiting on its own */
+  killed: boolean;
+  wallTimeMs: number;
+  /** User + system CPU time of the program (Linux and macOS, launch and attach by PID) */
+  cpuTimeMs?: number;
+}
+
+/**
+ * Statistics of an --aggregate expression. count is the hits the expression evaluated at;
+ * numeric stats cover the values that parsed as numbers (omitted when none did)
+ */
+export interface AggregateSummary {
+  expression: string;
+  count: number;
+  /** Hits where evaluation failed (omitted when 0) */
+  errors?: number;
+  /** Values that weren't numbers (omitted when 0) */
+  nonNumeric?: number;
+  min?: number;
+  max?: number;
+  mean?: number;
+  sum?: number;
+  /** Number of distinct values */
+  distinct?: number;
+}
+
 export interface SessionEndEvent extends BaseEvent {
   type: 'session_end';
   summary: {
@@ -79,6 +210,38 @@ export interface SessionEndEvent extends BaseEvent {
     breakpointsHit: number;
     exceptionsCaught: number;
     stepsExecuted: number;
+    /** Time spent in each session phase (connecting, launching, running, ...), in ms */
+    phases?: Record<string, number>;
+    /** Progress the adapter reported, such as js-debug loading source maps (omitted if none) */
+    progress?: ProgressRecord[];
+    /** Every thread seen, in the order they appeared (omitted for single-threaded runs) */
+    threads?: ThreadLifetime[];
+    /** Modules loaded by the end of the session, in load order (--modules) */
+    modules?: ModuleInfo[];
+    /** Source files that differ from what the program is running (omitted when none) */
+    staleSources?: string[];
+    /** js-debug skipFiles globs used for the launch; frames in them are left out of stacks */
+    skipFiles?: string[];
+    /** Each --watch-memory region: first and last value seen and how often it changed */
+    memory?: MemoryWatchSummary[];
+    /** Times the program was relaunched (omitted when 0) */
+    restarts?: number;
+    /** Statistics of each --aggregate expression over the breakpoint hits */
+    aggregates?: AggregateSummary[];
+    /**
+     * Breakpoint stops resumed by --continue-if, --skip-to-hit or a hit window without a
+     * breakpoint_hit (omitted when 0)
+     */
+    breakpointsSkipped?: number;
+    /** Ordered breakpoint hits (omitted when no breakpoint was hit) */
+    path?: HitPathEntry[];
+    /** Round trips per DAP command, the most time-consuming first (--request-stats) */
+    requests?: RequestStatsRecord[];
+    /** True if the session ended because --max-hits was reached */
+    maxHitsReached?: boolean;
+    /** True if the session ended on a crash (--until-crash) */
+    crashed?: boolean;
+    termination: TerminationInfo;
   };
 }
 
@@ -93,6 +256,29 @@ export interface ProcessAttachedEvent extends BaseEvent {
   pid: number;
 }
 
+/** The program is held at entry until --trigger-file or --trigger-signal fires */
+export interface TriggerWaitingEvent extends BaseEvent {
+  type: 'trigger_waiting';
+  threadId: number;
+  location: SourceLocation;
+  file?: string;
+  signal?: string;
+}
+
+export interface TriggerFiredEvent extends BaseEvent {
+  type: 'trigger_fired';
+  source: 'file' | 'signal';
+  /** How long the program was held at entry */
+  waitedMs: number;
+}
+
+/** The native adapter attached to the program for --native-break (mixed mode) */
+export interface NativeAttachedEvent extends BaseEvent {
+  type: 'native_attached';
+  adapter: string;
+  pid: number;
+}
+
 export interface ProcessExitedEvent extends BaseEvent {
   type: 'process_exited';
   exitCode: number;
@@ -121,21 +307,273 @@ export interface BreakpointSetEvent extends BaseEvent {
   id: number;
   file: string;
   line: number;
+  /** Column, for breakpoints set with one (file:line:column) */
+  column?: number;
   verified: boolean;
   condition?: string;
   message?: string;
+  /** The breakpoint's label (-b "file:line#label") */
+  label?: string;
   /** Diagnostics with actionable suggestions when verified is false */
   diagnostics?: BreakpointDiagnostics;
 }
 
+/**
+ * Breakpoints in a file the adapter didn't verify, reported as soon as its setBreakpoints
+ * response arrives; they may still bind once the code they're in loads (breakpoint_verified)
+ */
+export interface BreakpointWarningEvent extends BaseEvent {
+  type: 'breakpoint_warning';
+  file: string;
+  breakpoints: { id: number; line: number; message?: string }[];
+  message: string;
+  /** Actionable suggestions, for the first adapter message given */
+  suggestions: string[];
+}
+
+/** A breakpoint the adapter verified after it was set (its code loaded later) */
+export interface BreakpointVerifiedEvent extends BaseEvent {
+  type: 'breakpoint_verified';
+  id: number;
+  file: string;
+  line: number;
+  column?: number;
+  label?: string;
+}
+
+export interface BreakpointsExpandedEvent extends BaseEvent {
+  type: 'breakpoints_expanded';
+  /** The -b glob pattern as given */
+  pattern: string;
+  /** The breakpoints it expanded to (file:line...) */
+  breakpoints: string[];
+}
+
+/** The program printed the --arm-on-output line; its breakpoints are being set */
+export interface BreakpointsArmedEvent extends BaseEvent {
+  type: 'breakpoints_armed';
+  /** The --arm-on-output pattern */
+  pattern: string;
+  /** The output line that matched it */
+  line: string;
+}
+
+export interface InstructionBreakpointSetEvent extends BaseEvent {
+  type: 'instruction_breakpoint_set';
+  id: number;
+  /** Instruction address as given (lowercase hex) */
+  address: string;
+  verified: boolean;
+  condition?: string;
+  hitCondition?: string;
+  message?: string;
+  /** Source location of the address (file:line), when the adapter has line info */
+  location?: string;
+}
+
+/** An object that keeps memory alive, from a heap snapshot */
+export interface HeapRetainer {
+  name: string;
+  /** V8 node type (object, closure, array, string, ...) */
+  type: string;
+  /** Heap snapshot object ID (@id in DevTools) */
+  id: number;
+  selfSize: number;
+  /** Memory that would be freed if this object were collected */
+  retainedSize: number;
+}
+
+export interface HeapConstructorSummary {
+  /** Constructor name, or "(type)" for non-objects such as strings and closures */
+  name: string;
+  count: number;
+  selfSize: number;
+}
+
+/** Heap snapshot written at a breakpoint hit (--heap-snapshot) */
+export interface HeapSnapshotInfo {
+  /** Path of the .heapsnapshot file (opens in Chrome DevTools' Memory tab) */
+  file: string;
+  /** Total size of reachable objects in bytes */
+  totalSize?: number;
+  nodeCount?: number;
+  /** Objects with the largest retained size */
+  topRetainers?: HeapRetainer[];
+  /** Self size grouped by constructor, largest first */
+  topConstructors?: HeapConstructorSummary[];
+  /** Set if the snapshot couldn't be taken or summarized */
+  error?: string;
+}
+
+/** The --on-stop-exec command run at a breakpoint hit */
+export interface StopCommandResult {
+  /** The command as run, with placeholders filled in */
+  command: string[];
+  /** Exit code (null if it was killed or couldn't run) */
+  exitCode: number | null;
+  stdout: string;
+  stderr: string;
+  durationMs: number;
+  /** True if output was cut off at the size limit */
+  truncated?: boolean;
+  /** Set if a placeholder couldn't be resolved, or the command couldn't run or timed out */
+  error?: string;
+}
+
 export interface BreakpointHitEvent extends BaseEvent {
   type: 'breakpoint_hit';
   id?: number;
+  /** The breakpoint's label (-b "file:line#label") */
+  label?: string;
   threadId: number;
+  threadName?: string;
   location: SourceLocation;
   stackTrace: StackFrameInfo[];
   locals: Record<string, VariableValue>;
-  evaluations?: Record<string, { result: string; type?: string; error?: string }>;
+  evaluations?: Record<string, EvaluationResult>;
+  /** Source around the stop location (--include-source) */
+  source?: SourceSnippet;
+  /** Heap snapshot taken at this hit (--heap-snapshot) */
+  heapSnapshot?: HeapSnapshotInfo;
+  /** Output of the --on-stop-exec command run at this hit */
+  stopCommand?: StopCommandResult;
+  /** --set assignments applied after capturing this hit, keyed by target */
+  assignments?: Record<string, AssignmentResult>;
+  /** True for the hit in a frame re-run by --on-hit restart-frame */
+  rerun?: boolean;
+  /** True for a --native-break hit, reported by the native adapter (mixed mode) */
+  native?: boolean;
+  /** For a native hit: the Python stack that called into it, innermost first */
+  pythonStack?: SourceLocation[];
+  /** Why the Python stack of a native hit couldn't be read */
+  pythonStackError?: string;
+}
+
+/** Outcome of one --set assignment */
+export interface AssignmentResult {
+  /** Value the target holds after the assignment (as the adapter reports it) */
+  value: string;
+  error?: string;
+}
+
+/** The source file on disk isn't what the program is running (edited but not rebuilt) */
+export interface StaleSourceEvent extends BaseEvent {
+  type: 'stale_source';
+  file: string;
+  /** The affected breakpoint (omitted when the stale file has no breakpoints) */
+  breakpointId?: number;
+  line?: number;
+  /** checksum: the adapter's checksums don't match; content: its loaded source differs */
+  reason: 'checksum' | 'content';
+  algorithm?: string;
+  /** Checksum of what's running vs of the file on disk */
+  expected?: string;
+  actual?: string;
+  message: string;
+}
+
+/** stop: sampled at a stop; write: caught by a hardware watchpoint on the region */
+export type MemoryChangeReason = 'stop' | 'write';
+
+/** Watched bytes (--watch-memory) differ from the previous sample */
+export interface MemoryChangedEvent extends BaseEvent {
+  type: 'memory_changed';
+  /** The --watch-memory expression */
+  expression: string;
+  address: string;
+  size: number;
+  /** Bytes before and after, as hex */
+  before: string;
+  after: string;
+  reason: MemoryChangeReason;
+  threadId: number;
+  /** Where the program was stopped when the change was seen */
+  location: SourceLocation;
+}
+
+/** DAP requests of one command in session_end (--request-stats) */
+export interface RequestStatsRecord {
+  command: string;
+  count: number;
+  /** Requests the adapter answered with an error (omitted when 0) */
+  failed?: number;
+  /** Time from sending each request to its response, in ms */
+  totalMs: number;
+  meanMs: number;
+  maxMs: number;
+}
+
+/** One --watch-memory region in session_end */
+export interface MemoryWatchSummary {
+  expression: string;
+  size: number;
+  address?: string;
+  /** First and last bytes read, as hex */
+  initial?: string;
+  value?: string;
+  changes: number;
+  /** True when a hardware watchpoint reported writes, false when the adapter couldn't set one */
+  watchpoint?: boolean;
+  /** Why the last sample failed */
+  error?: string;
+}
+
+/** Execution moved to another line by --on-hit goto, skipping the code in between */
+export interface ExecutionMovedEvent extends BaseEvent {
+  type: 'execution_moved';
+  threadId: number;
+  /** Where the thread was stopped */
+  from: SourceLocation;
+  /** The line it continues from */
+  to: { file: string; line: number };
+}
+
+/** The program relaunched through the adapter (--on-exit restart, serve mode restart) */
+export interface ProcessRestartedEvent extends BaseEvent {
+  type: 'process_restarted';
+  /** 1-based count of restarts this session */
+  restart: number;
+  /** exited: the program exited under --on-exit restart; requested: a client asked */
+  reason: 'exited' | 'requested';
+}
+
+/** A finished adapter progress report (DAP progressStart ... progressEnd) */
+export interface ProgressRecord {
+  title: string;
+  /** The last message the adapter sent for it */
+  message?: string;
+  startedAt: string;
+  durationMs: number;
+}
+
+/** One thread's lifetime (timestamps are missing for threads that predate the session) */
+export interface ThreadLifetime {
+  id: number;
+  name?: string;
+  startedAt?: string;
+  exitedAt?: string;
+}
+
+/** A module (DLL, shared library, Python module) the adapter reported loading (--modules) */
+export interface ModuleInfo {
+  name: string;
+  path?: string;
+  version?: string;
+  /** Whether debug symbols loaded, when the adapter reports a symbol status */
+  symbolsLoaded?: boolean;
+  /** The adapter's symbol status text (e.g., "Symbols loaded.", "Cannot find the PDB file.") */
+  symbolStatus?: string;
+  symbolFilePath?: string;
+  isUserCode?: boolean;
+}
+
+/** A frame restarted by --on-hit restart-frame, stopped at its start */
+export interface FrameRestartedEvent extends BaseEvent {
+  type: 'frame_restarted';
+  threadId: number;
+  location: SourceLocation;
+  /** --set assignments applied before the frame runs again, keyed by target */
+  assignments?: Record<string, AssignmentResult>;
 }
 
 // Exception events
@@ -186,6 +624,7 @@ export interface RootCauseInfo {
 export interface ExceptionThrownEvent extends BaseEvent {
   type: 'exception_thrown';
   threadId: number;
+  threadName?: string;
   exception: {
     type: string;
     message: string;
@@ -198,11 +637,53 @@ export interface ExceptionThrownEvent extends BaseEvent {
   rootCause?: RootCauseInfo;
   location: SourceLocation;
   locals: Record<string, VariableValue>;
+  /** Source around the stop location (--include-source) */
+  source?: SourceSnippet;
+}
+
+/** Exceptions of one type thrown at one place, under --sample-exceptions */
+export interface ExceptionSampleGroup {
+  type: string;
+  /** Message of the latest occurrence */
+  message: string;
+  /** Different messages seen, when more than one (counted up to 100) */
+  distinctMessages?: number;
+  /** Where the exception was thrown */
+  location: SourceLocation;
+  /** Occurrences in the report's period */
+  count: number;
+  /** Occurrences since the session started */
+  total: number;
+  firstSeen: string;
+  lastSeen: string;
+  /** Stack of the latest occurrence (top 10 frames) */
+  stackTrace: StackFrameInfo[];
+}
+
+/** Exceptions sampled since the previous report (--sample-exceptions) */
+export interface ExceptionSampleReportEvent extends BaseEvent {
+  type: 'exception_sample_report';
+  periodStart: string;
+  periodEnd: string;
+  /** Exceptions in the period */
+  count: number;
+  /** Exceptions since the session started */
+  total: number;
+  /** The period's exceptions by type and throw site, the most frequent first */
+  groups: ExceptionSampleGroup[];
+  /** Set on the report emitted as the session ends */
+  final?: boolean;
 }
 
 export interface ExceptionBreakpointSetEvent extends BaseEvent {
   type: 'exception_breakpoint_set';
   filters: string[];
+  /** Exception types to break on (type:Name) */
+  types?: string[];
+  /** Conditions sent to the adapter, per filter */
+  conditions?: { filter: string; condition: string }[];
+  /** Specs the adapter can't honor, with the reason */
+  unsupported?: string[];
 }
 
 // Logpoint events
@@ -218,9 +699,13 @@ export interface LogpointHitEvent extends BaseEvent {
 export interface StepCompletedEvent extends BaseEvent {
   type: 'step_completed';
   threadId: number;
+  /** Label of the breakpoint these steps started from */
+  label?: string;
   location: SourceLocation;
   stackTrace: StackFrameInfo[];
   locals: Record<string, VariableValue>;
+  /** Source around the stop location (--include-source) */
+  source?: SourceSnippet;
 }
 
 // Trace mode events
@@ -257,13 +742,15 @@ export interface TraceStepEvent extends BaseEvent {
 export interface TraceCompletedEvent extends BaseEvent {
   type: 'trace_completed';
   threadId: number;
+  /** Label of the breakpoint the trace started from */
+  label?: string;
   stopReason: TraceStopReason;
   stepsExecuted: number;
   path: SourceLocation[];
   finalLocation: SourceLocation;
   stackTrace: StackFrameInfo[];
   locals: Record<string, VariableValue>;
-  evaluations?: Record<string, { result: string; type?: string; error?: string }>;
+  evaluations?: Record<string, EvaluationResult>;
 }
 
 // Output from the debuggee
@@ -284,12 +771,128 @@ export interface ErrorEvent extends BaseEvent {
 export interface AssertionFailedEvent extends BaseEvent {
   type: 'assertion_failed';
   threadId: number;
+  /** Label of the breakpoint the assertion was checked at (or stepped on from) */
+  label?: string;
   assertion: string;
   actualValue: string;
   evaluationError?: string;
   location: SourceLocation;
   stackTrace: StackFrameInfo[];
   locals: Record<string, VariableValue>;
+  /** Source around the stop location (--include-source) */
+  source?: SourceSnippet;
+}
+
+// Interrupt events (Ctrl+C / SIGTERM, see --sigint-mode)
+
+/** What debug-run did in response to an interrupt */
+export type InterruptAction = 'snapshot' | 'forward' | 'stop';
+
+export interface InterruptedEvent extends BaseEvent {
+  type: 'interrupted';
+  signal: 'SIGINT' | 'SIGTERM';
+  action: InterruptAction;
+  /** Debuggee PID the signal was forwarded to (action: forward) */
+  pid?: number;
+}
+
+/** State captured by pausing the debuggee on the first Ctrl+C */
+export interface InterruptSnapshotEvent extends BaseEvent {
+  type: 'interrupt_snapshot';
+  threadId: number;
+  location: SourceLocation;
+  stackTrace: StackFrameInfo[];
+  locals: Record<string, VariableValue>;
+  evaluations?: Record<string, EvaluationResult>;
+  /** Source around the stop location (--include-source) */
+  source?: SourceSnippet;
+}
+
+// Crash triage events (--until-crash)
+
+/** A thread's stack at the time of a crash */
+export interface CrashThread {
+  id: number;
+  name: string;
+  /** True for the thread that crashed */
+  faulting?: boolean;
+  stackTrace: StackFrameInfo[];
+  /** Set if the adapter couldn't produce this thread's stack */
+  error?: string;
+}
+
+/** A fatal signal as reported by a native debugger */
+export interface SignalInfo {
+  /** Signal name (e.g., SIGSEGV) */
+  name: string;
+  /** Signal number on this platform */
+  number?: number;
+  /** The adapter's description of the stop */
+  description?: string;
+  /** Address that caused the fault, when the adapter reports it */
+  faultAddress?: string;
+}
+
+/** The program received a fatal signal (lldb/gdb); the session ends after this event */
+export interface SignalReceivedEvent extends BaseEvent {
+  type: 'signal_received';
+  threadId: number;
+  signal: SignalInfo;
+  location: SourceLocation;
+  /** Locals of the faulting frame */
+  locals: Record<string, VariableValue>;
+  /** General-purpose registers of the faulting frame */
+  registers?: Record<string, string>;
+  evaluations?: Record<string, EvaluationResult>;
+  /** Source around the stop location (--include-source) */
+  source?: SourceSnippet;
+  /** All threads, faulting thread first */
+  threads: CrashThread[];
+}
+
+/** Everything captured when the program crashes under --until-crash */
+export interface CrashReportEvent extends BaseEvent {
+  type: 'crash_report';
+  /** Adapter stop reason (exception for unhandled exceptions, signal for native crashes) */
+  reason: string;
+  threadId: number;
+  exception: {
+    type: string;
+    message: string;
+  };
+  /** Flattened exception chain from outer to inner (root cause) */
+  exceptionChain?: ExceptionChainEntry[];
+  /** Classified root cause with actionable hint */
+  rootCause?: RootCauseInfo;
+  location: SourceLocation;
+  /** Locals of the faulting frame */
+  locals: Record<string, VariableValue>;
+  evaluations?: Record<string, EvaluationResult>;
+  /** Source around the stop location (--include-source) */
+  source?: SourceSnippet;
+  /** All threads, faulting thread first */
+  threads: CrashThread[];
+  /** Last lines of program output before the crash, oldest first */
+  recentOutput: string[];
+  /** The fatal signal, for native crashes */
+  signal?: SignalInfo;
+  /** General-purpose registers of the faulting frame (native crashes) */
+  registers?: Record<string, string>;
+}
+
+/** Edited files applied to the running debuggee (serve mode session.reload) */
+export interface CodeReloadedEvent extends BaseEvent {
+  type: 'code_reloaded';
+  /** Reload mechanism (js-debug, debugpy) */
+  mechanism: string;
+  /** Files whose code was reloaded */
+  reloaded: string[];
+  /** Requested files the program hasn't loaded (or can't reload, e.g. ES modules) */
+  notLoaded: string[];
+  /** Whether the paused frame was restarted to re-run the current function */
+  frameRestarted: boolean;
+  /** Breakpoints in the reloaded files after re-verification */
+  breakpoints: { id?: number; file: string; line: number; verified: boolean; message?: string }[];
 }
 
 // Union type of all events
@@ -298,10 +901,20 @@ export type DebugEvent =
   | SessionEndEvent
   | ProcessLaunchedEvent
   | ProcessAttachedEvent
+  | TriggerWaitingEvent
+  | TriggerFiredEvent
+  | NativeAttachedEvent
   | ProcessExitedEvent
+  | ProcessRestartedEvent
   | BreakpointSetEvent
+  | BreakpointWarningEvent
+  | BreakpointVerifiedEvent
+  | BreakpointsExpandedEvent
+  | BreakpointsArmedEvent
+  | InstructionBreakpointSetEvent
   | BreakpointHitEvent
   | ExceptionThrownEvent
+  | ExceptionSampleReportEvent
   | ExceptionBreakpointSetEvent
   | LogpointHitEvent
   | StepCompletedEvent
@@ -310,4 +923,13 @@ export type DebugEvent =
   | TraceCompletedEvent
   | ProgramOutputEvent
   | ErrorEvent
-  | AssertionFailedEvent;
+  | AssertionFailedEvent
+  | InterruptedEvent
+  | InterruptSnapshotEvent
+  | CrashReportEvent
+  | SignalReceivedEvent
+  | FrameRestartedEvent
+  | ExecutionMovedEvent
+  | MemoryChangedEvent
+  | StaleSourceEvent
+  | CodeReloadedEvent;
@@ -10,21 +10,37 @@ import type {
   StackFrameInfo,
   VariableValue,
   SourceLocation,
+  SessionEndEvent,
 } from './events.js';
+import type { Redactor } from './redaction.js';
+import { parseOutputFilters, omitSections, type OutputSection } from './sections.js';
 
 export interface FormatterOptions {
   /** Write to a custom stream (default: stdout) */
   stream?: NodeJS.WritableStream;
   /** Pretty print JSON (default: false) */
   pretty?: boolean;
-  /** Only emit these event types (if specified) */
+  /** Only emit these event types and sections (if specified; see sections.ts) */
   include?: string[];
-  /** Suppress these event types */
+  /** Suppress these event types and sections */
   exclude?: string[];
   /** Enable compact output mode for reduced token usage */
   compact?: boolean;
   /** Maximum stack frames to include (default: 3 in compact mode) */
   stackLimit?: number;
+  /**
+   * Write the program's output as-is to these streams instead of as program_output
+   * events, keeping the event stream free of it (--quiet-program, --result-fd)
+   */
+  programStreams?: ProgramStreams;
+  /** Mask secrets in every event and in the program's output before it's written (--redact) */
+  redactor?: Redactor;
+}
+
+export interface ProgramStreams {
+  stdout: NodeJS.WritableStream;
+  /** Also receives the adapter's console output */
+  stderr: NodeJS.WritableStream;
 }
 
 /** Patterns for detecting internal/runtime stack frames to filter in compact mode */
@@ -50,20 +66,30 @@ export class OutputFormatter {
   private pretty: boolean;
   private include?: Set<string>;
   private exclude?: Set<string>;
+  private omittedSections: Set<OutputSection>;
   private compact: boolean;
   private stackLimit: number;
+  private programStreams?: ProgramStreams;
+  private redactor?: Redactor;
 
   /** Track previous locals for variable diffing in compact mode */
   private previousLocals: Record<string, unknown> = {};
 
+  /** Listeners notified of every event (before include/exclude filtering and compaction) */
+  private listeners: Array<(event: DebugEvent) => void> = [];
+
   constructor(options: FormatterOptions = {}) {
     this.stream = options.stream ?? process.stdout;
     this.pretty = options.pretty ?? false;
-    this.include = options.include ? new Set(options.include) : undefined;
-    this.exclude = options.exclude ? new Set(options.exclude) : undefined;
+    const filters = parseOutputFilters(options.include, options.exclude);
+    this.include = filters.includeTypes;
+    this.exclude = filters.excludeTypes;
+    this.omittedSections = filters.omittedSections;
     this.compact = options.compact ?? false;
     // Default stack limit: 3 in compact mode, unlimited otherwise
     this.stackLimit = options.stackLimit ?? (options.compact ? 3 : Infinity);
+    this.programStreams = options.programStreams;
+    this.redactor = options.redactor;
   }
 
   /**
@@ -78,25 +104,47 @@ export class OutputFormatter {
     if (this.exclude && this.exclude.has(type)) {
       return false;
     }
+    // The program's output is a section of its own
+    if (type === 'program_output' && this.omittedSections.has('output')) {
+      return false;
+    }
     return true;
   }
 
+  /**
+   * Register a listener for every event, regardless of output filters.
+   * Used by sinks other than the NDJSON stream (e.g., the --store database).
+   */
+  onEvent(listener: (event: DebugEvent) => void): void {
+    this.listeners.push(listener);
+  }
+
   /**
    * Emit a debug event
    */
   emit(event: DebugEvent): void {
-    if (!this.shouldEmit(event.type)) {
+    const redacted = this.redactor ? this.redactor.redactEvent(event) : event;
+    this.notify(redacted);
+
+    if (!this.shouldEmit(redacted.type)) {
       return;
     }
 
     // Apply compact transformations if enabled
-    const outputEvent = this.compact ? this.compactifyEvent(event) : event;
+    const compacted = this.compact ? this.compactifyEvent(redacted) : redacted;
+    const outputEvent = omitSections(compacted, this.omittedSections);
 
     const json = this.pretty ? JSON.stringify(outputEvent, null, 2) : JSON.stringify(outputEvent);
 
     this.stream.write(json + '\n');
   }
 
+  private notify(event: DebugEvent): void {
+    for (const listener of this.listeners) {
+      listener(event);
+    }
+  }
+
   /**
    * Check if a stack frame is an internal/runtime frame
    */
@@ -363,7 +411,21 @@ export class OutputFormatter {
         };
       }
 
-      case 'assertion_failed': {
+      case 'crash_report':
+      case 'signal_received': {
+        return {
+          ...event,
+          location: this.compactifyLocation(event.location),
+          locals: this.abbreviateLocals(event.locals),
+          threads: event.threads.map((thread) => ({
+            ...thread,
+            stackTrace: this.compactifyStackTrace(thread.stackTrace),
+          })),
+        };
+      }
+
+      case 'assertion_failed':
+      case 'interrupt_snapshot': {
         return {
           ...event,
           location: this.compactifyLocation(event.location),
@@ -449,11 +511,12 @@ export class OutputFormatter {
   /**
    * Emit a session_start event (attach mode)
    */
-  sessionStartAttach(adapter: string, pid: number): void {
+  sessionStartAttach(adapter: string, pid?: number, port?: number): void {
     this.emit(
       this.createEvent('session_start', {
         adapter,
         pid,
+        ...(port !== undefined && { port }),
         attach: true,
       })
     );
@@ -462,13 +525,7 @@ export class OutputFormatter {
   /**
    * Emit a session_end event
    */
-  sessionEnd(summary: {
-    durationMs: number;
-    exitCode: number | null;
-    breakpointsHit: number;
-    exceptionsCaught: number;
-    stepsExecuted: number;
-  }): void {
+  sessionEnd(summary: SessionEndEvent['summary']): void {
     this.emit(this.createEvent('session_end', { summary }));
   }
 
@@ -482,16 +539,20 @@ export class OutputFormatter {
     verified: boolean,
     condition?: string,
     message?: string,
-    diagnostics?: BreakpointDiagnostics
+    diagnostics?: BreakpointDiagnostics,
+    column?: number,
+    label?: string
   ): void {
     this.emit(
       this.createEvent('breakpoint_set', {
         id,
         file,
         line,
+        ...(column !== undefined && { column }),
         verified,
         condition,
         message,
+        ...(label && { label }),
         diagnostics,
       })
     );
@@ -508,6 +569,15 @@ export class OutputFormatter {
    * Emit a program_output event
    */
   programOutput(category: 'stdout' | 'stderr' | 'console', output: string): void {
-    this.emit(this.createEvent('program_output', { category, output }));
+    const event = this.createEvent('program_output', { category, output });
+    if (!this.programStreams) {
+      this.emit(event);
+      return;
+    }
+
+    // Sinks such as --store still see the output as an event
+    this.notify(this.redactor ? this.redactor.redactEvent(event) : event);
+    const stream = category === 'stdout' ? this.programStreams.stdout : this.programStreams.stderr;
+    stream.write(this.redactor ? this.redactor.redactText(output) : output);
   }
 }
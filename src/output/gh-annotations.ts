@@ -0,0 +1,141 @@
+/**
+ * GitHub Actions Annotations (--output-format gh-annotations)
+ *
+ * Turns a session's events into workflow commands, so a debug-run step in a GitHub Actions
+ * job annotates the lines involved on the PR diff: failed assertions and crash locations as
+ * errors, breakpoints the adapter couldn't verify as warnings, and a notice with how the
+ * session ended. Annotated files are made relative to the workspace; a location outside it
+ * is still reported, without a file.
+ */
+
+import * as path from 'node:path';
+import type { DebugEvent, SessionEndEvent, SourceLocation } from './events.js';
+
+export type AnnotationLevel = 'error' | 'warning' | 'notice';
+
+export interface AnnotationProperties {
+  title?: string;
+  file?: string;
+  line?: number;
+  col?: number;
+}
+
+/** Workflow command data can't contain raw newlines or a bare % */
+function escapeData(text: string): string {
+  return text.replace(/%/g, '%25').replace(/\r/g, '%0D').replace(/\n/g, '%0A');
+}
+
+/** Property values additionally can't contain the : and , that separate them */
+function escapeProperty(text: string): string {
+  return escapeData(text).replace(/:/g, '%3A').replace(/,/g, '%2C');
+}
+
+/**
+ * A workflow command line ("::error file=src/app.ts,line=3,title=...::message")
+ */
+export function workflowCommand(
+  level: AnnotationLevel,
+  message: string,
+  properties: AnnotationProperties = {}
+): string {
+  const fields = Object.entries(properties)
+    .filter(([, value]) => value !== undefined && value !== '')
+    .map(([key, value]) => `${key}=${escapeProperty(String(value))}`);
+  const props = fields.length > 0 ? ` ${fields.join(',')}` : '';
+  return `::${level}${props}::${escapeData(message)}`;
+}
+
+function plural(count: number, noun: string): string {
+  return `${count} ${noun}${count === 1 ? '' : 's'}`;
+}
+
+export class GitHubAnnotations {
+  private workspace: string;
+  private assertionsFailed = 0;
+
+  constructor(workspace: string = process.env.GITHUB_WORKSPACE || process.cwd()) {
+    this.workspace = path.resolve(workspace);
+  }
+
+  /**
+   * The workflow command for an event, or undefined for events that aren't annotated
+   */
+  annotate(event: DebugEvent): string | undefined {
+    switch (event.type) {
+      case 'assertion_failed': {
+        this.assertionsFailed++;
+        const outcome = event.evaluationError
+          ? `could not be evaluated: ${event.evaluationError}`
+          : `was ${event.actualValue}`;
+        return workflowCommand('error', `${event.assertion} ${outcome}`, {
+          title: event.label ? `Assertion failed at ${event.label}` : 'Assertion failed',
+          ...this.position(event.location),
+        });
+      }
+      case 'breakpoint_set': {
+        if (event.verified) return undefined;
+        const reasons = [event.message, ...(event.diagnostics?.suggestions ?? [])].filter(
+          (reason): reason is string => Boolean(reason)
+        );
+        return workflowCommand(
+          'warning',
+          reasons.length > 0 ? reasons.join('\n') : 'The debug adapter could not bind it',
+          {
+            title: 'Breakpoint not verified',
+            ...this.position({ file: event.file, line: event.line, column: event.column }),
+          }
+        );
+      }
+      case 'crash_report': {
+        const what = event.signal ? event.signal.name : event.exception.type;
+        const hint = event.rootCause?.actionableHint;
+        const message = hint ? `${event.exception.message}\n${hint}` : event.exception.message;
+        return workflowCommand('error', message, {
+          title: `Crash: ${what}`,
+          ...this.position(event.location),
+        });
+      }
+      case 'signal_received':
+        return workflowCommand('error', event.signal.description ?? event.signal.name, {
+          title: `Crash: ${event.signal.name}`,
+          ...this.position(event.location),
+        });
+      case 'error':
+        return workflowCommand('error', event.details ?? event.message, {
+          title: event.details ? event.message : 'debug-run',
+        });
+      case 'session_end':
+        return workflowCommand('notice', this.describeOutcome(event.summary), {
+          title: 'debug-run',
+        });
+      default:
+        return undefined;
+    }
+  }
+
+  /** file/line/col for a location inside the workspace (none for one outside it) */
+  private position(location: SourceLocation): AnnotationProperties {
+    if (!location.file) return {};
+    const file = path.relative(this.workspace, path.resolve(location.file));
+    if (file.startsWith('..') || path.isAbsolute(file)) return {};
+    return {
+      file: file.split(path.sep).join('/'),
+      line: location.line,
+      ...(location.column !== undefined && { col: location.column }),
+    };
+  }
+
+  private describeOutcome(summary: SessionEndEvent['summary']): string {
+    const { termination } = summary;
+    const ended =
+      termination.reason === 'exited'
+        ? `Program exited with code ${summary.exitCode ?? 'unknown'}`
+        : `Session ended: ${termination.reason.replace(/_/g, ' ')}`;
+    const counts = [
+      plural(summary.breakpointsHit, 'breakpoint hit'),
+      plural(summary.exceptionsCaught, 'exception'),
+      ...(this.assertionsFailed > 0 ? [plural(this.assertionsFailed, 'failed assertion')] : []),
+    ];
+    return `${ended} (${counts.join(', ')})`;
+  }
+}
@@ -0,0 +1,335 @@
+/**
+ * HTML Report (--output-format html)
+ *
+ * Collects a session's events and renders them as one standalone HTML page for readers who
+ * won't open raw NDJSON: the session summary, then every stop with its location, source
+ * snippet, stack, locals and evaluations (as collapsible trees) and the program output
+ * written since the previous stop. The page has no scripts or external assets, so it can
+ * be attached to an issue or a CI run as-is.
+ */
+
+import * as path from 'node:path';
+import type {
+  DebugEvent,
+  ErrorEvent,
+  EvaluationResult,
+  SessionEndEvent,
+  SessionStartEvent,
+  SourceLocation,
+  SourceSnippet,
+  StackFrameInfo,
+  VariableValue,
+} from './events.js';
+
+type StopEvent = Extract<
+  DebugEvent,
+  {
+    type:
+      | 'breakpoint_hit'
+      | 'exception_thrown'
+      | 'step_completed'
+      | 'assertion_failed'
+      | 'trace_completed'
+      | 'interrupt_snapshot'
+      | 'crash_report'
+      | 'signal_received';
+  }
+>;
+
+const STOP_EVENT_TYPES: ReadonlySet<DebugEvent['type']> = new Set([
+  'breakpoint_hit',
+  'exception_thrown',
+  'step_completed',
+  'assertion_failed',
+  'trace_completed',
+  'interrupt_snapshot',
+  'crash_report',
+  'signal_received',
+]);
+
+interface ReportStop {
+  event: StopEvent;
+  /** Program output (and logpoint messages) since the previous stop */
+  output: string;
+}
+
+const STYLE = `
+body { font: 14px/1.45 system-ui, sans-serif; margin: 2rem auto; max-width: 960px; color: #1f2328; }
+h1 { font-size: 1.4rem; } h2 { font-size: 1.1rem; margin-top: 2rem; }
+table.summary td { padding: 0.15rem 1rem 0.15rem 0; vertical-align: top; }
+table.summary td:first-child { color: #656d76; }
+details.stop { border: 1px solid #d0d7de; border-radius: 6px; margin: 0.75rem 0; }
+details.stop { padding: 0.5rem 0.75rem; }
+details.stop > summary { cursor: pointer; font-weight: 600; }
+h3 { font-size: 0.95rem; margin: 0.9rem 0 0.3rem; color: #656d76; }
+pre, code, .tree { font-family: ui-monospace, SFMono-Regular, Menlo, monospace; font-size: 12.5px; }
+pre { background: #f6f8fa; padding: 0.5rem; overflow-x: auto; margin: 0; }
+pre .current { background: #fff8c5; display: block; }
+.tree details > summary { cursor: pointer; }
+.tree .children { margin-left: 1.25rem; }
+.type { color: #8250df; } .value { color: #0a3069; } .error { color: #cf222e; }
+ol.stack { margin: 0; padding-left: 1.5rem; }
+`;
+
+function escapeHtml(text: string): string {
+  return text
+    .replace(/&/g, '&amp;')
+    .replace(/</g, '&lt;')
+    .replace(/>/g, '&gt;')
+    .replace(/"/g, '&quot;');
+}
+
+function isStopEvent(event: DebugEvent): event is StopEvent {
+  return STOP_EVENT_TYPES.has(event.type);
+}
+
+function isVariableValue(value: unknown): value is VariableValue {
+  return typeof value === 'object' && value !== null && 'type' in value && 'value' in value;
+}
+
+function formatLocation(location: SourceLocation): string {
+  const file = location.file ? path.basename(location.file) : (location.sourceName ?? '?');
+  const where = `${file}:${location.line}`;
+  return location.function ? `${location.function} (${where})` : where;
+}
+
+function formatScalar(value: unknown): string {
+  return typeof value === 'string' ? value : JSON.stringify(value);
+}
+
+/**
+ * A variable as a line, or as a collapsible tree when it was expanded
+ */
+function renderVariable(name: string, value: unknown): string {
+  const label = `<span class="name">${escapeHtml(name)}</span>`;
+  const inner = isVariableValue(value) ? value.value : value;
+  const type = isVariableValue(value) ? ` <span class="type">${escapeHtml(value.type)}</span>` : '';
+
+  if (typeof inner !== 'object' || inner === null) {
+    const scalar = escapeHtml(formatScalar(inner));
+    return `<div>${label}${type} = <span class="value">${scalar}</span></div>`;
+  }
+  return `<details><summary>${label}${type}</summary>${renderChildren(inner)}</details>`;
+}
+
+function renderChildren(value: object): string {
+  let entries: [string, unknown][];
+  let more = 0;
+  // Collections are { type, count, items }
+  const collection = value as { items?: unknown; count?: unknown };
+  if (Array.isArray(collection.items)) {
+    entries = collection.items.map((item, i) => [`[${i}]`, item]);
+    if (typeof collection.count === 'number') more = collection.count - entries.length;
+  } else if (Array.isArray(value)) {
+    entries = value.map((item, i) => [`[${i}]`, item]);
+  } else {
+    entries = Object.entries(value);
+  }
+
+  const children = entries.map(([name, child]) => renderVariable(name, child)).join('');
+  const remainder = more > 0 ? `<div class="type">… ${more} more</div>` : '';
+  return `<div class="children">${children}${remainder}</div>`;
+}
+
+function renderLocals(locals: Record<string, VariableValue>): string {
+  const names = Object.keys(locals);
+  if (names.length === 0) return '';
+  const tree = names.map((name) => renderVariable(name, locals[name])).join('');
+  return `<h3>Locals</h3><div class="tree">${tree}</div>`;
+}
+
+function renderEvaluations(evaluations: Record<string, EvaluationResult> | undefined): string {
+  if (!evaluations || Object.keys(evaluations).length === 0) return '';
+  const rows = Object.entries(evaluations).map(([expression, result]) => {
+    // Results expanded with --eval-expand render like locals
+    if (!result.error && typeof result.value === 'object' && result.value !== null) {
+      return renderVariable(expression, { type: result.type ?? '', value: result.value });
+    }
+    const value = result.error
+      ? `<span class="error">${escapeHtml(result.error)}</span>`
+      : `<span class="value">${escapeHtml(result.result)}</span>`;
+    return `<div><span class="name">${escapeHtml(expression)}</span> = ${value}</div>`;
+  });
+  return `<h3>Evaluations</h3><div class="tree">${rows.join('')}</div>`;
+}
+
+function renderSource(source: SourceSnippet | undefined): string {
+  if (!source) return '';
+  const lines = source.lines.map((line) =>
+    line.startsWith('>')
+      ? `<span class="current">${escapeHtml(line)}</span>`
+      : `${escapeHtml(line)}\n`
+  );
+  return `<h3>Source</h3><pre class="source">${lines.join('')}</pre>`;
+}
+
+function renderStack(frames: StackFrameInfo[] | undefined): string {
+  if (!frames?.length) return '';
+  const items = frames.map((frame) => {
+    const where = frame.file ? `${escapeHtml(frame.file)}:${frame.line ?? '?'}` : '';
+    return `<li><code>${escapeHtml(frame.function)}</code> ${where}</li>`;
+  });
+  return `<h3>Stack</h3><ol class="stack">${items.join('')}</ol>`;
+}
+
+function renderOutput(output: string, title = 'Program output'): string {
+  if (!output) return '';
+  return `<h3>${title}</h3><pre>${escapeHtml(output)}</pre>`;
+}
+
+/**
+ * What stopped the program, for stops that carry more than a location
+ */
+function renderReason(event: StopEvent): string {
+  switch (event.type) {
+    case 'exception_thrown':
+    case 'crash_report': {
+      const { type, message } = event.exception;
+      const root = event.rootCause && `${event.rootCause.type}: ${event.rootCause.message}`;
+      const rootLine = root ? `<div>Root cause: ${escapeHtml(root)}</div>` : '';
+      return `<div class="error">${escapeHtml(`${type}: ${message}`)}</div>${rootLine}`;
+    }
+    case 'assertion_failed': {
+      const assertion = `<code>${escapeHtml(event.assertion)}</code>`;
+      const actual = escapeHtml(event.evaluationError ?? event.actualValue);
+      return `<div class="error">Assertion failed: ${assertion} (${actual})</div>`;
+    }
+    case 'signal_received':
+      return `<div class="error">${escapeHtml(event.signal.name)}</div>`;
+    case 'trace_completed':
+      return `<div>Trace ended (${event.stopReason}) after ${event.stepsExecuted} steps</div>`;
+    default:
+      return '';
+  }
+}
+
+function stopLocation(event: StopEvent): SourceLocation {
+  return event.type === 'trace_completed' ? event.finalLocation : event.location;
+}
+
+function stopStack(event: StopEvent): StackFrameInfo[] | undefined {
+  if (event.type === 'crash_report' || event.type === 'signal_received') {
+    return (event.threads.find((thread) => thread.faulting) ?? event.threads[0])?.stackTrace;
+  }
+  return 'stackTrace' in event ? event.stackTrace : undefined;
+}
+
+export class HtmlReport {
+  private start?: SessionStartEvent;
+  private end?: SessionEndEvent;
+  private stops: ReportStop[] = [];
+  private errors: ErrorEvent[] = [];
+  /** Output since the last stop */
+  private output = '';
+
+  /**
+   * Record an event (register with OutputFormatter.onEvent)
+   */
+  observe(event: DebugEvent): void {
+    switch (event.type) {
+      case 'session_start':
+        this.start = event;
+        return;
+      case 'session_end':
+        this.end = event;
+        return;
+      case 'error':
+        this.errors.push(event);
+        return;
+      case 'program_output':
+        this.output += event.output;
+        return;
+      case 'logpoint_hit':
+        this.output += `[logpoint ${formatLocation(event.location)}] ${event.logOutput}\n`;
+        return;
+    }
+    if (isStopEvent(event)) {
+      this.stops.push({ event, output: this.output });
+      this.output = '';
+    }
+  }
+
+  /**
+   * The report as a complete HTML document
+   */
+  render(): string {
+    const program = this.start?.program ?? (this.start?.pid ? `PID ${this.start.pid}` : 'session');
+    const title = `debug-run: ${path.basename(program)}`;
+
+    const stops = this.stops.map((stop, i) => this.renderStop(stop, i));
+    const body = [
+      `<h1>${escapeHtml(title)}</h1>`,
+      this.renderSummary(),
+      ...this.errors.map((error) => {
+        const text = error.details ? `${error.message} — ${error.details}` : error.message;
+        return `<div class="error">Error: ${escapeHtml(text)}</div>`;
+      }),
+      `<h2>Stops (${this.stops.length})</h2>`,
+      stops.length ? stops.join('\n') : '<p>The program did not stop.</p>',
+      renderOutput(this.output, stops.length ? 'Output after the last stop' : 'Program output'),
+    ];
+
+    return [
+      '<!DOCTYPE html>',
+      '<html lang="en">',
+      '<head>',
+      '<meta charset="utf-8">',
+      `<title>${escapeHtml(title)}</title>`,
+      `<style>${STYLE}</style>`,
+      '</head>',
+      '<body>',
+      ...body.filter(Boolean),
+      '</body>',
+      '</html>',
+      '',
+    ].join('\n');
+  }
+
+  private renderSummary(): string {
+    const rows: [string, string | number | undefined][] = [
+      ['Adapter', this.start?.adapter],
+      ['Program', this.start?.program],
+      ['Arguments', this.start?.args?.length ? this.start.args.join(' ') : undefined],
+      ['Started', this.start?.timestamp],
+    ];
+    const summary = this.end?.summary;
+    if (summary) {
+      const { reason, exitCode } = summary.termination;
+      rows.push(
+        ['Ended', exitCode === null ? reason : `${reason} (exit code ${exitCode})`],
+        ['Duration', `${summary.durationMs} ms`],
+        ['Breakpoint hits', summary.breakpointsHit],
+        ['Exceptions', summary.exceptionsCaught],
+        ['Steps', summary.stepsExecuted]
+      );
+    }
+
+    const cells = rows
+      .filter(([, value]) => value !== undefined)
+      .map(([name, value]) => `<tr><td>${name}</td><td>${escapeHtml(String(value))}</td></tr>`);
+    return `<table class="summary">${cells.join('')}</table>`;
+  }
+
+  private renderStop({ event, output }: ReportStop, index: number): string {
+    const label = 'label' in event && event.label ? ` [${event.label}]` : '';
+    const heading = `#${index + 1} ${event.type}${label} — ${formatLocation(stopLocation(event))}`;
+    const thread = 'threadName' in event && event.threadName ? ` · ${event.threadName}` : '';
+    const time = event.timestamp.replace('T', ' ').replace(/\.\d+Z$/, 'Z') + thread;
+    const evaluations = 'evaluations' in event ? event.evaluations : undefined;
+
+    return [
+      // The first stop starts open; the others are a click away
+      `<details class="stop"${index === 0 ? ' open' : ''}>`,
+      `<summary>${escapeHtml(heading)} <span class="type">${escapeHtml(time)}</span></summary>`,
+      renderReason(event),
+      renderSource('source' in event ? event.source : undefined),
+      renderStack(stopStack(event)),
+      renderLocals(event.locals),
+      renderEvaluations(evaluations),
+      renderOutput(output),
+      '</details>',
+    ]
+      .filter(Boolean)
+      .join('\n');
+  }
+}
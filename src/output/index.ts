@@ -1,2 +1,3 @@
 export * from './events.js';
 export * from './formatter.js';
+export * from './otlp.js';
@@ -0,0 +1,340 @@
+/**
+ * OTLP Trace Exporter
+ *
+ * Exports session telemetry to an OpenTelemetry collector (--otlp) as OTLP/HTTP JSON:
+ * - one span per diagnostic span (session, connecting, launching, running, ...)
+ * - span events for breakpoint hits, exceptions and assertion failures
+ *
+ * When TRACEPARENT is set (as CI tracing integrations do), the session span joins that
+ * trace so debug-run shows up under the build that invoked it.
+ */
+
+import { randomBytes } from 'node:crypto';
+import type { DebugEvent } from './events.js';
+import { logger, type SpanInfo, type SpanObserver } from '../util/logger.js';
+
+const DEFAULT_ENDPOINT = 'http://localhost:4318';
+const TRACES_PATH = '/v1/traces';
+
+/** OTLP span kind INTERNAL */
+const SPAN_KIND_INTERNAL = 1;
+/** OTLP status code ERROR */
+const STATUS_CODE_ERROR = 2;
+
+export interface OtlpExporterOptions {
+  /** Full traces URL (e.g., http://localhost:4318/v1/traces) */
+  endpoint: string;
+  /** Extra request headers (e.g., authentication) */
+  headers?: Record<string, string>;
+  /** Resource attributes; service.name defaults to "debug-run" */
+  resourceAttributes?: Record<string, string>;
+  /** W3C traceparent of the invoking process, used as the parent of the session span */
+  traceParent?: string;
+  /** Request timeout in milliseconds (default: 5000) */
+  timeoutMs?: number;
+}
+
+interface OtlpAttribute {
+  key: string;
+  value:
+    | { stringValue: string }
+    | { intValue: string }
+    | { doubleValue: number }
+    | { boolValue: boolean };
+}
+
+interface OtlpEvent {
+  timeUnixNano: string;
+  name: string;
+  attributes: OtlpAttribute[];
+}
+
+interface OtlpSpan {
+  traceId: string;
+  spanId: string;
+  parentSpanId?: string;
+  name: string;
+  kind: number;
+  startTimeUnixNano: string;
+  endTimeUnixNano: string;
+  attributes: OtlpAttribute[];
+  events: OtlpEvent[];
+  status?: { code: number; message?: string };
+}
+
+/**
+ * Convert fields to OTLP attributes, skipping undefined values
+ */
+export function toOtlpAttributes(fields: Record<string, unknown> | undefined): OtlpAttribute[] {
+  const attributes: OtlpAttribute[] = [];
+  for (const [key, value] of Object.entries(fields ?? {})) {
+    if (value === undefined || value === null) continue;
+    if (typeof value === 'string') {
+      attributes.push({ key, value: { stringValue: value } });
+    } else if (typeof value === 'boolean') {
+      attributes.push({ key, value: { boolValue: value } });
+    } else if (typeof value === 'number') {
+      attributes.push({
+        key,
+        value: Number.isInteger(value) ? { intValue: String(value) } : { doubleValue: value },
+      });
+    } else {
+      attributes.push({ key, value: { stringValue: JSON.stringify(value) } });
+    }
+  }
+  return attributes;
+}
+
+/**
+ * Parse a W3C traceparent header ("00-<trace-id>-<parent-id>-<flags>")
+ */
+export function parseTraceParent(
+  value: string | undefined
+): { traceId: string; spanId: string } | null {
+  const match = value?.trim().match(/^[0-9a-f]{2}-([0-9a-f]{32})-([0-9a-f]{16})-[0-9a-f]{2}$/);
+  if (!match || /^0+$/.test(match[1]) || /^0+$/.test(match[2])) {
+    return null;
+  }
+  return { traceId: match[1], spanId: match[2] };
+}
+
+/**
+ * Parse an OTEL_EXPORTER_OTLP_HEADERS / OTEL_RESOURCE_ATTRIBUTES style list ("k1=v1,k2=v2")
+ */
+export function parseKeyValueList(value: string | undefined): Record<string, string> {
+  const result: Record<string, string> = {};
+  for (const pair of (value ?? '').split(',')) {
+    const eqIndex = pair.indexOf('=');
+    if (eqIndex <= 0) continue;
+    result[pair.slice(0, eqIndex).trim()] = decodeURIComponent(pair.slice(eqIndex + 1).trim());
+  }
+  return result;
+}
+
+/**
+ * Build exporter options from --otlp and the standard OTEL_* environment variables.
+ * An explicit endpoint is treated like OTEL_EXPORTER_OTLP_ENDPOINT (the /v1/traces
+ * path is appended unless already present).
+ */
+export function otlpOptionsFromEnv(
+  endpoint?: string,
+  env: NodeJS.ProcessEnv = process.env
+): OtlpExporterOptions {
+  let tracesEndpoint: string;
+  if (endpoint) {
+    tracesEndpoint = withTracesPath(endpoint);
+  } else if (env.OTEL_EXPORTER_OTLP_TRACES_ENDPOINT) {
+    tracesEndpoint = env.OTEL_EXPORTER_OTLP_TRACES_ENDPOINT;
+  } else {
+    tracesEndpoint = withTracesPath(env.OTEL_EXPORTER_OTLP_ENDPOINT || DEFAULT_ENDPOINT);
+  }
+
+  const resourceAttributes = parseKeyValueList(env.OTEL_RESOURCE_ATTRIBUTES);
+  if (env.OTEL_SERVICE_NAME) {
+    resourceAttributes['service.name'] = env.OTEL_SERVICE_NAME;
+  }
+
+  return {
+    endpoint: tracesEndpoint,
+    headers: {
+      ...parseKeyValueList(env.OTEL_EXPORTER_OTLP_HEADERS),
+      ...parseKeyValueList(env.OTEL_EXPORTER_OTLP_TRACES_HEADERS),
+    },
+    resourceAttributes,
+    traceParent: env.TRACEPARENT,
+  };
+}
+
+function withTracesPath(endpoint: string): string {
+  const base = endpoint.replace(/\/+$/, '');
+  return base.endsWith(TRACES_PATH) ? base : base + TRACES_PATH;
+}
+
+function toUnixNano(ms: number): string {
+  return (BigInt(ms) * 1_000_000n).toString();
+}
+
+export class OtlpExporter implements SpanObserver {
+  private readonly traceId: string;
+  private readonly remoteParentId?: string;
+  private spanIds: Map<number, string> = new Map();
+  private openSpans: Map<number, OtlpSpan> = new Map();
+  private finishedSpans: OtlpSpan[] = [];
+  /** Attributes from session_start/session_end, applied to the root span */
+  private sessionAttributes: Record<string, unknown> = {};
+
+  constructor(private options: OtlpExporterOptions) {
+    const parent = parseTraceParent(options.traceParent);
+    this.traceId = parent?.traceId ?? randomBytes(16).toString('hex');
+    this.remoteParentId = parent?.spanId;
+  }
+
+  spanStarted(span: SpanInfo): void {
+    const spanId = randomBytes(8).toString('hex');
+    this.spanIds.set(span.id, spanId);
+    const parentSpanId =
+      span.parentId !== undefined ? this.spanIds.get(span.parentId) : this.remoteParentId;
+
+    this.openSpans.set(span.id, {
+      traceId: this.traceId,
+      spanId,
+      ...(parentSpanId && { parentSpanId }),
+      name: span.name,
+      kind: SPAN_KIND_INTERNAL,
+      startTimeUnixNano: toUnixNano(span.startTime),
+      endTimeUnixNano: toUnixNano(span.startTime),
+      attributes: toOtlpAttributes(span.fields),
+      events: [],
+    });
+  }
+
+  spanEnded(span: SpanInfo, endTime: number, fields?: Record<string, unknown>): void {
+    const otlpSpan = this.openSpans.get(span.id);
+    if (!otlpSpan) return;
+    this.openSpans.delete(span.id);
+
+    otlpSpan.endTimeUnixNano = toUnixNano(endTime);
+    otlpSpan.attributes.push(...toOtlpAttributes(fields));
+    if (typeof fields?.error === 'string') {
+      otlpSpan.status = { code: STATUS_CODE_ERROR, message: fields.error };
+    }
+    this.finishedSpans.push(otlpSpan);
+  }
+
+  /**
+   * Record a debug event. Stops become span events on the innermost open span.
+   */
+  recordEvent(event: DebugEvent): void {
+    switch (event.type) {
+      case 'session_start':
+        Object.assign(this.sessionAttributes, {
+          'debug_run.adapter': event.adapter,
+          'debug_run.program': event.program,
+          'debug_run.pid': event.pid,
+        });
+        break;
+
+      case 'session_end':
+        Object.assign(this.sessionAttributes, {
+          'debug_run.exit_code': event.summary.exitCode,
+          'debug_run.breakpoints_hit': event.summary.breakpointsHit,
+          'debug_run.exceptions_caught': event.summary.exceptionsCaught,
+          'debug_run.steps_executed': event.summary.stepsExecuted,
+          'debug_run.termination_reason': event.summary.termination?.reason,
+          'debug_run.cpu_time_ms': event.summary.termination?.cpuTimeMs,
+        });
+        break;
+
+      case 'breakpoint_hit':
+        this.addEvent(event, {
+          'debug_run.breakpoint_id': event.id,
+          'code.filepath': event.location.file,
+          'code.lineno': event.location.line,
+          'code.function': event.location.function,
+          'thread.id': event.threadId,
+        });
+        break;
+
+      case 'exception_thrown':
+      case 'crash_report':
+        this.addEvent(event, {
+          'exception.type': event.exception.type,
+          'exception.message': event.exception.message,
+          'code.filepath': event.location.file,
+          'code.lineno': event.location.line,
+          'thread.id': event.threadId,
+        });
+        break;
+
+      case 'signal_received':
+        this.addEvent(event, {
+          'debug_run.signal': event.signal.name,
+          'debug_run.fault_address': event.signal.faultAddress,
+          'code.filepath': event.location.file,
+          'code.lineno': event.location.line,
+          'thread.id': event.threadId,
+        });
+        break;
+
+      case 'assertion_failed':
+        this.addEvent(event, {
+          'debug_run.assertion': event.assertion,
+          'code.filepath': event.location.file,
+          'code.lineno': event.location.line,
+          'thread.id': event.threadId,
+        });
+        break;
+    }
+  }
+
+  /**
+   * Build the OTLP/HTTP JSON request body for all finished spans
+   */
+  buildPayload(): object {
+    const spans = this.finishedSpans.map((span) =>
+      span.parentSpanId === undefined || span.parentSpanId === this.remoteParentId
+        ? { ...span, attributes: [...span.attributes, ...toOtlpAttributes(this.sessionAttributes)] }
+        : span
+    );
+
+    return {
+      resourceSpans: [
+        {
+          resource: {
+            attributes: toOtlpAttributes({
+              'service.name': 'debug-run',
+              ...this.options.resourceAttributes,
+            }),
+          },
+          scopeSpans: [{ scope: { name: 'debug-run' }, spans }],
+        },
+      ],
+    };
+  }
+
+  /**
+   * Send finished spans to the collector. Failures are logged, never thrown, so telemetry
+   * problems can't fail a debug session.
+   */
+  async flush(): Promise<void> {
+    if (this.finishedSpans.length === 0) return;
+
+    const body = JSON.stringify(this.buildPayload());
+    const spanCount = this.finishedSpans.length;
+    this.finishedSpans = [];
+
+    try {
+      const response = await fetch(this.options.endpoint, {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/json', ...this.options.headers },
+        body,
+        signal: AbortSignal.timeout(this.options.timeoutMs ?? 5000),
+      });
+      if (!response.ok) {
+        logger.warn('otlp', 'collector rejected spans', {
+          endpoint: this.options.endpoint,
+          status: response.status,
+        });
+        return;
+      }
+      logger.debug('otlp', 'exported spans', { endpoint: this.options.endpoint, spanCount });
+    } catch (error) {
+      logger.warn('otlp', 'failed to export spans', {
+        endpoint: this.options.endpoint,
+        error: error instanceof Error ? error.message : String(error),
+      });
+    }
+  }
+
+  private addEvent(event: DebugEvent, attributes: Record<string, unknown>): void {
+    const spans = [...this.openSpans.values()];
+    const target = spans[spans.length - 1];
+    if (!target) return;
+
+    target.events.push({
+      timeUnixNano: toUnixNano(Date.parse(event.timestamp)),
+      name: event.type,
+      attributes: toOtlpAttributes(attributes),
+    });
+  }
+}
@@ -0,0 +1,247 @@
+/**
+ * Parquet Writer
+ *
+ * Just enough of the Parquet format to write a small table that pandas, DuckDB, Polars and
+ * Spark can read, without a native dependency: one row group, one uncompressed PLAIN data
+ * page per column, UTF-8 string and 64-bit integer columns, and nulls in optional columns.
+ * The footer is Thrift compact protocol, written by hand below.
+ */
+
+const MAGIC = Buffer.from('PAR1', 'ascii');
+
+export interface ParquetColumn {
+  name: string;
+  type: 'string' | 'int64';
+  /** Whether the column may hold nulls */
+  optional?: boolean;
+  values: Array<string | number | null | undefined>;
+}
+
+// Parquet enums (parquet.thrift)
+const PHYSICAL_TYPES = { string: 6, int64: 2 }; // BYTE_ARRAY, INT64
+const REQUIRED = 0;
+const OPTIONAL = 1;
+const CONVERTED_UTF8 = 0;
+const ENCODING_PLAIN = 0;
+const ENCODING_RLE = 3;
+const CODEC_UNCOMPRESSED = 0;
+const PAGE_DATA = 0;
+
+// Thrift compact protocol field types
+const T_I32 = 5;
+const T_I64 = 6;
+const T_BINARY = 8;
+const T_LIST = 9;
+const T_STRUCT = 12;
+
+type ThriftValue =
+  | { type: typeof T_I32 | typeof T_I64; value: number }
+  | { type: typeof T_BINARY; value: string }
+  | { type: typeof T_STRUCT; value: ThriftStruct }
+  | { type: typeof T_LIST; element: number; value: ThriftValue[] };
+
+/** Fields by id; undefined fields are left out */
+type ThriftStruct = Array<[number, ThriftValue | undefined]>;
+
+const i32 = (value: number): ThriftValue => ({ type: T_I32, value });
+const i64 = (value: number): ThriftValue => ({ type: T_I64, value });
+const binary = (value: string): ThriftValue => ({ type: T_BINARY, value });
+const struct = (value: ThriftStruct): ThriftValue => ({ type: T_STRUCT, value });
+const list = (element: number, value: ThriftValue[]): ThriftValue => ({
+  type: T_LIST,
+  element,
+  value,
+});
+
+function varint(value: bigint): number[] {
+  const bytes: number[] = [];
+  let rest = value;
+  do {
+    let byte = Number(rest & 0x7fn);
+    rest >>= 7n;
+    if (rest > 0n) byte |= 0x80;
+    bytes.push(byte);
+  } while (rest > 0n);
+  return bytes;
+}
+
+function zigzag(value: number): number[] {
+  const n = BigInt(value);
+  return varint(n >= 0n ? n << 1n : (-n << 1n) - 1n);
+}
+
+function encodeValue(value: ThriftValue, out: number[]): void {
+  switch (value.type) {
+    case T_I32:
+    case T_I64:
+      out.push(...zigzag(value.value));
+      return;
+    case T_BINARY: {
+      const bytes = Buffer.from(value.value, 'utf-8');
+      out.push(...varint(BigInt(bytes.length)), ...bytes);
+      return;
+    }
+    case T_STRUCT:
+      encodeStruct(value.value, out);
+      return;
+    case T_LIST:
+      if (value.value.length < 15) out.push((value.value.length << 4) | value.element);
+      else out.push(0xf0 | value.element, ...varint(BigInt(value.value.length)));
+      for (const item of value.value) encodeValue(item, out);
+      return;
+  }
+}
+
+function encodeStruct(fields: ThriftStruct, out: number[]): void {
+  let last = 0;
+  for (const [id, value] of fields) {
+    if (value === undefined) continue;
+    const delta = id - last;
+    if (delta > 0 && delta <= 15) out.push((delta << 4) | value.type);
+    else out.push(value.type, ...zigzag(id));
+    encodeValue(value, out);
+    last = id;
+  }
+  out.push(0);
+}
+
+function thrift(fields: ThriftStruct): Buffer {
+  const out: number[] = [];
+  encodeStruct(fields, out);
+  return Buffer.from(out);
+}
+
+/**
+ * Definition levels (1 = present) in the RLE/bit-packed hybrid encoding, as bit-packed runs
+ * of 8 values, prefixed with their length
+ */
+function definitionLevels(present: boolean[]): Buffer {
+  const bits = Buffer.alloc(Math.ceil(present.length / 8));
+  present.forEach((value, index) => {
+    if (value) bits[index >> 3] |= 1 << (index & 7);
+  });
+  // One bit-packed run: its header is the number of 8-value groups, shifted, with the low bit set
+  const header = Buffer.from(varint(BigInt((bits.length << 1) | 1)));
+  const encoded = Buffer.concat([header, bits]);
+  const length = Buffer.alloc(4);
+  length.writeUInt32LE(encoded.length);
+  return Buffer.concat([length, encoded]);
+}
+
+function plainValues(column: ParquetColumn): Buffer {
+  const parts: Buffer[] = [];
+  for (const value of column.values) {
+    if (value === null || value === undefined) continue;
+    if (column.type === 'int64') {
+      const bytes = Buffer.alloc(8);
+      bytes.writeBigInt64LE(BigInt(value));
+      parts.push(bytes);
+    } else {
+      const bytes = Buffer.from(String(value), 'utf-8');
+      const length = Buffer.alloc(4);
+      length.writeUInt32LE(bytes.length);
+      parts.push(length, bytes);
+    }
+  }
+  return Buffer.concat(parts);
+}
+
+/**
+ * Encode a table as a Parquet file
+ *
+ * @throws Error if the columns have different lengths, or a required column has a null
+ */
+export function writeParquet(columns: ParquetColumn[], createdBy = 'debug-run'): Buffer {
+  const rows = columns[0]?.values.length ?? 0;
+  const chunks: Buffer[] = [MAGIC];
+  let offset = MAGIC.length;
+  const columnChunks: ThriftValue[] = [];
+
+  for (const column of columns) {
+    if (column.values.length !== rows) {
+      throw new Error(
+        `Parquet column ${column.name} has ${column.values.length} values, not ${rows}`
+      );
+    }
+    const present = column.values.map((value) => value !== null && value !== undefined);
+    if (!column.optional && present.includes(false)) {
+      throw new Error(`Parquet column ${column.name} is required but has nulls`);
+    }
+
+    const page = Buffer.concat([
+      ...(column.optional ? [definitionLevels(present)] : []),
+      plainValues(column),
+    ]);
+    const header = thrift([
+      [1, i32(PAGE_DATA)],
+      [2, i32(page.length)],
+      [3, i32(page.length)],
+      [
+        5,
+        struct([
+          [1, i32(rows)],
+          [2, i32(ENCODING_PLAIN)],
+          [3, i32(ENCODING_RLE)],
+          [4, i32(ENCODING_RLE)],
+        ]),
+      ],
+    ]);
+
+    const size = header.length + page.length;
+    columnChunks.push(
+      struct([
+        [2, i64(offset)],
+        [
+          3,
+          struct([
+            [1, i32(PHYSICAL_TYPES[column.type])],
+            [2, list(T_I32, [i32(ENCODING_PLAIN), i32(ENCODING_RLE)])],
+            [3, list(T_BINARY, [binary(column.name)])],
+            [4, i32(CODEC_UNCOMPRESSED)],
+            [5, i64(rows)],
+            [6, i64(size)],
+            [7, i64(size)],
+            [9, i64(offset)],
+          ]),
+        ],
+      ])
+    );
+    chunks.push(header, page);
+    offset += size;
+  }
+
+  const schema: ThriftValue[] = [
+    struct([
+      [4, binary('schema')],
+      [5, i32(columns.length)],
+    ]),
+    ...columns.map((column) =>
+      struct([
+        [1, i32(PHYSICAL_TYPES[column.type])],
+        [3, i32(column.optional ? OPTIONAL : REQUIRED)],
+        [4, binary(column.name)],
+        [6, column.type === 'string' ? i32(CONVERTED_UTF8) : undefined],
+      ])
+    ),
+  ];
+  const footer = thrift([
+    [1, i32(1)],
+    [2, list(T_STRUCT, schema)],
+    [3, i64(rows)],
+    [
+      4,
+      list(T_STRUCT, [
+        struct([
+          [1, list(T_STRUCT, columnChunks)],
+          [2, i64(offset - MAGIC.length)],
+          [3, i64(rows)],
+        ]),
+      ]),
+    ],
+    [6, binary(createdBy)],
+  ]);
+
+  const footerLength = Buffer.alloc(4);
+  footerLength.writeUInt32LE(footer.length);
+  return Buffer.concat([...chunks, footer, footerLength, MAGIC]);
+}
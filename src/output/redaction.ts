@@ -0,0 +1,154 @@
+/**
+ * Secrets Redaction (--redact)
+ *
+ * Masks credentials before anything is written, so result files, artifacts and stores can be
+ * shared or uploaded. Three kinds of rules:
+ * - variable name patterns (password|token|secret...): a local, evaluation, --set target or
+ *   DAP variable with a matching name has its value replaced
+ * - environment variables (named, or with a name matching a pattern): their values are
+ *   replaced wherever they appear, including the program's output and the launch request
+ * - value patterns (e.g., ghp_[A-Za-z0-9]{36}): matching text is replaced in any string
+ */
+
+import type { ProtocolMessage } from '../dap/protocol.js';
+import type { DebugEvent } from './events.js';
+
+export const REDACTED = '[REDACTED]';
+
+/** Names that usually hold credentials (--redact without --redact-name) */
+export const DEFAULT_REDACT_NAMES = [
+  'password|passwd|secret|token|api[_-]?key|credential|private[_-]?key|access[_-]?key',
+];
+
+/** Shorter environment values (e.g., "1", "dev") are too likely to occur by chance */
+const MIN_SECRET_LENGTH = 4;
+
+/** Fields of a captured value that hold what it contains */
+const VALUE_FIELDS = ['value', 'result', 'oldValue', 'newValue'];
+
+export interface RedactionRules {
+  /** Variable and environment variable name patterns (case-insensitive) */
+  names: string[];
+  /** Environment variables whose values are masked wherever they appear */
+  envNames?: string[];
+  /** Patterns masked wherever they match */
+  values?: string[];
+}
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return typeof value === 'object' && value !== null && !Array.isArray(value);
+}
+
+function escapeRegExp(text: string): string {
+  return text.replace(/[\\^$.*+?()[\]{}|]/g, '\\$&');
+}
+
+/** A VariableValue or AssignmentResult ({value}), or an EvaluationResult ({result}) */
+function isCapturedValue(value: unknown): value is Record<string, unknown> {
+  return isRecord(value) && ('value' in value || 'result' in value);
+}
+
+/**
+ * Compile a pattern, naming the option it came from in the error
+ *
+ * @throws Error if the pattern isn't a valid regular expression
+ */
+function compile(pattern: string, flags: string, option: string): RegExp {
+  try {
+    return new RegExp(pattern, flags);
+  } catch (error) {
+    throw new Error(`${option} "${pattern}": ${error instanceof Error ? error.message : error}`);
+  }
+}
+
+export class Redactor {
+  private names: RegExp[];
+  /** Environment values and value patterns, as one alternation */
+  private text?: RegExp;
+
+  /**
+   * @throws Error if a pattern isn't a valid regular expression
+   */
+  constructor(rules: RedactionRules, env: Record<string, string | undefined> = process.env) {
+    this.names = rules.names.map((pattern) => compile(pattern, 'i', '--redact-name'));
+    const envNames = new Set(rules.envNames);
+    const secrets = Object.entries(env)
+      .filter(([name]) => envNames.has(name) || this.matchesName(name))
+      .map(([, value]) => value)
+      .filter((value): value is string => value !== undefined && value.length >= MIN_SECRET_LENGTH)
+      // Longest first, so a secret containing another is masked whole
+      .sort((a, b) => b.length - a.length)
+      .map(escapeRegExp);
+    const values = (rules.values ?? []).map((pattern) => {
+      const compiled = compile(pattern, '', '--redact-value');
+      if (compiled.test('')) {
+        throw new Error(`--redact-value "${pattern}" matches empty text`);
+      }
+      return compiled.source;
+    });
+    const alternatives = [...new Set(secrets), ...values];
+    if (alternatives.length > 0) {
+      this.text = new RegExp(alternatives.map((source) => `(?:${source})`).join('|'), 'g');
+    }
+  }
+
+  /**
+   * Whether a variable name is one whose value is masked
+   */
+  matchesName(name: string): boolean {
+    return this.names.some((pattern) => pattern.test(name));
+  }
+
+  /**
+   * Mask environment secrets and value patterns in text
+   */
+  redactText(text: string): string {
+    return this.text ? text.replace(this.text, REDACTED) : text;
+  }
+
+  /**
+   * A copy of an event with its secrets masked
+   */
+  redactEvent<T extends DebugEvent>(event: T): T {
+    return this.redactValue(event) as T;
+  }
+
+  /**
+   * A copy of a DAP message with its secrets masked (variables responses, setVariable and
+   * evaluate arguments, the launch request's environment, output events)
+   */
+  redactMessage<T extends ProtocolMessage>(message: T): T {
+    return this.redactValue(message) as T;
+  }
+
+  private redactValue(value: unknown): unknown {
+    if (typeof value === 'string') return this.redactText(value);
+    if (Array.isArray(value)) return value.map((item) => this.redactValue(item));
+    if (!isRecord(value)) return value;
+
+    // A {name, value} pair: a DAP variable, a variable change or an assignment
+    const named = typeof value.name === 'string' && this.matchesName(value.name);
+    const redacted: Record<string, unknown> = {};
+    for (const [key, child] of Object.entries(value)) {
+      if (named && VALUE_FIELDS.includes(key)) {
+        redacted[key] = REDACTED;
+      } else if (isCapturedValue(child) && this.matchesName(key)) {
+        // Keyed by name: locals, evaluations, --set assignments
+        redacted[key] = this.mask(child);
+      } else {
+        redacted[key] = this.redactValue(child);
+      }
+    }
+    return redacted;
+  }
+
+  private mask(captured: Record<string, unknown>): Record<string, unknown> {
+    const masked: Record<string, unknown> = { ...captured };
+    for (const field of VALUE_FIELDS) {
+      if (field in masked) masked[field] = REDACTED;
+    }
+    // The children can't be expanded without revealing them
+    delete masked.variablesReference;
+    return masked;
+  }
+}
@@ -0,0 +1,176 @@
+/**
+ * Result Files (--out-dir, --name)
+ *
+ * Keeps repeated investigation runs organized: each run writes its events to a
+ * timestamped, named NDJSON file, a "latest" symlink points at the newest run, and
+ * index.json lists every run with its outcome.
+ */
+
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+import type { DebugEvent } from './events.js';
+import { logger } from '../util/logger.js';
+
+export const RESULT_INDEX_VERSION = 1;
+export const RESULT_INDEX_FILE = 'index.json';
+export const LATEST_LINK = 'latest';
+/** Used when --name is given without --out-dir */
+export const DEFAULT_RESULTS_DIR = '.debug-run/results';
+
+export interface ResultRunEntry {
+  name: string;
+  /** File name within the results directory */
+  file: string;
+  startedAt: string;
+  endedAt?: string;
+  status: 'running' | 'completed' | 'failed';
+  adapter?: string;
+  program?: string;
+  exitCode?: number | null;
+  breakpointsHit?: number;
+  exceptionsCaught?: number;
+}
+
+export interface ResultIndex {
+  version: number;
+  runs: ResultRunEntry[];
+}
+
+/**
+ * Make a run name safe to use in a file name
+ */
+export function sanitizeRunName(name: string): string {
+  const safe = name
+    .trim()
+    .replace(/[^A-Za-z0-9._-]+/g, '-')
+    .replace(/^[-.]+|-+$/g, '');
+  return safe || 'session';
+}
+
+/**
+ * Local timestamp for result file names (20261014-093015)
+ */
+export function formatRunTimestamp(date: Date): string {
+  const pad = (n: number) => String(n).padStart(2, '0');
+  return (
+    `${date.getFullYear()}${pad(date.getMonth() + 1)}${pad(date.getDate())}-` +
+    `${pad(date.getHours())}${pad(date.getMinutes())}${pad(date.getSeconds())}`
+  );
+}
+
+/**
+ * Read a results directory's index (empty if it doesn't exist yet)
+ */
+export function readResultIndex(dir: string): ResultIndex {
+  const file = path.join(dir, RESULT_INDEX_FILE);
+  if (!fs.existsSync(file)) {
+    return { version: RESULT_INDEX_VERSION, runs: [] };
+  }
+  try {
+    const index = JSON.parse(fs.readFileSync(file, 'utf-8')) as ResultIndex;
+    if (Array.isArray(index.runs)) {
+      return { version: RESULT_INDEX_VERSION, runs: index.runs };
+    }
+  } catch {
+    // Fall through and start a new index
+  }
+  logger.warn('results', 'index.json is unreadable, starting a new one', { file });
+  return { version: RESULT_INDEX_VERSION, runs: [] };
+}
+
+function writeResultIndex(dir: string, index: ResultIndex): void {
+  // Write then rename, so a crash never leaves a truncated index
+  const file = path.join(dir, RESULT_INDEX_FILE);
+  const temp = `${file}.${process.pid}.tmp`;
+  fs.writeFileSync(temp, JSON.stringify(index, null, 2) + '\n');
+  fs.renameSync(temp, file);
+}
+
+/**
+ * One run's result file and index entry
+ */
+export class ResultRun {
+  readonly file: string;
+  readonly stream: fs.WriteStream;
+  private entry: ResultRunEntry;
+
+  private constructor(private dir: string, entry: ResultRunEntry) {
+    this.entry = entry;
+    this.file = path.join(dir, entry.file);
+    this.stream = fs.createWriteStream(this.file, { flags: 'wx' });
+  }
+
+  /**
+   * Create a new result file in dir, add it to the index and point "latest" at it
+   */
+  static start(dir: string, name: string, now: Date = new Date()): ResultRun {
+    fs.mkdirSync(dir, { recursive: true });
+
+    const base = `${sanitizeRunName(name)}-${formatRunTimestamp(now)}`;
+    let file = `${base}.ndjson`;
+    for (let n = 2; fs.existsSync(path.join(dir, file)); n++) {
+      file = `${base}-${n}.ndjson`;
+    }
+
+    const run = new ResultRun(dir, {
+      name,
+      file,
+      startedAt: now.toISOString(),
+      status: 'running',
+    });
+    run.updateIndex();
+    run.updateLatestLink();
+    return run;
+  }
+
+  /**
+   * Pick up run details from the session's events
+   */
+  observe(event: DebugEvent): void {
+    if (event.type === 'session_start') {
+      this.entry.adapter = event.adapter;
+      this.entry.program = event.program;
+    } else if (event.type === 'session_end') {
+      this.entry.exitCode = event.summary.exitCode;
+      this.entry.breakpointsHit = event.summary.breakpointsHit;
+      this.entry.exceptionsCaught = event.summary.exceptionsCaught;
+    }
+  }
+
+  /**
+   * Flush the result file and record the outcome in the index
+   */
+  async finish(failed: boolean): Promise<void> {
+    await new Promise<void>((resolve) => this.stream.end(resolve));
+    this.entry.endedAt = new Date().toISOString();
+    this.entry.status = failed ? 'failed' : 'completed';
+    this.updateIndex();
+  }
+
+  private updateIndex(): void {
+    const index = readResultIndex(this.dir);
+    const existing = index.runs.findIndex((run) => run.file === this.entry.file);
+    if (existing === -1) {
+      index.runs.push({ ...this.entry });
+    } else {
+      index.runs[existing] = { ...this.entry };
+    }
+    writeResultIndex(this.dir, index);
+  }
+
+  private updateLatestLink(): void {
+    const link = path.join(this.dir, LATEST_LINK);
+    try {
+      if (fs.lstatSync(link, { throwIfNoEntry: false })) {
+        fs.unlinkSync(link);
+      }
+      // Relative target, so the directory can be moved or shared
+      fs.symlinkSync(this.entry.file, link);
+    } catch (error) {
+      // Symlinks can need extra privileges on Windows; index.json still lists the run
+      logger.warn('results', 'could not update the latest link', {
+        error: error instanceof Error ? error.message : String(error),
+      });
+    }
+  }
+}
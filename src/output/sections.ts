@@ -0,0 +1,112 @@
+/**
+ * Output Sections (--include stacks,variables --exclude modules,threads)
+ *
+ * --include and --exclude take section names as well as event types, so a high-frequency
+ * automated run can ask for exactly the parts of each event it reads. A section is a kind of
+ * data wherever it appears: "stacks" is every event's stack trace, "variables" every locals
+ * capture. Sections only shape what is written; stores, artifacts and other listeners still
+ * get every event in full.
+ */
+
+import type { DebugEvent } from './events.js';
+
+export const OUTPUT_SECTIONS = [
+  'stacks',
+  'variables',
+  'evaluations',
+  'source',
+  'output',
+  'threads',
+  'modules',
+] as const;
+
+export type OutputSection = (typeof OUTPUT_SECTIONS)[number];
+
+/** Event fields each section covers (session_end summary fields are listed separately) */
+const EVENT_FIELDS: Record<OutputSection, string[]> = {
+  stacks: ['stackTrace'],
+  variables: ['locals', 'changes', 'registers'],
+  evaluations: ['evaluations'],
+  source: ['source'],
+  output: ['recentOutput'],
+  threads: ['threads'],
+  modules: [],
+};
+
+const SUMMARY_FIELDS: Partial<Record<OutputSection, string[]>> = {
+  threads: ['threads'],
+  modules: ['modules'],
+};
+
+export interface OutputFilters {
+  /** Event types to emit (undefined for all) */
+  includeTypes?: Set<string>;
+  excludeTypes: Set<string>;
+  /** Sections left out of every event */
+  omittedSections: Set<OutputSection>;
+}
+
+export function isOutputSection(name: string): name is OutputSection {
+  return (OUTPUT_SECTIONS as readonly string[]).includes(name);
+}
+
+function splitNames(values: string[] | undefined): string[] {
+  return (values ?? [])
+    .flatMap((value) => value.split(','))
+    .map((name) => name.trim())
+    .filter(Boolean);
+}
+
+/**
+ * Sort --include/--exclude values (space- or comma-separated) into event types and sections.
+ * An --include of sections only keeps every event type; sections not included are omitted.
+ */
+export function parseOutputFilters(include?: string[], exclude?: string[]): OutputFilters {
+  const included = splitNames(include);
+  const excluded = splitNames(exclude);
+  const includeTypes = included.filter((name) => !isOutputSection(name));
+  const includeSections = included.filter(isOutputSection);
+
+  const omittedSections = new Set(excluded.filter(isOutputSection));
+  if (includeSections.length > 0) {
+    for (const section of OUTPUT_SECTIONS) {
+      if (!includeSections.includes(section)) omittedSections.add(section);
+    }
+  }
+
+  return {
+    includeTypes: includeTypes.length > 0 ? new Set(includeTypes) : undefined,
+    excludeTypes: new Set(excluded.filter((name) => !isOutputSection(name))),
+    omittedSections,
+  };
+}
+
+/**
+ * An event without the omitted sections' fields (the event itself when nothing is omitted)
+ */
+export function omitSections(event: DebugEvent, omitted: Set<OutputSection>): DebugEvent {
+  if (omitted.size === 0) return event;
+
+  const fields = [...omitted].flatMap((section) => EVENT_FIELDS[section]);
+  const result: Record<string, unknown> = { ...event };
+  for (const field of fields) delete result[field];
+
+  // Crash and signal reports keep their thread list without stacks when only stacks are omitted
+  if (omitted.has('stacks') && Array.isArray(result.threads)) {
+    result.threads = result.threads.map((thread: Record<string, unknown>) => {
+      const withoutStack = { ...thread };
+      delete withoutStack.stackTrace;
+      return withoutStack;
+    });
+  }
+
+  if (event.type === 'session_end') {
+    const summary: Record<string, unknown> = { ...event.summary };
+    for (const section of omitted) {
+      for (const field of SUMMARY_FIELDS[section] ?? []) delete summary[field];
+    }
+    result.summary = summary;
+  }
+
+  return result as unknown as DebugEvent;
+}
@@ -0,0 +1,38 @@
+/**
+ * Live Status Line
+ *
+ * One terminal line, rewritten in place, that says what the session is waiting on while
+ * nothing else is happening ("launching...", "Loading source maps (40%)"). The CLI only
+ * uses it when stderr is a terminal that events and program output don't also go to.
+ */
+
+/** Erase the current terminal line and return to its start */
+const CLEAR_LINE = '\r\x1b[2K';
+
+export class StatusLine {
+  private stream: NodeJS.WriteStream;
+  private shown = false;
+
+  constructor(stream: NodeJS.WriteStream = process.stderr) {
+    this.stream = stream;
+  }
+
+  /**
+   * Replace the status line, truncated to the terminal width
+   */
+  update(text: string): void {
+    const width = (this.stream.columns || 80) - 1;
+    const line = text.length > width ? `${text.slice(0, width - 1)}…` : text;
+    this.stream.write(CLEAR_LINE + line);
+    this.shown = true;
+  }
+
+  /**
+   * Remove the status line, if one is shown
+   */
+  clear(): void {
+    if (!this.shown) return;
+    this.stream.write(CLEAR_LINE);
+    this.shown = false;
+  }
+}
@@ -0,0 +1,278 @@
+/**
+ * Session Timeline (--timeline)
+ *
+ * Records where a session's time went as a Chrome trace (the JSON Trace Event Format that
+ * chrome://tracing and Perfetto open), one track each for:
+ * - the session and its phases (connecting, launching, running, ...)
+ * - the program: launch, each pause from the stop to the request that resumed it, exit
+ * - breakpoints as the adapter verified (or rejected) them
+ * - the stops debug-run reported (breakpoint hits, exceptions, steps, ...)
+ * - program output, merged into bursts
+ */
+
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+import type { ProtocolMessage, Request, Event } from '../dap/protocol.js';
+import type { DebugEvent, SourceLocation } from './events.js';
+import type { SpanInfo, SpanObserver } from '../util/logger.js';
+
+export interface TraceEvent {
+  name: string;
+  cat: string;
+  /** X: a slice with a duration, i: an instant, M: track metadata */
+  ph: 'X' | 'i' | 'M';
+  /** Microseconds since the first event */
+  ts: number;
+  dur?: number;
+  pid: number;
+  tid: number;
+  /** Instant events are drawn on their track */
+  s?: 't';
+  args?: Record<string, unknown>;
+}
+
+type Track = 'session' | 'program' | 'breakpoints' | 'stops' | 'output';
+
+/** Each track is drawn as a thread of one process */
+const TRACK_IDS: Record<Track, number> = {
+  session: 1,
+  program: 2,
+  breakpoints: 3,
+  stops: 4,
+  output: 5,
+};
+
+const PID = 1;
+
+/** Output written within this long of the previous output joins its burst */
+const OUTPUT_BURST_GAP_MS = 100;
+
+/** Requests that let a paused program run again */
+const RESUME_COMMANDS = new Set([
+  'continue',
+  'next',
+  'stepIn',
+  'stepOut',
+  'stepBack',
+  'reverseContinue',
+  'goto',
+  'restartFrame',
+]);
+
+/** An event recorded in ms since the epoch, converted when the trace is built */
+interface RecordedEvent {
+  name: string;
+  track: Track;
+  at: number;
+  durationMs?: number;
+  args?: Record<string, unknown>;
+}
+
+function describeLocation(location: SourceLocation): string {
+  return `${path.basename(location.file || location.sourceName || '?')}:${location.line}`;
+}
+
+export class SessionTimeline implements SpanObserver {
+  private recorded: RecordedEvent[] = [];
+  private paused: { at: number; reason: string; threadId?: number } | null = null;
+  private burst: { at: number; end: number; bytes: number; writes: number } | null = null;
+
+  spanStarted(): void {
+    // Spans are recorded when they end, with their duration
+  }
+
+  spanEnded(span: SpanInfo, endTime: number, fields?: Record<string, unknown>): void {
+    this.recorded.push({
+      name: span.name,
+      track: 'session',
+      at: span.startTime,
+      durationMs: endTime - span.startTime,
+      ...((span.fields || fields) && { args: { ...span.fields, ...fields } }),
+    });
+  }
+
+  /**
+   * Place pauses: a stopped event starts one, the request that resumes the program (or the
+   * adapter's own continued, terminated or exited event) ends it
+   */
+  protocolMessage(
+    direction: 'send' | 'receive',
+    message: ProtocolMessage,
+    _session?: string,
+    now: number = Date.now()
+  ): void {
+    if (direction === 'send' && message.type === 'request') {
+      const { command } = message as Request;
+      if (RESUME_COMMANDS.has(command)) this.resume(now, command);
+      return;
+    }
+    if (direction !== 'receive' || message.type !== 'event') return;
+
+    const { event, body } = message as Event;
+    if (event === 'stopped') {
+      const stopped = body as { reason?: string; threadId?: number } | undefined;
+      this.paused ??= { at: now, reason: stopped?.reason ?? 'paused', threadId: stopped?.threadId };
+    } else if (event === 'continued' || event === 'terminated' || event === 'exited') {
+      this.resume(now, event);
+    }
+  }
+
+  /**
+   * Record a debug event
+   */
+  recordEvent(event: DebugEvent): void {
+    const at = Date.parse(event.timestamp);
+
+    switch (event.type) {
+      case 'program_output':
+        this.recordOutput(at, Buffer.byteLength(event.output));
+        break;
+      case 'breakpoint_hit':
+      case 'exception_thrown':
+      case 'step_completed':
+      case 'assertion_failed':
+      case 'interrupt_snapshot':
+      case 'crash_report':
+      case 'signal_received':
+        this.recordStop(event.type, at, event.location, event.threadId);
+        break;
+      case 'trace_completed':
+        this.recordStop(event.type, at, event.finalLocation, event.threadId);
+        break;
+      case 'breakpoint_set':
+        this.recorded.push({
+          name: `${event.verified ? 'verified' : 'unverified'} ${describeLocation(event)}`,
+          track: 'breakpoints',
+          at,
+          args: {
+            id: event.id,
+            file: event.file,
+            line: event.line,
+            verified: event.verified,
+            ...(event.message && { message: event.message }),
+          },
+        });
+        break;
+      case 'process_launched':
+      case 'process_attached':
+        this.recorded.push({
+          name: event.type === 'process_launched' ? 'launched' : 'attached',
+          track: 'program',
+          at,
+          ...(event.pid !== undefined && { args: { pid: event.pid } }),
+        });
+        break;
+      case 'process_exited':
+        this.resume(at, 'exited');
+        this.recorded.push({
+          name: 'exited',
+          track: 'program',
+          at,
+          args: { exitCode: event.exitCode },
+        });
+        break;
+      case 'session_end':
+        this.resume(at, 'session_end');
+        this.flushOutput();
+        this.recorded.push({
+          name: 'session_end',
+          track: 'session',
+          at,
+          args: { reason: event.summary.termination.reason },
+        });
+        break;
+    }
+  }
+
+  /**
+   * The trace, with timestamps relative to the first event
+   */
+  build(): { traceEvents: TraceEvent[]; displayTimeUnit: 'ms' } {
+    this.flushOutput();
+    const recorded = [...this.recorded].sort((a, b) => a.at - b.at);
+    const origin = recorded[0]?.at ?? 0;
+
+    const metadata = Object.entries(TRACK_IDS).map(
+      ([name, tid]): TraceEvent => ({
+        name: 'thread_name',
+        cat: '__metadata',
+        ph: 'M',
+        ts: 0,
+        pid: PID,
+        tid,
+        args: { name },
+      })
+    );
+    const events = recorded.map(
+      (event): TraceEvent => ({
+        name: event.name,
+        cat: event.track,
+        ...(event.durationMs !== undefined
+          ? { ph: 'X' as const, dur: event.durationMs * 1000 }
+          : { ph: 'i' as const, s: 't' as const }),
+        ts: (event.at - origin) * 1000,
+        pid: PID,
+        tid: TRACK_IDS[event.track],
+        ...(event.args && { args: event.args }),
+      })
+    );
+    return { traceEvents: [...metadata, ...events], displayTimeUnit: 'ms' };
+  }
+
+  /**
+   * Write the trace to a file
+   */
+  write(file: string): void {
+    fs.writeFileSync(file, JSON.stringify(this.build()));
+  }
+
+  private recordStop(name: string, at: number, location: SourceLocation, threadId: number): void {
+    this.recorded.push({
+      name,
+      track: 'stops',
+      at,
+      args: {
+        location: describeLocation(location),
+        ...(location.function && { function: location.function }),
+        threadId,
+      },
+    });
+  }
+
+  private resume(at: number, resumedBy: string): void {
+    if (!this.paused) return;
+    const { reason, threadId } = this.paused;
+    this.recorded.push({
+      name: `paused (${reason})`,
+      track: 'program',
+      at: this.paused.at,
+      durationMs: Math.max(0, at - this.paused.at),
+      args: { reason, resumedBy, ...(threadId !== undefined && { threadId }) },
+    });
+    this.paused = null;
+  }
+
+  private recordOutput(at: number, bytes: number): void {
+    if (this.burst && at - this.burst.end <= OUTPUT_BURST_GAP_MS) {
+      this.burst.end = at;
+      this.burst.bytes += bytes;
+      this.burst.writes++;
+      return;
+    }
+    this.flushOutput();
+    this.burst = { at, end: at, bytes, writes: 1 };
+  }
+
+  private flushOutput(): void {
+    if (!this.burst) return;
+    const { at, end, bytes, writes } = this.burst;
+    this.recorded.push({
+      name: 'output',
+      track: 'output',
+      at,
+      durationMs: end - at,
+      args: { bytes, writes },
+    });
+    this.burst = null;
+  }
+}
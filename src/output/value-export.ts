@@ -0,0 +1,234 @@
+/**
+ * Captured-Value Export (--export-values values.csv, values.parquet)
+ *
+ * Flattens every local and evaluation captured at a session's stops into one table, a row
+ * per value, so values can be compared across hits and runs in pandas, DuckDB or a
+ * spreadsheet instead of by reading NDJSON. Expanded objects and collections are flattened to
+ * their members (order.Total, items[0]), and each row records the stop it came from.
+ */
+
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+import type { DebugEvent, EvaluationResult, SourceLocation, VariableValue } from './events.js';
+import { writeParquet, type ParquetColumn } from './parquet.js';
+
+export type ValueExportFormat = 'csv' | 'parquet';
+
+/** Events that carry captured locals and evaluations */
+export type StopEvent = Extract<
+  DebugEvent,
+  {
+    type:
+      | 'breakpoint_hit'
+      | 'exception_thrown'
+      | 'step_completed'
+      | 'assertion_failed'
+      | 'trace_completed'
+      | 'interrupt_snapshot'
+      | 'crash_report'
+      | 'signal_received';
+  }
+>;
+
+const STOP_EVENT_TYPES: ReadonlySet<DebugEvent['type']> = new Set([
+  'breakpoint_hit',
+  'exception_thrown',
+  'step_completed',
+  'assertion_failed',
+  'trace_completed',
+  'interrupt_snapshot',
+  'crash_report',
+  'signal_received',
+]);
+
+/**
+ * Whether an event carries captured locals and evaluations
+ */
+export function isStopEvent(event: DebugEvent): event is StopEvent {
+  return STOP_EVENT_TYPES.has(event.type);
+}
+
+export interface CapturedValueRow {
+  /** The run's name (--name, or the program's) */
+  session: string;
+  /** When the session started */
+  sessionStart: string;
+  /** 1 for the session's first stop, 2 for the next, ... */
+  hit: number;
+  /** Event the value was captured in (breakpoint_hit, exception_thrown, ...) */
+  event: string;
+  /** The breakpoint's id, for breakpoint hits */
+  breakpoint?: number;
+  /** The breakpoint's label (-b "file:line#label") */
+  label?: string;
+  /** Stop location as file:line */
+  location: string;
+  timestamp: string;
+  /** local or evaluation */
+  kind: 'local' | 'evaluation';
+  /** Variable or expression, with the path to a member (order.Total, items[0]) */
+  name: string;
+  type?: string;
+  value: string;
+}
+
+/** Columns in file order, with the row field each comes from */
+const COLUMNS: Array<{
+  name: string;
+  field: keyof CapturedValueRow;
+  type: ParquetColumn['type'];
+  optional?: boolean;
+}> = [
+  { name: 'session', field: 'session', type: 'string' },
+  { name: 'session_start', field: 'sessionStart', type: 'string' },
+  { name: 'hit', field: 'hit', type: 'int64' },
+  { name: 'event', field: 'event', type: 'string' },
+  { name: 'breakpoint', field: 'breakpoint', type: 'int64', optional: true },
+  { name: 'label', field: 'label', type: 'string', optional: true },
+  { name: 'location', field: 'location', type: 'string' },
+  { name: 'timestamp', field: 'timestamp', type: 'string' },
+  { name: 'kind', field: 'kind', type: 'string' },
+  { name: 'name', field: 'name', type: 'string' },
+  { name: 'type', field: 'type', type: 'string', optional: true },
+  { name: 'value', field: 'value', type: 'string' },
+];
+
+/**
+ * The export format for a file, by its extension
+ *
+ * @throws Error for an extension other than .csv or .parquet
+ */
+export function valueExportFormat(file: string): ValueExportFormat {
+  const extension = path.extname(file).toLowerCase();
+  if (extension === '.csv') return 'csv';
+  if (extension === '.parquet') return 'parquet';
+  throw new Error(`--export-values "${file}": use a .csv or .parquet file`);
+}
+
+/** A leaf value as text: strings as-is, anything else as JSON */
+function valueText(value: unknown): string {
+  return typeof value === 'string' ? value : (JSON.stringify(value) ?? String(value));
+}
+
+/**
+ * Rows for a captured value and, for an expanded object or collection, each of its members
+ */
+export function flattenValue(
+  name: string,
+  variable: VariableValue
+): Array<{ name: string; type?: string; value: string }> {
+  const value = variable.value;
+  if (typeof value !== 'object' || value === null) {
+    return [{ name, type: variable.type, value: valueText(value) }];
+  }
+
+  // Collections are { type, count, items }, objects map member names to values
+  const record = value as Record<string, unknown>;
+  if (Array.isArray(record.items) && 'count' in record) {
+    const items = record.items as VariableValue[];
+    const rows = items.flatMap((item, index) => flattenValue(`${name}[${index}]`, item));
+    return rows.length > 0 ? rows : [{ name, type: variable.type, value: '[]' }];
+  }
+  const members = Object.entries(record as Record<string, VariableValue>);
+  const rows = members.flatMap(([member, child]) => flattenValue(`${name}.${member}`, child));
+  return rows.length > 0 ? rows : [{ name, type: variable.type, value: '{}' }];
+}
+
+export function evaluationRows(
+  expression: string,
+  evaluation: EvaluationResult
+): Array<{ name: string; type?: string; value: string }> {
+  if (evaluation.error) {
+    return [{ name: expression, type: 'error', value: evaluation.error }];
+  }
+  if (typeof evaluation.value === 'object' && evaluation.value !== null) {
+    return flattenValue(expression, { type: evaluation.type ?? '', value: evaluation.value });
+  }
+  return [{ name: expression, type: evaluation.type, value: evaluation.result }];
+}
+
+/**
+ * Where a stop event happened (a trace, where it ended)
+ */
+export function stopLocation(event: StopEvent): SourceLocation {
+  return event.type === 'trace_completed' ? event.finalLocation : event.location;
+}
+
+function csvField(value: string | number | undefined): string {
+  if (value === undefined) return '';
+  const text = String(value);
+  return /[",\r\n]/.test(text) ? `"${text.replace(/"/g, '""')}"` : text;
+}
+
+/**
+ * Collects captured values from a session's events (register with OutputFormatter.onEvent)
+ */
+export class ValueExport {
+  readonly rows: CapturedValueRow[] = [];
+  private sessionStart = '';
+  private stops = 0;
+
+  constructor(private session: string) {}
+
+  observe(event: DebugEvent): void {
+    if (event.type === 'session_start') {
+      this.sessionStart = event.timestamp;
+      return;
+    }
+    if (isStopEvent(event)) {
+      this.recordStop(event);
+    }
+  }
+
+  private recordStop(event: StopEvent): void {
+    const location = stopLocation(event);
+    const stop = {
+      session: this.session,
+      sessionStart: this.sessionStart,
+      hit: ++this.stops,
+      event: event.type,
+      ...(event.type === 'breakpoint_hit' && event.id !== undefined && { breakpoint: event.id }),
+      ...('label' in event && event.label && { label: event.label }),
+      location: `${location.file}:${location.line}`,
+      timestamp: event.timestamp,
+    };
+    for (const [name, variable] of Object.entries(event.locals)) {
+      for (const row of flattenValue(name, variable)) {
+        this.rows.push({ ...stop, kind: 'local', ...row });
+      }
+    }
+    const evaluations = 'evaluations' in event ? event.evaluations : undefined;
+    for (const [expression, evaluation] of Object.entries(evaluations ?? {})) {
+      for (const row of evaluationRows(expression, evaluation)) {
+        this.rows.push({ ...stop, kind: 'evaluation', ...row });
+      }
+    }
+  }
+
+  toCsv(): string {
+    const lines = [COLUMNS.map((column) => column.name).join(',')];
+    for (const row of this.rows) {
+      lines.push(COLUMNS.map((column) => csvField(row[column.field])).join(','));
+    }
+    return lines.join('\n') + '\n';
+  }
+
+  toParquet(): Buffer {
+    return writeParquet(
+      COLUMNS.map((column) => ({
+        name: column.name,
+        type: column.type,
+        optional: column.optional,
+        values: this.rows.map((row) => row[column.field]),
+      }))
+    );
+  }
+
+  /**
+   * Write the table, as CSV or Parquet by the file's extension
+   */
+  write(file: string): void {
+    const format = valueExportFormat(file);
+    fs.writeFileSync(file, format === 'csv' ? this.toCsv() : this.toParquet());
+  }
+}
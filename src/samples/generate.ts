@@ -0,0 +1,630 @@
+/**
+ * Sample Projects (samples generate)
+ *
+ * Writes a small, ready-to-build app for one of the supported languages, with its good
+ * breakpoint lines marked in the source and a .debug-run.toml that sets one of them, so a
+ * new user or an integration test has a known-good target for an adapter without cloning
+ * the debug-run repository. Every sample does the same thing: it prices a few orders,
+ * discounting large ones, and rejects one with an invalid item.
+ */
+
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+import { PROJECT_CONFIG_FILE } from '../util/config.js';
+
+export interface SampleProject {
+  language: string;
+  /** Adapter to debug it with (-a) */
+  adapter: string;
+  /** Files to write, by path relative to the sample directory */
+  files: Record<string, string>;
+  /** The file with the marked breakpoint lines */
+  source: string;
+  /** Build command, if the sample has to be built */
+  build?: string;
+  /** What to pass to debug-run as the program */
+  program: string;
+  /** The marked line .debug-run.toml sets a breakpoint on (its description) */
+  defaultBreakpoint: string;
+  /** Expressions worth evaluating at that line */
+  evals: string[];
+}
+
+/** A "Good breakpoint: <description>" comment, on the line before the one it marks */
+const MARKER_PATTERN = /^\s*(?:\/\/|#|\/\*)\s*Good breakpoint: (.+?)\s*(?:\*\/)?$/;
+
+const JAVASCRIPT = `/**
+ * Sample app for debug-run: prices a few orders, discounting large ones
+ *
+ * Good breakpoint lines are marked below; .debug-run.toml sets one of them. Debug with:
+ *
+ *   npx debug-run sample_app.js -a node
+ */
+
+const DISCOUNT_THRESHOLD = 100;
+const TIER_DISCOUNTS = { bronze: 0, silver: 0.05, gold: 0.1 };
+
+const orders = [
+  {
+    id: 'ORD-001',
+    tier: 'gold',
+    items: [
+      { sku: 'WIDGET', quantity: 2, price: 25 },
+      { sku: 'GADGET', quantity: 1, price: 75 },
+    ],
+  },
+  { id: 'ORD-002', tier: 'bronze', items: [{ sku: 'CABLE', quantity: 3, price: 5 }] },
+  { id: 'ORD-003', tier: 'silver', items: [{ sku: 'WIDGET', quantity: 0, price: 25 }] },
+];
+
+function calculateSubtotal(order) {
+  let subtotal = 0;
+  for (const item of order.items) {
+    if (item.quantity <= 0) {
+      // Good breakpoint: an invalid order is rejected
+      throw new Error(\`\${order.id}: \${item.sku} has quantity \${item.quantity}\`);
+    }
+    subtotal += item.quantity * item.price;
+  }
+  return subtotal;
+}
+
+function calculateDiscount(subtotal, tier) {
+  const rate = subtotal >= DISCOUNT_THRESHOLD ? TIER_DISCOUNTS[tier] : 0;
+  const discount = subtotal * rate;
+  // Good breakpoint: the discount for the customer's tier
+  return discount;
+}
+
+function processOrder(order) {
+  const subtotal = calculateSubtotal(order);
+  const discount = calculateDiscount(subtotal, order.tier);
+  const total = subtotal - discount;
+  // Good breakpoint: an order's totals
+  console.log(\`\${order.id}: subtotal=\${subtotal} discount=\${discount} total=\${total}\`);
+  return total;
+}
+
+function main() {
+  let revenue = 0;
+  for (const order of orders) {
+    try {
+      revenue += processOrder(order);
+    } catch (error) {
+      console.log(\`skipped \${error.message}\`);
+    }
+  }
+  console.log(\`revenue=\${revenue}\`);
+}
+
+main();
+`;
+
+const PYTHON = `"""
+Sample app for debug-run: prices a few orders, discounting large ones
+
+Good breakpoint lines are marked below; .debug-run.toml sets one of them. Debug with:
+
+  npx debug-run sample_app.py -a python
+"""
+
+from dataclasses import dataclass
+
+DISCOUNT_THRESHOLD = 100.0
+TIER_DISCOUNTS = {"bronze": 0.0, "silver": 0.05, "gold": 0.1}
+
+
+@dataclass
+class Item:
+    sku: str
+    quantity: int
+    price: float
+
+
+@dataclass
+class Order:
+    id: str
+    tier: str
+    items: list[Item]
+
+
+ORDERS = [
+    Order("ORD-001", "gold", [Item("WIDGET", 2, 25.0), Item("GADGET", 1, 75.0)]),
+    Order("ORD-002", "bronze", [Item("CABLE", 3, 5.0)]),
+    Order("ORD-003", "silver", [Item("WIDGET", 0, 25.0)]),
+]
+
+
+def calculate_subtotal(order: Order) -> float:
+    subtotal = 0.0
+    for item in order.items:
+        if item.quantity <= 0:
+            # Good breakpoint: an invalid order is rejected
+            raise ValueError(f"{order.id}: {item.sku} has quantity {item.quantity}")
+        subtotal += item.quantity * item.price
+    return subtotal
+
+
+def calculate_discount(subtotal: float, tier: str) -> float:
+    rate = TIER_DISCOUNTS[tier] if subtotal >= DISCOUNT_THRESHOLD else 0.0
+    discount = subtotal * rate
+    # Good breakpoint: the discount for the customer's tier
+    return discount
+
+
+def process_order(order: Order) -> float:
+    subtotal = calculate_subtotal(order)
+    discount = calculate_discount(subtotal, order.tier)
+    total = subtotal - discount
+    # Good breakpoint: an order's totals
+    print(f"{order.id}: subtotal={subtotal} discount={discount} total={total}")
+    return total
+
+
+def main() -> None:
+    revenue = 0.0
+    for order in ORDERS:
+        try:
+            revenue += process_order(order)
+        except ValueError as error:
+            print(f"skipped {error}")
+    print(f"revenue={revenue}")
+
+
+if __name__ == "__main__":
+    main()
+`;
+
+const CSHARP = `// Sample app for debug-run: prices a few orders, discounting large ones
+//
+// Good breakpoint lines are marked below; .debug-run.toml sets one of them. Build and
+// debug with:
+//
+//   dotnet build
+//   npx debug-run bin/Debug/net8.0/SampleApp.dll -a dotnet
+
+using System;
+using System.Collections.Generic;
+
+record Item(string Sku, int Quantity, decimal Price);
+
+record Order(string Id, string Tier, List<Item> Items);
+
+static class Program
+{
+    const decimal DiscountThreshold = 100m;
+
+    static readonly Dictionary<string, decimal> TierDiscounts = new()
+    {
+        ["bronze"] = 0m,
+        ["silver"] = 0.05m,
+        ["gold"] = 0.1m,
+    };
+
+    static decimal CalculateSubtotal(Order order)
+    {
+        decimal subtotal = 0m;
+        foreach (var item in order.Items)
+        {
+            if (item.Quantity <= 0)
+            {
+                // Good breakpoint: an invalid order is rejected
+                throw new InvalidOperationException(
+                    $"{order.Id}: {item.Sku} has quantity {item.Quantity}");
+            }
+            subtotal += item.Quantity * item.Price;
+        }
+        return subtotal;
+    }
+
+    static decimal CalculateDiscount(decimal subtotal, string tier)
+    {
+        decimal rate = subtotal >= DiscountThreshold ? TierDiscounts[tier] : 0m;
+        decimal discount = subtotal * rate;
+        // Good breakpoint: the discount for the customer's tier
+        return discount;
+    }
+
+    static decimal ProcessOrder(Order order)
+    {
+        decimal subtotal = CalculateSubtotal(order);
+        decimal discount = CalculateDiscount(subtotal, order.Tier);
+        decimal total = subtotal - discount;
+        // Good breakpoint: an order's totals
+        Console.WriteLine($"{order.Id}: subtotal={subtotal} discount={discount} total={total}");
+        return total;
+    }
+
+    static void Main()
+    {
+        var orders = new List<Order>
+        {
+            new("ORD-001", "gold", new() { new("WIDGET", 2, 25m), new("GADGET", 1, 75m) }),
+            new("ORD-002", "bronze", new() { new("CABLE", 3, 5m) }),
+            new("ORD-003", "silver", new() { new("WIDGET", 0, 25m) }),
+        };
+        decimal revenue = 0m;
+        foreach (var order in orders)
+        {
+            try
+            {
+                revenue += ProcessOrder(order);
+            }
+            catch (InvalidOperationException error)
+            {
+                Console.WriteLine($"skipped {error.Message}");
+            }
+        }
+        Console.WriteLine($"revenue={revenue}");
+    }
+}
+`;
+
+const CSPROJ = `<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <OutputType>Exe</OutputType>
+    <TargetFramework>net8.0</TargetFramework>
+    <Nullable>enable</Nullable>
+    <DebugType>portable</DebugType>
+  </PropertyGroup>
+</Project>
+`;
+
+const C = `/*
+ * Sample app for debug-run: prices a few orders, discounting large ones
+ *
+ * Good breakpoint lines are marked below; .debug-run.toml sets one of them. Build and
+ * debug with:
+ *
+ *   make
+ *   npx debug-run ./sample_app -a lldb
+ */
+
+#include <stdio.h>
+#include <string.h>
+
+#define DISCOUNT_THRESHOLD 100.0
+
+struct item {
+  const char *sku;
+  int quantity;
+  double price;
+};
+
+struct order {
+  const char *id;
+  const char *tier;
+  struct item items[2];
+  int item_count;
+};
+
+static int calculate_subtotal(const struct order *order, double *subtotal) {
+  *subtotal = 0.0;
+  for (int i = 0; i < order->item_count; i++) {
+    const struct item *item = &order->items[i];
+    if (item->quantity <= 0) {
+      /* Good breakpoint: an invalid order is rejected */
+      printf("skipped %s: %s has quantity %d\\n", order->id, item->sku, item->quantity);
+      return -1;
+    }
+    *subtotal += item->quantity * item->price;
+  }
+  return 0;
+}
+
+static double calculate_discount(double subtotal, const char *tier) {
+  double rate = 0.0;
+  if (subtotal >= DISCOUNT_THRESHOLD) {
+    rate = strcmp(tier, "gold") == 0 ? 0.1 : strcmp(tier, "silver") == 0 ? 0.05 : 0.0;
+  }
+  double discount = subtotal * rate;
+  /* Good breakpoint: the discount for the customer's tier */
+  return discount;
+}
+
+static int process_order(const struct order *order, double *total) {
+  double subtotal;
+  if (calculate_subtotal(order, &subtotal) != 0) return -1;
+  double discount = calculate_discount(subtotal, order->tier);
+  *total = subtotal - discount;
+  /* Good breakpoint: an order's totals */
+  printf("%s: subtotal=%g discount=%g total=%g\\n", order->id, subtotal, discount, *total);
+  return 0;
+}
+
+int main(void) {
+  struct order orders[] = {
+      {"ORD-001", "gold", {{"WIDGET", 2, 25.0}, {"GADGET", 1, 75.0}}, 2},
+      {"ORD-002", "bronze", {{"CABLE", 3, 5.0}}, 1},
+      {"ORD-003", "silver", {{"WIDGET", 0, 25.0}}, 1},
+  };
+  double revenue = 0.0;
+  for (size_t i = 0; i < sizeof(orders) / sizeof(orders[0]); i++) {
+    double total;
+    if (process_order(&orders[i], &total) == 0) revenue += total;
+  }
+  printf("revenue=%g\\n", revenue);
+  return 0;
+}
+`;
+
+const MAKEFILE = `sample_app: sample_app.c
+\tcc -g -O0 -o sample_app sample_app.c
+
+clean:
+\trm -f sample_app
+`;
+
+const RUST = `//! Sample app for debug-run: prices a few orders, discounting large ones
+//!
+//! Good breakpoint lines are marked below; .debug-run.toml sets one of them. Build and
+//! debug with:
+//!
+//!   cargo build
+//!   npx debug-run ./target/debug/sample_app -a rust
+
+const DISCOUNT_THRESHOLD: f64 = 100.0;
+
+#[derive(Debug, Clone, Copy)]
+enum Tier {
+    Bronze,
+    Silver,
+    Gold,
+}
+
+#[derive(Debug)]
+struct Item {
+    sku: &'static str,
+    quantity: u32,
+    price: f64,
+}
+
+#[derive(Debug)]
+struct Order {
+    id: &'static str,
+    tier: Tier,
+    items: Vec<Item>,
+}
+
+fn calculate_subtotal(order: &Order) -> Result<f64, String> {
+    let mut subtotal = 0.0;
+    for item in &order.items {
+        if item.quantity == 0 {
+            // Good breakpoint: an invalid order is rejected
+            return Err(format!("{}: {} has quantity 0", order.id, item.sku));
+        }
+        subtotal += f64::from(item.quantity) * item.price;
+    }
+    Ok(subtotal)
+}
+
+fn calculate_discount(subtotal: f64, tier: Tier) -> f64 {
+    let rate = match tier {
+        _ if subtotal < DISCOUNT_THRESHOLD => 0.0,
+        Tier::Bronze => 0.0,
+        Tier::Silver => 0.05,
+        Tier::Gold => 0.1,
+    };
+    // Good breakpoint: the discount for the customer's tier
+    subtotal * rate
+}
+
+fn process_order(order: &Order) -> Result<f64, String> {
+    let subtotal = calculate_subtotal(order)?;
+    let discount = calculate_discount(subtotal, order.tier);
+    let total = subtotal - discount;
+    // Good breakpoint: an order's totals
+    println!("{}: subtotal={} discount={} total={}", order.id, subtotal, discount, total);
+    Ok(total)
+}
+
+fn main() {
+    let orders = vec![
+        Order {
+            id: "ORD-001",
+            tier: Tier::Gold,
+            items: vec![
+                Item { sku: "WIDGET", quantity: 2, price: 25.0 },
+                Item { sku: "GADGET", quantity: 1, price: 75.0 },
+            ],
+        },
+        Order {
+            id: "ORD-002",
+            tier: Tier::Bronze,
+            items: vec![Item { sku: "CABLE", quantity: 3, price: 5.0 }],
+        },
+        Order {
+            id: "ORD-003",
+            tier: Tier::Silver,
+            items: vec![Item { sku: "WIDGET", quantity: 0, price: 25.0 }],
+        },
+    ];
+    let mut revenue = 0.0;
+    for order in &orders {
+        match process_order(order) {
+            Ok(total) => revenue += total,
+            Err(error) => println!("skipped {}", error),
+        }
+    }
+    println!("revenue={}", revenue);
+}
+`;
+
+const CARGO_TOML = `[package]
+name = "sample_app"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+`;
+
+export const SAMPLE_PROJECTS: Record<string, SampleProject> = {
+  javascript: {
+    language: 'javascript',
+    adapter: 'node',
+    files: { 'sample_app.js': JAVASCRIPT },
+    source: 'sample_app.js',
+    program: 'sample_app.js',
+    defaultBreakpoint: "an order's totals",
+    evals: ['order.id', 'total'],
+  },
+  python: {
+    language: 'python',
+    adapter: 'python',
+    files: { 'sample_app.py': PYTHON },
+    source: 'sample_app.py',
+    program: 'sample_app.py',
+    defaultBreakpoint: "an order's totals",
+    evals: ['order.id', 'total'],
+  },
+  csharp: {
+    language: 'csharp',
+    adapter: 'dotnet',
+    files: { 'Program.cs': CSHARP, 'SampleApp.csproj': CSPROJ },
+    source: 'Program.cs',
+    build: 'dotnet build',
+    program: 'bin/Debug/net8.0/SampleApp.dll',
+    defaultBreakpoint: "an order's totals",
+    evals: ['order.Id', 'total'],
+  },
+  c: {
+    language: 'c',
+    adapter: 'lldb',
+    files: { 'sample_app.c': C, Makefile: MAKEFILE },
+    source: 'sample_app.c',
+    build: 'make',
+    program: './sample_app',
+    defaultBreakpoint: "an order's totals",
+    evals: ['order->id', '*total'],
+  },
+  rust: {
+    language: 'rust',
+    adapter: 'rust',
+    files: { 'src/main.rs': RUST, 'Cargo.toml': CARGO_TOML },
+    source: 'src/main.rs',
+    build: 'cargo build',
+    program: './target/debug/sample_app',
+    defaultBreakpoint: "an order's totals",
+    evals: ['order.id', 'total'],
+  },
+};
+
+/** Other names a language can be given by */
+const LANGUAGE_ALIASES: Record<string, string> = {
+  js: 'javascript',
+  node: 'javascript',
+  py: 'python',
+  cs: 'csharp',
+  'c#': 'csharp',
+  dotnet: 'csharp',
+  rs: 'rust',
+};
+
+/**
+ * The sample for a language name or alias
+ *
+ * @throws Error naming the available languages if there is none
+ */
+export function getSampleProject(language: string): SampleProject {
+  const name = language.toLowerCase();
+  const project = SAMPLE_PROJECTS[LANGUAGE_ALIASES[name] ?? name];
+  if (!project) {
+    throw new Error(
+      `No sample for "${language}" (available: ${Object.keys(SAMPLE_PROJECTS).join(', ')})`
+    );
+  }
+  return project;
+}
+
+export interface SampleBreakpoint {
+  /** -b spec, relative to the sample directory */
+  spec: string;
+  description: string;
+}
+
+/**
+ * The marked breakpoint lines of a sample's source, in file order
+ */
+export function sampleBreakpoints(project: SampleProject): SampleBreakpoint[] {
+  const breakpoints: SampleBreakpoint[] = [];
+  project.files[project.source].split('\n').forEach((text, index) => {
+    const match = text.match(MARKER_PATTERN);
+    if (match) {
+      breakpoints.push({ spec: `${project.source}:${index + 2}`, description: match[1] });
+    }
+  });
+  return breakpoints;
+}
+
+/**
+ * The debug-run command for a sample, run from its directory
+ */
+export function sampleCommand(project: SampleProject): string {
+  return `npx debug-run ${project.program} -a ${project.adapter}`;
+}
+
+/**
+ * The sample's .debug-run.toml: its default breakpoint and evaluations, with the other
+ * marked lines and the commands to build and debug it in comments
+ */
+export function sampleConfig(project: SampleProject): string {
+  const breakpoints = sampleBreakpoints(project);
+  const primary =
+    breakpoints.find((bp) => bp.description === project.defaultBreakpoint) ?? breakpoints[0];
+  const others = breakpoints.filter((bp) => bp !== primary);
+  const width = Math.max(...others.map((bp) => bp.spec.length));
+  const list = (values: string[]) => `[${values.map((value) => JSON.stringify(value)).join(', ')}]`;
+
+  const lines = [
+    '# debug-run options for this sample (names without the leading dashes)',
+    '#',
+    ...(project.build ? [`# Build:  ${project.build}`] : []),
+    `# Debug:  ${sampleCommand(project)}`,
+    '#',
+    '# Other good breakpoint lines, to add with -b:',
+    ...others.map((bp) => `#   ${bp.spec.padEnd(width)}  ${bp.description}`),
+    '',
+    `[adapters.${project.adapter}]`,
+    `# ${primary.description}`,
+    `breakpoint = ${list([primary.spec])}`,
+    `eval = ${list(project.evals)}`,
+    'timeout = "30s"',
+  ];
+  return lines.join('\n') + '\n';
+}
+
+export interface GeneratedSample {
+  project: SampleProject;
+  directory: string;
+  /** Files written, relative to the directory */
+  files: string[];
+}
+
+/**
+ * Write a sample and its .debug-run.toml to a directory, creating it if needed
+ *
+ * @throws Error if one of the files already exists (unless force is set)
+ */
+export function generateSample(
+  language: string,
+  directory: string,
+  options: { force?: boolean } = {}
+): GeneratedSample {
+  const project = getSampleProject(language);
+  const files = { ...project.files, [PROJECT_CONFIG_FILE]: sampleConfig(project) };
+
+  if (!options.force) {
+    const existing = Object.keys(files).filter((file) => fs.existsSync(path.join(directory, file)));
+    if (existing.length > 0) {
+      throw new Error(
+        `${path.join(directory, existing[0])} already exists; use --force to overwrite it`
+      );
+    }
+  }
+  for (const [file, content] of Object.entries(files)) {
+    const target = path.join(directory, file);
+    fs.mkdirSync(path.dirname(target), { recursive: true });
+    fs.writeFileSync(target, content);
+  }
+  return { project, directory, files: Object.keys(files) };
+}
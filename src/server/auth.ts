@@ -0,0 +1,78 @@
+/**
+ * Server Access (serve mode)
+ *
+ * A session runs whatever program and expressions its client asks for, so whoever can talk
+ * to the server can run code as the user. The server is only meant for the user's own tools,
+ * and two checks keep everything else out, web pages in particular (a page can open a
+ * WebSocket to 127.0.0.1 or send it a form POST):
+ *
+ *   Origin  browsers send one on cross-origin requests and WebSocket upgrades; only loopback
+ *           origins, or none (curl, SDKs, editor extensions), are accepted
+ *   Token   a random token per server, printed at startup (or set with --token), given as
+ *           "Authorization: Bearer <token>" or a token query parameter (browser WebSocket
+ *           clients can't set headers), and in the "token" member of every JSON-RPC request
+ */
+
+import { randomBytes, timingSafeEqual } from 'node:crypto';
+import type { IncomingMessage } from 'node:http';
+
+/** Environment variable clients and the server read the token from */
+export const TOKEN_ENV = 'DEBUG_RUN_TOKEN';
+
+const LOOPBACK_HOSTS = new Set(['localhost', '127.0.0.1', '[::1]']);
+
+/**
+ * A new random server token
+ */
+export function generateToken(): string {
+  return randomBytes(24).toString('base64url');
+}
+
+/**
+ * Whether a request's Origin header may reach the server: absent, or a loopback host
+ */
+export function isAllowedOrigin(origin: string | undefined): boolean {
+  if (origin === undefined) return true;
+  try {
+    const url = new URL(origin);
+    return (
+      (url.protocol === 'http:' || url.protocol === 'https:') && LOOPBACK_HOSTS.has(url.hostname)
+    );
+  } catch {
+    // Includes "null", sent by sandboxed frames and file:// pages
+    return false;
+  }
+}
+
+/**
+ * Compare a given token with the server's in constant time
+ */
+export function tokensMatch(given: unknown, token: string): boolean {
+  if (typeof given !== 'string') return false;
+  const a = Buffer.from(given);
+  const b = Buffer.from(token);
+  return a.length === b.length && timingSafeEqual(a, b);
+}
+
+/**
+ * The token a request carries: its bearer token, or its token query parameter
+ */
+export function requestToken(request: IncomingMessage): string | undefined {
+  const bearer = request.headers.authorization?.match(/^Bearer\s+(\S+)$/i)?.[1];
+  if (bearer) return bearer;
+  return new URL(request.url ?? '/', 'http://localhost').searchParams.get('token') ?? undefined;
+}
+
+/**
+ * Why a request is refused, or null if it may go ahead
+ */
+export function accessRefusal(request: IncomingMessage, token: string): string | null {
+  const origin = request.headers.origin;
+  if (!isAllowedOrigin(origin)) {
+    return `Requests from origin ${origin} are not allowed`;
+  }
+  if (!tokensMatch(requestToken(request), token)) {
+    return 'Missing or invalid token (send "Authorization: Bearer <token>" or ?token=)';
+  }
+  return null;
+}
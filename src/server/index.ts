@@ -0,0 +1,3 @@
+export * from './server.js';
+export * from './sessions.js';
+export * from './websocket.js';
@@ -0,0 +1,169 @@
+/**
+ * Server Metrics (GET /metrics)
+ *
+ * Counts what the server's sessions do, so a debug-run server shared by a team can be
+ * scraped by Prometheus and monitored like any other service. Everything is taken from the
+ * sessions' events and outcomes, per adapter:
+ *
+ *   debug_run_sessions_started_total        sessions started
+ *   debug_run_sessions_finished_total       sessions ended or failed (status label)
+ *   debug_run_sessions_active               sessions running now
+ *   debug_run_adapter_launch_failures_total sessions that failed before the program ran
+ *   debug_run_session_errors_total          error events reported by sessions
+ *   debug_run_breakpoint_bind_seconds       session start to each breakpoint verified
+ *   debug_run_first_stop_seconds            session start to its first stop
+ *
+ * The two timings are summaries: _sum / _count is the average.
+ */
+
+import type { DebugEvent } from '../output/events.js';
+
+/** Events that mean the program is running under the adapter */
+const LAUNCH_EVENTS = new Set(['process_launched', 'process_attached']);
+
+/** Events of a stop in the program */
+const STOP_EVENTS = new Set(['breakpoint_hit', 'exception_thrown']);
+
+interface SessionTiming {
+  adapter: string;
+  startedAt: number;
+  launched: boolean;
+  stopped: boolean;
+}
+
+/**
+ * Values of one metric by label set
+ */
+class LabelledValues {
+  private values = new Map<string, number>();
+
+  add(labels: Record<string, string>, value: number = 1): void {
+    const key = Object.entries(labels)
+      .map(([name, text]) => `${name}="${escapeLabel(text)}"`)
+      .join(',');
+    this.values.set(key, (this.values.get(key) ?? 0) + value);
+  }
+
+  samples(name: string): string[] {
+    return [...this.values].map(([labels, value]) => `${name}{${labels}} ${value}`);
+  }
+}
+
+function escapeLabel(text: string): string {
+  return text.replace(/\\/g, '\\\\').replace(/"/g, '\\"').replace(/\n/g, '\\n');
+}
+
+function family(name: string, type: string, help: string, samples: string[]): string[] {
+  return [`# HELP ${name} ${help}`, `# TYPE ${name} ${type}`, ...samples];
+}
+
+export class ServerMetrics {
+  private sessions = new Map<string, SessionTiming>();
+  private started = new LabelledValues();
+  private finished = new LabelledValues();
+  private launchFailures = new LabelledValues();
+  private errors = new LabelledValues();
+  private bindSum = new LabelledValues();
+  private bindCount = new LabelledValues();
+  private stopSum = new LabelledValues();
+  private stopCount = new LabelledValues();
+
+  sessionStarted(id: string, adapter: string, now: number = Date.now()): void {
+    this.sessions.set(id, { adapter, startedAt: now, launched: false, stopped: false });
+    this.started.add({ adapter });
+  }
+
+  /**
+   * Record one of a session's events
+   */
+  observe(id: string, event: DebugEvent, now: number = Date.now()): void {
+    const session = this.sessions.get(id);
+    if (!session) return;
+    const { adapter } = session;
+    const elapsed = (now - session.startedAt) / 1000;
+
+    if (LAUNCH_EVENTS.has(event.type)) {
+      session.launched = true;
+    } else if (STOP_EVENTS.has(event.type) && !session.stopped) {
+      session.stopped = true;
+      this.stopSum.add({ adapter }, elapsed);
+      this.stopCount.add({ adapter });
+    } else if (
+      (event.type === 'breakpoint_set' && event.verified) ||
+      event.type === 'breakpoint_verified'
+    ) {
+      this.bindSum.add({ adapter }, elapsed);
+      this.bindCount.add({ adapter });
+    } else if (event.type === 'error') {
+      this.errors.add({ adapter });
+    }
+  }
+
+  sessionFinished(id: string, status: 'ended' | 'failed'): void {
+    const session = this.sessions.get(id);
+    if (!session) return;
+    this.sessions.delete(id);
+    this.finished.add({ adapter: session.adapter, status });
+    if (status === 'failed' && !session.launched) {
+      this.launchFailures.add({ adapter: session.adapter });
+    }
+  }
+
+  /**
+   * The metrics in the Prometheus text exposition format
+   */
+  render(): string {
+    const active = new LabelledValues();
+    for (const { adapter } of this.sessions.values()) active.add({ adapter });
+    const summary = (name: string, sum: LabelledValues, count: LabelledValues) => [
+      ...sum.samples(`${name}_sum`),
+      ...count.samples(`${name}_count`),
+    ];
+
+    const lines = [
+      ...family(
+        'debug_run_sessions_started_total',
+        'counter',
+        'Debug sessions started',
+        this.started.samples('debug_run_sessions_started_total')
+      ),
+      ...family(
+        'debug_run_sessions_finished_total',
+        'counter',
+        'Debug sessions that ended or failed',
+        this.finished.samples('debug_run_sessions_finished_total')
+      ),
+      ...family(
+        'debug_run_sessions_active',
+        'gauge',
+        'Debug sessions running',
+        active.samples('debug_run_sessions_active')
+      ),
+      ...family(
+        'debug_run_adapter_launch_failures_total',
+        'counter',
+        'Debug sessions that failed before the program was launched or attached to',
+        this.launchFailures.samples('debug_run_adapter_launch_failures_total')
+      ),
+      ...family(
+        'debug_run_session_errors_total',
+        'counter',
+        'Error events reported by debug sessions',
+        this.errors.samples('debug_run_session_errors_total')
+      ),
+      ...family(
+        'debug_run_breakpoint_bind_seconds',
+        'summary',
+        'Time from session start to a breakpoint being verified',
+        summary('debug_run_breakpoint_bind_seconds', this.bindSum, this.bindCount)
+      ),
+      ...family(
+        'debug_run_first_stop_seconds',
+        'summary',
+        "Time from session start to the session's first breakpoint or exception stop",
+        summary('debug_run_first_stop_seconds', this.stopSum, this.stopCount)
+      ),
+    ];
+    return lines.join('\n') + '\n';
+  }
+}
@@ -0,0 +1,125 @@
+/**
+ * Project State (serve --state-file)
+ *
+ * What a project's sessions were last configured with: breakpoints, logpoints, watch
+ * expressions and named profiles of session parameters. Projects are keyed by directory.
+ * A session started without breakpoints, logpoints or evaluations gets the project's, so a
+ * client that reconnects (an editor plugin restarting, an agent retrying) picks up where the
+ * last one left off instead of starting from scratch. With a state file the state outlives
+ * the server; without one it lasts until the server stops.
+ */
+
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+import type { SessionParams } from './sessions.js';
+
+/** Format version of the state file */
+export const PROJECT_STATE_VERSION = 1;
+
+export interface ProjectState {
+  /** Project directory (absolute) */
+  project: string;
+  breakpoints: string[];
+  logpoints: string[];
+  /** Watch expressions, evaluated at each stop */
+  evaluations: string[];
+  /** Named sets of session parameters a session can start from (session.start "profile") */
+  profiles: Record<string, Partial<SessionParams>>;
+  updatedAt?: string;
+}
+
+/**
+ * Changes to a project's state: lists replace the saved ones, profiles are merged by name
+ * (null deletes one)
+ */
+export interface ProjectStateUpdate {
+  breakpoints?: string[];
+  logpoints?: string[];
+  evaluations?: string[];
+  profiles?: Record<string, Partial<SessionParams> | null>;
+}
+
+interface ProjectStateFile {
+  version: number;
+  projects: Record<string, Omit<ProjectState, 'project'>>;
+}
+
+function isPlainObject(value: unknown): value is Record<string, unknown> {
+  return typeof value === 'object' && value !== null && !Array.isArray(value);
+}
+
+export class ProjectStateStore {
+  private projects: ProjectStateFile['projects'] = {};
+
+  /**
+   * @param file State file to read and keep up to date (in memory only when omitted)
+   * @throws Error if the file exists but isn't valid project state
+   */
+  constructor(private file?: string) {
+    if (!file || !fs.existsSync(file)) return;
+
+    let parsed: unknown;
+    try {
+      parsed = JSON.parse(fs.readFileSync(file, 'utf-8'));
+    } catch (error) {
+      throw new Error(`Invalid state file ${file}: ${(error as Error).message}`);
+    }
+    if (!isPlainObject(parsed) || !isPlainObject(parsed.projects)) {
+      throw new Error(`Invalid state file ${file}: expected a "projects" object`);
+    }
+    if (parsed.version !== PROJECT_STATE_VERSION) {
+      throw new Error(
+        `Unsupported state file version in ${file}: ${parsed.version} ` +
+          `(expected ${PROJECT_STATE_VERSION})`
+      );
+    }
+    this.projects = parsed.projects as ProjectStateFile['projects'];
+  }
+
+  /**
+   * A project's state (empty for a project no session has configured yet)
+   */
+  get(directory: string): ProjectState {
+    const project = path.resolve(directory);
+    const saved = this.projects[project];
+    return {
+      project,
+      breakpoints: saved?.breakpoints ?? [],
+      logpoints: saved?.logpoints ?? [],
+      evaluations: saved?.evaluations ?? [],
+      profiles: saved?.profiles ?? {},
+      ...(saved?.updatedAt && { updatedAt: saved.updatedAt }),
+    };
+  }
+
+  /**
+   * Apply changes to a project's state and write the state file
+   */
+  update(directory: string, changes: ProjectStateUpdate): ProjectState {
+    const { project, ...current } = this.get(directory);
+    const profiles = { ...current.profiles };
+    for (const [name, profile] of Object.entries(changes.profiles ?? {})) {
+      if (profile === null) delete profiles[name];
+      else profiles[name] = profile;
+    }
+
+    this.projects[project] = {
+      breakpoints: changes.breakpoints ?? current.breakpoints,
+      logpoints: changes.logpoints ?? current.logpoints,
+      evaluations: changes.evaluations ?? current.evaluations,
+      profiles,
+      updatedAt: new Date().toISOString(),
+    };
+    this.save();
+    return this.get(project);
+  }
+
+  private save(): void {
+    if (!this.file) return;
+    const state: ProjectStateFile = { version: PROJECT_STATE_VERSION, projects: this.projects };
+    // Written aside and renamed, so a crash mid-write can't truncate the saved state
+    const temporary = `${this.file}.${process.pid}.tmp`;
+    fs.writeFileSync(temporary, JSON.stringify(state, null, 2) + '\n');
+    fs.renameSync(temporary, this.file);
+  }
+}
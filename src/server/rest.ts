@@ -0,0 +1,546 @@
+/**
+ * REST API (serve mode)
+ *
+ * Plain-HTTP access to the session registry for tools that can't speak WebSocket or
+ * JSON-RPC. Routes are declared once in REST_ROUTES, which drives both request
+ * dispatch and the OpenAPI document served at /openapi.json. Prometheus metrics are
+ * served at /metrics (see metrics.ts). Every request needs the server's token, and request
+ * bodies must be sent as application/json, which a web page can't do without the page's
+ * origin being checked (see auth.ts).
+ */
+
+import type * as http from 'node:http';
+import {
+  InvalidParamsError,
+  SessionNotFoundError,
+  SessionStateError,
+  type SessionParams,
+  type SessionRegistry,
+} from './sessions.js';
+import { accessRefusal } from './auth.js';
+import { logger } from '../util/logger.js';
+
+/** Largest accepted request body */
+const MAX_BODY_BYTES = 1024 * 1024;
+
+type JsonSchema = Record<string, unknown>;
+
+interface RestContext {
+  sessions: SessionRegistry;
+  params: Record<string, string>;
+  query: URLSearchParams;
+  body: Record<string, unknown>;
+}
+
+interface RestRoute {
+  method: 'GET' | 'POST' | 'DELETE';
+  /** OpenAPI-style path, e.g. /sessions/{sessionId} */
+  path: string;
+  summary: string;
+  /** Schema of the JSON request body, if any */
+  requestBody?: JsonSchema;
+  /** Query parameters (all optional strings) */
+  queryParameters?: Record<string, string>;
+  /** Success status code (default: 200) */
+  status?: number;
+  /** Schema of the success response */
+  response: JsonSchema;
+  handler(context: RestContext): Promise<unknown> | unknown;
+}
+
+const ref = (name: string): JsonSchema => ({ $ref: `#/components/schemas/${name}` });
+
+const SCHEMAS: Record<string, JsonSchema> = {
+  SessionParams: {
+    type: 'object',
+    required: ['adapter'],
+    properties: {
+      adapter: { type: 'string', description: 'Debug adapter (e.g., node, python, dotnet)' },
+      backend: { type: 'string' },
+      program: { type: 'string' },
+      module: { type: 'string' },
+      args: { type: 'array', items: { type: 'string' } },
+      cwd: { type: 'string' },
+      env: { type: 'object', additionalProperties: { type: 'string' } },
+      breakpoints: { type: 'array', items: { type: 'string' }, example: ['src/app.ts:10'] },
+      logpoints: { type: 'array', items: { type: 'string' } },
+      instructionBreakpoints: { type: 'array', items: { type: 'string' }, example: ['0x55aa113e'] },
+      evaluations: { type: 'array', items: { type: 'string' } },
+      assertions: { type: 'array', items: { type: 'string' } },
+      evalSafe: { type: 'boolean', default: false },
+      evalContext: {
+        type: 'string',
+        enum: ['watch', 'repl', 'hover', 'clipboard'],
+        default: 'watch',
+        description: 'Context for evaluations without a prefix (e.g., "repl:order")',
+      },
+      evalExpand: {
+        type: 'integer',
+        default: 0,
+        description: 'Levels to expand structured evaluation results, like locals',
+      },
+      provenance: {
+        type: 'boolean',
+        default: false,
+        description: 'Tag locals with their frame, scope, reference path and evaluateName',
+      },
+      captureBudgetMs: {
+        type: 'integer',
+        description: 'Time each stop may spend capturing; unfetched values are marked truncated',
+      },
+      exceptionFilters: { type: 'array', items: { type: 'string' } },
+      timeoutMs: { type: 'integer', default: 60000 },
+      captureLocals: { type: 'boolean', default: true },
+      steps: { type: 'integer' },
+      trace: { type: 'boolean' },
+      traceInto: { type: 'boolean' },
+      traceLimit: { type: 'integer', default: 500 },
+      traceUntil: { type: 'string' },
+      maxHits: { type: 'integer' },
+      skipToHit: {
+        type: 'integer',
+        description: 'Resume the breakpoint hits before this one without capturing them',
+      },
+      includeSource: { type: 'integer' },
+      captureArgs: {
+        type: 'boolean',
+        default: false,
+        description: "Attach each stack frame's argument values to it",
+      },
+      pauseOnHit: {
+        type: 'boolean',
+        default: false,
+        description: 'Stay paused at each breakpoint hit until /continue is called',
+      },
+      history: {
+        type: 'integer',
+        description: 'Earlier stops evaluate can refer to as $1, $2, ... (e.g., "$1.subtotal")',
+      },
+      attach: { type: 'boolean' },
+      pid: { type: 'integer' },
+      project: {
+        type: 'string',
+        description: 'Project whose saved breakpoints and watches fill in those not given',
+      },
+      profile: { type: 'string', description: 'Saved profile of the project to start from' },
+      defines: {
+        type: 'object',
+        additionalProperties: { type: 'string' },
+        description: 'Values for ${NAME} placeholders in breakpoints, evaluations and assertions',
+      },
+    },
+  },
+  SessionSummary: {
+    type: 'object',
+    required: ['sessionId', 'adapter', 'status', 'startedAt', 'eventCount'],
+    properties: {
+      sessionId: { type: 'string' },
+      adapter: { type: 'string' },
+      program: { type: 'string' },
+      pid: { type: 'integer' },
+      status: { type: 'string', enum: ['running', 'paused', 'ended', 'failed'] },
+      startedAt: { type: 'string', format: 'date-time' },
+      endedAt: { type: 'string', format: 'date-time' },
+      error: { type: 'string' },
+      eventCount: { type: 'integer' },
+    },
+  },
+  EvaluateRequest: {
+    type: 'object',
+    required: ['expression'],
+    properties: {
+      expression: { type: 'string' },
+      frameId: { type: 'integer', description: 'Stack frame (default: top frame)' },
+    },
+  },
+  EvaluateResult: {
+    type: 'object',
+    required: ['result', 'variablesReference'],
+    properties: {
+      result: { type: 'string' },
+      type: { type: 'string' },
+      variablesReference: { type: 'integer' },
+    },
+  },
+  ReloadRequest: {
+    type: 'object',
+    required: ['files'],
+    properties: {
+      files: { type: 'array', items: { type: 'string' }, example: ['src/handlers.py'] },
+      restartFrame: {
+        type: 'boolean',
+        default: false,
+        description: 'Re-enter the paused function so it runs again with the new code',
+      },
+    },
+  },
+  DebugEvent: {
+    type: 'object',
+    required: ['type', 'timestamp'],
+    description: 'A debug-run event, as emitted in the NDJSON output',
+    properties: {
+      type: { type: 'string' },
+      timestamp: { type: 'string', format: 'date-time' },
+    },
+    additionalProperties: true,
+  },
+  SavedSessionState: {
+    type: 'object',
+    required: ['version', 'savedAt', 'sessions'],
+    description: 'Attach sessions saved for `debug-run resume`',
+    properties: {
+      version: { type: 'integer', enum: [1] },
+      savedAt: { type: 'string', format: 'date-time' },
+      sessions: {
+        type: 'array',
+        items: {
+          type: 'object',
+          required: ['sessionId', 'status', 'params'],
+          properties: {
+            sessionId: { type: 'string' },
+            status: { type: 'string', enum: ['running', 'paused'] },
+            params: ref('SessionParams'),
+          },
+        },
+      },
+    },
+  },
+  ProjectState: {
+    type: 'object',
+    required: ['project', 'breakpoints', 'logpoints', 'evaluations', 'profiles'],
+    description: 'What sessions in a project were last configured with',
+    properties: {
+      project: { type: 'string', description: 'Project directory' },
+      breakpoints: { type: 'array', items: { type: 'string' } },
+      logpoints: { type: 'array', items: { type: 'string' } },
+      evaluations: { type: 'array', items: { type: 'string' } },
+      profiles: { type: 'object', additionalProperties: ref('SessionParams') },
+      updatedAt: { type: 'string', format: 'date-time' },
+    },
+  },
+  ProjectStateUpdate: {
+    type: 'object',
+    description: 'Lists replace the saved ones; profiles merge by name (null deletes one)',
+    properties: {
+      breakpoints: { type: 'array', items: { type: 'string' } },
+      logpoints: { type: 'array', items: { type: 'string' } },
+      evaluations: { type: 'array', items: { type: 'string' } },
+      profiles: { type: 'object', additionalProperties: { type: 'object', nullable: true } },
+    },
+  },
+  Error: {
+    type: 'object',
+    required: ['error'],
+    properties: { error: { type: 'string' } },
+  },
+};
+
+export const REST_ROUTES: RestRoute[] = [
+  {
+    method: 'GET',
+    path: '/sessions',
+    summary: 'List sessions',
+    response: { type: 'array', items: ref('SessionSummary') },
+    handler: ({ sessions }) => sessions.list(),
+  },
+  {
+    method: 'POST',
+    path: '/sessions',
+    summary: 'Start a debug session',
+    requestBody: ref('SessionParams'),
+    status: 201,
+    response: ref('SessionSummary'),
+    handler: ({ sessions, body }) => sessions.start(body as unknown as SessionParams),
+  },
+  {
+    method: 'POST',
+    path: '/sessions/resume',
+    summary: 'Re-attach saved sessions with their breakpoints and watches',
+    requestBody: ref('SavedSessionState'),
+    status: 201,
+    response: { type: 'array', items: ref('SessionSummary') },
+    handler: ({ sessions, body }) => sessions.resume(body),
+  },
+  {
+    method: 'GET',
+    path: '/sessions/{sessionId}',
+    summary: 'Get a session',
+    response: ref('SessionSummary'),
+    handler: ({ sessions, params }) => {
+      const summary = sessions.get(params.sessionId);
+      if (!summary) {
+        throw new SessionNotFoundError(`Unknown session: ${params.sessionId}`);
+      }
+      return summary;
+    },
+  },
+  {
+    method: 'DELETE',
+    path: '/sessions/{sessionId}',
+    summary: 'Stop a session (terminates the debuggee unless attached)',
+    response: ref('SessionSummary'),
+    handler: ({ sessions, params }) => sessions.stop(params.sessionId),
+  },
+  {
+    method: 'GET',
+    path: '/sessions/{sessionId}/events',
+    summary: 'Get buffered session events',
+    queryParameters: { types: 'Comma-separated event types to include' },
+    response: { type: 'array', items: ref('DebugEvent') },
+    handler: ({ sessions, params, query }) =>
+      sessions.events(params.sessionId, query.get('types')?.split(',').filter(Boolean)),
+  },
+  {
+    method: 'POST',
+    path: '/sessions/{sessionId}/continue',
+    summary: 'Resume a session paused at a breakpoint (pauseOnHit)',
+    response: ref('SessionSummary'),
+    handler: ({ sessions, params }) => sessions.continue(params.sessionId),
+  },
+  {
+    method: 'POST',
+    path: '/sessions/{sessionId}/reload',
+    summary: 'Apply edited files without restarting and re-verify their breakpoints',
+    requestBody: ref('ReloadRequest'),
+    response: ref('DebugEvent'),
+    handler: ({ sessions, params, body }) =>
+      sessions.reload(params.sessionId, body.files, body.restartFrame as boolean | undefined),
+  },
+  {
+    method: 'POST',
+    path: '/sessions/{sessionId}/restart',
+    summary: 'Relaunch the program, keeping the adapter and its breakpoints',
+    response: ref('SessionSummary'),
+    handler: ({ sessions, params }) => sessions.restart(params.sessionId),
+  },
+  {
+    method: 'GET',
+    path: '/sessions/{sessionId}/state',
+    summary: 'Save an attach session for resuming later',
+    response: ref('SavedSessionState'),
+    handler: ({ sessions, params }) => sessions.save(params.sessionId),
+  },
+  {
+    method: 'POST',
+    path: '/sessions/{sessionId}/eval',
+    summary: 'Evaluate an expression in a paused session',
+    requestBody: ref('EvaluateRequest'),
+    response: ref('EvaluateResult'),
+    handler: ({ sessions, params, body }) =>
+      sessions.evaluate(
+        params.sessionId,
+        body.expression as string,
+        body.frameId as number | undefined
+      ),
+  },
+  {
+    method: 'GET',
+    path: '/project',
+    summary: "Get a project's saved breakpoints, watches and profiles",
+    queryParameters: { project: "Project directory (default: the server's working directory)" },
+    response: ref('ProjectState'),
+    handler: ({ sessions, query }) => sessions.project(query.get('project') ?? undefined),
+  },
+  {
+    method: 'POST',
+    path: '/project',
+    summary: "Change a project's saved breakpoints, watches and profiles",
+    requestBody: ref('ProjectStateUpdate'),
+    queryParameters: { project: "Project directory (default: the server's working directory)" },
+    response: ref('ProjectState'),
+    handler: ({ sessions, query, body }) =>
+      sessions.updateProject(query.get('project') ?? undefined, body),
+  },
+];
+
+/**
+ * Generate the OpenAPI 3.0 document for the REST API
+ */
+export function buildOpenApiSpec(version: string): Record<string, unknown> {
+  const paths: Record<string, Record<string, unknown>> = {};
+
+  for (const route of REST_ROUTES) {
+    const pathParameters = [...route.path.matchAll(/\{(\w+)\}/g)].map((match) => ({
+      name: match[1],
+      in: 'path',
+      required: true,
+      schema: { type: 'string' },
+    }));
+    const queryParameters = Object.entries(route.queryParameters ?? {}).map(
+      ([name, description]) => ({ name, in: 'query', description, schema: { type: 'string' } })
+    );
+    const parameters = [...pathParameters, ...queryParameters];
+
+    const errorResponse = { content: { 'application/json': { schema: ref('Error') } } };
+    paths[route.path] ??= {};
+    paths[route.path][route.method.toLowerCase()] = {
+      summary: route.summary,
+      ...(parameters.length > 0 && { parameters }),
+      ...(route.requestBody && {
+        requestBody: {
+          required: true,
+          content: { 'application/json': { schema: route.requestBody } },
+        },
+      }),
+      responses: {
+        [String(route.status ?? 200)]: {
+          description: 'Success',
+          content: { 'application/json': { schema: route.response } },
+        },
+        '400': { description: 'Invalid request', ...errorResponse },
+        '403': { description: 'Missing token or not allowed origin', ...errorResponse },
+        '404': { description: 'Not found', ...errorResponse },
+        '409': { description: 'Not valid in the current session state', ...errorResponse },
+      },
+    };
+  }
+
+  return {
+    openapi: '3.0.3',
+    info: {
+      title: 'debug-run server',
+      version,
+      description: 'Start debug sessions and inspect their events over plain HTTP',
+    },
+    paths,
+    components: {
+      schemas: SCHEMAS,
+      securitySchemes: { token: { type: 'http', scheme: 'bearer' } },
+    },
+    security: [{ token: [] }],
+  };
+}
+
+/**
+ * Match a request path against a route pattern, returning path parameters on success
+ */
+export function matchRoute(pattern: string, pathname: string): Record<string, string> | null {
+  const patternParts = pattern.split('/');
+  const pathParts = pathname.replace(/\/+$/, '').split('/');
+  if (patternParts.length !== pathParts.length) return null;
+
+  const params: Record<string, string> = {};
+  for (let i = 0; i < patternParts.length; i++) {
+    const name = patternParts[i].match(/^\{(\w+)\}$/)?.[1];
+    if (name) {
+      params[name] = decodeURIComponent(pathParts[i]);
+    } else if (patternParts[i] !== pathParts[i]) {
+      return null;
+    }
+  }
+  return params;
+}
+
+/**
+ * Handle a plain HTTP request against the REST API
+ */
+export async function handleRestRequest(
+  sessions: SessionRegistry,
+  request: http.IncomingMessage,
+  response: http.ServerResponse,
+  version: string,
+  token: string
+): Promise<void> {
+  const url = new URL(request.url ?? '/', 'http://localhost');
+
+  const refusal = accessRefusal(request, token);
+  if (refusal) {
+    logger.warn('server', 'refused REST request', { reason: refusal, path: url.pathname });
+    sendJson(response, 403, { error: refusal });
+    return;
+  }
+
+  if (request.method === 'GET' && url.pathname === '/openapi.json') {
+    sendJson(response, 200, buildOpenApiSpec(version));
+    return;
+  }
+  if (request.method === 'GET' && url.pathname === '/metrics') {
+    response.writeHead(200, { 'Content-Type': 'text/plain; version=0.0.4; charset=utf-8' });
+    response.end(sessions.metrics.render());
+    return;
+  }
+
+  let matchedPath = false;
+  for (const route of REST_ROUTES) {
+    const params = matchRoute(route.path, url.pathname);
+    if (!params) continue;
+    matchedPath = true;
+    if (route.method !== request.method) continue;
+
+    if (route.requestBody && !isJsonContentType(request.headers['content-type'])) {
+      sendJson(response, 415, { error: 'Request body must be sent as application/json' });
+      return;
+    }
+    try {
+      const body = route.requestBody ? await readJsonBody(request) : {};
+      const result = await route.handler({ sessions, params, query: url.searchParams, body });
+      sendJson(response, route.status ?? 200, result);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      if (error instanceof SessionNotFoundError) {
+        sendJson(response, 404, { error: message });
+      } else if (error instanceof InvalidParamsError) {
+        sendJson(response, 400, { error: message });
+      } else if (error instanceof SessionStateError) {
+        sendJson(response, 409, { error: message });
+      } else {
+        logger.error('server', 'REST request failed', {
+          method: request.method,
+          path: url.pathname,
+          error: message,
+        });
+        sendJson(response, 500, { error: message });
+      }
+    }
+    return;
+  }
+
+  if (matchedPath) {
+    sendJson(response, 405, { error: `Method not allowed: ${request.method} ${url.pathname}` });
+  } else {
+    sendJson(response, 404, { error: `Not found: ${request.method} ${url.pathname}` });
+  }
+}
+
+function isJsonContentType(contentType: string | undefined): boolean {
+  return contentType?.split(';')[0].trim().toLowerCase() === 'application/json';
+}
+
+function readJsonBody(request: http.IncomingMessage): Promise<Record<string, unknown>> {
+  return new Promise((resolve, reject) => {
+    const chunks: Buffer[] = [];
+    let size = 0;
+    request.on('data', (chunk: Buffer) => {
+      size += chunk.length;
+      if (size > MAX_BODY_BYTES) {
+        reject(new InvalidParamsError('Request body too large'));
+        request.destroy();
+        return;
+      }
+      chunks.push(chunk);
+    });
+    request.on('end', () => {
+      const text = Buffer.concat(chunks).toString('utf-8').trim();
+      if (!text) {
+        resolve({});
+        return;
+      }
+      try {
+        const body = JSON.parse(text);
+        if (typeof body !== 'object' || body === null || Array.isArray(body)) {
+          reject(new InvalidParamsError('Request body must be a JSON object'));
+          return;
+        }
+        resolve(body);
+      } catch {
+        reject(new InvalidParamsError('Request body is not valid JSON'));
+      }
+    });
+    request.on('error', reject);
+  });
+}
+
+function sendJson(response: http.ServerResponse, status: number, body: unknown): void {
+  response.writeHead(status, { 'Content-Type': 'application/json' });
+  response.end(JSON.stringify(body));
+}
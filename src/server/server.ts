@@ -0,0 +1,308 @@
+/**
+ * Debug Server (serve mode)
+ *
+ * Runs debug sessions on request and streams their events to clients. Clients connect
+ * to ws://<host>:<port>/ws and speak JSON-RPC 2.0:
+ *
+ *   session.start       params: SessionParams             -> SessionSummary
+ *   session.list        -                                 -> SessionSummary[]
+ *   session.get         { sessionId }                     -> SessionSummary
+ *   session.subscribe   { sessionId, types?, replay? }    -> { sessionId, subscribed: true }
+ *   session.unsubscribe { sessionId }                     -> { sessionId, subscribed: false }
+ *   session.continue    { sessionId }                     -> SessionSummary
+ *   session.evaluate    { sessionId, expression, frameId? } -> { result, type, ... }
+ *   session.reload      { sessionId, files, restartFrame? } -> code_reloaded event
+ *   session.restart     { sessionId }                     -> SessionSummary
+ *   session.stop        { sessionId }                     -> SessionSummary
+ *   session.save        { sessionId }                     -> SavedSessionState
+ *   session.resume      { state: SavedSessionState }      -> SessionSummary[]
+ *   project.get         { project? }                      -> ProjectState
+ *   project.update      { project?, breakpoints?, logpoints?, evaluations?, profiles? }
+ *                                                         -> ProjectState
+ *
+ * Subscribed events arrive as "session.event" notifications: { sessionId, event }.
+ * The same server also answers plain HTTP requests (see rest.ts and /openapi.json) and
+ * serves Prometheus metrics at /metrics. The upgrade, every JSON-RPC request (in its "token"
+ * member) and every HTTP request need the server's token (see auth.ts).
+ */
+
+import * as http from 'node:http';
+import type { AddressInfo } from 'node:net';
+import type { Duplex } from 'node:stream';
+import { acceptWebSocket, type WebSocketConnection } from './websocket.js';
+import {
+  SessionRegistry,
+  InvalidParamsError,
+  SessionStateError,
+  type SessionParams,
+  type SessionRegistryOptions,
+  type SubscribeOptions,
+} from './sessions.js';
+import { handleRestRequest } from './rest.js';
+import { accessRefusal, generateToken, tokensMatch } from './auth.js';
+import { logger } from '../util/logger.js';
+
+export const WEBSOCKET_PATH = '/ws';
+
+/** JSON-RPC 2.0 error codes */
+const PARSE_ERROR = -32700;
+const INVALID_REQUEST = -32600;
+const METHOD_NOT_FOUND = -32601;
+const INVALID_PARAMS = -32602;
+const INTERNAL_ERROR = -32603;
+/** Implementation-defined server error: request not valid in the session's state */
+const SESSION_STATE_ERROR = -32000;
+/** Implementation-defined server error: the request's token is missing or wrong */
+const UNAUTHORIZED = -32001;
+
+interface JsonRpcRequest {
+  jsonrpc: '2.0';
+  id?: string | number | null;
+  method: string;
+  params?: unknown;
+  /** The server's token */
+  token?: string;
+}
+
+export interface DebugServerOptions extends SessionRegistryOptions {
+  /** Token clients must present (default: a new random one) */
+  token?: string;
+}
+
+export class DebugServer {
+  readonly sessions: SessionRegistry;
+  /** Token clients must present, to print for the user */
+  readonly token: string;
+  private httpServer: http.Server;
+  private connections: Set<WebSocketConnection> = new Set();
+
+  /**
+   * @param version Reported in the OpenAPI document
+   * @param options Defaults for the sessions the server starts, and its token
+   */
+  constructor(private version: string = 'dev', options: DebugServerOptions = {}) {
+    const { token, ...registryOptions } = options;
+    this.token = token || generateToken();
+    this.sessions = new SessionRegistry(registryOptions);
+    this.httpServer = http.createServer((request, response) =>
+      this.handleHttpRequest(request, response)
+    );
+    this.httpServer.on('upgrade', (request, socket) => this.handleUpgrade(request, socket));
+  }
+
+  /**
+   * Start listening. Resolves with the bound address (port 0 picks a free port).
+   */
+  listen(port: number, host: string): Promise<AddressInfo> {
+    return new Promise((resolve, reject) => {
+      this.httpServer.once('error', reject);
+      this.httpServer.listen(port, host, () => {
+        this.httpServer.off('error', reject);
+        resolve(this.httpServer.address() as AddressInfo);
+      });
+    });
+  }
+
+  /**
+   * Stop all sessions, disconnect clients and stop listening
+   */
+  async close(): Promise<void> {
+    await this.sessions.stopAll();
+    for (const connection of this.connections) {
+      connection.close(1001);
+    }
+    await new Promise<void>((resolve) => this.httpServer.close(() => resolve()));
+  }
+
+  private handleHttpRequest(request: http.IncomingMessage, response: http.ServerResponse): void {
+    void handleRestRequest(this.sessions, request, response, this.version, this.token);
+  }
+
+  private handleUpgrade(request: http.IncomingMessage, socket: Duplex): void {
+    if (new URL(request.url ?? '/', 'http://localhost').pathname !== WEBSOCKET_PATH) {
+      socket.end('HTTP/1.1 404 Not Found\r\nConnection: close\r\n\r\n');
+      return;
+    }
+    const refusal = accessRefusal(request, this.token);
+    if (refusal) {
+      logger.warn('server', 'refused client', { reason: refusal, origin: request.headers.origin });
+      socket.end('HTTP/1.1 403 Forbidden\r\nConnection: close\r\n\r\n');
+      return;
+    }
+
+    const connection = acceptWebSocket(request, socket);
+    if (!connection) return;
+
+    this.connections.add(connection);
+    const subscriptions: Map<string, () => void> = new Map();
+    logger.info('server', 'client connected', { clients: this.connections.size });
+
+    connection.on('message', (text: string) => {
+      void this.handleMessage(connection, subscriptions, text);
+    });
+    connection.on('close', () => {
+      for (const unsubscribe of subscriptions.values()) {
+        unsubscribe();
+      }
+      subscriptions.clear();
+      this.connections.delete(connection);
+      logger.info('server', 'client disconnected', { clients: this.connections.size });
+    });
+  }
+
+  private async handleMessage(
+    connection: WebSocketConnection,
+    subscriptions: Map<string, () => void>,
+    text: string
+  ): Promise<void> {
+    let request: JsonRpcRequest;
+    try {
+      request = JSON.parse(text);
+    } catch {
+      connection.send(rpcError(null, PARSE_ERROR, 'Parse error'));
+      return;
+    }
+
+    if (!request || request.jsonrpc !== '2.0' || typeof request.method !== 'string') {
+      connection.send(rpcError(request?.id ?? null, INVALID_REQUEST, 'Invalid request'));
+      return;
+    }
+
+    const id = request.id ?? null;
+    if (!tokensMatch(request.token, this.token)) {
+      connection.send(rpcError(id, UNAUTHORIZED, 'Missing or invalid "token"'));
+      return;
+    }
+    try {
+      const result = await this.dispatch(connection, subscriptions, request);
+      // Requests without an id are notifications and get no response
+      if (request.id !== undefined) {
+        connection.send(JSON.stringify({ jsonrpc: '2.0', id, result }));
+      }
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      if (error instanceof MethodNotFoundError) {
+        connection.send(rpcError(id, METHOD_NOT_FOUND, message));
+      } else if (error instanceof InvalidParamsError) {
+        connection.send(rpcError(id, INVALID_PARAMS, message));
+      } else if (error instanceof SessionStateError) {
+        connection.send(rpcError(id, SESSION_STATE_ERROR, message));
+      } else {
+        logger.error('server', 'request failed', { method: request.method, error: message });
+        connection.send(rpcError(id, INTERNAL_ERROR, message));
+      }
+    }
+  }
+
+  private async dispatch(
+    connection: WebSocketConnection,
+    subscriptions: Map<string, () => void>,
+    request: JsonRpcRequest
+  ): Promise<unknown> {
+    const params = (request.params ?? {}) as Record<string, unknown>;
+
+    switch (request.method) {
+      case 'session.start':
+        return this.sessions.start(params as unknown as SessionParams);
+
+      case 'session.list':
+        return this.sessions.list();
+
+      case 'session.get': {
+        const summary = this.sessions.get(sessionIdParam(params));
+        if (!summary) {
+          throw new InvalidParamsError(`Unknown session: ${params.sessionId}`);
+        }
+        return summary;
+      }
+
+      case 'session.subscribe': {
+        const sessionId = sessionIdParam(params);
+        subscriptions.get(sessionId)?.();
+        const unsubscribe = this.sessions.subscribe(
+          sessionId,
+          (eventSessionId, event) => {
+            connection.send(
+              JSON.stringify({
+                jsonrpc: '2.0',
+                method: 'session.event',
+                params: { sessionId: eventSessionId, event },
+              })
+            );
+          },
+          params as SubscribeOptions
+        );
+        subscriptions.set(sessionId, unsubscribe);
+        return { sessionId, subscribed: true };
+      }
+
+      case 'session.unsubscribe': {
+        const sessionId = sessionIdParam(params);
+        subscriptions.get(sessionId)?.();
+        subscriptions.delete(sessionId);
+        return { sessionId, subscribed: false };
+      }
+
+      case 'session.continue':
+        return this.sessions.continue(sessionIdParam(params));
+
+      case 'session.evaluate':
+        return this.sessions.evaluate(
+          sessionIdParam(params),
+          params.expression as string,
+          params.frameId as number | undefined
+        );
+
+      case 'session.reload':
+        return this.sessions.reload(
+          sessionIdParam(params),
+          params.files,
+          params.restartFrame as boolean | undefined
+        );
+
+      case 'session.restart':
+        return this.sessions.restart(sessionIdParam(params));
+
+      case 'session.stop':
+        return this.sessions.stop(sessionIdParam(params));
+
+      case 'session.save':
+        return this.sessions.save(sessionIdParam(params));
+
+      case 'session.resume':
+        return this.sessions.resume(params.state);
+
+      case 'project.get':
+        return this.sessions.project(projectParam(params));
+
+      case 'project.update': {
+        const changes = { ...params };
+        delete changes.project;
+        return this.sessions.updateProject(projectParam(params), changes);
+      }
+
+      default:
+        throw new MethodNotFoundError(`Unknown method: ${request.method}`);
+    }
+  }
+}
+
+class MethodNotFoundError extends Error {}
+
+function sessionIdParam(params: Record<string, unknown>): string {
+  if (typeof params.sessionId !== 'string') {
+    throw new InvalidParamsError('"sessionId" is required');
+  }
+  return params.sessionId;
+}
+
+function projectParam(params: Record<string, unknown>): string | undefined {
+  if (params.project !== undefined && typeof params.project !== 'string') {
+    throw new InvalidParamsError('"project" must be a directory path');
+  }
+  return params.project;
+}
+
+function rpcError(id: JsonRpcRequest['id'], code: number, message: string): string {
+  return JSON.stringify({ jsonrpc: '2.0', id: id ?? null, error: { code, message } });
+}
@@ -0,0 +1,784 @@
+/**
+ * Session Registry
+ *
+ * Owns the debug sessions started in serve mode. Each session runs with its own
+ * formatter; every event is buffered (so late subscribers can replay it) and fanned
+ * out to subscribers. Attach sessions can be saved and resumed later, since their
+ * debuggee outlives the session. Each project's breakpoints, watches and profiles are kept
+ * across sessions (see project-state.ts).
+ */
+
+import { randomUUID } from 'node:crypto';
+import { Writable } from 'node:stream';
+import { getAdapter, getAdapterBackend, getAdapterNames } from '../adapters/index.js';
+import { DebugSession, type SessionConfig } from '../session/manager.js';
+import { isStdioAdapter } from '../adapters/stdio.js';
+import { validateAllBreakpoints } from '../session/breakpoints.js';
+import { validateExceptionBreakpointSpecs } from '../session/exception-breakpoints.js';
+import { expandDefines } from '../session/defines.js';
+import {
+  EVALUATE_CONTEXTS,
+  hasEvaluationContexts,
+  type EvaluateContext,
+} from '../session/variables.js';
+import { OutputFormatter } from '../output/formatter.js';
+import type { CodeReloadedEvent, DebugEvent } from '../output/events.js';
+import { logger } from '../util/logger.js';
+import { attachRefusals, attachTarget } from '../util/attach-guard.js';
+import { attachDenyRules, loadConfigFiles } from '../util/config.js';
+import {
+  ProjectStateStore,
+  type ProjectState,
+  type ProjectStateUpdate,
+} from './project-state.js';
+import { ServerMetrics } from './metrics.js';
+
+/** Events kept per session for replay; older events are dropped first */
+const MAX_BUFFERED_EVENTS = 10000;
+
+/** Ended sessions kept by default; the longest-ended are dropped first */
+export const DEFAULT_KEEP_SESSIONS = 100;
+
+/** Format version of saved session state */
+export const SAVED_SESSION_VERSION = 1;
+
+/**
+ * Parameters for starting a session (the JSON counterpart of the CLI options)
+ */
+export interface SessionParams {
+  adapter: string;
+  backend?: string;
+  program?: string;
+  module?: string;
+  args?: string[];
+  cwd?: string;
+  env?: Record<string, string>;
+  breakpoints?: string[];
+  logpoints?: string[];
+  /** Instruction address breakpoints (native adapters) */
+  instructionBreakpoints?: string[];
+  evaluations?: string[];
+  assertions?: string[];
+  /** Evaluate evaluations and assertions without side effects */
+  evalSafe?: boolean;
+  /** DAP context for evaluations without a context prefix (default: watch) */
+  evalContext?: EvaluateContext;
+  /** Levels to expand structured evaluation results (default: 0) */
+  evalExpand?: number;
+  /** Tag captured locals with the frame, scope and reference path they came from */
+  provenance?: boolean;
+  /** Time in milliseconds each stop may spend capturing locals and evaluations */
+  captureBudgetMs?: number;
+  exceptionFilters?: string[];
+  /** Session timeout in milliseconds (default: 60000) */
+  timeoutMs?: number;
+  captureLocals?: boolean;
+  steps?: number;
+  trace?: boolean;
+  traceInto?: boolean;
+  traceLimit?: number;
+  traceUntil?: string;
+  maxHits?: number;
+  /** Resume the breakpoint hits before this one without capturing them */
+  skipToHit?: number;
+  includeSource?: number;
+  /** Attach each stack frame's argument values to it */
+  captureArgs?: boolean;
+  /** Stay paused at each breakpoint hit until continued (default: false) */
+  pauseOnHit?: boolean;
+  /** Earlier stops evaluate can refer to as $1, $2, ... (default: serve --history) */
+  history?: number;
+  attach?: boolean;
+  pid?: number;
+  /**
+   * Project directory whose saved state fills in breakpoints, logpoints and evaluations
+   * not given here (default: cwd, else the server's working directory)
+   */
+  project?: string;
+  /** Start from this saved profile of the project; parameters given here win */
+  profile?: string;
+  /** Values for ${NAME} placeholders in breakpoints, evaluations and assertions */
+  defines?: Record<string, string>;
+}
+
+export type SessionStatus = 'running' | 'paused' | 'ended' | 'failed';
+
+export interface SessionSummary {
+  sessionId: string;
+  adapter: string;
+  program?: string;
+  pid?: number;
+  status: SessionStatus;
+  startedAt: string;
+  endedAt?: string;
+  error?: string;
+  eventCount: number;
+}
+
+/**
+ * Saved attach sessions (session.save, `serve --save-session`), read by `debug-run resume`
+ */
+export interface SavedSessionState {
+  version: number;
+  savedAt: string;
+  sessions: SavedSession[];
+}
+
+export interface SavedSession {
+  /** ID of the session the state was saved from */
+  sessionId: string;
+  status: SessionStatus;
+  /** Start parameters: target PID, breakpoints, logpoints and watch expressions */
+  params: SessionParams;
+}
+
+export interface SubscribeOptions {
+  /** Only deliver these event types (default: all) */
+  types?: string[];
+  /** Deliver already-buffered events first (default: true) */
+  replay?: boolean;
+}
+
+type EventListener = (sessionId: string, event: DebugEvent) => void;
+
+interface ManagedSession {
+  id: string;
+  params: SessionParams;
+  session: DebugSession;
+  status: 'running' | 'ended' | 'failed';
+  startedAt: string;
+  endedAt?: string;
+  error?: string;
+  events: DebugEvent[];
+  eventCount: number;
+  listeners: Set<EventListener>;
+  done: Promise<void>;
+}
+
+/**
+ * Error caused by invalid client input (maps to JSON-RPC -32602 / HTTP 400)
+ */
+export class InvalidParamsError extends Error {}
+
+/**
+ * Unknown session ID (an InvalidParamsError for JSON-RPC, HTTP 404 for REST)
+ */
+export class SessionNotFoundError extends InvalidParamsError {}
+
+/**
+ * Request not valid in the session's current state, e.g. continuing a session that
+ * isn't paused (maps to JSON-RPC -32000 / HTTP 409)
+ */
+export class SessionStateError extends Error {}
+
+export interface SessionRegistryOptions {
+  /** Stops kept for $N references when a session doesn't set history (default: 0) */
+  history?: number;
+  /** Where project state is kept (default: in memory) */
+  projectState?: ProjectStateStore;
+  /** Ended sessions kept for their summaries and events (default: DEFAULT_KEEP_SESSIONS) */
+  keepSessions?: number;
+}
+
+export class SessionRegistry {
+  /** Counts of the sessions run, served at /metrics */
+  readonly metrics = new ServerMetrics();
+  private sessions: Map<string, ManagedSession> = new Map();
+  private options: SessionRegistryOptions;
+  private projectState: ProjectStateStore;
+
+  constructor(options: SessionRegistryOptions = {}) {
+    this.options = options;
+    this.projectState = options.projectState ?? new ProjectStateStore();
+  }
+
+  /**
+   * Validate parameters and start a session in the background. Breakpoints, logpoints and
+   * evaluations not given come from the project's state, which then records the session's.
+   */
+  async start(requested: SessionParams): Promise<SessionSummary> {
+    const params = this.withProjectState(requested);
+    const config = await this.buildConfig(params);
+    const id = randomUUID();
+    this.projectState.update(projectDirectory(requested), {
+      breakpoints: params.breakpoints,
+      logpoints: params.logpoints,
+      evaluations: params.evaluations,
+    });
+
+    const formatter = new OutputFormatter({
+      stream: new Writable({
+        write(_chunk, _encoding, callback) {
+          callback();
+        },
+      }),
+    });
+    const managed: ManagedSession = {
+      id,
+      params,
+      session: new DebugSession(config, formatter),
+      status: 'running',
+      startedAt: new Date().toISOString(),
+      events: [],
+      eventCount: 0,
+      listeners: new Set(),
+      done: Promise.resolve(),
+    };
+    formatter.onEvent((event) => this.dispatch(managed, event));
+    this.sessions.set(id, managed);
+    this.metrics.sessionStarted(id, config.adapter.name);
+
+    logger.info('server', 'session started', { sessionId: id, adapter: params.adapter });
+    managed.done = managed.session.run().then(
+      () => this.finish(managed),
+      (error: unknown) => this.finish(managed, error)
+    );
+
+    return this.summarize(managed);
+  }
+
+  get(id: string): SessionSummary | undefined {
+    const managed = this.sessions.get(id);
+    return managed && this.summarize(managed);
+  }
+
+  list(): SessionSummary[] {
+    return [...this.sessions.values()].map((managed) => this.summarize(managed));
+  }
+
+  /**
+   * Subscribe to a session's events. Returns a function that unsubscribes.
+   */
+  subscribe(id: string, listener: EventListener, options: SubscribeOptions = {}): () => void {
+    const managed = this.require(id);
+    const types = options.types && options.types.length > 0 ? new Set(options.types) : null;
+    const filtered: EventListener = (sessionId, event) => {
+      if (!types || types.has(event.type)) {
+        listener(sessionId, event);
+      }
+    };
+
+    if (options.replay !== false) {
+      for (const event of managed.events) {
+        filtered(id, event);
+      }
+    }
+    managed.listeners.add(filtered);
+    return () => {
+      managed.listeners.delete(filtered);
+    };
+  }
+
+  /**
+   * Buffered events for a session, optionally filtered by type
+   */
+  events(id: string, types?: string[]): DebugEvent[] {
+    const managed = this.require(id);
+    return types && types.length > 0
+      ? managed.events.filter((event) => types.includes(event.type))
+      : [...managed.events];
+  }
+
+  /**
+   * Resume a session paused at a breakpoint (pauseOnHit)
+   */
+  async continue(id: string): Promise<SessionSummary> {
+    const managed = this.requirePaused(id);
+    await managed.session.continue();
+    return this.summarize(managed);
+  }
+
+  /**
+   * Evaluate an expression in a paused session
+   */
+  async evaluate(
+    id: string,
+    expression: string,
+    frameId?: number
+  ): Promise<{ result: string; type?: string; variablesReference: number }> {
+    if (typeof expression !== 'string' || expression.length === 0) {
+      throw new InvalidParamsError('"expression" is required');
+    }
+    const managed = this.requirePaused(id);
+    return managed.session.evaluate(expression, frameId);
+  }
+
+  /**
+   * Apply edited files to a running session's debuggee without restarting it
+   */
+  async reload(id: string, files: unknown, restartFrame?: boolean): Promise<CodeReloadedEvent> {
+    if (
+      !Array.isArray(files) ||
+      files.length === 0 ||
+      !files.every((file) => typeof file === 'string')
+    ) {
+      throw new InvalidParamsError('"files" must be a non-empty array of file paths');
+    }
+    const managed = this.require(id);
+    if (managed.status !== 'running') {
+      throw new SessionStateError(`Session ${id} has ${managed.status}`);
+    }
+    try {
+      return await managed.session.reloadCode(files, { restartFrame });
+    } catch (error) {
+      throw new SessionStateError(error instanceof Error ? error.message : String(error));
+    }
+  }
+
+  /**
+   * Relaunch a running session's program without restarting the adapter
+   */
+  async restart(id: string): Promise<SessionSummary> {
+    const managed = this.require(id);
+    if (managed.status !== 'running') {
+      throw new SessionStateError(`Session ${id} has ${managed.status}`);
+    }
+    try {
+      await managed.session.restart();
+    } catch (error) {
+      throw new SessionStateError(error instanceof Error ? error.message : String(error));
+    }
+    return this.summarize(managed);
+  }
+
+  /**
+   * End a running session (the debuggee is terminated unless attached)
+   */
+  async stop(id: string): Promise<SessionSummary> {
+    const managed = this.require(id);
+    if (managed.status === 'running') {
+      managed.session.stop();
+      await managed.done;
+    }
+    return this.summarize(managed);
+  }
+
+  /**
+   * Save a running attach session so it can be resumed after a restart
+   */
+  save(id: string): SavedSessionState {
+    const managed = this.require(id);
+    if (!managed.params.attach) {
+      throw new SessionStateError(
+        `Session ${id} launched its program, which ends with the session. ` +
+          'Only attach sessions can be saved'
+      );
+    }
+    if (managed.status !== 'running') {
+      throw new SessionStateError(`Session ${id} has ${managed.status}`);
+    }
+    return savedState([this.toSaved(managed)]);
+  }
+
+  /**
+   * Save every running attach session (launched sessions are skipped)
+   */
+  saveAll(): SavedSessionState {
+    return savedState(
+      [...this.sessions.values()]
+        .filter((managed) => managed.params.attach && managed.status === 'running')
+        .map((managed) => this.toSaved(managed))
+    );
+  }
+
+  /**
+   * Start new sessions from saved state, re-attaching with the saved breakpoints and watches
+   */
+  async resume(state: unknown): Promise<SessionSummary[]> {
+    const saved = parseSavedSessionState(state);
+    const summaries: SessionSummary[] = [];
+    for (const session of saved.sessions) {
+      summaries.push(await this.start(session.params));
+      logger.info('server', 'session resumed', {
+        from: session.sessionId,
+        sessionId: summaries[summaries.length - 1].sessionId,
+      });
+    }
+    return summaries;
+  }
+
+  /**
+   * A project's saved breakpoints, logpoints, watches and profiles
+   */
+  project(directory?: string): ProjectState {
+    return this.projectState.get(directory ?? process.cwd());
+  }
+
+  /**
+   * Change a project's saved state (lists replace the saved ones; profiles merge by name,
+   * null deleting one)
+   */
+  updateProject(directory: string | undefined, changes: unknown): ProjectState {
+    return this.projectState.update(directory ?? process.cwd(), parseProjectStateUpdate(changes));
+  }
+
+  /**
+   * Stop all running sessions (server shutdown)
+   */
+  async stopAll(): Promise<void> {
+    await Promise.all(
+      [...this.sessions.values()]
+        .filter((managed) => managed.status === 'running')
+        .map((managed) => this.stop(managed.id))
+    );
+  }
+
+  private require(id: string): ManagedSession {
+    const managed = this.sessions.get(id);
+    if (!managed) {
+      throw new SessionNotFoundError(`Unknown session: ${id}`);
+    }
+    return managed;
+  }
+
+  private requirePaused(id: string): ManagedSession {
+    const managed = this.require(id);
+    if (managed.status !== 'running' || !managed.session.isPaused()) {
+      throw new SessionStateError(
+        `Session ${id} is not paused (start it with pauseOnHit to stop at breakpoints)`
+      );
+    }
+    return managed;
+  }
+
+  /**
+   * Fill in parameters from the project's state: the named profile, then the saved
+   * breakpoints, logpoints and evaluations for any not given
+   */
+  private withProjectState(params: SessionParams): SessionParams {
+    // Malformed parameters are reported by buildConfig
+    if (!params || typeof params !== 'object') return params;
+
+    const state = this.projectState.get(projectDirectory(params));
+    let merged = params;
+    if (params.profile !== undefined) {
+      const profile = state.profiles[params.profile];
+      if (!profile) {
+        throw new InvalidParamsError(`Unknown profile "${params.profile}" for ${state.project}`);
+      }
+      merged = { ...profile, ...params } as SessionParams;
+      // A run's defines add to the profile's rather than replacing them
+      if (profile.defines && params.defines) {
+        merged.defines = { ...profile.defines, ...params.defines };
+      }
+    }
+    return {
+      ...merged,
+      breakpoints: merged.breakpoints ?? state.breakpoints,
+      logpoints: merged.logpoints ?? state.logpoints,
+      evaluations: merged.evaluations ?? state.evaluations,
+    };
+  }
+
+  private async buildConfig(requested: SessionParams): Promise<SessionConfig> {
+    const params = withDefines(requested);
+    if (!params || typeof params.adapter !== 'string') {
+      throw new InvalidParamsError(
+        `"adapter" is required (available adapters: ${getAdapterNames().join(', ')})`
+      );
+    }
+
+    if (isStdioAdapter(params.adapter)) {
+      // The server would run whatever command a client names
+      throw new InvalidParamsError('stdio: adapters are only available on the command line');
+    }
+    let adapter = getAdapter(params.adapter);
+    if (!adapter) {
+      throw new InvalidParamsError(
+        `Unknown adapter: ${params.adapter}. Available adapters: ${getAdapterNames().join(', ')}`
+      );
+    }
+    if (params.backend) {
+      try {
+        adapter = getAdapterBackend(adapter, params.backend);
+      } catch (error) {
+        throw new InvalidParamsError(error instanceof Error ? error.message : String(error));
+      }
+    }
+
+    if (params.attach ? !params.pid : !params.program && !params.module) {
+      throw new InvalidParamsError(
+        params.attach ? '"pid" is required when attaching' : '"program" or "module" is required'
+      );
+    }
+    if (params.attach) {
+      // The same guardrails as the CLI, without --allow-other-user: a client can't opt out
+      const refusals = attachRefusals(attachTarget(params.pid), {
+        denylist: attachDenyRules(loadConfigFiles()),
+        uid: process.getuid?.(),
+      });
+      if (refusals.length > 0) {
+        throw new InvalidParamsError(`Refusing to attach: ${refusals.join('; ')}`);
+      }
+    }
+
+    const breakpointErrors = validateAllBreakpoints(
+      params.breakpoints ?? [],
+      params.logpoints ?? [],
+      params.instructionBreakpoints ?? []
+    );
+    if (breakpointErrors.length > 0) {
+      throw new InvalidParamsError(breakpointErrors.join('; '));
+    }
+    const exceptionErrors = validateExceptionBreakpointSpecs(
+      params.exceptionFilters ?? [],
+      adapter
+    );
+    if (exceptionErrors.length > 0) {
+      throw new InvalidParamsError(exceptionErrors.join('; '));
+    }
+    if (params.instructionBreakpoints?.length && !adapter.instructionBreakpoints) {
+      throw new InvalidParamsError(
+        `Adapter "${adapter.name}" does not support instruction breakpoints`
+      );
+    }
+    if (params.evalContext !== undefined && !EVALUATE_CONTEXTS.includes(params.evalContext)) {
+      throw new InvalidParamsError(`"evalContext" must be one of: ${EVALUATE_CONTEXTS.join(', ')}`);
+    }
+    if (
+      params.history !== undefined &&
+      (!Number.isInteger(params.history) || params.history < 0)
+    ) {
+      throw new InvalidParamsError('"history" must be a non-negative integer');
+    }
+    if (
+      params.evalExpand !== undefined &&
+      (!Number.isInteger(params.evalExpand) || params.evalExpand < 0)
+    ) {
+      throw new InvalidParamsError('"evalExpand" must be a non-negative integer');
+    }
+    if (
+      params.skipToHit !== undefined &&
+      (!Number.isInteger(params.skipToHit) || params.skipToHit < 1)
+    ) {
+      throw new InvalidParamsError('"skipToHit" must be a positive integer');
+    }
+    if (
+      params.captureBudgetMs !== undefined &&
+      (!Number.isInteger(params.captureBudgetMs) || params.captureBudgetMs < 0)
+    ) {
+      throw new InvalidParamsError('"captureBudgetMs" must be a non-negative integer');
+    }
+    if (params.evalSafe && hasEvaluationContexts(params.evaluations ?? [], params.evalContext)) {
+      throw new InvalidParamsError(
+        '"evalSafe" picks the evaluation context; drop "evalContext" and context prefixes'
+      );
+    }
+
+    if (!(await adapter.detect())) {
+      throw new InvalidParamsError(
+        `Adapter "${adapter.name}" is not installed. ${adapter.installHint}`
+      );
+    }
+    // Checked after detect(), which picks the backend for dotnet and rust
+    if (params.evalSafe && !adapter.safeEvaluation) {
+      throw new InvalidParamsError(
+        `Adapter "${adapter.name}" does not support side-effect-free evaluation`
+      );
+    }
+
+    return {
+      adapter,
+      program: params.program,
+      module: params.module,
+      args: params.args,
+      cwd: params.cwd,
+      env: params.env,
+      breakpoints: params.breakpoints ?? [],
+      logpoints: params.logpoints,
+      instructionBreakpoints: params.instructionBreakpoints,
+      evaluations: params.evaluations,
+      assertions: params.assertions,
+      evalSafe: params.evalSafe,
+      evalContext: params.evalContext,
+      evalExpand: params.evalExpand,
+      provenance: params.provenance,
+      captureBudget: params.captureBudgetMs,
+      exceptionFilters: params.exceptionFilters,
+      timeout: params.timeoutMs ?? 60000,
+      captureLocals: params.captureLocals ?? true,
+      steps: params.steps,
+      trace: params.trace,
+      traceInto: params.traceInto,
+      traceLimit: params.traceLimit ?? 500,
+      traceUntil: params.traceUntil,
+      maxHits: params.maxHits,
+      skipToHit: params.skipToHit,
+      includeSource: params.includeSource,
+      captureArgs: params.captureArgs,
+      pauseOnHit: params.pauseOnHit,
+      history: params.history ?? this.options.history,
+      attach: params.attach,
+      pid: params.pid,
+    };
+  }
+
+  private toSaved(managed: ManagedSession): SavedSession {
+    return {
+      sessionId: managed.id,
+      status: this.summarize(managed).status,
+      params: managed.params,
+    };
+  }
+
+  private dispatch(managed: ManagedSession, event: DebugEvent): void {
+    this.metrics.observe(managed.id, event);
+    managed.events.push(event);
+    managed.eventCount++;
+    if (managed.events.length > MAX_BUFFERED_EVENTS) {
+      managed.events.shift();
+    }
+
+    for (const listener of managed.listeners) {
+      try {
+        listener(managed.id, event);
+      } catch (error) {
+        logger.warn('server', 'event listener failed', {
+          sessionId: managed.id,
+          error: error instanceof Error ? error.message : String(error),
+        });
+      }
+    }
+  }
+
+  private finish(managed: ManagedSession, error?: unknown): void {
+    managed.status = error === undefined ? 'ended' : 'failed';
+    managed.endedAt = new Date().toISOString();
+    if (error !== undefined) {
+      managed.error = error instanceof Error ? error.message : String(error);
+    }
+    this.metrics.sessionFinished(managed.id, managed.status);
+    logger.info('server', 'session ended', {
+      sessionId: managed.id,
+      status: managed.status,
+      error: managed.error,
+    });
+    this.evictEnded();
+  }
+
+  /**
+   * Forget the longest-ended sessions beyond the number kept
+   */
+  private evictEnded(): void {
+    const ended = [...this.sessions.values()]
+      .filter((managed) => managed.status !== 'running')
+      .sort((a, b) => (a.endedAt ?? '').localeCompare(b.endedAt ?? ''));
+    const excess = ended.length - (this.options.keepSessions ?? DEFAULT_KEEP_SESSIONS);
+    for (const managed of ended.slice(0, Math.max(excess, 0))) {
+      managed.listeners.clear();
+      this.sessions.delete(managed.id);
+    }
+  }
+
+  private summarize(managed: ManagedSession): SessionSummary {
+    return {
+      sessionId: managed.id,
+      adapter: managed.params.adapter,
+      ...(managed.params.program && { program: managed.params.program }),
+      ...(managed.params.pid && { pid: managed.params.pid }),
+      status:
+        managed.status === 'running' && managed.session.isPaused() ? 'paused' : managed.status,
+      startedAt: managed.startedAt,
+      ...(managed.endedAt && { endedAt: managed.endedAt }),
+      ...(managed.error && { error: managed.error }),
+      eventCount: managed.eventCount,
+    };
+  }
+}
+
+/**
+ * Parameters with the "defines" values filled into the ${NAME} placeholders of their
+ * breakpoints, evaluations and assertions (the saved project state keeps the placeholders)
+ */
+function withDefines(params: SessionParams): SessionParams {
+  const defines = params?.defines;
+  if (defines === undefined) return params;
+  if (
+    !defines ||
+    typeof defines !== 'object' ||
+    Array.isArray(defines) ||
+    Object.values(defines).some((value) => typeof value !== 'string')
+  ) {
+    throw new InvalidParamsError('"defines" must be an object of string values');
+  }
+  // Malformed lists are reported by the validation that follows
+  const expand = (texts?: string[]) =>
+    Array.isArray(texts)
+      ? texts.map((text) => (typeof text === 'string' ? expandDefines(text, defines) : text))
+      : texts;
+  return {
+    ...params,
+    breakpoints: expand(params.breakpoints),
+    evaluations: expand(params.evaluations),
+    assertions: expand(params.assertions),
+  };
+}
+
+/**
+ * The project a session's state is kept under
+ */
+function projectDirectory(params: SessionParams): string {
+  return params.project ?? params.cwd ?? process.cwd();
+}
+
+function isStringArray(value: unknown): value is string[] {
+  return Array.isArray(value) && value.every((item) => typeof item === 'string');
+}
+
+/**
+ * Validate a project state update (e.g., from a client request)
+ *
+ * @throws InvalidParamsError if a field has the wrong type
+ */
+export function parseProjectStateUpdate(value: unknown): ProjectStateUpdate {
+  const update = value as Record<string, unknown> | null;
+  if (!update || typeof update !== 'object' || Array.isArray(update)) {
+    throw new InvalidParamsError('Project state must be an object');
+  }
+  for (const field of ['breakpoints', 'logpoints', 'evaluations']) {
+    if (update[field] !== undefined && !isStringArray(update[field])) {
+      throw new InvalidParamsError(`"${field}" must be an array of strings`);
+    }
+  }
+  const profiles = update.profiles as Record<string, unknown> | undefined;
+  if (profiles !== undefined) {
+    if (!profiles || typeof profiles !== 'object' || Array.isArray(profiles)) {
+      throw new InvalidParamsError('"profiles" must be an object of named session parameters');
+    }
+    for (const [name, profile] of Object.entries(profiles)) {
+      if (profile !== null && (typeof profile !== 'object' || Array.isArray(profile))) {
+        throw new InvalidParamsError(`Profile "${name}" must be an object of parameters or null`);
+      }
+    }
+  }
+  return update as ProjectStateUpdate;
+}
+
+function savedState(sessions: SavedSession[]): SavedSessionState {
+  return { version: SAVED_SESSION_VERSION, savedAt: new Date().toISOString(), sessions };
+}
+
+/**
+ * Validate saved session state (e.g., read from a file)
+ *
+ * @throws InvalidParamsError if the state is malformed or not resumable
+ */
+export function parseSavedSessionState(value: unknown): SavedSessionState {
+  const state = value as Partial<SavedSessionState> | null;
+  if (!state || typeof state !== 'object' || !Array.isArray(state.sessions)) {
+    throw new InvalidParamsError('Saved session state must have a "sessions" array');
+  }
+  if (state.version !== SAVED_SESSION_VERSION) {
+    throw new InvalidParamsError(
+      `Unsupported saved session version: ${state.version} (expected ${SAVED_SESSION_VERSION})`
+    );
+  }
+  for (const session of state.sessions) {
+    if (!session?.params?.attach || typeof session.params.pid !== 'number') {
+      throw new InvalidParamsError(
+        `Saved session ${session?.sessionId ?? '?'} is not an attach session with a "pid"`
+      );
+    }
+  }
+  return state as SavedSessionState;
+}
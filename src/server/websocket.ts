@@ -0,0 +1,243 @@
+/**
+ * Minimal WebSocket Server Support
+ *
+ * Implements the server side of RFC 6455 on top of node:http upgrade requests:
+ * handshake, masked client frames, fragmentation, ping/pong and close. Only text
+ * messages are surfaced; that's all the serve protocol uses.
+ */
+
+import { createHash } from 'node:crypto';
+import { EventEmitter } from 'node:events';
+import type { IncomingMessage } from 'node:http';
+import type { Duplex } from 'node:stream';
+
+const HANDSHAKE_GUID = '258EAFA5-E914-47DA-95CA-C5AB0DC85B11';
+
+/** Largest accepted message (client messages are small JSON requests) */
+const MAX_MESSAGE_BYTES = 16 * 1024 * 1024;
+
+const OPCODE_CONTINUATION = 0x0;
+const OPCODE_TEXT = 0x1;
+const OPCODE_BINARY = 0x2;
+const OPCODE_CLOSE = 0x8;
+const OPCODE_PING = 0x9;
+const OPCODE_PONG = 0xa;
+
+/**
+ * Compute the Sec-WebSocket-Accept value for a client key
+ */
+export function computeAcceptKey(key: string): string {
+  return createHash('sha1')
+    .update(key + HANDSHAKE_GUID)
+    .digest('base64');
+}
+
+/**
+ * Encode a single unmasked server frame
+ */
+export function encodeFrame(opcode: number, payload: Buffer): Buffer {
+  let header: Buffer;
+  if (payload.length < 126) {
+    header = Buffer.from([0x80 | opcode, payload.length]);
+  } else if (payload.length < 0x10000) {
+    header = Buffer.alloc(4);
+    header[0] = 0x80 | opcode;
+    header[1] = 126;
+    header.writeUInt16BE(payload.length, 2);
+  } else {
+    header = Buffer.alloc(10);
+    header[0] = 0x80 | opcode;
+    header[1] = 127;
+    header.writeBigUInt64BE(BigInt(payload.length), 2);
+  }
+  return Buffer.concat([header, payload]);
+}
+
+export interface DecodedFrame {
+  fin: boolean;
+  opcode: number;
+  payload: Buffer;
+  /** Total bytes consumed from the buffer */
+  length: number;
+}
+
+/**
+ * Decode one frame from the start of a buffer, or return null if more data is needed
+ */
+export function decodeFrame(buffer: Buffer): DecodedFrame | null {
+  if (buffer.length < 2) return null;
+
+  const fin = (buffer[0] & 0x80) !== 0;
+  const opcode = buffer[0] & 0x0f;
+  const masked = (buffer[1] & 0x80) !== 0;
+  let payloadLength = buffer[1] & 0x7f;
+  let offset = 2;
+
+  if (payloadLength === 126) {
+    if (buffer.length < 4) return null;
+    payloadLength = buffer.readUInt16BE(2);
+    offset = 4;
+  } else if (payloadLength === 127) {
+    if (buffer.length < 10) return null;
+    const length = buffer.readBigUInt64BE(2);
+    if (length > BigInt(MAX_MESSAGE_BYTES)) {
+      throw new Error('WebSocket frame too large');
+    }
+    payloadLength = Number(length);
+    offset = 10;
+  }
+
+  const maskOffset = offset;
+  if (masked) offset += 4;
+  if (buffer.length < offset + payloadLength) return null;
+
+  const payload = Buffer.from(buffer.subarray(offset, offset + payloadLength));
+  if (masked) {
+    for (let i = 0; i < payload.length; i++) {
+      payload[i] ^= buffer[maskOffset + (i % 4)];
+    }
+  }
+
+  return { fin, opcode, payload, length: offset + payloadLength };
+}
+
+/**
+ * A server-side WebSocket connection.
+ *
+ * Events: 'message' (text: string), 'close' ()
+ */
+export class WebSocketConnection extends EventEmitter {
+  private buffer: Buffer = Buffer.alloc(0);
+  private fragments: Buffer[] = [];
+  private fragmentOpcode: number | null = null;
+  private closed = false;
+
+  constructor(private socket: Duplex) {
+    super();
+    socket.on('data', (chunk: Buffer) => this.handleData(chunk));
+    socket.on('close', () => this.handleClose());
+    socket.on('error', () => this.handleClose());
+  }
+
+  get isOpen(): boolean {
+    return !this.closed;
+  }
+
+  /**
+   * Send a text message
+   */
+  send(text: string): void {
+    if (this.closed) return;
+    this.socket.write(encodeFrame(OPCODE_TEXT, Buffer.from(text, 'utf-8')));
+  }
+
+  /**
+   * Send a close frame and end the connection
+   */
+  close(code: number = 1000): void {
+    if (this.closed) return;
+    const payload = Buffer.alloc(2);
+    payload.writeUInt16BE(code, 0);
+    this.socket.end(encodeFrame(OPCODE_CLOSE, payload));
+    // Don't wait forever for the peer to finish the closing handshake
+    setTimeout(() => this.socket.destroy(), 1000).unref();
+    this.handleClose();
+  }
+
+  private handleData(chunk: Buffer): void {
+    this.buffer = Buffer.concat([this.buffer, chunk]);
+
+    try {
+      let frame: DecodedFrame | null;
+      while (!this.closed && (frame = decodeFrame(this.buffer))) {
+        this.buffer = this.buffer.subarray(frame.length);
+        this.handleFrame(frame);
+      }
+    } catch {
+      // Protocol error
+      this.close(1002);
+    }
+  }
+
+  private handleFrame(frame: DecodedFrame): void {
+    switch (frame.opcode) {
+      case OPCODE_TEXT:
+      case OPCODE_BINARY:
+        if (frame.fin) {
+          this.deliver(frame.opcode, frame.payload);
+        } else {
+          this.fragmentOpcode = frame.opcode;
+          this.fragments = [frame.payload];
+        }
+        break;
+
+      case OPCODE_CONTINUATION:
+        if (this.fragmentOpcode === null) {
+          throw new Error('Unexpected continuation frame');
+        }
+        this.fragments.push(frame.payload);
+        if (this.fragments.reduce((n, f) => n + f.length, 0) > MAX_MESSAGE_BYTES) {
+          throw new Error('WebSocket message too large');
+        }
+        if (frame.fin) {
+          this.deliver(this.fragmentOpcode, Buffer.concat(this.fragments));
+          this.fragmentOpcode = null;
+          this.fragments = [];
+        }
+        break;
+
+      case OPCODE_PING:
+        this.socket.write(encodeFrame(OPCODE_PONG, frame.payload));
+        break;
+
+      case OPCODE_PONG:
+        break;
+
+      case OPCODE_CLOSE:
+        this.close(1000);
+        break;
+
+      default:
+        throw new Error(`Unknown opcode ${frame.opcode}`);
+    }
+  }
+
+  private deliver(opcode: number, payload: Buffer): void {
+    if (opcode === OPCODE_TEXT) {
+      this.emit('message', payload.toString('utf-8'));
+    }
+  }
+
+  private handleClose(): void {
+    if (this.closed) return;
+    this.closed = true;
+    this.emit('close');
+  }
+}
+
+/**
+ * Complete the WebSocket handshake for an HTTP upgrade request.
+ * Returns null (after rejecting the request) if it isn't a valid WebSocket upgrade.
+ */
+export function acceptWebSocket(
+  request: IncomingMessage,
+  socket: Duplex
+): WebSocketConnection | null {
+  const key = request.headers['sec-websocket-key'];
+  if (request.headers.upgrade?.toLowerCase() !== 'websocket' || typeof key !== 'string') {
+    socket.end('HTTP/1.1 400 Bad Request\r\nConnection: close\r\n\r\n');
+    return null;
+  }
+
+  socket.write(
+    [
+      'HTTP/1.1 101 Switching Protocols',
+      'Upgrade: websocket',
+      'Connection: Upgrade',
+      `Sec-WebSocket-Accept: ${computeAcceptKey(key)}`,
+      '',
+      '',
+    ].join('\r\n')
+  );
+  return new WebSocketConnection(socket);
+}
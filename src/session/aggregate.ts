@@ -0,0 +1,145 @@
+/**
+ * Hit Aggregation (--aggregate)
+ *
+ * For breakpoints in hot loops: instead of a breakpoint_hit snapshot per hit, each
+ * "expression:stat,stat" spec is evaluated at every hit and folded into running
+ * statistics, reported once in session_end. Numeric stats (min, max, mean, sum) use the
+ * values that parse as numbers; count and distinct use every value.
+ */
+
+import type { AggregateSummary } from '../output/events.js';
+
+export const AGGREGATE_STATS = ['min', 'max', 'mean', 'sum', 'distinct'] as const;
+export type AggregateStat = (typeof AGGREGATE_STATS)[number];
+
+/** Stats reported when a spec doesn't list any */
+const DEFAULT_STATS: AggregateStat[] = ['min', 'max', 'mean'];
+
+/** The part after the last colon, when it's a list of words ("min,max") */
+const STATS_LIST_PATTERN = /^\s*[a-z]+(?:\s*,\s*[a-z]+)*\s*$/;
+
+export interface AggregateSpec {
+  expression: string;
+  stats: AggregateStat[];
+}
+
+function isAggregateStat(name: string): name is AggregateStat {
+  return (AGGREGATE_STATS as readonly string[]).includes(name);
+}
+
+/**
+ * Parse "item.unit_price:min,max,mean". Without a stats list ("item.unit_price") the
+ * default stats apply. A trailing part with no known stat in it ("a ? b : c", "xs[1:3]")
+ * stays in the expression.
+ *
+ * @throws Error if the expression is empty or a stat is unknown
+ */
+export function parseAggregateSpec(spec: string): AggregateSpec {
+  const colon = spec.lastIndexOf(':');
+  const tail = colon === -1 ? '' : spec.slice(colon + 1);
+  const names = STATS_LIST_PATTERN.test(tail) ? tail.split(',').map((name) => name.trim()) : [];
+
+  let expression = spec.trim();
+  let stats = DEFAULT_STATS;
+  if (names.some(isAggregateStat)) {
+    const unknown = names.find((name) => !isAggregateStat(name));
+    if (unknown) {
+      throw new Error(
+        `Invalid --aggregate "${spec}": unknown stat "${unknown}" ` +
+          `(use ${AGGREGATE_STATS.join(', ')})`
+      );
+    }
+    expression = spec.slice(0, colon).trim();
+    stats = names as AggregateStat[];
+  }
+
+  if (!expression) {
+    throw new Error(`Invalid --aggregate "${spec}": missing expression`);
+  }
+  return { expression, stats };
+}
+
+/**
+ * Parse a debugger's rendering of a value as a number ("12.5", "-3", "1e3")
+ */
+export function parseNumericValue(value: string): number | undefined {
+  const trimmed = value.trim();
+  if (!/^[-+]?(\d+\.?\d*|\.\d+)(e[-+]?\d+)?$/i.test(trimmed)) return undefined;
+  return Number(trimmed);
+}
+
+interface Accumulator {
+  spec: AggregateSpec;
+  samples: number;
+  errors: number;
+  numeric: number;
+  min: number;
+  max: number;
+  sum: number;
+  values: Set<string>;
+}
+
+export class HitAggregator {
+  private accumulators: Accumulator[];
+
+  constructor(specs: AggregateSpec[]) {
+    this.accumulators = specs.map((spec) => ({
+      spec,
+      samples: 0,
+      errors: 0,
+      numeric: 0,
+      min: Infinity,
+      max: -Infinity,
+      sum: 0,
+      values: new Set<string>(),
+    }));
+  }
+
+  /**
+   * Expressions to evaluate at each hit
+   */
+  expressions(): string[] {
+    return this.accumulators.map((acc) => acc.spec.expression);
+  }
+
+  /**
+   * Fold one hit's value (or evaluation error) for the spec at `index` into its stats
+   */
+  record(index: number, value: { result: string } | { error: string }): void {
+    const acc = this.accumulators[index];
+    if ('error' in value) {
+      acc.errors++;
+      return;
+    }
+    acc.samples++;
+    acc.values.add(value.result);
+    const number = parseNumericValue(value.result);
+    if (number === undefined) return;
+    acc.numeric++;
+    acc.min = Math.min(acc.min, number);
+    acc.max = Math.max(acc.max, number);
+    acc.sum += number;
+  }
+
+  /**
+   * The statistics so far, one entry per spec
+   */
+  list(): AggregateSummary[] {
+    return this.accumulators.map((acc) => {
+      const stats = new Set(acc.spec.stats);
+      const numeric = acc.numeric > 0;
+      const nonNumeric = acc.samples - acc.numeric;
+      return {
+        expression: acc.spec.expression,
+        count: acc.samples,
+        ...(acc.errors > 0 && { errors: acc.errors }),
+        ...(nonNumeric > 0 && !(stats.size === 1 && stats.has('distinct')) && { nonNumeric }),
+        ...(stats.has('min') && numeric && { min: acc.min }),
+        ...(stats.has('max') && numeric && { max: acc.max }),
+        ...(stats.has('mean') && numeric && { mean: acc.sum / acc.numeric }),
+        ...(stats.has('sum') && numeric && { sum: acc.sum }),
+        ...(stats.has('distinct') && { distinct: acc.values.size }),
+      };
+    });
+  }
+}
@@ -0,0 +1,498 @@
+/**
+ * Structural Breakpoints (--break-ast)
+ *
+ * Hard-coded line numbers go stale as soon as the code above them changes. A structural
+ * query such as "fn:calculate_discount:first-return" or "loop-body:process_order" is
+ * resolved against the source when the session starts instead.
+ *
+ * Source files are parsed with tree-sitter. Its Node binding and the grammars are optional
+ * peer dependencies, loaded on first use, so debug-run installs without a compiler and only
+ * the languages breakpoints are placed in need a grammar.
+ */
+
+import * as fs from 'node:fs';
+import { createRequire } from 'node:module';
+import * as path from 'node:path';
+import { sourceExtensions } from './diff-breakpoints.js';
+
+const require = createRequire(import.meta.url);
+
+export type StructuralPoint = 'start' | 'first-return' | 'returns' | 'loop-body';
+
+export interface StructuralQuery {
+  /** The query as given */
+  spec: string;
+  /** File to look in; without one, source files under cwd are searched */
+  file?: string;
+  /** Name of the function (or method); * and ? match any characters of a name */
+  function: string;
+  point: StructuralPoint;
+}
+
+const FUNCTION_POINTS = ['start', 'first-return', 'returns'];
+
+/** [file:]fn:<name>[:point] or [file:]loop-body:<name> */
+const QUERY_PATTERN = /^(?:(.+):)?(fn|loop-body):([\w$*?]+)(?::([a-z-]+))?$/;
+
+/** Directories never searched for functions */
+const EXCLUDED_DIRS = [
+  'node_modules',
+  '.git',
+  '__pycache__',
+  '.venv',
+  'venv',
+  'dist',
+  'build',
+  'out',
+  'bin',
+  'obj',
+  'target',
+  'coverage',
+];
+
+/** A tree-sitter grammar package, and the export holding the language if it has several */
+interface Grammar {
+  package: string;
+  language?: string;
+}
+
+/** Grammars by file extension */
+const GRAMMARS: Record<string, Grammar> = {
+  '.js': { package: 'tree-sitter-javascript' },
+  '.mjs': { package: 'tree-sitter-javascript' },
+  '.cjs': { package: 'tree-sitter-javascript' },
+  '.jsx': { package: 'tree-sitter-javascript' },
+  '.ts': { package: 'tree-sitter-typescript', language: 'typescript' },
+  '.mts': { package: 'tree-sitter-typescript', language: 'typescript' },
+  '.cts': { package: 'tree-sitter-typescript', language: 'typescript' },
+  '.tsx': { package: 'tree-sitter-typescript', language: 'tsx' },
+  '.py': { package: 'tree-sitter-python' },
+  '.rs': { package: 'tree-sitter-rust' },
+  '.cs': { package: 'tree-sitter-c-sharp' },
+  '.c': { package: 'tree-sitter-c' },
+  '.h': { package: 'tree-sitter-cpp' },
+  '.cc': { package: 'tree-sitter-cpp' },
+  '.cpp': { package: 'tree-sitter-cpp' },
+  '.cxx': { package: 'tree-sitter-cpp' },
+  '.hpp': { package: 'tree-sitter-cpp' },
+  '.go': { package: 'tree-sitter-go' },
+  '.java': { package: 'tree-sitter-java' },
+};
+
+/** Functions, methods and closures, across the grammars */
+const FUNCTION_TYPES = new Set([
+  'function_declaration',
+  'generator_function_declaration',
+  'function_expression',
+  'function',
+  'generator_function',
+  'arrow_function',
+  'method_definition',
+  'function_definition',
+  'lambda',
+  'function_item',
+  'closure_expression',
+  'method_declaration',
+  'constructor_declaration',
+  'local_function_statement',
+  'lambda_expression',
+  'anonymous_method_expression',
+  'func_literal',
+]);
+
+/** Nodes that hold a function's or loop's statements */
+const BLOCK_TYPES = new Set(['statement_block', 'block', 'compound_statement']);
+
+const RETURN_TYPES = new Set(['return_statement', 'return_expression']);
+
+const LOOP_TYPES = new Set([
+  'for_statement',
+  'for_in_statement',
+  'while_statement',
+  'do_statement',
+  'foreach_statement',
+  'enhanced_for_statement',
+  'for_range_loop',
+  'for_expression',
+  'while_expression',
+  'loop_expression',
+]);
+
+/** Where an anonymous function's name is, by the node it is assigned in */
+const BINDING_FIELDS: Record<string, string> = {
+  variable_declarator: 'name',
+  assignment_expression: 'left',
+  pair: 'key',
+  public_field_definition: 'name',
+  field_definition: 'property',
+  let_declaration: 'pattern',
+};
+
+/** The subset of a tree-sitter syntax node used here */
+interface SyntaxNode {
+  type: string;
+  text: string;
+  startPosition: { row: number; column: number };
+  namedChildren: SyntaxNode[];
+  parent: SyntaxNode | null;
+  childForFieldName(name: string): SyntaxNode | null;
+}
+
+/** The subset of tree-sitter's Parser used here */
+interface Parser {
+  setLanguage(language: unknown): void;
+  parse(
+    source: string,
+    oldTree?: unknown,
+    options?: { bufferSize?: number }
+  ): { rootNode: SyntaxNode };
+}
+
+/** Parsers created so far, by grammar package and language */
+const parsers = new Map<string, Parser>();
+
+/**
+ * Parse a --break-ast query (or the structural part of a -b glob, named by `option` in errors)
+ *
+ * @throws Error if the query isn't fn:<name>[:start|first-return|returns] or loop-body:<name>
+ */
+export function parseStructuralQuery(spec: string, option = '--break-ast'): StructuralQuery {
+  const match = spec.trim().match(QUERY_PATTERN);
+  if (!match) {
+    throw new Error(
+      `Invalid ${option} "${spec}": expected [file:]fn:<function>[:start|first-return|returns] ` +
+        'or [file:]loop-body:<function>'
+    );
+  }
+  const [, file, kind, name, point] = match;
+  if (kind === 'loop-body' && point) {
+    throw new Error(`Invalid ${option} "${spec}": loop-body takes no point`);
+  }
+  if (point && !FUNCTION_POINTS.includes(point)) {
+    throw new Error(
+      `Invalid ${option} "${spec}": unknown point "${point}" (use ${FUNCTION_POINTS.join(', ')})`
+    );
+  }
+  return {
+    spec,
+    ...(file && { file }),
+    function: name,
+    point: kind === 'loop-body' ? 'loop-body' : ((point ?? 'start') as StructuralPoint),
+  };
+}
+
+/**
+ * Whether structural breakpoints can be placed in a file: tree-sitter has a grammar for its
+ * language (which may still need installing)
+ */
+export function hasGrammar(file: string): boolean {
+  return path.extname(file).toLowerCase() in GRAMMARS;
+}
+
+/**
+ * A parser for a file's language, loading tree-sitter and the grammar the first time
+ *
+ * @throws Error if there is no grammar for the language, or it or tree-sitter isn't installed
+ */
+function parserFor(file: string): Parser {
+  const extension = path.extname(file).toLowerCase();
+  const grammar = GRAMMARS[extension];
+  if (!grammar) {
+    throw new Error(`No tree-sitter grammar for ${extension || 'extensionless'} files (${file})`);
+  }
+
+  const key = `${grammar.package}:${grammar.language ?? ''}`;
+  let parser = parsers.get(key);
+  if (!parser) {
+    let TreeSitter: new () => Parser;
+    let module: Record<string, unknown>;
+    try {
+      TreeSitter = require('tree-sitter');
+      module = require(grammar.package);
+    } catch {
+      throw new Error(
+        `Parsing ${extension} files needs the optional tree-sitter packages ` +
+          `(npm install tree-sitter ${grammar.package})`
+      );
+    }
+    parser = new TreeSitter();
+    parser.setLanguage(grammar.language ? module[grammar.language] : module);
+    parsers.set(key, parser);
+  }
+  return parser;
+}
+
+/**
+ * Whether tree-sitter and the grammar for a file's language are installed
+ */
+export function isParserAvailable(file: string): boolean {
+  try {
+    parserFor(file);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * A function's name: its own, the one it is assigned to, or for C/C++ the declarator's
+ * (the last part of a qualified name)
+ */
+function functionName(node: SyntaxNode): string | undefined {
+  const name = node.childForFieldName('name');
+  if (name) return name.text;
+
+  let declarator = node.childForFieldName('declarator');
+  while (declarator) {
+    if (declarator.type === 'function_declarator') {
+      return declarator.childForFieldName('declarator')?.text.split('::').pop();
+    }
+    declarator =
+      declarator.childForFieldName('declarator') ??
+      declarator.namedChildren.find((child) => child.type.endsWith('declarator')) ??
+      null;
+  }
+
+  const parent = node.parent;
+  const field = parent && BINDING_FIELDS[parent.type];
+  const binding = field ? parent.childForFieldName(field) : null;
+  if (!binding) return undefined;
+  // this.handler = () => ...
+  return (binding.childForFieldName('property') ?? binding).text;
+}
+
+function namePattern(name: string): RegExp {
+  const id = name.replace(/\$/g, '\\$').replace(/\*/g, '[\\w$]*').replace(/\?/g, '[\\w$]');
+  return new RegExp(`^${id}$`);
+}
+
+/**
+ * Nodes under a node that belong to its function: everything but nested functions
+ */
+function ownDescendants(node: SyntaxNode, found: SyntaxNode[] = []): SyntaxNode[] {
+  for (const child of node.namedChildren) {
+    if (FUNCTION_TYPES.has(child.type)) continue;
+    found.push(child);
+    ownDescendants(child, found);
+  }
+  return found;
+}
+
+function isComment(node: SyntaxNode): boolean {
+  return node.type.endsWith('comment');
+}
+
+/**
+ * A leading string statement: a Python docstring or a JavaScript directive
+ */
+function isDocstring(node: SyntaxNode): boolean {
+  return (
+    node.type === 'expression_statement' &&
+    node.namedChildren.length === 1 &&
+    node.namedChildren[0].type === 'string'
+  );
+}
+
+/**
+ * Row of the first statement in a body (besides comments, a docstring and nested function
+ * declarations), or of an expression body itself; `fallback` when the body is empty
+ */
+function firstStatementRow(body: SyntaxNode | null, fallback: number): number {
+  if (!body) return fallback;
+  if (!BLOCK_TYPES.has(body.type)) return body.startPosition.row;
+
+  let statements = body.namedChildren.filter((child) => !isComment(child));
+  // Go wraps a block's statements in a statement_list
+  if (statements.length === 1 && statements[0].type === 'statement_list') {
+    statements = statements[0].namedChildren.filter((child) => !isComment(child));
+  }
+  if (statements.length > 0 && isDocstring(statements[0])) statements = statements.slice(1);
+  const first = statements.find((statement) => !FUNCTION_TYPES.has(statement.type));
+  return first ? first.startPosition.row : fallback;
+}
+
+/**
+ * The callback of `items.forEach(...)`, which runs once per item like a loop body
+ */
+function forEachCallback(node: SyntaxNode): SyntaxNode | undefined {
+  if (node.type !== 'call_expression') return undefined;
+  const callee = node.childForFieldName('function');
+  if (callee?.type !== 'member_expression') return undefined;
+  if (callee.childForFieldName('property')?.text !== 'forEach') return undefined;
+  return node
+    .childForFieldName('arguments')
+    ?.namedChildren.find((argument) => FUNCTION_TYPES.has(argument.type));
+}
+
+/**
+ * Rows (0-based) a query places breakpoints on in one function
+ */
+function pointRows(fn: SyntaxNode, point: StructuralPoint): number[] {
+  const row = fn.startPosition.row;
+  const body = fn.childForFieldName('body');
+  if (!body) return [];
+  // An expression body (`=> expr`, a lambda) is where the function starts and returns
+  if (!BLOCK_TYPES.has(body.type)) {
+    return point === 'loop-body' ? [] : [body.startPosition.row];
+  }
+
+  const nodes = ownDescendants(body);
+  switch (point) {
+    case 'start':
+      return [firstStatementRow(body, row)];
+    case 'first-return':
+    case 'returns': {
+      const returns = nodes.filter((node) => RETURN_TYPES.has(node.type));
+      const rows = returns.map((node) => node.startPosition.row);
+      return point === 'first-return' ? rows.slice(0, 1) : rows;
+    }
+    case 'loop-body': {
+      const rows: number[] = [];
+      for (const node of nodes) {
+        const loopBody = LOOP_TYPES.has(node.type)
+          ? node.childForFieldName('body')
+          : forEachCallback(node)?.childForFieldName('body');
+        if (loopBody !== undefined) {
+          rows.push(firstStatementRow(loopBody, node.startPosition.row));
+        }
+      }
+      return rows;
+    }
+  }
+}
+
+/**
+ * Lines (1-based) a query places breakpoints on in one file, and how many functions with
+ * the query's name the file declares
+ *
+ * @throws Error if the file's language can't be parsed
+ */
+export function structuralBreakpointLines(
+  source: string,
+  query: StructuralQuery,
+  file: string
+): { functions: number; lines: number[] } {
+  // The Node binding rejects a source longer than its default buffer
+  const tree = parserFor(file).parse(source, undefined, { bufferSize: source.length * 2 + 1 });
+  const pattern = namePattern(query.function);
+  const rows: number[] = [];
+  let functions = 0;
+
+  const visit = (node: SyntaxNode): void => {
+    if (FUNCTION_TYPES.has(node.type)) {
+      const name = functionName(node);
+      if (name !== undefined && pattern.test(name)) {
+        functions++;
+        rows.push(...pointRows(node, query.point));
+      }
+    }
+    for (const child of node.namedChildren) visit(child);
+  };
+  visit(tree.rootNode);
+
+  return {
+    functions,
+    lines: [...new Set(rows)].sort((a, b) => a - b).map((row) => row + 1),
+  };
+}
+
+/**
+ * The part of a function name before its first wildcard, which a file must contain
+ */
+function literalPrefix(name: string): string {
+  return name.split(/[*?]/)[0];
+}
+
+function describeName(name: string): string {
+  return /[*?]/.test(name) ? `matching ${name}` : `named ${name}`;
+}
+
+/**
+ * Files under a directory (all of them, or those with these extensions), skipping dependency
+ * and build directories
+ */
+export function findSourceFiles(root: string, extensions?: Set<string>): string[] {
+  const files: string[] = [];
+
+  function walk(dir: string): void {
+    let entries: fs.Dirent[];
+    try {
+      entries = fs.readdirSync(dir, { withFileTypes: true });
+    } catch {
+      return;
+    }
+
+    for (const entry of entries) {
+      const fullPath = path.join(dir, entry.name);
+      if (entry.isDirectory()) {
+        if (!EXCLUDED_DIRS.includes(entry.name)) {
+          walk(fullPath);
+        }
+      } else if (
+        entry.isFile() &&
+        (!extensions || extensions.has(path.extname(entry.name).toLowerCase()))
+      ) {
+        files.push(fullPath);
+      }
+    }
+  }
+
+  walk(root);
+  return files.sort();
+}
+
+/**
+ * Resolve --break-ast queries to breakpoint specs (absolute path:line). A query without a
+ * file searches the source files under cwd in the adapter's languages that have a grammar,
+ * skipping dependency and build directories.
+ *
+ * @throws Error if a query's function isn't found or has nothing to break on, or a file's
+ * grammar isn't installed
+ */
+export function resolveStructuralBreakpoints(
+  queries: StructuralQuery[],
+  options: { cwd?: string; adapter?: string } = {}
+): string[] {
+  const cwd = options.cwd ?? process.cwd();
+  let sourceFiles: string[] | undefined;
+  const specs = new Set<string>();
+
+  for (const query of queries) {
+    const files = query.file
+      ? [path.resolve(cwd, query.file)]
+      : (sourceFiles ??= findSourceFiles(cwd, sourceExtensions(options.adapter)).filter(
+          hasGrammar
+        ));
+
+    let functions = 0;
+    let placed = 0;
+    for (const file of files) {
+      let content: string;
+      try {
+        content = fs.readFileSync(file, 'utf-8');
+      } catch (error) {
+        if (query.file) throw new Error(`Cannot read ${query.file}: ${(error as Error).message}`);
+        continue;
+      }
+      if (!content.includes(literalPrefix(query.function))) continue;
+
+      const found = structuralBreakpointLines(content, query, file);
+      functions += found.functions;
+      placed += found.lines.length;
+      for (const line of found.lines) specs.add(`${file}:${line}`);
+    }
+
+    if (functions === 0) {
+      throw new Error(
+        `--break-ast "${query.spec}": no function ${describeName(query.function)} in ` +
+          (query.file ?? `the source files under ${cwd}`)
+      );
+    }
+    if (placed === 0) {
+      const missing = query.point === 'loop-body' ? 'loops' : 'return statements';
+      throw new Error(`--break-ast "${query.spec}": ${query.function} has no ${missing}`);
+    }
+  }
+
+  return [...specs];
+}
@@ -0,0 +1,249 @@
+/**
+ * Breakable Line Discovery
+ *
+ * Backs the breakpoint-locations command: launches the program stopped at entry and asks
+ * the adapter which lines in a range can hold a breakpoint. Adapters that support the
+ * breakpointLocations request answer from their debug info directly; for the others, a
+ * breakpoint is set on every line in the range and the lines the adapter verifies without
+ * moving are reported (the probe breakpoints are cleared afterwards).
+ */
+
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+import type { AdapterConfig, LaunchOptions } from '../adapters/base.js';
+import { DapClient } from '../dap/client.js';
+import { SocketDapClient } from '../dap/socket-client.js';
+import type { IDapClient } from '../dap/client-interface.js';
+import { logger } from '../util/logger.js';
+
+export interface LineRange {
+  /** Absolute path of the source file */
+  file: string;
+  startLine: number;
+  endLine: number;
+}
+
+export interface BreakableLine {
+  line: number;
+  /** Valid breakpoint columns on the line, when the adapter reports them */
+  columns?: number[];
+}
+
+export interface BreakableLinesReport extends LineRange {
+  adapter: string;
+  /** How the lines were found */
+  method: 'breakpointLocations' | 'setBreakpoints';
+  lines: BreakableLine[];
+}
+
+const LINE_RANGE_PATTERN = /^(.+?):(\d+)(?:-(\d+))?$/;
+
+/** How long to wait for the program to stop at entry before querying anyway */
+const ENTRY_STOP_TIMEOUT_MS = 10000;
+
+/**
+ * Parse "file:start-end" (or "file:line") into an absolute line range
+ *
+ * @throws Error if the spec is malformed or the range is empty
+ */
+export function parseLineRange(spec: string, cwd: string = process.cwd()): LineRange {
+  const match = spec.match(LINE_RANGE_PATTERN);
+  if (!match) {
+    throw new Error(`Invalid line range "${spec}". Expected "file:start-end" or "file:line"`);
+  }
+
+  const [, file, startStr, endStr] = match;
+  const startLine = parseInt(startStr, 10);
+  const endLine = endStr !== undefined ? parseInt(endStr, 10) : startLine;
+  if (startLine < 1) {
+    throw new Error(`Invalid start line in "${spec}". Lines start at 1`);
+  }
+  if (endLine < startLine) {
+    throw new Error(`Invalid line range "${spec}": end line is before start line`);
+  }
+
+  return { file: path.resolve(cwd, file), startLine, endLine };
+}
+
+/**
+ * Ask a connected, initialized client which lines in a range are breakable
+ */
+export async function findBreakableLines(
+  client: IDapClient,
+  range: LineRange
+): Promise<Pick<BreakableLinesReport, 'method' | 'lines'>> {
+  const source = { path: range.file };
+
+  if (client.getCapabilities().supportsBreakpointLocationsRequest) {
+    const { breakpoints } = await client.breakpointLocations({
+      source,
+      line: range.startLine,
+      endLine: range.endLine,
+    });
+
+    const columns = new Map<number, Set<number>>();
+    for (const location of breakpoints) {
+      if (location.line < range.startLine || location.line > range.endLine) continue;
+      const lineColumns = columns.get(location.line) ?? new Set<number>();
+      if (location.column !== undefined) lineColumns.add(location.column);
+      columns.set(location.line, lineColumns);
+    }
+
+    const lines = [...columns.keys()]
+      .sort((a, b) => a - b)
+      .map((line) => {
+        const lineColumns = [...columns.get(line)!].sort((a, b) => a - b);
+        return lineColumns.length > 0 ? { line, columns: lineColumns } : { line };
+      });
+    return { method: 'breakpointLocations', lines };
+  }
+
+  const requested: number[] = [];
+  for (let line = range.startLine; line <= range.endLine; line++) {
+    requested.push(line);
+  }
+
+  const response = await client.setBreakpoints({
+    source,
+    breakpoints: requested.map((line) => ({ line })),
+  });
+  // Clear the probe breakpoints; failures here only leave unused breakpoints behind
+  await client.setBreakpoints({ source, breakpoints: [] }).catch(() => undefined);
+
+  // A line is breakable if the adapter verified it without moving it elsewhere
+  const lines = requested
+    .filter((line, i) => {
+      const bp = response.breakpoints[i];
+      return bp?.verified === true && (bp.line ?? line) === line;
+    })
+    .map((line) => ({ line }));
+  return { method: 'setBreakpoints', lines };
+}
+
+/**
+ * Launch a program stopped at entry, find the breakable lines in a range, and terminate it
+ */
+export async function queryBreakableLines(
+  adapter: AdapterConfig,
+  range: LineRange,
+  options: Omit<LaunchOptions, 'stopAtEntry'> & { timeout?: number }
+): Promise<BreakableLinesReport> {
+  const { timeout, ...launchOptions } = options;
+  const clientOptions = {
+    command: adapter.command,
+    args: adapter.args,
+    cwd: options.cwd,
+    env: { ...adapter.env, ...options.env },
+    timeout,
+  };
+  const client: IDapClient =
+    adapter.transport === 'socket' && adapter.socketPort
+      ? new SocketDapClient({ ...clientOptions, port: adapter.socketPort })
+      : new DapClient(clientOptions);
+
+  const span = logger.span('breakpoint-locations', {
+    adapter: adapter.name,
+    file: range.file,
+    startLine: range.startLine,
+    endLine: range.endLine,
+  });
+
+  try {
+    await client.connect();
+    await client.initialize({ adapterID: adapter.id });
+
+    const stopped = new Promise<void>((resolve) => {
+      const timer = setTimeout(resolve, ENTRY_STOP_TIMEOUT_MS);
+      client.once('stopped', () => {
+        clearTimeout(timer);
+        resolve();
+      });
+      client.once('terminated', () => {
+        clearTimeout(timer);
+        resolve();
+      });
+    });
+
+    // Same launch ordering as DebugSession.start()
+    const launchConfig = adapter.launchConfig({ ...launchOptions, stopAtEntry: true });
+    if (adapter.requiresLaunchFirst) {
+      const initialized = new Promise<void>((resolve) => client.once('initialized', resolve));
+      const launched = client.launch(launchConfig);
+      await initialized;
+      await client.configurationDone();
+      await launched;
+    } else if (adapter.transport === 'socket') {
+      await client.configurationDone();
+      await client.launch(launchConfig);
+    } else {
+      await client.launch(launchConfig);
+      await client.configurationDone();
+    }
+    await stopped;
+
+    const result = await findBreakableLines(client, range);
+    span.end({ method: result.method, breakable: result.lines.length });
+    return { ...range, adapter: adapter.name, ...result };
+  } catch (error) {
+    span.end({ error: error instanceof Error ? error.message : String(error) });
+    throw error;
+  } finally {
+    if (client.isConnected()) {
+      await client.disconnect(true).catch(() => undefined);
+    }
+    await client.reapProcessTree();
+  }
+}
+
+/**
+ * Collapse sorted line numbers into ranges ("140, 142-145, 150")
+ */
+export function formatLineList(lines: number[]): string {
+  const parts: string[] = [];
+  let start = lines[0];
+  for (let i = 1; i <= lines.length; i++) {
+    if (lines[i] === lines[i - 1] + 1) continue;
+    const end = lines[i - 1];
+    parts.push(start === end ? `${start}` : `${start}-${end}`);
+    start = lines[i];
+  }
+  return parts.join(', ');
+}
+
+/**
+ * Human-readable report: the range's source with breakable lines marked
+ */
+export function formatBreakableLinesReport(report: BreakableLinesReport, cwd?: string): string {
+  const relative = path.relative(cwd ?? process.cwd(), report.file) || report.file;
+  const lines: string[] = [
+    `${relative}:${report.startLine}-${report.endLine} (${report.adapter}, via ${report.method})`,
+  ];
+
+  if (report.lines.length === 0) {
+    lines.push('No breakable lines in this range.');
+    if (report.method === 'setBreakpoints') {
+      lines.push('The file may not be loaded by the program yet, or was built without debug info.');
+    }
+    return lines.join('\n');
+  }
+
+  lines.push(`Breakable lines: ${formatLineList(report.lines.map((l) => l.line))}`);
+
+  let source: string[] | undefined;
+  try {
+    source = fs.readFileSync(report.file, 'utf-8').split(/\r?\n/);
+  } catch {
+    // Without the source, the line list above is the whole report
+  }
+  if (source) {
+    const breakable = new Set(report.lines.map((l) => l.line));
+    const width = String(report.endLine).length;
+    lines.push('');
+    for (let line = report.startLine; line <= report.endLine && line <= source.length; line++) {
+      const marker = breakable.has(line) ? '●' : ' ';
+      lines.push(`${marker} ${String(line).padStart(width)} │ ${source[line - 1]}`.trimEnd());
+    }
+  }
+
+  return lines.join('\n');
+}
@@ -6,24 +6,66 @@
 
 import * as path from 'node:path';
 import type { IDapClient } from '../dap/client-interface.js';
-import type { SourceBreakpoint } from '../dap/protocol.js';
+import type { BreakpointEventBody, Checksum, Source, SourceBreakpoint } from '../dap/protocol.js';
 import type { OutputFormatter } from '../output/formatter.js';
 import type { BreakpointDiagnostics } from '../output/events.js';
+import { StaleSourceChecker } from './stale-source.js';
+import { logger } from '../util/logger.js';
 
 export interface BreakpointSpec {
   file: string;
   line: number;
+  /** Column (1-based) for minified or one-line code */
+  column?: number;
   condition?: string;
   hitCondition?: string;
   logMessage?: string;
+  /** Stable name for the breakpoint, carried into its stop events (file:line#label) */
+  label?: string;
 }
 
 export interface TrackedBreakpoint extends BreakpointSpec {
   id?: number;
   verified: boolean;
   message?: string;
+  /** Line as given in the spec, before the adapter moved the breakpoint */
+  requestedLine: number;
 }
 
+/** An --eval expression, optionally tied to a breakpoint location */
+export interface ScopedEvaluation {
+  /** The expression (may carry a context prefix, see parseEvaluationSpec) */
+  expression: string;
+  file?: string;
+  line?: number;
+}
+
+/** A breakpoint on an instruction address (native adapters) */
+export interface InstructionBreakpointSpec {
+  /** Hex address, e.g. 0x55aa113e */
+  address: string;
+  condition?: string;
+  hitCondition?: string;
+}
+
+export interface TrackedInstructionBreakpoint extends InstructionBreakpointSpec {
+  id?: number;
+  verified: boolean;
+  message?: string;
+}
+
+/** file:line or file:line:column, then #label, then ?condition or #hitCount */
+const BREAKPOINT_PATTERN = /^(.+?):(\d+)(?::(\d+))?(?:#([A-Za-z_][\w.-]*))?(?:\?(.+)|#(\d+))?$/;
+
+/**
+ * file:line:expression. The file needs an extension and no brackets or spaces, so
+ * expressions like "arr[1:2:3]" aren't mistaken for a scope.
+ */
+const SCOPED_EVALUATION_PATTERN = /^((?:[A-Za-z]:)?[^\s:?#|*"<>()[\]{}]*\.\w+):(\d+):(.+)$/;
+
+/** address, address?condition or address#hitCount */
+const INSTRUCTION_BREAKPOINT_PATTERN = /^(0x[0-9a-f]+)(?:\?(.+)|#(\d+))?$/i;
+
 export interface PathResolutionOptions {
   /** Working directory - preferred base for relative paths */
   cwd?: string;
@@ -66,6 +108,8 @@ function resolveBreakpointPath(file: string, options: PathResolutionOptions = {}
  * - "src/file.ts:45" - with path
  * - "file.ts:45?condition" - with condition
  * - "file.ts:45#3" - with hit count
+ * - "file.ts:45#discount" - with a label (starts with a letter or _), before ?condition or #3
+ * - "bundle.js:1:34567" - with column (minified or one-line code)
  *
  * @param spec The breakpoint specification string
  * @param pathOptions Options for resolving relative breakpoint paths
@@ -107,8 +151,8 @@ export function validateBreakpointSpec(spec: string): BreakpointValidationResult
     };
   }
 
-  // Match: file:line?condition or file:line#hitCount
-  const match = trimmed.match(/^(.+):(\d+)(?:\?(.+)|#(\d+))?$/);
+  // Match: file:line[:column][#label], then ?condition or #hitCount
+  const match = trimmed.match(BREAKPOINT_PATTERN);
 
   if (!match) {
     // Try to give a more specific error message
@@ -142,7 +186,7 @@ export function validateBreakpointSpec(spec: string): BreakpointValidationResult
     };
   }
 
-  const [, file, lineStr] = match;
+  const [, file, lineStr, columnStr] = match;
   const line = parseInt(lineStr, 10);
 
   // Check for empty file path
@@ -163,6 +207,15 @@ export function validateBreakpointSpec(spec: string): BreakpointValidationResult
     };
   }
 
+  // Check for valid column number (must be positive)
+  if (columnStr && parseInt(columnStr, 10) < 1) {
+    return {
+      valid: false,
+      error: `Invalid column number "${columnStr}" in breakpoint "${spec}". Columns start at 1`,
+      spec,
+    };
+  }
+
   return { valid: true };
 }
 
@@ -272,22 +325,73 @@ export function validateLogpointSpec(spec: string): BreakpointValidationResult {
   return { valid: true };
 }
 
+/**
+ * Validate an instruction breakpoint specification (--break-addr)
+ *
+ * @param spec The instruction breakpoint specification string
+ * @returns Validation result with error message if invalid
+ */
+export function validateInstructionBreakpointSpec(spec: string): BreakpointValidationResult {
+  if (!INSTRUCTION_BREAKPOINT_PATTERN.test(spec.trim())) {
+    return {
+      valid: false,
+      error: `Invalid instruction address "${spec}". Expected a hex address (e.g., "0x55aa113e")`,
+      spec,
+    };
+  }
+  return { valid: true };
+}
+
+/**
+ * Parse an instruction breakpoint specification
+ *
+ * Formats supported:
+ * - "0x55aa113e" - break when the instruction executes
+ * - "0x55aa113e?condition" - with condition
+ * - "0x55aa113e#3" - with hit count
+ */
+export function parseInstructionBreakpointSpec(spec: string): InstructionBreakpointSpec {
+  const match = spec.trim().match(INSTRUCTION_BREAKPOINT_PATTERN);
+  if (!match) {
+    throw new Error(validateInstructionBreakpointSpec(spec).error);
+  }
+  const [, address, condition, hitCount] = match;
+  return {
+    address: address.toLowerCase(),
+    condition: condition?.trim(),
+    hitCondition: hitCount,
+  };
+}
+
 /**
  * Validate multiple breakpoint and logpoint specifications.
  * Returns all validation errors found.
  *
  * @param breakpoints Array of breakpoint specifications
  * @param logpoints Array of logpoint specifications
+ * @param instructionBreakpoints Array of instruction breakpoint specifications
  * @returns Array of error messages, empty if all valid
  */
-export function validateAllBreakpoints(breakpoints: string[], logpoints: string[] = []): string[] {
+export function validateAllBreakpoints(
+  breakpoints: string[],
+  logpoints: string[] = [],
+  instructionBreakpoints: string[] = []
+): string[] {
   const errors: string[] = [];
+  const labels = new Set<string>();
 
   for (const bp of breakpoints) {
     const result = validateBreakpointSpec(bp);
     if (!result.valid && result.error) {
       errors.push(result.error);
     }
+    const label = bp.trim().match(BREAKPOINT_PATTERN)?.[4];
+    if (label !== undefined) {
+      if (labels.has(label)) {
+        errors.push(`Breakpoint label "${label}" is used by more than one breakpoint`);
+      }
+      labels.add(label);
+    }
   }
 
   for (const lp of logpoints) {
@@ -297,6 +401,13 @@ export function validateAllBreakpoints(breakpoints: string[], logpoints: string[
     }
   }
 
+  for (const ib of instructionBreakpoints) {
+    const result = validateInstructionBreakpointSpec(ib);
+    if (!result.valid && result.error) {
+      errors.push(result.error);
+    }
+  }
+
   return errors;
 }
 
@@ -304,8 +415,8 @@ export function parseBreakpointSpec(
   spec: string,
   pathOptions: PathResolutionOptions = {}
 ): BreakpointSpec {
-  // Match: file:line?condition or file:line#hitCount
-  const match = spec.match(/^(.+):(\d+)(?:\?(.+)|#(\d+))?$/);
+  // Match: file:line[:column][#label], then ?condition or #hitCount
+  const match = spec.match(BREAKPOINT_PATTERN);
 
   if (!match) {
     throw new Error(
@@ -313,18 +424,25 @@ export function parseBreakpointSpec(
     );
   }
 
-  const [, file, lineStr, condition, hitCount] = match;
+  const [, file, lineStr, columnStr, label, condition, hitCount] = match;
   const line = parseInt(lineStr, 10);
 
   if (isNaN(line) || line < 1) {
     throw new Error(`Invalid line number: ${lineStr}`);
   }
 
+  const column = columnStr ? parseInt(columnStr, 10) : undefined;
+  if (column !== undefined && column < 1) {
+    throw new Error(`Invalid column number: ${columnStr}`);
+  }
+
   return {
     file: resolveBreakpointPath(file, pathOptions),
     line,
+    ...(column !== undefined && { column }),
     condition: condition || undefined,
     hitCondition: hitCount || undefined,
+    ...(label && { label }),
   };
 }
 
@@ -362,6 +480,26 @@ export function parseLogpointSpec(
   };
 }
 
+/**
+ * Split an --eval spec into its expression and the breakpoint location it's tied to, if any
+ * ("pricing.rs:100:discount_rate" evaluates discount_rate only when stopped there)
+ */
+export function parseScopedEvaluation(
+  spec: string,
+  pathOptions: PathResolutionOptions = {}
+): ScopedEvaluation {
+  const match = spec.match(SCOPED_EVALUATION_PATTERN);
+  if (!match) {
+    return { expression: spec };
+  }
+  const [, file, lineStr, expression] = match;
+  return {
+    expression: expression.trim(),
+    file: resolveBreakpointPath(file, pathOptions),
+    line: parseInt(lineStr, 10),
+  };
+}
+
 export interface BreakpointManagerOptions extends PathResolutionOptions {
   /** The adapter type for generating context-aware diagnostics */
   adapterType?: string;
@@ -371,9 +509,13 @@ export class BreakpointManager {
   private client: IDapClient;
   private formatter: OutputFormatter;
   private breakpoints: Map<string, TrackedBreakpoint[]> = new Map();
+  private instructionBreakpoints: TrackedInstructionBreakpoint[] = [];
   private nextId: number = 1;
   private pathOptions: PathResolutionOptions;
   private adapterType?: string;
+  private staleSourceChecker = new StaleSourceChecker();
+  /** Files found to differ from what the program is running */
+  private staleSources: string[] = [];
 
   constructor(
     client: IDapClient,
@@ -412,25 +554,149 @@ export class BreakpointManager {
     existing.push({
       ...spec,
       verified: false,
+      requestedLine: spec.line,
     });
     this.breakpoints.set(spec.file, existing);
   }
 
   /**
-   * Set all breakpoints on the debug adapter
+   * Add an instruction breakpoint from a spec string (e.g., "0x55aa113e")
+   */
+  addInstructionBreakpoint(spec: string): InstructionBreakpointSpec {
+    const ib = parseInstructionBreakpointSpec(spec);
+    this.instructionBreakpoints.push({ ...ib, verified: false });
+    return ib;
+  }
+
+  /**
+   * Set all breakpoints on the debug adapter. Every file's request is sent at once and its
+   * breakpoint_set events are emitted as its response arrives, so one slow file doesn't hold
+   * back the others.
    */
   async setAllBreakpoints(): Promise<void> {
-    for (const [file, specs] of this.breakpoints) {
-      await this.setFileBreakpoints(file, specs);
+    await Promise.all([
+      ...[...this.breakpoints].map(([file, specs]) => this.setFileBreakpoints(file, specs)),
+      ...(this.instructionBreakpoints.length > 0 ? [this.setInstructionBreakpoints()] : []),
+    ]);
+  }
+
+  /**
+   * Remove every breakpoint from the debug adapter, keeping them here to be set again
+   */
+  async clearAllBreakpoints(): Promise<void> {
+    const instructions =
+      this.instructionBreakpoints.length > 0 &&
+      this.client.getCapabilities().supportsInstructionBreakpoints;
+    await Promise.all([
+      ...[...this.breakpoints.keys()].map((file) =>
+        this.client.setBreakpoints({ source: { path: file }, breakpoints: [] })
+      ),
+      ...(instructions ? [this.client.setInstructionBreakpoints({ breakpoints: [] })] : []),
+    ]);
+    for (const spec of [...[...this.breakpoints.values()].flat(), ...this.instructionBreakpoints]) {
+      spec.verified = false;
+    }
+  }
+
+  /**
+   * Move a requested column to a valid breakpoint location on its line: the first one at
+   * or after it, else the last one before it. Unchanged if the adapter can't list locations.
+   */
+  private async snapColumn(file: string, spec: BreakpointSpec): Promise<number | undefined> {
+    if (
+      spec.column === undefined ||
+      !this.client.getCapabilities().supportsBreakpointLocationsRequest
+    ) {
+      return spec.column;
+    }
+    try {
+      const { breakpoints } = await this.client.breakpointLocations({
+        source: { path: file },
+        line: spec.line,
+      });
+      const valid = breakpoints
+        .filter((location) => location.line === spec.line && location.column !== undefined)
+        .map((location) => location.column!)
+        .sort((a, b) => a - b);
+      return valid.find((column) => column >= spec.column!) ?? valid.pop() ?? spec.column;
+    } catch {
+      return spec.column;
     }
   }
 
+  /**
+   * Set all instruction breakpoints (the request replaces any previous ones)
+   */
+  private async setInstructionBreakpoints(): Promise<void> {
+    const specs = this.instructionBreakpoints;
+    let results: { id?: number; verified: boolean; message?: string; location?: string }[];
+
+    if (!this.client.getCapabilities().supportsInstructionBreakpoints) {
+      const adapter = this.adapterType ?? 'debug';
+      const message = `The ${adapter} adapter doesn't support instruction breakpoints`;
+      results = specs.map(() => ({ verified: false, message }));
+    } else {
+      try {
+        const response = await this.client.setInstructionBreakpoints({
+          breakpoints: specs.map((spec) => ({
+            instructionReference: spec.address,
+            condition: spec.condition,
+            hitCondition: spec.hitCondition,
+          })),
+        });
+        results = specs.map((_, i) => {
+          const bp = response.breakpoints[i];
+          return {
+            id: bp?.id,
+            verified: bp?.verified ?? false,
+            message: bp?.message,
+            // Where the address maps to, when the adapter has line info for it
+            location: bp?.source?.path && bp.line ? `${bp.source.path}:${bp.line}` : undefined,
+          };
+        });
+      } catch (error) {
+        const message = error instanceof Error ? error.message : 'Failed to set breakpoint';
+        results = specs.map(() => ({ verified: false, message }));
+      }
+    }
+
+    for (let i = 0; i < specs.length; i++) {
+      const { id, verified, message, location } = results[i];
+      specs[i].id = id ?? this.nextId++;
+      specs[i].verified = verified;
+      specs[i].message = message;
+      this.formatter.emit(
+        this.formatter.createEvent('instruction_breakpoint_set', {
+          id: specs[i].id!,
+          address: specs[i].address,
+          verified,
+          condition: specs[i].condition,
+          hitCondition: specs[i].hitCondition,
+          message,
+          location,
+        })
+      );
+    }
+  }
+
+  /**
+   * Set the breakpoints of some files again, e.g. after their code was reloaded.
+   * Returns the re-verified breakpoints.
+   */
+  async reverifyBreakpoints(files: Set<string>): Promise<TrackedBreakpoint[]> {
+    const reverified = [...this.breakpoints].filter(([file]) => files.has(file));
+    await Promise.all(reverified.map(([file, specs]) => this.setFileBreakpoints(file, specs)));
+    return reverified.flatMap(([, specs]) => specs);
+  }
+
   /**
    * Set breakpoints for a single file
    */
   private async setFileBreakpoints(file: string, specs: TrackedBreakpoint[]): Promise<void> {
-    const sourceBreakpoints: SourceBreakpoint[] = specs.map((spec) => ({
+    const columns = await Promise.all(specs.map((spec) => this.snapColumn(file, spec)));
+    const sourceBreakpoints: SourceBreakpoint[] = specs.map((spec, i) => ({
       line: spec.line,
+      column: columns[i],
       condition: spec.condition,
       hitCondition: spec.hitCondition,
       logMessage: spec.logMessage,
@@ -453,6 +719,9 @@ export class BreakpointManager {
           specs[i].verified = bp.verified;
           specs[i].message = bp.message;
           specs[i].line = bp.line ?? specs[i].line;
+          if (specs[i].column !== undefined) {
+            specs[i].column = bp.column ?? columns[i];
+          }
 
           // Generate diagnostics for unverified breakpoints
           const diagnostics = !bp.verified
@@ -467,10 +736,18 @@ export class BreakpointManager {
             specs[i].verified,
             specs[i].condition,
             specs[i].message,
-            diagnostics
+            diagnostics,
+            specs[i].column,
+            specs[i].label
           );
         }
       }
+
+      // Adapters that know what they loaded may return its checksums with the breakpoints
+      const loaded = response.breakpoints.find((bp) => bp.source?.checksums?.length)?.source;
+      if (loaded?.checksums) {
+        this.reportStaleSource(file, { checksums: loaded.checksums });
+      }
     } catch (error) {
       // Emit error for each breakpoint that failed
       for (let i = 0; i < specs.length; i++) {
@@ -489,10 +766,130 @@ export class BreakpointManager {
           false,
           spec.condition,
           spec.message,
-          diagnostics
+          diagnostics,
+          spec.column,
+          spec.label
         );
       }
     }
+    this.warnUnverified(file, specs);
+  }
+
+  /**
+   * Emit breakpoint_warning for the breakpoints of a file the adapter didn't verify, as soon
+   * as its response arrives (before the program runs, when set at startup)
+   */
+  private warnUnverified(file: string, specs: TrackedBreakpoint[]): void {
+    const unverified = specs.filter((spec) => spec.id !== undefined && !spec.verified);
+    if (unverified.length === 0) return;
+    const reason = unverified.find((spec) => spec.message)?.message;
+    const count = unverified.length === 1 ? '1 breakpoint' : `${unverified.length} breakpoints`;
+    this.formatter.emit(
+      this.formatter.createEvent('breakpoint_warning', {
+        file,
+        breakpoints: unverified.map((spec) => ({
+          id: spec.id!,
+          line: spec.line,
+          ...(spec.message && { message: spec.message }),
+        })),
+        message: `${count} in ${path.basename(file)} not verified${reason ? `: ${reason}` : ''}`,
+        suggestions: getBreakpointSuggestions(
+          this.adapterType,
+          path.extname(file).toLowerCase(),
+          reason
+        ),
+      })
+    );
+  }
+
+  /**
+   * Apply a breakpoint event: a breakpoint the adapter verifies later, once the code it's in
+   * has loaded, is reported with breakpoint_verified
+   */
+  handleBreakpointEvent(body: BreakpointEventBody): void {
+    const { breakpoint } = body;
+    if (body.reason !== 'changed' || breakpoint.id === undefined) return;
+    const spec = this.findBreakpointById(breakpoint.id);
+    if (!spec) return;
+
+    const wasVerified = spec.verified;
+    spec.verified = breakpoint.verified;
+    spec.message = breakpoint.message;
+    spec.line = breakpoint.line ?? spec.line;
+    if (spec.column !== undefined) spec.column = breakpoint.column ?? spec.column;
+    if (wasVerified || !spec.verified) return;
+
+    this.formatter.emit(
+      this.formatter.createEvent('breakpoint_verified', {
+        id: breakpoint.id,
+        file: spec.file,
+        line: spec.line,
+        ...(spec.column !== undefined && { column: spec.column }),
+        ...(spec.label && { label: spec.label }),
+      })
+    );
+  }
+
+  /**
+   * Check the source of a stop location against what the adapter has loaded: its
+   * checksums, or the content behind its source reference. Each file is checked once.
+   */
+  async checkSource(source: Source | undefined): Promise<void> {
+    if (!source?.path || !this.staleSourceChecker.shouldCheck(source.path)) return;
+    const file = source.path;
+
+    if (source.checksums?.length) {
+      this.reportStaleSource(file, { checksums: source.checksums });
+    } else if (source.sourceReference) {
+      try {
+        const { content } = await this.client.source({
+          source,
+          sourceReference: source.sourceReference,
+        });
+        this.reportStaleSource(file, { loadedContent: content });
+      } catch {
+        // The adapter can't serve it, so there's nothing to compare
+      }
+    }
+  }
+
+  /**
+   * Files found to differ from what the program is running
+   */
+  getStaleSources(): string[] {
+    return [...this.staleSources];
+  }
+
+  /**
+   * Emit stale_source for each breakpoint in a file that isn't what the program is running
+   * (once for the file when it has no breakpoints)
+   */
+  private reportStaleSource(
+    file: string,
+    evidence: { checksums?: Checksum[]; loadedContent?: string }
+  ): void {
+    const mismatch = this.staleSourceChecker.check(file, evidence);
+    if (!mismatch) return;
+
+    this.staleSources.push(file);
+    const message =
+      `${path.basename(file)} has changed since the program was built; rebuild it, or ` +
+      'breakpoints and locals may not match the code';
+    logger.warn('breakpoints', 'source file differs from the running program', {
+      file,
+      reason: mismatch.reason,
+    });
+    const specs = this.breakpoints.get(file) ?? [];
+    for (const spec of specs.length > 0 ? specs : [undefined]) {
+      this.formatter.emit(
+        this.formatter.createEvent('stale_source', {
+          file,
+          ...(spec && { breakpointId: spec.id, line: spec.line }),
+          ...mismatch,
+          message,
+        })
+      );
+    }
   }
 
   /**
@@ -506,6 +903,78 @@ export class BreakpointManager {
     return all;
   }
 
+  /**
+   * Get all tracked instruction breakpoints
+   */
+  getInstructionBreakpoints(): TrackedInstructionBreakpoint[] {
+    return [...this.instructionBreakpoints];
+  }
+
+  /**
+   * Select the --eval specs that apply at a stop: unscoped ones, plus those tied to the
+   * stop location or to a hit breakpoint's requested location. Scopes are removed.
+   */
+  selectEvaluations(
+    specs: string[],
+    location: { file: string; line: number },
+    hitBreakpointIds: number[] = []
+  ): string[] {
+    const hit = hitBreakpointIds
+      .map((id) => this.findBreakpointById(id))
+      .filter((bp): bp is TrackedBreakpoint => bp !== undefined);
+
+    const selected: string[] = [];
+    for (const spec of specs) {
+      const { expression, file, line } = parseScopedEvaluation(spec, this.pathOptions);
+      if (
+        file === undefined ||
+        (file === location.file && line === location.line) ||
+        hit.some((bp) => bp.file === file && (bp.requestedLine === line || bp.line === line))
+      ) {
+        selected.push(expression);
+      }
+    }
+    return selected;
+  }
+
+  /**
+   * Scoped --eval specs whose location has no breakpoint or logpoint
+   */
+  findUnmatchedEvaluations(specs: string[]): string[] {
+    const breakpoints = this.getAllBreakpoints();
+    return specs.filter((spec) => {
+      const { file, line } = parseScopedEvaluation(spec, this.pathOptions);
+      return (
+        file !== undefined &&
+        !breakpoints.some((bp) => bp.file === file && bp.requestedLine === line)
+      );
+    });
+  }
+
+  /**
+   * The label of the breakpoint a stop is at: that of a hit breakpoint or, for adapters that
+   * don't say which were hit, of a breakpoint on the stop's line
+   */
+  labelFor(
+    location: { file: string; line: number },
+    hitBreakpointIds: number[] = []
+  ): string | undefined {
+    return this.labelsFor(location, hitBreakpointIds)[0];
+  }
+
+  /**
+   * The labels of all the breakpoints a stop is at, found the same way as labelFor()
+   */
+  labelsFor(location: { file: string; line: number }, hitBreakpointIds: number[] = []): string[] {
+    const specs =
+      hitBreakpointIds.length > 0
+        ? hitBreakpointIds.map((id) => this.findBreakpointById(id))
+        : (this.breakpoints.get(location.file) ?? []).filter(
+            (bp) => bp.line === location.line || bp.requestedLine === location.line
+          );
+    return specs.flatMap((bp) => (bp?.label ? [bp.label] : []));
+  }
+
   /**
    * Find a breakpoint by ID
    */
@@ -572,7 +1041,7 @@ function normalizeAdapterType(adapterType: string | undefined): string | undefin
   }
 
   // LLDB adapters
-  if (['lldb', 'codelldb', 'cpp', 'c', 'rust'].includes(lower)) {
+  if (['lldb', 'codelldb', 'cpp', 'c', 'rust', 'rust-lldb', 'rust-gdb'].includes(lower)) {
     return 'lldb';
   }
 
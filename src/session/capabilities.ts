@@ -0,0 +1,214 @@
+/**
+ * Adapter Capabilities (capabilities)
+ *
+ * Backs the capabilities command: the adapter is started, sent initialize and shut down
+ * again, and the capabilities it reported are listed next to the debug-run features that
+ * depend on them. Answers "does this adapter do conditional breakpoints / data breakpoints /
+ * setVariable?" before a run fails on it.
+ */
+
+import type { AdapterConfig } from '../adapters/base.js';
+import { DapClient } from '../dap/client.js';
+import { SocketDapClient } from '../dap/socket-client.js';
+import type { IDapClient } from '../dap/client-interface.js';
+import type { Capabilities, ExceptionBreakpointsFilter } from '../dap/protocol.js';
+import { logger } from '../util/logger.js';
+
+type CapabilityFlag = {
+  [K in keyof Capabilities]-?: Capabilities[K] extends boolean | undefined ? K : never;
+}[keyof Capabilities];
+
+interface CapabilityInfo {
+  flag: CapabilityFlag;
+  name: string;
+  /** The debug-run option that needs it */
+  usedBy?: string;
+}
+
+/** The capabilities worth knowing about, grouped the way the report prints them */
+const CAPABILITY_GROUPS: { title: string; capabilities: CapabilityInfo[] }[] = [
+  {
+    title: 'Breakpoints',
+    capabilities: [
+      {
+        flag: 'supportsConditionalBreakpoints',
+        name: 'Conditional breakpoints',
+        usedBy: '-b file:line?cond',
+      },
+      {
+        flag: 'supportsHitConditionalBreakpoints',
+        name: 'Hit count breakpoints',
+        usedBy: '-b file:line#count',
+      },
+      { flag: 'supportsLogPoints', name: 'Logpoints', usedBy: '-l' },
+      { flag: 'supportsFunctionBreakpoints', name: 'Function breakpoints' },
+      {
+        flag: 'supportsInstructionBreakpoints',
+        name: 'Instruction breakpoints',
+        usedBy: '--break-addr',
+      },
+      {
+        flag: 'supportsDataBreakpoints',
+        name: 'Data breakpoints',
+        usedBy: '--watch-memory (on write)',
+      },
+      {
+        flag: 'supportsBreakpointLocationsRequest',
+        name: 'Breakpoint locations',
+        usedBy: 'breakable line hints',
+      },
+      {
+        flag: 'supportsExceptionFilterOptions',
+        name: 'Exception filter conditions',
+        usedBy: '--break-on-exception all?cond',
+      },
+      {
+        flag: 'supportsExceptionOptions',
+        name: 'Exception options',
+        usedBy: '--break-on-exception type:Name',
+      },
+    ],
+  },
+  {
+    title: 'Execution',
+    capabilities: [
+      { flag: 'supportsRestartFrame', name: 'Restart frame', usedBy: '--on-hit restart-frame' },
+      { flag: 'supportsGotoTargetsRequest', name: 'Goto targets', usedBy: '--on-hit goto:' },
+      { flag: 'supportsRestartRequest', name: 'Restart', usedBy: '--on-exit restart' },
+      { flag: 'supportsStepBack', name: 'Step back' },
+      { flag: 'supportsStepInTargetsRequest', name: 'Step-in targets' },
+      { flag: 'supportsTerminateRequest', name: 'Terminate' },
+    ],
+  },
+  {
+    title: 'Inspection',
+    capabilities: [
+      { flag: 'supportsSetVariable', name: 'Set variable', usedBy: '--set' },
+      { flag: 'supportsSetExpression', name: 'Set expression', usedBy: '--set' },
+      { flag: 'supportsExceptionInfoRequest', name: 'Exception info' },
+      {
+        flag: 'supportsEvaluateForHovers',
+        name: 'Hover evaluation',
+        usedBy: '--eval-context hover',
+      },
+      {
+        flag: 'supportsClipboardContext',
+        name: 'Clipboard evaluation',
+        usedBy: '--eval-context clipboard',
+      },
+      { flag: 'supportsCompletionsRequest', name: 'Completions' },
+      { flag: 'supportsModulesRequest', name: 'Modules', usedBy: '--modules' },
+      { flag: 'supportsReadMemoryRequest', name: 'Read memory', usedBy: '--watch-memory' },
+      { flag: 'supportsValueFormattingOptions', name: 'Value formatting' },
+    ],
+  },
+];
+
+export interface CapabilityEntry {
+  flag: string;
+  name: string;
+  supported: boolean;
+  usedBy?: string;
+}
+
+export interface CapabilitiesReport {
+  adapter: string;
+  groups: { title: string; capabilities: CapabilityEntry[] }[];
+  exceptionFilters: ExceptionBreakpointsFilter[];
+  /** Other capabilities the adapter reported as supported */
+  other: string[];
+  /** Everything initialize returned, as-is */
+  raw: Capabilities;
+}
+
+/**
+ * Sort an initialize response into the report's groups
+ */
+export function describeCapabilities(adapter: string, raw: Capabilities): CapabilitiesReport {
+  const known = new Set<string>();
+  const groups = CAPABILITY_GROUPS.map(({ title, capabilities }) => ({
+    title,
+    capabilities: capabilities.map(({ flag, name, usedBy }) => {
+      known.add(flag);
+      return { flag, name, supported: raw[flag] === true, ...(usedBy && { usedBy }) };
+    }),
+  }));
+  const other = Object.entries(raw)
+    .filter(([flag, value]) => value === true && !known.has(flag))
+    .map(([flag]) => flag)
+    .sort();
+
+  return { adapter, groups, exceptionFilters: raw.exceptionBreakpointFilters ?? [], other, raw };
+}
+
+/**
+ * Start the adapter, initialize it and shut it down, returning what it reported
+ */
+export async function queryCapabilities(
+  adapter: AdapterConfig,
+  options: { cwd?: string; timeout: number }
+): Promise<CapabilitiesReport> {
+  const clientOptions = {
+    command: adapter.command,
+    args: adapter.args,
+    cwd: options.cwd,
+    env: adapter.env,
+    timeout: options.timeout,
+  };
+  const client: IDapClient =
+    adapter.transport === 'socket' && adapter.socketPort
+      ? new SocketDapClient({ ...clientOptions, port: adapter.socketPort })
+      : new DapClient(clientOptions);
+
+  const span = logger.span('capabilities', { adapter: adapter.name });
+  try {
+    await client.connect();
+    const capabilities = await client.initialize({ adapterID: adapter.id });
+    span.end();
+    return describeCapabilities(adapter.name, capabilities ?? {});
+  } catch (error) {
+    span.end({ error: error instanceof Error ? error.message : String(error) });
+    throw error;
+  } finally {
+    if (client.isConnected()) {
+      await client.disconnect(false).catch(() => undefined);
+    }
+    await client.reapProcessTree();
+  }
+}
+
+/**
+ * Human-readable report: a ✓/✗ table per group, the exception filters and anything else
+ */
+export function formatCapabilitiesReport(report: CapabilitiesReport): string {
+  const entries = report.groups.flatMap((group) => group.capabilities);
+  const width = Math.max(...entries.map((entry) => entry.name.length));
+  const lines: string[] = [`${report.adapter} capabilities`];
+
+  for (const group of report.groups) {
+    lines.push('', group.title);
+    for (const entry of group.capabilities) {
+      const name = entry.usedBy ? entry.name.padEnd(width) : entry.name;
+      const usedBy = entry.usedBy ? `  ${entry.usedBy}` : '';
+      lines.push(`  ${entry.supported ? '✓' : '✗'} ${name}${usedBy}`);
+    }
+  }
+
+  if (report.exceptionFilters.length > 0) {
+    const filterWidth = Math.max(...report.exceptionFilters.map((f) => f.filter.length));
+    lines.push('', 'Exception filters (--break-on-exception)');
+    for (const filter of report.exceptionFilters) {
+      const notes = [filter.default && 'default', filter.supportsCondition && 'conditions']
+        .filter(Boolean)
+        .join(', ');
+      const suffix = notes ? ` (${notes})` : '';
+      lines.push(`  ${filter.filter.padEnd(filterWidth)}  ${filter.label}${suffix}`);
+    }
+  }
+
+  if (report.other.length > 0) {
+    lines.push('', 'Also supported', ...report.other.map((flag) => `  ${flag}`));
+  }
+
+  return lines.join('\n');
+}
@@ -0,0 +1,42 @@
+/**
+ * Capture Budget (--capture-budget)
+ *
+ * Time-boxes what a stop captures, so a frame with huge objects can't stall the session
+ * past its timeout. Requested expressions are evaluated first; locals are then expanded
+ * breadth-first (every variable before any of its children) until the budget runs out,
+ * and whatever wasn't fetched in time is marked truncated.
+ */
+
+export class CaptureBudget {
+  private readonly deadline: number;
+
+  constructor(ms: number, now: number = Date.now()) {
+    this.deadline = now + ms;
+  }
+
+  expired(): boolean {
+    return Date.now() >= this.deadline;
+  }
+
+  /**
+   * Wait for a request for at most the remaining budget; undefined if the budget runs out
+   * first (a late response is dropped)
+   */
+  async within<T>(request: Promise<T>): Promise<T | undefined> {
+    const remaining = this.deadline - Date.now();
+    if (remaining <= 0) {
+      request.catch(() => undefined);
+      return undefined;
+    }
+
+    let timer: NodeJS.Timeout | undefined;
+    const expired = new Promise<undefined>((resolve) => {
+      timer = setTimeout(() => resolve(undefined), remaining);
+    });
+    try {
+      return await Promise.race([request, expired]);
+    } finally {
+      clearTimeout(timer);
+    }
+  }
+}
@@ -0,0 +1,809 @@
+/**
+ * Adapter Conformance (conformance)
+ *
+ * Backs the conformance command: a small sample program, bundled here for each language,
+ * is written to a temporary directory, built if it needs to be, and debugged once per
+ * feature of a matrix (breakpoints, conditions, logpoints, stepping, variables, ...). Each
+ * feature is checked against what the program is known to do at its marked lines, so the
+ * report says what an adapter actually does rather than what its capabilities claim, and a
+ * new adapter integration can be checked the same way.
+ */
+
+import { execFile } from 'node:child_process';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import { promisify } from 'node:util';
+import type { AdapterConfig } from '../adapters/base.js';
+import { DapClient } from '../dap/client.js';
+import { SocketDapClient } from '../dap/socket-client.js';
+import type { IDapClient } from '../dap/client-interface.js';
+import type {
+  Capabilities,
+  OutputEventBody,
+  SourceBreakpoint,
+  StackFrame,
+  StoppedEventBody,
+} from '../dap/protocol.js';
+import { logger } from '../util/logger.js';
+
+const execFileAsync = promisify(execFile);
+
+/** How long a sample may take to build (dotnet restores packages the first time) */
+const BUILD_TIMEOUT_MS = 5 * 60 * 1000;
+
+/**
+ * A sample program: what each marked line (a "@name" comment) does is the same in every
+ * language, so the checks can be shared
+ *
+ *   @add     inside add(total, value), called once per loop iteration
+ *   @loop    the loop body, for i = 0..4, calling add
+ *   @result  after the loop, with total = 10
+ *   @throw   throws an exception that is caught (languages with exceptions)
+ */
+export interface ConformanceProgram {
+  language: string;
+  /** Files to write, by path relative to the sample directory */
+  files: Record<string, string>;
+  /** The file with the marked lines */
+  source: string;
+  /** Build command (program and arguments), run in the sample directory */
+  build?: string[];
+  /** What to launch, relative to the sample directory */
+  program: string;
+}
+
+const JAVASCRIPT = `function add(total, value) {
+  const sum = total + value; // @add
+  return sum;
+}
+
+function main() {
+  let total = 0;
+  for (let i = 0; i < 5; i++) {
+    total = add(total, i); // @loop
+  }
+  const label = 'done'; // @result
+  try {
+    throw new Error('conformance'); // @throw
+  } catch (error) {
+    console.log(\`caught \${error.message}\`);
+  }
+  console.log(\`total=\${total} \${label}\`);
+}
+
+main();
+`;
+
+const PYTHON = `def add(total, value):
+    total_sum = total + value  # @add
+    return total_sum
+
+
+def main():
+    total = 0
+    for i in range(5):
+        total = add(total, i)  # @loop
+    label = "done"  # @result
+    try:
+        raise ValueError("conformance")  # @throw
+    except ValueError as error:
+        print(f"caught {error}")
+    print(f"total={total} {label}")
+
+
+if __name__ == "__main__":
+    main()
+`;
+
+const CSHARP = `using System;
+
+static class Program
+{
+    static int Add(int total, int value)
+    {
+        int sum = total + value; // @add
+        return sum;
+    }
+
+    static void Main()
+    {
+        int total = 0;
+        for (int i = 0; i < 5; i++)
+        {
+            total = Add(total, i); // @loop
+        }
+        string label = "done"; // @result
+        try
+        {
+            throw new InvalidOperationException("conformance"); // @throw
+        }
+        catch (InvalidOperationException error)
+        {
+            Console.WriteLine($"caught {error.Message}");
+        }
+        Console.WriteLine($"total={total} {label}");
+    }
+}
+`;
+
+const CSPROJ = `<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <OutputType>Exe</OutputType>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+</Project>
+`;
+
+const C = `#include <stdio.h>
+
+static int add(int total, int value) {
+  int sum = total + value; /* @add */
+  return sum;
+}
+
+int main(void) {
+  int total = 0;
+  for (int i = 0; i < 5; i++) {
+    total = add(total, i); /* @loop */
+  }
+  const char *label = "done"; /* @result */
+  printf("total=%d %s\\n", total, label);
+  return 0;
+}
+`;
+
+const RUST = `fn add(total: i32, value: i32) -> i32 {
+    let sum = total + value; // @add
+    sum
+}
+
+fn main() {
+    let mut total = 0;
+    for i in 0..5 {
+        total = add(total, i); // @loop
+    }
+    let label = "done"; // @result
+    println!("total={} {}", total, label);
+}
+`;
+
+export const CONFORMANCE_PROGRAMS: Record<string, ConformanceProgram> = {
+  javascript: {
+    language: 'javascript',
+    files: { 'conformance.js': JAVASCRIPT },
+    source: 'conformance.js',
+    program: 'conformance.js',
+  },
+  python: {
+    language: 'python',
+    files: { 'conformance.py': PYTHON },
+    source: 'conformance.py',
+    program: 'conformance.py',
+  },
+  csharp: {
+    language: 'csharp',
+    files: { 'Program.cs': CSHARP, 'Conformance.csproj': CSPROJ },
+    source: 'Program.cs',
+    build: ['dotnet', 'build', '-c', 'Debug', '-o', 'out', '--nologo'],
+    program: path.join('out', 'Conformance.dll'),
+  },
+  c: {
+    language: 'c',
+    files: { 'conformance.c': C },
+    source: 'conformance.c',
+    build: ['cc', '-g', '-O0', '-o', 'conformance', 'conformance.c'],
+    program: 'conformance',
+  },
+  rust: {
+    language: 'rust',
+    files: { 'main.rs': RUST },
+    source: 'main.rs',
+    build: ['rustc', '-g', '-C', 'opt-level=0', '-o', 'conformance', 'main.rs'],
+    program: 'conformance',
+  },
+};
+
+/** The sample each built-in adapter is checked with */
+const ADAPTER_LANGUAGES: Record<string, string> = {
+  node: 'javascript',
+  debugpy: 'python',
+  dotnet: 'csharp',
+  netcoredbg: 'csharp',
+  vsdbg: 'csharp',
+  lldb: 'c',
+  rust: 'rust',
+  'rust-lldb': 'rust',
+  'rust-gdb': 'rust',
+};
+
+/**
+ * The sample language for an adapter (adapters without one, like stdio: adapters, need
+ * --language)
+ */
+export function conformanceLanguage(adapterName: string): string | undefined {
+  return ADAPTER_LANGUAGES[adapterName];
+}
+
+/**
+ * Line numbers of a source's "@name" markers
+ */
+export function markerLines(source: string): Record<string, number> {
+  const lines: Record<string, number> = {};
+  source.split('\n').forEach((text, index) => {
+    const match = text.match(/(?:\/\/|#|\/\*)\s*@(\w+)/);
+    if (match) lines[match[1]] = index + 1;
+  });
+  return lines;
+}
+
+/**
+ * pass: works as DAP describes; fail: advertised (or assumed) but didn't work;
+ * unsupported: the adapter doesn't advertise it; skipped: the sample can't exercise it
+ */
+export type ConformanceStatus = 'pass' | 'fail' | 'unsupported' | 'skipped';
+
+export interface ConformanceResult {
+  feature: string;
+  description: string;
+  status: ConformanceStatus;
+  /** What was seen, or why it failed */
+  detail?: string;
+  durationMs: number;
+}
+
+export interface ConformanceReport {
+  adapter: string;
+  language: string;
+  results: ConformanceResult[];
+  /** What initialize returned */
+  capabilities: Capabilities;
+}
+
+/** A check that didn't hold; its message becomes the result's detail */
+class ConformanceFailure extends Error {}
+
+/** A session of one feature check: the launched sample and what it has reported */
+export interface ConformanceContext {
+  client: IDapClient;
+  capabilities: Capabilities;
+  /** The sample source's marked lines */
+  lines: Record<string, number>;
+  /** Program output seen so far */
+  output(): string;
+  /** The next stop, failing if the program ends or nothing stops in time */
+  nextStop(): Promise<StoppedEventBody>;
+  /** Resolves when the program has ended, failing if it stops or takes too long */
+  ended(): Promise<void>;
+  /** The stopped thread's frames */
+  frames(stop: StoppedEventBody): Promise<StackFrame[]>;
+  /** Evaluate in the stopped thread's top frame */
+  evaluate(stop: StoppedEventBody, expression: string): Promise<string>;
+}
+
+export interface ConformanceCheck {
+  feature: string;
+  description: string;
+  /** Capability the adapter has to advertise for the check to run */
+  capability?: keyof Capabilities;
+  /** Marker the sample needs */
+  marker?: string;
+  /** Breakpoints to set in the sample source, from its marked lines */
+  breakpoints?: (lines: Record<string, number>) => SourceBreakpoint[];
+  /** Exception filter to enable, chosen from those the adapter reports */
+  exceptions?: boolean;
+  /** Runs once the program is launched; returns what was seen */
+  run(context: ConformanceContext): Promise<string | undefined>;
+}
+
+function expectValue(actual: string, expected: string, what: string): string {
+  if (!new RegExp(`^${expected}\\b`).test(actual.trim())) {
+    throw new ConformanceFailure(`${what} was ${JSON.stringify(actual)}, expected ${expected}`);
+  }
+  return `${what} = ${expected}`;
+}
+
+function expectFrame(frame: StackFrame | undefined, name: RegExp, where: string): string {
+  if (!frame || !name.test(frame.name)) {
+    throw new ConformanceFailure(`Top frame was ${frame?.name ?? 'missing'}, expected ${where}`);
+  }
+  return `in ${frame.name} at line ${frame.line}`;
+}
+
+const ADD_FRAME = /add/i;
+const MAIN_FRAME = /main/i;
+
+export const CONFORMANCE_CHECKS: ConformanceCheck[] = [
+  {
+    feature: 'launch',
+    description: 'Launch the program and run it to the end',
+    run: async (context) => {
+      await context.ended();
+      return undefined;
+    },
+  },
+  {
+    feature: 'output',
+    description: 'Report program output as output events',
+    run: async (context) => {
+      await context.ended();
+      if (!context.output().includes('total=10')) {
+        throw new ConformanceFailure('The program printed "total=10 done" but no output said so');
+      }
+      return undefined;
+    },
+  },
+  {
+    feature: 'breakpoint',
+    description: 'Stop at a line breakpoint',
+    breakpoints: (lines) => [{ line: lines.result }],
+    run: async (context) => {
+      const stop = await context.nextStop();
+      const [top] = await context.frames(stop);
+      if (top?.line !== context.lines.result) {
+        const expected = context.lines.result;
+        throw new ConformanceFailure(`Stopped at line ${top?.line}, expected ${expected}`);
+      }
+      return `stopped (${stop.reason}) at line ${top.line}`;
+    },
+  },
+  {
+    feature: 'conditional-breakpoint',
+    description: 'Stop only when a breakpoint condition holds',
+    capability: 'supportsConditionalBreakpoints',
+    breakpoints: (lines) => [{ line: lines.loop, condition: 'i == 3' }],
+    run: async (context) => {
+      const stop = await context.nextStop();
+      return expectValue(await context.evaluate(stop, 'i'), '3', 'i');
+    },
+  },
+  {
+    feature: 'hit-count-breakpoint',
+    description: 'Stop at the hit a hit condition names',
+    capability: 'supportsHitConditionalBreakpoints',
+    breakpoints: (lines) => [{ line: lines.loop, hitCondition: '3' }],
+    run: async (context) => {
+      const stop = await context.nextStop();
+      return expectValue(await context.evaluate(stop, 'i'), '2', 'i');
+    },
+  },
+  {
+    feature: 'logpoint',
+    description: 'Log an interpolated message without stopping',
+    capability: 'supportsLogPoints',
+    breakpoints: (lines) => [{ line: lines.result, logMessage: 'logged total={total}' }],
+    run: async (context) => {
+      await context.ended();
+      const logged = context.output().match(/logged total=\S*/)?.[0];
+      if (logged !== 'logged total=10') {
+        throw new ConformanceFailure(
+          logged ? `Logged "${logged}", expected "logged total=10"` : 'Nothing was logged'
+        );
+      }
+      return logged;
+    },
+  },
+  {
+    feature: 'stack-trace',
+    description: "List the stopped thread's frames",
+    breakpoints: (lines) => [{ line: lines.add }],
+    run: async (context) => {
+      const frames = await context.frames(await context.nextStop());
+      expectFrame(frames[0], ADD_FRAME, 'add');
+      if (!frames[1] || !MAIN_FRAME.test(frames[1].name)) {
+        const caller = frames[1]?.name ?? 'missing';
+        throw new ConformanceFailure(`The caller was ${caller}, expected main`);
+      }
+      return frames
+        .slice(0, 2)
+        .map((frame) => frame.name)
+        .join(' <- ');
+    },
+  },
+  {
+    feature: 'variables',
+    description: "Read a frame's locals through scopes and variables",
+    breakpoints: (lines) => [{ line: lines.result }],
+    run: async (context) => {
+      const [top] = await context.frames(await context.nextStop());
+      const { scopes } = await context.client.scopes({ frameId: top.id });
+      for (const scope of scopes.filter((s) => !s.expensive)) {
+        const { variables } = await context.client.variables({
+          variablesReference: scope.variablesReference,
+        });
+        const total = variables.find((variable) => variable.name === 'total');
+        if (total) return `${expectValue(total.value, '10', 'total')} (${scope.name})`;
+      }
+      throw new ConformanceFailure('No scope of the top frame has the local "total"');
+    },
+  },
+  {
+    feature: 'evaluate',
+    description: 'Evaluate an expression in the stopped frame',
+    breakpoints: (lines) => [{ line: lines.result }],
+    run: async (context) =>
+      expectValue(await context.evaluate(await context.nextStop(), 'total * 2'), '20', 'total * 2'),
+  },
+  {
+    feature: 'set-variable',
+    description: 'Change a local with setVariable',
+    capability: 'supportsSetVariable',
+    breakpoints: (lines) => [{ line: lines.result }],
+    run: async (context) => {
+      const stop = await context.nextStop();
+      const [top] = await context.frames(stop);
+      const { scopes } = await context.client.scopes({ frameId: top.id });
+      for (const scope of scopes.filter((s) => !s.expensive)) {
+        const { variables } = await context.client.variables({
+          variablesReference: scope.variablesReference,
+        });
+        if (!variables.some((variable) => variable.name === 'total')) continue;
+        await context.client.customRequest('setVariable', {
+          variablesReference: scope.variablesReference,
+          name: 'total',
+          value: '42',
+        });
+        return expectValue(await context.evaluate(stop, 'total'), '42', 'total');
+      }
+      throw new ConformanceFailure('No scope of the top frame has the local "total"');
+    },
+  },
+  {
+    feature: 'step-over',
+    description: 'Step over a line',
+    breakpoints: (lines) => [{ line: lines.result }],
+    run: async (context) => {
+      const stop = await context.nextStop();
+      await context.client.next({ threadId: stop.threadId ?? 1 });
+      const [top] = await context.frames(await context.nextStop());
+      const where = expectFrame(top, MAIN_FRAME, 'main');
+      if (top.line <= context.lines.result) {
+        throw new ConformanceFailure(`Still at line ${top.line} after stepping over`);
+      }
+      return where;
+    },
+  },
+  {
+    feature: 'step-in',
+    description: 'Step into a called function',
+    breakpoints: (lines) => [{ line: lines.loop }],
+    run: async (context) => {
+      const stop = await context.nextStop();
+      await context.client.stepIn({ threadId: stop.threadId ?? 1 });
+      const [top] = await context.frames(await context.nextStop());
+      return expectFrame(top, ADD_FRAME, 'add');
+    },
+  },
+  {
+    feature: 'step-out',
+    description: 'Step out to the caller',
+    breakpoints: (lines) => [{ line: lines.add }],
+    run: async (context) => {
+      const stop = await context.nextStop();
+      await context.client.stepOut({ threadId: stop.threadId ?? 1 });
+      const [top] = await context.frames(await context.nextStop());
+      return expectFrame(top, MAIN_FRAME, 'main');
+    },
+  },
+  {
+    feature: 'exception-breakpoint',
+    description: 'Stop where a caught exception is thrown',
+    marker: 'throw',
+    exceptions: true,
+    run: async (context) => {
+      const stop = await context.nextStop();
+      if (stop.reason !== 'exception') {
+        throw new ConformanceFailure(`Stopped for ${stop.reason}, expected an exception`);
+      }
+      const [top] = await context.frames(stop);
+      return `stopped at line ${top?.line}`;
+    },
+  },
+];
+
+/**
+ * The exception filter that stops at caught exceptions: "all" or "raised" if the adapter
+ * has one, else the first that isn't only for uncaught ones
+ */
+export function caughtExceptionFilter(capabilities: Capabilities): string | undefined {
+  const filters = (capabilities.exceptionBreakpointFilters ?? []).map((f) => f.filter);
+  return (
+    filters.find((filter) => filter === 'all' || filter === 'raised') ??
+    filters.find((filter) => !/uncaught|unhandled/i.test(filter))
+  );
+}
+
+/**
+ * Write the sample to a new temporary directory and build it
+ *
+ * @throws Error if the build tool can't be run or the build fails
+ */
+export async function prepareSample(program: ConformanceProgram): Promise<string> {
+  const directory = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-conformance-'));
+  for (const [file, content] of Object.entries(program.files)) {
+    fs.writeFileSync(path.join(directory, file), content);
+  }
+  if (program.build) {
+    const [command, ...args] = program.build;
+    try {
+      await execFileAsync(command, args, { cwd: directory, timeout: BUILD_TIMEOUT_MS });
+    } catch (error) {
+      fs.rmSync(directory, { recursive: true, force: true });
+      const { code, stdout, stderr } = error as NodeJS.ErrnoException & {
+        stdout?: string;
+        stderr?: string;
+      };
+      if (code === 'ENOENT') {
+        throw new Error(`The ${program.language} sample needs ${command}, which was not found`);
+      }
+      const output = `${stdout ?? ''}${stderr ?? ''}`.trim();
+      throw new Error(`Building the ${program.language} sample failed: ${output || error}`);
+    }
+  }
+  return directory;
+}
+
+function createClient(adapter: AdapterConfig, cwd: string, timeout: number): IDapClient {
+  const clientOptions = {
+    command: adapter.command,
+    args: adapter.args,
+    cwd,
+    env: adapter.env,
+    timeout,
+  };
+  return adapter.transport === 'socket' && adapter.socketPort
+    ? new SocketDapClient({ ...clientOptions, port: adapter.socketPort })
+    : new DapClient(clientOptions);
+}
+
+/**
+ * Events from the launched sample, queued so a check can wait for the next one
+ */
+function watchSession(client: IDapClient, timeout: number) {
+  const stops: StoppedEventBody[] = [];
+  let output = '';
+  let ended = false;
+  let wake: (() => void) | null = null;
+  const notify = () => {
+    wake?.();
+    wake = null;
+  };
+  client.on('stopped', (body: StoppedEventBody) => {
+    stops.push(body);
+    notify();
+  });
+  client.on('output', (body: OutputEventBody) => {
+    output += body.output;
+  });
+  const onEnd = () => {
+    ended = true;
+    notify();
+  };
+  client.on('terminated', onEnd);
+  client.on('exited', onEnd);
+
+  const wait = async (done: () => boolean, what: string): Promise<void> => {
+    const deadline = Date.now() + timeout;
+    while (!done()) {
+      const remaining = deadline - Date.now();
+      if (remaining <= 0) throw new ConformanceFailure(`Nothing ${what} within ${timeout}ms`);
+      let timer: NodeJS.Timeout | undefined;
+      await new Promise<void>((resolve) => {
+        wake = resolve;
+        timer = setTimeout(resolve, remaining);
+      });
+      clearTimeout(timer);
+    }
+  };
+
+  return {
+    output: () => output,
+    nextStop: async () => {
+      await wait(() => stops.length > 0 || ended, 'stopped');
+      const stop = stops.shift();
+      if (!stop) throw new ConformanceFailure('The program ended without stopping');
+      return stop;
+    },
+    ended: async () => {
+      await wait(() => stops.length > 0 || ended, 'ended');
+      if (!ended) throw new ConformanceFailure(`The program stopped (${stops[0].reason})`);
+    },
+  };
+}
+
+/**
+ * Launch the sample with a check's breakpoints and run the check
+ */
+async function runCheck(
+  adapter: AdapterConfig,
+  check: ConformanceCheck,
+  sample: { directory: string; program: ConformanceProgram; lines: Record<string, number> },
+  timeout: number
+): Promise<{ capabilities: Capabilities; detail?: string }> {
+  const client = createClient(adapter, sample.directory, Math.max(timeout, 10000));
+  const session = watchSession(client, timeout);
+  try {
+    await client.connect();
+    const capabilities = (await client.initialize({ adapterID: adapter.id })) ?? {};
+    if (check.capability && !capabilities[check.capability]) {
+      return { capabilities };
+    }
+
+    const configure = async () => {
+      if (check.breakpoints) {
+        await client.setBreakpoints({
+          source: { path: path.join(sample.directory, sample.program.source) },
+          breakpoints: check.breakpoints(sample.lines),
+        });
+      }
+      const filter = check.exceptions ? caughtExceptionFilter(capabilities) : undefined;
+      await client.setExceptionBreakpoints({ filters: filter ? [filter] : [] });
+    };
+
+    // Same launch ordering as DebugSession.start()
+    const launchConfig = adapter.launchConfig({
+      program: path.join(sample.directory, sample.program.program),
+      cwd: sample.directory,
+    });
+    if (adapter.requiresLaunchFirst) {
+      const initialized = new Promise<void>((resolve) => client.once('initialized', resolve));
+      const launched = client.launch(launchConfig);
+      await initialized;
+      await configure();
+      await client.configurationDone();
+      await launched;
+    } else {
+      await configure();
+      if (adapter.transport === 'socket') {
+        await client.configurationDone();
+        await client.launch(launchConfig);
+      } else {
+        await client.launch(launchConfig);
+        await client.configurationDone();
+      }
+    }
+
+    const detail = await check.run({
+      client,
+      capabilities,
+      lines: sample.lines,
+      output: session.output,
+      nextStop: session.nextStop,
+      ended: session.ended,
+      frames: async (stop) =>
+        (await client.stackTrace({ threadId: stop.threadId ?? 1, levels: 20 })).stackFrames,
+      evaluate: async (stop, expression) => {
+        const threadId = stop.threadId ?? 1;
+        const { stackFrames } = await client.stackTrace({ threadId, levels: 1 });
+        const response = await client.evaluate({
+          expression,
+          frameId: stackFrames[0]?.id,
+          context: 'watch',
+        });
+        return response.result;
+      },
+    });
+    return { capabilities, detail };
+  } finally {
+    if (client.isConnected()) {
+      await client.disconnect(true).catch(() => undefined);
+    }
+    await client.reapProcessTree();
+  }
+}
+
+export interface ConformanceOptions {
+  /** Sample language (default: the adapter's) */
+  language?: string;
+  /** Time each check may wait for a stop or the program's end, in ms */
+  timeout: number;
+  /** Only run these features (default: all) */
+  features?: string[];
+}
+
+/**
+ * Run the feature matrix against an adapter with its language's sample
+ *
+ * @throws Error for an unknown language or feature, or a sample that won't build
+ */
+export async function runConformance(
+  adapter: AdapterConfig,
+  options: ConformanceOptions
+): Promise<ConformanceReport> {
+  const language = options.language ?? conformanceLanguage(adapter.name);
+  const program = language ? CONFORMANCE_PROGRAMS[language] : undefined;
+  if (!program) {
+    const known = Object.keys(CONFORMANCE_PROGRAMS).join(', ');
+    throw new Error(
+      language
+        ? `No conformance sample for "${language}" (available: ${known})`
+        : `No conformance sample for the ${adapter.name} adapter; ` +
+            `pick one with --language (${known})`
+    );
+  }
+  const features = new Set(CONFORMANCE_CHECKS.map((check) => check.feature));
+  for (const feature of options.features ?? []) {
+    if (!features.has(feature)) {
+      throw new Error(`Unknown feature "${feature}" (available: ${[...features].join(', ')})`);
+    }
+  }
+
+  const directory = await prepareSample(program);
+  const lines = markerLines(program.files[program.source]);
+  const sample = { directory, program, lines };
+  const results: ConformanceResult[] = [];
+  let capabilities: Capabilities = {};
+  const span = logger.span('conformance', { adapter: adapter.name, language: program.language });
+
+  try {
+    for (const check of CONFORMANCE_CHECKS) {
+      if (options.features?.length && !options.features.includes(check.feature)) continue;
+      const started = Date.now();
+      const result: ConformanceResult = {
+        feature: check.feature,
+        description: check.description,
+        status: 'pass',
+        durationMs: 0,
+      };
+      if (check.marker && !(check.marker in lines)) {
+        result.status = 'skipped';
+        result.detail = `The ${program.language} sample has no @${check.marker} line`;
+      } else {
+        try {
+          const outcome = await runCheck(adapter, check, sample, options.timeout);
+          capabilities = outcome.capabilities;
+          if (check.capability && !capabilities[check.capability]) {
+            result.status = 'unsupported';
+            result.detail = `The adapter doesn't report ${check.capability}`;
+          } else if (check.exceptions && !caughtExceptionFilter(capabilities)) {
+            result.status = 'unsupported';
+            result.detail = 'The adapter has no exception filter for caught exceptions';
+          } else if (outcome.detail) {
+            result.detail = outcome.detail;
+          }
+        } catch (error) {
+          result.status = 'fail';
+          result.detail = error instanceof Error ? error.message : String(error);
+        }
+      }
+      result.durationMs = Date.now() - started;
+      logger.debug('conformance', 'checked', { feature: check.feature, status: result.status });
+      results.push(result);
+    }
+    span.end({ failed: results.filter((result) => result.status === 'fail').length });
+    return { adapter: adapter.name, language: program.language, results, capabilities };
+  } finally {
+    fs.rmSync(directory, { recursive: true, force: true });
+  }
+}
+
+const STATUS_MARKS: Record<ConformanceStatus, string> = {
+  pass: '✓',
+  fail: '✗',
+  unsupported: '-',
+  skipped: '-',
+};
+
+/**
+ * Human-readable report: one line per feature, with what was seen or why it failed
+ */
+export function formatConformanceReport(report: ConformanceReport): string {
+  const passed = report.results.filter((result) => result.status === 'pass').length;
+  const failed = report.results.filter((result) => result.status === 'fail').length;
+  const width = Math.max(...report.results.map((result) => result.feature.length));
+  const lines = [
+    `${report.adapter} conformance (${report.language} sample): ${passed} passed, ` +
+      `${failed} failed, ${report.results.length - passed - failed} not run`,
+    '',
+  ];
+  for (const result of report.results) {
+    const ran = result.status === 'pass' || result.status === 'fail';
+    const status = ran ? '' : ` (${result.status})`;
+    const detail = result.detail ? `: ${result.detail}` : '';
+    const feature = result.feature.padEnd(width);
+    lines.push(
+      `  ${STATUS_MARKS[result.status]} ${feature}  ${result.description}${status}${detail}`
+    );
+  }
+  return lines.join('\n');
+}
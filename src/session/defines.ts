@@ -0,0 +1,52 @@
+/**
+ * Run Variables (--define ORDER_ID=ORD-002)
+ *
+ * Fills ${NAME} placeholders in breakpoint specs, evaluations and assertions, so a script can
+ * run the same config file or profile against a different order, user or tenant each time
+ * without editing it. Values are inserted as written: quote them in the expression where a
+ * string is meant (order.id == "${ORDER_ID}").
+ */
+
+const NAME_PATTERN = /^[A-Za-z_][A-Za-z0-9_]*$/;
+const PLACEHOLDER_PATTERN = /\$\{([A-Za-z_][A-Za-z0-9_]*)\}/g;
+
+/**
+ * Parse NAME=VALUE definitions (a later one for the same name wins)
+ *
+ * @throws Error for a definition without a name or "="
+ */
+export function parseDefines(specs: string[]): Record<string, string> {
+  const defines: Record<string, string> = {};
+  for (const spec of specs) {
+    const separator = spec.indexOf('=');
+    const name = separator === -1 ? '' : spec.slice(0, separator).trim();
+    if (!NAME_PATTERN.test(name)) {
+      throw new Error(`Invalid --define "${spec}": expected NAME=VALUE (e.g., ORDER_ID=ORD-002)`);
+    }
+    defines[name] = spec.slice(separator + 1);
+  }
+  return defines;
+}
+
+/**
+ * Replace the ${NAME} placeholders that have a definition; others are left as they are
+ * (a JavaScript template literal in a condition, say)
+ */
+export function expandDefines(text: string, defines: Record<string, string>): string {
+  return text.replace(PLACEHOLDER_PATTERN, (placeholder, name: string) =>
+    Object.hasOwn(defines, name) ? defines[name] : placeholder
+  );
+}
+
+/**
+ * Names of the placeholders in texts that have no definition
+ */
+export function undefinedPlaceholders(texts: string[], defines: Record<string, string>): string[] {
+  const names = new Set<string>();
+  for (const text of texts) {
+    for (const [, name] of text.matchAll(PLACEHOLDER_PATTERN)) {
+      if (!Object.hasOwn(defines, name)) names.add(name);
+    }
+  }
+  return [...names];
+}
@@ -0,0 +1,234 @@
+/**
+ * Breakpoints from a Git Diff
+ *
+ * Turns "debug whatever I just changed" into breakpoint specs: reads the changed hunks of
+ * `git diff <rev>`, groups the changed lines by the function that contains them, and picks
+ * the first executable changed line in each function.
+ */
+
+import { execFileSync } from 'node:child_process';
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+
+export interface DiffHunk {
+  /** Repository-relative path of the file after the change */
+  file: string;
+  /** First changed line in the new file (1-based) */
+  start: number;
+  /** Number of changed lines in the new file (0 for pure deletions) */
+  count: number;
+}
+
+const DOTNET_EXTENSIONS = ['.cs', '.fs', '.vb'];
+const RUST_EXTENSIONS = ['.rs'];
+
+/** Source file extensions considered for each adapter (others accept any source file) */
+const ADAPTER_EXTENSIONS: Record<string, string[]> = {
+  node: ['.js', '.mjs', '.cjs', '.jsx', '.ts', '.mts', '.cts', '.tsx'],
+  debugpy: ['.py'],
+  dotnet: DOTNET_EXTENSIONS,
+  netcoredbg: DOTNET_EXTENSIONS,
+  vsdbg: DOTNET_EXTENSIONS,
+  rust: RUST_EXTENSIONS,
+  'rust-lldb': RUST_EXTENSIONS,
+  'rust-gdb': RUST_EXTENSIONS,
+  lldb: ['.c', '.cc', '.cpp', '.cxx', '.h', '.hpp', '.m', '.mm', '.swift', '.rs'],
+};
+
+const SOURCE_EXTENSIONS = new Set([...Object.values(ADAPTER_EXTENSIONS).flat(), '.go', '.java']);
+
+/** Lines that start a function, method or closure body */
+const FUNCTION_START_PATTERNS = [
+  // JavaScript/TypeScript functions, arrow functions and methods
+  /^\s*(export\s+)?(default\s+)?(async\s+)?function\b/,
+  /=\s*(async\s+)?(\([^)]*\)|[\w$]+)\s*(:\s*[^=]+)?=>/,
+  /^\s*((public|private|protected|static|async|readonly|override|get|set)\s+)*(?!(if|for|while|switch|catch|with|return)\b)[\w$]+\s*\([^)]*\)\s*(:\s*[^{]+)?\{\s*$/,
+  // Python
+  /^\s*(async\s+)?def\s+\w+/,
+  // C#, Java
+  /^\s*((public|private|protected|internal|static|async|override|virtual|sealed|final|abstract|synchronized)\s+)+[\w<>[\],.?\s]+\s+\w+\s*\(/,
+  // Rust, Go
+  /^\s*(pub(\([\w:]+\))?\s+)?(const\s+)?(async\s+)?(unsafe\s+)?(extern\s+"\w+"\s+)?fn\s+\w+/,
+  /^func\b/,
+];
+
+/** Lines where a breakpoint can't usefully stop */
+const NON_EXECUTABLE_PATTERNS = [
+  /^$/,
+  /^(\/\/|#|\/\*|\*|--|"""|''')/,
+  /^[{}()[\];,]+$/,
+  /^(import|from|using|package|use|namespace|module)\b/,
+  /^@/,
+  /^(}\s*)?(else|try|finally|do)\b\s*:?\s*{?$/,
+  /^(case\b.*|default):$/,
+  /^(class|interface|struct|enum|type|trait|impl|extern)\b/,
+  /^(export\s+)?(abstract\s+)?(class|interface|type|enum)\b/,
+];
+
+/**
+ * Source file extensions in an adapter's languages (every known source extension without one)
+ */
+export function sourceExtensions(adapter?: string): Set<string> {
+  return new Set((adapter && ADAPTER_EXTENSIONS[adapter]) || SOURCE_EXTENSIONS);
+}
+
+/**
+ * Parse `git diff --unified=0` output into changed ranges of the new files
+ */
+export function parseUnifiedDiff(diff: string): DiffHunk[] {
+  const hunks: DiffHunk[] = [];
+  let file: string | null = null;
+
+  for (const line of diff.split('\n')) {
+    if (line.startsWith('+++ ')) {
+      const target = line.slice(4).trim();
+      file = target === '/dev/null' ? null : target.replace(/^b\//, '');
+      continue;
+    }
+
+    const match = line.match(/^@@ -\d+(?:,\d+)? \+(\d+)(?:,(\d+))? @@/);
+    if (match && file) {
+      hunks.push({
+        file,
+        start: parseInt(match[1], 10),
+        count: match[2] === undefined ? 1 : parseInt(match[2], 10),
+      });
+    }
+  }
+
+  return hunks;
+}
+
+/**
+ * Whether a source line is a statement a debugger can stop on
+ */
+export function isExecutableLine(line: string): boolean {
+  const trimmed = line.trim();
+  return (
+    !NON_EXECUTABLE_PATTERNS.some((pattern) => pattern.test(trimmed)) &&
+    !isFunctionStart(line)
+  );
+}
+
+function isFunctionStart(line: string): boolean {
+  return FUNCTION_START_PATTERNS.some((pattern) => pattern.test(line));
+}
+
+/**
+ * Index of the function-start line enclosing a line (0-based), or -1 at top level.
+ * Indentation-based: the nearest preceding function start indented less than the line.
+ */
+function enclosingFunction(lines: string[], index: number): number {
+  const indent = (line: string) => line.length - line.trimStart().length;
+  const lineIndent = lines[index].trim() ? indent(lines[index]) : Infinity;
+
+  for (let i = index; i >= 0; i--) {
+    if (isFunctionStart(lines[i]) && (i === index || indent(lines[i]) < lineIndent)) {
+      return i;
+    }
+  }
+  return -1;
+}
+
+/**
+ * Pick one breakpoint line (1-based) per changed function in a file
+ */
+export function pickBreakpointLines(lines: string[], hunks: DiffHunk[]): number[] {
+  const groups = new Map<string, number[]>();
+
+  for (const hunk of hunks) {
+    for (let line = hunk.start; line < hunk.start + hunk.count && line <= lines.length; line++) {
+      const fn = enclosingFunction(lines, line - 1);
+      // Top-level changes are grouped per hunk
+      const key = fn >= 0 ? `fn:${fn}` : `hunk:${hunk.start}`;
+      const group = groups.get(key) ?? [];
+      group.push(line);
+      groups.set(key, group);
+    }
+  }
+
+  const picked: number[] = [];
+  for (const [key, changedLines] of groups) {
+    const executable = changedLines.find((line) => isExecutableLine(lines[line - 1]));
+    if (executable !== undefined) {
+      picked.push(executable);
+      continue;
+    }
+
+    // Only the signature (or comments) changed: break on the first statement of the body
+    if (key.startsWith('fn:')) {
+      const start = parseInt(key.slice(3), 10);
+      for (let i = start + 1; i < Math.min(lines.length, start + 50); i++) {
+        if (isFunctionStart(lines[i])) break;
+        if (isExecutableLine(lines[i])) {
+          picked.push(i + 1);
+          break;
+        }
+      }
+    }
+  }
+
+  return [...new Set(picked)].sort((a, b) => a - b);
+}
+
+/**
+ * Derive breakpoint specs (absolute path:line) from the changes since a git revision.
+ * Compares the revision to the working tree, so uncommitted edits are included. With an
+ * adapter name, only files in that adapter's languages are considered.
+ *
+ * @throws Error if the revision looks like an option, or git fails (not a repository,
+ * unknown revision, ...)
+ */
+export function breakpointsFromDiff(
+  revision: string,
+  options: { cwd?: string; adapter?: string } = {}
+): string[] {
+  // git would take it as an option (--output=<file> writes anywhere)
+  if (revision.startsWith('-')) {
+    throw new Error(`Invalid revision "${revision}": revisions can't start with "-"`);
+  }
+
+  const cwd = options.cwd ?? process.cwd();
+  const run = (args: string[]) => {
+    try {
+      return execFileSync('git', args, {
+        cwd,
+        encoding: 'utf-8',
+        stdio: ['ignore', 'pipe', 'pipe'],
+        maxBuffer: 64 * 1024 * 1024,
+      });
+    } catch (error) {
+      const stderr = (error as { stderr?: string }).stderr?.trim();
+      throw new Error(`git ${args[0]} failed: ${stderr || (error as Error).message}`);
+    }
+  };
+
+  const root = run(['rev-parse', '--show-toplevel']).trim();
+  const diff = run(['diff', '--unified=0', '--no-color', '--no-ext-diff', revision, '--']);
+
+  const extensions = sourceExtensions(options.adapter);
+  const hunksByFile = new Map<string, DiffHunk[]>();
+  for (const hunk of parseUnifiedDiff(diff)) {
+    if (hunk.count === 0 || !extensions.has(path.extname(hunk.file).toLowerCase())) continue;
+    const fileHunks = hunksByFile.get(hunk.file) ?? [];
+    fileHunks.push(hunk);
+    hunksByFile.set(hunk.file, fileHunks);
+  }
+
+  const specs: string[] = [];
+  for (const [file, fileHunks] of hunksByFile) {
+    const absolute = path.join(root, file);
+    let lines: string[];
+    try {
+      lines = fs.readFileSync(absolute, 'utf-8').split(/\r?\n/);
+    } catch {
+      // Deleted from the working tree since
+      continue;
+    }
+    for (const line of pickBreakpointLines(lines, fileHunks)) {
+      specs.push(`${absolute}:${line}`);
+    }
+  }
+
+  return specs;
+}
@@ -0,0 +1,234 @@
+/**
+ * .NET Symbols and SourceLink (--symbol-server, --source-link)
+ *
+ * netcoredbg only loads a PDB that is embedded in its assembly or sits next to it, and
+ * reports the paths sources had on the build machine. Before launch, PDBs the program's
+ * assemblies don't have are downloaded from symbol servers into a cache and copied next to
+ * them. During the session, build paths covered by a loaded module's SourceLink map are
+ * reported as a cached copy of the file, downloaded the first time a stack shows it.
+ */
+
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+import type { PathMapper } from '../dap/path-mapper.js';
+import { getAdaptersDir } from '../util/adapter-installer.js';
+import { logger } from '../util/logger.js';
+import {
+  pdbFileName,
+  readAssemblyDebugInfo,
+  sourceLinkDocuments,
+  sourceLinkUrl,
+  symbolServerKey,
+  type AssemblyDebugInfo,
+} from '../util/pdb.js';
+
+/** Symbol servers that can be named instead of given as a URL */
+export const SYMBOL_SERVERS: Record<string, string> = {
+  microsoft: 'https://msdl.microsoft.com/download/symbols',
+  nuget: 'https://symbols.nuget.org/download/symbols',
+};
+
+const DOWNLOAD_TIMEOUT_MS = 30_000;
+
+/**
+ * A --symbol-server value as a URL
+ *
+ * @throws Error for a value that is neither a known server nor an http(s) URL
+ */
+export function resolveSymbolServer(spec: string): string {
+  const named = SYMBOL_SERVERS[spec.toLowerCase()];
+  if (named) return named;
+  if (!/^https?:\/\/[^/]+/i.test(spec)) {
+    const names = Object.keys(SYMBOL_SERVERS).join(' or ');
+    throw new Error(`Invalid --symbol-server "${spec}": expected an http(s) URL, ${names}`);
+  }
+  return spec.replace(/\/+$/, '');
+}
+
+/**
+ * Where downloaded PDBs are kept
+ */
+export function symbolCacheDir(): string {
+  return path.join(getAdaptersDir(), 'dotnet-symbols');
+}
+
+/**
+ * Where sources downloaded through SourceLink are kept
+ */
+export function sourceCacheDir(): string {
+  return path.join(getAdaptersDir(), 'dotnet-sources');
+}
+
+async function download(url: string, fetchImpl: typeof fetch): Promise<Buffer | undefined> {
+  const response = await fetchImpl(url, { signal: AbortSignal.timeout(DOWNLOAD_TIMEOUT_MS) });
+  if (response.status === 404) return undefined;
+  if (!response.ok) throw new Error(`${url}: ${response.status} ${response.statusText}`);
+  return Buffer.from(await response.arrayBuffer());
+}
+
+function debugInfoOf(assembly: string): AssemblyDebugInfo | undefined {
+  try {
+    return readAssemblyDebugInfo(fs.readFileSync(assembly));
+  } catch {
+    return undefined;
+  }
+}
+
+export interface SymbolPrefetchResult {
+  /** PDBs copied next to their assembly */
+  loaded: string[];
+  /** Assemblies no symbol server had a PDB for */
+  missing: string[];
+}
+
+/**
+ * Put a PDB next to each assembly in a directory that has none, from the cache or the first
+ * symbol server that has it
+ */
+export async function prefetchSymbols(
+  directory: string,
+  servers: string[],
+  cacheDir = symbolCacheDir(),
+  fetchImpl: typeof fetch = fetch
+): Promise<SymbolPrefetchResult> {
+  const result: SymbolPrefetchResult = { loaded: [], missing: [] };
+  const assemblies = fs
+    .readdirSync(directory)
+    .filter((name) => /\.(dll|exe)$/i.test(name))
+    .map((name) => path.join(directory, name));
+
+  for (const assembly of assemblies) {
+    const info = debugInfoOf(assembly);
+    if (!info?.codeView || info.embeddedPdb) continue;
+    const target = path.join(directory, pdbFileName(info.codeView));
+    if (fs.existsSync(target)) continue;
+
+    const key = symbolServerKey(info.codeView);
+    const cached = path.join(cacheDir, ...key.split('/'));
+    if (!fs.existsSync(cached)) {
+      for (const server of servers) {
+        try {
+          const pdb = await download(`${server}/${key}`, fetchImpl);
+          if (!pdb) continue;
+          fs.mkdirSync(path.dirname(cached), { recursive: true });
+          fs.writeFileSync(cached, pdb);
+          break;
+        } catch (error) {
+          logger.warn('symbols', 'symbol server request failed', {
+            server,
+            key,
+            error: error instanceof Error ? error.message : String(error),
+          });
+        }
+      }
+    }
+
+    if (fs.existsSync(cached)) {
+      fs.copyFileSync(cached, target);
+      result.loaded.push(target);
+    } else {
+      result.missing.push(assembly);
+    }
+  }
+  return result;
+}
+
+/**
+ * The cache path of a downloaded source, laid out by host and URL path
+ */
+export function sourceCachePath(url: string, cacheDir = sourceCacheDir()): string {
+  const { host, pathname } = new URL(url);
+  const segments = pathname
+    .split('/')
+    .map((segment) => decodeURIComponent(segment).replace(/[\\/:*?"<>|]/g, '_'))
+    .filter((segment) => segment !== '' && segment !== '.' && segment !== '..');
+  return path.join(cacheDir, host.replace(/:/g, '_'), ...segments);
+}
+
+/**
+ * Reports build paths that a loaded module's SourceLink map covers as their cached download,
+ * and sends breakpoints set in a cached download to the build path
+ */
+export class SourceLinkMapper implements PathMapper {
+  private pendingModules: string[] = [];
+  private maps: Record<string, string>[] = [];
+  /** Cached download to build path, and to the URL it comes from */
+  private cached = new Map<string, { buildPath: string; url: string }>();
+  /** Build path to cached download */
+  private resolved = new Map<string, string>();
+  private failed = new Set<string>();
+
+  constructor(
+    private cacheDir = sourceCacheDir(),
+    private fetchImpl: typeof fetch = fetch
+  ) {}
+
+  /**
+   * Note a module the adapter loaded; its SourceLink map is read when first needed
+   */
+  addModule(modulePath: string | undefined): void {
+    if (modulePath && /\.(dll|exe)$/i.test(modulePath)) this.pendingModules.push(modulePath);
+  }
+
+  toAdapter(localPath: string): string {
+    return this.cached.get(localPath)?.buildPath ?? localPath;
+  }
+
+  toLocal(adapterPath: string): string {
+    const known = this.resolved.get(adapterPath);
+    if (known !== undefined) return known;
+    if (!path.isAbsolute(adapterPath) || fs.existsSync(adapterPath)) return adapterPath;
+
+    this.loadPendingMaps();
+    for (const documents of this.maps) {
+      const url = sourceLinkUrl(documents, adapterPath);
+      if (!url) continue;
+      let local: string;
+      try {
+        local = sourceCachePath(url, this.cacheDir);
+      } catch {
+        continue;
+      }
+      this.cached.set(local, { buildPath: adapterPath, url });
+      this.resolved.set(adapterPath, local);
+      return local;
+    }
+    return adapterPath;
+  }
+
+  /**
+   * Download the sources behind these (local) paths that aren't cached yet
+   */
+  async fetchSources(paths: (string | null | undefined)[]): Promise<void> {
+    for (const local of new Set(paths)) {
+      const source = local ? this.cached.get(local) : undefined;
+      if (!local || !source || this.failed.has(local) || fs.existsSync(local)) continue;
+      try {
+        const content = await download(source.url, this.fetchImpl);
+        if (!content) throw new Error(`${source.url}: not found`);
+        fs.mkdirSync(path.dirname(local), { recursive: true });
+        fs.writeFileSync(local, content);
+        logger.info('symbols', 'downloaded source through SourceLink', { url: source.url });
+      } catch (error) {
+        this.failed.add(local);
+        logger.warn('symbols', 'SourceLink download failed', {
+          url: source.url,
+          error: error instanceof Error ? error.message : String(error),
+        });
+      }
+    }
+  }
+
+  private loadPendingMaps(): void {
+    for (const modulePath of this.pendingModules.splice(0)) {
+      const info = debugInfoOf(modulePath);
+      let pdb = info?.embeddedPdb;
+      if (!pdb && info?.codeView) {
+        const beside = path.join(path.dirname(modulePath), pdbFileName(info.codeView));
+        pdb = fs.existsSync(beside) ? fs.readFileSync(beside) : undefined;
+      }
+      const documents = pdb && sourceLinkDocuments(pdb);
+      if (documents) this.maps.push(documents);
+    }
+  }
+}
@@ -0,0 +1,175 @@
+/**
+ * Exception Breakpoint Specs
+ *
+ * Parses --break-on-exception values and turns them into setExceptionBreakpoints
+ * arguments for an adapter. A spec is a filter id or "type:Name", optionally followed by
+ * "?condition" (as in breakpoint specs):
+ *
+ *   all                            break on every exception (filter id)
+ *   type:ValidationError           break only on this exception type
+ *   uncaught?error.status >= 500   filter with a condition on the exception
+ */
+
+import type { AdapterConfig } from '../adapters/base.js';
+import type {
+  Capabilities,
+  ExceptionFilterOptions,
+  ExceptionOptions,
+  SetExceptionBreakpointsArguments,
+} from '../dap/protocol.js';
+
+export interface ExceptionBreakpointSpec {
+  /** Exception filter id (e.g., all, uncaught) */
+  filter?: string;
+  /** Exception type name (type:Name) */
+  type?: string;
+  condition?: string;
+}
+
+export interface ExceptionBreakpointsPlan {
+  args: SetExceptionBreakpointsArguments;
+  /** Exception types requested */
+  types: string[];
+  /** Specs this adapter can't honor, with the reason */
+  unsupported: string[];
+}
+
+const TYPE_PREFIX = 'type:';
+
+/**
+ * Parse an exception breakpoint spec
+ *
+ * @throws Error if the spec is empty or has an empty type or condition
+ */
+export function parseExceptionBreakpointSpec(spec: string): ExceptionBreakpointSpec {
+  const trimmed = spec.trim();
+  const separator = trimmed.indexOf('?');
+  const head = (separator === -1 ? trimmed : trimmed.slice(0, separator)).trim();
+  const condition = separator === -1 ? undefined : trimmed.slice(separator + 1).trim();
+
+  if (!head) {
+    throw new Error(`Invalid exception breakpoint "${spec}". Expected a filter or "type:Name"`);
+  }
+  if (condition === '') {
+    throw new Error(`Empty condition in exception breakpoint "${spec}"`);
+  }
+  if (head.startsWith(TYPE_PREFIX)) {
+    const type = head.slice(TYPE_PREFIX.length).trim();
+    if (!type) {
+      throw new Error(`Missing exception type in "${spec}" (e.g., "type:ValidationError")`);
+    }
+    return { type, condition };
+  }
+  return { filter: head, condition };
+}
+
+/**
+ * Validate exception breakpoint specs against what an adapter supports.
+ * Returns error messages (empty if all valid).
+ */
+export function validateExceptionBreakpointSpecs(
+  specs: string[],
+  adapter: Pick<AdapterConfig, 'name' | 'exceptionFilters' | 'exceptionTypes'>
+): string[] {
+  const errors: string[] = [];
+  for (const spec of specs) {
+    let parsed: ExceptionBreakpointSpec;
+    try {
+      parsed = parseExceptionBreakpointSpec(spec);
+    } catch (error) {
+      errors.push((error as Error).message);
+      continue;
+    }
+
+    if (parsed.type && !adapter.exceptionTypes) {
+      errors.push(`Adapter "${adapter.name}" does not support exception type filters ("${spec}")`);
+    } else if (
+      parsed.filter &&
+      adapter.exceptionFilters &&
+      !adapter.exceptionFilters.includes(parsed.filter)
+    ) {
+      errors.push(
+        `Adapter "${adapter.name}" does not support exception filter "${parsed.filter}". ` +
+          `Supported filters: ${adapter.exceptionFilters.join(', ')}`
+      );
+    }
+  }
+  return errors;
+}
+
+/**
+ * Build setExceptionBreakpoints arguments for specs, using the adapter's capabilities to
+ * decide between plain filters, filter conditions and exceptionOptions
+ */
+export function planExceptionBreakpoints(
+  specs: string[],
+  adapter: Pick<AdapterConfig, 'name' | 'exceptionTypes'>,
+  capabilities: Capabilities
+): ExceptionBreakpointsPlan {
+  const filters: string[] = [];
+  const filterOptions: ExceptionFilterOptions[] = [];
+  const exceptionOptions: ExceptionOptions[] = [];
+  const types: string[] = [];
+  const unsupported: string[] = [];
+
+  const supportsCondition = (filter: string) => {
+    if (!capabilities.supportsExceptionFilterOptions) return false;
+    const described = capabilities.exceptionBreakpointFilters?.find((f) => f.filter === filter);
+    // Adapters that don't describe their filters get the benefit of the doubt
+    return described ? described.supportsCondition === true : true;
+  };
+
+  for (const spec of specs) {
+    const { filter, type, condition } = parseExceptionBreakpointSpec(spec);
+
+    if (type) {
+      types.push(type);
+      const support = adapter.exceptionTypes;
+      if (!support) {
+        unsupported.push(`${spec}: exception type filters are not supported`);
+      } else if (support.kind === 'condition') {
+        if (!supportsCondition(support.filter)) {
+          unsupported.push(`${spec}: the adapter doesn't support exception conditions`);
+          continue;
+        }
+        const typeCondition = support.condition(type);
+        filterOptions.push({
+          filterId: support.filter,
+          condition: condition ? `(${typeCondition}) && (${condition})` : typeCondition,
+        });
+      } else {
+        if (!capabilities.supportsExceptionOptions) {
+          unsupported.push(`${spec}: the adapter doesn't support exceptionOptions`);
+          continue;
+        }
+        if (condition) {
+          unsupported.push(`${spec}: conditions can't be combined with exception types here`);
+          continue;
+        }
+        exceptionOptions.push({
+          path: [{ names: [support.category] }, { names: [type] }],
+          breakMode: 'always',
+        });
+      }
+      continue;
+    }
+
+    if (!condition) {
+      filters.push(filter!);
+    } else if (supportsCondition(filter!)) {
+      filterOptions.push({ filterId: filter!, condition });
+    } else {
+      unsupported.push(`${spec}: the adapter doesn't support conditions on "${filter}"`);
+    }
+  }
+
+  return {
+    args: {
+      filters,
+      ...(filterOptions.length > 0 && { filterOptions }),
+      ...(exceptionOptions.length > 0 && { exceptionOptions }),
+    },
+    types,
+    unsupported,
+  };
+}
@@ -0,0 +1,119 @@
+/**
+ * Exception Sampling (--sample-exceptions)
+ *
+ * Triage for a service that has to keep serving: only exception breakpoints are set, and each
+ * exception stop records the exception's type, message and stack before the service is
+ * resumed, without fetching locals or running evaluations. Exceptions are grouped by type
+ * and throw site, and each period's groups are reported in an exception_sample_report event,
+ * the most frequent first.
+ */
+
+import type {
+  ExceptionSampleGroup,
+  ExceptionSampleReportEvent,
+  SourceLocation,
+  StackFrameInfo,
+} from '../output/events.js';
+
+/** Frames kept of each group's latest stack */
+const MAX_STACK_FRAMES = 10;
+
+/** Distinct messages counted per group (beyond that, new messages aren't told apart) */
+const MAX_DISTINCT_MESSAGES = 100;
+
+export interface SampledException {
+  type: string;
+  message: string;
+  location: SourceLocation;
+  stackTrace: StackFrameInfo[];
+}
+
+interface Group {
+  type: string;
+  location: SourceLocation;
+  message: string;
+  stackTrace: StackFrameInfo[];
+  /** Occurrences in the current period */
+  count: number;
+  total: number;
+  firstSeen: string;
+  lastSeen: string;
+  messages: Set<string>;
+}
+
+export class ExceptionSampler {
+  private groups = new Map<string, Group>();
+  private periodStart: string;
+  private total = 0;
+
+  constructor(now: Date = new Date()) {
+    this.periodStart = now.toISOString();
+  }
+
+  /**
+   * Record an exception stop
+   */
+  record(exception: SampledException, now: Date = new Date()): void {
+    const { location } = exception;
+    const key = `${exception.type}\0${location.file}:${location.line}`;
+    const seen = now.toISOString();
+    const group = this.groups.get(key) ?? {
+      type: exception.type,
+      location,
+      message: exception.message,
+      stackTrace: [],
+      count: 0,
+      total: 0,
+      firstSeen: seen,
+      lastSeen: seen,
+      messages: new Set<string>(),
+    };
+
+    group.count++;
+    group.total++;
+    group.lastSeen = seen;
+    group.message = exception.message;
+    group.stackTrace = exception.stackTrace.slice(0, MAX_STACK_FRAMES);
+    if (group.messages.size < MAX_DISTINCT_MESSAGES) group.messages.add(exception.message);
+    this.groups.set(key, group);
+    this.total++;
+  }
+
+  /**
+   * Report the exceptions since the last report, and start a new period
+   *
+   * @param final Whether this is the last report of the session
+   */
+  report(
+    final: boolean = false,
+    now: Date = new Date()
+  ): Omit<ExceptionSampleReportEvent, 'type' | 'timestamp'> {
+    const groups: ExceptionSampleGroup[] = [...this.groups.values()]
+      .filter((group) => group.count > 0)
+      .sort((a, b) => b.count - a.count || b.total - a.total)
+      .map((group) => ({
+        type: group.type,
+        message: group.message,
+        ...(group.messages.size > 1 && { distinctMessages: group.messages.size }),
+        location: group.location,
+        count: group.count,
+        total: group.total,
+        firstSeen: group.firstSeen,
+        lastSeen: group.lastSeen,
+        stackTrace: group.stackTrace,
+      }));
+
+    const report = {
+      periodStart: this.periodStart,
+      periodEnd: now.toISOString(),
+      count: groups.reduce((sum, group) => sum + group.count, 0),
+      total: this.total,
+      groups,
+      ...(final && { final: true }),
+    };
+
+    for (const group of this.groups.values()) group.count = 0;
+    this.periodStart = report.periodEnd;
+    return report;
+  }
+}
@@ -0,0 +1,199 @@
+/**
+ * Frame Arguments (--capture-args)
+ *
+ * Most adapters list a frame's parameters together with its other locals in one scope, so
+ * the parameter names come from the function's declaration: the nearest line above the
+ * frame's line that declares the frame's function, with its parameter list read in the
+ * style of the file's language. This is a line scan, not a parser.
+ */
+
+import * as path from 'node:path';
+
+/** How far above a frame's line to look for its function's declaration */
+const MAX_DECLARATION_DISTANCE = 500;
+
+/** How many lines a parameter list may span */
+const MAX_PARAMETER_LINES = 40;
+
+/** Languages that write parameters as "name: Type" */
+const NAME_FIRST_EXTENSIONS = new Set([
+  '.js',
+  '.jsx',
+  '.mjs',
+  '.cjs',
+  '.ts',
+  '.tsx',
+  '.mts',
+  '.cts',
+  '.py',
+  '.rs',
+  '.kt',
+  '.swift',
+  '.scala',
+]);
+
+/** Modifiers and keywords that can precede a parameter's name */
+const PARAMETER_KEYWORDS = new Set([
+  'mut',
+  'ref',
+  'out',
+  'in',
+  'params',
+  'this',
+  'final',
+  'const',
+  'public',
+  'private',
+  'protected',
+  'readonly',
+  'override',
+  'void',
+]);
+
+const IDENTIFIER = /^[A-Za-z_$][\w$]*$/;
+
+/**
+ * Where a function's parameter list starts, for languages that declare functions with a
+ * keyword; declarations elsewhere are the name followed by "(" (or "= function (", "= (")
+ */
+const DECLARATION_PATTERNS: Record<string, (name: string) => string> = {
+  '.py': (name) => `\\bdef\\s+${name}\\s*\\(`,
+  '.rs': (name) => `\\bfn\\s+${name}\\s*(<.*>)?\\s*\\(`,
+  '.go': (name) => `\\bfunc\\s*(\\([^)]*\\)\\s*)?${name}\\s*(\\[.*\\])?\\s*\\(`,
+};
+
+const DEFAULT_DECLARATION = (name: string) =>
+  `(^|[^\\w$.])${name}\\s*(<[^()]*>)?\\s*` +
+  `([:=]\\s*(async\\s*)?(function\\b\\s*\\*?\\s*)?)?\\(`;
+
+/**
+ * The bare function name in a stack frame's name (OrderService.reserve, app::orders::reserve,
+ * MyApp.dll!MyApp.Orders.Reserve(int id)), or undefined for anonymous frames
+ */
+export function shortFunctionName(frameName: string): string | undefined {
+  let name = frameName.replace(/^.*!/, '').replace(/\(.*$/, '');
+  // Generic arguments, innermost first
+  while (/<[^<>]*>/.test(name)) name = name.replace(/<[^<>]*>/g, '');
+  const last = name
+    .split(/::|\.|\s+/)
+    .filter(Boolean)
+    .pop();
+  return last && IDENTIFIER.test(last) ? last : undefined;
+}
+
+function escapeRegExp(text: string): string {
+  return text.replace(/[.*+?^${}()|[\]\\]/g, '\\$&');
+}
+
+/**
+ * Split a parameter list on its top-level commas
+ */
+function splitParameters(list: string): string[] {
+  const parts: string[] = [];
+  let depth = 0;
+  let current = '';
+  for (let index = 0; index < list.length; index++) {
+    const char = list[index];
+    if ('([{<'.includes(char)) depth++;
+    // The > of => isn't a bracket
+    else if (')]}'.includes(char) || (char === '>' && list[index - 1] !== '=')) {
+      depth = Math.max(0, depth - 1);
+    }
+    if (char === ',' && depth === 0) {
+      parts.push(current);
+      current = '';
+    } else {
+      current += char;
+    }
+  }
+  parts.push(current);
+  return parts.map((part) => part.trim()).filter(Boolean);
+}
+
+/**
+ * A parameter's name, or undefined for one that has none (destructuring, Python's / and *)
+ */
+function parameterName(parameter: string, extension: string): string | undefined {
+  const text = parameter
+    .replace(/^@[\w.]+(\([^)]*\))?\s*/, '')
+    .replace(/\[[^\]]*\]\s*/g, '')
+    .replace(/=(?!=|>).*$/, '')
+    .trim();
+  if (/^[{[(]/.test(text)) return undefined;
+
+  let words: string[];
+  if (NAME_FIRST_EXTENSIONS.has(extension)) {
+    words = text.split(':')[0].replace(/\?$/, '').split(/[\s*&]+|\.\.\./);
+  } else if (extension === '.go') {
+    words = text.split(/\s+/).slice(0, 1);
+  } else {
+    // Type first (C#, Java, C, C++): the name is the last word
+    words = text.replace(/[*&]/g, ' ').split(/\s+/).slice(-1);
+  }
+  const name = words.filter((word) => word && !PARAMETER_KEYWORDS.has(word)).pop();
+  return name && IDENTIFIER.test(name) ? name : undefined;
+}
+
+/**
+ * Whether what follows a parameter list makes it a declaration rather than a call
+ */
+function isDeclarationTail(tail: string): boolean {
+  const text = tail.replace(/\/\/.*$|#.*$/, '').trim();
+  return (
+    text === '' ||
+    /(\{|:|=>)$/.test(text) ||
+    text.includes('=>') ||
+    /^(->|:|where\b|throws\b)/.test(text)
+  );
+}
+
+/**
+ * Names of the parameters of the function a frame is in, read from its declaration above
+ * `line` (1-based); undefined if the declaration can't be found
+ */
+export function parameterNames(
+  lines: string[],
+  line: number,
+  functionName: string,
+  file: string
+): string[] | undefined {
+  const extension = path.extname(file).toLowerCase();
+  const declaration = new RegExp(
+    (DECLARATION_PATTERNS[extension] ?? DEFAULT_DECLARATION)(escapeRegExp(functionName))
+  );
+
+  const first = Math.max(0, line - 1 - MAX_DECLARATION_DISTANCE);
+  for (let index = Math.min(line, lines.length) - 1; index >= first; index--) {
+    const match = declaration.exec(lines[index]);
+    if (!match) continue;
+
+    // Read the parameter list to its closing parenthesis, across lines if need be
+    let text = lines[index].slice(match.index + match[0].length);
+    let depth = 1;
+    let list = '';
+    let tail: string | undefined;
+    for (let next = index + 1; tail === undefined; next++) {
+      for (let position = 0; position < text.length; position++) {
+        const char = text[position];
+        if (char === '(') depth++;
+        if (char === ')' && --depth === 0) {
+          tail = text.slice(position + 1);
+          break;
+        }
+        list += char;
+      }
+      if (tail !== undefined || next >= lines.length || next - index > MAX_PARAMETER_LINES) {
+        break;
+      }
+      list += ' ';
+      text = lines[next];
+    }
+    if (tail === undefined || !isDeclarationTail(tail)) continue;
+
+    const names = splitParameters(list.replace(/\/\*.*?\*\//g, ''))
+      .map((parameter) => parameterName(parameter, extension))
+      .filter((name): name is string => name !== undefined);
+    return [...new Set(names)];
+  }
+  return undefined;
+}
@@ -0,0 +1,165 @@
+/**
+ * Glob Breakpoints (-b "src/pricing*.rs:fn:calculate_*")
+ *
+ * When it isn't clear which of several similar modules is involved, a -b whose file is a glob
+ * places the breakpoint in every matching file: at a line (with any column, label, condition
+ * or hit count), or at a structural point (fn:/loop-body:, as --break-ast takes) in the
+ * functions whose names match. Patterns are expanded to concrete breakpoints before the
+ * session starts, and each expansion is reported in a breakpoints_expanded event.
+ */
+
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+import {
+  findSourceFiles,
+  hasGrammar,
+  parseStructuralQuery,
+  structuralBreakpointLines,
+} from './ast-breakpoints.js';
+
+export interface BreakpointExpansion {
+  /** The -b pattern as given */
+  pattern: string;
+  /** The breakpoints it expanded to (absolute path:line...) */
+  breakpoints: string[];
+}
+
+/** The file part of a -b spec: everything before :<line>, :fn: or :loop-body: */
+const FILE_PATTERN = /^(.+?):(?=\d|fn:|loop-body:)/;
+
+/** A line location with a label: line[:column]#label, then any condition or hit count */
+const LABEL_PATTERN = /^(\d+(?::\d+)?#[A-Za-z_][\w.-]*)(.*)$/;
+
+function hasWildcards(text: string): boolean {
+  return /[*?]/.test(text);
+}
+
+function toSlashes(file: string): string {
+  return file.replace(/\\/g, '/');
+}
+
+/**
+ * The file glob of a -b spec, or undefined for a breakpoint in one file
+ */
+export function breakpointGlob(spec: string): string | undefined {
+  const file = spec.match(FILE_PATTERN)?.[1];
+  return file !== undefined && hasWildcards(file) ? file : undefined;
+}
+
+/**
+ * Compile a path glob: ** matches any number of directories, * and ? match within one
+ */
+export function globToRegExp(glob: string): RegExp {
+  const pattern = toSlashes(glob);
+  let source = '';
+  for (let i = 0; i < pattern.length; i++) {
+    const char = pattern[i];
+    if (char === '*' && pattern[i + 1] === '*') {
+      const directories = pattern[i + 2] === '/';
+      source += directories ? '(?:[^/]*/)*' : '.*';
+      i += directories ? 2 : 1;
+    } else if (char === '*') {
+      source += '[^/]*';
+    } else if (char === '?') {
+      source += '[^/]';
+    } else {
+      source += char.replace(/[\\^$.|+()[\]{}]/g, '\\$&');
+    }
+  }
+  return new RegExp(`^${source}$`);
+}
+
+/**
+ * Files matching a glob (relative to cwd, or absolute), searched from the glob's directory
+ * without descending into dependency and build directories
+ */
+export function matchFiles(glob: string, cwd: string): string[] {
+  const segments = toSlashes(glob).split('/');
+  const literal = segments.slice(0, segments.findIndex(hasWildcards)).join('/');
+  const root = path.resolve(cwd, literal || '.');
+  const pattern = globToRegExp(toSlashes(path.resolve(cwd, glob)));
+  return findSourceFiles(root).filter((file) => pattern.test(toSlashes(file)));
+}
+
+/**
+ * A line location for the nth of several files, with its label (which must be unique)
+ * numbered
+ */
+function numberLabel(location: string, n: number): string {
+  const match = location.match(LABEL_PATTERN);
+  return match ? `${match[1]}-${n}${match[2]}` : location;
+}
+
+/**
+ * Breakpoints for a structural point in every matching file
+ *
+ * @throws Error if no matching file declares a matching function, or none has the point
+ */
+function structuralBreakpoints(
+  spec: string,
+  glob: string,
+  location: string,
+  files: string[]
+): string[] {
+  const query = parseStructuralQuery(location, '-b');
+  const breakpoints: string[] = [];
+  let functions = 0;
+
+  for (const file of files.filter(hasGrammar)) {
+    let content: string;
+    try {
+      content = fs.readFileSync(file, 'utf-8');
+    } catch {
+      continue;
+    }
+    const found = structuralBreakpointLines(content, query, file);
+    functions += found.functions;
+    breakpoints.push(...found.lines.map((line) => `${file}:${line}`));
+  }
+
+  if (functions === 0) {
+    throw new Error(`-b "${spec}": no function matching ${query.function} in ${glob}`);
+  }
+  if (breakpoints.length === 0) {
+    const missing = query.point === 'loop-body' ? 'loops' : 'return statements';
+    throw new Error(`-b "${spec}": the functions matching ${query.function} have no ${missing}`);
+  }
+  return breakpoints;
+}
+
+/**
+ * Expand the -b specs whose file is a glob, keeping the others as given
+ *
+ * @throws Error if a glob matches no files, or its structural point matches nothing
+ */
+export function expandGlobBreakpoints(
+  specs: string[],
+  options: { cwd?: string } = {}
+): { breakpoints: string[]; expansions: BreakpointExpansion[] } {
+  const cwd = options.cwd ?? process.cwd();
+  const breakpoints: string[] = [];
+  const expansions: BreakpointExpansion[] = [];
+
+  for (const spec of specs) {
+    const glob = breakpointGlob(spec);
+    if (glob === undefined) {
+      breakpoints.push(spec);
+      continue;
+    }
+
+    const files = matchFiles(glob, cwd);
+    if (files.length === 0) {
+      throw new Error(`-b "${spec}": no files match ${glob}`);
+    }
+    const location = spec.slice(glob.length + 1);
+    const expanded = /^(fn|loop-body):/.test(location)
+      ? structuralBreakpoints(spec, glob, location, files)
+      : files.map(
+          (file, i) => `${file}:${files.length > 1 ? numberLabel(location, i + 1) : location}`
+        );
+    breakpoints.push(...expanded);
+    expansions.push({ pattern: spec, breakpoints: expanded });
+  }
+
+  return { breakpoints, expansions };
+}
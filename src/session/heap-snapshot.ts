@@ -0,0 +1,214 @@
+/**
+ * Heap Snapshots (js-debug)
+ *
+ * Takes a V8 heap snapshot while stopped at a breakpoint through js-debug's profiling
+ * requests, and summarizes it so memory-leak triage can start from the NDJSON output:
+ * the objects with the largest retained size, and self size grouped by constructor.
+ */
+
+import * as fs from 'node:fs';
+import type { IDapClient } from '../dap/client-interface.js';
+import type { HeapSnapshotInfo, HeapRetainer, HeapConstructorSummary } from '../output/events.js';
+
+/** js-debug profile type that writes a heap snapshot */
+const HEAP_SNAPSHOT_PROFILE = 'memory';
+
+/** Node types that are bookkeeping rather than program objects */
+const SYNTHETIC_NODE_TYPES = new Set(['synthetic', 'hidden']);
+
+/** The parts of a .heapsnapshot file used for the summary */
+export interface HeapSnapshotJson {
+  snapshot: {
+    meta: {
+      node_fields: string[];
+      node_types: (string[] | string)[];
+      edge_fields: string[];
+      edge_types: (string[] | string)[];
+    };
+  };
+  nodes: number[];
+  edges: number[];
+  strings: string[];
+}
+
+/**
+ * Ask js-debug to write a heap snapshot of the paused target to a file
+ */
+export async function takeHeapSnapshot(client: IDapClient, file: string): Promise<void> {
+  await client.customRequest('startProfile', { type: HEAP_SNAPSHOT_PROFILE, file });
+  await client.customRequest('stopProfile');
+  if (!fs.existsSync(file)) {
+    throw new Error('js-debug did not write the heap snapshot (is this a js-debug session?)');
+  }
+}
+
+/**
+ * Summarize a heap snapshot: total size, top retainers and top constructors
+ */
+export function summarizeHeapSnapshot(
+  snapshot: HeapSnapshotJson,
+  limit: number = 10
+): Omit<HeapSnapshotInfo, 'file'> {
+  const { meta } = snapshot.snapshot;
+  const nodeFieldCount = meta.node_fields.length;
+  const edgeFieldCount = meta.edge_fields.length;
+  const typeOffset = meta.node_fields.indexOf('type');
+  const nameOffset = meta.node_fields.indexOf('name');
+  const idOffset = meta.node_fields.indexOf('id');
+  const selfSizeOffset = meta.node_fields.indexOf('self_size');
+  const edgeCountOffset = meta.node_fields.indexOf('edge_count');
+  const edgeTypeOffset = meta.edge_fields.indexOf('type');
+  const edgeToOffset = meta.edge_fields.indexOf('to_node');
+  const nodeTypes = meta.node_types[typeOffset] as string[];
+  const weakEdgeType = (meta.edge_types[edgeTypeOffset] as string[]).indexOf('weak');
+
+  const { nodes, edges, strings } = snapshot;
+  const nodeCount = nodes.length / nodeFieldCount;
+
+  // First edge (as an index into edges) of each node; edges are stored in node order
+  const firstEdge = new Uint32Array(nodeCount + 1);
+  for (let i = 0; i < nodeCount; i++) {
+    firstEdge[i + 1] = firstEdge[i] + nodes[i * nodeFieldCount + edgeCountOffset] * edgeFieldCount;
+  }
+  const forEachChild = (node: number, visit: (child: number) => void) => {
+    for (let e = firstEdge[node]; e < firstEdge[node + 1]; e += edgeFieldCount) {
+      if (edges[e + edgeTypeOffset] !== weakEdgeType) {
+        visit(edges[e + edgeToOffset] / nodeFieldCount);
+      }
+    }
+  };
+
+  // Post-order DFS from the synthetic root (node 0)
+  const UNVISITED = 0xffffffff;
+  const postOrderIndex = new Uint32Array(nodeCount).fill(UNVISITED);
+  const postOrder: number[] = [];
+  const visited = new Uint8Array(nodeCount);
+  const stack: number[] = [0];
+  const edgeCursor = new Uint32Array(nodeCount);
+  visited[0] = 1;
+  edgeCursor[0] = firstEdge[0];
+  while (stack.length > 0) {
+    const node = stack[stack.length - 1];
+    let pushed = false;
+    while (edgeCursor[node] < firstEdge[node + 1]) {
+      const e = edgeCursor[node];
+      edgeCursor[node] += edgeFieldCount;
+      if (edges[e + edgeTypeOffset] === weakEdgeType) continue;
+      const child = edges[e + edgeToOffset] / nodeFieldCount;
+      if (!visited[child]) {
+        visited[child] = 1;
+        edgeCursor[child] = firstEdge[child];
+        stack.push(child);
+        pushed = true;
+        break;
+      }
+    }
+    if (!pushed) {
+      stack.pop();
+      postOrderIndex[node] = postOrder.length;
+      postOrder.push(node);
+    }
+  }
+
+  // Predecessors of each reachable node
+  const predecessors: number[][] = Array.from({ length: nodeCount }, () => []);
+  for (const node of postOrder) {
+    forEachChild(node, (child) => {
+      predecessors[child].push(node);
+    });
+  }
+
+  // Immediate dominators (Cooper, Harvey & Kennedy's iterative algorithm, as in DevTools)
+  const root = 0;
+  const dominator = new Uint32Array(nodeCount).fill(UNVISITED);
+  dominator[root] = root;
+  const intersect = (a: number, b: number) => {
+    while (a !== b) {
+      while (postOrderIndex[a] < postOrderIndex[b]) a = dominator[a];
+      while (postOrderIndex[b] < postOrderIndex[a]) b = dominator[b];
+    }
+    return a;
+  };
+  let changed = true;
+  while (changed) {
+    changed = false;
+    for (let i = postOrder.length - 2; i >= 0; i--) {
+      const node = postOrder[i];
+      let idom = UNVISITED;
+      for (const predecessor of predecessors[node]) {
+        if (dominator[predecessor] === UNVISITED) continue;
+        idom = idom === UNVISITED ? predecessor : intersect(predecessor, idom);
+      }
+      if (idom !== UNVISITED && dominator[node] !== idom) {
+        dominator[node] = idom;
+        changed = true;
+      }
+    }
+  }
+
+  // Retained size: a node's self size plus everything it dominates (children come first
+  // in post-order)
+  const retained = new Float64Array(nodeCount);
+  let totalSize = 0;
+  for (const node of postOrder) {
+    const selfSize = nodes[node * nodeFieldCount + selfSizeOffset];
+    retained[node] += selfSize;
+    totalSize += selfSize;
+    if (node !== root) {
+      retained[dominator[node]] += retained[node];
+    }
+  }
+
+  const describe = (node: number) => {
+    const base = node * nodeFieldCount;
+    return {
+      type: nodeTypes[nodes[base + typeOffset]] ?? 'unknown',
+      name: strings[nodes[base + nameOffset]] ?? '',
+    };
+  };
+
+  const candidates: HeapRetainer[] = [];
+  const constructors = new Map<string, HeapConstructorSummary>();
+  for (const node of postOrder) {
+    const { type, name } = describe(node);
+    if (node === root || SYNTHETIC_NODE_TYPES.has(type)) continue;
+
+    const base = node * nodeFieldCount;
+    const selfSize = nodes[base + selfSizeOffset];
+    candidates.push({
+      name,
+      type,
+      id: nodes[base + idOffset],
+      selfSize,
+      retainedSize: retained[node],
+    });
+
+    const group = type === 'object' || type === 'native' ? name : `(${type})`;
+    const summary = constructors.get(group) ?? { name: group, count: 0, selfSize: 0 };
+    summary.count++;
+    summary.selfSize += selfSize;
+    constructors.set(group, summary);
+  }
+
+  return {
+    totalSize,
+    nodeCount,
+    topRetainers: candidates.sort((a, b) => b.retainedSize - a.retainedSize).slice(0, limit),
+    topConstructors: [...constructors.values()]
+      .sort((a, b) => b.selfSize - a.selfSize)
+      .slice(0, limit),
+  };
+}
+
+/**
+ * Read and summarize a .heapsnapshot file
+ */
+export function readHeapSnapshotSummary(
+  file: string,
+  limit?: number
+): Omit<HeapSnapshotInfo, 'file'> {
+  return summarizeHeapSnapshot(
+    JSON.parse(fs.readFileSync(file, 'utf-8')) as HeapSnapshotJson,
+    limit
+  );
+}
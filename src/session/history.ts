@@ -0,0 +1,145 @@
+/**
+ * Stop History (history: N)
+ *
+ * Keeps the locals and evaluations of the last N stops of a serve mode session so an
+ * expression evaluated at the current stop can refer to earlier ones: `$1` is the stop
+ * before the current one, `$2` the one before that. `$1.subtotal` alone returns the value
+ * captured then; inside a larger expression ("subtotal - $1.subtotal") each reference is
+ * replaced by that value before the adapter evaluates it, so it only works for primitive
+ * values.
+ */
+
+import type { EvaluationResult, SourceLocation, VariableValue } from '../output/events.js';
+
+export interface StopSnapshot {
+  type: string;
+  timestamp: string;
+  location: SourceLocation;
+  locals: Record<string, VariableValue>;
+  evaluations?: Record<string, EvaluationResult>;
+}
+
+/** A history reference: $N followed by .name and [index] accessors */
+const REFERENCE_PATTERN = /\$(\d+)((?:\.[A-Za-z_$][\w$]*|\[\d+\])*)/g;
+
+interface HistoryValue {
+  result: string;
+  type?: string;
+  /** The captured value, or undefined for an object */
+  literal?: string;
+}
+
+/**
+ * Split an accessor chain (".order.items[0]") into names
+ */
+function pathSegments(accessors: string): string[] {
+  return [...accessors.matchAll(/\.([A-Za-z_$][\w$]*)|\[(\d+)\]/g)].map((m) => m[1] ?? m[2]);
+}
+
+/**
+ * Find a value in a snapshot: the first name is a local or an --eval expression, the
+ * rest are properties or elements
+ */
+export function resolveSnapshotValue(
+  snapshot: StopSnapshot,
+  segments: string[]
+): HistoryValue | undefined {
+  const [name, ...rest] = segments;
+  if (name === undefined) return undefined;
+
+  let variable: VariableValue | undefined = snapshot.locals[name];
+  if (!variable) {
+    const evaluation = snapshot.evaluations?.[name];
+    if (!evaluation || evaluation.error || rest.length > 0) return undefined;
+    return { result: evaluation.result, type: evaluation.type, literal: evaluation.result };
+  }
+
+  for (const segment of rest) {
+    const children = variable.value;
+    if (!children || typeof children !== 'object') return undefined;
+    const members = children as Record<string, VariableValue>;
+    variable = members[segment] ?? members[`[${segment}]`];
+    if (!variable) return undefined;
+  }
+
+  const value = variable.value;
+  if (value !== null && typeof value === 'object') {
+    return { result: JSON.stringify(value), type: variable.type };
+  }
+  const literal = typeof value === 'string' ? JSON.stringify(value) : String(value);
+  return { result: typeof value === 'string' ? value : literal, type: variable.type, literal };
+}
+
+export class StopHistory {
+  private snapshots: StopSnapshot[] = [];
+  private limit: number;
+
+  constructor(limit: number) {
+    this.limit = limit;
+  }
+
+  /**
+   * Record a stop, dropping the oldest one when the history is full. The current stop is
+   * kept too, so `$1` counts back from it.
+   */
+  record(snapshot: StopSnapshot): void {
+    this.snapshots.push(snapshot);
+    if (this.snapshots.length > this.limit + 1) {
+      this.snapshots.shift();
+    }
+  }
+
+  /**
+   * The stop `back` stops before the current one
+   */
+  get(back: number): StopSnapshot | undefined {
+    return back > 0 ? this.snapshots[this.snapshots.length - 1 - back] : undefined;
+  }
+
+  /**
+   * Whether an expression refers to the history
+   */
+  static references(expression: string): boolean {
+    return new RegExp(REFERENCE_PATTERN.source).test(expression);
+  }
+
+  /**
+   * Resolve the history references in an expression. An expression that is a single
+   * reference resolves to its value; otherwise the expression comes back with the
+   * references replaced by their values.
+   *
+   * @throws Error when a reference isn't in the history or isn't a primitive value
+   */
+  expand(expression: string): { expression: string; value?: HistoryValue } {
+    const lookup = (index: string, accessors: string): HistoryValue => {
+      const back = parseInt(index, 10);
+      const snapshot = this.get(back);
+      if (!snapshot) {
+        throw new Error(
+          `$${back} is not in the history (${Math.max(0, this.snapshots.length - 1)} earlier ` +
+            'stops recorded)'
+        );
+      }
+      const value = resolveSnapshotValue(snapshot, pathSegments(accessors));
+      if (!value) {
+        throw new Error(`$${back}${accessors} was not captured at that stop`);
+      }
+      return value;
+    };
+
+    const whole = expression.trim().match(new RegExp(`^${REFERENCE_PATTERN.source}$`));
+    if (whole) {
+      return { expression, value: lookup(whole[1], whole[2]) };
+    }
+
+    return {
+      expression: expression.replace(REFERENCE_PATTERN, (reference, index, accessors) => {
+        const value = lookup(index, accessors);
+        if (value.literal === undefined) {
+          throw new Error(`${reference} is an object; refer to one of its fields`);
+        }
+        return value.literal;
+      }),
+    };
+  }
+}
@@ -0,0 +1,130 @@
+/**
+ * Breakpoint Hit Windows (--active-after / --active-until)
+ *
+ * A breakpoint deep in an iterative workload is usually only interesting for a few
+ * iterations. A hit window makes a labelled breakpoint active only while another (or the
+ * same) labelled breakpoint's hit count is within a range:
+ *
+ *   -b "src/loop.ts:10#iteration" -b "src/parse.ts:42#parse" \
+ *   --active-after "parse=iteration@10" --active-until "parse=iteration@20"
+ *
+ * stops at parse only from the 10th through the 20th time the loop passes iteration. A DAP
+ * hit condition can only count its own breakpoint's hits, so debug-run counts them itself
+ * and resumes the stops outside the window without capturing them.
+ */
+
+export interface HitWindowBound {
+  /** The label of the breakpoint whose hits are counted */
+  counter: string;
+  /** The hit of the counter breakpoint the window starts (after) or ends (until) at */
+  count: number;
+}
+
+export interface HitWindow {
+  /** The label of the breakpoint the window applies to */
+  label: string;
+  after?: HitWindowBound;
+  until?: HitWindowBound;
+}
+
+type Bound = 'after' | 'until';
+
+/** label=counter@count */
+const WINDOW_PATTERN = /^([^=@\s]+)=([^=@\s]+)@(\d+)$/;
+
+/**
+ * Combine --active-after and --active-until specs (label=counter@count) into one window
+ * per breakpoint label
+ *
+ * @throws Error for a malformed spec or a label given the same bound twice
+ */
+export function parseHitWindows(after: string[] = [], until: string[] = []): HitWindow[] {
+  const windows = new Map<string, HitWindow>();
+  const add = (bound: Bound, spec: string) => {
+    const match = spec.trim().match(WINDOW_PATTERN);
+    if (!match) {
+      throw new Error(
+        `Invalid --active-${bound} "${spec}": expected label=counter@count ` +
+          '(e.g., parse=iteration@10)'
+      );
+    }
+    const [, label, counter, countText] = match;
+    const count = parseInt(countText, 10);
+    // until@0 never opens: a breakpoint that is only there to count hits
+    if (bound === 'after' && count < 1) {
+      throw new Error(`Invalid --active-after "${spec}": the count must be 1 or more`);
+    }
+    const window: HitWindow = windows.get(label) ?? { label };
+    if (window[bound]) {
+      throw new Error(`--active-${bound} is given more than once for breakpoint "${label}"`);
+    }
+    window[bound] = { counter, count };
+    windows.set(label, window);
+  };
+  for (const spec of after) add('after', spec);
+  for (const spec of until) add('until', spec);
+  for (const { label, after, until } of windows.values()) {
+    if (after && until && after.counter === until.counter && until.count < after.count) {
+      throw new Error(`The hit window of breakpoint "${label}" ends before it starts`);
+    }
+  }
+  return [...windows.values()];
+}
+
+/**
+ * The breakpoint labels hit windows refer to, to check against the labelled breakpoints
+ */
+export function hitWindowLabels(windows: HitWindow[]): string[] {
+  const labels = new Set<string>();
+  for (const window of windows) {
+    labels.add(window.label);
+    if (window.after) labels.add(window.after.counter);
+    if (window.until) labels.add(window.until.counter);
+  }
+  return [...labels];
+}
+
+/**
+ * Counts hits per breakpoint label and decides whether a stop falls inside the windows
+ */
+export class HitWindowTracker {
+  private hits = new Map<string, number>();
+  private windows: Map<string, HitWindow>;
+
+  constructor(windows: HitWindow[]) {
+    this.windows = new Map(windows.map((window) => [window.label, window]));
+  }
+
+  /** Hits counted so far for a label */
+  hitCount(label: string): number {
+    return this.hits.get(label) ?? 0;
+  }
+
+  /**
+   * Count a breakpoint stop at these labels (every hit counts, captured or not). Returns
+   * whether the stop should be captured: a stop at an unlabelled breakpoint or one without
+   * a window always is, otherwise one of the labels has to be inside its window.
+   */
+  record(labels: string[]): boolean {
+    for (const label of labels) {
+      this.hits.set(label, this.hitCount(label) + 1);
+    }
+    if (labels.length === 0) return true;
+    return labels.some((label) => this.isActive(label));
+  }
+
+  /** Whether a label's breakpoint is inside its window with the hits counted so far */
+  isActive(label: string): boolean {
+    const window = this.windows.get(label);
+    if (!window) return true;
+    const { after, until } = window;
+    if (after && this.hitCount(after.counter) < after.count) return false;
+    if (until && this.hitCount(until.counter) > until.count) return false;
+    return true;
+  }
+
+  /** Start counting again (the program was restarted) */
+  reset(): void {
+    this.hits.clear();
+  }
+}
@@ -0,0 +1,70 @@
+/**
+ * Hot Code Reload
+ *
+ * Applies edited source files to a running debuggee without restarting it, by evaluating
+ * a reload expression in the debuggee. Each expression reports one flag per requested
+ * file ('1' reloaded, '0' not loaded by the program), which avoids having to parse the
+ * adapter's rendering of strings and arrays.
+ */
+
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+import type { HotReloadKind } from '../adapters/base.js';
+
+/**
+ * Resolve files to reload to the real absolute paths the debuggee loaded them from
+ */
+export function resolveReloadFiles(files: string[], cwd?: string): string[] {
+  return files.map((file) => {
+    const absolute = path.resolve(cwd ?? process.cwd(), file);
+    try {
+      return fs.realpathSync(absolute);
+    } catch {
+      return absolute;
+    }
+  });
+}
+
+/**
+ * Build the expression that reloads files in the debuggee
+ */
+export function buildReloadExpression(kind: HotReloadKind, files: string[]): string {
+  // JSON string literals are valid in both JavaScript and Python
+  const list = `[${files.map((file) => JSON.stringify(file)).join(', ')}]`;
+
+  switch (kind) {
+    case 'js-debug':
+      // require is only in scope inside a CommonJS frame; fall back to the main module's
+      return (
+        '(() => { ' +
+        "const load = typeof require === 'function' ? require : process.mainModule?.require; " +
+        'if (!load) return ""; ' +
+        `return ${list}.map((file) => { ` +
+        "if (!load.cache[file]) return '0'; " +
+        'delete load.cache[file]; load(file); ' +
+        "return '1'; }).join(''); })()"
+      );
+    case 'debugpy':
+      return (
+        "(lambda importlib, os, sys, files: ''.join(" +
+        "'1' if [importlib.reload(m) for m in list(sys.modules.values()) " +
+        "if os.path.realpath(getattr(m, '__file__', None) or '') == f] else '0' " +
+        "for f in files))(__import__('importlib'), __import__('os'), __import__('sys'), " +
+        `${list})`
+      );
+  }
+}
+
+/**
+ * Split files by the flags a reload expression returned
+ */
+export function parseReloadResult(
+  files: string[],
+  result: string
+): { reloaded: string[]; notLoaded: string[] } {
+  const flags = result.replace(/[^01]/g, '');
+  return {
+    reloaded: files.filter((_, i) => flags[i] === '1'),
+    notLoaded: files.filter((_, i) => flags[i] !== '1'),
+  };
+}
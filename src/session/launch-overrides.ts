@@ -0,0 +1,87 @@
+/**
+ * Launch Request Overrides (--launch-arg, --launch-json)
+ *
+ * An escape hatch for adapter options debug-run doesn't wrap: fields from --launch-json files
+ * and "key=value" --launch-arg pairs are deep-merged into the generated launch or attach
+ * request. Objects merge key by key; arrays and scalars replace what the adapter generated.
+ */
+
+import * as fs from 'node:fs';
+
+export type LaunchOverrides = Record<string, unknown>;
+
+function isPlainObject(value: unknown): value is Record<string, unknown> {
+  return typeof value === 'object' && value !== null && !Array.isArray(value);
+}
+
+/**
+ * Parse "sourceMaps=true" or "env.DEBUG=app:*" into a nested override. The value is read
+ * as JSON when it is valid JSON (true, 3, ["a"], {"x":1}) and as a string otherwise.
+ *
+ * @throws Error if there is no "=" or the key has an empty segment
+ */
+export function parseLaunchArg(spec: string): LaunchOverrides {
+  const equals = spec.indexOf('=');
+  const key = equals === -1 ? '' : spec.slice(0, equals).trim();
+  const keys = key.split('.');
+  if (!key || keys.some((segment) => !segment)) {
+    throw new Error(`Invalid --launch-arg "${spec}": expected key=value (e.g., sourceMaps=true)`);
+  }
+
+  const raw = spec.slice(equals + 1);
+  let value: unknown;
+  try {
+    value = JSON.parse(raw);
+  } catch {
+    value = raw;
+  }
+
+  // "env.DEBUG" nests as { env: { DEBUG: value } }
+  let override: LaunchOverrides = { [keys[keys.length - 1]]: value };
+  for (const segment of keys.slice(0, -1).reverse()) {
+    override = { [segment]: override };
+  }
+  return override;
+}
+
+/**
+ * Read a --launch-json file, which must hold a JSON object
+ *
+ * @throws Error if the file can't be read or isn't a JSON object
+ */
+export function loadLaunchJson(file: string): LaunchOverrides {
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(fs.readFileSync(file, 'utf-8'));
+  } catch (error) {
+    const reason = error instanceof Error ? error.message : String(error);
+    throw new Error(`Invalid --launch-json "${file}": ${reason}`);
+  }
+  if (!isPlainObject(parsed)) {
+    throw new Error(`Invalid --launch-json "${file}": expected a JSON object`);
+  }
+  return parsed;
+}
+
+/**
+ * Merge overrides into a launch or attach request without modifying either
+ */
+export function mergeLaunchOverrides<T extends object>(request: T, overrides: LaunchOverrides): T {
+  const merged: Record<string, unknown> = Object.fromEntries(Object.entries(request));
+  for (const [key, value] of Object.entries(overrides)) {
+    const current = merged[key];
+    merged[key] =
+      isPlainObject(current) && isPlainObject(value) ? mergeLaunchOverrides(current, value) : value;
+  }
+  return merged as T;
+}
+
+/**
+ * Combine --launch-json files (in order) and then --launch-arg pairs, later ones winning
+ */
+export function buildLaunchOverrides(files: string[], args: string[]): LaunchOverrides {
+  return [...files.map(loadLaunchJson), ...args.map(parseLaunchArg)].reduce(
+    (merged, overrides) => mergeLaunchOverrides(merged, overrides),
+    {}
+  );
+}
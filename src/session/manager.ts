@@ -10,11 +10,37 @@
  * - Manages session cleanup
  */
 
-import type { AdapterConfig } from '../adapters/base.js';
+import * as path from 'node:path';
+import type { AdapterConfig, AttachOptions } from '../adapters/base.js';
 import { DapClient } from '../dap/client.js';
 import { SocketDapClient } from '../dap/socket-client.js';
 import type { IDapClient } from '../dap/client-interface.js';
-import type { StoppedEventBody, ExitedEventBody, OutputEventBody } from '../dap/protocol.js';
+import type { CommandWrapper } from '../dap/terminal.js';
+import type {
+  Capabilities,
+  LaunchRequestArguments,
+  StoppedEventBody,
+  ExitedEventBody,
+  OutputEventBody,
+  ProcessEventBody,
+  GotoTargetsArguments,
+  GotoTargetsResponse,
+  BreakpointEventBody,
+  ModuleEventBody,
+  ModulesResponse,
+  ReadMemoryResponse,
+  DataBreakpoint,
+  DataBreakpointInfoArguments,
+  DataBreakpointInfoResponse,
+  SetDataBreakpointsResponse,
+  ThreadEventBody,
+  ProgressEndEventBody,
+  ProgressEventKind,
+  ProgressStartEventBody,
+  ProgressUpdateEventBody,
+  ProtocolMessage,
+  StackFrame,
+} from '../dap/protocol.js';
 import { OutputFormatter } from '../output/formatter.js';
 import type {
   SourceLocation,
@@ -28,22 +54,209 @@ import type {
   TraceCompletedEvent,
   TraceStopReason,
   AssertionFailedEvent,
+  HitPathEntry,
+  SessionEndEvent,
+  SourceSnippet,
+  InterruptAction,
+  InterruptedEvent,
+  InterruptSnapshotEvent,
+  CrashReportEvent,
+  CrashThread,
+  SignalInfo,
+  SignalReceivedEvent,
+  HeapSnapshotInfo,
+  StopCommandResult,
+  CodeReloadedEvent,
+  AssignmentResult,
+  FrameRestartedEvent,
+  ProcessRestartedEvent,
+  ExecutionMovedEvent,
+  MemoryChangedEvent,
+  MemoryChangeReason,
+  EvaluationResult,
+  TerminationInfo,
+  TerminationReason,
+  BreakpointsArmedEvent,
 } from '../output/events.js';
 import { BreakpointManager } from './breakpoints.js';
-import { VariableInspector } from './variables.js';
+import type { BreakpointExpansion } from './glob-breakpoints.js';
+import { VariableInspector, parseAssignment, type EvaluateContext } from './variables.js';
 import { flattenExceptionChainFromLocals } from './exceptions.js';
+import { parseFatalSignal } from './signals.js';
+import { ModuleTracker } from './modules.js';
+import { ThreadTimeline } from './threads.js';
+import { ProgressTracker } from './progress.js';
+import { RequestStats } from './request-stats.js';
+import { matchesStepFilter } from './step-filter.js';
+import { HitAggregator, type AggregateSpec } from './aggregate.js';
+import { ExceptionSampler } from './exception-sampler.js';
+import { mergeLaunchOverrides, type LaunchOverrides } from './launch-overrides.js';
+import { CaptureBudget } from './capture-budget.js';
+import { StopHistory } from './history.js';
+import {
+  MemoryWatchLog,
+  decodeMemory,
+  parseAddress,
+  type MemoryWatchSpec,
+} from './memory-watch.js';
+import { planExceptionBreakpoints } from './exception-breakpoints.js';
+import { SourceSnippetReader } from './source-snippets.js';
+import { parameterNames, shortFunctionName } from './frame-args.js';
+import { readHeapSnapshotSummary, takeHeapSnapshot } from './heap-snapshot.js';
+import { armTrigger, type SessionTrigger, type TriggerSource } from './trigger.js';
+import { OutputArming } from './output-arming.js';
+import { HitWindowTracker, type HitWindow } from './hit-windows.js';
+import { NativeCompanion, type MixedModeConfig } from './mixed-mode.js';
+import { prefetchSymbols, SourceLinkMapper, symbolCacheDir } from './dotnet-symbols.js';
+import {
+  capturedValue,
+  evaluationText,
+  fillStopCommand,
+  runStopCommand,
+  stopCommandPlaceholders,
+} from './stop-command.js';
+import { buildReloadExpression, parseReloadResult, resolveReloadFiles } from './hot-reload.js';
+import { logger, type Span } from '../util/logger.js';
+import {
+  applyResourceLimits,
+  describeResourceLimits,
+  type ResourceLimits,
+} from '../util/resource-limits.js';
+import { readCpuTimeMs } from '../util/process-stats.js';
+import { wslPathMapper, wslSpawn } from '../util/wsl.js';
+import { sandboxScope, sandboxSpawn, type SandboxPolicy } from '../util/sandbox.js';
+
+/**
+ * How Ctrl+C escalates. Each interrupt performs the next action in the mode's ladder;
+ * once teardown has been requested, interrupt() returns 'kill' so the caller force-exits.
+ * - snapshot: pause and capture a snapshot, then forward SIGINT, then tear down
+ * - forward: forward SIGINT to the debuggee, then tear down
+ * - stop: tear down immediately
+ */
+export const SIGINT_MODES = ['snapshot', 'forward', 'stop'] as const;
+export type SigintMode = (typeof SIGINT_MODES)[number];
+
+/**
+ * What to do after capturing a breakpoint hit, instead of continuing
+ * - restart-frame: re-run the stopped function from its start (with --set applied there),
+ *   capture the hit again, then continue
+ * - goto:<file>:<line>: continue from another line (DAP goto), skipping the code between
+ */
+export type OnHitAction =
+  | { action: 'restart-frame' }
+  | { action: 'goto'; file: string; line: number };
+
+/**
+ * Parse an --on-hit spec ("restart-frame" or "goto:src/main.rs:260")
+ */
+export function parseOnHitAction(spec: string): OnHitAction {
+  if (spec === 'restart-frame') {
+    return { action: 'restart-frame' };
+  }
+  const goto = spec.match(/^goto:(.+):(\d+)$/);
+  if (goto) {
+    return { action: 'goto', file: goto[1], line: parseInt(goto[2], 10) };
+  }
+  throw new Error(
+    `Invalid --on-hit "${spec}": expected restart-frame or goto:<file>:<line> ` +
+      '(e.g., goto:src/main.rs:260)'
+  );
+}
+
+/**
+ * What to do when the program exits, instead of ending the session
+ * - restart: relaunch it with DAP's restart request, keeping the adapter and breakpoints
+ */
+export const ON_EXIT_ACTIONS = ['restart'] as const;
+export type OnExitAction = (typeof ON_EXIT_ACTIONS)[number];
+
+const INTERRUPT_LADDERS: Record<SigintMode, InterruptAction[]> = {
+  snapshot: ['snapshot', 'forward', 'stop'],
+  forward: ['forward', 'stop'],
+  stop: ['stop'],
+};
+
+/** Stop reasons that mean the program crashed (unhandled exception or fatal signal) */
+const CRASH_STOP_REASONS = new Set(['exception', 'signal', 'signal-received']);
+
+/** End reasons where debug-run, not the program, decided the session was over */
+const TEARDOWN_REASONS: ReadonlySet<TerminationReason> = new Set([
+  'crashed',
+  'assertion_failed',
+  'max_hits',
+  'timeout',
+  'interrupted',
+  'stopped',
+]);
+
+/** How often to sample the debuggee's CPU time while it runs */
+const CPU_SAMPLE_INTERVAL_MS = 1000;
+
+/** Stop reasons for source and instruction (--break-addr) breakpoints */
+const BREAKPOINT_STOP_REASONS = new Set(['breakpoint', 'instruction breakpoint']);
+
+/** Lines of program output kept for crash reports */
+const CRASH_OUTPUT_LINES = 50;
+
+/** Lines of the adapter's own stderr/stdout attached to startup errors */
+const ADAPTER_OUTPUT_LINES = 20;
+
+/** Phases before the program runs; an adapter failing in these never got it started */
+const STARTUP_PHASES = new Set([
+  'connecting',
+  'initializing',
+  'configuring',
+  'attaching',
+  'launching',
+]);
 
 export interface SessionConfig {
   adapter: AdapterConfig;
   program?: string;
+  /** Module to run instead of a program file (python -m) */
+  module?: string;
+  /** Interpreter for the debuggee (e.g., a virtualenv's python) */
+  interpreter?: string;
+  /** Globs js-debug skips when stepping and leaves out of stacks (node; default: node_modules) */
+  skipFiles?: string[];
+  /** Only stop and step in user code (default: true for .NET, false for Python) */
+  justMyCode?: boolean;
+  /** Python: debug greenlet-based code (gevent, eventlet) */
+  gevent?: boolean;
+  /** Python: enable Django template debugging without a detected runserver */
+  django?: boolean;
+  /** .NET: require source files to match the PDB checksum (default: true) */
+  requireExactSource?: boolean;
+  /** .NET: symbol server URLs to load the PDBs the program's assemblies are missing from */
+  symbolServers?: string[];
+  /** .NET: download dependency sources through their PDBs' SourceLink maps */
+  sourceLink?: boolean;
+  /** Extra runtime arguments placed before the program (e.g., node --import tsx) */
+  runtimeArgs?: string[];
+  /** Runtime to launch instead of the program (e.g., npm for unexpanded npm scripts) */
+  runtimeExecutable?: string;
   args?: string[];
   cwd?: string;
   env?: Record<string, string>;
   breakpoints: string[];
+  /** The -b glob patterns the breakpoints include, reported after session_start */
+  breakpointExpansions?: BreakpointExpansion[];
   logpoints?: string[];
+  /** Instruction address breakpoints (e.g., 0x55aa113e) for native adapters */
+  instructionBreakpoints?: string[];
+  /** Native breakpoints for a second adapter attached to the program (--native-break) */
+  mixedMode?: MixedModeConfig;
   exceptionFilters?: string[];
   evaluations?: string[];
   assertions?: string[];
+  /** Resume without capturing a breakpoint stop when any of these evaluates truthy there */
+  continueIf?: string[];
+  /** Assignments (target=value) applied after capturing each breakpoint hit */
+  assignments?: string[];
+  /** Action after capturing each breakpoint hit (assignments apply when the frame restarts) */
+  onHit?: OnHitAction;
+  /** Action when the program exits (the session otherwise ends with it) */
+  onExit?: OnExitAction;
   timeout?: number;
   captureLocals?: boolean;
   /** Number of steps to execute after hitting a breakpoint */
@@ -54,10 +267,21 @@ export interface SessionConfig {
   attach?: boolean;
   /** Process ID to attach to */
   pid?: number;
+  /** Inspector port to attach to (node --discover); pid is then only reported */
+  attachPort?: number;
   /** Enable trace mode - step through code after breakpoint hit */
   trace?: boolean;
   /** Use stepIn instead of stepOver in trace mode */
   traceInto?: boolean;
+  /** Namespaces (or types) to step back out of when a step lands in them */
+  stepFilters?: string[];
+  /** Fold these expressions into statistics at each breakpoint hit instead of emitting hits */
+  aggregates?: AggregateSpec[];
+  /**
+   * Record exception stops without capturing locals, resume straight away, and report them
+   * every this many ms (--sample-exceptions)
+   */
+  sampleExceptions?: number;
   /** Maximum steps in trace mode before stopping (default: 500) */
   traceLimit?: number;
   /** Stop trace when this expression evaluates to truthy */
@@ -66,6 +290,50 @@ export interface SessionConfig {
   diffVars?: boolean;
   /** Step once before evaluating expressions (for variables assigned on breakpoint line) */
   evalAfterStep?: boolean;
+  /** Evaluate --eval and --assert expressions without side effects (adapter.safeEvaluation) */
+  evalSafe?: boolean;
+  /** DAP context for --eval expressions without a context prefix (default: watch) */
+  evalContext?: EvaluateContext;
+  /** Levels to expand structured --eval results (default: 0) */
+  evalExpand?: number;
+  /** Tag captured locals with the frame, scope and reference path they came from */
+  provenance?: boolean;
+  /** Time in ms each stop's locals and --eval results may take to capture */
+  captureBudget?: number;
+  /** End the session after this many breakpoint hits */
+  maxHits?: number;
+  /** Resume the breakpoint hits before this one without capturing them (--skip-to-hit) */
+  skipToHit?: number;
+  /** Labelled breakpoints captured only within a range of hits (--active-after/--active-until) */
+  hitWindows?: HitWindow[];
+  /** Set breakpoints only once the program prints a line matching this (--arm-on-output) */
+  armOnOutput?: RegExp;
+  /** Lines of source to include before/after each stop location */
+  includeSource?: number;
+  /** Attach each stack frame's argument values to it */
+  captureArgs?: boolean;
+  /** Write a heap snapshot to this directory at each breakpoint hit (js-debug only) */
+  heapSnapshotDir?: string;
+  /** Command (as words, with {name} placeholders) to run at each breakpoint hit */
+  onStopExec?: string[];
+  /** Hold the launched program at entry until this file appears or signal arrives */
+  trigger?: SessionTrigger;
+  /** Stops kept for evaluate() to refer back to with $1, $2, ... (serve mode) */
+  history?: number;
+  /** Stay paused after each breakpoint hit until continue() is called (serve mode) */
+  pauseOnHit?: boolean;
+  /** How Ctrl+C escalates (default: snapshot) */
+  sigintMode?: SigintMode;
+  /** Memory/CPU/file descriptor limits for the launched program */
+  resourceLimits?: ResourceLimits;
+  /** Keep running without resourceLimits when they can't be applied, instead of failing */
+  limitsBestEffort?: boolean;
+  /** Run until the program crashes, then emit a crash_report and end the session */
+  untilCrash?: boolean;
+  /** Run the adapter inside this WSL distribution, translating paths (--wsl) */
+  wslDistro?: string;
+  /** Run the launched program with reduced privileges (--sandbox) */
+  sandbox?: SandboxPolicy;
   // Token efficiency options
   /** Fully expand service-like types instead of compact form (default: false) */
   expandServices?: boolean;
@@ -81,6 +349,45 @@ export interface SessionConfig {
   // Source map options
   /** Source map path overrides for TypeScript/bundled code */
   sourceMapOverrides?: Record<string, string>;
+  /** Fields deep-merged into the generated launch/attach request (--launch-arg, --launch-json) */
+  launchOverrides?: LaunchOverrides;
+  /** List the loaded modules and their symbol status in session_end */
+  captureModules?: boolean;
+  /** Time DAP round trips per command and report them in session_end */
+  requestStats?: boolean;
+  /** Memory regions sampled at every stop and, with hardware watchpoints, on every write */
+  watchMemory?: MemoryWatchSpec[];
+  /** Receives the raw adapter traffic and output (--artifacts) */
+  adapterTraffic?: AdapterTrafficObserver;
+  /** Receives DAP messages to place the program's pauses on the session timeline (--timeline) */
+  timeline?: ProtocolObserver;
+  /** Shows what the session is waiting on (the CLI's live status line) */
+  statusLine?: StatusObserver;
+}
+
+/**
+ * Receives every DAP message
+ */
+export interface ProtocolObserver {
+  /** session is set for messages on a child debug session (js-debug) */
+  protocolMessage(direction: 'send' | 'receive', message: ProtocolMessage, session?: string): void;
+}
+
+/**
+ * Receives every DAP message and the adapter's own stderr/stdout
+ */
+export interface AdapterTrafficObserver extends ProtocolObserver {
+  adapterStderr(data: string): void;
+  /** Only for adapters whose stdout isn't the DAP stream (socket adapters like js-debug) */
+  adapterStdout(data: string): void;
+}
+
+/**
+ * Receives a one-line description of the current phase or adapter progress
+ */
+export interface StatusObserver {
+  update(text: string): void;
+  clear(): void;
 }
 
 type SessionState =
@@ -95,6 +402,7 @@ type SessionState =
 export class DebugSession {
   private config: SessionConfig;
   private client: IDapClient | null = null;
+  private capabilities: Capabilities = {};
   private formatter: OutputFormatter;
   private breakpointManager: BreakpointManager | null = null;
   private variableInspector: VariableInspector | null = null;
@@ -105,6 +413,10 @@ export class DebugSession {
   private breakpointsHit: number = 0;
   private exceptionsCaught: number = 0;
   private stepsExecuted: number = 0;
+  /** Breakpoint stops resumed by a --continue-if rule, --skip-to-hit or a hit window */
+  private breakpointsSkipped: number = 0;
+  /** Breakpoint hits resumed by --skip-to-hit, which still count in the hit order */
+  private hitsFastForwarded: number = 0;
 
   /** Remaining steps to execute after the current breakpoint */
   private remainingSteps: number = 0;
@@ -117,6 +429,10 @@ export class DebugSession {
   private traceStepCount: number = 0;
   /** Path of locations visited during trace */
   private tracePath: SourceLocation[] = [];
+  /** Ordered record of every breakpoint hit this session */
+  private hitPath: HitPathEntry[] = [];
+  /** Cached source reader for --include-source */
+  private sourceReader: SourceSnippetReader = new SourceSnippetReader();
   /** Initial stack depth when trace started (to detect function return) */
   private traceInitialStackDepth: number = 0;
   /** Previous locals for variable diffing (only used when diffVars is enabled) */
@@ -138,10 +454,145 @@ export class DebugSession {
   private sessionError: Error | null = null;
   /** Whether session_end event has been emitted (to prevent duplicate emissions) */
   private sessionEndEmitted: boolean = false;
+  /** Thread and top frame of the most recent stop */
+  private lastStop: { threadId: number; frameId?: number } | null = null;
+  /** Set while paused at a breakpoint waiting for continue() (pauseOnHit) */
+  private paused: { threadId: number; frameId?: number } | null = null;
+  /** Number of interrupts received so far (position in the sigint ladder) */
+  private interruptCount: number = 0;
+  /** Set once an interrupt has asked for teardown; further interrupts force-exit */
+  private teardownRequested: boolean = false;
+  /** Set when we paused the debuggee for an interrupt snapshot */
+  private snapshotPending: boolean = false;
+  /** Debuggee PID reported by the adapter's process event */
+  private debuggeePid: number | undefined;
+  /** PIDs that resource limits were applied to */
+  private limitedPids: Set<number> = new Set();
+  /** Recent program output lines (untilCrash) */
+  private recentOutput: string[] = [];
+  /** Recent lines the adapter wrote to its own stderr/stdout, for startup errors */
+  private adapterOutput: string[] = [];
+  /** Set once a crash report has been emitted */
+  private crashed: boolean = false;
+  /** Why the session ended (set when session_end is emitted, or earlier by interrupt()) */
+  private endReason: TerminationReason | null = null;
+  /** Signal named by the stop that crashed the program */
+  private crashSignal: string | undefined;
+  /** Last CPU time sampled from the debuggee */
+  private cpuTimeMs: number | undefined;
+  private cpuSampler: NodeJS.Timeout | null = null;
+  /** Set while a frame restarted by reloadCode() runs to its next stop */
+  private frameRestartPending: boolean = false;
+  /**
+   * Frame restarted by --on-hit restart-frame: restarting until it stops at its start,
+   * then cleared by the next breakpoint hit
+   */
+  private rerun: { function?: string; depth: number; restarting: boolean } | null = null;
+  /** Label of the last breakpoint hit, carried by its stop and the steps or trace it starts */
+  private breakpointLabel?: string;
+  /** Launch arguments, sent again with restart requests */
+  private launchArgs: LaunchRequestArguments | null = null;
+  /** Times the program has been relaunched this session */
+  private restarts: number = 0;
+  /** Modules reported by the adapter (captureModules) */
+  private modules: ModuleTracker = new ModuleTracker();
+  /** Set once the modules request has been sent (at the first stop) */
+  private modulesRequested: boolean = false;
+  /** Thread starts, exits and names */
+  private threadTimeline: ThreadTimeline = new ThreadTimeline();
+  /** Recent stop snapshots for $N references in evaluate() */
+  private history: StopHistory | null = null;
+  /** Running statistics of the --aggregate expressions */
+  private aggregator: HitAggregator | null = null;
+  /** Exception stops grouped for the --sample-exceptions reports */
+  private exceptionSampler: ExceptionSampler | null = null;
+  private sampleReporter: NodeJS.Timeout | null = null;
+  /** Samples and changes of the --watch-memory regions */
+  private memoryWatch: MemoryWatchLog | null = null;
+  /** The armed --trigger-file/--trigger-signal, until it fires or the session ends */
+  private trigger: { fired: Promise<TriggerSource | null>; cancel: () => void } | null = null;
+  /** Waits for the --arm-on-output line before breakpoints go to the adapter */
+  private arming: OutputArming | null = null;
+  /** Hit counts per breakpoint label for --active-after/--active-until */
+  private hitWindows: HitWindowTracker | null = null;
+  /** The adapter takes breakpoints now (the configuring phase has been reached) */
+  private breakpointsConfigurable: boolean = false;
+  /** lldb attached next to the adapter for --native-break, once the program is at entry */
+  private native: NativeCompanion | null = null;
+  /** --source-link for an adapter that leaves SourceLink to debug-run (netcoredbg) */
+  private sourceLink: SourceLinkMapper | null = null;
+  /** Hardware watchpoints set for --watch-memory: expression -> address and data ID */
+  private memoryWatchpoints = new Map<string, { address: string; dataId: string | null }>();
+  /** Set after --on-hit goto moves execution, until the adapter's goto stop */
+  private gotoPending: boolean = false;
+  /** Set once an adapter that can't perform the --on-hit action has been reported */
+  private onHitUnsupportedReported: boolean = false;
+  /** Diagnostic span covering the whole session */
+  private sessionSpan: Span | null = null;
+  /** Diagnostic span for the current setup phase (connecting, initializing, ...) */
+  private phaseSpan: Span | null = null;
+  /** The current phase and when it began */
+  private phase: { name: string; startedAt: number } | null = null;
+  /** Time spent in each finished phase, in ms */
+  private phaseDurations: Record<string, number> = {};
+  /** Progress reports from the adapter */
+  private progress: ProgressTracker = new ProgressTracker();
+  /** DAP round trips per command (--request-stats) */
+  private requestStats?: RequestStats;
 
   constructor(config: SessionConfig, formatter?: OutputFormatter) {
     this.config = config;
     this.formatter = formatter ?? new OutputFormatter();
+    if (config.watchMemory?.length) {
+      this.memoryWatch = new MemoryWatchLog(config.watchMemory);
+    }
+    if (config.aggregates?.length) {
+      this.aggregator = new HitAggregator(config.aggregates);
+    }
+    if (config.sampleExceptions) {
+      this.exceptionSampler = new ExceptionSampler();
+    }
+    if (config.requestStats) {
+      this.requestStats = new RequestStats();
+    }
+    if (config.armOnOutput) {
+      this.arming = new OutputArming(config.armOnOutput);
+    }
+    if (config.hitWindows?.length) {
+      this.hitWindows = new HitWindowTracker(config.hitWindows);
+    }
+    if (config.mixedMode) {
+      this.native = new NativeCompanion(
+        {
+          ...config.mixedMode,
+          cwd: config.cwd,
+          timeout: config.timeout,
+          captureLocals: config.captureLocals,
+        },
+        this.formatter,
+        (event) => this.handleNativeHit(event)
+      );
+    }
+    if (config.history && config.history > 0) {
+      const history = new StopHistory(config.history);
+      this.history = history;
+      this.formatter.onEvent((event) => {
+        if (
+          event.type === 'breakpoint_hit' ||
+          event.type === 'exception_thrown' ||
+          event.type === 'step_completed' ||
+          event.type === 'interrupt_snapshot'
+        ) {
+          history.record({
+            type: event.type,
+            timestamp: event.timestamp,
+            location: event.location,
+            locals: event.locals,
+            evaluations: 'evaluations' in event ? event.evaluations : undefined,
+          });
+        }
+      });
+    }
   }
 
   /**
@@ -151,18 +602,47 @@ export class DebugSession {
     this.startTime = Date.now();
     this.sessionError = null;
     this.sessionEndEmitted = false;
+    this.hitPath = [];
+    this.recentOutput = [];
+    this.adapterOutput = [];
+    this.crashed = false;
+    this.endReason = null;
+    this.crashSignal = undefined;
+    this.cpuTimeMs = undefined;
+    this.frameRestartPending = false;
+    this.rerun = null;
+    this.restarts = 0;
+    this.modules.clear();
+    this.modulesRequested = false;
+    this.threadTimeline.clear();
+    this.memoryWatchpoints.clear();
+    this.phase = null;
+    this.phaseDurations = {};
+    this.progress.clear();
+    this.sessionSpan = logger.span('session', {
+      adapter: this.config.adapter.name,
+      program: this.config.program,
+      pid: this.config.pid,
+    });
 
     // Emit session start
-    if (this.config.attach && this.config.pid) {
-      this.formatter.sessionStartAttach(this.config.adapter.name, this.config.pid);
+    if (this.attachTarget()) {
+      this.formatter.sessionStartAttach(
+        this.config.adapter.name,
+        this.config.pid,
+        this.config.attachPort
+      );
     } else {
       this.formatter.sessionStart(
         this.config.adapter.name,
-        this.config.program!,
+        this.describeProgram(),
         this.config.args,
         this.config.cwd
       );
     }
+    for (const expansion of this.config.breakpointExpansions ?? []) {
+      this.formatter.emit(this.formatter.createEvent('breakpoints_expanded', expansion));
+    }
 
     // Create promise to track session completion
     // Note: This promise always resolves (never rejects) to avoid unhandled promise rejections
@@ -177,6 +657,20 @@ export class DebugSession {
         this.handleTimeout();
       }, this.config.timeout);
     }
+    this.cpuSampler = setInterval(() => this.sampleCpuTime(), CPU_SAMPLE_INTERVAL_MS);
+    this.cpuSampler.unref();
+    // Listen from the start: a trigger sent before the program reaches entry still counts
+    if (this.config.trigger) {
+      this.trigger = armTrigger(this.config.trigger);
+    }
+    if (this.exceptionSampler) {
+      const sampler = this.exceptionSampler;
+      this.sampleReporter = setInterval(() => {
+        const report = sampler.report();
+        this.formatter.emit(this.formatter.createEvent('exception_sample_report', report));
+      }, this.config.sampleExceptions);
+      this.sampleReporter.unref();
+    }
 
     try {
       // Race start() against sessionPromise to handle timeout during startup.
@@ -198,39 +692,326 @@ export class DebugSession {
     } catch (error) {
       // Only emit error if it wasn't already emitted by endSessionWithError
       if (error !== this.sessionError) {
+        const message = error instanceof Error ? error.message : String(error);
         this.formatter.error(
           'Session failed',
-          error instanceof Error ? error.message : String(error)
+          this.isStarting() ? this.withAdapterOutput(message) : message
         );
       }
+      this.emitSessionEnd('error');
       throw error;
     } finally {
       await this.cleanup();
     }
   }
 
+  /**
+   * End the session early (e.g., on request in serve mode). run() then cleans up,
+   * terminating the debuggee unless attached.
+   */
+  stop(): void {
+    this.paused = null;
+    this.endSession('stopped');
+  }
+
+  /**
+   * Handle Ctrl+C (SIGINT) or SIGTERM by performing the next action in the interrupt
+   * ladder (see SIGINT_MODES). SIGTERM always tears down. Returns the action taken, or
+   * 'kill' once teardown is already underway and the caller should force-exit.
+   */
+  async interrupt(signal: 'SIGINT' | 'SIGTERM' = 'SIGINT'): Promise<InterruptAction | 'kill'> {
+    if (this.teardownRequested || this.sessionEndEmitted) {
+      return 'kill';
+    }
+    const ladder: InterruptAction[] =
+      signal === 'SIGINT' ? INTERRUPT_LADDERS[this.config.sigintMode ?? 'snapshot'] : ['stop'];
+    let action = ladder[Math.min(this.interruptCount, ladder.length - 1)];
+
+    // Fall through to the next action when this one isn't possible right now
+    if (action === 'snapshot' && !(await this.requestSnapshot())) {
+      action = 'forward';
+    }
+    const pid = this.debuggeePid ?? (this.config.attach ? this.config.pid : undefined);
+    if (action === 'forward' && !(pid && this.forwardSignal(pid))) {
+      logger.warn('session', 'cannot forward SIGINT to the debuggee, ending session');
+      action = 'stop';
+    }
+    this.interruptCount = ladder.indexOf(action) + 1;
+
+    this.emitInterrupted(signal, action, action === 'forward' ? pid : undefined);
+    if (action === 'stop') {
+      this.teardownRequested = true;
+      this.endReason = 'interrupted';
+      this.stop();
+    }
+    return action;
+  }
+
+  /**
+   * Whether the session is paused at a breakpoint waiting for continue() (pauseOnHit)
+   */
+  isPaused(): boolean {
+    return this.paused !== null;
+  }
+
+  /**
+   * Resume a session paused by pauseOnHit
+   */
+  async continue(): Promise<void> {
+    if (!this.paused) {
+      throw new Error('Session is not paused');
+    }
+    const { threadId } = this.paused;
+    this.paused = null;
+    await this.client!.continue({ threadId });
+    this.state = 'running';
+  }
+
+  /**
+   * Evaluate an expression while paused (defaults to the top frame of the stopped thread)
+   */
+  async evaluate(
+    expression: string,
+    frameId?: number
+  ): Promise<{ result: string; type?: string; variablesReference: number }> {
+    if (!this.paused) {
+      throw new Error('Session is not paused');
+    }
+    // $1.subtotal: a value from an earlier stop
+    if (this.history && StopHistory.references(expression)) {
+      const expanded = this.history.expand(expression);
+      if (expanded.value) {
+        return { result: expanded.value.result, type: expanded.value.type, variablesReference: 0 };
+      }
+      expression = expanded.expression;
+    }
+    const response = await this.client!.evaluate({
+      expression,
+      frameId: frameId ?? this.paused.frameId,
+      context: 'repl',
+    });
+    return {
+      result: response.result,
+      type: response.type,
+      variablesReference: response.variablesReference,
+    };
+  }
+
+  /**
+   * Relaunch the program with DAP's restart request. The adapter stays up and keeps its
+   * breakpoints, and --eval/--assert carry over as session config.
+   */
+  async restart(reason: ProcessRestartedEvent['reason'] = 'requested'): Promise<void> {
+    if (!this.client || this.sessionEndEmitted) {
+      throw new Error('Session is not running');
+    }
+    if (this.config.attach) {
+      throw new Error("An attached program can't be relaunched");
+    }
+    if (!this.capabilities.supportsRestartRequest) {
+      throw new Error(`The ${this.config.adapter.name} adapter doesn't support restart requests`);
+    }
+
+    // Stepping, tracing and pauses belong to the previous run
+    this.paused = null;
+    this.rerun = null;
+    this.isStepping = false;
+    this.isTracing = false;
+    this.isEvalAfterStep = false;
+    this.evalAfterStepData = null;
+    this.exitCode = null;
+    // The relaunched program stops at entry again: hold it for the next trigger
+    if (this.config.trigger) {
+      this.trigger?.cancel();
+      this.trigger = armTrigger(this.config.trigger);
+    }
+    // ...where lldb attaches to the new process again
+    await this.native?.detach();
+    // ...and runs its warm-up without breakpoints until it prints the --arm-on-output line
+    if (this.arming?.armed) {
+      this.arming.disarm();
+      await this.breakpointManager!.clearAllBreakpoints();
+    }
+    // ...and counts hit windows from the start again
+    this.hitWindows?.reset();
+
+    await this.client.customRequest('restart', { arguments: this.launchArgs ?? undefined });
+    this.restarts++;
+    this.state = 'running';
+    logger.info('session', 'program restarted', { restart: this.restarts, reason });
+    const event: ProcessRestartedEvent = {
+      type: 'process_restarted',
+      timestamp: new Date().toISOString(),
+      restart: this.restarts,
+      reason,
+    };
+    this.formatter.emit(event);
+  }
+
+  /**
+   * Apply edited source files to the running debuggee without restarting it, then
+   * re-verify the breakpoints in those files. With restartFrame, a session paused at a
+   * breakpoint re-enters the current function so it runs again with the new code.
+   */
+  async reloadCode(
+    files: string[],
+    options: { restartFrame?: boolean } = {}
+  ): Promise<CodeReloadedEvent> {
+    const mechanism = this.config.adapter.hotReload;
+    if (!mechanism) {
+      throw new Error(`The ${this.config.adapter.name} adapter doesn't support hot code reload`);
+    }
+    if (!this.client || this.sessionEndEmitted) {
+      throw new Error('Session is not running');
+    }
+    const frameId = this.paused?.frameId;
+    if (mechanism === 'debugpy' && frameId === undefined) {
+      throw new Error('Python modules can only be reloaded while paused (start with pauseOnHit)');
+    }
+    if (options.restartFrame && frameId === undefined) {
+      throw new Error('restartFrame needs a session paused at a breakpoint');
+    }
+    if (options.restartFrame && !this.capabilities.supportsRestartFrame) {
+      throw new Error(`The ${this.config.adapter.name} adapter can't restart frames`);
+    }
+
+    const resolved = resolveReloadFiles(files, this.config.cwd);
+    const span = logger.span('hot_reload', { mechanism, files: resolved.length });
+    let result: { reloaded: string[]; notLoaded: string[] };
+    try {
+      const response = await this.client.evaluate({
+        expression: buildReloadExpression(mechanism, resolved),
+        frameId,
+        context: 'repl',
+      });
+      result = parseReloadResult(resolved, response.result);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      span.end({ error: message });
+      throw new Error(`Reload failed: ${message}`);
+    }
+
+    let frameRestarted = false;
+    if (options.restartFrame && frameId !== undefined) {
+      this.frameRestartPending = true;
+      await this.client.customRequest('restartFrame', { frameId });
+      frameRestarted = true;
+    }
+
+    // Breakpoints are keyed by resolved (not real) path
+    const breakpointFiles = new Set([
+      ...resolved,
+      ...files.map((file) => path.resolve(this.config.cwd ?? process.cwd(), file)),
+    ]);
+    // Breakpoints not armed yet aren't in the adapter to re-verify
+    const reverified =
+      this.arming && !this.arming.armed
+        ? []
+        : await this.breakpointManager!.reverifyBreakpoints(breakpointFiles);
+    const breakpoints = reverified.map((bp) => ({
+      id: bp.id,
+      file: bp.file,
+      line: bp.line,
+      verified: bp.verified,
+      ...(bp.message && { message: bp.message }),
+    }));
+    span.end({ reloaded: result.reloaded.length, frameRestarted });
+
+    const event: CodeReloadedEvent = {
+      type: 'code_reloaded',
+      timestamp: new Date().toISOString(),
+      mechanism,
+      ...result,
+      frameRestarted,
+      breakpoints,
+    };
+    this.formatter.emit(event);
+    return event;
+  }
+
+  /**
+   * The adapter's generated launch/attach request with --launch-arg/--launch-json applied
+   */
+  private withLaunchOverrides<T extends object>(request: T): T {
+    const overrides = this.config.launchOverrides;
+    if (!overrides) return request;
+    logger.debug('session', 'applying launch request overrides', { overrides });
+    return mergeLaunchOverrides(request, overrides);
+  }
+
+  /**
+   * The adapter's command line and environment, run through wsl.exe with --wsl and in the
+   * sandbox when --sandbox can't confine the program alone
+   */
+  private adapterProcess(): { command: string; args: string[]; env: Record<string, string> } {
+    const { command, args = [] } = this.config.adapter;
+    const env = { ...this.config.adapter.env, ...this.config.env };
+    if (this.sandboxScope() === 'adapter') {
+      return { ...sandboxSpawn(command, args, this.config.sandbox!, env), env };
+    }
+    if (this.config.wslDistro === undefined) return { command, args, env };
+    return wslSpawn(command, args, env, this.config.wslDistro);
+  }
+
+  /**
+   * What --sandbox confines: the program alone (started through runInTerminal), or the
+   * adapter and so the program it starts
+   */
+  private sandboxScope(): 'program' | 'adapter' | undefined {
+    const { sandbox } = this.config;
+    if (!sandbox || this.config.attach) return undefined;
+    return sandboxScope(this.config.adapter, sandbox);
+  }
+
   private async start(): Promise<void> {
     // Create and connect DAP client
     this.state = 'connecting';
-
+    this.enterPhase('connecting');
+
+    const { command, args, env } = this.adapterProcess();
+    const { sandbox } = this.config;
+    const scope = this.sandboxScope();
+    let terminalWrapper: CommandWrapper | undefined;
+    if (sandbox && scope) {
+      // Commands the adapter asks debug-run to run are sandboxed in either scope
+      terminalWrapper = (command, args, env) => sandboxSpawn(command, args, sandbox, env);
+      logger.info('session', 'sandboxing the program', {
+        scope,
+        writable: sandbox.writable,
+        hidden: sandbox.hidden,
+        network: sandbox.network,
+      });
+    }
+    this.sourceLink =
+      this.config.sourceLink && this.config.adapter.symbolSupport === 'prefetch'
+        ? new SourceLinkMapper()
+        : null;
+    const pathMapper =
+      this.config.wslDistro !== undefined
+        ? wslPathMapper(this.config.wslDistro)
+        : (this.sourceLink ?? undefined);
     if (this.config.adapter.transport === 'socket' && this.config.adapter.socketPort) {
       // Use socket-based client for adapters like js-debug
       this.client = new SocketDapClient({
-        command: this.config.adapter.command,
-        args: this.config.adapter.args,
+        command,
+        args,
         cwd: this.config.cwd,
-        env: { ...this.config.adapter.env, ...this.config.env },
+        env,
         port: this.config.adapter.socketPort,
         timeout: this.config.timeout,
+        pathMapper,
+        terminalWrapper,
       });
     } else {
       // Use stdio-based client (default)
       this.client = new DapClient({
-        command: this.config.adapter.command,
-        args: this.config.adapter.args,
+        command,
+        args,
         cwd: this.config.cwd,
-        env: { ...this.config.adapter.env, ...this.config.env },
+        env,
         timeout: this.config.timeout,
+        pathMapper,
+        terminalWrapper,
       });
     }
 
@@ -239,9 +1020,12 @@ export class DebugSession {
 
     // Initialize DAP session
     this.state = 'initializing';
-    await this.client.initialize({
+    this.enterPhase('initializing');
+    const capabilities = await this.client.initialize({
       adapterID: this.config.adapter.id,
     });
+    this.capabilities = capabilities ?? {};
+    logger.debug('session', 'adapter capabilities', { capabilities });
 
     // Create managers
     this.breakpointManager = new BreakpointManager(this.client, this.formatter, {
@@ -253,6 +1037,10 @@ export class DebugSession {
       compactServices: !this.config.expandServices,
       omitNullProperties: !this.config.showNullProps,
       deduplicateByContent: !this.config.noDedupe,
+      safeEvaluation: this.config.evalSafe ? this.config.adapter.safeEvaluation : undefined,
+      evalContext: this.config.evalContext,
+      evalExpandDepth: this.config.evalExpand,
+      provenance: this.config.provenance,
     });
 
     // Add breakpoints to the manager (will be set after launch for some adapters)
@@ -267,30 +1055,44 @@ export class DebugSession {
       }
     }
 
+    // Add instruction breakpoints
+    for (const ib of this.config.instructionBreakpoints ?? []) {
+      this.breakpointManager.addInstructionBreakpoint(ib);
+    }
+
+    // Scoped evaluations ("file:line:expr") only run when stopped at their location
+    const evaluations = this.config.evaluations ?? [];
+    for (const spec of this.breakpointManager.findUnmatchedEvaluations(evaluations)) {
+      logger.warn('session', 'evaluation is tied to a location without a breakpoint', { spec });
+    }
+
     // Some adapters (like debugpy) require launch before breakpoints can be set
     const requiresLaunchFirst = this.config.adapter.requiresLaunchFirst === true;
 
     if (!requiresLaunchFirst) {
       // Standard DAP flow: set breakpoints before launch
       this.state = 'configuring';
-      await this.breakpointManager.setAllBreakpoints();
+      this.enterPhase('configuring');
+      await this.configureBreakpoints();
       await this.setExceptionBreakpoints();
     }
 
     // Launch or attach
-    if (this.config.attach && this.config.pid) {
+    const attachTarget = this.attachTarget();
+    if (attachTarget) {
       // Attach to running process
-      const attachConfig = this.config.adapter.attachConfig({
-        pid: this.config.pid,
-      });
+      const attachConfig = this.withLaunchOverrides(this.config.adapter.attachConfig(attachTarget));
 
+      this.enterPhase('attaching');
+      logger.debug('session', 'attach config', { config: attachConfig });
       await this.client.attach(attachConfig);
 
       if (requiresLaunchFirst) {
         // Wait for 'initialized' event after attach, then set breakpoints
         await this.waitForInitialized();
         this.state = 'configuring';
-        await this.breakpointManager.setAllBreakpoints();
+        this.enterPhase('configuring');
+        await this.configureBreakpoints();
         await this.setExceptionBreakpoints();
       }
 
@@ -298,24 +1100,45 @@ export class DebugSession {
       await this.client.configurationDone();
 
       this.state = 'running';
+      this.enterPhase('running');
       this.formatter.emit(
         this.formatter.createEvent('process_attached', {
           pid: this.config.pid,
         })
       );
     } else {
+      if (this.config.symbolServers?.length && this.config.adapter.symbolSupport === 'prefetch') {
+        await this.loadMissingSymbols();
+      }
+
       // Launch the program
-      const launchConfig = this.config.adapter.launchConfig({
-        program: this.config.program!,
-        args: this.config.args,
-        cwd: this.config.cwd,
-        env: this.config.env,
-        sourceMapOverrides: this.config.sourceMapOverrides,
-      });
+      const launchConfig = this.withLaunchOverrides(
+        this.config.adapter.launchConfig({
+          program: this.config.program ?? '',
+          module: this.config.module,
+          interpreter: this.config.interpreter,
+          skipFiles: this.config.skipFiles,
+          justMyCode: this.config.justMyCode,
+          stopAtEntry: this.config.trigger !== undefined || this.native !== null,
+          gevent: this.config.gevent,
+          django: this.config.django,
+          requireExactSource: this.config.requireExactSource,
+          symbolServers: this.config.symbolServers,
+          symbolCache: this.config.symbolServers?.length ? symbolCacheDir() : undefined,
+          sourceLink: this.config.sourceLink,
+          runInTerminal: this.sandboxScope() === 'program',
+          runtimeArgs: this.config.runtimeArgs,
+          runtimeExecutable: this.config.runtimeExecutable,
+          args: this.config.args,
+          cwd: this.config.cwd,
+          env: this.config.env,
+          sourceMapOverrides: this.config.sourceMapOverrides,
+        })
+      );
 
-      if (process.env.DEBUG_DAP) {
-        console.error('[Launch config]', JSON.stringify(launchConfig, null, 2));
-      }
+      this.launchArgs = launchConfig;
+      this.enterPhase('launching');
+      logger.debug('session', 'launch config', { config: launchConfig });
 
       // Adapter-specific order varies:
       // - js-debug (socket): configurationDone before launch
@@ -333,7 +1156,8 @@ export class DebugSession {
         const launchPromise = this.client.launch(launchConfig);
         await this.waitForInitialized();
         this.state = 'configuring';
-        await this.breakpointManager.setAllBreakpoints();
+        this.enterPhase('configuring');
+        await this.configureBreakpoints();
         await this.setExceptionBreakpoints();
         await this.client.configurationDone();
         await launchPromise;
@@ -348,10 +1172,136 @@ export class DebugSession {
       }
 
       this.state = 'running';
+      this.enterPhase('running');
       this.formatter.emit(this.formatter.createEvent('process_launched', {}));
     }
   }
 
+  /**
+   * What to attach to: an inspector port when one was discovered, otherwise the process ID
+   * (undefined when launching)
+   */
+  private attachTarget(): AttachOptions | undefined {
+    if (!this.config.attach) return undefined;
+    if (this.config.attachPort) return { port: this.config.attachPort };
+    return this.config.pid ? { pid: this.config.pid } : undefined;
+  }
+
+  /**
+   * A fresh --capture-budget for one stop's capture, if one was set
+   */
+  private startCaptureBudget(): CaptureBudget | undefined {
+    const ms = this.config.captureBudget;
+    return ms === undefined ? undefined : new CaptureBudget(ms);
+  }
+
+  /**
+   * Capture locals (unless captureLocals is off) and evaluate expressions in a frame. Under
+   * --capture-budget the expressions go first, since they were asked for by name, and both
+   * share the budget.
+   */
+  private async captureStop(
+    frameId: number | undefined,
+    expressions: string[]
+  ): Promise<{
+    locals: Record<string, VariableValue>;
+    evaluations?: Record<string, EvaluationResult>;
+  }> {
+    let locals: Record<string, VariableValue> = {};
+    let evaluations: Record<string, EvaluationResult> | undefined;
+    if (frameId === undefined) {
+      return { locals };
+    }
+
+    const inspector = this.variableInspector!;
+    const budget = this.startCaptureBudget();
+    if (budget && expressions.length) {
+      evaluations = await inspector.evaluateExpressions(frameId, expressions, budget);
+    }
+    if (this.config.captureLocals !== false) {
+      locals = await inspector.getLocals(frameId, budget);
+    }
+    if (!budget && expressions.length) {
+      evaluations = await inspector.evaluateExpressions(frameId, expressions);
+    }
+    return { locals, evaluations };
+  }
+
+  /**
+   * --eval specs that apply at a stop, without their breakpoint scopes
+   */
+  private evaluationsFor(location: SourceLocation, hitBreakpointIds?: number[]): string[] {
+    const specs = this.config.evaluations ?? [];
+    if (!this.breakpointManager) {
+      return specs;
+    }
+    return this.breakpointManager.selectEvaluations(specs, location, hitBreakpointIds);
+  }
+
+  /**
+   * Program description for session_start (a path, "-m module", or "npm run script")
+   */
+  private describeProgram(): string {
+    if (this.config.program) {
+      return this.config.program;
+    }
+    if (this.config.module) {
+      return `-m ${this.config.module}`;
+    }
+    return [this.config.runtimeExecutable, ...(this.config.runtimeArgs ?? [])].join(' ');
+  }
+
+  /**
+   * End the current phase span (if any) and open a span for the next phase
+   */
+  private enterPhase(phase: string): void {
+    this.phaseSpan?.end();
+    this.phaseSpan = logger.span(phase);
+    this.endPhase();
+    this.phase = { name: phase, startedAt: Date.now() };
+    this.showStatus();
+  }
+
+  /**
+   * Add the time spent in the current phase to its total
+   */
+  private endPhase(): void {
+    if (!this.phase) return;
+    const { name, startedAt } = this.phase;
+    this.phaseDurations[name] = (this.phaseDurations[name] ?? 0) + Date.now() - startedAt;
+    this.phase = null;
+  }
+
+  /**
+   * Time spent in each phase so far, including the current one
+   */
+  private phaseTimes(): Record<string, number> {
+    const times = { ...this.phaseDurations };
+    if (this.phase) {
+      times[this.phase.name] = (times[this.phase.name] ?? 0) + Date.now() - this.phase.startedAt;
+    }
+    return times;
+  }
+
+  /**
+   * Update the status line: adapter progress first, then the setup phase. Nothing is
+   * shown while the program runs unless the adapter reports progress.
+   */
+  private showStatus(): void {
+    const statusLine = this.config.statusLine;
+    if (!statusLine) return;
+    const text =
+      this.progress.describe() ??
+      (this.phase && this.phase.name !== 'running' && this.phase.name !== 'cleanup'
+        ? `${this.phase.name}...`
+        : undefined);
+    if (text) {
+      statusLine.update(text);
+    } else {
+      statusLine.clear();
+    }
+  }
+
   /**
    * Wait for the 'initialized' event from the debug adapter
    */
@@ -376,17 +1326,69 @@ export class DebugSession {
     });
   }
 
+  /**
+   * Send the breakpoints to the adapter, unless --arm-on-output holds them back until
+   * the program prints its line
+   */
+  private async configureBreakpoints(): Promise<void> {
+    this.breakpointsConfigurable = true;
+    if (this.arming && !this.arming.armed) {
+      logger.info('session', 'breakpoints wait for program output', {
+        pattern: this.arming.pattern.source,
+      });
+      return;
+    }
+    await this.breakpointManager!.setAllBreakpoints();
+  }
+
+  /**
+   * The program printed the --arm-on-output line: set its breakpoints now
+   */
+  private armBreakpoints(line: string): void {
+    const event: BreakpointsArmedEvent = {
+      type: 'breakpoints_armed',
+      timestamp: new Date().toISOString(),
+      pattern: this.arming!.pattern.source,
+      line,
+    };
+    this.formatter.emit(event);
+    logger.info('session', 'breakpoints armed by program output', { line });
+    // Output before the configuring phase (rare) arms them as they're first set
+    if (!this.breakpointsConfigurable) return;
+    this.breakpointManager!.setAllBreakpoints().catch((error) => {
+      logger.warn('session', 'failed to set armed breakpoints', {
+        error: error instanceof Error ? error.message : String(error),
+      });
+    });
+  }
+
   /**
    * Set exception breakpoints if configured
    */
   private async setExceptionBreakpoints(): Promise<void> {
     if (this.config.exceptionFilters && this.config.exceptionFilters.length > 0) {
-      await this.client!.setExceptionBreakpoints({
-        filters: this.config.exceptionFilters,
-      });
+      const plan = planExceptionBreakpoints(
+        this.config.exceptionFilters,
+        this.config.adapter,
+        this.capabilities
+      );
+      if (plan.unsupported.length > 0) {
+        logger.warn('session', 'exception breakpoints not supported by adapter', {
+          unsupported: plan.unsupported,
+        });
+      }
+      await this.client!.setExceptionBreakpoints(plan.args);
       this.formatter.emit(
         this.formatter.createEvent('exception_breakpoint_set', {
-          filters: this.config.exceptionFilters,
+          filters: plan.args.filters,
+          ...(plan.types.length > 0 && { types: plan.types }),
+          ...(plan.args.filterOptions && {
+            conditions: plan.args.filterOptions.map(({ filterId, condition }) => ({
+              filter: filterId,
+              condition: condition ?? '',
+            })),
+          }),
+          ...(plan.unsupported.length > 0 && { unsupported: plan.unsupported }),
         })
       );
     }
@@ -418,68 +1420,440 @@ export class DebugSession {
     this.client.on('exit', () => {
       this.handleAdapterExit();
     });
-  }
-
-  private async handleStopped(body: StoppedEventBody): Promise<void> {
-    this.state = 'stopped';
-    const threadId = body.threadId ?? 1;
-    const reason = body.reason;
 
-    try {
-      // Get stack trace
-      const stackResponse = await this.client!.stackTrace({
-        threadId,
-        levels: 20,
-      });
+    if (this.config.captureModules) {
+      this.client.on('module', (body: ModuleEventBody) => this.modules.update(body));
+    }
+    const sourceLink = this.sourceLink;
+    if (sourceLink) {
+      this.client.on('module', (body: ModuleEventBody) => sourceLink.addModule(body.module.path));
+    }
 
-      const stackTrace: StackFrameInfo[] = stackResponse.stackFrames.map((frame) => ({
-        frameId: frame.id,
-        function: frame.name,
-        file: frame.source?.path ?? null,
-        line: frame.line ?? null,
-        column: frame.column ?? null,
-        module: frame.source?.name,
-        sourceReference: frame.source?.sourceReference,
-        sourceName: frame.source?.name,
-      }));
+    this.client.on('thread', (body: ThreadEventBody) => this.threadTimeline.update(body));
 
-      const topFrame = stackResponse.stackFrames[0];
-      const location: SourceLocation = {
-        file: topFrame?.source?.path ?? 'unknown',
-        line: topFrame?.line ?? 0,
-        column: topFrame?.column,
-        function: topFrame?.name,
-        module: topFrame?.source?.name,
-        sourceReference: topFrame?.source?.sourceReference,
-        sourceName: topFrame?.source?.name,
-      };
+    this.client.on('breakpoint', (body: BreakpointEventBody) =>
+      this.breakpointManager?.handleBreakpointEvent(body)
+    );
 
-      // Get locals if requested
-      let locals: Record<string, VariableValue> = {};
-      if (this.config.captureLocals !== false && topFrame) {
-        locals = await this.variableInspector!.getLocals(topFrame.id);
+    this.client.on(
+      'progress',
+      (
+        kind: ProgressEventKind,
+        body: ProgressStartEventBody | ProgressUpdateEventBody | ProgressEndEventBody
+      ) => {
+        if (kind === 'start') {
+          this.progress.start(body as ProgressStartEventBody);
+        } else if (kind === 'update') {
+          this.progress.update(body);
+        } else {
+          this.progress.end(body);
+        }
+        this.showStatus();
       }
+    );
 
-      // Run evaluations if specified (skip for breakpoints when evalAfterStep is enabled)
-      let evaluations:
-        | Record<string, { result: string; type?: string; error?: string }>
-        | undefined;
-      const shouldDeferEval = this.config.evalAfterStep && reason === 'breakpoint';
-      if (this.config.evaluations?.length && topFrame && !shouldDeferEval) {
-        evaluations = await this.variableInspector!.evaluateExpressions(
-          topFrame.id,
-          this.config.evaluations
-        );
+    this.client.on('process', (body: ProcessEventBody) => {
+      if (body.systemProcessId) {
+        this.debuggeePid = body.systemProcessId;
+        void this.limitDebuggee(body.systemProcessId);
       }
+    });
 
-      // Handle trace step
-      if (reason === 'step' && this.isTracing) {
-        await this.handleTraceStep(
-          threadId,
-          location,
-          stackResponse.stackFrames.length,
-          stackTrace,
-          topFrame?.id
+    const protocolObservers = [this.requestStats, this.config.timeline].filter(
+      (observer): observer is ProtocolObserver => observer !== undefined
+    );
+    if (protocolObservers.length > 0) {
+      this.client.on(
+        'protocolMessage',
+        (direction: 'send' | 'receive', message: ProtocolMessage, session?: string) => {
+          for (const observer of protocolObservers) {
+            observer.protocolMessage(direction, message, session);
+          }
+        }
+      );
+    }
+
+    const traffic = this.config.adapterTraffic;
+    if (traffic) {
+      this.client.on(
+        'protocolMessage',
+        (direction: 'send' | 'receive', message: ProtocolMessage, session?: string) => {
+          traffic.protocolMessage(direction, message, session);
+        }
+      );
+      this.client.on('stderr', (data: string) => traffic.adapterStderr(data));
+      this.client.on('stdout', (data: string) => traffic.adapterStdout(data));
+    }
+
+    // Kept regardless of --artifacts: when startup fails, this is usually where the cause is
+    this.client.on('stderr', (data: string) => this.recordAdapterOutput(data));
+    this.client.on('stdout', (data: string) => this.recordAdapterOutput(data));
+  }
+
+  /**
+   * Keep the last ADAPTER_OUTPUT_LINES lines the adapter wrote to its stderr/stdout
+   */
+  private recordAdapterOutput(data: string): void {
+    const lines = data.replace(/\r?\n$/, '').split(/\r?\n/);
+    this.adapterOutput.push(...lines);
+    if (this.adapterOutput.length > ADAPTER_OUTPUT_LINES) {
+      this.adapterOutput.splice(0, this.adapterOutput.length - ADAPTER_OUTPUT_LINES);
+    }
+  }
+
+  /**
+   * An error message with the adapter's recent output appended, if it wrote any
+   */
+  private withAdapterOutput(message: string): string {
+    const output = this.adapterOutput.filter((line) => line.trim()).join('\n');
+    return output ? `${message}\nAdapter output:\n${output}` : message;
+  }
+
+  /**
+   * Whether the session is still connecting to, initializing or launching through the adapter
+   */
+  private isStarting(): boolean {
+    return this.phase !== null && STARTUP_PHASES.has(this.phase.name);
+  }
+
+  /**
+   * Pause the debuggee so the resulting stop is captured as an interrupt_snapshot.
+   * Returns false if the debuggee isn't running or the adapter refused.
+   */
+  private async requestSnapshot(): Promise<boolean> {
+    if (!this.client || this.state !== 'running') return false;
+    try {
+      const { threads } = await this.client.threads();
+      const threadId = threads[0]?.id ?? this.lastStop?.threadId ?? 1;
+      this.snapshotPending = true;
+      await this.client.pause(threadId);
+      return true;
+    } catch (error) {
+      this.snapshotPending = false;
+      logger.warn('session', 'pause for interrupt snapshot failed', {
+        error: error instanceof Error ? error.message : String(error),
+      });
+      return false;
+    }
+  }
+
+  private forwardSignal(pid: number): boolean {
+    try {
+      process.kill(pid, 'SIGINT');
+      return true;
+    } catch (error) {
+      logger.warn('session', 'failed to forward SIGINT', {
+        pid,
+        error: error instanceof Error ? error.message : String(error),
+      });
+      return false;
+    }
+  }
+
+  private emitInterrupted(
+    signal: InterruptedEvent['signal'],
+    action: InterruptAction,
+    pid?: number
+  ): void {
+    logger.info('session', 'interrupted', { signal, action, pid });
+    const event: InterruptedEvent = {
+      type: 'interrupted',
+      timestamp: new Date().toISOString(),
+      signal,
+      action,
+      ...(pid !== undefined && { pid }),
+    };
+    this.formatter.emit(event);
+  }
+
+  /**
+   * Apply --limit-* resource limits to a launched debuggee
+   */
+  private async limitDebuggee(pid: number): Promise<void> {
+    const limits = this.config.resourceLimits;
+    if (!limits || this.config.attach || this.limitedPids.has(pid)) return;
+    this.limitedPids.add(pid);
+    try {
+      await applyResourceLimits(pid, limits);
+      logger.info('session', 'resource limits applied', {
+        pid,
+        ...describeResourceLimits(limits),
+      });
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      this.formatter.error('Failed to apply resource limits', message);
+      if (!this.config.limitsBestEffort) {
+        this.state = 'terminated';
+        this.endSessionWithError(new Error(`Failed to apply resource limits: ${message}`));
+      }
+    }
+  }
+
+  /**
+   * End the session if the program got to run (it stopped or exited) without the resource
+   * limits, because the adapter never reported its PID. Returns true if it was ended.
+   */
+  private failWithoutLimits(): boolean {
+    if (
+      !this.config.resourceLimits ||
+      this.config.attach ||
+      this.config.limitsBestEffort ||
+      this.limitedPids.size > 0 ||
+      this.sessionEndEmitted
+    ) {
+      return false;
+    }
+    const message =
+      'The adapter never reported the debuggee PID, so resource limits were not applied ' +
+      '(use --limits-best-effort to run without them)';
+    this.formatter.error('Resource limits not applied', message);
+    this.state = 'terminated';
+    this.endSessionWithError(new Error(message));
+    return true;
+  }
+
+  private async handleStopped(body: StoppedEventBody): Promise<void> {
+    if (this.failWithoutLimits()) return;
+    this.state = 'stopped';
+    const threadId = body.threadId ?? 1;
+    const reason = body.reason;
+    logger.info('session', 'stopped', {
+      reason,
+      threadId,
+      hitBreakpointIds: body.hitBreakpointIds,
+    });
+
+    try {
+      // --skip-to-hit: resume the hits before the one of interest without asking for
+      // anything, so getting there costs a continue request per hit
+      if (
+        BREAKPOINT_STOP_REASONS.has(reason) &&
+        this.config.skipToHit !== undefined &&
+        this.hitsFastForwarded < this.config.skipToHit - 1 &&
+        !this.isTracing &&
+        !this.isStepping
+      ) {
+        this.hitsFastForwarded++;
+        this.breakpointsSkipped++;
+        logger.debug('session', 'skipping to hit', {
+          hit: this.hitsFastForwarded,
+          skipToHit: this.config.skipToHit,
+        });
+        await this.client!.continue({ threadId });
+        this.state = 'running';
+        return;
+      }
+
+      if (this.config.captureModules && !this.modulesRequested) {
+        await this.requestModules();
+      }
+
+      // Get stack trace
+      const stackResponse = await this.client!.stackTrace({
+        threadId,
+        levels: 20,
+      });
+      await this.sourceLink?.fetchSources(
+        stackResponse.stackFrames.map((frame) => frame.source?.path)
+      );
+
+      const stackTrace = this.reportedFrames(stackResponse.stackFrames).map(toStackFrameInfo);
+
+      const topFrame = stackResponse.stackFrames[0];
+      this.lastStop = { threadId, frameId: topFrame?.id };
+
+      // A frame restarted by reloadCode() stops again at the start of the function: stay
+      // paused there
+      if (this.frameRestartPending) {
+        this.frameRestartPending = false;
+        this.paused = { threadId, frameId: topFrame?.id };
+        return;
+      }
+
+      const location: SourceLocation = {
+        file: topFrame?.source?.path ?? 'unknown',
+        line: topFrame?.line ?? 0,
+        column: topFrame?.column,
+        function: topFrame?.name,
+        module: topFrame?.source?.name,
+        sourceReference: topFrame?.source?.sourceReference,
+        sourceName: topFrame?.source?.name,
+        address: topFrame?.instructionPointerReference,
+      };
+
+      // --native-break: lldb attaches while the program is held at its entry stop
+      if (this.native && !this.native.attached) {
+        await this.attachNative();
+        if (!this.trigger) {
+          await this.client!.continue({ threadId });
+          this.state = 'running';
+          return;
+        }
+      }
+
+      // --trigger-file/--trigger-signal: the program is held at its entry stop
+      if (this.trigger && reason === 'entry') {
+        await this.holdForTrigger(threadId, location);
+        return;
+      }
+
+      // --sample-exceptions: note the exception and let the service carry on, without
+      // fetching locals
+      if (reason === 'exception' && this.exceptionSampler) {
+        this.exceptionsCaught++;
+        this.exceptionSampler.record({
+          type: body.text ?? 'Exception',
+          message: body.description ?? 'Unknown exception',
+          location,
+          stackTrace,
+        });
+        await this.client!.continue({ threadId });
+        this.state = 'running';
+        return;
+      }
+
+      // Warn when the stopped-in file isn't what's running (edited but not rebuilt)
+      await this.breakpointManager?.checkSource(topFrame?.source);
+
+      // --watch-memory: sample the watched bytes at every stop. A watchpoint stop (a write
+      // to a watched region) only records the change.
+      if (this.memoryWatch && topFrame) {
+        const write = reason === 'data breakpoint';
+        await this.sampleMemory(threadId, topFrame.id, location, write ? 'write' : 'stop');
+        if (write) {
+          await this.client!.continue({ threadId });
+          this.state = 'running';
+          return;
+        }
+      }
+
+      // A frame restarted by --on-hit restart-frame stops at its start: apply --set there,
+      // then let it run again
+      if (this.rerun?.restarting) {
+        this.rerun.restarting = false;
+        const event: FrameRestartedEvent = {
+          type: 'frame_restarted',
+          timestamp: new Date().toISOString(),
+          threadId,
+          location,
+          assignments: topFrame ? await this.applyAssignments(topFrame.id) : undefined,
+        };
+        this.formatter.emit(event);
+        await this.client!.continue({ threadId });
+        this.state = 'running';
+        return;
+      }
+
+      // The stop that follows --on-hit goto: carry on from the new line
+      if (this.gotoPending) {
+        this.gotoPending = false;
+        await this.client!.continue({ threadId });
+        this.state = 'running';
+        return;
+      }
+
+      // --active-after/--active-until: count the hit, and resume it outside its window
+      if (
+        BREAKPOINT_STOP_REASONS.has(reason) &&
+        this.hitWindows &&
+        !this.isTracing &&
+        !this.isStepping
+      ) {
+        const labels = this.breakpointManager?.labelsFor(location, body.hitBreakpointIds) ?? [];
+        if (!this.hitWindows.record(labels)) {
+          this.breakpointsSkipped++;
+          logger.debug('session', 'hit outside its window', { labels });
+          await this.client!.continue({ threadId });
+          this.state = 'running';
+          return;
+        }
+      }
+
+      // --continue-if: resume straight away when a rule says this stop isn't interesting
+      if (
+        BREAKPOINT_STOP_REASONS.has(reason) &&
+        topFrame &&
+        !this.isTracing &&
+        !this.isStepping &&
+        (await this.matchContinueRule(topFrame.id))
+      ) {
+        this.breakpointsSkipped++;
+        await this.client!.continue({ threadId });
+        this.state = 'running';
+        return;
+      }
+
+      // --aggregate: fold the expressions into the statistics and carry on
+      if (
+        BREAKPOINT_STOP_REASONS.has(reason) &&
+        this.aggregator &&
+        topFrame &&
+        !this.isTracing &&
+        !this.isStepping
+      ) {
+        this.breakpointsHit++;
+        await this.aggregateHit(topFrame.id);
+        await this.resumeAfterHit(threadId);
+        return;
+      }
+
+      // --step-filter: step back out of library code a step landed in
+      if (
+        reason === 'step' &&
+        (this.isTracing || this.isStepping) &&
+        this.config.stepFilters?.length &&
+        topFrame &&
+        matchesStepFilter(topFrame.name, this.config.stepFilters)
+      ) {
+        logger.debug('session', 'stepping out of filtered frame', { function: topFrame.name });
+        await this.client!.stepOut({ threadId });
+        this.state = 'running';
+        return;
+      }
+
+      const threadName = await this.threadNameFor(threadId);
+
+      if (this.config.captureArgs && !this.isTracing) {
+        await this.captureFrameArgs(stackTrace);
+      }
+
+      // Get locals and run evaluations (skip evaluations for breakpoints when evalAfterStep
+      // is enabled)
+      const shouldDeferEval = this.config.evalAfterStep && BREAKPOINT_STOP_REASONS.has(reason);
+      const stopEvaluations = this.evaluationsFor(location, body.hitBreakpointIds);
+      const { locals, evaluations } = await this.captureStop(
+        topFrame?.id,
+        shouldDeferEval ? [] : stopEvaluations
+      );
+
+      // Handle interrupt snapshot: capture state, then let the program run again so a
+      // forwarded SIGINT can be handled
+      if (reason === 'pause' && this.snapshotPending) {
+        this.snapshotPending = false;
+        const event: InterruptSnapshotEvent = {
+          type: 'interrupt_snapshot',
+          timestamp: new Date().toISOString(),
+          threadId,
+          location,
+          stackTrace,
+          locals,
+          evaluations,
+          source: this.snippetFor(location),
+        };
+        this.formatter.emit(event);
+        await this.client!.continue({ threadId });
+        this.state = 'running';
+        return;
+      }
+
+      // Handle trace step
+      if (reason === 'step' && this.isTracing) {
+        await this.handleTraceStep(
+          threadId,
+          location,
+          stackResponse.stackFrames.length,
+          stackTrace,
+          topFrame?.id
         );
         return;
       }
@@ -491,13 +1865,16 @@ export class DebugSession {
         this.evalAfterStepData = null;
 
         // Now evaluate expressions after the line has executed
-        let evaluations:
-          | Record<string, { result: string; type?: string; error?: string }>
-          | undefined;
-        if (this.config.evaluations?.length && topFrame) {
+        let evaluations: Record<string, EvaluationResult> | undefined;
+        const stepEvaluations = this.evaluationsFor(
+          pendingData.originalLocation,
+          pendingData.breakpointId !== undefined ? [pendingData.breakpointId] : []
+        );
+        if (stepEvaluations.length && topFrame) {
           evaluations = await this.variableInspector!.evaluateExpressions(
             topFrame.id,
-            this.config.evaluations
+            stepEvaluations,
+            this.startCaptureBudget()
           );
         }
 
@@ -514,7 +1891,7 @@ export class DebugSession {
               stackTrace,
               topFrame.id
             );
-            this.endSession();
+            this.endSession('assertion_failed');
             return;
           }
         }
@@ -524,13 +1901,19 @@ export class DebugSession {
           type: 'breakpoint_hit',
           timestamp: new Date().toISOString(),
           id: pendingData.breakpointId,
+          ...(this.breakpointLabel && { label: this.breakpointLabel }),
           threadId: pendingData.threadId,
+          threadName,
           location: pendingData.originalLocation,
           stackTrace: pendingData.originalStackTrace,
           locals,
           evaluations,
+          source: this.snippetFor(pendingData.originalLocation),
+          heapSnapshot: await this.captureHeapSnapshot(),
+          stopCommand: await this.execStopCommand(topFrame?.id, locals, evaluations),
         };
         this.formatter.emit(event);
+        this.recordHit(event);
 
         // Start trace mode if configured (takes precedence over steps)
         if (this.config.trace) {
@@ -553,8 +1936,7 @@ export class DebugSession {
         }
 
         // Continue execution after capturing state
-        await this.client!.continue({ threadId });
-        this.state = 'running';
+        await this.resumeAfterHit(threadId);
         return;
       }
 
@@ -577,7 +1959,7 @@ export class DebugSession {
               topFrame.id
             );
             this.isStepping = false;
-            this.endSession();
+            this.endSession('assertion_failed');
             return;
           }
         }
@@ -588,9 +1970,11 @@ export class DebugSession {
             type: 'step_completed',
             timestamp: new Date().toISOString(),
             threadId,
+            ...(this.breakpointLabel && { label: this.breakpointLabel }),
             location,
             stackTrace,
             locals,
+            source: this.snippetFor(location),
           };
           this.formatter.emit(event);
         }
@@ -604,8 +1988,42 @@ export class DebugSession {
 
         // Done stepping, continue execution
         this.isStepping = false;
-        await this.client!.continue({ threadId });
-        this.state = 'running';
+        await this.resumeAfterHit(threadId);
+        return;
+      }
+
+      // Fatal signals (lldb/gdb) end the program, so capture them as a stop of their own
+      const signal = this.config.adapter.nativeSignals ? parseFatalSignal(body) : undefined;
+
+      // Handle crash (--until-crash): capture everything and end the session
+      if (this.config.untilCrash && CRASH_STOP_REASONS.has(reason)) {
+        await this.emitCrashReport(
+          body,
+          threadId,
+          location,
+          stackTrace,
+          locals,
+          evaluations,
+          topFrame?.id,
+          signal
+        );
+        this.endSession('crashed');
+        return;
+      }
+
+      if (signal) {
+        this.isTracing = false;
+        this.isStepping = false;
+        await this.emitSignalReceived(
+          threadId,
+          signal,
+          location,
+          stackTrace,
+          locals,
+          evaluations,
+          topFrame?.id
+        );
+        this.endSession('crashed');
         return;
       }
 
@@ -623,12 +2041,14 @@ export class DebugSession {
           type: 'exception_thrown',
           timestamp: new Date().toISOString(),
           threadId,
+          threadName,
           exception: {
             type: body.text ?? 'Exception',
             message: body.description ?? 'Unknown exception',
           },
           location,
           locals,
+          source: this.snippetFor(location),
         };
 
         // Flatten exception chain if enabled (default: true)
@@ -655,16 +2075,26 @@ export class DebugSession {
       }
 
       // Handle breakpoint hit
-      if (reason === 'breakpoint') {
+      if (BREAKPOINT_STOP_REASONS.has(reason)) {
         // If tracing and we hit another breakpoint, end the trace first
         if (this.isTracing) {
           await this.endTrace(threadId, 'breakpoint', stackTrace, topFrame?.id);
         }
 
         this.breakpointsHit++;
+        this.breakpointLabel = this.breakpointManager?.labelFor(location, body.hitBreakpointIds);
+
+        // The first hit after a restart is the re-run of the restarted frame
+        const rerun =
+          this.rerun !== null &&
+          this.rerun.function === location.function &&
+          this.rerun.depth === stackResponse.stackFrames.length;
+        this.rerun = null;
+        const onHit = this.config.onHit;
+        const restartFrame = onHit?.action === 'restart-frame' && !rerun;
 
         // If evalAfterStep is enabled, step first before evaluating
-        if (this.config.evalAfterStep && this.config.evaluations?.length) {
+        if (this.config.evalAfterStep && stopEvaluations.length) {
           this.isEvalAfterStep = true;
           this.evalAfterStepData = {
             threadId,
@@ -691,7 +2121,7 @@ export class DebugSession {
               topFrame.id
             );
             // End session on assertion failure
-            this.endSession();
+            this.endSession('assertion_failed');
             return;
           }
         }
@@ -700,13 +2130,41 @@ export class DebugSession {
           type: 'breakpoint_hit',
           timestamp: new Date().toISOString(),
           id: body.hitBreakpointIds?.[0],
+          ...(this.breakpointLabel && { label: this.breakpointLabel }),
           threadId,
+          threadName,
           location,
           stackTrace,
           locals,
           evaluations,
+          source: this.snippetFor(location),
+          heapSnapshot: await this.captureHeapSnapshot(),
+          stopCommand: await this.execStopCommand(topFrame?.id, locals, evaluations),
+          // With restart-frame, --set applies where the restarted frame starts instead
+          assignments:
+            topFrame && onHit?.action !== 'restart-frame'
+              ? await this.applyAssignments(topFrame.id)
+              : undefined,
+          ...(rerun && { rerun: true }),
         };
         this.formatter.emit(event);
+        this.recordHit(event);
+
+        if (
+          restartFrame &&
+          topFrame &&
+          !this.maxHitsReached() &&
+          (await this.restartFrameForRerun(topFrame.id, location, stackResponse.stackFrames.length))
+        ) {
+          return;
+        }
+        if (
+          onHit?.action === 'goto' &&
+          !this.maxHitsReached() &&
+          (await this.gotoLine(threadId, onHit, location))
+        ) {
+          return;
+        }
 
         // Start trace mode if configured (takes precedence over steps)
         if (this.config.trace) {
@@ -724,8 +2182,7 @@ export class DebugSession {
         }
 
         // Continue execution after capturing state
-        await this.client!.continue({ threadId });
-        this.state = 'running';
+        await this.resumeAfterHit(threadId);
         return;
       }
 
@@ -735,10 +2192,12 @@ export class DebugSession {
         timestamp: new Date().toISOString(),
         id: body.hitBreakpointIds?.[0],
         threadId,
+        threadName,
         location,
         stackTrace,
         locals,
         evaluations,
+        source: this.snippetFor(location),
       };
       this.formatter.emit(event);
 
@@ -771,43 +2230,343 @@ export class DebugSession {
   }
 
   private handleTerminated(): void {
+    if (this.failWithoutLimits()) return;
+    // --on-exit restart: relaunch unless the session is ending or an interrupt ended the program
+    if (
+      this.config.onExit === 'restart' &&
+      !this.config.attach &&
+      !this.sessionEndEmitted &&
+      this.interruptCount === 0
+    ) {
+      this.restart('exited').catch((error) => {
+        this.formatter.error(
+          'Failed to restart the program',
+          error instanceof Error ? error.message : String(error)
+        );
+        this.state = 'terminated';
+        this.endSession('exited');
+      });
+      return;
+    }
     this.state = 'terminated';
-    this.endSession();
+    this.endSession('exited');
   }
 
   private handleOutput(body: OutputEventBody): void {
     if (body.category === 'stdout' || body.category === 'stderr' || body.category === 'console') {
       this.formatter.programOutput(body.category, body.output);
+      if (this.config.untilCrash && body.category !== 'console') {
+        this.recordRecentOutput(body.output);
+      }
+      if (this.arming && body.category !== 'console') {
+        const line = this.arming.feed(body.category, body.output);
+        if (line !== undefined) this.armBreakpoints(line);
+      }
+    }
+  }
+
+  /**
+   * Keep the last CRASH_OUTPUT_LINES lines of program output for the crash report
+   */
+  private recordRecentOutput(output: string): void {
+    const lines = output.replace(/\r?\n$/, '').split(/\r?\n/);
+    this.recentOutput.push(...lines);
+    if (this.recentOutput.length > CRASH_OUTPUT_LINES) {
+      this.recentOutput.splice(0, this.recentOutput.length - CRASH_OUTPUT_LINES);
+    }
+  }
+
+  /**
+   * Emit a crash_report with the faulting frame, every thread's stack and recent output
+   */
+  private async emitCrashReport(
+    body: StoppedEventBody,
+    threadId: number,
+    location: SourceLocation,
+    stackTrace: StackFrameInfo[],
+    locals: Record<string, VariableValue>,
+    evaluations?: Record<string, EvaluationResult>,
+    frameId?: number,
+    signal?: SignalInfo
+  ): Promise<void> {
+    this.crashed = true;
+    this.exceptionsCaught++;
+    this.crashSignal = signal?.name;
+
+    const threads = await this.captureThreads(threadId, stackTrace);
+    const registers =
+      signal && frameId !== undefined ? await this.readRegisters(frameId) : undefined;
+
+    const event: CrashReportEvent = {
+      type: 'crash_report',
+      timestamp: new Date().toISOString(),
+      reason: body.reason,
+      threadId,
+      exception: {
+        type: body.text ?? (body.reason === 'exception' ? 'Exception' : 'Signal'),
+        message: body.description ?? 'Unknown error',
+      },
+      location,
+      locals,
+      evaluations,
+      source: this.snippetFor(location),
+      threads,
+      recentOutput: [...this.recentOutput],
+      ...(signal && { signal }),
+      ...(registers && { registers }),
+    };
+
+    if (this.config.flattenExceptions !== false) {
+      const chainResult = flattenExceptionChainFromLocals(
+        locals,
+        this.config.exceptionChainDepth ?? 10
+      );
+      if (chainResult) {
+        event.exceptionChain = chainResult.chain;
+        event.rootCause = chainResult.rootCause;
+      }
+    }
+
+    this.formatter.emit(event);
+  }
+
+  /**
+   * Emit signal_received for a fatal signal: the faulting frame's locals and registers
+   * and every thread's stack
+   */
+  private async emitSignalReceived(
+    threadId: number,
+    signal: SignalInfo,
+    location: SourceLocation,
+    stackTrace: StackFrameInfo[],
+    locals: Record<string, VariableValue>,
+    evaluations?: Record<string, EvaluationResult>,
+    frameId?: number
+  ): Promise<void> {
+    this.exceptionsCaught++;
+    this.crashSignal = signal.name;
+
+    const threads = await this.captureThreads(threadId, stackTrace);
+    const registers = frameId !== undefined ? await this.readRegisters(frameId) : undefined;
+    const event: SignalReceivedEvent = {
+      type: 'signal_received',
+      timestamp: new Date().toISOString(),
+      threadId,
+      signal,
+      location,
+      locals,
+      ...(registers && { registers }),
+      evaluations,
+      source: this.snippetFor(location),
+      threads,
+    };
+    this.formatter.emit(event);
+  }
+
+  /**
+   * The skipFiles globs the program was launched with (js-debug)
+   */
+  private skipFiles(): string[] {
+    const skipFiles = this.launchArgs?.skipFiles;
+    return Array.isArray(skipFiles) ? (skipFiles as string[]) : [];
+  }
+
+  /**
+   * Frames to report in a stack trace: those js-debug skips (skipFiles) are left out, except
+   * the frame the program stopped in
+   */
+  private reportedFrames(frames: StackFrame[]): StackFrame[] {
+    if (this.skipFiles().length === 0) return frames;
+    return frames.filter(
+      (frame, index) => index === 0 || frame.source?.presentationHint !== 'deemphasize'
+    );
+  }
+
+  /**
+   * Name of a stopped thread, listing threads when it hasn't been seen with a name yet
+   */
+  private async threadNameFor(threadId: number): Promise<string | undefined> {
+    if (this.threadTimeline.nameOf(threadId) === undefined) {
+      try {
+        this.threadTimeline.nameThreads((await this.client!.threads()).threads);
+      } catch (error) {
+        logger.warn('session', 'failed to list threads', {
+          error: error instanceof Error ? error.message : String(error),
+        });
+      }
+    }
+    return this.threadTimeline.nameOf(threadId);
+  }
+
+  /**
+   * Every thread's stack, the stopped thread first
+   */
+  private async captureThreads(
+    threadId: number,
+    stackTrace: StackFrameInfo[]
+  ): Promise<CrashThread[]> {
+    const threads: CrashThread[] = [];
+    let others: { id: number; name: string }[] = [];
+    try {
+      others = (await this.client!.threads()).threads;
+      this.threadTimeline.nameThreads(others);
+    } catch (error) {
+      logger.warn('session', 'failed to list threads', {
+        error: error instanceof Error ? error.message : String(error),
+      });
+    }
+    const faultingName = others.find((thread) => thread.id === threadId)?.name;
+    threads.push({
+      id: threadId,
+      name: faultingName ?? `Thread ${threadId}`,
+      faulting: true,
+      stackTrace,
+    });
+
+    for (const thread of others) {
+      if (thread.id === threadId) continue;
+      try {
+        const response = await this.client!.stackTrace({ threadId: thread.id, levels: 20 });
+        await this.sourceLink?.fetchSources(
+          response.stackFrames.map((frame) => frame.source?.path)
+        );
+        threads.push({
+          id: thread.id,
+          name: thread.name,
+          stackTrace: this.reportedFrames(response.stackFrames).map(toStackFrameInfo),
+        });
+      } catch (error) {
+        threads.push({
+          id: thread.id,
+          name: thread.name,
+          stackTrace: [],
+          error: error instanceof Error ? error.message : String(error),
+        });
+      }
+    }
+    return threads;
+  }
+
+  private async readRegisters(frameId: number): Promise<Record<string, string> | undefined> {
+    try {
+      return await this.variableInspector!.getRegisters(frameId);
+    } catch (error) {
+      logger.warn('session', 'failed to read registers', {
+        error: error instanceof Error ? error.message : String(error),
+      });
+      return undefined;
     }
   }
 
   private handleAdapterExit(): void {
+    if (this.isStarting()) {
+      // Not a normal end: the program never ran
+      const message = `Debug adapter exited while ${this.phase!.name}`;
+      this.formatter.error('Debug adapter failed to start', this.withAdapterOutput(message));
+      this.state = 'terminated';
+      this.endSessionWithError(new Error(message), 'adapter_exited');
+      return;
+    }
     if (this.state !== 'terminated') {
       this.state = 'terminated';
-      this.endSession();
+      this.endSession(this.exitCode !== null ? 'exited' : 'adapter_exited');
     }
   }
 
   private handleTimeout(): void {
     this.formatter.error('Session timed out', `Timeout after ${this.config.timeout}ms`);
-    this.endSessionWithError(new Error(`Session timed out after ${this.config.timeout}ms`));
+    this.endSessionWithError(
+      new Error(`Session timed out after ${this.config.timeout}ms`),
+      'timeout'
+    );
   }
 
-  private endSessionWithError(error: Error): void {
-    // Store the error so run() can throw it after promise resolves
-    this.sessionError = error;
+  /**
+   * Build the session_end summary
+   */
+  private buildSummary(): SessionEndEvent['summary'] {
+    const threads = this.threadTimeline.list();
+    const progress = this.progress.list();
+    const phases = this.phaseTimes();
+    const staleSources = this.breakpointManager?.getStaleSources() ?? [];
+    const skipFiles = this.skipFiles();
+    return {
+      durationMs: Date.now() - this.startTime,
+      exitCode: this.exitCode,
+      breakpointsHit: this.breakpointsHit,
+      exceptionsCaught: this.exceptionsCaught,
+      stepsExecuted: this.stepsExecuted,
+      ...(Object.keys(phases).length > 0 && { phases }),
+      ...(progress.length > 0 && { progress }),
+      ...(threads.length > 1 && { threads }),
+      ...(this.config.captureModules && { modules: this.modules.list() }),
+      ...(staleSources.length > 0 && { staleSources }),
+      ...(skipFiles.length > 0 && { skipFiles }),
+      ...(this.memoryWatch && { memory: this.memoryWatch.list() }),
+      ...(this.restarts > 0 && { restarts: this.restarts }),
+      ...(this.aggregator && { aggregates: this.aggregator.list() }),
+      ...(this.breakpointsSkipped > 0 && { breakpointsSkipped: this.breakpointsSkipped }),
+      ...(this.hitPath.length > 0 && { path: this.hitPath }),
+      ...(this.requestStats && { requests: this.requestStats.list() }),
+      ...(this.maxHitsReached() && { maxHitsReached: true }),
+      ...(this.crashed && { crashed: true }),
+      termination: this.buildTermination(),
+    };
+  }
 
-    // Emit session end (only once)
-    if (!this.sessionEndEmitted) {
-      this.sessionEndEmitted = true;
-      this.formatter.sessionEnd({
-        durationMs: Date.now() - this.startTime,
-        exitCode: this.exitCode,
-        breakpointsHit: this.breakpointsHit,
-        exceptionsCaught: this.exceptionsCaught,
-        stepsExecuted: this.stepsExecuted,
-      });
+  /**
+   * Build session_end's termination section: why and how the program ended
+   */
+  private buildTermination(): TerminationInfo {
+    const reason = this.endReason ?? 'exited';
+    this.sampleCpuTime();
+    return {
+      reason,
+      exitCode: this.exitCode,
+      ...(this.crashSignal && { signal: this.crashSignal }),
+      timedOut: reason === 'timeout',
+      // Ending the session disconnects with terminateDebuggee, except when attached
+      killed: !this.config.attach && this.exitCode === null && TEARDOWN_REASONS.has(reason),
+      wallTimeMs: Date.now() - this.startTime,
+      ...(this.cpuTimeMs !== undefined && { cpuTimeMs: this.cpuTimeMs }),
+    };
+  }
+
+  /**
+   * Record the debuggee's CPU time while it's still around to ask
+   */
+  private sampleCpuTime(): void {
+    const pid = this.debuggeePid ?? (this.config.attach ? this.config.pid : undefined);
+    if (pid === undefined || this.exitCode !== null) return;
+    const cpuTimeMs = readCpuTimeMs(pid);
+    if (cpuTimeMs !== undefined) {
+      this.cpuTimeMs = cpuTimeMs;
+    }
+  }
+
+  /**
+   * Emit session_end (only once)
+   */
+  private emitSessionEnd(reason: TerminationReason): void {
+    if (this.sessionEndEmitted) return;
+    this.sessionEndEmitted = true;
+    this.endReason ??= reason;
+    if (this.sampleReporter) {
+      clearInterval(this.sampleReporter);
+      this.sampleReporter = null;
     }
+    if (this.exceptionSampler) {
+      const report = this.exceptionSampler.report(true);
+      this.formatter.emit(this.formatter.createEvent('exception_sample_report', report));
+    }
+    this.formatter.sessionEnd(this.buildSummary());
+  }
+
+  private endSessionWithError(error: Error, reason: TerminationReason = 'error'): void {
+    // Store the error so run() can throw it after promise resolves
+    this.sessionError = error;
+
+    this.emitSessionEnd(reason);
 
     // Resolve the session promise (not reject, to avoid unhandled promise rejection
     // if timeout fires during start())
@@ -817,18 +2576,8 @@ export class DebugSession {
     }
   }
 
-  private endSession(): void {
-    // Emit session end (only once)
-    if (!this.sessionEndEmitted) {
-      this.sessionEndEmitted = true;
-      this.formatter.sessionEnd({
-        durationMs: Date.now() - this.startTime,
-        exitCode: this.exitCode,
-        breakpointsHit: this.breakpointsHit,
-        exceptionsCaught: this.exceptionsCaught,
-        stepsExecuted: this.stepsExecuted,
-      });
-    }
+  private endSession(reason: TerminationReason): void {
+    this.emitSessionEnd(reason);
 
     // Resolve the session promise
     if (this.sessionResolve) {
@@ -843,6 +2592,16 @@ export class DebugSession {
       clearTimeout(this.timeoutHandle);
       this.timeoutHandle = null;
     }
+    this.trigger?.cancel();
+    this.trigger = null;
+    // Let go of the program before its own adapter ends it
+    await this.native?.detach();
+    if (this.cpuSampler) {
+      clearInterval(this.cpuSampler);
+      this.cpuSampler = null;
+    }
+
+    this.enterPhase('cleanup');
 
     // Disconnect client
     // In attach mode, don't terminate the debuggee - leave the process running
@@ -854,6 +2613,27 @@ export class DebugSession {
         // Ignore cleanup errors
       }
     }
+    if (this.config.resourceLimits && !this.config.attach && this.limitedPids.size === 0) {
+      logger.warn(
+        'session',
+        'adapter never reported the debuggee PID; resource limits not applied'
+      );
+    }
+
+    // The adapter may have died or the connection dropped without a disconnect;
+    // make sure nothing it launched outlives the session
+    await this.client?.reapProcessTree();
+
+    this.phaseSpan?.end();
+    this.phaseSpan = null;
+    this.endPhase();
+    this.config.statusLine?.clear();
+    this.sessionSpan?.end({
+      exitCode: this.exitCode,
+      breakpointsHit: this.breakpointsHit,
+      error: this.sessionError?.message,
+    });
+    this.sessionSpan = null;
   }
 
   // ========== Trace Mode Methods ==========
@@ -954,7 +2734,7 @@ export class DebugSession {
         );
         // End trace and session
         this.isTracing = false;
-        this.endSession();
+        this.endSession('assertion_failed');
         return;
       }
     }
@@ -1030,25 +2810,17 @@ export class DebugSession {
       line: 0,
     };
 
-    // Capture full locals at trace completion
-    let locals: Record<string, VariableValue> = {};
-    if (frameId && this.config.captureLocals !== false) {
-      locals = await this.variableInspector!.getLocals(frameId);
-    }
-
-    // Run evaluations at trace completion
-    let evaluations: Record<string, { result: string; type?: string; error?: string }> | undefined;
-    if (this.config.evaluations?.length && frameId) {
-      evaluations = await this.variableInspector!.evaluateExpressions(
-        frameId,
-        this.config.evaluations
-      );
-    }
+    // Capture full locals and run evaluations at trace completion
+    const { locals, evaluations } = await this.captureStop(
+      frameId,
+      this.evaluationsFor(finalLocation)
+    );
 
     const event: TraceCompletedEvent = {
       type: 'trace_completed',
       timestamp: new Date().toISOString(),
       threadId,
+      ...(this.breakpointLabel && { label: this.breakpointLabel }),
       stopReason: reason,
       stepsExecuted: this.traceStepCount,
       path: this.tracePath,
@@ -1069,6 +2841,50 @@ export class DebugSession {
     this.previousLocals = {};
 
     // Continue execution after trace
+    await this.resumeAfterHit(threadId);
+  }
+
+  /**
+   * Append a breakpoint hit to the ordered hit path
+   */
+  private recordHit(event: BreakpointHitEvent): void {
+    this.hitPath.push({
+      hit: this.hitsFastForwarded + this.hitPath.length + 1,
+      breakpointId: event.id,
+      threadId: event.threadId,
+      location: {
+        file: event.location.file,
+        line: event.location.line,
+        function: event.location.function,
+      },
+      timestamp: event.timestamp,
+    });
+  }
+
+  /**
+   * Check if --max-hits has been reached
+   */
+  private maxHitsReached(): boolean {
+    return this.config.maxHits !== undefined && this.breakpointsHit >= this.config.maxHits;
+  }
+
+  /**
+   * Continue after handling a breakpoint (and any steps/trace it started), or end the
+   * session once --max-hits breakpoint hits have been captured. With pauseOnHit the
+   * thread stays stopped until continue() is called.
+   */
+  private async resumeAfterHit(threadId: number): Promise<void> {
+    if (this.maxHitsReached()) {
+      this.endSession('max_hits');
+      return;
+    }
+    if (this.config.pauseOnHit) {
+      this.paused = {
+        threadId,
+        frameId: this.lastStop?.threadId === threadId ? this.lastStop.frameId : undefined,
+      };
+      return;
+    }
     await this.client!.continue({ threadId });
     this.state = 'running';
   }
@@ -1095,6 +2911,302 @@ export class DebugSession {
     return value.length > 0 && value !== '""' && value !== "''";
   }
 
+  /**
+   * Apply the --set assignments in a frame: with setExpression where the adapter supports
+   * it, otherwise by evaluating "target = value" in the repl context
+   */
+  private async applyAssignments(
+    frameId: number
+  ): Promise<Record<string, AssignmentResult> | undefined> {
+    if (!this.config.assignments?.length) return undefined;
+
+    const results: Record<string, AssignmentResult> = {};
+    for (const spec of this.config.assignments) {
+      const { target, value } = parseAssignment(spec);
+      try {
+        if (this.capabilities.supportsSetExpression) {
+          const response = await this.client!.customRequest<{ value: string }>('setExpression', {
+            expression: target,
+            value,
+            frameId,
+          });
+          results[target] = { value: response.value };
+        } else {
+          const response = await this.client!.evaluate({
+            expression: `${target} = ${value}`,
+            frameId,
+            context: 'repl',
+          });
+          results[target] = { value: response.result };
+        }
+      } catch (error) {
+        results[target] = {
+          value: '',
+          error: error instanceof Error ? error.message : String(error),
+        };
+      }
+    }
+    return results;
+  }
+
+  /**
+   * Restart the stopped frame for --on-hit restart-frame. Returns false (and the hit is
+   * resumed as usual) if the adapter can't restart frames or the restart fails.
+   */
+  private async restartFrameForRerun(
+    frameId: number,
+    location: SourceLocation,
+    depth: number
+  ): Promise<boolean> {
+    if (!this.capabilities.supportsRestartFrame) {
+      this.reportOnHitUnsupported(`The ${this.config.adapter.name} adapter can't restart frames`);
+      return false;
+    }
+
+    try {
+      this.rerun = { function: location.function, depth, restarting: true };
+      await this.client!.customRequest('restartFrame', { frameId });
+      this.state = 'running';
+      return true;
+    } catch (error) {
+      this.rerun = null;
+      this.formatter.error(
+        'Failed to restart frame',
+        error instanceof Error ? error.message : String(error)
+      );
+      return false;
+    }
+  }
+
+  /**
+   * Move execution to the --on-hit goto line. Returns false (and the hit is resumed as
+   * usual) if the adapter doesn't support goto or the line isn't a valid target.
+   */
+  private async gotoLine(
+    threadId: number,
+    target: { file: string; line: number },
+    from: SourceLocation
+  ): Promise<boolean> {
+    if (!this.capabilities.supportsGotoTargetsRequest) {
+      this.reportOnHitUnsupported(`The ${this.config.adapter.name} adapter doesn't support goto`);
+      return false;
+    }
+
+    try {
+      const args: GotoTargetsArguments = {
+        source: { path: path.resolve(this.config.cwd ?? process.cwd(), target.file) },
+        line: target.line,
+      };
+      const { targets } = await this.client!.customRequest<GotoTargetsResponse>(
+        'gotoTargets',
+        args
+      );
+      if (!targets?.length) {
+        throw new Error(`${target.file}:${target.line} is not a valid goto target`);
+      }
+      this.gotoPending = true;
+      await this.client!.customRequest('goto', { threadId, targetId: targets[0].id });
+      const event: ExecutionMovedEvent = {
+        type: 'execution_moved',
+        timestamp: new Date().toISOString(),
+        threadId,
+        from,
+        to: { file: target.file, line: targets[0].line ?? target.line },
+      };
+      this.formatter.emit(event);
+      return true;
+    } catch (error) {
+      this.gotoPending = false;
+      this.formatter.error(
+        'Failed to move execution',
+        error instanceof Error ? error.message : String(error)
+      );
+      return false;
+    }
+  }
+
+  /**
+   * Fill in modules loaded before the adapter started sending module events, while the
+   * program is stopped (adapters with the modules request only)
+   */
+  private async requestModules(): Promise<void> {
+    this.modulesRequested = true;
+    if (!this.capabilities.supportsModulesRequest) return;
+    try {
+      const response = await this.client!.customRequest<ModulesResponse>('modules', {});
+      this.modules.merge(response.modules ?? []);
+    } catch (error) {
+      logger.warn('session', 'modules request failed', {
+        error: error instanceof Error ? error.message : String(error),
+      });
+    }
+  }
+
+  /**
+   * Read every --watch-memory region and emit memory_changed for the ones that differ from
+   * the last sample
+   */
+  private async sampleMemory(
+    threadId: number,
+    frameId: number,
+    location: SourceLocation,
+    reason: MemoryChangeReason
+  ): Promise<void> {
+    const log = this.memoryWatch!;
+    if (!this.capabilities.supportsReadMemoryRequest) {
+      this.formatter.error(
+        `The ${this.config.adapter.name} adapter doesn't support reading memory`,
+        '--watch-memory is ignored'
+      );
+      this.memoryWatch = null;
+      return;
+    }
+
+    for (const watch of this.config.watchMemory ?? []) {
+      try {
+        const address = await this.resolveWatchAddress(watch.expression, frameId);
+        const response = await this.client!.customRequest<ReadMemoryResponse>('readMemory', {
+          memoryReference: address,
+          count: watch.size,
+        });
+        const value = response?.data ? decodeMemory(response.data) : '';
+        if (value.length < watch.size * 2) {
+          const readable = value.length / 2;
+          throw new Error(`Only ${readable} of ${watch.size} bytes at ${address} are readable`);
+        }
+        const change = log.record(watch.expression, address, value, reason);
+        if (change) {
+          const event: MemoryChangedEvent = {
+            type: 'memory_changed',
+            timestamp: new Date().toISOString(),
+            ...change,
+            threadId,
+            location,
+          };
+          this.formatter.emit(event);
+        }
+        await this.setMemoryWatchpoint(watch, address, frameId);
+      } catch (error) {
+        const message = error instanceof Error ? error.message : String(error);
+        if (log.fail(watch.expression, message)) {
+          logger.warn('session', 'memory watch failed', {
+            expression: watch.expression,
+            error: message,
+          });
+        }
+      }
+    }
+  }
+
+  /**
+   * Evaluate a --watch-memory expression to the address it names
+   */
+  private async resolveWatchAddress(expression: string, frameId: number): Promise<string> {
+    const response = await this.client!.evaluate({ expression, frameId, context: 'watch' });
+    const address = response.memoryReference ?? parseAddress(response.result);
+    if (!address) {
+      throw new Error(`"${expression}" is not an address (got ${response.result})`);
+    }
+    return address;
+  }
+
+  /**
+   * Set a write watchpoint (DAP data breakpoint) on a watched region, once per address.
+   * Without one, changes are only seen at stops.
+   */
+  private async setMemoryWatchpoint(
+    watch: MemoryWatchSpec,
+    address: string,
+    frameId: number
+  ): Promise<void> {
+    if (this.memoryWatchpoints.get(watch.expression)?.address === address) return;
+    if (!this.capabilities.supportsDataBreakpoints) {
+      this.memoryWatchpoints.set(watch.expression, { address, dataId: null });
+      this.memoryWatch!.setWatchpoint(watch.expression, false);
+      return;
+    }
+
+    // Adapters with data breakpoint bytes take the address itself; others want the
+    // variable the address expression names
+    const args: DataBreakpointInfoArguments = this.capabilities.supportsDataBreakpointBytes
+      ? { name: address, asAddress: true, bytes: watch.size }
+      : { name: watch.expression.replace(/^&/, ''), frameId };
+    const info = await this.client!.customRequest<DataBreakpointInfoResponse>(
+      'dataBreakpointInfo',
+      args
+    );
+    this.memoryWatchpoints.set(watch.expression, { address, dataId: info?.dataId ?? null });
+    if (!info?.dataId) {
+      logger.info('session', 'no watchpoint for memory watch', {
+        expression: watch.expression,
+        reason: info?.description,
+      });
+      this.memoryWatch!.setWatchpoint(watch.expression, false);
+      return;
+    }
+
+    // setDataBreakpoints replaces every data breakpoint, so send them all
+    const watched = [...this.memoryWatchpoints].filter(([, entry]) => entry.dataId);
+    const breakpoints: DataBreakpoint[] = watched.map(([, entry]) => ({
+      dataId: entry.dataId!,
+      accessType: 'write',
+    }));
+    const response = await this.client!.customRequest<SetDataBreakpointsResponse>(
+      'setDataBreakpoints',
+      { breakpoints }
+    );
+    watched.forEach(([expression], i) => {
+      this.memoryWatch!.setWatchpoint(expression, response?.breakpoints?.[i]?.verified ?? false);
+    });
+  }
+
+  /**
+   * Report once that the adapter can't perform the --on-hit action
+   */
+  private reportOnHitUnsupported(message: string): void {
+    if (this.onHitUnsupportedReported) return;
+    this.onHitUnsupportedReported = true;
+    this.formatter.error(message, '--on-hit is ignored; breakpoint hits continue as usual');
+  }
+
+  /**
+   * Find the first --continue-if rule that holds in the current frame. A rule that fails
+   * to evaluate doesn't hold, so the stop is captured rather than silently skipped.
+   */
+  private async matchContinueRule(frameId: number): Promise<string | undefined> {
+    for (const rule of this.config.continueIf ?? []) {
+      try {
+        const result = await this.variableInspector!.evaluate(rule, frameId, 'watch');
+        if (this.isTruthy(result.result)) {
+          logger.info('session', 'continue-if rule matched', { rule, value: result.result });
+          return rule;
+        }
+      } catch (error) {
+        logger.warn('session', 'continue-if rule failed to evaluate', {
+          rule,
+          error: error instanceof Error ? error.message : String(error),
+        });
+      }
+    }
+    return undefined;
+  }
+
+  /**
+   * Evaluate the --aggregate expressions at a breakpoint hit and record the values
+   */
+  private async aggregateHit(frameId: number): Promise<void> {
+    const aggregator = this.aggregator!;
+    const expressions = aggregator.expressions();
+    for (let i = 0; i < expressions.length; i++) {
+      try {
+        const result = await this.variableInspector!.evaluate(expressions[i], frameId, 'watch');
+        aggregator.record(i, { result: result.result });
+      } catch (error) {
+        aggregator.record(i, { error: error instanceof Error ? error.message : String(error) });
+      }
+    }
+  }
+
   // ========== Assertion Methods ==========
 
   /**
@@ -1108,11 +3220,7 @@ export class DebugSession {
 
     for (const assertion of this.config.assertions) {
       try {
-        const result = await this.client!.evaluate({
-          expression: assertion,
-          frameId,
-          context: 'watch',
-        });
+        const result = await this.variableInspector!.evaluate(assertion, frameId, 'watch');
 
         // Assertion fails if result is falsy
         if (!this.isTruthy(result.result)) {
@@ -1147,23 +3255,236 @@ export class DebugSession {
     frameId: number
   ): Promise<void> {
     // Capture full locals for debugging context
-    let locals: Record<string, VariableValue> = {};
-    if (this.config.captureLocals !== false) {
-      locals = await this.variableInspector!.getLocals(frameId);
-    }
+    const { locals } = await this.captureStop(frameId, []);
 
     const event: AssertionFailedEvent = {
       type: 'assertion_failed',
       timestamp: new Date().toISOString(),
       threadId,
+      ...(this.breakpointLabel && { label: this.breakpointLabel }),
       assertion,
       actualValue,
       evaluationError,
       location,
       stackTrace,
       locals,
+      source: this.snippetFor(location),
     };
 
     this.formatter.emit(event);
   }
+
+  /**
+   * Attach the --native-break adapter to the program held at entry. Without it the session
+   * carries on with the Python breakpoints alone.
+   */
+  private async attachNative(): Promise<void> {
+    const native = this.native!;
+    const adapter = this.config.mixedMode!.adapter.name;
+    const pid = this.debuggeePid;
+    try {
+      if (pid === undefined) {
+        throw new Error(`The ${this.config.adapter.name} adapter didn't report the program's PID`);
+      }
+      await native.attach(pid);
+      this.formatter.emit(this.formatter.createEvent('native_attached', { adapter, pid }));
+    } catch (error) {
+      this.native = null;
+      const message = error instanceof Error ? error.message : String(error);
+      this.formatter.error(`${adapter} could not attach for --native-break`, message);
+    }
+  }
+
+  /**
+   * A --native-break hit, captured by the native adapter: it counts like any other hit
+   */
+  private handleNativeHit(event: BreakpointHitEvent): void {
+    this.breakpointsHit++;
+    this.formatter.emit(event);
+    this.recordHit(event);
+    if (this.maxHitsReached()) {
+      this.endSession('max_hits');
+    }
+  }
+
+  /**
+   * Keep the program paused at its entry stop until --trigger-file or --trigger-signal fires,
+   * then let it run. --timeout still applies while it's held.
+   */
+  private async holdForTrigger(threadId: number, location: SourceLocation): Promise<void> {
+    // Kept until it fires, so cleanup can still cancel it
+    const trigger = this.trigger!;
+    this.enterPhase('held');
+    this.formatter.emit(
+      this.formatter.createEvent('trigger_waiting', {
+        threadId,
+        location,
+        file: this.config.trigger?.file,
+        signal: this.config.trigger?.signal,
+      })
+    );
+
+    const heldAt = Date.now();
+    const source = await trigger.fired;
+    // Cancelled: the session ended while the program was held
+    if (!source) return;
+    if (this.trigger === trigger) this.trigger = null;
+    logger.info('session', 'trigger fired', { source });
+    this.formatter.emit(
+      this.formatter.createEvent('trigger_fired', { source, waitedMs: Date.now() - heldAt })
+    );
+    await this.client!.continue({ threadId });
+    this.state = 'running';
+    this.enterPhase('running');
+  }
+
+  /**
+   * Run the --on-stop-exec command for a breakpoint hit. Placeholders the hit didn't capture
+   * are evaluated in the stopped frame; failures are reported in the result.
+   */
+  private async execStopCommand(
+    frameId: number | undefined,
+    locals: Record<string, VariableValue>,
+    evaluations: Record<string, EvaluationResult> | undefined
+  ): Promise<StopCommandResult | undefined> {
+    const words = this.config.onStopExec;
+    if (!words) {
+      return undefined;
+    }
+
+    const values: Record<string, string> = {};
+    const uncaptured: string[] = [];
+    for (const name of stopCommandPlaceholders(words)) {
+      const value = capturedValue(name, locals, evaluations);
+      if (value !== undefined) values[name] = value;
+      else uncaptured.push(name);
+    }
+    if (uncaptured.length && frameId !== undefined) {
+      const results = await this.variableInspector!.evaluateExpressions(frameId, uncaptured);
+      for (const name of uncaptured) {
+        if (results[name] && !results[name].error) values[name] = evaluationText(results[name]);
+      }
+    }
+
+    const command = fillStopCommand(words, values);
+    const unresolved = uncaptured.filter((name) => values[name] === undefined);
+    if (unresolved.length) {
+      return {
+        command,
+        exitCode: null,
+        stdout: '',
+        stderr: '',
+        durationMs: 0,
+        error: `Could not resolve ${unresolved.map((name) => `{${name}}`).join(', ')}`,
+      };
+    }
+
+    const span = logger.span('stop_command', { command: command[0] });
+    const result = await runStopCommand(command, { cwd: this.config.cwd });
+    span.end({ exitCode: result.exitCode, ...(result.error && { error: result.error }) });
+    return result;
+  }
+
+  /**
+   * Write a heap snapshot for the current breakpoint hit and summarize it (--heap-snapshot).
+   * Failures are reported in the result rather than ending the session.
+   */
+  private async captureHeapSnapshot(): Promise<HeapSnapshotInfo | undefined> {
+    if (!this.config.heapSnapshotDir) {
+      return undefined;
+    }
+    const file = path.join(
+      this.config.heapSnapshotDir,
+      `debug-run-${this.startTime}-hit${this.breakpointsHit}.heapsnapshot`
+    );
+
+    const span = logger.span('heap_snapshot', { file });
+    try {
+      await takeHeapSnapshot(this.client!, file);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      span.end({ error: message });
+      return { file, error: `Heap snapshot failed: ${message}` };
+    }
+    try {
+      const summary = readHeapSnapshotSummary(file);
+      span.end({ totalSize: summary.totalSize });
+      return { file, ...summary };
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      span.end({ error: message });
+      return { file, error: `Heap snapshot written but could not be summarized: ${message}` };
+    }
+  }
+
+  /**
+   * --capture-args: add each frame's argument values to it. Parameter names come from the
+   * function's declaration in the frame's source, when the adapter has no Arguments scope.
+   */
+  private async captureFrameArgs(stackTrace: StackFrameInfo[]): Promise<void> {
+    for (const frame of stackTrace) {
+      const name = shortFunctionName(frame.function);
+      const lines = frame.file ? this.sourceReader.lines(frame.file) : null;
+      const parameters =
+        name && lines && frame.file && frame.line
+          ? parameterNames(lines, frame.line, name, frame.file)
+          : undefined;
+      try {
+        const args = await this.variableInspector!.getFrameArguments(frame.frameId, parameters);
+        if (args) frame.args = args;
+      } catch (error) {
+        logger.debug('session', 'failed to capture frame arguments', {
+          function: frame.function,
+          error: error instanceof Error ? error.message : String(error),
+        });
+      }
+    }
+  }
+
+  /**
+   * --symbol-server with an adapter that only loads PDBs next to their assembly: copy the
+   * ones the program's assemblies are missing there before it starts
+   */
+  private async loadMissingSymbols(): Promise<void> {
+    const directory = path.dirname(path.resolve(this.config.program ?? ''));
+    try {
+      const { loaded, missing } = await prefetchSymbols(directory, this.config.symbolServers!);
+      logger.info('symbols', 'loaded missing PDBs from symbol servers', {
+        directory,
+        loaded: loaded.length,
+        missing: missing.map((assembly) => path.basename(assembly)),
+      });
+    } catch (error) {
+      logger.warn('symbols', 'failed to load PDBs from symbol servers', {
+        directory,
+        error: error instanceof Error ? error.message : String(error),
+      });
+    }
+  }
+
+  /**
+   * Source lines around a location when --include-source is set
+   */
+  private snippetFor(location: SourceLocation): SourceSnippet | undefined {
+    if (!this.config.includeSource || location.file === 'unknown') {
+      return undefined;
+    }
+    return this.sourceReader.read(location.file, location.line, this.config.includeSource);
+  }
+}
+
+/**
+ * Convert a DAP stack frame to the output format
+ */
+function toStackFrameInfo(frame: StackFrame): StackFrameInfo {
+  return {
+    frameId: frame.id,
+    function: frame.name,
+    file: frame.source?.path ?? null,
+    line: frame.line ?? null,
+    column: frame.column ?? null,
+    module: frame.source?.name,
+    sourceReference: frame.source?.sourceReference,
+    sourceName: frame.source?.name,
+  };
 }
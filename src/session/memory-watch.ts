@@ -0,0 +1,116 @@
+/**
+ * Memory Watches (--watch-memory)
+ *
+ * Samples a few bytes of the debuggee's memory ("&counter:8") at every stop, and on every
+ * write where the adapter can set a hardware watchpoint (data breakpoint) on them. Each
+ * sample that differs from the last one is a change, which makes it possible to see when a
+ * buffer shared across an FFI boundary got overwritten and by which code.
+ */
+
+import type { MemoryChangeReason, MemoryWatchSummary } from '../output/events.js';
+
+export interface MemoryWatchSpec {
+  /** Expression that evaluates to the address ("&counter", "buf->data", "0x7ffd5e0c") */
+  expression: string;
+  /** Bytes to watch */
+  size: number;
+}
+
+/** Largest region a watch may cover */
+const MAX_WATCH_BYTES = 4096;
+
+/**
+ * Parse a --watch-memory spec ("<address expression>:<bytes>")
+ */
+export function parseMemoryWatch(spec: string): MemoryWatchSpec {
+  const match = spec.match(/^(.+):(\d+)$/s);
+  const size = match ? parseInt(match[2], 10) : 0;
+  if (!match || !match[1].trim() || size < 1 || size > MAX_WATCH_BYTES) {
+    throw new Error(
+      `Invalid --watch-memory "${spec}": expected <address expression>:<bytes> ` +
+        `with 1-${MAX_WATCH_BYTES} bytes (e.g., "&counter:8")`
+    );
+  }
+  return { expression: match[1].trim(), size };
+}
+
+/**
+ * Pull the address out of an evaluate result ("(int *) 0x00007ffd5e0c", "0x1000 <buf>")
+ */
+export function parseAddress(result: string): string | undefined {
+  return result.match(/\b0x[0-9a-f]+\b/i)?.[0];
+}
+
+/**
+ * Hex string for readMemory's base64 data ("2a00000000000000")
+ */
+export function decodeMemory(data: string): string {
+  return Buffer.from(data, 'base64').toString('hex');
+}
+
+export interface MemoryChange {
+  expression: string;
+  address: string;
+  size: number;
+  before: string;
+  after: string;
+  reason: MemoryChangeReason;
+}
+
+export class MemoryWatchLog {
+  private watches = new Map<string, MemoryWatchSummary>();
+  /** Watches whose failure has been reported */
+  private reported = new Set<string>();
+
+  constructor(specs: MemoryWatchSpec[]) {
+    for (const spec of specs) {
+      this.watches.set(spec.expression, { ...spec, changes: 0 });
+    }
+  }
+
+  /**
+   * Record a sample and return the change it shows, if any. The first sample is the
+   * baseline.
+   */
+  record(
+    expression: string,
+    address: string,
+    value: string,
+    reason: MemoryChangeReason
+  ): MemoryChange | undefined {
+    const watch = this.watches.get(expression);
+    if (!watch) return undefined;
+    const before = watch.address === address ? watch.value : undefined;
+    watch.address = address;
+    watch.value = value;
+    watch.initial ??= value;
+    delete watch.error;
+    if (before === undefined || before === value) return undefined;
+    watch.changes++;
+    return { expression, address, size: watch.size, before, after: value, reason };
+  }
+
+  /**
+   * Record a failed sample. Returns true the first time, so the caller reports it once.
+   */
+  fail(expression: string, error: string): boolean {
+    const watch = this.watches.get(expression);
+    if (!watch) return false;
+    watch.error = error;
+    if (this.reported.has(expression)) return false;
+    this.reported.add(expression);
+    return true;
+  }
+
+  /**
+   * Note that a hardware watchpoint covers this watch
+   */
+  setWatchpoint(expression: string, installed: boolean): void {
+    const watch = this.watches.get(expression);
+    if (watch) watch.watchpoint = installed;
+  }
+
+  list(): MemoryWatchSummary[] {
+    return [...this.watches.values()].map((watch) => ({ ...watch }));
+  }
+}
@@ -0,0 +1,273 @@
+/**
+ * Mixed-Mode Sessions: Python with Native Extensions (--native-break)
+ *
+ * debugpy only sees Python frames, so a breakpoint in a C extension needs a second adapter.
+ * The companion attaches lldb to the process debugpy launched (held at entry until it's
+ * ready), sets the --native-break breakpoints, and reports their hits as breakpoint_hit
+ * events in the same session. The thread that stopped in native code still holds the GIL,
+ * so lldb can ask the interpreter for the Python stack that called into the extension.
+ */
+
+import { DapClient } from '../dap/client.js';
+import { SocketDapClient } from '../dap/socket-client.js';
+import type { IDapClient } from '../dap/client-interface.js';
+import type { StoppedEventBody } from '../dap/protocol.js';
+import type { AdapterConfig } from '../adapters/base.js';
+import { getLldbAdapterType, parseLldbExpressionOutput } from '../adapters/lldb.js';
+import type { OutputFormatter } from '../output/formatter.js';
+import type { BreakpointHitEvent, SourceLocation, StackFrameInfo } from '../output/events.js';
+import { BreakpointManager } from './breakpoints.js';
+import { VariableInspector } from './variables.js';
+import { logger } from '../util/logger.js';
+
+/** Primary adapters whose programs can have a native companion */
+export const MIXED_MODE_ADAPTERS = ['python'];
+
+/** Longest string lldb shows in full, so deep Python stacks aren't cut off */
+const STRING_SUMMARY_LENGTH = 65536;
+
+/**
+ * A call through a function pointer cast, since libpython's exports usually come without
+ * debug info and lldb won't call a function whose return type it doesn't know
+ */
+function cCall(returns: string, name: string, params: string, args: string): string {
+  return `((${returns} (*)(${params}))${name})(${args})`;
+}
+
+/**
+ * "".join(traceback.format_stack()) for the stopped thread, or a null pointer when it
+ * doesn't hold the GIL (the extension released it) and the interpreter can't be called.
+ * The few references it creates are leaked, which a debug session can afford.
+ */
+const PYTHON_STACK_EXPRESSION = (() => {
+  const traceback = cCall('void *', 'PyImport_ImportModule', 'const char *', '"traceback"');
+  const frames = cCall(
+    'void *',
+    'PyObject_CallMethod',
+    'void *, const char *, const char *, ...',
+    `${traceback}, "format_stack", (const char *)0`
+  );
+  const empty = cCall('void *', 'PyUnicode_FromString', 'const char *', '""');
+  const joined = cCall('void *', 'PyUnicode_Join', 'void *, void *', `${empty}, ${frames}`);
+  const text = cCall('const char *', 'PyUnicode_AsUTF8', 'void *', joined);
+  return `${cCall('int', 'PyGILState_Check', 'void', '')} ? ${text} : (const char *)0`;
+})();
+
+/**
+ * An lldb command for the repl, which lldb-dap only runs with a backtick prefix
+ */
+function lldbCommand(command: string): string {
+  return `${getLldbAdapterType() === 'codelldb' ? '' : '`'}${command}`;
+}
+
+/**
+ * The text of a C string result ('0x7f0... "text"'), undefined for a null pointer
+ */
+export function lldbStringResult(result: string): string | undefined {
+  const match = result.match(/"((?:[^"\\]|\\.)*)"/);
+  if (!match) return undefined;
+  const escapes: Record<string, string> = { n: '\n', t: '\t', r: '\r', '0': '\0' };
+  return match[1].replace(/\\(.)/g, (_, char: string) => escapes[char] ?? char);
+}
+
+/**
+ * Frames of Python's traceback.format_stack() output, innermost first
+ */
+export function parsePythonStack(text: string): SourceLocation[] {
+  const frames: SourceLocation[] = [];
+  for (const match of text.matchAll(/^\s*File "(.+)", line (\d+), in (.+)$/gm)) {
+    frames.push({ file: match[1], line: parseInt(match[2], 10), function: match[3] });
+  }
+  return frames.reverse();
+}
+
+export interface MixedModeConfig {
+  /** The native adapter (lldb) */
+  adapter: AdapterConfig;
+  /** --native-break specs: file:line in the extension's C/C++/Rust sources */
+  breakpoints: string[];
+}
+
+export interface NativeCompanionOptions extends MixedModeConfig {
+  cwd?: string;
+  timeout?: number;
+  /** Capture the native frame's locals at each hit */
+  captureLocals?: boolean;
+}
+
+/**
+ * The native adapter attached alongside the session's own. Hits are handed to onHit and
+ * the stopped thread continues once it returns.
+ */
+export class NativeCompanion {
+  private client: IDapClient | null = null;
+  private breakpointManager: BreakpointManager | null = null;
+  private variableInspector: VariableInspector | null = null;
+
+  constructor(
+    private readonly options: NativeCompanionOptions,
+    private readonly formatter: OutputFormatter,
+    private readonly onHit: (event: BreakpointHitEvent) => void
+  ) {}
+
+  get attached(): boolean {
+    return this.client !== null;
+  }
+
+  /**
+   * Attach the native adapter to the process and set the native breakpoints
+   */
+  async attach(pid: number): Promise<void> {
+    const { adapter } = this.options;
+    const clientOptions = {
+      command: adapter.command,
+      args: adapter.args,
+      cwd: this.options.cwd,
+      env: adapter.env,
+      timeout: this.options.timeout,
+    };
+    const client: IDapClient =
+      adapter.transport === 'socket' && adapter.socketPort
+        ? new SocketDapClient({ ...clientOptions, port: adapter.socketPort })
+        : new DapClient(clientOptions);
+    this.client = client;
+
+    const span = logger.span('mixed-mode', { adapter: adapter.name, pid });
+    try {
+      client.on('stopped', (body: StoppedEventBody) => {
+        this.handleStopped(body).catch((error) => {
+          logger.warn('mixed-mode', 'native stop not captured', {
+            error: error instanceof Error ? error.message : String(error),
+          });
+        });
+      });
+      await client.connect();
+      await client.initialize({ adapterID: adapter.id });
+      await this.repl(`settings set target.max-string-summary-length ${STRING_SUMMARY_LENGTH}`);
+      this.breakpointManager = new BreakpointManager(client, this.formatter, {
+        cwd: this.options.cwd,
+        adapterType: adapter.name,
+      });
+      this.variableInspector = new VariableInspector(client);
+      for (const spec of this.options.breakpoints) {
+        this.breakpointManager.addBreakpoint(spec);
+      }
+      await this.breakpointManager.setAllBreakpoints();
+      await client.attach(adapter.attachConfig({ pid }));
+      await client.configurationDone();
+      span.end();
+    } catch (error) {
+      span.end({ error: error instanceof Error ? error.message : String(error) });
+      await this.detach();
+      throw error;
+    }
+  }
+
+  /**
+   * Detach, leaving the process running for the session's own adapter
+   */
+  async detach(): Promise<void> {
+    const client = this.client;
+    this.client = null;
+    if (!client) return;
+    if (client.isConnected()) {
+      await client.disconnect(false).catch(() => undefined);
+    }
+    await client.reapProcessTree();
+  }
+
+  private async repl(command: string, frameId?: number): Promise<string | undefined> {
+    try {
+      const response = await this.client!.evaluate({
+        expression: lldbCommand(command),
+        frameId,
+        context: 'repl',
+      });
+      return response.result;
+    } catch (error) {
+      logger.debug('mixed-mode', 'lldb command failed', {
+        command,
+        error: error instanceof Error ? error.message : String(error),
+      });
+      return undefined;
+    }
+  }
+
+  /**
+   * The Python stack that called into the native frame
+   *
+   * @throws Error when the interpreter can't be asked
+   */
+  private async pythonStack(frameId: number): Promise<SourceLocation[]> {
+    const output = await this.repl(
+      `expression --try-all-threads false -- ${PYTHON_STACK_EXPRESSION}`,
+      frameId
+    );
+    if (output === undefined) {
+      throw new Error('lldb could not evaluate the Python stack');
+    }
+    const text = lldbStringResult(parseLldbExpressionOutput(output).result);
+    if (text === undefined) {
+      throw new Error('The thread released the GIL, so the interpreter could not be asked');
+    }
+    return parsePythonStack(text);
+  }
+
+  private async handleStopped(body: StoppedEventBody): Promise<void> {
+    const client = this.client;
+    const threadId = body.threadId;
+    if (!client || threadId === undefined) return;
+    // Only --native-break stops are reported; the rest (attach, signals) carry on
+    if (body.reason !== 'breakpoint') {
+      logger.debug('mixed-mode', 'native stop resumed', { reason: body.reason, threadId });
+      await client.continue({ threadId });
+      return;
+    }
+
+    const { stackFrames } = await client.stackTrace({ threadId, levels: 20 });
+    const top = stackFrames[0];
+    const stackTrace: StackFrameInfo[] = stackFrames.map((frame) => ({
+      frameId: frame.id,
+      function: frame.name,
+      file: frame.source?.path ?? null,
+      line: frame.line ?? null,
+      column: frame.column ?? null,
+      module: frame.source?.name,
+    }));
+    let pythonStack: SourceLocation[] | undefined;
+    let pythonStackError: string | undefined;
+    if (top) {
+      try {
+        pythonStack = await this.pythonStack(top.id);
+      } catch (error) {
+        pythonStackError = error instanceof Error ? error.message : String(error);
+      }
+    }
+
+    this.onHit({
+      type: 'breakpoint_hit',
+      timestamp: new Date().toISOString(),
+      id: body.hitBreakpointIds?.[0],
+      threadId,
+      location: {
+        file: top?.source?.path ?? 'unknown',
+        line: top?.line ?? 0,
+        column: top?.column,
+        function: top?.name,
+        address: top?.instructionPointerReference,
+      },
+      stackTrace,
+      locals:
+        top && this.options.captureLocals !== false
+          ? await this.variableInspector!.getLocals(top.id)
+          : {},
+      native: true,
+      pythonStack,
+      pythonStackError,
+    });
+    // The session may have ended (--max-hits) and detached while the hit was handled
+    if (this.client) {
+      await this.client.continue({ threadId });
+    }
+  }
+}
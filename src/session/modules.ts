@@ -0,0 +1,69 @@
+/**
+ * Loaded Modules (--modules)
+ *
+ * Tracks the modules (DLLs, shared libraries, Python modules) an adapter reports through
+ * module events and the modules request. A module whose symbols never loaded is the usual
+ * reason a breakpoint in it stays unverified, so session_end lists them with their
+ * symbol status.
+ */
+
+import type { Module, ModuleEventBody } from '../dap/protocol.js';
+import type { ModuleInfo } from '../output/events.js';
+
+/**
+ * Whether a symbol status text says the symbols loaded ("Symbols loaded." but not
+ * "Skipped loading symbols." or "Symbols not loaded")
+ */
+export function symbolsLoaded(status: string | undefined): boolean | undefined {
+  if (!status) return undefined;
+  return /\bloaded\b/i.test(status) && !/\b(not|no|failed|cannot)\b/i.test(status);
+}
+
+/**
+ * Convert a DAP module to the output format
+ */
+export function toModuleInfo(module: Module): ModuleInfo {
+  const loaded = symbolsLoaded(module.symbolStatus);
+  return {
+    name: module.name,
+    ...(module.path && { path: module.path }),
+    ...(module.version && { version: module.version }),
+    ...(loaded !== undefined && { symbolsLoaded: loaded }),
+    ...(module.symbolStatus && { symbolStatus: module.symbolStatus }),
+    ...(module.symbolFilePath && { symbolFilePath: module.symbolFilePath }),
+    ...(module.isUserCode !== undefined && { isUserCode: module.isUserCode }),
+  };
+}
+
+export class ModuleTracker {
+  private modules = new Map<string, ModuleInfo>();
+
+  /**
+   * Apply a module event (new, changed or removed)
+   */
+  update(body: ModuleEventBody): void {
+    const id = String(body.module.id);
+    if (body.reason === 'removed') {
+      this.modules.delete(id);
+    } else {
+      this.modules.set(id, toModuleInfo(body.module));
+    }
+  }
+
+  /**
+   * Add the modules from a modules response, keeping load order for ones already seen
+   */
+  merge(modules: Module[]): void {
+    for (const module of modules) {
+      this.modules.set(String(module.id), toModuleInfo(module));
+    }
+  }
+
+  list(): ModuleInfo[] {
+    return [...this.modules.values()];
+  }
+
+  clear(): void {
+    this.modules.clear();
+  }
+}
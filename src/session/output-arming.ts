@@ -0,0 +1,62 @@
+/**
+ * Breakpoint Arming on Program Output (--arm-on-output "Server listening on")
+ *
+ * Servers and batch jobs can pass a breakpoint thousands of times while they start up.
+ * With --arm-on-output the adapter gets no breakpoints until the program prints a line
+ * matching the pattern, so the warm-up runs without a single stop; a restarted program
+ * has its breakpoints removed until it prints the line again.
+ */
+
+/** Longest unfinished line kept while waiting for the rest of it */
+const MAX_PARTIAL_LINE = 4096;
+
+/**
+ * A --arm-on-output pattern as a regular expression
+ *
+ * @throws Error for an invalid regular expression
+ */
+export function parseArmPattern(pattern: string): RegExp {
+  try {
+    return new RegExp(pattern);
+  } catch (error) {
+    throw new Error(`Invalid --arm-on-output pattern "${pattern}": ${(error as Error).message}`);
+  }
+}
+
+/**
+ * Watches program output, a line at a time per stream, for the line that arms breakpoints
+ */
+export class OutputArming {
+  private partial = new Map<string, string>();
+  private armedBy: string | null = null;
+
+  constructor(readonly pattern: RegExp) {}
+
+  get armed(): boolean {
+    return this.armedBy !== null;
+  }
+
+  /**
+   * Feed a chunk of a stream's output. Returns the matching line the first time one
+   * matches; an unfinished line is tested too, for prompts without a newline.
+   */
+  feed(category: string, output: string): string | undefined {
+    if (this.armed) return undefined;
+    const lines = ((this.partial.get(category) ?? '') + output).split(/\r?\n/);
+    const rest = lines[lines.length - 1];
+    this.partial.set(category, rest.slice(-MAX_PARTIAL_LINE));
+    const match = lines.find((line) => this.pattern.test(line));
+    if (match === undefined) return undefined;
+    this.armedBy = match;
+    this.partial.clear();
+    return match;
+  }
+
+  /**
+   * Wait for the line again (the program was restarted)
+   */
+  disarm(): void {
+    this.armedBy = null;
+    this.partial.clear();
+  }
+}
@@ -0,0 +1,80 @@
+/**
+ * Adapter Progress
+ *
+ * Tracks DAP progress reports (progressStart/progressUpdate/progressEnd). js-debug sends
+ * them while it loads source maps, for example, so a long quiet stretch during startup
+ * shows what the adapter is doing: live in the status line and afterwards in session_end.
+ */
+
+import type {
+  ProgressEndEventBody,
+  ProgressStartEventBody,
+  ProgressUpdateEventBody,
+} from '../dap/protocol.js';
+import type { ProgressRecord } from '../output/events.js';
+
+interface ActiveProgress {
+  title: string;
+  message?: string;
+  percentage?: number;
+  startedAt: number;
+}
+
+export class ProgressTracker {
+  private active = new Map<string, ActiveProgress>();
+  private completed: ProgressRecord[] = [];
+
+  start(body: ProgressStartEventBody, now: number = Date.now()): void {
+    this.active.set(body.progressId, {
+      title: body.title,
+      message: body.message,
+      percentage: body.percentage,
+      startedAt: now,
+    });
+  }
+
+  update(body: ProgressUpdateEventBody): void {
+    const progress = this.active.get(body.progressId);
+    if (!progress) return;
+    if (body.message !== undefined) progress.message = body.message;
+    if (body.percentage !== undefined) progress.percentage = body.percentage;
+  }
+
+  end(body: ProgressEndEventBody, now: number = Date.now()): void {
+    const progress = this.active.get(body.progressId);
+    if (!progress) return;
+    this.active.delete(body.progressId);
+    const message = body.message ?? progress.message;
+    this.completed.push({
+      title: progress.title,
+      ...(message && { message }),
+      startedAt: new Date(progress.startedAt).toISOString(),
+      durationMs: now - progress.startedAt,
+    });
+  }
+
+  /**
+   * Status line text for the latest report still in progress ("Loading source maps: 12/40
+   * (30%)"), or undefined when nothing is in progress
+   */
+  describe(): string | undefined {
+    const progress = [...this.active.values()].pop();
+    if (!progress) return undefined;
+    const message = progress.message ? `: ${progress.message}` : '';
+    const percentage =
+      progress.percentage !== undefined ? ` (${Math.round(progress.percentage)}%)` : '';
+    return `${progress.title}${message}${percentage}`;
+  }
+
+  /**
+   * Finished reports, in the order they finished
+   */
+  list(): ProgressRecord[] {
+    return [...this.completed];
+  }
+
+  clear(): void {
+    this.active.clear();
+    this.completed = [];
+  }
+}
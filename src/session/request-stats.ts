@@ -0,0 +1,81 @@
+/**
+ * DAP Request Statistics (--request-stats)
+ *
+ * Times every request debug-run sends the adapter, from sending it to its response, and
+ * totals them per command. When stops are slow, session_end then shows whether the time went
+ * into many small round trips (variables, scopes) or a few slow requests (evaluate).
+ */
+
+import type { ProtocolMessage, Request, Response } from '../dap/protocol.js';
+import type { RequestStatsRecord } from '../output/events.js';
+
+interface CommandTotals {
+  count: number;
+  failed: number;
+  totalMs: number;
+  maxMs: number;
+}
+
+export class RequestStats {
+  /** Send time and command of requests awaiting a response, by session and seq */
+  private pending = new Map<string, { command: string; sentAt: number }>();
+  private commands = new Map<string, CommandTotals>();
+
+  /**
+   * Record a DAP message; session is set for messages on a child debug session (js-debug)
+   */
+  protocolMessage(
+    direction: 'send' | 'receive',
+    message: ProtocolMessage,
+    session?: string,
+    now: number = performance.now()
+  ): void {
+    if (direction === 'send' && message.type === 'request') {
+      const { command } = message as Request;
+      this.pending.set(`${session ?? ''}:${message.seq}`, { command, sentAt: now });
+      return;
+    }
+    if (direction !== 'receive' || message.type !== 'response') return;
+
+    const response = message as Response;
+    const key = `${session ?? ''}:${response.request_seq}`;
+    const request = this.pending.get(key);
+    if (!request) return;
+    this.pending.delete(key);
+
+    const elapsed = now - request.sentAt;
+    const totals = this.commands.get(request.command) ?? {
+      count: 0,
+      failed: 0,
+      totalMs: 0,
+      maxMs: 0,
+    };
+    totals.count++;
+    if (!response.success) totals.failed++;
+    totals.totalMs += elapsed;
+    totals.maxMs = Math.max(totals.maxMs, elapsed);
+    this.commands.set(request.command, totals);
+  }
+
+  /**
+   * Totals per command, the most time-consuming first (times rounded to 0.1ms)
+   */
+  list(): RequestStatsRecord[] {
+    const round = (ms: number) => Math.round(ms * 10) / 10;
+    return [...this.commands]
+      .sort(([, a], [, b]) => b.totalMs - a.totalMs)
+      .map(([command, totals]) => ({
+        command,
+        count: totals.count,
+        ...(totals.failed > 0 && { failed: totals.failed }),
+        totalMs: round(totals.totalMs),
+        meanMs: round(totals.totalMs / totals.count),
+        maxMs: round(totals.maxMs),
+      }));
+  }
+
+  clear(): void {
+    this.pending.clear();
+    this.commands.clear();
+  }
+}
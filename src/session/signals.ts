@@ -0,0 +1,59 @@
+/**
+ * Native Signal Stops
+ *
+ * lldb and gdb stop the debuggee when it receives a fatal signal. Adapters describe the
+ * signal differently: lldb-dap reports reason "exception" with "signal SIGSEGV: invalid
+ * address (fault address: 0x0)" on Linux and a Mach exception ("EXC_BAD_ACCESS (code=1,
+ * address=0x0)") on macOS, CodeLLDB and gdb report reason "signal" with the name in the
+ * text. This module turns those stops into a structured signal description.
+ */
+
+import { constants as osConstants } from 'node:os';
+import type { StoppedEventBody } from '../dap/protocol.js';
+import type { SignalInfo } from '../output/events.js';
+
+/** Signals that end the program unless it handles them */
+export const FATAL_SIGNALS: ReadonlySet<string> = new Set([
+  'SIGSEGV',
+  'SIGBUS',
+  'SIGILL',
+  'SIGFPE',
+  'SIGABRT',
+  'SIGSYS',
+]);
+
+/** Stop reasons adapters use for signals */
+const SIGNAL_STOP_REASONS = new Set(['signal', 'signal-received', 'exception']);
+
+/** Mach exceptions (macOS) and the signal each one becomes */
+const MACH_EXCEPTION_SIGNALS: Record<string, string> = {
+  EXC_BAD_ACCESS: 'SIGSEGV',
+  EXC_BAD_INSTRUCTION: 'SIGILL',
+  EXC_ARITHMETIC: 'SIGFPE',
+  EXC_CRASH: 'SIGABRT',
+};
+
+const FAULT_ADDRESS_PATTERN = /\b(?:fault address|address)\s*[:=]\s*(0x[0-9a-f]+)/i;
+
+/**
+ * Describe a fatal signal stop, or return undefined for any other stop (breakpoints,
+ * language exceptions, signals the program survives)
+ */
+export function parseFatalSignal(body: StoppedEventBody): SignalInfo | undefined {
+  if (!SIGNAL_STOP_REASONS.has(body.reason)) return undefined;
+
+  const text = [body.text, body.description].filter(Boolean).join(' ');
+  const name =
+    text.match(/\bSIG[A-Z0-9]+\b/)?.[0] ??
+    MACH_EXCEPTION_SIGNALS[text.match(/\bEXC_[A-Z_]+\b/)?.[0] ?? ''];
+  if (!name || !FATAL_SIGNALS.has(name)) return undefined;
+
+  const number = osConstants.signals[name as NodeJS.Signals];
+  const faultAddress = text.match(FAULT_ADDRESS_PATTERN)?.[1];
+  return {
+    name,
+    ...(number !== undefined && { number }),
+    ...(text && { description: text }),
+    ...(faultAddress && { faultAddress }),
+  };
+}
@@ -0,0 +1,62 @@
+/**
+ * Source Snippets
+ *
+ * Reads a few lines of source around a stop location so events can be interpreted
+ * without re-opening the file. Files are read once per session and cached.
+ */
+
+import * as fs from 'node:fs';
+import type { SourceSnippet } from '../output/events.js';
+
+/**
+ * Render source lines with right-aligned line numbers and a ">" marker on the current line
+ */
+export function formatSnippetLines(
+  lines: string[],
+  startLine: number,
+  currentLine: number
+): string[] {
+  const width = String(startLine + lines.length - 1).length;
+  return lines.map((text, index) => {
+    const lineNumber = startLine + index;
+    const marker = lineNumber === currentLine ? '>' : ' ';
+    return `${marker} ${String(lineNumber).padStart(width)} | ${text}`;
+  });
+}
+
+export class SourceSnippetReader {
+  private cache: Map<string, string[] | null> = new Map();
+
+  /**
+   * Get `context` lines before and after `line` (1-based), or undefined if the file
+   * can't be read or the line is out of range
+   */
+  read(file: string, line: number, context: number): SourceSnippet | undefined {
+    const fileLines = this.lines(file);
+    if (!fileLines || line < 1 || line > fileLines.length) {
+      return undefined;
+    }
+
+    const startLine = Math.max(1, line - context);
+    const endLine = Math.min(fileLines.length, line + context);
+    return {
+      startLine,
+      endLine,
+      lines: formatSnippetLines(fileLines.slice(startLine - 1, endLine), startLine, line),
+    };
+  }
+
+  /**
+   * A file's lines, or null if it can't be read
+   */
+  lines(file: string): string[] | null {
+    if (!this.cache.has(file)) {
+      try {
+        this.cache.set(file, fs.readFileSync(file, 'utf-8').split(/\r?\n/));
+      } catch {
+        this.cache.set(file, null);
+      }
+    }
+    return this.cache.get(file) ?? null;
+  }
+}
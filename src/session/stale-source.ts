@@ -0,0 +1,109 @@
+/**
+ * Stale Source Detection
+ *
+ * Catches the "edited but not rebuilt" case: the file on disk no longer matches what the
+ * program is running, so breakpoints land on the wrong lines and locals don't match the
+ * code. Adapters that know what they loaded say so either with checksums on a source
+ * (vsdbg reports the PDB's document hashes) or by serving the loaded content through a
+ * source reference; both are compared against the local file.
+ */
+
+import { createHash } from 'node:crypto';
+import * as fs from 'node:fs';
+import type { Checksum, ChecksumAlgorithm } from '../dap/protocol.js';
+
+/** Node crypto names for the DAP checksum algorithms ("timestamp" can't be compared) */
+const HASH_ALGORITHMS: Partial<Record<ChecksumAlgorithm, string>> = {
+  MD5: 'md5',
+  SHA1: 'sha1',
+  SHA256: 'sha256',
+};
+
+export interface SourceMismatch {
+  reason: 'checksum' | 'content';
+  /** Checksum algorithm, for checksum mismatches */
+  algorithm?: ChecksumAlgorithm;
+  /** What the adapter has loaded vs what is on disk (checksums only) */
+  expected?: string;
+  actual?: string;
+}
+
+/**
+ * The file as it might have been hashed: as-is, and with the other line ending style
+ * (a checkout on another platform changes line endings without changing the code)
+ */
+function lineEndingVariants(content: Buffer): Buffer[] {
+  const text = content.toString('utf-8');
+  const lf = text.replace(/\r\n/g, '\n');
+  return [content, Buffer.from(lf), Buffer.from(lf.replace(/\n/g, '\r\n'))];
+}
+
+/**
+ * Compare the adapter's checksums for a file with the file's content. Returns undefined
+ * when any checksum matches or none can be compared.
+ */
+export function compareChecksums(
+  content: Buffer,
+  checksums: Checksum[]
+): SourceMismatch | undefined {
+  let mismatch: SourceMismatch | undefined;
+  for (const { algorithm, checksum } of checksums) {
+    const hash = HASH_ALGORITHMS[algorithm];
+    if (!hash) continue;
+    const actual = lineEndingVariants(content).map((variant) =>
+      createHash(hash).update(variant).digest('hex')
+    );
+    const expected = checksum.toLowerCase();
+    if (actual.includes(expected)) return undefined;
+    mismatch ??= { reason: 'checksum', algorithm, expected, actual: actual[0] };
+  }
+  return mismatch;
+}
+
+/**
+ * Compare the source the adapter loaded with the file on disk, ignoring line endings and
+ * trailing newlines
+ */
+export function compareContent(content: Buffer, loaded: string): SourceMismatch | undefined {
+  const normalize = (text: string) => text.replace(/\r\n/g, '\n').replace(/\n+$/, '');
+  return normalize(content.toString('utf-8')) === normalize(loaded)
+    ? undefined
+    : { reason: 'content' };
+}
+
+export class StaleSourceChecker {
+  private checked = new Set<string>();
+
+  /**
+   * Whether a file still needs checking (each file is checked once per session)
+   */
+  shouldCheck(file: string): boolean {
+    return !this.checked.has(file) && fs.existsSync(file);
+  }
+
+  /**
+   * Check a file against the adapter's checksums or loaded content. Returns the mismatch
+   * the first time a file is checked, if it's stale.
+   */
+  check(
+    file: string,
+    evidence: { checksums?: Checksum[]; loadedContent?: string }
+  ): SourceMismatch | undefined {
+    if (this.checked.has(file)) return undefined;
+    let content: Buffer;
+    try {
+      content = fs.readFileSync(file);
+    } catch {
+      return undefined;
+    }
+    const mismatch = evidence.checksums?.length
+      ? compareChecksums(content, evidence.checksums)
+      : evidence.loadedContent !== undefined
+        ? compareContent(content, evidence.loadedContent)
+        : undefined;
+    if (evidence.checksums?.length || evidence.loadedContent !== undefined) {
+      this.checked.add(file);
+    }
+    return mismatch;
+  }
+}
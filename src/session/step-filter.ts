@@ -0,0 +1,29 @@
+/**
+ * Step Filters (--step-filter)
+ *
+ * Just My Code keeps the .NET debuggers out of framework assemblies without symbols, but
+ * stepping still descends into libraries that ship them (a logging package, an ORM). A
+ * step that lands in a frame whose function is in a filtered namespace is stepped back out
+ * of instead of being reported.
+ */
+
+/**
+ * The fully qualified function name of a stack frame: vsdbg prefixes it with the module
+ * ("App.dll!App.Orders.Checkout(...)"), both .NET debuggers append the parameter list
+ */
+export function qualifiedFunctionName(frameName: string): string {
+  const name = frameName.slice(frameName.indexOf('!') + 1);
+  const paren = name.indexOf('(');
+  return (paren === -1 ? name : name.slice(0, paren)).trim();
+}
+
+/**
+ * Whether a frame's function is in one of the namespaces (or is one of the types) given
+ */
+export function matchesStepFilter(frameName: string, namespaces: string[]): boolean {
+  const name = qualifiedFunctionName(frameName);
+  return namespaces.some((namespace) => {
+    const prefix = namespace.replace(/\.\*?$/, '');
+    return name === prefix || name.startsWith(`${prefix}.`);
+  });
+}
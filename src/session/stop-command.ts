@@ -0,0 +1,164 @@
+/**
+ * Stop Commands (--on-stop-exec "./scripts/capture_db_state.sh {order_id}")
+ *
+ * Runs an external command at each breakpoint hit, while the program is still paused, and
+ * attaches its output to the breakpoint_hit event, so in-process state can be lined up with
+ * the outside world at the same moment (the database row, the queue depth). {name}
+ * placeholders take captured values: a local ({order_id}), a path into one ({order.Id}) or an
+ * --eval expression; anything else is evaluated in the stopped frame. {{ and }} stand for
+ * literal braces (jq '{{a: .b}}'). The command is run directly, not through a shell, so values
+ * can't inject shell syntax.
+ */
+
+import { spawn } from 'node:child_process';
+import type { EvaluationResult, StopCommandResult, VariableValue } from '../output/events.js';
+import { splitCommandLine } from '../util/npm-scripts.js';
+
+/** How long a stop command may run before it is killed */
+export const STOP_COMMAND_TIMEOUT_MS = 30_000;
+
+/** Output kept per stream; the rest is cut off */
+export const MAX_STOP_COMMAND_OUTPUT = 64 * 1024;
+
+/** A {name} placeholder, or a {{ or }} escape (which captures no name) */
+const PLACEHOLDER_PATTERN = /\{\{|\}\}|\{\s*([^{}\s][^{}]*?)\s*\}/g;
+
+/**
+ * Split an --on-stop-exec command line into words
+ *
+ * @throws Error if the command is empty
+ */
+export function parseStopCommand(command: string): string[] {
+  const words = splitCommandLine(command);
+  if (words.length === 0) {
+    throw new Error('--on-stop-exec needs a command');
+  }
+  return words;
+}
+
+/**
+ * Every distinct placeholder in a command's words, in order
+ */
+export function stopCommandPlaceholders(words: string[]): string[] {
+  const names = words.flatMap((word) =>
+    [...word.matchAll(PLACEHOLDER_PATTERN)].flatMap((match) => match[1] ?? [])
+  );
+  return [...new Set(names)];
+}
+
+/** A captured value without the type and reference bookkeeping of each level */
+function plainValue(variable: VariableValue): unknown {
+  const value = variable.value;
+  if (typeof value !== 'object' || value === null) return value;
+  // Collections are { type, count, items }, objects map member names to values
+  const record = value as Record<string, unknown>;
+  if (Array.isArray(record.items) && 'count' in record) {
+    return (record.items as VariableValue[]).map(plainValue);
+  }
+  const members = Object.entries(record as Record<string, VariableValue>);
+  return Object.fromEntries(members.map(([name, member]) => [name, plainValue(member)]));
+}
+
+/** A value as command-line text: strings as-is, structured values as JSON */
+function valueText(variable: VariableValue): string {
+  const value = plainValue(variable);
+  if (typeof value === 'string') return value;
+  return typeof value === 'object' && value !== null ? JSON.stringify(value) : String(value);
+}
+
+/**
+ * An evaluation result as command-line text, without the quotes adapters put around strings
+ */
+export function evaluationText(evaluation: EvaluationResult): string {
+  if (typeof evaluation.value === 'object' && evaluation.value !== null) {
+    return valueText({ type: evaluation.type ?? 'object', value: evaluation.value });
+  }
+  const quoted = evaluation.result.match(/^(["'])(.*)\1$/s);
+  return quoted ? quoted[2] : evaluation.result;
+}
+
+/**
+ * A placeholder's value from what the stop captured (an --eval result, a local, or a dotted
+ * path into one), or undefined if it wasn't captured
+ */
+export function capturedValue(
+  name: string,
+  locals: Record<string, VariableValue>,
+  evaluations: Record<string, EvaluationResult> = {}
+): string | undefined {
+  const evaluation = evaluations[name];
+  if (evaluation && !evaluation.error) return evaluationText(evaluation);
+
+  const [root, ...path] = name.split('.');
+  let current: VariableValue | undefined = locals[root];
+  for (const key of path) {
+    const value = current?.value;
+    if (typeof value !== 'object' || value === null) return undefined;
+    const record = value as Record<string, unknown>;
+    const members = Array.isArray(record.items) ? record.items : record;
+    current = (members as Record<string, VariableValue | undefined>)[key];
+  }
+  return current === undefined ? undefined : valueText(current);
+}
+
+/**
+ * Fill in a command's placeholders and turn {{ and }} into single braces
+ */
+export function fillStopCommand(words: string[], values: Record<string, string>): string[] {
+  return words.map((word) =>
+    word.replace(PLACEHOLDER_PATTERN, (placeholder, name: string | undefined) =>
+      name === undefined ? placeholder[0] : (values[name] ?? placeholder)
+    )
+  );
+}
+
+/**
+ * Run a filled-in stop command, collecting its output. Failures are reported in the result.
+ */
+export function runStopCommand(
+  command: string[],
+  options: { cwd?: string; timeoutMs?: number } = {}
+): Promise<StopCommandResult> {
+  const started = Date.now();
+  const timeoutMs = options.timeoutMs ?? STOP_COMMAND_TIMEOUT_MS;
+
+  return new Promise((resolve) => {
+    const output = { stdout: '', stderr: '' };
+    let truncated = false;
+    let error: string | undefined;
+
+    const child = spawn(command[0], command.slice(1), {
+      cwd: options.cwd,
+      stdio: ['ignore', 'pipe', 'pipe'],
+    });
+    const timer = setTimeout(() => {
+      error = `Timed out after ${timeoutMs}ms`;
+      child.kill('SIGKILL');
+    }, timeoutMs);
+
+    // Decoded as text so a character split across chunks isn't mangled
+    const collect = (stream: 'stdout' | 'stderr') => (chunk: string) => {
+      const room = MAX_STOP_COMMAND_OUTPUT - output[stream].length;
+      if (chunk.length > room) truncated = true;
+      if (room > 0) output[stream] += chunk.slice(0, room);
+    };
+    child.stdout.setEncoding('utf8').on('data', collect('stdout'));
+    child.stderr.setEncoding('utf8').on('data', collect('stderr'));
+
+    child.on('error', (spawnError) => {
+      error = `Could not run ${command[0]}: ${spawnError.message}`;
+    });
+    child.on('close', (exitCode) => {
+      clearTimeout(timer);
+      resolve({
+        command,
+        exitCode: error ? null : exitCode,
+        stdout: output.stdout,
+        stderr: output.stderr,
+        durationMs: Date.now() - started,
+        ...(truncated && { truncated: true }),
+        ...(error && { error }),
+      });
+    });
+  });
+}
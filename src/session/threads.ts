@@ -0,0 +1,59 @@
+/**
+ * Thread Timeline
+ *
+ * Records when each thread started and exited (DAP thread events) and what it's called
+ * (threads request), so stops can name their thread and session_end can show which
+ * workers existed when.
+ */
+
+import type { Thread, ThreadEventBody } from '../dap/protocol.js';
+import type { ThreadLifetime } from '../output/events.js';
+
+export class ThreadTimeline {
+  private threads = new Map<number, ThreadLifetime>();
+
+  /**
+   * Apply a thread event (started or exited)
+   */
+  update(body: ThreadEventBody, at: Date = new Date()): void {
+    const thread = this.entry(body.threadId);
+    if (body.reason === 'started') {
+      thread.startedAt ??= at.toISOString();
+    } else if (body.reason === 'exited') {
+      thread.exitedAt = at.toISOString();
+    }
+  }
+
+  /**
+   * Record thread names from a threads response
+   */
+  nameThreads(threads: Thread[]): void {
+    for (const { id, name } of threads) {
+      this.entry(id).name = name;
+    }
+  }
+
+  nameOf(threadId: number): string | undefined {
+    return this.threads.get(threadId)?.name;
+  }
+
+  /**
+   * Every thread seen, in the order they were first seen
+   */
+  list(): ThreadLifetime[] {
+    return [...this.threads.values()];
+  }
+
+  clear(): void {
+    this.threads.clear();
+  }
+
+  private entry(threadId: number): ThreadLifetime {
+    let thread = this.threads.get(threadId);
+    if (!thread) {
+      thread = { id: threadId };
+      this.threads.set(threadId, thread);
+    }
+    return thread;
+  }
+}
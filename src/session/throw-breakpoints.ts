@@ -0,0 +1,124 @@
+/**
+ * Throw-Site Breakpoints (--break-throws src/services/)
+ *
+ * Some adapters have no exception filters, or only ones that stop after the stack has
+ * unwound. Breaking on the throw statements themselves (throw, raise, panic! and friends)
+ * captures the locals that led to the error where it was raised. Throw sites are found by a
+ * line scan in the files or directories given, with strings and comments left out.
+ */
+
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+import { findSourceFiles } from './ast-breakpoints.js';
+import { sourceExtensions } from './diff-breakpoints.js';
+
+/** A throw at the start of a statement, after a condition or label, or as a C# expression */
+const THROW_PATTERN = /(^|[;{}):]\s*|\belse\s+|\?\?\s*|=>\s*)throw\b/;
+
+/** Throw statements by language, keyed by file extension (THROW_PATTERN for the rest) */
+const LANGUAGE_PATTERNS: Record<string, RegExp> = {
+  '.py': /(^|:\s*)raise\b/,
+  '.rs': /(^|[^\w:])(panic|unreachable|todo|unimplemented)!\s*[([{]/,
+  '.go': /(^|[^\w.])panic\s*\(/,
+};
+
+/** Line comments, by file extension (// for the rest) */
+const COMMENT_PREFIXES: Record<string, RegExp> = {
+  '.py': /#.*$/,
+};
+
+/** String literals, which may mention "throw" without throwing */
+const STRING_PATTERN = /"(\\.|[^"\\])*"|'(\\.|[^'\\])*'|`(\\.|[^`\\])*`/g;
+/** In Rust ' also starts a lifetime, so only double-quoted strings are literals */
+const RUST_STRING_PATTERN = /"(\\.|[^"\\])*"/g;
+
+/**
+ * A line with its string literals emptied and trailing comment removed
+ */
+function codeOf(line: string, extension: string): string {
+  const code = line.replace(extension === '.rs' ? RUST_STRING_PATTERN : STRING_PATTERN, '""');
+  return code.replace(COMMENT_PREFIXES[extension] ?? /\/\/.*$/, '').trim();
+}
+
+/**
+ * Lines (1-based) of a file's throw statements
+ */
+export function throwSiteLines(lines: string[], file: string): number[] {
+  const extension = path.extname(file).toLowerCase();
+  const pattern = LANGUAGE_PATTERNS[extension] ?? THROW_PATTERN;
+  const python = extension === '.py';
+  const found: number[] = [];
+  // What ends the block comment (or Python docstring) the scan is in
+  let closer: string | null = null;
+
+  for (let index = 0; index < lines.length; index++) {
+    let text = lines[index].trim();
+    if (closer) {
+      const end = text.indexOf(closer);
+      if (end === -1) continue;
+      text = text.slice(end + closer.length).trim();
+      closer = null;
+    }
+
+    // A docstring opened but not closed on this line
+    const docstring = python && text.match(/^[rRuU]?("""|''')/);
+    if (docstring && !text.slice(docstring[0].length).includes(docstring[1])) {
+      closer = docstring[1];
+      continue;
+    }
+
+    let code = codeOf(text, extension).replace(/\/\*.*?\*\//g, '');
+    const opened = python ? -1 : code.indexOf('/*');
+    if (opened !== -1) {
+      closer = '*/';
+      code = code.slice(0, opened);
+    }
+    if (pattern.test(code)) found.push(index + 1);
+  }
+  return found;
+}
+
+/**
+ * Breakpoint specs (absolute path:line) on every throw site in the given files and
+ * directories. Directories are searched for source files in the adapter's languages,
+ * skipping dependency and build directories.
+ *
+ * @throws Error if a path doesn't exist or the paths have no throw sites
+ */
+export function resolveThrowBreakpoints(
+  paths: string[],
+  options: { cwd?: string; adapter?: string } = {}
+): string[] {
+  const cwd = options.cwd ?? process.cwd();
+  const extensions = sourceExtensions(options.adapter);
+  const specs = new Set<string>();
+
+  for (const given of paths) {
+    const resolved = path.resolve(cwd, given);
+    let files: string[];
+    try {
+      files = fs.statSync(resolved).isDirectory()
+        ? findSourceFiles(resolved, extensions)
+        : [resolved];
+    } catch {
+      throw new Error(`--break-throws "${given}": no such file or directory`);
+    }
+
+    for (const file of files) {
+      let content: string;
+      try {
+        content = fs.readFileSync(file, 'utf-8');
+      } catch (error) {
+        throw new Error(`Cannot read ${file}: ${(error as Error).message}`);
+      }
+      for (const line of throwSiteLines(content.split(/\r?\n/), file)) {
+        specs.add(`${file}:${line}`);
+      }
+    }
+  }
+
+  if (specs.size === 0) {
+    throw new Error(`--break-throws: no throw statements in ${paths.join(', ')}`);
+  }
+  return [...specs];
+}
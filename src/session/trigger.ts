@@ -0,0 +1,90 @@
+/**
+ * Start Triggers (--trigger-file /tmp/go, --trigger-signal USR1)
+ *
+ * Launches the program with its breakpoints bound but holds it at entry until a file appears
+ * or debug-run receives a signal, so a test harness can seed databases, start mocks or attach
+ * other tools before the first line runs under the debugger.
+ */
+
+import * as fs from 'node:fs';
+
+export interface SessionTrigger {
+  /** Start once this file exists */
+  file?: string;
+  /** Start once debug-run receives this signal */
+  signal?: NodeJS.Signals;
+}
+
+export type TriggerSource = 'file' | 'signal';
+
+/** How often the trigger file is checked for */
+export const TRIGGER_POLL_MS = 100;
+
+/** Signals a trigger may use; SIGINT and SIGTERM already interrupt the session */
+const TRIGGER_SIGNALS: NodeJS.Signals[] = ['SIGUSR1', 'SIGUSR2', 'SIGHUP'];
+
+/**
+ * A --trigger-signal name (USR1, SIGUSR1, usr2) as a signal
+ *
+ * @throws Error for a signal that can't be a trigger
+ */
+export function parseTriggerSignal(name: string): NodeJS.Signals {
+  const upper = name.trim().toUpperCase();
+  const signal = (upper.startsWith('SIG') ? upper : `SIG${upper}`) as NodeJS.Signals;
+  if (!TRIGGER_SIGNALS.includes(signal)) {
+    const names = TRIGGER_SIGNALS.map((allowed) => allowed.slice(3)).join(', ');
+    throw new Error(`--trigger-signal "${name}": use one of ${names}`);
+  }
+  return signal;
+}
+
+/**
+ * What starts a held program, as shell commands (touch /tmp/go or kill -USR1 4242)
+ */
+export function triggerHint(trigger: SessionTrigger, pid = process.pid): string {
+  const commands = [
+    ...(trigger.file ? [`touch ${trigger.file}`] : []),
+    ...(trigger.signal ? [`kill -${trigger.signal.slice(3)} ${pid}`] : []),
+  ];
+  return commands.join(' or ');
+}
+
+/**
+ * Wait for a trigger, starting now so one that fires before the program reaches entry isn't
+ * missed. `fired` resolves with what fired, or null once cancelled.
+ */
+export function armTrigger(
+  trigger: SessionTrigger,
+  pollMs = TRIGGER_POLL_MS
+): { fired: Promise<TriggerSource | null>; cancel: () => void } {
+  let release: (source: TriggerSource | null) => void = () => {};
+  const fired = new Promise<TriggerSource | null>((resolve) => {
+    release = resolve;
+  });
+
+  let poll: NodeJS.Timeout | undefined;
+  const onSignal = () => finish('signal');
+  const finish = (source: TriggerSource | null) => {
+    if (poll) clearInterval(poll);
+    if (trigger.signal) process.removeListener(trigger.signal, onSignal);
+    release(source);
+  };
+
+  if (trigger.signal) {
+    process.on(trigger.signal, onSignal);
+  }
+  const file = trigger.file;
+  if (file) {
+    if (fs.existsSync(file)) {
+      finish('file');
+    } else {
+      poll = setInterval(() => {
+        if (fs.existsSync(file)) finish('file');
+      }, pollMs);
+      // Waiting for the file shouldn't keep debug-run running once the session is over
+      poll.unref();
+    }
+  }
+
+  return { fired, cancel: () => finish(null) };
+}
@@ -5,8 +5,22 @@
  */
 
 import type { IDapClient } from '../dap/client-interface.js';
-import type { Variable as DapVariable } from '../dap/protocol.js';
-import type { VariableValue, VariableChange } from '../output/events.js';
+import type {
+  EvaluateArguments,
+  EvaluateResponse,
+  Scope,
+  Variable as DapVariable,
+} from '../dap/protocol.js';
+import type { SafeEvaluation } from '../adapters/base.js';
+import { parseScopedEvaluation } from './breakpoints.js';
+import type { CaptureBudget } from './capture-budget.js';
+import type {
+  EvaluationResult,
+  VariableProvenance,
+  VariableValue,
+  VariableChange,
+} from '../output/events.js';
+import { logger } from '../util/logger.js';
 
 /**
  * Property names that provide no debugging value and waste tokens.
@@ -84,6 +98,95 @@ const SERVICE_TYPE_PATTERNS = [
   /Handler$/,
 ];
 
+export type EvaluateContext = NonNullable<EvaluateArguments['context']>;
+
+export const EVALUATE_CONTEXTS: readonly EvaluateContext[] = [
+  'watch',
+  'repl',
+  'hover',
+  'clipboard',
+];
+
+/**
+ * Split an --eval spec into its expression and optional context prefix
+ * ("repl:order" evaluates "order" in the repl context)
+ */
+export function parseEvaluationSpec(spec: string): {
+  expression: string;
+  context?: EvaluateContext;
+} {
+  const separator = spec.indexOf(':');
+  const prefix = separator === -1 ? '' : spec.slice(0, separator);
+  if ((EVALUATE_CONTEXTS as readonly string[]).includes(prefix)) {
+    return { expression: spec.slice(separator + 1).trim(), context: prefix as EvaluateContext };
+  }
+  return { expression: spec };
+}
+
+/**
+ * Split a --set spec ("order.total=0") into its target and value. The first "=" that
+ * isn't part of a comparison (==, !=, <=, >=) separates them.
+ */
+export function parseAssignment(spec: string): { target: string; value: string } {
+  const match = spec.match(/^(.*?[^=!<>])=(?!=)(.*)$/s);
+  const target = match?.[1].trim();
+  const value = match?.[2].trim();
+  if (!target || !value) {
+    throw new Error(`Invalid --set "${spec}": expected target=value (e.g., order.total=0)`);
+  }
+  return { target, value };
+}
+
+/**
+ * Whether --eval specs pick a context (a default context or any prefix, after a breakpoint
+ * scope)
+ */
+export function hasEvaluationContexts(specs: string[], defaultContext?: string): boolean {
+  return (
+    defaultContext !== undefined ||
+    specs.some((spec) => parseEvaluationSpec(parseScopedEvaluation(spec).expression).context)
+  );
+}
+
+/** Longest argument value shown inline in a stack frame (--capture-args) */
+const MAX_ARGUMENT_LENGTH = 200;
+
+function truncateArgument(value: string): string {
+  return value.length > MAX_ARGUMENT_LENGTH ? `${value.slice(0, MAX_ARGUMENT_LENGTH)}…` : value;
+}
+
+/** An expression --eval-safe refuses itself, before the adapter sees it */
+class SideEffectRefusal extends Error {}
+
+/**
+ * An evaluate response as an --eval result, keeping the metadata that lets a structured
+ * result be expanded or read from memory
+ */
+function toEvaluationResult(response: EvaluateResponse): EvaluationResult {
+  const structured = response.variablesReference > 0;
+  return {
+    result: response.result,
+    type: response.type,
+    ...(structured && { variablesReference: response.variablesReference }),
+    ...(structured &&
+      response.namedVariables !== undefined && { namedVariables: response.namedVariables }),
+    ...(structured &&
+      response.indexedVariables !== undefined && { indexedVariables: response.indexedVariables }),
+    ...(response.presentationHint && { presentationHint: response.presentationHint }),
+    ...(response.memoryReference && { memoryReference: response.memoryReference }),
+  };
+}
+
+/** What a container's children have in common: its frame, scope and reference path */
+type ProvenanceContainer = Omit<VariableProvenance, 'evaluateName'>;
+
+/** A captured value waiting for its children during breadth-first expansion */
+interface PendingExpansion {
+  variable: DapVariable;
+  value: VariableValue;
+  container?: ProvenanceContainer;
+}
+
 export interface VariableInspectorOptions {
   /** Maximum depth for recursive variable expansion (default: 2) */
   maxDepth?: number;
@@ -113,14 +216,34 @@ export interface VariableInspectorOptions {
    * When enabled, variables from these scopes are included. Can produce large output.
    */
   captureClosures?: boolean;
+  /**
+   * DAP context for --eval expressions without a context prefix (default: watch)
+   * Adapters format results differently per context (e.g., js-debug is richer in repl).
+   */
+  evalContext?: EvaluateContext;
+  /**
+   * Levels to expand structured --eval results, like locals (default: 0, the string only)
+   */
+  evalExpandDepth?: number;
+  /**
+   * Evaluate expressions without side effects (--eval-safe)
+   * Expressions the adapter rejects for side effects are reported with sideEffect set.
+   */
+  safeEvaluation?: SafeEvaluation;
+  /**
+   * Tag locals with their frame, scope, reference path and evaluateName (default: false)
+   */
+  provenance?: boolean;
 }
 
 export class VariableInspector {
   private client: IDapClient;
-  private options: Required<VariableInspectorOptions>;
+  private options: Required<Omit<VariableInspectorOptions, 'safeEvaluation'>>;
+  private safeEvaluation?: SafeEvaluation;
 
   constructor(client: IDapClient, options: VariableInspectorOptions = {}) {
     this.client = client;
+    this.safeEvaluation = options.safeEvaluation;
     this.options = {
       maxDepth: options.maxDepth ?? 2,
       maxCollectionItems: options.maxCollectionItems ?? 20,
@@ -129,13 +252,27 @@ export class VariableInspector {
       compactServices: options.compactServices ?? true,
       omitNullProperties: options.omitNullProperties ?? true,
       captureClosures: options.captureClosures ?? false,
+      evalContext: options.evalContext ?? 'watch',
+      evalExpandDepth: options.evalExpandDepth ?? 0,
+      provenance: options.provenance ?? false,
     };
   }
 
   /**
-   * Get all local variables for a stack frame
+   * Get all local variables for a stack frame. With a capture budget, they are expanded
+   * breadth-first and values left unfetched when it runs out are marked truncated.
+   *
+   * @param frameIndex The frame's index in the stack, recorded in provenance
    */
-  async getLocals(frameId: number): Promise<Record<string, VariableValue>> {
+  async getLocals(
+    frameId: number,
+    budget?: CaptureBudget,
+    frameIndex: number = 0
+  ): Promise<Record<string, VariableValue>> {
+    if (budget) {
+      return this.getLocalsWithinBudget(frameId, budget, frameIndex);
+    }
+
     const result: Record<string, VariableValue> = {};
     // Track visited variablesReferences to detect circular references
     const visited = new Set<number>();
@@ -145,45 +282,12 @@ export class VariableInspector {
     try {
       const scopesResponse = await this.client.scopes({ frameId });
 
-      // Track whether we found a primary locals scope (to conditionally include block/closure)
-      let foundPrimaryLocals = false;
-
-      for (const scope of scopesResponse.scopes) {
-        // Categorize scope by name
-        // Different adapters use different naming conventions:
-        // - netcoredbg/vsdbg: "Locals", "Arguments"
-        // - js-debug (node): "Local: functionName", "Block", "Closure"
-        // - debugpy: "Locals", "Arguments"
-        // - lldb: "Local Variables", "Arguments"
-        const scopeLower = scope.name.toLowerCase();
-
-        const isPrimaryLocals =
-          scopeLower === 'locals' ||
-          scopeLower === 'local' ||
-          scopeLower.startsWith('local:') ||
-          scopeLower.startsWith('local ');
-
-        const isArguments = scopeLower === 'arguments' || scopeLower.startsWith('arg');
-
-        const isBlockOrClosure = scopeLower === 'block' || scopeLower === 'closure';
-
-        // Always include primary locals and arguments
-        // Only include block/closure if captureClosures is enabled OR no primary locals found
-        const shouldInclude =
-          isPrimaryLocals ||
-          isArguments ||
-          (isBlockOrClosure && (this.options.captureClosures || !foundPrimaryLocals));
-
-        if (!shouldInclude) continue;
-
-        if (isPrimaryLocals) {
-          foundPrimaryLocals = true;
-        }
-
+      for (const scope of this.selectScopes(scopesResponse.scopes)) {
         const vars = await this.client.variables({
           variablesReference: scope.variablesReference,
           count: this.options.maxCollectionItems,
         });
+        const container = this.scopeContainer(frameIndex, scope);
 
         for (const v of vars.variables) {
           // Handle name collisions across scopes
@@ -196,7 +300,8 @@ export class VariableInspector {
               this.options.maxDepth,
               visited,
               contentHashes,
-              v.name
+              v.name,
+              container
             );
 
             // Only rename if values differ
@@ -211,39 +316,206 @@ export class VariableInspector {
               this.options.maxDepth,
               visited,
               contentHashes,
-              v.name
+              v.name,
+              container
             );
           }
         }
       }
     } catch (error) {
       // Return empty if we can't get variables
-      console.error('Failed to get locals:', error);
+      logger.warn('variables', 'failed to get locals', {
+        frameId,
+        error: error instanceof Error ? error.message : String(error),
+      });
     }
 
     return result;
   }
 
   /**
-   * Expand a single variable to the specified depth
-   * @param v The variable to expand
-   * @param depth Maximum expansion depth
-   * @param visited Set of already-visited variablesReferences to detect circular references
-   * @param contentHashes Map of content hashes to paths (for deduplication)
-   * @param currentPath Path to this variable (e.g., "order.Items[0]")
+   * The scopes locals are read from: primary locals and arguments, plus block/closure
+   * scopes when captureClosures is set or no primary locals scope precedes them
    */
-  async expandVariable(
-    v: DapVariable,
-    depth: number = 2,
-    visited: Set<number> = new Set(),
-    contentHashes: Map<string, string> = new Map(),
-    currentPath: string = ''
-  ): Promise<VariableValue> {
+  private selectScopes(scopes: Scope[]): Scope[] {
+    const selected: Scope[] = [];
+    // Track whether we found a primary locals scope (to conditionally include block/closure)
+    let foundPrimaryLocals = false;
+
+    for (const scope of scopes) {
+      // Categorize scope by name
+      // Different adapters use different naming conventions:
+      // - netcoredbg/vsdbg: "Locals", "Arguments"
+      // - js-debug (node): "Local: functionName", "Block", "Closure"
+      // - debugpy: "Locals", "Arguments"
+      // - lldb: "Local Variables", "Arguments"
+      const scopeLower = scope.name.toLowerCase();
+
+      const isPrimaryLocals =
+        scopeLower === 'locals' ||
+        scopeLower === 'local' ||
+        scopeLower.startsWith('local:') ||
+        scopeLower.startsWith('local ');
+
+      const isArguments = scopeLower === 'arguments' || scopeLower.startsWith('arg');
+
+      const isBlockOrClosure = scopeLower === 'block' || scopeLower === 'closure';
+
+      // Always include primary locals and arguments
+      // Only include block/closure if captureClosures is enabled OR no primary locals found
+      const shouldInclude =
+        isPrimaryLocals ||
+        isArguments ||
+        (isBlockOrClosure && (this.options.captureClosures || !foundPrimaryLocals));
+
+      if (!shouldInclude) continue;
+
+      if (isPrimaryLocals) {
+        foundPrimaryLocals = true;
+      }
+      selected.push(scope);
+    }
+
+    return selected;
+  }
+
+  /**
+   * getLocals within a capture budget: every variable's own value first, then one level of
+   * children at a time, so the budget buys breadth instead of one deep object
+   */
+  private async getLocalsWithinBudget(
+    frameId: number,
+    budget: CaptureBudget,
+    frameIndex: number
+  ): Promise<Record<string, VariableValue>> {
+    const result: Record<string, VariableValue> = {};
+    const roots: PendingExpansion[] = [];
+
+    try {
+      const scopesResponse = await budget.within(this.client.scopes({ frameId }));
+      for (const scope of scopesResponse ? this.selectScopes(scopesResponse.scopes) : []) {
+        const vars = await budget.within(
+          this.client.variables({
+            variablesReference: scope.variablesReference,
+            count: this.options.maxCollectionItems,
+          })
+        );
+        if (!vars) {
+          logger.debug('variables', 'capture budget ran out before a scope', {
+            scope: scope.name,
+          });
+          break;
+        }
+        const container = this.scopeContainer(frameIndex, scope);
+
+        for (const v of vars.variables) {
+          const value = this.shallowValue(v, container);
+          let varName = v.name;
+          if (varName in result) {
+            // Nothing is expanded yet, so only the values themselves can be compared
+            if (this.valuesEqual(result[varName], value)) continue;
+            varName = `${v.name} (${scope.name})`;
+          }
+          result[varName] = value;
+          roots.push({ variable: v, value, container });
+        }
+      }
+
+      await this.expandBreadthFirst(roots, this.options.maxDepth, budget);
+    } catch (error) {
+      logger.warn('variables', 'failed to get locals', {
+        frameId,
+        error: error instanceof Error ? error.message : String(error),
+      });
+    }
+
+    return result;
+  }
+
+  /**
+   * Expand values level by level, up to depth levels below the given ones. When the budget
+   * runs out, every value still waiting for its children is marked truncated and false is
+   * returned.
+   */
+  private async expandBreadthFirst(
+    level: PendingExpansion[],
+    depth: number,
+    budget: CaptureBudget
+  ): Promise<boolean> {
+    // Content deduplication hashes fully expanded children, which a level-at-a-time
+    // expansion doesn't have yet, so only circular references are collapsed here
+    const visited = new Set<number>();
+    for (let remaining = depth; remaining > 0; remaining--) {
+      const pending = level.filter((node) => node.value.variablesReference !== undefined);
+      const next: PendingExpansion[] = [];
+
+      for (let i = 0; i < pending.length; i++) {
+        const { variable, value } = pending[i];
+        const reference = variable.variablesReference;
+        const container = this.innerContainer(pending[i].container, reference);
+        if (visited.has(reference)) {
+          value.value = '[Circular Reference]';
+          value.circular = true;
+          continue;
+        }
+        visited.add(reference);
+
+        let children: DapVariable[] | undefined;
+        try {
+          children = await budget.within(this.fetchChildren(reference));
+        } catch {
+          // Keep the string value if expansion fails
+          continue;
+        }
+        if (!children) {
+          // Values on the last level wouldn't have been expanded anyway
+          const waiting = remaining > 1 ? [...pending.slice(i), ...next] : pending.slice(i);
+          for (const node of waiting) {
+            if (node.value.variablesReference !== undefined) node.value.truncated = true;
+          }
+          return false;
+        }
+
+        if (this.isCollection(variable.type, children)) {
+          const items = children.slice(0, this.options.maxCollectionItems).map((child) => {
+            const item = this.shallowValue(child, container);
+            next.push({ variable: child, value: item, container });
+            return item;
+          });
+          value.value = {
+            type: variable.type || 'collection',
+            count: this.getCollectionCount(variable, children.length),
+            items,
+          };
+          continue;
+        }
+
+        const obj: Record<string, VariableValue> = {};
+        for (const child of children) {
+          obj[child.name] = this.shallowValue(child, container);
+          next.push({ variable: child, value: obj[child.name], container });
+        }
+        value.value = obj;
+      }
+
+      level = next;
+    }
+    return true;
+  }
+
+  /**
+   * A variable's own value, before any expansion (blocked and service types can't expand),
+   * with its provenance when read from a container of a scope
+   */
+  private shallowValue(v: DapVariable, container?: ProvenanceContainer): VariableValue {
     const variable: VariableValue = {
       type: v.type || 'unknown',
       value: this.parseValue(v.value, v.type),
       expandable: v.variablesReference > 0,
       variablesReference: v.variablesReference > 0 ? v.variablesReference : undefined,
+      ...(container && {
+        provenance: { ...container, ...(v.evaluateName && { evaluateName: v.evaluateName }) },
+      }),
     };
 
     // Don't expand blocked types (reflection metadata, etc.)
@@ -259,11 +531,119 @@ export class VariableInspector {
       variable.value = `{${v.type}}`;
       variable.expandable = false;
       variable.variablesReference = undefined;
-      return variable;
     }
 
+    return variable;
+  }
+
+  /**
+   * The provenance shared by a scope's variables (undefined unless provenance is on)
+   */
+  private scopeContainer(frameIndex: number, scope: Scope): ProvenanceContainer | undefined {
+    if (!this.options.provenance) return undefined;
+    return { frameIndex, scope: scope.name, referencePath: [scope.variablesReference] };
+  }
+
+  /**
+   * The provenance shared by the children of a value read from container
+   */
+  private innerContainer(
+    container: ProvenanceContainer | undefined,
+    variablesReference: number
+  ): ProvenanceContainer | undefined {
+    if (!container) return undefined;
+    return { ...container, referencePath: [...container.referencePath, variablesReference] };
+  }
+
+  /**
+   * Fetch a value's children, without blocked (and, with omitNullProperties, null) ones
+   */
+  private async fetchChildren(variablesReference: number): Promise<DapVariable[]> {
+    const children = await this.client.variables({
+      variablesReference,
+      count: this.options.maxCollectionItems,
+    });
+
+    // Filter out blocked properties
+    let filteredChildren = children.variables.filter(
+      (child) => !this.isBlockedProperty(child.name)
+    );
+
+    // Optionally filter out null properties
+    if (this.options.omitNullProperties) {
+      filteredChildren = filteredChildren.filter((child) => !this.isNullValue(child.value));
+    }
+    return filteredChildren;
+  }
+
+  /**
+   * Read a frame's general-purpose registers (native adapters). Returns undefined when the
+   * adapter has no register scope.
+   */
+  async getRegisters(frameId: number): Promise<Record<string, string> | undefined> {
+    const { scopes } = await this.client.scopes({ frameId });
+    const scope = scopes.find(
+      (s) => s.presentationHint === 'registers' || s.name.toLowerCase() === 'registers'
+    );
+    if (!scope) return undefined;
+
+    const { variables } = await this.client.variables({
+      variablesReference: scope.variablesReference,
+    });
+    // lldb groups registers into sets (general purpose, floating point, ...); gdb lists
+    // them directly
+    const group = variables.find((v) => v.variablesReference > 0 && /general/i.test(v.name));
+    const registers = group
+      ? (await this.client.variables({ variablesReference: group.variablesReference })).variables
+      : variables.filter((v) => v.variablesReference === 0);
+    return Object.fromEntries(registers.map((v) => [v.name, v.value]));
+  }
+
+  /**
+   * A frame's argument values as the adapter displays them: everything in its Arguments
+   * scope if it has one, otherwise the named parameters among its locals. Returns undefined
+   * when none are found.
+   */
+  async getFrameArguments(
+    frameId: number,
+    parameters: string[] = []
+  ): Promise<Record<string, string> | undefined> {
+    const { scopes } = await this.client.scopes({ frameId });
+    const argumentsScope = scopes.find((s) => /^arg/i.test(s.name));
+    const scope = argumentsScope ?? this.selectScopes(scopes)[0];
+    if (!scope || (!argumentsScope && parameters.length === 0)) return undefined;
+
+    const { variables } = await this.client.variables({
+      variablesReference: scope.variablesReference,
+    });
+    const args = argumentsScope
+      ? variables
+      : parameters.flatMap((name) => variables.filter((v) => v.name === name));
+    if (args.length === 0) return undefined;
+    return Object.fromEntries(args.map((v) => [v.name, truncateArgument(v.value)]));
+  }
+
+  /**
+   * Expand a single variable to the specified depth
+   * @param v The variable to expand
+   * @param depth Maximum expansion depth
+   * @param visited Set of already-visited variablesReferences to detect circular references
+   * @param contentHashes Map of content hashes to paths (for deduplication)
+   * @param currentPath Path to this variable (e.g., "order.Items[0]")
+   * @param container Provenance of the variable's container, for tagging it and its children
+   */
+  async expandVariable(
+    v: DapVariable,
+    depth: number = 2,
+    visited: Set<number> = new Set(),
+    contentHashes: Map<string, string> = new Map(),
+    currentPath: string = '',
+    container?: ProvenanceContainer
+  ): Promise<VariableValue> {
+    const variable = this.shallowValue(v, container);
+
     // Auto-expand objects to the specified depth
-    if (v.variablesReference > 0 && depth > 0) {
+    if (variable.variablesReference !== undefined && depth > 0) {
       // Check for circular reference
       if (visited.has(v.variablesReference)) {
         variable.value = '[Circular Reference]';
@@ -275,20 +655,8 @@ export class VariableInspector {
       visited.add(v.variablesReference);
 
       try {
-        const children = await this.client.variables({
-          variablesReference: v.variablesReference,
-          count: this.options.maxCollectionItems,
-        });
-
-        // Filter out blocked properties
-        let filteredChildren = children.variables.filter(
-          (child) => !this.isBlockedProperty(child.name)
-        );
-
-        // Optionally filter out null properties
-        if (this.options.omitNullProperties) {
-          filteredChildren = filteredChildren.filter((child) => !this.isNullValue(child.value));
-        }
+        const filteredChildren = await this.fetchChildren(v.variablesReference);
+        const inner = this.innerContainer(container, v.variablesReference);
 
         // Check if this is a collection/array
         if (this.isCollection(v.type, filteredChildren)) {
@@ -300,7 +668,8 @@ export class VariableInspector {
               depth - 1,
               visited,
               contentHashes,
-              currentPath
+              currentPath,
+              inner
             ),
           };
         } else {
@@ -313,7 +682,8 @@ export class VariableInspector {
               depth - 1,
               visited,
               contentHashes,
-              childPath
+              childPath,
+              inner
             );
           }
 
@@ -346,30 +716,52 @@ export class VariableInspector {
   }
 
   /**
-   * Evaluate expressions in the context of a stack frame
+   * Evaluate expressions in the context of a stack frame. Results are keyed by the
+   * spec as given, context prefix included. Results a capture budget ran out on are
+   * marked truncated.
    */
   async evaluateExpressions(
     frameId: number,
-    expressions: string[]
-  ): Promise<Record<string, { result: string; type?: string; error?: string }>> {
-    const results: Record<string, { result: string; type?: string; error?: string }> = {};
+    expressions: string[],
+    budget?: CaptureBudget
+  ): Promise<Record<string, EvaluationResult>> {
+    const results: Record<string, EvaluationResult> = {};
 
-    for (const expression of expressions) {
+    for (const spec of expressions) {
+      const { expression, context } = parseEvaluationSpec(spec);
       try {
-        const response = await this.client.evaluate({
-          expression,
-          frameId,
-          context: 'watch',
-        });
-
-        results[expression] = {
-          result: response.result,
-          type: response.type,
-        };
+        if (budget) {
+          results[spec] = await this.evaluateWithinBudget(spec, frameId, budget);
+          continue;
+        }
+        const result = await this.evaluate(expression, frameId, context);
+        if (this.options.evalExpandDepth > 0 && result.variablesReference) {
+          const expanded = await this.expandVariable(
+            {
+              name: spec,
+              value: result.result,
+              type: result.type,
+              variablesReference: result.variablesReference,
+            },
+            this.options.evalExpandDepth,
+            new Set(),
+            new Map(),
+            spec
+          );
+          // Compact services, circular and deduplicated values stay a string
+          if (typeof expanded.value === 'object' && expanded.value !== null) {
+            result.value = expanded.value;
+          }
+        }
+        results[spec] = result;
       } catch (error) {
-        results[expression] = {
+        const message = error instanceof Error ? error.message : String(error);
+        const sideEffect =
+          error instanceof SideEffectRefusal || this.safeEvaluation?.rejected.test(message);
+        results[spec] = {
           result: '',
-          error: error instanceof Error ? error.message : String(error),
+          error: message,
+          ...(sideEffect && { sideEffect: true }),
         };
       }
     }
@@ -377,6 +769,71 @@ export class VariableInspector {
     return results;
   }
 
+  /**
+   * One --eval spec under a capture budget, expanded breadth-first with --eval-expand
+   */
+  private async evaluateWithinBudget(
+    spec: string,
+    frameId: number,
+    budget: CaptureBudget
+  ): Promise<EvaluationResult> {
+    const { expression, context } = parseEvaluationSpec(spec);
+    const result = await budget.within(this.evaluate(expression, frameId, context));
+    if (!result) {
+      return { result: '', truncated: true };
+    }
+    if (this.options.evalExpandDepth > 0 && result.variablesReference) {
+      const variable: DapVariable = {
+        name: spec,
+        value: result.result,
+        type: result.type,
+        variablesReference: result.variablesReference,
+      };
+      const root = { variable, value: this.shallowValue(variable) };
+      const complete = await this.expandBreadthFirst([root], this.options.evalExpandDepth, budget);
+      // Compact services, circular and deduplicated values stay a string
+      if (typeof root.value.value === 'object' && root.value.value !== null) {
+        result.value = root.value.value;
+      }
+      if (!complete) {
+        result.truncated = true;
+      }
+    }
+    return result;
+  }
+
+  /**
+   * Evaluate one expression in a stack frame (side-effect free with safeEvaluation, which
+   * picks its own context)
+   * @throws Error if evaluation fails or the adapter rejects it for side effects
+   */
+  async evaluate(
+    expression: string,
+    frameId: number,
+    context: EvaluateContext = this.options.evalContext
+  ): Promise<EvaluationResult> {
+    const safe = this.safeEvaluation;
+    if (!safe) {
+      const response = await this.client.evaluate({ expression, frameId, context });
+      return toEvaluationResult(response);
+    }
+
+    if (safe.mutates?.(expression)) {
+      throw new SideEffectRefusal(`${expression} changes program state`);
+    }
+    const response = await this.client.evaluate({
+      expression: safe.expression ? safe.expression(expression) : expression,
+      frameId,
+      context: safe.context,
+    });
+    // Some adapters report the rejection as the result instead of failing the request
+    if (safe.rejected.test(response.result)) {
+      throw new Error(response.result);
+    }
+    // Parsed command output has no structure left to describe
+    return safe.parseResult ? safe.parseResult(response.result) : toEvaluationResult(response);
+  }
+
   /**
    * Check if a property name should be skipped (reflection noise, etc.)
    */
@@ -510,14 +967,17 @@ export class VariableInspector {
     depth: number,
     visited: Set<number>,
     contentHashes: Map<string, string>,
-    currentPath: string
+    currentPath: string,
+    container?: ProvenanceContainer
   ): Promise<VariableValue[]> {
     const result: VariableValue[] = [];
 
     let index = 0;
     for (const item of items.slice(0, this.options.maxCollectionItems)) {
       const itemPath = `${currentPath}[${index}]`;
-      result.push(await this.expandVariable(item, depth, visited, contentHashes, itemPath));
+      result.push(
+        await this.expandVariable(item, depth, visited, contentHashes, itemPath, container)
+      );
       index++;
     }
 
@@ -575,7 +1035,9 @@ export class VariableInspector {
     }
 
     // For objects/arrays, use JSON serialization
-    // (acceptable for debugging output - not perf critical)
-    return JSON.stringify(a.value) === JSON.stringify(b.value);
+    // (acceptable for debugging output - not perf critical). Provenance says where a
+    // value was read, not what it is.
+    const content = (key: string, value: unknown) => (key === 'provenance' ? undefined : value);
+    return JSON.stringify(a.value, content) === JSON.stringify(b.value, content);
   }
 }
@@ -0,0 +1,385 @@
+/**
+ * Breakpoint Verification (verify)
+ *
+ * Backs the verify command, a preflight for a set of breakpoints: the program is launched
+ * with them set, runs only until every one is bound (or a short timeout passes, or it
+ * exits), and is terminated. Each breakpoint is reported with where the adapter bound it,
+ * the source maps that tie its file to generated code, and the likely reasons when it
+ * didn't bind. Breakpoint hits along the way count as bound; the program is resumed so
+ * later modules get a chance to load.
+ */
+
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+import type { AdapterConfig, LaunchOptions } from '../adapters/base.js';
+import { DapClient } from '../dap/client.js';
+import { SocketDapClient } from '../dap/socket-client.js';
+import type { IDapClient } from '../dap/client-interface.js';
+import type { BreakpointEventBody, StoppedEventBody } from '../dap/protocol.js';
+import { diagnoseSourceMaps } from '../sourcemaps/diagnose.js';
+import {
+  getBreakpointSuggestions,
+  parseBreakpointSpec,
+  type PathResolutionOptions,
+} from './breakpoints.js';
+import { logger } from '../util/logger.js';
+
+export interface SourceMapLink {
+  /** The .map file */
+  map: string;
+  /** The generated file it maps */
+  generated: string;
+}
+
+export interface BreakpointVerification {
+  /** The breakpoint spec as given */
+  spec: string;
+  file: string;
+  line: number;
+  verified: boolean;
+  /** Where the adapter bound it */
+  bound?: { line: number; column?: number };
+  /** The adapter's message about it */
+  message?: string;
+  /** Source maps whose sources include the file (node) */
+  sourceMaps?: SourceMapLink[];
+  /** Likely reasons it didn't bind (or moved), most specific first */
+  reasons?: string[];
+}
+
+/** Why the wait for binding ended */
+export type VerifyEnd = 'all_verified' | 'timeout' | 'terminated';
+
+export interface VerifyReport {
+  adapter: string;
+  program: string;
+  ended: VerifyEnd;
+  breakpoints: BreakpointVerification[];
+}
+
+/** A breakpoint being verified: its spec and the adapter's latest word on it */
+export interface PendingBreakpoint {
+  spec: string;
+  file: string;
+  line: number;
+  column?: number;
+  condition?: string;
+  hitCondition?: string;
+  id?: number;
+  verified: boolean;
+  boundLine?: number;
+  boundColumn?: number;
+  message?: string;
+}
+
+/**
+ * Parse breakpoint specs for verification
+ *
+ * @throws Error if a spec is malformed
+ */
+export function parseVerifySpecs(
+  specs: string[],
+  pathOptions: PathResolutionOptions = {}
+): PendingBreakpoint[] {
+  return specs.map((spec) => ({
+    spec,
+    ...parseBreakpointSpec(spec, pathOptions),
+    verified: false,
+  }));
+}
+
+/**
+ * Set the breakpoints, one setBreakpoints request per file, recording the adapter's answers
+ */
+export async function setPendingBreakpoints(
+  client: IDapClient,
+  breakpoints: PendingBreakpoint[]
+): Promise<void> {
+  const byFile = new Map<string, PendingBreakpoint[]>();
+  for (const bp of breakpoints) {
+    byFile.set(bp.file, [...(byFile.get(bp.file) ?? []), bp]);
+  }
+
+  for (const [file, specs] of byFile) {
+    try {
+      const response = await client.setBreakpoints({
+        source: { path: file },
+        breakpoints: specs.map((bp) => ({
+          line: bp.line,
+          column: bp.column,
+          condition: bp.condition,
+          hitCondition: bp.hitCondition,
+        })),
+      });
+      specs.forEach((bp, i) => {
+        const result = response.breakpoints[i];
+        if (!result) return;
+        bp.id = result.id;
+        bp.verified = result.verified;
+        bp.boundLine = result.line;
+        bp.boundColumn = result.column;
+        bp.message = result.message;
+      });
+    } catch (error) {
+      for (const bp of specs) {
+        bp.message = error instanceof Error ? error.message : String(error);
+      }
+    }
+  }
+}
+
+/**
+ * Wait until every breakpoint is bound, the program ends, or the timeout passes. Binding
+ * updates arrive as breakpoint events; a stop at a breakpoint also proves it bound, and the
+ * program is resumed after every stop.
+ */
+export function awaitBinding(
+  client: IDapClient,
+  breakpoints: PendingBreakpoint[],
+  timeoutMs: number
+): Promise<VerifyEnd> {
+  const allVerified = () => breakpoints.every((bp) => bp.verified);
+  if (allVerified()) return Promise.resolve('all_verified');
+
+  return new Promise<VerifyEnd>((resolve) => {
+    const finish = (end: VerifyEnd) => {
+      clearTimeout(timer);
+      client.off('breakpoint', onBreakpoint);
+      client.off('stopped', onStopped);
+      client.off('terminated', onTerminated);
+      client.off('exited', onTerminated);
+      resolve(end);
+    };
+    const onBreakpoint = (body: BreakpointEventBody) => {
+      const bp = breakpoints.find((b) => b.id !== undefined && b.id === body.breakpoint.id);
+      if (!bp || body.reason === 'removed') return;
+      bp.verified = body.breakpoint.verified;
+      bp.boundLine = body.breakpoint.line ?? bp.boundLine;
+      bp.boundColumn = body.breakpoint.column ?? bp.boundColumn;
+      bp.message = body.breakpoint.message ?? bp.message;
+      if (allVerified()) finish('all_verified');
+    };
+    const onStopped = (body: StoppedEventBody) => {
+      for (const bp of breakpoints) {
+        if (bp.id !== undefined && body.hitBreakpointIds?.includes(bp.id)) {
+          bp.verified = true;
+        }
+      }
+      if (allVerified()) {
+        finish('all_verified');
+        return;
+      }
+      client.continue({ threadId: body.threadId ?? 1 }).catch(() => undefined);
+    };
+    const onTerminated = () => finish('terminated');
+    const timer = setTimeout(() => finish('timeout'), timeoutMs);
+
+    client.on('breakpoint', onBreakpoint);
+    client.on('stopped', onStopped);
+    client.on('terminated', onTerminated);
+    client.on('exited', onTerminated);
+  });
+}
+
+/**
+ * Map each original source file to the source maps that list it, from the .map files under
+ * a directory
+ */
+export function indexSourceMaps(
+  directory: string,
+  sourceMapOverrides?: Record<string, string>
+): Map<string, SourceMapLink[]> {
+  const index = new Map<string, SourceMapLink[]>();
+  for (const map of diagnoseSourceMaps(directory, { sourceMapOverrides }).maps) {
+    if (!map.valid || !map.generatedFile) continue;
+    for (const source of map.sources) {
+      if (!source.resolved) continue;
+      const links = index.get(source.resolved) ?? [];
+      links.push({ map: map.mapPath, generated: map.generatedFile });
+      index.set(source.resolved, links);
+    }
+  }
+  return index;
+}
+
+/**
+ * Likely reasons a breakpoint didn't bind where it was asked to, most specific first
+ */
+export function candidateReasons(
+  adapterName: string,
+  bp: PendingBreakpoint,
+  context: { ended: VerifyEnd; sourceMaps?: SourceMapLink[] }
+): string[] {
+  const reasons: string[] = [];
+
+  if (bp.verified) {
+    if (bp.boundLine !== undefined && bp.boundLine !== bp.line) {
+      reasons.push(
+        `Line ${bp.line} has no code; the adapter moved the breakpoint to line ${bp.boundLine}`
+      );
+    }
+    return reasons;
+  }
+
+  let source: string | undefined;
+  try {
+    source = fs.readFileSync(bp.file, 'utf-8');
+  } catch {
+    reasons.push(`${bp.file} does not exist`);
+  }
+  if (source !== undefined) {
+    const lineCount = source.replace(/\r?\n$/, '').split(/\r?\n/).length;
+    if (bp.line > lineCount) {
+      reasons.push(`Line ${bp.line} is past the end of the file (${lineCount} lines)`);
+    }
+  }
+
+  if (bp.message) {
+    reasons.push(`Adapter: ${bp.message}`);
+  }
+
+  const ext = path.extname(bp.file).toLowerCase();
+  if (context.sourceMaps !== undefined) {
+    if (context.sourceMaps.length === 0 && !['.js', '.mjs', '.cjs'].includes(ext)) {
+      reasons.push('No source map lists this file; build with source maps enabled');
+    }
+    for (const link of context.sourceMaps) {
+      if (isNewer(bp.file, link.generated)) {
+        const generated = path.basename(link.generated);
+        reasons.push(`${path.basename(bp.file)} changed after ${generated} was built; rebuild`);
+      }
+    }
+  }
+
+  if (context.ended !== 'all_verified' && source !== undefined) {
+    reasons.push(
+      context.ended === 'timeout'
+        ? 'The program had not loaded this file by the timeout; it may load later (raise --timeout)'
+        : 'The program exited without loading this file'
+    );
+  }
+
+  reasons.push(...getBreakpointSuggestions(adapterName, ext, bp.message));
+  return reasons;
+}
+
+function isNewer(file: string, than: string): boolean {
+  try {
+    return fs.statSync(file).mtimeMs > fs.statSync(than).mtimeMs;
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Launch the program with the breakpoints set, wait for them to bind, and terminate it
+ */
+export async function verifyBreakpoints(
+  adapter: AdapterConfig,
+  breakpoints: PendingBreakpoint[],
+  options: Omit<LaunchOptions, 'stopAtEntry'> & { timeout: number }
+): Promise<VerifyReport> {
+  const { timeout, ...launchOptions } = options;
+  const clientOptions = {
+    command: adapter.command,
+    args: adapter.args,
+    cwd: options.cwd,
+    env: { ...adapter.env, ...options.env },
+    timeout: Math.max(timeout, 10000),
+  };
+  const client: IDapClient =
+    adapter.transport === 'socket' && adapter.socketPort
+      ? new SocketDapClient({ ...clientOptions, port: adapter.socketPort })
+      : new DapClient(clientOptions);
+
+  const span = logger.span('verify', { adapter: adapter.name, breakpoints: breakpoints.length });
+
+  try {
+    await client.connect();
+    await client.initialize({ adapterID: adapter.id });
+
+    // Same launch ordering as DebugSession.start()
+    const launchConfig = adapter.launchConfig(launchOptions);
+    if (adapter.requiresLaunchFirst) {
+      const initialized = new Promise<void>((resolve) => client.once('initialized', resolve));
+      const launched = client.launch(launchConfig);
+      await initialized;
+      await setPendingBreakpoints(client, breakpoints);
+      await client.configurationDone();
+      await launched;
+    } else {
+      await setPendingBreakpoints(client, breakpoints);
+      if (adapter.transport === 'socket') {
+        await client.configurationDone();
+        await client.launch(launchConfig);
+      } else {
+        await client.launch(launchConfig);
+        await client.configurationDone();
+      }
+    }
+
+    const ended = await awaitBinding(client, breakpoints, timeout);
+    const sourceMaps =
+      adapter.name === 'node'
+        ? indexSourceMaps(options.cwd ?? process.cwd(), options.sourceMapOverrides)
+        : undefined;
+
+    const results: BreakpointVerification[] = breakpoints.map((bp) => {
+      const links = sourceMaps && (sourceMaps.get(bp.file) ?? []);
+      const reasons = candidateReasons(adapter.name, bp, { ended, sourceMaps: links });
+      return {
+        spec: bp.spec,
+        file: bp.file,
+        line: bp.line,
+        verified: bp.verified,
+        ...(bp.verified &&
+          bp.boundLine !== undefined && {
+            bound: {
+              line: bp.boundLine,
+              ...(bp.boundColumn !== undefined && { column: bp.boundColumn }),
+            },
+          }),
+        ...(bp.message && { message: bp.message }),
+        ...(links && links.length > 0 && { sourceMaps: links }),
+        ...(reasons.length > 0 && { reasons }),
+      };
+    });
+    span.end({ ended, verified: results.filter((r) => r.verified).length });
+    return { adapter: adapter.name, program: options.program, ended, breakpoints: results };
+  } catch (error) {
+    span.end({ error: error instanceof Error ? error.message : String(error) });
+    throw error;
+  } finally {
+    if (client.isConnected()) {
+      await client.disconnect(true).catch(() => undefined);
+    }
+    await client.reapProcessTree();
+  }
+}
+
+/**
+ * Human-readable report: one line per breakpoint, with its source maps and reasons
+ */
+export function formatVerifyReport(report: VerifyReport, cwd?: string): string {
+  const relative = (file: string) => path.relative(cwd ?? process.cwd(), file) || file;
+  const verified = report.breakpoints.filter((bp) => bp.verified).length;
+  const lines: string[] = [
+    `${verified}/${report.breakpoints.length} breakpoints bound (${report.adapter}, ` +
+      `${report.ended.replace('_', ' ')})`,
+  ];
+
+  for (const bp of report.breakpoints) {
+    const where = `${relative(bp.file)}:${bp.line}`;
+    const bound = bp.bound && bp.bound.line !== bp.line ? ` (bound at line ${bp.bound.line})` : '';
+    lines.push('');
+    lines.push(`${bp.verified ? '✓' : '✗'} ${where}${bound}`);
+    for (const link of bp.sourceMaps ?? []) {
+      lines.push(`    source map: ${relative(link.map)} → ${relative(link.generated)}`);
+    }
+    for (const reason of bp.reasons ?? []) {
+      lines.push(`    - ${reason}`);
+    }
+  }
+
+  return lines.join('\n');
+}
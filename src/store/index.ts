@@ -0,0 +1 @@
+export * from './result-store.js';
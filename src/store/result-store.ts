@@ -0,0 +1,354 @@
+/**
+ * Result Store
+ *
+ * Appends sessions, stops, variables and evaluations to a SQLite database (--store)
+ * so values can be compared across runs with `debug-run query`.
+ *
+ * Uses the built-in node:sqlite module (Node.js 22.5+), so no native dependency is needed.
+ */
+
+import { createRequire } from 'node:module';
+import type {
+  DebugEvent,
+  EvaluationResult,
+  SourceLocation,
+  VariableValue,
+} from '../output/events.js';
+
+const require = createRequire(import.meta.url);
+
+type SqliteValue = null | number | bigint | string;
+
+/** The subset of node:sqlite's DatabaseSync used by the store */
+interface SqliteDatabase {
+  exec(sql: string): void;
+  prepare(sql: string): {
+    run(...params: SqliteValue[]): { lastInsertRowid: number | bigint };
+    all(...params: SqliteValue[]): Record<string, SqliteValue>[];
+  };
+  close(): void;
+}
+
+const SCHEMA = `
+CREATE TABLE IF NOT EXISTS runs (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  started_at TEXT NOT NULL,
+  ended_at TEXT,
+  adapter TEXT NOT NULL,
+  program TEXT,
+  args TEXT,
+  cwd TEXT,
+  pid INTEGER,
+  exit_code INTEGER,
+  duration_ms INTEGER,
+  breakpoints_hit INTEGER,
+  exceptions_caught INTEGER,
+  steps_executed INTEGER
+);
+CREATE TABLE IF NOT EXISTS stops (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  run_id INTEGER NOT NULL REFERENCES runs(id),
+  seq INTEGER NOT NULL,
+  type TEXT NOT NULL,
+  timestamp TEXT NOT NULL,
+  thread_id INTEGER,
+  breakpoint_id INTEGER,
+  file TEXT,
+  line INTEGER,
+  function TEXT,
+  exception_type TEXT,
+  exception_message TEXT
+);
+CREATE TABLE IF NOT EXISTS variables (
+  stop_id INTEGER NOT NULL REFERENCES stops(id),
+  name TEXT NOT NULL,
+  type TEXT,
+  value TEXT
+);
+CREATE TABLE IF NOT EXISTS evaluations (
+  stop_id INTEGER NOT NULL REFERENCES stops(id),
+  expression TEXT NOT NULL,
+  result TEXT,
+  type TEXT,
+  error TEXT
+);
+CREATE INDEX IF NOT EXISTS stops_run ON stops(run_id);
+CREATE INDEX IF NOT EXISTS variables_name ON variables(name);
+CREATE INDEX IF NOT EXISTS evaluations_expression ON evaluations(expression);
+`;
+
+/**
+ * Canned queries available through `debug-run query`
+ */
+export const STORE_QUERIES: Record<string, string> = {
+  runs: 'Recent runs with exit code, duration and hit counts',
+  value: 'Value of a local variable or --eval expression at each stop (requires <expression>)',
+  hits: 'Stop counts per location across recent runs',
+  exceptions: 'Exception types and counts across recent runs',
+};
+
+export interface StoreQueryOptions {
+  /** Expression or variable name (for the "value" query) */
+  expression?: string;
+  /** Only consider the most recent N runs */
+  last: number;
+}
+
+/**
+ * Check whether node:sqlite can be loaded by this Node.js runtime
+ */
+export function isSqliteAvailable(): boolean {
+  try {
+    require('node:sqlite');
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+function openDatabase(file: string): SqliteDatabase {
+  let sqlite: { DatabaseSync: new (file: string) => SqliteDatabase };
+  try {
+    sqlite = require('node:sqlite');
+  } catch {
+    throw new Error(
+      `--store requires Node.js 22.5+ with the built-in node:sqlite module (running ${process.version})`
+    );
+  }
+  return new sqlite.DatabaseSync(file);
+}
+
+export class ResultStore {
+  private runId: number | null = null;
+  private stopSeq = 0;
+
+  constructor(private db: SqliteDatabase) {
+    this.db.exec(SCHEMA);
+  }
+
+  /**
+   * Open (creating if needed) a result store at the given path
+   */
+  static open(file: string): ResultStore {
+    return new ResultStore(openDatabase(file));
+  }
+
+  /**
+   * Record a debug event. Events other than session lifecycle and stops are ignored.
+   */
+  record(event: DebugEvent): void {
+    switch (event.type) {
+      case 'session_start':
+        this.runId = Number(
+          this.db
+            .prepare(
+              `INSERT INTO runs (started_at, adapter, program, args, cwd, pid)
+               VALUES (?, ?, ?, ?, ?, ?)`
+            )
+            .run(
+              event.timestamp,
+              event.adapter,
+              event.program ?? null,
+              event.args ? JSON.stringify(event.args) : null,
+              event.cwd ?? null,
+              event.pid ?? null
+            ).lastInsertRowid
+        );
+        this.stopSeq = 0;
+        break;
+
+      case 'session_end':
+        if (this.runId === null) return;
+        this.db
+          .prepare(
+            `UPDATE runs SET ended_at = ?, exit_code = ?, duration_ms = ?, breakpoints_hit = ?,
+             exceptions_caught = ?, steps_executed = ? WHERE id = ?`
+          )
+          .run(
+            event.timestamp,
+            event.summary.exitCode,
+            event.summary.durationMs,
+            event.summary.breakpointsHit,
+            event.summary.exceptionsCaught,
+            event.summary.stepsExecuted,
+            this.runId
+          );
+        break;
+
+      case 'breakpoint_hit':
+        this.recordStop(event, event.location, {
+          breakpointId: event.id,
+          locals: event.locals,
+          evaluations: event.evaluations,
+        });
+        break;
+
+      case 'exception_thrown':
+      case 'crash_report':
+        this.recordStop(event, event.location, {
+          locals: event.locals,
+          exception: event.exception,
+        });
+        break;
+
+      case 'signal_received':
+        this.recordStop(event, event.location, {
+          locals: event.locals,
+          exception: { type: event.signal.name, message: event.signal.description ?? '' },
+        });
+        break;
+
+      case 'step_completed':
+      case 'assertion_failed':
+        this.recordStop(event, event.location, { locals: event.locals });
+        break;
+
+      case 'interrupt_snapshot':
+        this.recordStop(event, event.location, {
+          locals: event.locals,
+          evaluations: event.evaluations,
+        });
+        break;
+
+      case 'trace_completed':
+        this.recordStop(event, event.finalLocation, {
+          locals: event.locals,
+          evaluations: event.evaluations,
+        });
+        break;
+    }
+  }
+
+  /**
+   * Run a canned query (see STORE_QUERIES)
+   */
+  query(name: string, options: StoreQueryOptions): Record<string, unknown>[] {
+    const recentRuns = 'SELECT id FROM runs ORDER BY id DESC LIMIT ?';
+
+    switch (name) {
+      case 'runs':
+        return this.db
+          .prepare(
+            `SELECT id AS run, started_at AS startedAt, adapter, program, exit_code AS exitCode,
+             duration_ms AS durationMs, breakpoints_hit AS breakpointsHit,
+             exceptions_caught AS exceptionsCaught
+             FROM runs WHERE id IN (${recentRuns}) ORDER BY id`
+          )
+          .all(options.last);
+
+      case 'value': {
+        if (!options.expression) {
+          throw new Error('The "value" query requires an expression or variable name');
+        }
+        const rows = this.db
+          .prepare(
+            `SELECT s.run_id AS run, s.seq AS stop, s.file, s.line, 'local' AS source,
+             v.value AS value, v.type AS type, NULL AS error
+             FROM variables v JOIN stops s ON s.id = v.stop_id
+             WHERE v.name = ? AND s.run_id IN (${recentRuns})
+             UNION ALL
+             SELECT s.run_id, s.seq, s.file, s.line, 'eval', e.result, e.type, e.error
+             FROM evaluations e JOIN stops s ON s.id = e.stop_id
+             WHERE e.expression = ? AND s.run_id IN (${recentRuns})
+             ORDER BY run, stop`
+          )
+          .all(options.expression, options.last, options.expression, options.last);
+        return rows.map((row) => ({
+          ...row,
+          // Locals are stored as JSON so structured values round-trip
+          value: row.source === 'local' ? parseJson(row.value) : row.value,
+          error: row.error ?? undefined,
+        }));
+      }
+
+      case 'hits':
+        return this.db
+          .prepare(
+            `SELECT file, line, function, COUNT(*) AS hits, COUNT(DISTINCT run_id) AS runs
+             FROM stops WHERE type = 'breakpoint_hit' AND run_id IN (${recentRuns})
+             GROUP BY file, line, function ORDER BY hits DESC, file, line`
+          )
+          .all(options.last);
+
+      case 'exceptions':
+        return this.db
+          .prepare(
+            `SELECT exception_type AS type, COUNT(*) AS count, COUNT(DISTINCT run_id) AS runs,
+             MAX(exception_message) AS lastMessage
+             FROM stops WHERE type = 'exception_thrown' AND run_id IN (${recentRuns})
+             GROUP BY exception_type ORDER BY count DESC`
+          )
+          .all(options.last);
+
+      default:
+        throw new Error(
+          `Unknown query: ${name}. Available queries: ${Object.keys(STORE_QUERIES).join(', ')}`
+        );
+    }
+  }
+
+  close(): void {
+    this.db.close();
+  }
+
+  private recordStop(
+    event: DebugEvent,
+    location: SourceLocation,
+    details: {
+      breakpointId?: number;
+      locals?: Record<string, VariableValue>;
+      evaluations?: Record<string, EvaluationResult>;
+      exception?: { type: string; message: string };
+    }
+  ): void {
+    if (this.runId === null) return;
+
+    const stopId = this.db
+      .prepare(
+        `INSERT INTO stops (run_id, seq, type, timestamp, thread_id, breakpoint_id, file, line,
+         function, exception_type, exception_message) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)`
+      )
+      .run(
+        this.runId,
+        ++this.stopSeq,
+        event.type,
+        event.timestamp,
+        'threadId' in event ? event.threadId : null,
+        details.breakpointId ?? null,
+        location.file,
+        location.line,
+        location.function ?? null,
+        details.exception?.type ?? null,
+        details.exception?.message ?? null
+      ).lastInsertRowid;
+
+    const insertVariable = this.db.prepare(
+      'INSERT INTO variables (stop_id, name, type, value) VALUES (?, ?, ?, ?)'
+    );
+    for (const [name, variable] of Object.entries(details.locals ?? {})) {
+      insertVariable.run(stopId, name, variable.type, JSON.stringify(variable.value) ?? null);
+    }
+
+    const insertEvaluation = this.db.prepare(
+      'INSERT INTO evaluations (stop_id, expression, result, type, error) VALUES (?, ?, ?, ?, ?)'
+    );
+    for (const [expression, evaluation] of Object.entries(details.evaluations ?? {})) {
+      insertEvaluation.run(
+        stopId,
+        expression,
+        evaluation.result ?? null,
+        evaluation.type ?? null,
+        evaluation.error ?? null
+      );
+    }
+  }
+}
+
+function parseJson(value: unknown): unknown {
+  if (typeof value !== 'string') return value;
+  try {
+    return JSON.parse(value);
+  } catch {
+    return value;
+  }
+}
@@ -0,0 +1,421 @@
+/**
+ * Breakpoint Suggestions from Stack Traces
+ *
+ * Parses a pasted stack trace or exception log (Node.js, Python, .NET, Rust, Go, Java),
+ * maps its frames to files in the repository and suggests breakpoints on the innermost
+ * application frames, skipping runtime and dependency frames.
+ */
+
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+
+export type TraceLanguage = 'node' | 'python' | 'dotnet' | 'rust' | 'go' | 'java';
+
+export interface TraceFrame {
+  /** File as written in the trace */
+  file: string;
+  line: number;
+  column?: number;
+  function?: string;
+  language: TraceLanguage;
+}
+
+export interface SuggestedFrame extends TraceFrame {
+  /** Repository-relative path the frame was mapped to */
+  path: string;
+  /** True if several repository files matched equally well */
+  ambiguous?: boolean;
+}
+
+export interface SuggestResult {
+  language?: TraceLanguage;
+  exception?: { type: string; message?: string };
+  /** All frames found in the trace, innermost first */
+  frames: TraceFrame[];
+  /** Application frames mapped to repository files, innermost first */
+  applicationFrames: SuggestedFrame[];
+  /** Breakpoint specs for the most relevant application frames */
+  breakpoints: string[];
+  /** Ready-to-run debug-run command, when the language has an adapter */
+  command?: string;
+}
+
+export interface SuggestOptions {
+  /** Repository root frames are mapped against (default: cwd) */
+  root?: string;
+  /** Maximum number of breakpoints to suggest (default: 3) */
+  limit?: number;
+}
+
+/** Adapter to use for each trace language (languages without one get no command) */
+const LANGUAGE_ADAPTERS: Partial<Record<TraceLanguage, string>> = {
+  node: 'node',
+  python: 'python',
+  dotnet: 'dotnet',
+  rust: 'rust',
+};
+
+/** Path fragments that mark runtime, standard library and dependency frames */
+const LIBRARY_PATTERNS = [
+  /(^|[\\/])node_modules[\\/]/,
+  /^node:/,
+  /^internal[\\/]/,
+  /(^|[\\/])(site|dist)-packages[\\/]/,
+  /(^|[\\/])lib[\\/]python\d/i,
+  /^<.*>$/,
+  /(^|[\\/])\.cargo[\\/]registry[\\/]/,
+  /^\/rustc\//,
+  /(^|[\\/])go[\\/]src[\\/]runtime[\\/]/,
+  /(^|[\\/])pkg[\\/]mod[\\/]/,
+];
+
+/** Directories never searched when mapping frames to repository files */
+const EXCLUDED_DIRS = [
+  'node_modules',
+  '.git',
+  '__pycache__',
+  '.venv',
+  'venv',
+  '.tox',
+  'target',
+  'bin',
+  'obj',
+];
+
+const PATTERNS: Array<{
+  language: TraceLanguage;
+  regex: RegExp;
+  frame(match: RegExpMatchArray): Omit<TraceFrame, 'language'>;
+}> = [
+  {
+    // File "app/orders.py", line 42, in process
+    language: 'python',
+    regex: /^\s*File "(.+?)", line (\d+)(?:, in (.+))?$/,
+    frame: (m) => ({ file: m[1], line: Number(m[2]), function: m[3] }),
+  },
+  {
+    // at Orders.Service.Process() in /src/Orders/Service.cs:line 42
+    language: 'dotnet',
+    regex: /^\s*at (.+?) in (.+):line (\d+)\s*$/,
+    frame: (m) => ({ file: m[2], line: Number(m[3]), function: m[1] }),
+  },
+  {
+    // at com.example.Orders.process(Orders.java:42)
+    language: 'java',
+    regex: /^\s*at ([\w$.<>]+)\(([\w$]+\.(?:java|kt|scala)):(\d+)\)\s*$/,
+    frame: (m) => ({ file: m[2], line: Number(m[3]), function: m[1] }),
+  },
+  {
+    // at ./src/main.rs:42:5 (Rust backtrace; the function is on the previous line)
+    language: 'rust',
+    regex: /^\s*at (.+\.rs):(\d+)(?::(\d+))?\s*$/,
+    frame: (m) => ({ file: m[1], line: Number(m[2]), column: m[3] ? Number(m[3]) : undefined }),
+  },
+  {
+    // thread 'main' panicked at src/main.rs:42:5:  (or the older "panicked at 'msg', file")
+    language: 'rust',
+    regex: /panicked at (?:'.*', )?(.+\.rs):(\d+):(\d+)/,
+    frame: (m) => ({ file: m[1], line: Number(m[2]), column: Number(m[3]) }),
+  },
+  {
+    // \t/home/me/app/orders.go:42 +0x1d (Go; the function is on the previous line)
+    language: 'go',
+    regex: /^\s+(.+\.go):(\d+)(?: \+0x[0-9a-f]+)?\s*$/,
+    frame: (m) => ({ file: m[1], line: Number(m[2]) }),
+  },
+  {
+    // at process (/app/src/orders.js:42:13)  or  at /app/src/orders.js:42:13
+    language: 'node',
+    regex: /^\s*at (?:(?:async )?(.+?) \()?(?:file:\/\/)?(.+?):(\d+):(\d+)\)?\s*$/,
+    frame: (m) => ({ file: m[2], line: Number(m[3]), column: Number(m[4]), function: m[1] }),
+  },
+];
+
+const EXCEPTION_PATTERN =
+  /^\s*(?:Unhandled exception\.\s*|Uncaught\s+|Exception in thread "[^"]*"\s+)?([A-Za-z_$][\w.$]*(?:Error|Exception|Exit|Interrupt))(?::\s*(.*))?$/;
+
+/**
+ * Extract stack frames from a trace, innermost first
+ */
+export function parseStackTrace(text: string): TraceFrame[] {
+  const lines = text.split(/\r?\n/);
+  const frames: TraceFrame[] = [];
+  let pythonFrames: TraceFrame[] = [];
+
+  const flushPython = () => {
+    // Python prints "most recent call last": reverse to innermost first
+    frames.push(...pythonFrames.reverse());
+    pythonFrames = [];
+  };
+
+  for (let i = 0; i < lines.length; i++) {
+    for (const pattern of PATTERNS) {
+      const match = lines[i].match(pattern.regex);
+      if (!match) continue;
+
+      const frame: TraceFrame = { ...pattern.frame(match), language: pattern.language };
+      if (!frame.function) delete frame.function;
+      if (frame.column === undefined) delete frame.column;
+
+      if ((frame.language === 'rust' || frame.language === 'go') && !frame.function) {
+        // Backtrace lines give the function on the line before the location
+        const previous = lines[i - 1]?.trim().replace(/^\d+:\s*/, '');
+        const name = previous?.replace(/\(.*\)$/, '');
+        if (name && !PATTERNS.some((p) => p.regex.test(lines[i - 1]))) {
+          frame.function = name;
+        }
+      }
+
+      if (frame.language === 'python') {
+        pythonFrames.push(frame);
+      } else {
+        flushPython();
+        // A Rust panic site is usually repeated in the backtrace as ./src/...
+        const duplicate = frames.some(
+          (f) => sameFile(f.file, frame.file) && f.line === frame.line
+        );
+        if (!duplicate) frames.push(frame);
+      }
+      break;
+    }
+  }
+  flushPython();
+
+  return frames;
+}
+
+/**
+ * Find the exception type and message in a trace
+ */
+export function parseException(
+  text: string,
+  language?: TraceLanguage
+): { type: string; message?: string } | undefined {
+  const matches = text
+    .split(/\r?\n/)
+    .map((line) => line.match(EXCEPTION_PATTERN))
+    .filter((match): match is RegExpMatchArray => match !== null);
+
+  // Python prints the exception after the traceback; everyone else before it
+  const match = language === 'python' ? matches[matches.length - 1] : matches[0];
+  if (match) {
+    return { type: match[1], ...(match[2] && { message: match[2].trim() }) };
+  }
+
+  const panic = text.match(/panicked at (?:'(.*)', .+|.+:\d+:\d+:\s*\n\s*(.*))/);
+  if (panic) {
+    return { type: 'panic', message: (panic[1] ?? panic[2]).trim() };
+  }
+  return undefined;
+}
+
+/**
+ * Whether a frame points into a runtime, standard library or dependency
+ */
+export function isLibraryFrame(file: string): boolean {
+  return LIBRARY_PATTERNS.some((pattern) => pattern.test(file));
+}
+
+/**
+ * Map a trace path to a repository-relative path. Tries the path as-is, then falls back
+ * to the repository file whose trailing path segments match best (traces often come
+ * from a container, CI machine or build directory with a different root).
+ */
+export function resolveFramePath(
+  file: string,
+  root: string,
+  filesByName: Map<string, string[]>
+): { path: string; ambiguous?: boolean } | null {
+  const normalized = file.replace(/^file:\/\//, '').replace(/\\/g, '/');
+
+  const direct = path.resolve(root, normalized);
+  if (isInside(root, direct) && fs.existsSync(direct) && fs.statSync(direct).isFile()) {
+    return { path: toPosix(path.relative(root, direct)) };
+  }
+
+  const segments = normalized.split('/').filter((s) => s && s !== '.');
+  const candidates = filesByName.get(segments[segments.length - 1]) ?? [];
+  let best: string[] = [];
+  let bestScore = 0;
+  for (const candidate of candidates) {
+    // Count matching trailing segments
+    const candidateSegments = candidate.split('/').reverse();
+    const traceSegments = [...segments].reverse();
+    let score = 0;
+    while (score < traceSegments.length && traceSegments[score] === candidateSegments[score]) {
+      score++;
+    }
+    if (score > bestScore) {
+      best = [candidate];
+      bestScore = score;
+    } else if (score === bestScore && score > 0) {
+      best.push(candidate);
+    }
+  }
+
+  if (best.length === 0) return null;
+  best.sort((a, b) => a.length - b.length || a.localeCompare(b));
+  return { path: best[0], ...(best.length > 1 && { ambiguous: true }) };
+}
+
+/**
+ * Index repository files by basename (only the names we need to look up)
+ */
+export function indexRepositoryFiles(root: string, names: Set<string>): Map<string, string[]> {
+  const index = new Map<string, string[]>();
+
+  function walk(dir: string): void {
+    let entries: fs.Dirent[];
+    try {
+      entries = fs.readdirSync(dir, { withFileTypes: true });
+    } catch {
+      return;
+    }
+
+    for (const entry of entries) {
+      const fullPath = path.join(dir, entry.name);
+      if (entry.isDirectory()) {
+        if (!EXCLUDED_DIRS.includes(entry.name)) {
+          walk(fullPath);
+        }
+      } else if (entry.isFile() && names.has(entry.name)) {
+        const matches = index.get(entry.name) ?? [];
+        matches.push(toPosix(path.relative(root, fullPath)));
+        index.set(entry.name, matches);
+      }
+    }
+  }
+
+  if (names.size > 0) walk(root);
+  return index;
+}
+
+/**
+ * Suggest breakpoints for a stack trace
+ */
+export function suggestBreakpoints(text: string, options: SuggestOptions = {}): SuggestResult {
+  const root = path.resolve(options.root ?? process.cwd());
+  const limit = options.limit ?? 3;
+
+  const frames = parseStackTrace(text);
+  const language = frames[0]?.language;
+  const exception = parseException(text, language);
+
+  const candidates = frames.filter((frame) => !isLibraryFrame(frame.file));
+  const names = new Set(
+    candidates.map((frame) => frame.file.replace(/\\/g, '/').split('/').pop() ?? frame.file)
+  );
+  const filesByName = indexRepositoryFiles(root, names);
+
+  const applicationFrames: SuggestedFrame[] = [];
+  for (const frame of candidates) {
+    const resolved = resolveFramePath(frame.file, root, filesByName);
+    if (resolved) {
+      applicationFrames.push({ ...frame, ...resolved });
+    }
+  }
+
+  const breakpoints: string[] = [];
+  for (const frame of applicationFrames) {
+    const spec = `${frame.path}:${frame.line}`;
+    if (breakpoints.length < limit && !breakpoints.includes(spec)) {
+      breakpoints.push(spec);
+    }
+  }
+
+  const result: SuggestResult = {
+    ...(language && { language }),
+    ...(exception && { exception }),
+    frames,
+    applicationFrames,
+    breakpoints,
+  };
+
+  const adapter = language && LANGUAGE_ADAPTERS[language];
+  if (adapter && breakpoints.length > 0) {
+    result.command = buildCommand(language, adapter, applicationFrames, breakpoints);
+  }
+  return result;
+}
+
+function buildCommand(
+  language: TraceLanguage,
+  adapter: string,
+  applicationFrames: SuggestedFrame[],
+  breakpoints: string[]
+): string {
+  // For scripts, the outermost application frame is usually the entry point
+  const outermost = applicationFrames[applicationFrames.length - 1];
+  const program =
+    language === 'node' || language === 'python' ? quote(outermost.path) : '<program>';
+  const flags = breakpoints.map((spec) => `-b ${quote(spec)}`).join(' ');
+  return `npx debug-run ${program} -a ${adapter} ${flags}`;
+}
+
+/**
+ * Human-readable report for `debug-run suggest`
+ */
+export function formatSuggestReport(result: SuggestResult): string {
+  const lines: string[] = [];
+
+  if (result.exception) {
+    const { type, message } = result.exception;
+    lines.push(`Exception: ${message ? `${type}: ${message}` : type}`);
+    lines.push('');
+  }
+
+  if (result.frames.length === 0) {
+    lines.push('No stack frames found. Paste a Node.js, Python, .NET, Rust, Go or Java trace.');
+    return lines.join('\n');
+  }
+
+  if (result.applicationFrames.length === 0) {
+    lines.push(
+      `Found ${result.frames.length} frames, but none map to files under this directory.` +
+        ' Run from the repository root or pass --root.'
+    );
+    return lines.join('\n');
+  }
+
+  lines.push('Application frames (innermost first):');
+  const width = Math.max(...result.applicationFrames.map((f) => `${f.path}:${f.line}`.length));
+  result.applicationFrames.forEach((frame, index) => {
+    const location = `${frame.path}:${frame.line}`.padEnd(width);
+    const notes = [frame.function, frame.ambiguous && '(ambiguous match)'].filter(Boolean);
+    lines.push(`  ${index + 1}. ${location}  ${notes.join(' ')}`.trimEnd());
+  });
+  const skipped = result.frames.length - result.applicationFrames.length;
+  if (skipped > 0) {
+    lines.push(`  (${skipped} library or unmapped frames skipped)`);
+  }
+  lines.push('');
+
+  lines.push('Breakpoints:');
+  lines.push(`  ${result.breakpoints.map((spec) => `-b ${quote(spec)}`).join(' ')}`);
+
+  if (result.command) {
+    lines.push('');
+    lines.push('Command:');
+    lines.push(`  ${result.command}`);
+  }
+
+  return lines.join('\n');
+}
+
+function quote(value: string): string {
+  return `"${value.replace(/(["\\$`])/g, '\\$1')}"`;
+}
+
+function sameFile(a: string, b: string): boolean {
+  const normalize = (file: string) => file.replace(/\\/g, '/').replace(/^\.\//, '');
+  return normalize(a) === normalize(b);
+}
+
+function isInside(root: string, target: string): boolean {
+  const relative = path.relative(root, target);
+  return relative !== '' && !relative.startsWith('..') && !path.isAbsolute(relative);
+}
+
+function toPosix(file: string): string {
+  return file.split(path.sep).join('/');
+}
@@ -0,0 +1,173 @@
+/**
+ * Attach Guardrails
+ *
+ * Attaching pauses a real process at every breakpoint, so attach mode checks what it is about
+ * to attach to first: a process owned by another user, or whose owner can't be read, is refused
+ * unless --allow-other-user is given, and process names and hosts on the config files' [attach]
+ * denylist are always refused. With --confirm-attach the user is also asked before attaching
+ * (on a terminal; --yes answers ahead of time elsewhere).
+ */
+
+import { execFileSync } from 'node:child_process';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import type { AttachDenyRule } from './config.js';
+import type { LaunchOverrides } from '../session/launch-overrides.js';
+
+export interface AttachTarget {
+  pid?: number;
+  /** Executable name */
+  name?: string;
+  /** Owning user (not read on Windows) */
+  uid?: number;
+  user?: string;
+  /** This machine and, for a remote attach, the host connected to */
+  hosts: string[];
+}
+
+export interface AttachGuardOptions {
+  denylist: AttachDenyRule[];
+  allowOtherUser?: boolean;
+  /** The user debug-run runs as (undefined on Windows) */
+  uid?: number;
+}
+
+const LOCAL_HOSTS = new Set(['localhost', '127.0.0.1', '::1', '0.0.0.0']);
+
+/**
+ * Parse `ps -o uid=,user=,comm=` output for one process
+ */
+export function parsePsOwner(output: string): Omit<AttachTarget, 'pid' | 'hosts'> | undefined {
+  const match = output.trim().match(/^(\d+)\s+(\S+)\s+(.+)$/);
+  if (!match) return undefined;
+  const [, uid, user, command] = match;
+  return { uid: Number(uid), user, name: command.split('/').pop() };
+}
+
+/**
+ * A Linux process's full executable name, which ps cuts to 15 characters: from its
+ * /proc/<pid>/exe link, or its argv[0] when the link can't be read (another user's process)
+ */
+export function linuxExecutableName(pid: number, procRoot = '/proc'): string | undefined {
+  const dir = path.join(procRoot, String(pid));
+  try {
+    return path.basename(fs.readlinkSync(path.join(dir, 'exe'))).replace(/ \(deleted\)$/, '');
+  } catch {
+    // Fall back to the command line
+  }
+  try {
+    const argv0 = fs.readFileSync(path.join(dir, 'cmdline'), 'utf-8');
+    return argv0.split('\0')[0].split('/').pop() || undefined;
+  } catch {
+    return undefined;
+  }
+}
+
+/**
+ * The name and owner of a running process, or undefined if it can't be read (e.g., it
+ * doesn't exist)
+ */
+export function readProcessOwner(pid: number): Omit<AttachTarget, 'pid' | 'hosts'> | undefined {
+  try {
+    if (process.platform === 'win32') {
+      const row = execFileSync('tasklist', ['/FI', `PID eq ${pid}`, '/FO', 'CSV', '/NH'], {
+        encoding: 'utf-8',
+        timeout: 5000,
+      });
+      const name = row.match(/^"([^"]+)"/)?.[1];
+      return name ? { name } : undefined;
+    }
+    const owner = parsePsOwner(
+      execFileSync('ps', ['-o', 'uid=,user=,comm=', '-p', String(pid)], {
+        encoding: 'utf-8',
+        timeout: 2000,
+      })
+    );
+    if (owner && process.platform === 'linux') {
+      owner.name = linuxExecutableName(pid) ?? owner.name;
+    }
+    return owner;
+  } catch {
+    return undefined;
+  }
+}
+
+/**
+ * The host a --launch-arg/--launch-json override connects the adapter to, when it isn't
+ * this machine (address for js-debug, host or connect.host for debugpy and others)
+ */
+export function remoteAttachHost(overrides: LaunchOverrides = {}): string | undefined {
+  const connect = overrides.connect as Record<string, unknown> | undefined;
+  const host = [overrides.address, overrides.host, connect?.host].find(
+    (value): value is string => typeof value === 'string' && value !== ''
+  );
+  return host !== undefined && !LOCAL_HOSTS.has(host.toLowerCase()) ? host : undefined;
+}
+
+/**
+ * The process and hosts an attach involves
+ */
+export function attachTarget(pid: number | undefined, overrides?: LaunchOverrides): AttachTarget {
+  const remote = remoteAttachHost(overrides);
+  return {
+    ...(pid !== undefined && { pid, ...readProcessOwner(pid) }),
+    hosts: [os.hostname(), ...(remote !== undefined ? [remote] : [])],
+  };
+}
+
+/**
+ * Case-insensitive match against a pattern with * and ? wildcards
+ */
+export function matchesPattern(value: string, pattern: string): boolean {
+  const source = pattern
+    .replace(/[\\^$.|+()[\]{}]/g, '\\$&')
+    .replace(/\*/g, '.*')
+    .replace(/\?/g, '.');
+  return new RegExp(`^${source}$`, 'i').test(value);
+}
+
+/**
+ * Why an attach is refused (empty when it may go ahead)
+ */
+export function attachRefusals(target: AttachTarget, options: AttachGuardOptions): string[] {
+  const refusals: string[] = [];
+  const pid = target.pid !== undefined ? `PID ${target.pid}` : 'the process';
+
+  // Owners aren't compared on Windows, where options.uid is undefined
+  if (!options.allowOtherUser && options.uid !== undefined && target.pid !== undefined) {
+    if (target.uid === undefined) {
+      refusals.push(
+        `can't tell who owns ${pid} (is it running?); pass --allow-other-user to attach anyway`
+      );
+    } else if (target.uid !== options.uid) {
+      const owner = target.user ?? `uid ${target.uid}`;
+      refusals.push(
+        `${pid} belongs to ${owner}, not the current user; pass --allow-other-user to attach anyway`
+      );
+    }
+  }
+
+  for (const { kind, pattern, source } of options.denylist) {
+    const ruled = `matches "${pattern}" in the attach denylist (${source})`;
+    if (kind === 'process' && target.name && matchesPattern(target.name, pattern)) {
+      refusals.push(`${target.name} (${pid}) ${ruled}`);
+    }
+    if (kind === 'host') {
+      for (const host of target.hosts.filter((name) => matchesPattern(name, pattern))) {
+        refusals.push(`host ${host} ${ruled}`);
+      }
+    }
+  }
+  return refusals;
+}
+
+/**
+ * One line naming the target, for the --confirm-attach question
+ */
+export function describeAttachTarget(target: AttachTarget): string {
+  const details = [target.name, target.user && `user ${target.user}`].filter(Boolean);
+  const what = target.pid !== undefined ? `PID ${target.pid}` : 'the process';
+  const where = target.hosts[target.hosts.length - 1];
+  return `${what}${details.length > 0 ? ` (${details.join(', ')})` : ''} on ${where}`;
+}
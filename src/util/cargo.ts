@@ -0,0 +1,99 @@
+/**
+ * Cargo Targets (--cargo-bin, --cargo-example)
+ *
+ * Builds a Rust binary or example by its cargo target name and finds the executable in
+ * cargo's JSON messages, so the binary debugged is the one just built with the requested
+ * features rather than a stale or hashed file picked out of target/ by hand.
+ */
+
+import { spawnSync } from 'node:child_process';
+
+export type CargoTargetKind = 'bin' | 'example';
+
+export interface CargoTarget {
+  kind: CargoTargetKind;
+  /** Target name, as in Cargo.toml [[bin]] / [[example]] (or the package name) */
+  name: string;
+  /** Features to enable (--features) */
+  features?: string[];
+}
+
+/** Cargo's output is one JSON message per line; large workspaces print a lot of them */
+const MAX_OUTPUT_BYTES = 64 * 1024 * 1024;
+
+interface CargoArtifactMessage {
+  reason?: string;
+  target?: { name?: string; kind?: string[] };
+  executable?: string | null;
+}
+
+/**
+ * Arguments for `cargo build` of a target, with JSON messages on stdout and human-readable
+ * diagnostics on stderr
+ */
+export function cargoBuildArgs(target: CargoTarget): string[] {
+  const args = [
+    'build',
+    '--message-format=json-render-diagnostics',
+    `--${target.kind}`,
+    target.name,
+  ];
+  if (target.features?.length) {
+    args.push('--features', target.features.join(','));
+  }
+  return args;
+}
+
+/**
+ * The executable cargo built for a target, from its JSON messages (the last one wins, as
+ * cargo reports a target once per build)
+ */
+export function parseCargoArtifact(output: string, target: CargoTarget): string | undefined {
+  let executable: string | undefined;
+  for (const line of output.split(/\r?\n/)) {
+    if (!line.startsWith('{')) continue;
+    let message: CargoArtifactMessage;
+    try {
+      message = JSON.parse(line) as CargoArtifactMessage;
+    } catch {
+      continue;
+    }
+    if (
+      message.reason === 'compiler-artifact' &&
+      message.target?.name === target.name &&
+      message.target.kind?.includes(target.kind) &&
+      message.executable
+    ) {
+      executable = message.executable;
+    }
+  }
+  return executable;
+}
+
+/**
+ * Build a cargo target in debug mode and return its executable. Build diagnostics go to
+ * stderr as cargo prints them.
+ *
+ * @throws Error if cargo can't be run, the build fails, or no executable is reported
+ */
+export function buildCargoTarget(target: CargoTarget, cwd: string): string {
+  const args = cargoBuildArgs(target);
+  const result = spawnSync('cargo', args, {
+    cwd,
+    encoding: 'utf-8',
+    stdio: ['ignore', 'pipe', 'inherit'],
+    maxBuffer: MAX_OUTPUT_BYTES,
+  });
+  if (result.error) {
+    throw new Error(`Could not run cargo: ${result.error.message}`);
+  }
+  if (result.status !== 0) {
+    throw new Error(`cargo ${args.join(' ')} failed (exit code ${result.status})`);
+  }
+
+  const executable = parseCargoArtifact(result.stdout, target);
+  if (!executable) {
+    throw new Error(`cargo built no executable for ${target.kind} "${target.name}"`);
+  }
+  return executable;
+}
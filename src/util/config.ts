@@ -0,0 +1,201 @@
+/**
+ * Config Files
+ *
+ * Defaults for debug-run's options, so personal preferences don't have to be repeated on
+ * every command. The user config (~/.config/debug-run/config.toml) is merged under the
+ * project's .debug-run.toml, and both under the flags given on the command line. Each file
+ * has a [defaults] table for every adapter and an [adapters.<name>] table per adapter; keys
+ * are option names without the leading dashes:
+ *
+ *   [defaults]
+ *   timeout = "2m"
+ *
+ *   [adapters.python]
+ *   just-my-code = false
+ *
+ * An [attach] table lists processes and hosts attach mode refuses (see attach-guard.ts):
+ *
+ *   [attach]
+ *   deny-processes = ["postgres", "java*"]
+ *   deny-hosts = ["prod-*"]
+ */
+
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import { getAdapter } from '../adapters/index.js';
+import { isStdioAdapter } from '../adapters/stdio.js';
+import type { AdapterConfig } from '../adapters/base.js';
+import { parseToml, type TomlTable, type TomlValue } from './toml.js';
+
+export const PROJECT_CONFIG_FILE = '.debug-run.toml';
+
+export interface ConfigFile {
+  path: string;
+  /** Options for every adapter */
+  defaults: TomlTable;
+  /** Options per adapter, keyed by the adapter name used in the file */
+  adapters: Record<string, TomlTable>;
+  /** The [attach] denylist, when the file has one */
+  attach?: AttachDenylist;
+}
+
+/** Process name and host patterns (* and ? wildcards) attach mode refuses */
+export interface AttachDenylist {
+  processes: string[];
+  hosts: string[];
+}
+
+/** A denylist pattern with the file it came from */
+export interface AttachDenyRule {
+  kind: 'process' | 'host';
+  pattern: string;
+  source: string;
+}
+
+const ATTACH_KEYS = new Map<string, keyof AttachDenylist>([
+  ['deny-processes', 'processes'],
+  ['deny-hosts', 'hosts'],
+]);
+
+/** An option value from a config file */
+export interface ConfigDefault {
+  value: TomlValue;
+  /** The file it came from */
+  source: string;
+}
+
+/**
+ * Where the user config lives ($XDG_CONFIG_HOME/debug-run/config.toml, falling back to
+ * ~/.config)
+ */
+export function userConfigPath(env: NodeJS.ProcessEnv = process.env): string {
+  const base = env.XDG_CONFIG_HOME || path.join(os.homedir(), '.config');
+  return path.join(base, 'debug-run', 'config.toml');
+}
+
+function isTable(value: TomlValue | undefined): value is TomlTable {
+  return typeof value === 'object' && !Array.isArray(value);
+}
+
+/**
+ * Read a config file; undefined if it doesn't exist
+ *
+ * @throws Error naming the file if it can't be parsed or has an unexpected layout
+ */
+export function readConfigFile(file: string): ConfigFile | undefined {
+  let text: string;
+  try {
+    text = fs.readFileSync(file, 'utf-8');
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code === 'ENOENT') return undefined;
+    throw new Error(`${file}: ${error instanceof Error ? error.message : error}`);
+  }
+
+  let parsed: TomlTable;
+  try {
+    parsed = parseToml(text);
+  } catch (error) {
+    throw new Error(`${file}: ${error instanceof Error ? error.message : error}`);
+  }
+
+  const { defaults = {}, adapters = {}, attach, ...unknown } = parsed;
+  const [stray] = Object.keys(unknown);
+  if (stray !== undefined) {
+    throw new Error(
+      `${file}: unexpected "${stray}"; put options under [defaults] or [adapters.<name>]`
+    );
+  }
+  if (!isTable(defaults)) {
+    throw new Error(`${file}: "defaults" must be a table`);
+  }
+  if (!isTable(adapters)) {
+    throw new Error(`${file}: "adapters" must be a table of [adapters.<name>] tables`);
+  }
+  for (const [name, options] of Object.entries(adapters)) {
+    if (!getAdapter(name) && !isStdioAdapter(name)) {
+      throw new Error(`${file}: unknown adapter "${name}" in [adapters.${name}]`);
+    }
+    if (!isTable(options)) {
+      throw new Error(`${file}: "adapters.${name}" must be a table`);
+    }
+  }
+
+  return {
+    path: file,
+    defaults,
+    adapters: adapters as Record<string, TomlTable>,
+    ...(attach !== undefined && { attach: readAttachDenylist(file, attach) }),
+  };
+}
+
+function readAttachDenylist(file: string, attach: TomlValue): AttachDenylist {
+  if (!isTable(attach)) {
+    throw new Error(`${file}: "attach" must be a table`);
+  }
+  const denylist: AttachDenylist = { processes: [], hosts: [] };
+  for (const [key, value] of Object.entries(attach)) {
+    const field = ATTACH_KEYS.get(key);
+    if (!field) {
+      throw new Error(`${file}: unknown key "${key}" in [attach] (use deny-processes, deny-hosts)`);
+    }
+    if (!Array.isArray(value) || !value.every((item) => typeof item === 'string')) {
+      throw new Error(`${file}: "attach.${key}" must be an array of strings`);
+    }
+    denylist[field] = value as string[];
+  }
+  return denylist;
+}
+
+/**
+ * The user config and the project config in a directory, in merge order (missing ones
+ * left out)
+ *
+ * @throws Error if either can't be read
+ */
+export function loadConfigFiles(cwd: string = process.cwd()): ConfigFile[] {
+  return [userConfigPath(), path.join(cwd, PROJECT_CONFIG_FILE)]
+    .map(readConfigFile)
+    .filter((file): file is ConfigFile => file !== undefined);
+}
+
+/**
+ * The attach denylist patterns of all config files (patterns add up across files)
+ */
+export function attachDenyRules(files: ConfigFile[]): AttachDenyRule[] {
+  return files.flatMap(({ path: source, attach }) => [
+    ...(attach?.processes ?? []).map((pattern) => ({ kind: 'process' as const, pattern, source })),
+    ...(attach?.hosts ?? []).map((pattern) => ({ kind: 'host' as const, pattern, source })),
+  ]);
+}
+
+/** The name of the adapter an [adapters.<name>] table is for */
+function tableAdapterName(name: string): string | undefined {
+  return isStdioAdapter(name) ? name : getAdapter(name)?.name;
+}
+
+/**
+ * The option values config files give an adapter. Later files win, and within a file an
+ * [adapters.<name>] table wins over [defaults]. Adapter aliases match (python and debugpy
+ * are the same adapter), and a ["stdio:<command>"] table matches that exact command line.
+ */
+export function configDefaultsFor(
+  files: ConfigFile[],
+  adapter: AdapterConfig
+): Map<string, ConfigDefault> {
+  const merged = new Map<string, ConfigDefault>();
+  for (const file of files) {
+    const tables = [
+      file.defaults,
+      ...Object.entries(file.adapters)
+        .filter(([name]) => tableAdapterName(name) === adapter.name)
+        .map(([, options]) => options),
+    ];
+    for (const table of tables) {
+      for (const [key, value] of Object.entries(table)) {
+        merged.set(key, { value, source: file.path });
+      }
+    }
+  }
+  return merged;
+}
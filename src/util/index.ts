@@ -1,3 +1,8 @@
 export * from './adapter-installer.js';
 export * from './vscode-adapters.js';
 export * from './vsda-signer.js';
+export * from './python-env.js';
+export * from './npm-scripts.js';
+export * from './cargo.js';
+export * from './process-tree.js';
+export * from './resource-limits.js';
@@ -0,0 +1,259 @@
+/**
+ * Diagnostic Logger
+ *
+ * Leveled diagnostics for debug-run itself (not the debuggee). Output goes to
+ * stderr or a log file so it never interleaves with the NDJSON event stream.
+ *
+ * Verbosity:
+ * - 0 (default): errors and warnings
+ * - 1 (-v): session phases and lifecycle info
+ * - 2 (-vv): adapter/config details
+ * - 3 (-vvv): every DAP message (same as DEBUG_DAP=1)
+ */
+
+import * as fs from 'node:fs';
+
+export type LogLevel = 'error' | 'warn' | 'info' | 'debug' | 'trace';
+export type LogFormat = 'text' | 'json';
+
+const LEVEL_VERBOSITY: Record<LogLevel, number> = {
+  error: 0,
+  warn: 0,
+  info: 1,
+  debug: 2,
+  trace: 3,
+};
+
+export interface LoggerOptions {
+  /** Verbosity level (0-3), typically the number of -v flags */
+  verbosity?: number;
+  /** Write log records to this file instead of stderr */
+  file?: string;
+  /** Record format (default: text) */
+  format?: LogFormat;
+  /** Write to a custom stream (overrides file/stderr, mainly for tests) */
+  stream?: NodeJS.WritableStream;
+}
+
+export interface LogRecord {
+  ts: string;
+  level: LogLevel;
+  target: string;
+  msg: string;
+  /** Active span path, outermost first (e.g., "session/configuring") */
+  span?: string;
+  [field: string]: unknown;
+}
+
+export interface Span {
+  /** Span name */
+  readonly name: string;
+  /** End the span and log its duration */
+  end(fields?: Record<string, unknown>): void;
+}
+
+/**
+ * A span as seen by span observers
+ */
+export interface SpanInfo {
+  /** Process-unique span ID */
+  id: number;
+  /** ID of the enclosing span, if any */
+  parentId?: number;
+  name: string;
+  /** Start time (ms since epoch) */
+  startTime: number;
+  /** Fields passed when the span was opened */
+  fields?: Record<string, unknown>;
+}
+
+/**
+ * Receives span lifecycle notifications regardless of verbosity (used by the OTLP exporter)
+ */
+export interface SpanObserver {
+  spanStarted(span: SpanInfo): void;
+  spanEnded(span: SpanInfo, endTime: number, fields?: Record<string, unknown>): void;
+}
+
+export class Logger {
+  private verbosity: number = 0;
+  private format: LogFormat = 'text';
+  private stream: NodeJS.WritableStream | null = null;
+  /** Log file descriptor; writes are synchronous so records survive process.exit() */
+  private fd: number | null = null;
+  private spans: SpanInfo[] = [];
+  private nextSpanId = 1;
+  private observers: SpanObserver[] = [];
+
+  constructor(options: LoggerOptions = {}) {
+    this.configure(options);
+  }
+
+  /**
+   * Apply logger options. DEBUG_DAP is honored as an alias for -vvv.
+   */
+  configure(options: LoggerOptions): void {
+    const envVerbosity = process.env.DEBUG_DAP ? 3 : 0;
+    this.verbosity = Math.max(options.verbosity ?? 0, envVerbosity);
+    this.format = options.format ?? 'text';
+
+    this.close();
+    this.stream = options.stream ?? null;
+    if (!options.stream && options.file) {
+      this.fd = fs.openSync(options.file, 'a');
+    }
+  }
+
+  /**
+   * Check whether records at this level would be written
+   */
+  enabled(level: LogLevel): boolean {
+    return LEVEL_VERBOSITY[level] <= this.verbosity;
+  }
+
+  error(target: string, msg: string, fields?: Record<string, unknown>): void {
+    this.log('error', target, msg, fields);
+  }
+
+  warn(target: string, msg: string, fields?: Record<string, unknown>): void {
+    this.log('warn', target, msg, fields);
+  }
+
+  info(target: string, msg: string, fields?: Record<string, unknown>): void {
+    this.log('info', target, msg, fields);
+  }
+
+  debug(target: string, msg: string, fields?: Record<string, unknown>): void {
+    this.log('debug', target, msg, fields);
+  }
+
+  trace(target: string, msg: string, fields?: Record<string, unknown>): void {
+    this.log('trace', target, msg, fields);
+  }
+
+  /**
+   * Open a span. Records logged until end() carry the span path.
+   */
+  span(name: string, fields?: Record<string, unknown>): Span {
+    const info: SpanInfo = {
+      id: this.nextSpanId++,
+      parentId: this.spans[this.spans.length - 1]?.id,
+      name,
+      startTime: Date.now(),
+      fields,
+    };
+    this.spans.push(info);
+    this.info('span', `enter ${name}`, fields);
+    for (const observer of this.observers) {
+      observer.spanStarted(info);
+    }
+
+    let ended = false;
+    return {
+      name,
+      end: (endFields?: Record<string, unknown>) => {
+        if (ended) return;
+        ended = true;
+        const endTime = Date.now();
+        this.info('span', `exit ${name}`, { ...endFields, durationMs: endTime - info.startTime });
+        const index = this.spans.indexOf(info);
+        if (index !== -1) {
+          this.spans.splice(index, 1);
+        }
+        for (const observer of this.observers) {
+          observer.spanEnded(info, endTime, endFields);
+        }
+      },
+    };
+  }
+
+  /**
+   * Register a span observer. Returns a function that unregisters it.
+   */
+  observeSpans(observer: SpanObserver): () => void {
+    this.observers.push(observer);
+    return () => {
+      this.observers = this.observers.filter((o) => o !== observer);
+    };
+  }
+
+  /**
+   * Run an async function inside a span
+   */
+  async inSpan<T>(name: string, fn: () => Promise<T>): Promise<T> {
+    const span = this.span(name);
+    try {
+      return await fn();
+    } catch (error) {
+      span.end({ error: error instanceof Error ? error.message : String(error) });
+      throw error;
+    } finally {
+      span.end();
+    }
+  }
+
+  /**
+   * Close the log file, if any
+   */
+  close(): void {
+    if (this.fd !== null) {
+      fs.closeSync(this.fd);
+      this.fd = null;
+    }
+  }
+
+  private log(
+    level: LogLevel,
+    target: string,
+    msg: string,
+    fields?: Record<string, unknown>
+  ): void {
+    if (!this.enabled(level)) return;
+
+    const record: LogRecord = {
+      ts: new Date().toISOString(),
+      level,
+      target,
+      msg,
+      ...(this.spans.length > 0 && { span: this.spans.map((s) => s.name).join('/') }),
+      ...fields,
+    };
+
+    const line = this.format === 'json' ? JSON.stringify(record) : formatText(record);
+    if (this.fd !== null) {
+      fs.writeSync(this.fd, line + '\n');
+    } else {
+      (this.stream ?? process.stderr).write(line + '\n');
+    }
+  }
+}
+
+/**
+ * Render a record as a single human-readable line
+ */
+export function formatText(record: LogRecord): string {
+  const { ts, level, target, msg, span, ...fields } = record;
+  const parts = [
+    ts,
+    level.toUpperCase().padEnd(5),
+    span ? `${span} ${target}:` : `${target}:`,
+    msg,
+  ];
+
+  for (const [key, value] of Object.entries(fields)) {
+    if (value === undefined) continue;
+    parts.push(`${key}=${typeof value === 'string' ? value : JSON.stringify(value)}`);
+  }
+
+  return parts.join(' ');
+}
+
+/** Process-wide logger used by all debug-run modules */
+export const logger = new Logger();
+
+/**
+ * Configure the process-wide logger (called once from the CLI)
+ */
+export function configureLogger(options: LoggerOptions): void {
+  logger.configure(options);
+}
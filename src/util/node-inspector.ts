@@ -0,0 +1,222 @@
+/**
+ * Node Inspector Discovery (--discover)
+ *
+ * Finds running Node processes started with --inspect so one can be attached to without
+ * knowing its port. Candidate ports (9229 and the ports after it, plus on Linux every local
+ * listening TCP port) are probed for the inspector's HTTP /json/list endpoint, and on Linux
+ * each inspector's port is traced back to the process that owns it.
+ */
+
+import * as fs from 'node:fs';
+import * as http from 'node:http';
+import { fileURLToPath } from 'node:url';
+
+export const DEFAULT_INSPECTOR_PORT = 9229;
+
+/** Ports after 9229 that are always probed (services started with --inspect=9230, ...) */
+const DEFAULT_PORT_RANGE = 20;
+
+/** Largest /json/list response read before giving up on a port */
+const MAX_RESPONSE_BYTES = 256 * 1024;
+
+const TCP_LISTEN = '0A';
+const IPV4_LOCAL = new Set(['00000000', '0100007F']);
+const IPV6_LOCAL = new Set(['0'.repeat(32), '0'.repeat(24) + '01000000']);
+
+export interface InspectorTarget {
+  host: string;
+  port: number;
+  /** Owning process (Linux only) */
+  pid?: number;
+  /** Entry script, from the target's file:// URL (or its title when there is none) */
+  script: string;
+  title: string;
+  webSocketDebuggerUrl?: string;
+}
+
+export interface ListeningSocket {
+  host: string;
+  port: number;
+  inode: string;
+}
+
+/** One entry of the inspector's /json/list response */
+interface InspectorListEntry {
+  type?: string;
+  title?: string;
+  url?: string;
+  webSocketDebuggerUrl?: string;
+}
+
+/**
+ * Local TCP sockets in the LISTEN state, from /proc/net/tcp and tcp6 (empty elsewhere)
+ */
+export function listeningSockets(procNet: string = '/proc/net'): ListeningSocket[] {
+  const sockets: ListeningSocket[] = [];
+  for (const [file, host, local] of [
+    ['tcp', '127.0.0.1', IPV4_LOCAL],
+    ['tcp6', '::1', IPV6_LOCAL],
+  ] as const) {
+    let table: string;
+    try {
+      table = fs.readFileSync(`${procNet}/${file}`, 'utf-8');
+    } catch {
+      continue;
+    }
+    // sl local_address rem_address st tx_queue:rx_queue tr:tm->when retrnsmt uid timeout inode
+    for (const line of table.split('\n').slice(1)) {
+      const fields = line.trim().split(/\s+/);
+      if (fields.length < 10 || fields[3] !== TCP_LISTEN) continue;
+      const [address, port] = fields[1].split(':');
+      if (!local.has(address)) continue;
+      sockets.push({ host, port: parseInt(port, 16), inode: fields[9] });
+    }
+  }
+  return sockets;
+}
+
+/**
+ * The process holding a socket, by scanning /proc/<pid>/fd (processes we can't read are
+ * skipped)
+ */
+function pidForSocket(inode: string): number | undefined {
+  const link = `socket:[${inode}]`;
+  let pids: string[];
+  try {
+    pids = fs.readdirSync('/proc').filter((entry) => /^\d+$/.test(entry));
+  } catch {
+    return undefined;
+  }
+  for (const pid of pids) {
+    let fds: string[];
+    try {
+      fds = fs.readdirSync(`/proc/${pid}/fd`);
+    } catch {
+      continue;
+    }
+    for (const fd of fds) {
+      try {
+        if (fs.readlinkSync(`/proc/${pid}/fd/${fd}`) === link) return parseInt(pid, 10);
+      } catch {
+        // The descriptor was closed while scanning
+      }
+    }
+  }
+  return undefined;
+}
+
+/**
+ * Ask a port for its inspector targets; empty if nothing there speaks the inspector protocol
+ */
+export function probeInspector(
+  host: string,
+  port: number,
+  timeout: number = 500
+): Promise<InspectorTarget[]> {
+  return new Promise((resolve) => {
+    const request = http.get({ host, port, path: '/json/list', timeout }, (response) => {
+      let body = '';
+      response.setEncoding('utf-8');
+      response.on('data', (chunk: string) => {
+        body += chunk;
+        if (body.length > MAX_RESPONSE_BYTES) request.destroy();
+      });
+      response.on('end', () => {
+        try {
+          resolve(parseInspectorList(host, port, JSON.parse(body)));
+        } catch {
+          resolve([]);
+        }
+      });
+      response.on('error', () => resolve([]));
+    });
+    request.on('timeout', () => request.destroy());
+    request.on('error', () => resolve([]));
+  });
+}
+
+/**
+ * Node targets from a /json/list response
+ */
+export function parseInspectorList(host: string, port: number, list: unknown): InspectorTarget[] {
+  if (!Array.isArray(list)) return [];
+  return (list as InspectorListEntry[])
+    .filter((entry) => entry?.type === 'node' && entry.webSocketDebuggerUrl)
+    .map((entry) => {
+      const title = entry.title ?? '';
+      let script = title;
+      if (entry.url?.startsWith('file://')) {
+        try {
+          script = fileURLToPath(entry.url);
+        } catch {
+          // Keep the title
+        }
+      }
+      return { host, port, script, title, webSocketDebuggerUrl: entry.webSocketDebuggerUrl };
+    });
+}
+
+/**
+ * Find Node processes listening for a debugger, sorted by port
+ */
+export async function discoverInspectors(
+  options: { timeout?: number } = {}
+): Promise<InspectorTarget[]> {
+  const sockets = listeningSockets();
+  const candidates = new Map<string, ListeningSocket | undefined>();
+  for (let offset = 0; offset <= DEFAULT_PORT_RANGE; offset++) {
+    candidates.set(`127.0.0.1:${DEFAULT_INSPECTOR_PORT + offset}`, undefined);
+  }
+  for (const socket of sockets) {
+    candidates.set(`${socket.host}:${socket.port}`, socket);
+  }
+
+  const probes = [...candidates].map(async ([key, socket]) => {
+    const separator = key.lastIndexOf(':');
+    const targets = await probeInspector(
+      key.slice(0, separator),
+      parseInt(key.slice(separator + 1), 10),
+      options.timeout
+    );
+    const pid = targets.length > 0 && socket ? pidForSocket(socket.inode) : undefined;
+    return targets.map((target) => (pid === undefined ? target : { ...target, pid }));
+  });
+
+  const targets = (await Promise.all(probes)).flat();
+  // An inspector listening on both 127.0.0.1 and ::1 is one process
+  const seen = new Set<string>();
+  return targets
+    .filter((target) => {
+      const id = target.webSocketDebuggerUrl?.replace(/^ws:\/\/[^/]+/, '') ?? '';
+      if (seen.has(id)) return false;
+      seen.add(id);
+      return true;
+    })
+    .sort((a, b) => a.port - b.port);
+}
+
+/**
+ * Narrow targets to the ones matching a --discover filter: a port or pid, or part of the
+ * script path or title
+ */
+export function matchInspectors(targets: InspectorTarget[], match: string): InspectorTarget[] {
+  if (/^\d+$/.test(match)) {
+    const number = parseInt(match, 10);
+    return targets.filter((target) => target.port === number || target.pid === number);
+  }
+  return targets.filter((target) => target.script.includes(match) || target.title.includes(match));
+}
+
+/**
+ * One numbered line per target for choosing between them
+ */
+export function formatInspectorTargets(targets: InspectorTarget[]): string {
+  const width = Math.max(...targets.map((target) => `${target.host}:${target.port}`.length));
+  return targets
+    .map((target, index) => {
+      const address = `${target.host}:${target.port}`.padEnd(width);
+      const pid = target.pid !== undefined ? `  pid ${target.pid}` : '';
+      return `  ${index + 1}. ${address}${pid}  ${target.script}`;
+    })
+    .join('\n');
+}
@@ -0,0 +1,189 @@
+/**
+ * npm Script Resolution
+ *
+ * Turns a package.json script into something js-debug can launch. Simple scripts
+ * ("node server.js --port 3000", "tsx src/index.ts") are expanded into a direct node
+ * invocation so the program is debugged in-process. Anything else (shell operators,
+ * other tools) is run through the package manager with child-process auto-attach.
+ */
+
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+
+export type PackageManager = 'npm' | 'yarn' | 'pnpm' | 'bun';
+
+export interface NpmScriptLaunch {
+  /** Package manager that owns the project */
+  packageManager: PackageManager;
+  /** Script name */
+  name: string;
+  /** Script command line from package.json */
+  command: string;
+  /** Directory containing package.json (used as the working directory) */
+  cwd: string;
+  /** Program to launch when the script was expanded into a node invocation */
+  program?: string;
+  /** node flags (or the package manager's "run <script>" when not expanded) */
+  runtimeArgs?: string[];
+  /** Runtime to launch instead of node (set when the script is not expanded) */
+  runtimeExecutable?: string;
+  /** Program arguments */
+  args?: string[];
+  /** Environment assignments from the script (e.g., NODE_ENV=development node app.js) */
+  env?: Record<string, string>;
+}
+
+const LOCKFILES: Array<[string, PackageManager]> = [
+  ['pnpm-lock.yaml', 'pnpm'],
+  ['yarn.lock', 'yarn'],
+  ['bun.lockb', 'bun'],
+  ['bun.lock', 'bun'],
+  ['package-lock.json', 'npm'],
+];
+
+/** Shell syntax that means the script can't be expanded into a single node invocation */
+const SHELL_OPERATORS = /&&|\|\||[|;<>`$()]/;
+
+/**
+ * Determine the project's package manager from package.json "packageManager" or lockfiles
+ */
+export function detectPackageManager(
+  projectDir: string,
+  packageJson: { packageManager?: string } = {}
+): PackageManager {
+  const declared = packageJson.packageManager?.split('@')[0];
+  if (declared === 'npm' || declared === 'yarn' || declared === 'pnpm' || declared === 'bun') {
+    return declared;
+  }
+
+  for (const [lockfile, manager] of LOCKFILES) {
+    if (fs.existsSync(path.join(projectDir, lockfile))) {
+      return manager;
+    }
+  }
+  return 'npm';
+}
+
+/**
+ * Split a script command line into words, honoring simple single/double quotes
+ */
+export function splitCommandLine(command: string): string[] {
+  const words: string[] = [];
+  const pattern = /"([^"]*)"|'([^']*)'|(\S+)/g;
+  let match: RegExpExecArray | null;
+  while ((match = pattern.exec(command)) !== null) {
+    words.push(match[1] ?? match[2] ?? match[3]);
+  }
+  return words;
+}
+
+/**
+ * Expand a script command into env/runtimeArgs/program/args, or null if it isn't a
+ * plain node/tsx/ts-node invocation
+ */
+export function expandScriptCommand(
+  command: string
+): Pick<NpmScriptLaunch, 'program' | 'runtimeArgs' | 'args' | 'env'> | null {
+  if (SHELL_OPERATORS.test(command)) {
+    return null;
+  }
+
+  const words = splitCommandLine(command);
+  const env: Record<string, string> = {};
+  while (words.length > 0 && /^[A-Za-z_][A-Za-z0-9_]*=/.test(words[0])) {
+    const [key, ...value] = words.shift()!.split('=');
+    env[key] = value.join('=');
+  }
+
+  const runtime = words.shift();
+  let runtimeArgs: string[];
+  if (runtime === 'node') {
+    runtimeArgs = [];
+  } else if (runtime === 'tsx') {
+    runtimeArgs = ['--import', 'tsx'];
+  } else if (runtime === 'ts-node') {
+    runtimeArgs = ['--require', 'ts-node/register'];
+  } else {
+    return null;
+  }
+
+  // Leading flags belong to the runtime; the first non-flag word is the program
+  while (words.length > 0 && words[0].startsWith('-')) {
+    const flag = words.shift()!;
+    runtimeArgs.push(flag);
+    // Flags that take a separate value
+    if (['-r', '--require', '--import', '--loader', '--env-file'].includes(flag) && words[0]) {
+      runtimeArgs.push(words.shift()!);
+    }
+  }
+
+  const program = words.shift();
+  if (!program) {
+    return null;
+  }
+  // "tsx watch src/index.ts" and the like: a bare word is a subcommand, not a script
+  if (runtime !== 'node' && !/[./\\]/.test(program)) {
+    return null;
+  }
+
+  return {
+    program,
+    runtimeArgs: runtimeArgs.length > 0 ? runtimeArgs : undefined,
+    args: words.length > 0 ? words : undefined,
+    env: Object.keys(env).length > 0 ? env : undefined,
+  };
+}
+
+/**
+ * Resolve a package.json script for launching under js-debug
+ *
+ * @throws Error if package.json or the script is missing
+ */
+export function resolveNpmScript(
+  projectDir: string,
+  name: string,
+  platform: NodeJS.Platform = process.platform
+): NpmScriptLaunch {
+  const packageJsonPath = path.join(projectDir, 'package.json');
+  if (!fs.existsSync(packageJsonPath)) {
+    throw new Error(`No package.json found in ${projectDir}`);
+  }
+
+  const packageJson = JSON.parse(fs.readFileSync(packageJsonPath, 'utf-8')) as {
+    scripts?: Record<string, string>;
+    packageManager?: string;
+  };
+  const command = packageJson.scripts?.[name];
+  if (command === undefined) {
+    const available = Object.keys(packageJson.scripts ?? {});
+    throw new Error(
+      `Script "${name}" not found in ${packageJsonPath}` +
+        (available.length > 0 ? `. Available scripts: ${available.join(', ')}` : '')
+    );
+  }
+
+  const packageManager = detectPackageManager(projectDir, packageJson);
+  const expanded = expandScriptCommand(command);
+  if (expanded) {
+    return {
+      packageManager,
+      name,
+      command,
+      cwd: projectDir,
+      ...expanded,
+      program: path.resolve(projectDir, expanded.program!),
+    };
+  }
+
+  return {
+    packageManager,
+    name,
+    command,
+    cwd: projectDir,
+    // The package managers are .cmd shims on Windows, which can't be spawned by bare name
+    runtimeExecutable: platform === 'win32' ? `${packageManager}.cmd` : packageManager,
+    runtimeArgs: ['run', name],
+    // Program arguments follow, and would otherwise be taken as the package manager's flags
+    args: ['--'],
+  };
+}
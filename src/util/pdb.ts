@@ -0,0 +1,192 @@
+/**
+ * .NET Assembly Debug Information
+ *
+ * Reads the debug directory of a .NET assembly: the CodeView entry that names its PDB and
+ * gives the id a symbol server files it under, and a portable PDB embedded in the assembly.
+ * Also finds the SourceLink map in a portable PDB, which says where each of its source files
+ * can be downloaded from.
+ */
+
+import * as path from 'node:path';
+import { inflateRawSync } from 'node:zlib';
+
+const DEBUG_TYPE_CODEVIEW = 2;
+const DEBUG_TYPE_EMBEDDED_PDB = 17;
+/** Minor version of a CodeView entry for a portable PDB ("PM") */
+const PORTABLE_PDB_MINOR_VERSION = 0x504d;
+
+export interface CodeViewInfo {
+  /** The PDB's path on the build machine */
+  pdbPath: string;
+  /** The PDB's id (32 hex digits) */
+  guid: string;
+  age: number;
+  /** Portable PDB rather than a Windows PDB */
+  portable: boolean;
+}
+
+export interface AssemblyDebugInfo {
+  codeView?: CodeViewInfo;
+  /** The decompressed portable PDB embedded in the assembly */
+  embeddedPdb?: Buffer;
+}
+
+function formatGuid(bytes: Buffer): string {
+  return (
+    bytes.readUInt32LE(0).toString(16).padStart(8, '0') +
+    bytes.readUInt16LE(4).toString(16).padStart(4, '0') +
+    bytes.readUInt16LE(6).toString(16).padStart(4, '0') +
+    bytes.subarray(8, 16).toString('hex')
+  ).toUpperCase();
+}
+
+/**
+ * The debug directory entries of a PE file
+ *
+ * @throws Error if the file isn't a PE image
+ */
+export function readAssemblyDebugInfo(image: Buffer): AssemblyDebugInfo {
+  if (image.length < 0x40 || image.readUInt16LE(0) !== 0x5a4d) {
+    throw new Error('Not a PE image');
+  }
+  const pe = image.readUInt32LE(0x3c);
+  if (pe + 24 > image.length || image.readUInt32LE(pe) !== 0x00004550) {
+    throw new Error('Not a PE image');
+  }
+  const sectionCount = image.readUInt16LE(pe + 6);
+  const optional = pe + 24;
+  const optionalSize = image.readUInt16LE(pe + 20);
+  const pe32Plus = image.readUInt16LE(optional) === 0x20b;
+  const directories = optional + (pe32Plus ? 112 : 96);
+  const directoryCount = image.readUInt32LE(optional + (pe32Plus ? 108 : 92));
+  if (directoryCount <= 6) return {};
+
+  const debugRva = image.readUInt32LE(directories + 6 * 8);
+  const debugSize = image.readUInt32LE(directories + 6 * 8 + 4);
+  const sections = optional + optionalSize;
+  let debugOffset: number | undefined;
+  for (let index = 0; index < sectionCount; index++) {
+    const section = sections + index * 40;
+    const virtualSize = image.readUInt32LE(section + 8);
+    const virtualAddress = image.readUInt32LE(section + 12);
+    if (debugRva >= virtualAddress && debugRva < virtualAddress + virtualSize) {
+      debugOffset = debugRva - virtualAddress + image.readUInt32LE(section + 20);
+      break;
+    }
+  }
+  if (debugOffset === undefined || debugSize === 0) return {};
+
+  const info: AssemblyDebugInfo = {};
+  for (let entry = debugOffset; entry + 28 <= debugOffset + debugSize; entry += 28) {
+    const type = image.readUInt32LE(entry + 12);
+    const size = image.readUInt32LE(entry + 16);
+    const data = image.subarray(image.readUInt32LE(entry + 24)).subarray(0, size);
+    const signature = data.toString('latin1', 0, 4);
+
+    if (type === DEBUG_TYPE_CODEVIEW && !info.codeView && signature === 'RSDS') {
+      const end = data.indexOf(0, 24);
+      info.codeView = {
+        pdbPath: data.toString('utf8', 24, end === -1 ? data.length : end),
+        guid: formatGuid(data.subarray(4, 20)),
+        age: data.readUInt32LE(20),
+        portable: image.readUInt16LE(entry + 10) === PORTABLE_PDB_MINOR_VERSION,
+      };
+    } else if (type === DEBUG_TYPE_EMBEDDED_PDB && signature === 'MPDB') {
+      info.embeddedPdb = inflateRawSync(data.subarray(8));
+    }
+  }
+  return info;
+}
+
+/**
+ * The PDB's file name, whichever machine it was built on
+ */
+export function pdbFileName(codeView: CodeViewInfo): string {
+  return path.win32.basename(codeView.pdbPath);
+}
+
+/**
+ * Where a symbol server files an assembly's PDB (name/id/name, lowercase); portable PDBs
+ * are filed with FFFFFFFF in place of the age
+ */
+export function symbolServerKey(codeView: CodeViewInfo): string {
+  const name = pdbFileName(codeView);
+  const age = codeView.portable ? 'FFFFFFFF' : codeView.age.toString(16);
+  return `${name}/${codeView.guid}${age}/${name}`.toLowerCase();
+}
+
+/**
+ * The SourceLink map in a portable PDB (build path pattern to URL pattern), if it has one
+ *
+ * The map is a JSON blob; it's found by its "documents" key rather than by reading the PDB's
+ * metadata tables.
+ */
+export function sourceLinkDocuments(pdb: Buffer): Record<string, string> | undefined {
+  const text = pdb.toString('latin1');
+  const start = /\{\s*"documents"\s*:/g;
+  for (let match = start.exec(text); match; match = start.exec(text)) {
+    const end = jsonObjectEnd(text, match.index);
+    if (end === undefined) continue;
+    try {
+      const map = JSON.parse(pdb.toString('utf8', match.index, end)) as { documents?: unknown };
+      const documents = map.documents;
+      if (documents && typeof documents === 'object' && !Array.isArray(documents)) {
+        return Object.fromEntries(
+          Object.entries(documents).filter(
+            (entry): entry is [string, string] => typeof entry[1] === 'string'
+          )
+        );
+      }
+    } catch {
+      // Not JSON after all; keep looking
+    }
+  }
+  return undefined;
+}
+
+/**
+ * The offset just past the JSON object starting at `start`
+ */
+function jsonObjectEnd(text: string, start: number): number | undefined {
+  let depth = 0;
+  let inString = false;
+  for (let index = start; index < text.length; index++) {
+    const char = text[index];
+    if (inString) {
+      if (char === '\\') index++;
+      else if (char === '"') inString = false;
+    } else if (char === '"') {
+      inString = true;
+    } else if (char === '{') {
+      depth++;
+    } else if (char === '}' && --depth === 0) {
+      return index + 1;
+    }
+  }
+  return undefined;
+}
+
+/**
+ * The URL a SourceLink map gives a build path, if any: exact entries first, then the
+ * longest matching "prefix*" entry (matched case-insensitively, as SourceLink does)
+ */
+export function sourceLinkUrl(
+  documents: Record<string, string>,
+  buildPath: string
+): string | undefined {
+  const lower = buildPath.toLowerCase();
+  let best: { prefix: string; url: string } | undefined;
+  for (const [pattern, url] of Object.entries(documents)) {
+    if (!pattern.endsWith('*')) {
+      if (pattern.toLowerCase() === lower) return url;
+      continue;
+    }
+    const prefix = pattern.slice(0, -1);
+    if (lower.startsWith(prefix.toLowerCase()) && (!best || prefix.length > best.prefix.length)) {
+      best = { prefix, url };
+    }
+  }
+  if (!best) return undefined;
+  const rest = buildPath.slice(best.prefix.length).replace(/\\/g, '/');
+  return best.url.replace('*', rest);
+}
@@ -0,0 +1,58 @@
+/**
+ * Process CPU Time
+ *
+ * Reads the user + system CPU time of the debuggee for session_end's termination
+ * section: from /proc/<pid>/stat on Linux and `ps -o time=` on macOS. The adapter
+ * reaps the debuggee as soon as it exits, so callers sample while it's still running.
+ */
+
+import { execFileSync } from 'node:child_process';
+import * as fs from 'node:fs';
+
+/** Clock ticks per second for /proc times (USER_HZ, 100 on every mainstream kernel) */
+const CLOCK_TICKS_PER_SECOND = 100;
+
+/**
+ * Parse the CPU time (utime + stime) out of a /proc/<pid>/stat line, in milliseconds
+ */
+export function parseProcStat(stat: string): number | undefined {
+  // The command name (field 2) is in parentheses and may contain spaces
+  const fields = stat.slice(stat.lastIndexOf(')') + 2).split(' ');
+  // Fields after the name start at 3 (state); utime and stime are 14 and 15
+  const utime = Number(fields[11]);
+  const stime = Number(fields[12]);
+  if (!Number.isFinite(utime) || !Number.isFinite(stime)) return undefined;
+  return ((utime + stime) * 1000) / CLOCK_TICKS_PER_SECOND;
+}
+
+/**
+ * Parse ps's cumulative CPU time ("[[dd-]hh:]mm:ss[.cc]"), in milliseconds
+ */
+export function parsePsCpuTime(value: string): number | undefined {
+  const match = value.trim().match(/^(?:(?:(\d+)-)?(\d+):)?(\d+):(\d+(?:\.\d+)?)$/);
+  if (!match) return undefined;
+  const [, days, hours, minutes, seconds] = match;
+  const totalSeconds =
+    Number(days ?? 0) * 86400 + Number(hours ?? 0) * 3600 + Number(minutes) * 60 + Number(seconds);
+  return Math.round(totalSeconds * 1000);
+}
+
+/**
+ * CPU time used so far by a running process, or undefined if it has exited or the
+ * platform isn't supported
+ */
+export function readCpuTimeMs(pid: number): number | undefined {
+  try {
+    if (process.platform === 'linux') {
+      return parseProcStat(fs.readFileSync(`/proc/${pid}/stat`, 'utf-8'));
+    }
+    if (process.platform === 'darwin') {
+      return parsePsCpuTime(
+        execFileSync('ps', ['-o', 'time=', '-p', String(pid)], { encoding: 'utf-8', timeout: 2000 })
+      );
+    }
+  } catch {
+    // The process is gone
+  }
+  return undefined;
+}
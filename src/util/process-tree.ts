@@ -0,0 +1,328 @@
+/**
+ * Process Tree Cleanup
+ *
+ * Adapters launch the debuggee (and sometimes helper processes) as their own children,
+ * so killing only the adapter leaves orphans behind. This module:
+ * - starts adapters in their own process group on Unix, so the whole tree can be signaled
+ * - terminates trees with SIGTERM, then SIGKILL after a grace period (taskkill /T /F on
+ *   Windows, since Job Objects aren't reachable from Node without a native module)
+ * - records spawned PIDs under ~/.debug-run/pids so trees left behind by a debug-run that
+ *   was itself SIGKILLed are swept on the next run
+ */
+
+import { execFileSync } from 'node:child_process';
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+import { getDebugRunHome } from './adapter-installer.js';
+import { logger } from './logger.js';
+
+/** Default time between SIGTERM and SIGKILL */
+const DEFAULT_GRACE_MS = 2000;
+const POLL_INTERVAL_MS = 50;
+
+/** Processes spawned by this debug-run that haven't been cleaned up yet */
+const trackedPids: Set<number> = new Set();
+let exitHandlerInstalled = false;
+
+export interface TrackedProcess {
+  /** PID of the debug-run process that spawned it */
+  owner: number;
+  pid: number;
+  /** Command that was spawned (guards against PID reuse when sweeping) */
+  command: string;
+  startedAt: string;
+  /** When the OS says the process started, compared before sweeping for the same reason */
+  processStart?: string;
+}
+
+/**
+ * Spawn options that put the child in its own process group (Unix only). On Windows,
+ * detached would open a new console window, and taskkill /T handles trees anyway.
+ */
+export function processGroupSpawnOptions(): { detached: boolean } {
+  return { detached: process.platform !== 'win32' };
+}
+
+/**
+ * Check whether a process (or, for negative values, a process group) exists
+ */
+export function isProcessAlive(pid: number): boolean {
+  try {
+    process.kill(pid, 0);
+    return true;
+  } catch (error) {
+    // EPERM means the process exists but belongs to someone else
+    return (error as NodeJS.ErrnoException).code === 'EPERM';
+  }
+}
+
+/**
+ * When a process started, as the OS records it: the starttime field of /proc/<pid>/stat on
+ * Linux, `ps -o lstart=` on other Unixes. Undefined if it can't be read (or on Windows).
+ */
+export function processStartTime(pid: number): string | undefined {
+  try {
+    if (process.platform === 'linux') {
+      const stat = fs.readFileSync(`/proc/${pid}/stat`, 'utf-8');
+      // The command name (field 2) may contain spaces and parentheses; field 3 follows the last ')'
+      return stat.slice(stat.lastIndexOf(')') + 1).trim().split(/\s+/)[19];
+    }
+    if (process.platform !== 'win32') {
+      const started = execFileSync('ps', ['-o', 'lstart=', '-p', String(pid)], {
+        encoding: 'utf-8',
+        timeout: 2000,
+      }).trim();
+      return started || undefined;
+    }
+  } catch {
+    // No such process
+  }
+  return undefined;
+}
+
+/**
+ * Parse `ps -A -o pid=,ppid=` output into a parent -> children map
+ */
+export function parseProcessTable(output: string): Map<number, number[]> {
+  const children = new Map<number, number[]>();
+  for (const line of output.split('\n')) {
+    const [pid, ppid] = line.trim().split(/\s+/).map(Number);
+    if (!pid || Number.isNaN(ppid)) continue;
+    const siblings = children.get(ppid) ?? [];
+    siblings.push(pid);
+    children.set(ppid, siblings);
+  }
+  return children;
+}
+
+/**
+ * List all descendants of a process (Unix), children before grandchildren.
+ * Returns [] if ps is unavailable.
+ */
+export function findDescendants(pid: number): number[] {
+  let table: Map<number, number[]>;
+  try {
+    table = parseProcessTable(
+      execFileSync('ps', ['-A', '-o', 'pid=,ppid='], { encoding: 'utf-8', timeout: 2000 })
+    );
+  } catch {
+    return [];
+  }
+
+  const descendants: number[] = [];
+  const queue = [pid];
+  while (queue.length > 0) {
+    for (const child of table.get(queue.shift()!) ?? []) {
+      if (!descendants.includes(child)) {
+        descendants.push(child);
+        queue.push(child);
+      }
+    }
+  }
+  return descendants;
+}
+
+function signalAll(pids: number[], groupLeader: number, signal: NodeJS.Signals): void {
+  // The process group covers children that stayed in it; individual PIDs cover
+  // descendants that moved to their own group (e.g., debuggees launched detached)
+  for (const target of [-groupLeader, groupLeader, ...pids]) {
+    try {
+      process.kill(target, signal);
+    } catch {
+      // Already gone
+    }
+  }
+}
+
+async function waitForExit(
+  pids: number[],
+  groupLeader: number,
+  timeoutMs: number
+): Promise<boolean> {
+  const deadline = Date.now() + timeoutMs;
+  while (Date.now() < deadline) {
+    if (!isProcessAlive(-groupLeader) && !pids.some((pid) => isProcessAlive(pid))) {
+      return true;
+    }
+    await new Promise((resolve) => setTimeout(resolve, POLL_INTERVAL_MS));
+  }
+  return false;
+}
+
+/**
+ * Terminate a process and all of its descendants. Sends SIGTERM, then SIGKILL to anything
+ * still alive after the grace period.
+ */
+export async function killProcessTree(
+  pid: number,
+  options: { graceMs?: number } = {}
+): Promise<void> {
+  if (process.platform === 'win32') {
+    try {
+      execFileSync('taskkill', ['/pid', String(pid), '/T', '/F'], { stdio: 'ignore' });
+    } catch {
+      // Already gone
+    }
+    return;
+  }
+
+  const descendants = findDescendants(pid);
+  signalAll(descendants, pid, 'SIGTERM');
+
+  if (await waitForExit(descendants, pid, options.graceMs ?? DEFAULT_GRACE_MS)) {
+    return;
+  }
+
+  logger.warn('process', 'process tree ignored SIGTERM, sending SIGKILL', {
+    pid,
+    descendants: descendants.length,
+  });
+  signalAll(descendants, pid, 'SIGKILL');
+}
+
+/**
+ * Directory holding PID files for processes spawned by running debug-run instances
+ */
+export function getPidDir(): string {
+  return path.join(getDebugRunHome(), 'pids');
+}
+
+function pidFile(pid: number): string {
+  return path.join(getPidDir(), `${process.pid}-${pid}.json`);
+}
+
+/**
+ * Send SIGTERM to every tracked tree. Synchronous so it can run in 'exit' handlers;
+ * PID files are kept so survivors are swept on the next run.
+ */
+export function terminateTrackedProcessesSync(): void {
+  for (const pid of trackedPids) {
+    if (process.platform === 'win32') {
+      try {
+        execFileSync('taskkill', ['/pid', String(pid), '/T', '/F'], { stdio: 'ignore' });
+      } catch {
+        // Already gone
+      }
+    } else {
+      signalAll([], pid, 'SIGTERM');
+    }
+  }
+  trackedPids.clear();
+}
+
+/**
+ * Record a spawned process so it can be swept if debug-run dies without cleaning up
+ */
+export function trackProcess(pid: number | undefined, command: string): void {
+  if (!pid) return;
+  trackedPids.add(pid);
+  if (!exitHandlerInstalled) {
+    exitHandlerInstalled = true;
+    // Covers process.exit() paths that skip normal session cleanup
+    process.on('exit', terminateTrackedProcessesSync);
+  }
+  const record: TrackedProcess = {
+    owner: process.pid,
+    pid,
+    command,
+    startedAt: new Date().toISOString(),
+    processStart: processStartTime(pid),
+  };
+  try {
+    fs.mkdirSync(getPidDir(), { recursive: true });
+    fs.writeFileSync(pidFile(pid), JSON.stringify(record));
+  } catch (error) {
+    logger.debug('process', 'could not write PID file', {
+      pid,
+      error: error instanceof Error ? error.message : String(error),
+    });
+  }
+}
+
+/**
+ * Forget a process once it has been cleaned up
+ */
+export function untrackProcess(pid: number | undefined): void {
+  if (!pid) return;
+  trackedPids.delete(pid);
+  try {
+    fs.unlinkSync(pidFile(pid));
+  } catch {
+    // Never written or already removed
+  }
+}
+
+/**
+ * Check that a PID is still the process we spawned (it may have been reused): it started at
+ * the recorded time and runs the command
+ */
+function isTrackedProcess(record: TrackedProcess): boolean {
+  if (record.processStart !== undefined && processStartTime(record.pid) !== record.processStart) {
+    return false;
+  }
+  return runsCommand(record.pid, record.command);
+}
+
+/**
+ * Check that a PID runs the command we spawned
+ */
+function runsCommand(pid: number, command: string): boolean {
+  try {
+    const actual =
+      process.platform === 'win32'
+        ? execFileSync('tasklist', ['/FI', `PID eq ${pid}`, '/FO', 'CSV', '/NH'], {
+            encoding: 'utf-8',
+            timeout: 5000,
+          })
+        : execFileSync('ps', ['-o', 'command=', '-p', String(pid)], {
+            encoding: 'utf-8',
+            timeout: 2000,
+          });
+    const name = path.basename(command).replace(/\.(exe|cmd|bat)$/i, '');
+    return actual.toLowerCase().includes(name.toLowerCase());
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Kill process trees left behind by debug-run instances that no longer exist
+ * (e.g., killed with SIGKILL). Returns the number of trees terminated.
+ */
+export async function sweepOrphanedProcesses(): Promise<number> {
+  let files: string[];
+  try {
+    files = fs.readdirSync(getPidDir()).filter((file) => file.endsWith('.json'));
+  } catch {
+    return 0;
+  }
+
+  let swept = 0;
+  for (const file of files) {
+    const filePath = path.join(getPidDir(), file);
+    let record: TrackedProcess;
+    try {
+      record = JSON.parse(fs.readFileSync(filePath, 'utf-8'));
+    } catch {
+      fs.rmSync(filePath, { force: true });
+      continue;
+    }
+
+    if (isProcessAlive(record.owner)) {
+      // Owned by a debug-run that is still running
+      continue;
+    }
+
+    if (isProcessAlive(record.pid) && isTrackedProcess(record)) {
+      logger.info('process', 'killing orphaned process tree', {
+        pid: record.pid,
+        command: record.command,
+        owner: record.owner,
+      });
+      await killProcessTree(record.pid, { graceMs: 500 });
+      swept++;
+    }
+    fs.rmSync(filePath, { force: true });
+  }
+  return swept;
+}
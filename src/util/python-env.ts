@@ -0,0 +1,146 @@
+/**
+ * Python Environment Detection
+ *
+ * Finds the interpreter a Python project actually runs with, so debugged programs
+ * see their virtualenv's packages without PATH tweaks. Checked in order:
+ * 1. An activated virtualenv ($VIRTUAL_ENV)
+ * 2. A project-local venv (.venv/ or venv/) in the program's directory, the working
+ *    directory, or a parent of either
+ * 3. Poetry's environment, when the nearest pyproject.toml uses [tool.poetry]
+ * 4. An activated conda environment ($CONDA_PREFIX)
+ */
+
+import { execSync } from 'node:child_process';
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+
+export type PythonEnvSource = 'virtualenv' | 'venv' | 'poetry' | 'conda';
+
+export interface PythonInterpreter {
+  /** Absolute path to the python executable */
+  path: string;
+  /** How the interpreter was found */
+  source: PythonEnvSource;
+}
+
+const LOCAL_VENV_DIRS = ['.venv', 'venv'];
+
+/**
+ * Path to the python executable inside an environment prefix
+ */
+export function pythonInPrefix(
+  prefix: string,
+  platform: NodeJS.Platform = process.platform
+): string {
+  return platform === 'win32'
+    ? path.join(prefix, 'Scripts', 'python.exe')
+    : path.join(prefix, 'bin', 'python');
+}
+
+/**
+ * Find the nearest directory (starting at `dir`) containing a project-local venv
+ */
+function findLocalVenv(dir: string): string | null {
+  let current = path.resolve(dir);
+  while (true) {
+    for (const name of LOCAL_VENV_DIRS) {
+      const python = pythonInPrefix(path.join(current, name));
+      if (fs.existsSync(python)) {
+        return python;
+      }
+    }
+
+    // Stop at the project root
+    if (fs.existsSync(path.join(current, 'pyproject.toml'))) {
+      return null;
+    }
+
+    const parent = path.dirname(current);
+    if (parent === current) {
+      return null;
+    }
+    current = parent;
+  }
+}
+
+/**
+ * Find the nearest directory (starting at `dir`) containing a pyproject.toml
+ */
+function findProjectRoot(dir: string): string | null {
+  let current = path.resolve(dir);
+  while (!fs.existsSync(path.join(current, 'pyproject.toml'))) {
+    const parent = path.dirname(current);
+    if (parent === current) {
+      return null;
+    }
+    current = parent;
+  }
+  return current;
+}
+
+/**
+ * Ask Poetry for the environment of the project containing `dir`, if it uses Poetry
+ */
+function findPoetryEnv(dir: string): string | null {
+  const root = findProjectRoot(dir);
+  if (!root) {
+    return null;
+  }
+  try {
+    if (!fs.readFileSync(path.join(root, 'pyproject.toml'), 'utf-8').includes('[tool.poetry')) {
+      return null;
+    }
+    const envPath = execSync('poetry env info --path', {
+      cwd: root,
+      encoding: 'utf-8',
+      stdio: ['ignore', 'pipe', 'ignore'],
+    }).trim();
+    const python = envPath ? pythonInPrefix(envPath) : '';
+    return python && fs.existsSync(python) ? python : null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Detect the project's Python interpreter, or null to use the default python3/python
+ *
+ * @param dirs - Directories to search from, in order (the program's, then the working directory)
+ */
+export function detectPythonInterpreter(
+  dirs: string[],
+  env: NodeJS.ProcessEnv = process.env
+): PythonInterpreter | null {
+  if (env.VIRTUAL_ENV) {
+    const python = pythonInPrefix(env.VIRTUAL_ENV);
+    if (fs.existsSync(python)) {
+      return { path: python, source: 'virtualenv' };
+    }
+  }
+
+  for (const dir of dirs) {
+    const localVenv = findLocalVenv(dir);
+    if (localVenv) {
+      return { path: localVenv, source: 'venv' };
+    }
+  }
+
+  for (const dir of dirs) {
+    const poetry = findPoetryEnv(dir);
+    if (poetry) {
+      return { path: poetry, source: 'poetry' };
+    }
+  }
+
+  if (env.CONDA_PREFIX) {
+    const python =
+      process.platform === 'win32'
+        ? path.join(env.CONDA_PREFIX, 'python.exe')
+        : path.join(env.CONDA_PREFIX, 'bin', 'python');
+    if (fs.existsSync(python)) {
+      return { path: python, source: 'conda' };
+    }
+  }
+
+  return null;
+}
@@ -0,0 +1,210 @@
+/**
+ * Resource Limits for the Debuggee
+ *
+ * Caps memory, CPU time and open files of the launched program so a runaway target
+ * under automated debugging can't take down the host. Limits are applied to the
+ * debuggee PID once the adapter reports it, so the adapter itself isn't constrained:
+ * - Linux: prlimit (RLIMIT_DATA, RLIMIT_CPU, RLIMIT_NOFILE), inherited by its children
+ * - Windows: a Job Object with per-process memory and CPU time limits (via PowerShell)
+ * - elsewhere: not supported (macOS can't set rlimits on another process)
+ */
+
+import { execFile } from 'node:child_process';
+import { promisify } from 'node:util';
+
+const execFileAsync = promisify(execFile);
+
+export interface ResourceLimits {
+  /** Maximum memory in bytes */
+  memoryBytes?: number;
+  /** Maximum CPU time in seconds */
+  cpuSeconds?: number;
+  /** Maximum open file descriptors */
+  fileDescriptors?: number;
+}
+
+const MEMORY_UNITS: Record<string, number> = {
+  '': 1,
+  K: 1024,
+  M: 1024 ** 2,
+  G: 1024 ** 3,
+  T: 1024 ** 4,
+};
+
+const CPU_UNITS: Record<string, number> = {
+  s: 1,
+  m: 60,
+  h: 3600,
+};
+
+/**
+ * Parse a memory size like "512M", "2G" or "1.5GiB" (binary units; bare numbers are bytes)
+ */
+export function parseMemoryLimit(value: string): number {
+  const match = value.trim().match(/^(\d+(?:\.\d+)?)\s*([KMGT]?)(?:i?B)?$/i);
+  if (!match) {
+    throw new Error(`Invalid memory limit: ${value}. Use a size like "512M" or "2G"`);
+  }
+  const bytes = Math.floor(parseFloat(match[1]) * MEMORY_UNITS[match[2].toUpperCase()]);
+  if (bytes <= 0) {
+    throw new Error(`Invalid memory limit: ${value}. Must be greater than zero`);
+  }
+  return bytes;
+}
+
+/**
+ * Parse a CPU time like "120s", "5m" or "1h" (bare numbers are seconds)
+ */
+export function parseCpuLimit(value: string): number {
+  const match = value.trim().match(/^(\d+)(s|m|h)?$/);
+  if (!match || parseInt(match[1], 10) === 0) {
+    throw new Error(`Invalid CPU limit: ${value}. Use a duration like "120s" or "5m"`);
+  }
+  return parseInt(match[1], 10) * CPU_UNITS[match[2] ?? 's'];
+}
+
+/**
+ * Parse an open file descriptor limit
+ */
+export function parseFdLimit(value: string): number {
+  if (!/^\d+$/.test(value.trim()) || parseInt(value, 10) === 0) {
+    throw new Error(`Invalid file descriptor limit: ${value}. Use a positive integer`);
+  }
+  return parseInt(value, 10);
+}
+
+/**
+ * Human-readable description of the limits, e.g. for logs
+ */
+export function describeResourceLimits(limits: ResourceLimits): Record<string, string | number> {
+  return {
+    ...(limits.memoryBytes !== undefined && {
+      memory: `${Math.round(limits.memoryBytes / 1024 ** 2)}M`,
+    }),
+    ...(limits.cpuSeconds !== undefined && { cpu: `${limits.cpuSeconds}s` }),
+    ...(limits.fileDescriptors !== undefined && { fds: limits.fileDescriptors }),
+  };
+}
+
+/**
+ * Limits the current platform can't enforce (e.g., file descriptors on Windows)
+ */
+export function unsupportedResourceLimits(
+  limits: ResourceLimits,
+  platform: NodeJS.Platform = process.platform
+): string[] {
+  const requested = [
+    limits.memoryBytes !== undefined && '--limit-mem',
+    limits.cpuSeconds !== undefined && '--limit-cpu',
+    limits.fileDescriptors !== undefined && '--limit-fds',
+  ].filter((flag): flag is string => Boolean(flag));
+
+  switch (platform) {
+    case 'linux':
+      return [];
+    case 'win32':
+      return requested.filter((flag) => flag === '--limit-fds');
+    default:
+      return requested;
+  }
+}
+
+/**
+ * Build the prlimit arguments for a process (Linux)
+ */
+export function prlimitArgs(pid: number, limits: ResourceLimits): string[] {
+  return [
+    '--pid',
+    String(pid),
+    // RLIMIT_DATA counts heap and private writable mappings, unlike RLIMIT_AS which also
+    // counts the large address space reservations made by V8 and the .NET runtime
+    ...(limits.memoryBytes !== undefined ? [`--data=${limits.memoryBytes}`] : []),
+    ...(limits.cpuSeconds !== undefined ? [`--cpu=${limits.cpuSeconds}`] : []),
+    ...(limits.fileDescriptors !== undefined ? [`--nofile=${limits.fileDescriptors}`] : []),
+  ];
+}
+
+/**
+ * PowerShell script that puts a process in a Job Object with memory/CPU limits (Windows).
+ * The job lives on after PowerShell exits because the process holds a reference to it.
+ */
+export function jobObjectScript(pid: number, limits: ResourceLimits): string {
+  const memory = limits.memoryBytes ?? 0;
+  // PerProcessUserTimeLimit is in 100ns units
+  const cpuTicks = (limits.cpuSeconds ?? 0) * 10_000_000;
+  return `
+$ErrorActionPreference = 'Stop'
+Add-Type -TypeDefinition @'
+using System;
+using System.Runtime.InteropServices;
+public static class DebugRunJob {
+  [StructLayout(LayoutKind.Sequential)]
+  struct BasicLimits {
+    public long PerProcessUserTimeLimit; public long PerJobUserTimeLimit; public uint LimitFlags;
+    public UIntPtr MinimumWorkingSetSize; public UIntPtr MaximumWorkingSetSize;
+    public uint ActiveProcessLimit; public UIntPtr Affinity; public uint PriorityClass;
+    public uint SchedulingClass;
+  }
+  [StructLayout(LayoutKind.Sequential)]
+  struct IoCounters { public ulong R, W, O, RB, WB, OB; }
+  [StructLayout(LayoutKind.Sequential)]
+  struct ExtendedLimits {
+    public BasicLimits Basic; public IoCounters Io; public UIntPtr ProcessMemoryLimit;
+    public UIntPtr JobMemoryLimit; public UIntPtr PeakProcessMemoryUsed; public UIntPtr PeakJobMemoryUsed;
+  }
+  [DllImport("kernel32.dll", SetLastError = true)] static extern IntPtr CreateJobObject(IntPtr attributes, string name);
+  [DllImport("kernel32.dll", SetLastError = true)] static extern bool SetInformationJobObject(IntPtr job, int infoClass, ref ExtendedLimits info, int length);
+  [DllImport("kernel32.dll", SetLastError = true)] static extern bool AssignProcessToJobObject(IntPtr job, IntPtr process);
+  [DllImport("kernel32.dll", SetLastError = true)] static extern IntPtr OpenProcess(uint access, bool inherit, int pid);
+  public static void Apply(int pid, ulong memory, long cpuTicks) {
+    var info = new ExtendedLimits();
+    if (memory > 0) { info.Basic.LimitFlags |= 0x100; info.ProcessMemoryLimit = new UIntPtr(memory); }
+    if (cpuTicks > 0) { info.Basic.LimitFlags |= 0x2; info.Basic.PerProcessUserTimeLimit = cpuTicks; }
+    var job = CreateJobObject(IntPtr.Zero, null);
+    if (job == IntPtr.Zero) throw new System.ComponentModel.Win32Exception();
+    if (!SetInformationJobObject(job, 9, ref info, Marshal.SizeOf(info))) throw new System.ComponentModel.Win32Exception();
+    var process = OpenProcess(0x0101, false, pid);
+    if (process == IntPtr.Zero) throw new System.ComponentModel.Win32Exception();
+    if (!AssignProcessToJobObject(job, process)) throw new System.ComponentModel.Win32Exception();
+  }
+}
+'@
+[DebugRunJob]::Apply(${pid}, ${memory}, ${cpuTicks})
+`;
+}
+
+/**
+ * Apply resource limits to a running process. Throws if the platform tool fails
+ * (e.g., prlimit missing, or raising a hard limit without privileges).
+ */
+export async function applyResourceLimits(pid: number, limits: ResourceLimits): Promise<void> {
+  if (process.platform === 'linux') {
+    try {
+      await execFileAsync('prlimit', prlimitArgs(pid, limits), { timeout: 5000 });
+    } catch (error) {
+      if ((error as NodeJS.ErrnoException).code === 'ENOENT') {
+        throw new Error('prlimit not found (install util-linux) - resource limits not applied');
+      }
+      throw new Error(`prlimit failed: ${(error as { stderr?: string }).stderr?.trim() || error}`);
+    }
+    return;
+  }
+
+  if (process.platform === 'win32') {
+    if (limits.memoryBytes === undefined && limits.cpuSeconds === undefined) return;
+    try {
+      await execFileAsync(
+        'powershell.exe',
+        ['-NoProfile', '-NonInteractive', '-Command', jobObjectScript(pid, limits)],
+        { timeout: 30000, windowsHide: true }
+      );
+    } catch (error) {
+      throw new Error(
+        `Job Object setup failed: ${(error as { stderr?: string }).stderr?.trim() || error}`
+      );
+    }
+    return;
+  }
+
+  throw new Error(`Resource limits are not supported on ${process.platform}`);
+}
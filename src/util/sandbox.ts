@@ -0,0 +1,421 @@
+/**
+ * Sandbox for the Debuggee (--sandbox)
+ *
+ * Runs the launched program with reduced privileges, so an automated agent can debug code
+ * it didn't write: the file system is read-only apart from the temp directory and
+ * --sandbox-write paths, --sandbox-hide paths (credentials, by default) can't be read, and
+ * there is no network without --sandbox-network.
+ * - Linux: bubblewrap (bwrap) with every namespace unshared (without network there is no
+ *   loopback either), no capabilities, a new session (so it can't type into our terminal),
+ *   an empty /run, only the SANDBOX_ENV variables and a seccomp filter (seccompFilter)
+ * - macOS: a sandbox-exec profile; loopback and Unix socket connections stay allowed
+ *
+ * Adapters that can start the program through a runInTerminal request do, and only that
+ * command is sandboxed. Otherwise, and with --sandbox-adapter, the adapter itself runs in
+ * the sandbox and the program inherits it.
+ */
+
+import { execFile } from 'node:child_process';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import { promisify } from 'node:util';
+import { commandExists, type AdapterConfig } from '../adapters/base.js';
+
+const execFileAsync = promisify(execFile);
+
+/** File descriptors bwrap reads its seccomp filter and its environment arguments from */
+const SECCOMP_FD = 3;
+const ENV_ARGS_FD = 4;
+
+export interface SandboxPolicy {
+  /** Paths that can be written besides the temp directory */
+  writable: string[];
+  /** Paths that can't be read */
+  hidden: string[];
+  /** Allow network access */
+  network: boolean;
+  /** Run the adapter in the sandbox too (--sandbox-adapter) */
+  adapter?: boolean;
+}
+
+/** Credential stores hidden unless --sandbox-hide is given, relative to the home directory */
+export const DEFAULT_HIDDEN_PATHS = [
+  '.ssh',
+  '.gnupg',
+  '.aws',
+  '.azure',
+  '.config/gcloud',
+  '.config/gh',
+  '.docker',
+  '.kube',
+  '.netrc',
+  '.npmrc',
+  '.pypirc',
+];
+
+/**
+ * Environment variables the sandbox keeps from ours (besides LC_*); the session's --env and
+ * what the adapter sets for the program are kept too
+ */
+export const SANDBOX_ENV = [
+  'PATH',
+  'HOME',
+  'USER',
+  'LOGNAME',
+  'SHELL',
+  'TERM',
+  'COLORTERM',
+  'LANG',
+  'LANGUAGE',
+  'TZ',
+  'TMPDIR',
+];
+
+/**
+ * The default --sandbox-hide paths
+ */
+export function defaultHiddenPaths(home = os.homedir()): string[] {
+  return DEFAULT_HIDDEN_PATHS.map((name) => path.join(home, name));
+}
+
+/**
+ * What the sandbox confines: just the program, or the adapter with the program it starts
+ */
+export function sandboxScope(
+  adapter: Pick<AdapterConfig, 'terminalLaunch'>,
+  policy: SandboxPolicy,
+  platform: NodeJS.Platform = process.platform
+): 'program' | 'adapter' {
+  if (policy.adapter || !adapter.terminalLaunch) return 'adapter';
+  // Without network a Linux sandbox has its own loopback, so the program couldn't reach
+  // an adapter outside it
+  if (adapter.terminalLaunch === 'loopback' && !policy.network && platform === 'linux') {
+    return 'adapter';
+  }
+  return 'program';
+}
+
+function isDirectory(target: string): boolean | undefined {
+  try {
+    return fs.statSync(target).isDirectory();
+  } catch {
+    return undefined;
+  }
+}
+
+function isRealDirectory(target: string): boolean {
+  try {
+    return fs.lstatSync(target).isDirectory();
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * The environment inside the sandbox: the SANDBOX_ENV variables of ours, then the given
+ * ones, where null removes a variable
+ */
+export function sandboxEnv(
+  env: Record<string, string | null> = {},
+  base: NodeJS.ProcessEnv = process.env
+): Record<string, string> {
+  const result: Record<string, string> = {};
+  for (const [name, value] of Object.entries(base)) {
+    if (value !== undefined && (SANDBOX_ENV.includes(name) || name.startsWith('LC_'))) {
+      result[name] = value;
+    }
+  }
+  for (const [name, value] of Object.entries(env)) {
+    if (value === null) delete result[name];
+    else result[name] = value;
+  }
+  return result;
+}
+
+/**
+ * bwrap arguments that run a command under the policy (Linux). Paths that don't exist are
+ * left out, since bwrap can't mount over them.
+ *
+ * The environment is cleared and set again from arguments on ENV_ARGS_FD, so values from
+ * --env don't show up in the command line. With seccomp, the filter is read from SECCOMP_FD.
+ * filesDir (where those are kept) is hidden from the program.
+ */
+export function bwrapArgs(
+  policy: SandboxPolicy,
+  tmpDir = os.tmpdir(),
+  options: { filesDir?: string; seccomp?: boolean } = {}
+): string[] {
+  const args = ['--unshare-all'];
+  if (policy.network) args.push('--share-net');
+  args.push('--new-session', '--die-with-parent', '--cap-drop', 'ALL');
+  args.push('--clearenv', '--args', String(ENV_ARGS_FD));
+  args.push('--ro-bind', '/', '/', '--dev-bind', '/dev', '/dev');
+  // Sockets of the session bus, the user's agents, ... (/run/user is under /run)
+  for (const target of ['/run', '/var/run']) {
+    if (isRealDirectory(target)) args.push('--tmpfs', target);
+  }
+  if (policy.network) {
+    // e.g. systemd-resolved's stub resolv.conf
+    const resolvConf = path.dirname(realPath('/etc/resolv.conf'));
+    if (resolvConf === '/run' || resolvConf.startsWith('/run/')) {
+      args.push('--ro-bind', resolvConf, resolvConf);
+    }
+  }
+  for (const target of [tmpDir, ...policy.writable]) {
+    if (isDirectory(target) !== undefined) args.push('--bind', target, target);
+  }
+  if (options.filesDir) args.push('--tmpfs', options.filesDir);
+  for (const target of policy.hidden) {
+    const directory = isDirectory(target);
+    if (directory) args.push('--tmpfs', target);
+    else if (directory === false) args.push('--ro-bind', '/dev/null', target);
+  }
+  if (options.seccomp) args.push('--seccomp', String(SECCOMP_FD));
+  args.push('--');
+  return args;
+}
+
+/**
+ * Syscall numbers the seccomp filter refuses, by architecture: syslog, acct, mount, umount2,
+ * swapon, swapoff, reboot, init_module, delete_module, quotactl, pivot_root, kexec_load,
+ * add_key, request_key, keyctl, unshare, perf_event_open, open_by_handle_at, setns,
+ * finit_module, kexec_file_load, bpf, userfaultfd and the mount API (open_tree, move_mount,
+ * fsopen, fsconfig, fsmount, fspick). clone, clone3 and ioctl are looked at more closely.
+ */
+const SECCOMP_ARCHES: Record<
+  string,
+  { audit: number; blocked: number[]; clone: number; clone3: number; ioctl: number }
+> = {
+  x64: {
+    audit: 0xc000003e,
+    blocked: [
+      103, 163, 165, 166, 167, 168, 169, 175, 176, 179, 155, 246, 248, 249, 250, 272, 298, 304,
+      308, 313, 320, 321, 323, 428, 429, 430, 431, 432, 433,
+    ],
+    clone: 56,
+    clone3: 435,
+    ioctl: 16,
+  },
+  arm64: {
+    audit: 0xc00000b7,
+    blocked: [
+      116, 89, 40, 39, 224, 225, 142, 105, 106, 60, 41, 104, 217, 218, 219, 97, 241, 265, 268,
+      273, 294, 280, 282, 428, 429, 430, 431, 432, 433,
+    ],
+    clone: 220,
+    clone3: 435,
+    ioctl: 29,
+  },
+};
+
+const BPF_LD_ABS = 0x20;
+const BPF_JEQ = 0x15;
+const BPF_JGE = 0x35;
+const BPF_JSET = 0x45;
+const BPF_RET = 0x06;
+const SECCOMP_RET_ALLOW = 0x7fff0000;
+const SECCOMP_RET_ERRNO = 0x00050000;
+const EPERM = 1;
+const ENOSYS = 38;
+const X32_SYSCALL_BIT = 0x40000000;
+const CLONE_NEWUSER = 0x10000000;
+const TIOCSTI = 0x5412;
+
+/**
+ * A seccomp BPF program (struct sock_filter[]) for bwrap --seccomp, or undefined on
+ * architectures it doesn't know. It fails with EPERM what a debuggee has no business doing:
+ * loading kernel modules or BPF programs, mounting, new namespaces (including clone with
+ * CLONE_NEWUSER), the kernel keyring, perf events, typing into the terminal (TIOCSTI), and
+ * any syscall of another ABI (x32, 32-bit). clone3 fails with ENOSYS, since its flags can't
+ * be checked, so libc falls back to clone.
+ */
+export function seccompFilter(arch: string = process.arch): Buffer | undefined {
+  const syscalls = SECCOMP_ARCHES[arch];
+  if (!syscalls) return undefined;
+
+  // Jumps name a label; they're turned into offsets once the program is laid out
+  type Instruction = { code: number; k: number; jt?: string; jf?: string; label?: string };
+  const load = (offset: number): Instruction => ({ code: BPF_LD_ABS, k: offset });
+  const ret = (k: number, label?: string): Instruction => ({ code: BPF_RET, k, label });
+  // seccomp_data: nr, arch, instruction_pointer, then six 64-bit args (low half first)
+  const NR = 0;
+  const ARCH = 4;
+  const arg = (n: number) => 16 + 8 * n;
+
+  const program: Instruction[] = [
+    load(ARCH),
+    { code: BPF_JEQ, k: syscalls.audit, jf: 'deny' },
+    load(NR),
+  ];
+  if (arch === 'x64') program.push({ code: BPF_JGE, k: X32_SYSCALL_BIT, jt: 'deny' });
+  for (const nr of syscalls.blocked) {
+    program.push({ code: BPF_JEQ, k: nr, jt: 'deny' });
+  }
+  program.push(
+    { code: BPF_JEQ, k: syscalls.clone3, jt: 'nosys' },
+    { code: BPF_JEQ, k: syscalls.clone, jt: 'clone' },
+    { code: BPF_JEQ, k: syscalls.ioctl, jt: 'ioctl' },
+    ret(SECCOMP_RET_ALLOW),
+    { ...load(arg(0)), label: 'clone' },
+    { code: BPF_JSET, k: CLONE_NEWUSER, jt: 'deny' },
+    ret(SECCOMP_RET_ALLOW),
+    { ...load(arg(1)), label: 'ioctl' },
+    { code: BPF_JEQ, k: TIOCSTI, jt: 'deny' },
+    ret(SECCOMP_RET_ALLOW),
+    ret(SECCOMP_RET_ERRNO | EPERM, 'deny'),
+    ret(SECCOMP_RET_ERRNO | ENOSYS, 'nosys')
+  );
+
+  const labels = new Map(program.map((instruction, i) => [instruction.label, i]));
+  const jump = (from: number, label?: string) => (label ? labels.get(label)! - from - 1 : 0);
+  const buffer = Buffer.alloc(program.length * 8);
+  program.forEach((instruction, i) => {
+    buffer.writeUInt16LE(instruction.code, i * 8);
+    buffer.writeUInt8(jump(i, instruction.jt), i * 8 + 2);
+    buffer.writeUInt8(jump(i, instruction.jf), i * 8 + 3);
+    buffer.writeUInt32LE(instruction.k >>> 0, i * 8 + 4);
+  });
+  return buffer;
+}
+
+/** Where the seccomp filter and environment arguments are kept */
+let filesDir: string | undefined;
+let envFileCount = 0;
+
+function sandboxFilesDir(): string {
+  if (!filesDir) {
+    const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-bwrap-'));
+    process.once('exit', () => fs.rmSync(dir, { recursive: true, force: true }));
+    filesDir = dir;
+  }
+  return filesDir;
+}
+
+/**
+ * The bwrap command line, run through sh to open the files bwrap reads its filter and
+ * environment from
+ */
+function bwrapSpawn(
+  command: string,
+  args: string[],
+  policy: SandboxPolicy,
+  env: Record<string, string | null>
+): { command: string; args: string[] } {
+  const dir = sandboxFilesDir();
+  const envFile = path.join(dir, `env-${++envFileCount}`);
+  const envArgs = Object.entries(sandboxEnv(env)).flatMap(([name, value]) => [
+    '--setenv',
+    name,
+    value,
+  ]);
+  fs.writeFileSync(envFile, envArgs.map((arg) => `${arg}\0`).join(''), { mode: 0o600 });
+
+  const filter = seccompFilter();
+  const filterFile = path.join(dir, `seccomp-${process.arch}.bpf`);
+  if (filter && !fs.existsSync(filterFile)) {
+    fs.writeFileSync(filterFile, filter, { mode: 0o600 });
+  }
+  const files = filter ? [envFile, filterFile] : [envFile];
+  const redirects = filter ? `${ENV_ARGS_FD}<"$1" ${SECCOMP_FD}<"$2"` : `${ENV_ARGS_FD}<"$1"`;
+  return {
+    command: 'sh',
+    args: [
+      '-c',
+      `exec ${redirects}; shift ${files.length}; exec bwrap "$@"`,
+      'bwrap',
+      ...files,
+      ...bwrapArgs(policy, os.tmpdir(), { filesDir: dir, seccomp: filter !== undefined }),
+      command,
+      ...args,
+    ],
+  };
+}
+
+function realPath(target: string): string {
+  try {
+    return fs.realpathSync(target);
+  } catch {
+    return path.resolve(target);
+  }
+}
+
+function profileString(value: string): string {
+  return `"${value.replace(/\\/g, '\\\\').replace(/"/g, '\\"')}"`;
+}
+
+/**
+ * A sandbox-exec profile for the policy (macOS). Later rules take precedence, so writes are
+ * denied and then allowed where the policy says.
+ */
+export function sandboxExecProfile(policy: SandboxPolicy, tmpDir = os.tmpdir()): string {
+  const subpaths = (targets: string[]) =>
+    targets.map((target) => `(subpath ${profileString(realPath(target))})`).join(' ');
+  const writable = subpaths([tmpDir, ...policy.writable]);
+  const lines = [
+    '(version 1)',
+    '(allow default)',
+    '(deny file-write*)',
+    `(allow file-write* (subpath "/dev") (subpath "/private/tmp") ${writable})`,
+  ];
+  if (policy.hidden.length > 0) {
+    lines.push(`(deny file-read* file-write* ${subpaths(policy.hidden)})`);
+  }
+  if (!policy.network) {
+    lines.push(
+      '(deny network*)',
+      '(allow network* (remote ip "localhost:*") (local ip "localhost:*") (remote unix-socket))'
+    );
+  }
+  return lines.join('\n');
+}
+
+/**
+ * The command line that runs a command in the sandbox. env is what the command should get
+ * on top of the SANDBOX_ENV variables (Linux; on macOS the command gets the whole
+ * environment).
+ *
+ * @throws Error on platforms without a sandbox
+ */
+export function sandboxSpawn(
+  command: string,
+  args: string[],
+  policy: SandboxPolicy,
+  env: Record<string, string | null> = {},
+  platform: NodeJS.Platform = process.platform
+): { command: string; args: string[] } {
+  switch (platform) {
+    case 'linux':
+      return bwrapSpawn(command, args, policy, env);
+    case 'darwin':
+      return {
+        command: 'sandbox-exec',
+        args: ['-p', sandboxExecProfile(policy), command, ...args],
+      };
+    default:
+      throw new Error(`--sandbox is not supported on ${platform}`);
+  }
+}
+
+/**
+ * Check that the sandbox can be created here, by running true in it
+ *
+ * @throws Error if the tool is missing or can't sandbox (e.g., user namespaces disabled)
+ */
+export async function checkSandbox(policy: SandboxPolicy): Promise<void> {
+  const { command, args } = sandboxSpawn('true', [], policy);
+  const tool = process.platform === 'linux' ? 'bwrap' : command;
+  if (!(await commandExists(tool))) {
+    throw new Error(
+      tool === 'bwrap'
+        ? 'bwrap not found (install bubblewrap) - needed for --sandbox'
+        : `${tool} not found - needed for --sandbox`
+    );
+  }
+  try {
+    await execFileAsync(command, args, { timeout: 10000 });
+  } catch (error) {
+    const reason = (error as { stderr?: string }).stderr?.trim() || error;
+    throw new Error(`${tool} can't create the sandbox: ${reason}`);
+  }
+}
@@ -8,6 +8,12 @@
 import { spawn, ChildProcess } from 'node:child_process';
 import * as path from 'node:path';
 import * as readline from 'node:readline';
+import {
+  killProcessTree,
+  processGroupSpawnOptions,
+  trackProcess,
+  untrackProcess,
+} from './process-tree.js';
 
 export interface TestRunnerConfig {
   /** Path to the test project directory or .csproj file */
@@ -69,7 +75,9 @@ export async function launchTestRunner(config: TestRunnerConfig): Promise<TestRu
       VSTEST_HOST_DEBUG: '1',
     },
     stdio: ['ignore', 'pipe', 'pipe'],
+    ...processGroupSpawnOptions(),
   });
+  trackProcess(testProcess.pid, 'dotnet');
 
   // Create readline interfaces for stdout and stderr
   const stdoutReader = readline.createInterface({ input: testProcess.stdout! });
@@ -146,15 +154,11 @@ export async function launchTestRunner(config: TestRunnerConfig): Promise<TestRu
 }
 
 /**
- * Cleans up the test runner process.
+ * Cleans up the test runner process and the testhost it launched.
  * Call this after debugging is complete.
  */
-export function cleanupTestRunner(testProcess: ChildProcess): void {
-  try {
-    if (!testProcess.killed) {
-      testProcess.kill('SIGTERM');
-    }
-  } catch {
-    // Process may already be dead
-  }
+export async function cleanupTestRunner(testProcess: ChildProcess): Promise<void> {
+  if (!testProcess.pid) return;
+  await killProcessTree(testProcess.pid);
+  untrackProcess(testProcess.pid);
 }
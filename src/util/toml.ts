@@ -0,0 +1,217 @@
+/**
+ * TOML Parsing
+ *
+ * The part of TOML that debug-run's config files use: [tables] (dotted names included),
+ * key = value pairs with bare, quoted or dotted keys, and values that are strings, numbers,
+ * booleans or arrays (which may span lines). Inline tables, multi-line strings, dates and
+ * arrays of tables are rejected with the line they appear on.
+ */
+
+export type TomlTable = { [key: string]: TomlValue };
+export type TomlValue = string | number | boolean | TomlValue[] | TomlTable;
+
+interface Cursor {
+  text: string;
+  pos: number;
+  line: number;
+}
+
+const BARE_KEY = /[A-Za-z0-9_-]/;
+const NUMBER = /^[+-]?\d[\d_]*(\.\d[\d_]*)?([eE][+-]?\d+)?/;
+const ESCAPES: Record<string, string> = { b: '\b', t: '\t', n: '\n', f: '\f', r: '\r' };
+
+function fail(cursor: Cursor, message: string): never {
+  throw new Error(`line ${cursor.line}: ${message}`);
+}
+
+function isTable(value: TomlValue | undefined): value is TomlTable {
+  return typeof value === 'object' && !Array.isArray(value);
+}
+
+/** Skip spaces and tabs */
+function skipSpace(cursor: Cursor): void {
+  while (cursor.text[cursor.pos] === ' ' || cursor.text[cursor.pos] === '\t') cursor.pos++;
+}
+
+/** Skip whitespace, newlines and comments */
+function skipBlank(cursor: Cursor): void {
+  for (;;) {
+    skipSpace(cursor);
+    const char = cursor.text[cursor.pos];
+    if (char === '#') {
+      while (cursor.pos < cursor.text.length && cursor.text[cursor.pos] !== '\n') cursor.pos++;
+    } else if (char === '\n' || char === '\r') {
+      if (char === '\n') cursor.line++;
+      cursor.pos++;
+    } else {
+      return;
+    }
+  }
+}
+
+/** Require the rest of the line to be empty (or a comment) */
+function endLine(cursor: Cursor): void {
+  skipSpace(cursor);
+  if (cursor.text[cursor.pos] === '#') {
+    while (cursor.pos < cursor.text.length && cursor.text[cursor.pos] !== '\n') cursor.pos++;
+  }
+  if (cursor.text[cursor.pos] === '\r') cursor.pos++;
+  if (cursor.pos < cursor.text.length && cursor.text[cursor.pos] !== '\n') {
+    fail(cursor, `unexpected "${cursor.text[cursor.pos]}" after value`);
+  }
+}
+
+function expect(cursor: Cursor, char: string): void {
+  skipSpace(cursor);
+  if (cursor.text[cursor.pos] !== char) {
+    fail(cursor, `expected "${char}"`);
+  }
+  cursor.pos++;
+}
+
+function parseString(cursor: Cursor): string {
+  const quote = cursor.text[cursor.pos];
+  if (cursor.text.startsWith(quote.repeat(3), cursor.pos)) {
+    fail(cursor, 'multi-line strings are not supported');
+  }
+  cursor.pos++;
+
+  let value = '';
+  for (;;) {
+    const char = cursor.text[cursor.pos++];
+    if (char === undefined || char === '\n') fail(cursor, 'unterminated string');
+    if (char === quote) return value;
+    // Literal ('...') strings have no escapes
+    if (char !== '\\' || quote === "'") {
+      value += char;
+      continue;
+    }
+
+    const escape = cursor.text[cursor.pos++];
+    if (escape === '"' || escape === '\\') {
+      value += escape;
+    } else if (escape in ESCAPES) {
+      value += ESCAPES[escape];
+    } else if (escape === 'u' || escape === 'U') {
+      const length = escape === 'u' ? 4 : 8;
+      const hex = cursor.text.slice(cursor.pos, cursor.pos + length);
+      if (!/^[0-9A-Fa-f]+$/.test(hex) || hex.length !== length) fail(cursor, 'invalid \\u escape');
+      value += String.fromCodePoint(parseInt(hex, 16));
+      cursor.pos += length;
+    } else {
+      fail(cursor, `invalid escape "\\${escape}"`);
+    }
+  }
+}
+
+/** A key, split into its dotted segments */
+function parseKey(cursor: Cursor): string[] {
+  const segments: string[] = [];
+  for (;;) {
+    skipSpace(cursor);
+    const char = cursor.text[cursor.pos];
+    if (char === '"' || char === "'") {
+      segments.push(parseString(cursor));
+    } else {
+      const start = cursor.pos;
+      while (BARE_KEY.test(cursor.text[cursor.pos] ?? '')) cursor.pos++;
+      if (cursor.pos === start) fail(cursor, 'expected a key');
+      segments.push(cursor.text.slice(start, cursor.pos));
+    }
+    skipSpace(cursor);
+    if (cursor.text[cursor.pos] !== '.') return segments;
+    cursor.pos++;
+  }
+}
+
+function parseValue(cursor: Cursor): TomlValue {
+  skipSpace(cursor);
+  const char = cursor.text[cursor.pos];
+  const rest = cursor.text.slice(cursor.pos);
+
+  if (char === '"' || char === "'") {
+    return parseString(cursor);
+  }
+  if (char === '[') {
+    cursor.pos++;
+    const items: TomlValue[] = [];
+    for (;;) {
+      skipBlank(cursor);
+      if (cursor.text[cursor.pos] === ']') break;
+      items.push(parseValue(cursor));
+      skipBlank(cursor);
+      if (cursor.text[cursor.pos] === ',') {
+        cursor.pos++;
+      } else if (cursor.text[cursor.pos] !== ']') {
+        fail(cursor, 'expected "," or "]" in array');
+      }
+    }
+    cursor.pos++;
+    return items;
+  }
+  if (char === '{') {
+    fail(cursor, 'inline tables are not supported; use a [table]');
+  }
+
+  const word = rest.match(/^(true|false)(?![A-Za-z0-9_-])/);
+  if (word) {
+    cursor.pos += word[1].length;
+    return word[1] === 'true';
+  }
+  const number = rest.match(NUMBER);
+  if (number) {
+    if (/^[-:T]/.test(rest.slice(number[0].length))) {
+      fail(cursor, 'dates and times are not supported; use a string');
+    }
+    cursor.pos += number[0].length;
+    return Number(number[0].replace(/_/g, ''));
+  }
+  return fail(cursor, char === undefined ? 'expected a value' : `unexpected "${char}"`);
+}
+
+/** Walk (creating as needed) the tables named by keys */
+function descend(cursor: Cursor, table: TomlTable, keys: string[]): TomlTable {
+  for (const key of keys) {
+    const existing = Object.hasOwn(table, key) ? table[key] : undefined;
+    if (existing === undefined) {
+      table[key] = {};
+    } else if (!isTable(existing)) {
+      fail(cursor, `"${key}" is already a value, not a table`);
+    }
+    table = table[key] as TomlTable;
+  }
+  return table;
+}
+
+/**
+ * Parse a TOML document
+ *
+ * @throws Error naming the line for syntax errors, duplicate keys and unsupported features
+ */
+export function parseToml(text: string): TomlTable {
+  const root: TomlTable = {};
+  const cursor: Cursor = { text, pos: 0, line: 1 };
+  let table = root;
+
+  for (;;) {
+    skipBlank(cursor);
+    if (cursor.pos >= text.length) return root;
+
+    if (text[cursor.pos] === '[') {
+      if (text[cursor.pos + 1] === '[') fail(cursor, 'arrays of tables are not supported');
+      cursor.pos++;
+      const keys = parseKey(cursor);
+      expect(cursor, ']');
+      table = descend(cursor, root, keys);
+    } else {
+      const keys = parseKey(cursor);
+      expect(cursor, '=');
+      const value = parseValue(cursor);
+      const parent = descend(cursor, table, keys.slice(0, -1));
+      const key = keys[keys.length - 1];
+      if (Object.hasOwn(parent, key)) fail(cursor, `"${keys.join('.')}" is defined twice`);
+      parent[key] = value;
+    }
+    endLine(cursor);
+  }
+}
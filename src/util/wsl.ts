@@ -0,0 +1,134 @@
+/**
+ * WSL Support (--wsl)
+ *
+ * Runs the debug adapter, and so the program, inside a WSL distribution while debug-run
+ * runs on Windows. The adapter is started through wsl.exe, and the paths in its DAP traffic
+ * are translated between the two views of the file system: C:\src\app.py is
+ * /mnt/c/src/app.py to the adapter, and a distribution path like /home/me/app.py comes back
+ * as \\wsl.localhost\<distro>\home\me\app.py.
+ */
+
+import { execFileSync } from 'node:child_process';
+import * as path from 'node:path';
+import type { PathMapper } from '../dap/path-mapper.js';
+
+const DRIVE_PATH = /^([A-Za-z]):[\\/](.*)$/s;
+const WSL_SHARE_PATH = /^\\\\(?:\?\\)?wsl(?:\$|\.localhost)\\[^\\]+(\\.*)?$/is;
+const MOUNTED_DRIVE_PATH = /^\/mnt\/([a-z])(?:\/(.*))?$/s;
+
+/** Runs the adapter through a login shell (for PATH set in the profile) without quoting */
+const LOGIN_SHELL_EXEC = ['sh', '-lc', 'exec "$0" "$@"'];
+
+/** wsl.exe with the distribution to use (the default one when there is no name) */
+function wslArgs(distro: string | undefined, args: string[]): string[] {
+  return [...(distro ? ['--distribution', distro] : []), ...args];
+}
+
+/**
+ * A Windows path as the distribution sees it: drive paths under /mnt, paths on the
+ * distribution's own \\wsl$ share from its root. Anything else is returned unchanged.
+ */
+export function windowsToWslPath(windowsPath: string): string {
+  const drive = windowsPath.match(DRIVE_PATH);
+  if (drive) {
+    return `/mnt/${drive[1].toLowerCase()}/${drive[2].replace(/\\/g, '/')}`;
+  }
+  const share = windowsPath.match(WSL_SHARE_PATH);
+  if (share) {
+    return (share[1] ?? '\\').replace(/\\/g, '/');
+  }
+  return windowsPath;
+}
+
+/**
+ * A path inside the distribution as Windows sees it: /mnt/<drive> paths on the drive, other
+ * absolute paths on the distribution's \\wsl.localhost share (left unchanged when the
+ * distribution isn't known). Relative paths and other strings are returned unchanged.
+ */
+export function wslToWindowsPath(wslPath: string, distro?: string): string {
+  const drive = wslPath.match(MOUNTED_DRIVE_PATH);
+  if (drive) {
+    return `${drive[1].toUpperCase()}:\\${(drive[2] ?? '').replace(/\//g, '\\')}`;
+  }
+  if (wslPath.startsWith('/') && !wslPath.startsWith('//') && distro) {
+    return `\\\\wsl.localhost\\${distro}${wslPath.replace(/\//g, '\\')}`;
+  }
+  return wslPath;
+}
+
+function isWindowsPath(value: string): boolean {
+  return windowsToWslPath(value) !== value;
+}
+
+/**
+ * Path translation for DAP traffic with an adapter running in a distribution
+ */
+export function wslPathMapper(distro?: string): PathMapper {
+  return {
+    toAdapter: windowsToWslPath,
+    toLocal: (wslPath) => wslToWindowsPath(wslPath, distro),
+  };
+}
+
+/**
+ * The wsl.exe command line that runs an adapter inside a distribution. Path arguments are
+ * translated, a Windows executable is replaced by the distribution's command of the same
+ * name, and the environment is shared through WSLENV (translating values that are paths).
+ */
+export function wslSpawn(
+  command: string,
+  args: string[],
+  env: Record<string, string>,
+  distro?: string,
+  hostEnv: NodeJS.ProcessEnv = process.env
+): { command: string; args: string[]; env: Record<string, string> } {
+  const executable = /\.exe$/i.test(command)
+    ? path.win32.basename(command, path.win32.extname(command))
+    : windowsToWslPath(command);
+  const shared = Object.entries(env).map(([name, value]) =>
+    (isWindowsPath(value) ? `${name}/p` : name)
+  );
+  return {
+    command: 'wsl.exe',
+    args: wslArgs(distro, [
+      '--exec',
+      ...LOGIN_SHELL_EXEC,
+      executable,
+      ...args.map(windowsToWslPath),
+    ]),
+    env: {
+      ...env,
+      WSLENV: [hostEnv.WSLENV, ...shared].filter(Boolean).join(':'),
+    },
+  };
+}
+
+/**
+ * The name of the distribution --wsl runs in, checking that it exists
+ *
+ * @throws Error if wsl.exe isn't available or the distribution can't be started
+ */
+export function wslDistroName(distro?: string): string {
+  let name: string;
+  try {
+    name = execFileSync('wsl.exe', wslArgs(distro, ['--exec', 'printenv', 'WSL_DISTRO_NAME']), {
+      encoding: 'utf-8',
+      stdio: ['ignore', 'pipe', 'pipe'],
+      timeout: 30000,
+    }).trim();
+  } catch (error) {
+    const { code } = error as NodeJS.ErrnoException;
+    if (code === 'ENOENT') {
+      throw new Error('wsl.exe not found; install WSL to use --wsl');
+    }
+    throw new Error(
+      distro
+        ? `Could not start WSL distribution "${distro}" (see wsl.exe --list)`
+        : 'Could not start the default WSL distribution (see wsl.exe --list)'
+    );
+  }
+  if (!name) {
+    throw new Error('Could not read the WSL distribution name');
+  }
+  return name;
+}
@@ -2,14 +2,35 @@
  * Unit tests for debug adapter configurations
  */
 
+import * as fs from 'node:fs';
+import * as os from 'node:os';
 import * as path from 'node:path';
 import { describe, it, expect } from 'vitest';
-import { nodeAdapter } from '../../src/adapters/node.js';
-import { debugpyAdapter } from '../../src/adapters/debugpy.js';
+import {
+  nodeAdapter,
+  isTypeScriptProgram,
+  typeScriptRuntimeArgs,
+  DEFAULT_SKIP_FILES,
+  normalizeSkipFiles,
+} from '../../src/adapters/node.js';
+import { debugpyAdapter, detectDevServer, devServerArgs } from '../../src/adapters/debugpy.js';
 import { netcoredbgAdapter } from '../../src/adapters/netcoredbg.js';
-import { lldbAdapter } from '../../src/adapters/lldb.js';
+import { lldbAdapter, parseLldbExpressionOutput } from '../../src/adapters/lldb.js';
 import { vsdbgAdapter } from '../../src/adapters/vsdbg.js';
 import type { LaunchOptions, AttachOptions } from '../../src/adapters/base.js';
+import { getAdapter, getAdapterBackend } from '../../src/adapters/index.js';
+import {
+  createStdioAdapter,
+  fillLaunchTemplate,
+  loadLaunchTemplate,
+} from '../../src/adapters/stdio.js';
+import {
+  rustAdapter,
+  rustGdbAdapter,
+  rustLldbAdapter,
+  rustLldbInitCommands,
+  rustGdbArgs,
+} from '../../src/adapters/rust.js';
 
 describe('Node.js Adapter', () => {
   describe('properties', () => {
@@ -120,6 +141,68 @@ describe('Node.js Adapter', () => {
       expect(config.resolveSourceMapLocations).toBeDefined();
       expect(config.autoAttachChildProcesses).toBe(false);
     });
+
+    it('skips Node internals and node_modules unless --skip-files replaces them', () => {
+      expect(nodeAdapter.launchConfig({ program: 'test.js' }).skipFiles).toEqual(
+        DEFAULT_SKIP_FILES
+      );
+
+      const config = nodeAdapter.launchConfig({
+        program: 'test.js',
+        skipFiles: ['node_modules/**', '!node_modules/my-lib/**'],
+      });
+      expect(config.skipFiles).toEqual(['**/node_modules/**', '!**/node_modules/my-lib/**']);
+    });
+
+    it('starts the program through runInTerminal for --sandbox', () => {
+      expect(nodeAdapter.launchConfig({ program: 'test.js' }).console).toBe('internalConsole');
+      expect(nodeAdapter.launchConfig({ program: 'test.js', runInTerminal: true }).console).toBe(
+        'integratedTerminal'
+      );
+    });
+  });
+
+  describe('normalizeSkipFiles', () => {
+    it('anchors relative globs at any depth', () => {
+      expect(
+        normalizeSkipFiles(['<node_internals>/**', './dist/vendor/*.js', '/opt/lib/**', '**/x'])
+      ).toEqual(['<node_internals>/**', '**/dist/vendor/*.js', '/opt/lib/**', '**/x']);
+    });
+  });
+
+  describe('TypeScript programs', () => {
+    it('recognizes TypeScript sources', () => {
+      expect(isTypeScriptProgram('src/index.ts')).toBe(true);
+      expect(isTypeScriptProgram('src/index.mts')).toBe(true);
+      expect(isTypeScriptProgram('dist/index.js')).toBe(false);
+    });
+
+    it('uses --import for tsx and --require for ts-node', () => {
+      expect(typeScriptRuntimeArgs('tsx')).toEqual(['--import', 'tsx']);
+      expect(typeScriptRuntimeArgs('ts-node')).toEqual(['--require', 'ts-node/register']);
+    });
+
+    it('falls back to built-in type stripping without a loader', () => {
+      expect(typeScriptRuntimeArgs(null, '22.6.0')).toEqual(['--experimental-strip-types']);
+      expect(typeScriptRuntimeArgs(null, '23.6.0')).toEqual([]);
+      expect(typeScriptRuntimeArgs(null, '20.11.1')).toEqual([]);
+    });
+
+    it('prefers explicit runtime args', () => {
+      const config = nodeAdapter.launchConfig({
+        program: 'src/index.ts',
+        runtimeArgs: ['--loader', 'custom-loader'],
+      });
+
+      expect(config.runtimeArgs).toEqual(['--loader', 'custom-loader']);
+      expect(config.sourceMaps).toBe(true);
+    });
+
+    it('does not add runtime args for JavaScript programs', () => {
+      const config = nodeAdapter.launchConfig({ program: 'dist/index.js' });
+
+      expect(config.runtimeArgs).toBeUndefined();
+    });
   });
 
   describe('attachConfig', () => {
@@ -239,6 +322,23 @@ describe('Python (debugpy) Adapter', () => {
       expect(config.justMyCode).toBe(false);
     });
 
+    it('passes --just-my-code, --gevent and --django through', () => {
+      const config = debugpyAdapter.launchConfig({
+        program: 'app.py',
+        justMyCode: true,
+        gevent: true,
+        django: true,
+      });
+
+      expect(config).toMatchObject({ justMyCode: true, gevent: true, django: true });
+      expect(debugpyAdapter.launchConfig({ program: 'app.py' }).gevent).toBeUndefined();
+    });
+
+    it('starts the program through runInTerminal for --sandbox', () => {
+      const config = debugpyAdapter.launchConfig({ program: 'app.py', runInTerminal: true });
+      expect(config.console).toBe('integratedTerminal');
+    });
+
     it('uses cwd when provided', () => {
       const options: LaunchOptions = {
         program: 'test.py',
@@ -249,6 +349,53 @@ describe('Python (debugpy) Adapter', () => {
 
       expect(config.cwd).toBe('/project/src');
     });
+
+    it('runs a module with -m semantics', () => {
+      const options: LaunchOptions = {
+        program: '',
+        module: 'mypkg.cli',
+        cwd: '/project',
+      };
+
+      const config = debugpyAdapter.launchConfig(options);
+
+      expect(config.module).toBe('mypkg.cli');
+      expect(config.program).toBeUndefined();
+      expect(config.cwd).toBe('/project');
+    });
+
+    it('disables the Django autoreloader', () => {
+      const config = debugpyAdapter.launchConfig({
+        program: 'manage.py',
+        args: ['runserver', '8000'],
+      });
+
+      expect(config.args).toEqual(['runserver', '8000', '--noreload']);
+      expect(config.django).toBe(true);
+    });
+
+    it('disables the Flask reloader and debugger', () => {
+      const config = debugpyAdapter.launchConfig({
+        program: '',
+        module: 'flask',
+        args: ['run'],
+      });
+
+      expect(config.args).toEqual(['run', '--no-reload', '--no-debugger']);
+      expect(config.subProcess).toBe(true);
+      expect(config.jinja).toBe(true);
+    });
+
+    it('uses the given interpreter for the debuggee', () => {
+      const options: LaunchOptions = {
+        program: 'test.py',
+        interpreter: '/project/.venv/bin/python',
+      };
+
+      const config = debugpyAdapter.launchConfig(options);
+
+      expect(config.python).toBe('/project/.venv/bin/python');
+    });
   });
 
   describe('attachConfig', () => {
@@ -284,6 +431,32 @@ describe('Python (debugpy) Adapter', () => {
   });
 });
 
+describe('Python dev servers', () => {
+  it('detects Django runserver and flask run', () => {
+    expect(detectDevServer({ program: 'src/manage.py', args: ['runserver'] })).toBe('django');
+    expect(detectDevServer({ program: '', module: 'flask', args: ['--app', 'app', 'run'] })).toBe(
+      'flask'
+    );
+    expect(detectDevServer({ program: 'manage.py', args: ['migrate'] })).toBeNull();
+  });
+
+  it('adds --no-reload and --no-debugger for flask', () => {
+    expect(devServerArgs('flask', ['run'])).toEqual(['run', '--no-reload', '--no-debugger']);
+  });
+
+  it('respects reload flags already given', () => {
+    expect(devServerArgs('django', ['runserver', '--noreload'])).toEqual([
+      'runserver',
+      '--noreload',
+    ]);
+    expect(devServerArgs('flask', ['run', '--reload'])).toEqual([
+      'run',
+      '--reload',
+      '--no-debugger',
+    ]);
+  });
+});
+
 describe('.NET (netcoredbg) Adapter', () => {
   describe('properties', () => {
     it('has correct adapter ID', () => {
@@ -339,6 +512,17 @@ describe('.NET (netcoredbg) Adapter', () => {
       expect(config.args).toEqual(['--environment', 'Development']);
     });
 
+    it('enables Just My Code and exact source matching unless turned off', () => {
+      expect(netcoredbgAdapter.launchConfig({ program: 'MyApp.dll' })).toMatchObject({
+        justMyCode: true,
+        enableStepFiltering: true,
+        requireExactSource: true,
+      });
+
+      const config = netcoredbgAdapter.launchConfig({ program: 'MyApp.dll', justMyCode: false });
+      expect(config.justMyCode).toBe(false);
+    });
+
     it('uses cwd when provided', () => {
       const options: LaunchOptions = {
         program: 'MyApp.dll',
@@ -495,6 +679,35 @@ describe('LLDB Adapter', () => {
       expect(config.pid).toBe(11111);
     });
   });
+
+  describe('safeEvaluation', () => {
+    it('runs expressions without JIT', () => {
+      expect(lldbAdapter.safeEvaluation?.expression?.('count + 1')).toMatch(
+        /expression --allow-jit false -- count \+ 1$/
+      );
+    });
+
+    it('refuses assignments, which lldb interprets without JIT', () => {
+      const mutates = lldbAdapter.safeEvaluation!.mutates!;
+      for (const expression of ['x = 1', 'total += 2', '*p=3', 'n++', '--n', 'flags <<= 1']) {
+        expect(mutates(expression)).toBe(true);
+      }
+      for (const expression of ['a == b', 'a != b', 'a <= b', 's == "x = 1"', '(0..=5).len()']) {
+        expect(mutates(expression)).toBe(false);
+      }
+    });
+
+    it('parses expression command output', () => {
+      expect(parseLldbExpressionOutput('(int) $0 = 42\n')).toEqual({ result: '42', type: 'int' });
+      expect(parseLldbExpressionOutput('(const char *) $1 = 0x0000 "hi"')).toEqual({
+        result: '0x0000 "hi"',
+        type: 'const char *',
+      });
+      expect(() =>
+        parseLldbExpressionOutput("error: expression can't be interpreted or run")
+      ).toThrow("can't be interpreted or run");
+    });
+  });
 });
 
 describe('.NET (vsdbg) Adapter', () => {
@@ -596,6 +809,36 @@ describe('.NET (vsdbg) Adapter', () => {
       expect(config.console).toBe('internalConsole');
     });
 
+    it('passes --just-my-code and --require-exact-source through', () => {
+      const config = vsdbgAdapter.launchConfig({
+        program: 'MyApp.dll',
+        justMyCode: false,
+        requireExactSource: false,
+      });
+
+      expect(config.justMyCode).toBe(false);
+      expect(config.requireExactSource).toBe(false);
+    });
+
+    it('passes --symbol-server and --source-link to vsdbg', () => {
+      expect(vsdbgAdapter.launchConfig({ program: 'MyApp.dll' }).sourceLinkOptions).toBeUndefined();
+
+      const config = vsdbgAdapter.launchConfig({
+        program: 'MyApp.dll',
+        symbolServers: ['https://symbols.nuget.org/download/symbols'],
+        symbolCache: '/home/me/.debug-run/adapters/dotnet-symbols',
+        sourceLink: true,
+      });
+      expect(config.symbolOptions).toEqual({
+        searchMicrosoftSymbolServer: false,
+        searchNuGetOrgSymbolServer: false,
+        searchPaths: ['https://symbols.nuget.org/download/symbols'],
+        cachePath: '/home/me/.debug-run/adapters/dotnet-symbols',
+      });
+      expect(config.sourceLinkOptions).toEqual({ '*': { enabled: true } });
+      expect(vsdbgAdapter.symbolSupport).toBe('launch');
+    });
+
     it('includes symbol and logging options', () => {
       const options: LaunchOptions = {
         program: 'MyApp.dll',
@@ -633,3 +876,128 @@ describe('.NET (vsdbg) Adapter', () => {
     });
   });
 });
+
+describe('Adapter backends', () => {
+  it('selects netcoredbg or vsdbg for the dotnet adapter', () => {
+    const dotnet = getAdapter('dotnet')!;
+
+    expect(getAdapterBackend(dotnet, 'netcoredbg')).toBe(netcoredbgAdapter);
+    expect(getAdapterBackend(dotnet, 'VSDBG')).toBe(vsdbgAdapter);
+  });
+
+  it('rejects unknown backends with the available list', () => {
+    const dotnet = getAdapter('dotnet')!;
+
+    expect(() => getAdapterBackend(dotnet, 'mdbg')).toThrow(
+      'Available backends: netcoredbg, vsdbg'
+    );
+  });
+
+  it('rejects --backend for adapters without backends', () => {
+    expect(() => getAdapterBackend(nodeAdapter, 'vsdbg')).toThrow('does not support --backend');
+  });
+
+  it('selects lldb or gdb for the rust adapter', () => {
+    expect(getAdapter('rust')).toBe(rustAdapter);
+    expect(getAdapterBackend(rustAdapter, 'lldb')).toBe(rustLldbAdapter);
+    expect(getAdapterBackend(rustAdapter, 'gdb')).toBe(rustGdbAdapter);
+  });
+});
+
+describe('Rust Adapter', () => {
+  it('imports the toolchain LLDB formatters', () => {
+    const commands = rustLldbInitCommands('/rust/lib/rustlib/etc');
+
+    expect(commands[0]).toBe(
+      `command script import "${path.join('/rust/lib/rustlib/etc', 'lldb_lookup.py')}"`
+    );
+    expect(commands[1]).toContain('lldb_commands');
+  });
+
+  it('marks the pretty-printer directory safe for GDB auto-load', () => {
+    expect(rustGdbArgs('/rust/lib/rustlib/etc')).toEqual([
+      '--directory',
+      '/rust/lib/rustlib/etc',
+      '-iex',
+      'add-auto-load-safe-path /rust/lib/rustlib/etc',
+    ]);
+  });
+
+  it('builds a GDB DAP launch configuration', () => {
+    const config = rustGdbAdapter.launchConfig({
+      program: '/project/target/debug/myapp',
+      args: ['--fast'],
+      stopAtEntry: true,
+    });
+
+    expect(config.request).toBe('launch');
+    expect(config.program).toBe('/project/target/debug/myapp');
+    expect(config.args).toEqual(['--fast']);
+    expect(config.cwd).toBe('/project/target/debug');
+    expect(config.stopAtBeginningOfMainSubprogram).toBe(true);
+  });
+
+  it('runs GDB with the DAP interpreter', () => {
+    expect(rustGdbAdapter.args?.[0]).toBe('--interpreter=dap');
+  });
+});
+
+describe('stdio: adapters', () => {
+  const options: LaunchOptions = { program: '/app/main.lang', args: ['--fast'], cwd: '/app' };
+
+  it('runs the command line as a stdio DAP server', () => {
+    const adapter = createStdioAdapter('stdio:./tools/my-adapter --log "a b"')!;
+
+    expect(adapter.name).toBe('stdio:./tools/my-adapter --log "a b"');
+    expect(adapter.id).toBe('my-adapter');
+    expect(adapter.command).toBe(path.resolve('tools/my-adapter'));
+    expect(adapter.args).toEqual(['--log', 'a b']);
+    expect(adapter.transport).toBeUndefined();
+    expect(createStdioAdapter('stdio:')).toBeUndefined();
+  });
+
+  it('is not in the adapter registry', () => {
+    expect(getAdapter('stdio:./tools/my-adapter')).toBeUndefined();
+  });
+
+  it('sends a generic launch request without a template', () => {
+    expect(createStdioAdapter('stdio:my-adapter')!.launchConfig(options)).toEqual({
+      name: 'my-adapter Launch',
+      type: 'my-adapter',
+      request: 'launch',
+      program: '/app/main.lang',
+      args: ['--fast'],
+      cwd: '/app',
+      env: {},
+      stopOnEntry: false,
+    });
+  });
+
+  it('fills in a launch template', () => {
+    const template = { type: 'lang', file: '${program}', argv: '${args}', title: 'run ${args}' };
+    expect(createStdioAdapter('stdio:my-adapter', template)!.launchConfig(options)).toEqual({
+      request: 'launch',
+      type: 'lang',
+      file: '/app/main.lang',
+      argv: ['--fast'],
+      title: 'run --fast',
+    });
+    expect(fillLaunchTemplate({ request: 'attach', nested: ['${cwd}'] }, { cwd: '/w' })).toEqual({
+      request: 'attach',
+      nested: ['/w'],
+    });
+  });
+
+  it('rejects templates with unknown placeholders', () => {
+    const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-template-'));
+    try {
+      const file = path.join(dir, 'launch.json');
+      fs.writeFileSync(file, JSON.stringify({ file: '${workspaceFolder}/main' }));
+      expect(() => loadLaunchTemplate(file)).toThrow('unknown placeholder ${workspaceFolder}');
+      fs.writeFileSync(file, '[]');
+      expect(() => loadLaunchTemplate(file)).toThrow('expected a JSON object');
+    } finally {
+      fs.rmSync(dir, { recursive: true, force: true });
+    }
+  });
+});
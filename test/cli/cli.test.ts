@@ -3,7 +3,8 @@
  */
 
 import { describe, it, expect } from 'vitest';
-import { parseTimeout, createCli } from '../../src/cli.js';
+import type { Command } from 'commander';
+import { parseTimeout, createCli, configOptionValue, debuggeeExitCode } from '../../src/cli.js';
 
 describe('parseTimeout', () => {
   describe('milliseconds', () => {
@@ -111,6 +112,11 @@ describe('createCli', () => {
     expect(commands).toContain('list-adapters');
     expect(commands).toContain('install-adapter');
     expect(commands).toContain('install-skill');
+    expect(commands).toContain('query');
+    expect(commands).toContain('serve');
+    expect(commands).toContain('suggest');
+    expect(commands).toContain('resume');
+    expect(commands).toContain('verify');
   });
 
   it('has the expected main options', () => {
@@ -159,4 +165,176 @@ describe('createCli', () => {
     expect(optionNames).toContain('--compact');
     expect(optionNames).toContain('--stack-limit');
   });
+
+  it('has diagnostic logging options', () => {
+    const cli = createCli();
+    const optionNames = cli.options.map((opt) => opt.long);
+
+    expect(optionNames).toContain('--verbose');
+    expect(optionNames).toContain('--log-file');
+    expect(optionNames).toContain('--log-format');
+  });
+});
+
+describe('subcommand options', () => {
+  /** Parse a command line, returning the options the subcommand's action would get */
+  function parseSubcommand(args: string[]): Record<string, unknown> | undefined {
+    const cli = createCli().exitOverride();
+    let parsed: Record<string, unknown> | undefined;
+    for (const command of cli.commands) {
+      command.exitOverride().action((...actionArgs: unknown[]) => {
+        parsed = (actionArgs[actionArgs.length - 1] as Command).opts();
+      });
+    }
+    cli.parse(args, { from: 'user' });
+    return parsed;
+  }
+
+  it('gives options after the subcommand to the subcommand, not the main command', () => {
+    const options = parseSubcommand([
+      'breakpoint-locations',
+      'app.js:1-20',
+      '-a',
+      'node',
+      '--args',
+      'one',
+      'two',
+      '--cwd',
+      '/work',
+      '-t',
+      '5s',
+    ]);
+
+    expect(options).toMatchObject({
+      adapter: 'node',
+      args: ['one', 'two'],
+      cwd: '/work',
+      timeout: '5s',
+    });
+  });
+
+  it('still takes main options before the subcommand', () => {
+    const options = parseSubcommand([
+      '--log-format',
+      'text',
+      'breakpoint-locations',
+      'app.js:1-20',
+      '-a',
+      'node',
+    ]);
+    expect(options).toMatchObject({ adapter: 'node', timeout: '30s' });
+  });
+
+  it('parses verify with its required -a, -b and --program', () => {
+    const options = parseSubcommand([
+      'verify',
+      '-a',
+      'python',
+      '--program',
+      'app.py',
+      '-b',
+      'app.py:10',
+      'lib.py:3',
+      '--cwd',
+      '/work',
+      '-t',
+      '10s',
+    ]);
+
+    expect(options).toMatchObject({
+      adapter: 'python',
+      program: 'app.py',
+      breakpoint: ['app.py:10', 'lib.py:3'],
+      cwd: '/work',
+      timeout: '10s',
+    });
+  });
+
+  it('parses capabilities with its own -a, --cwd and -t', () => {
+    const options = parseSubcommand(['capabilities', '-a', 'lldb', '--cwd', '/work', '-t', '3s']);
+    expect(options).toMatchObject({ adapter: 'lldb', cwd: '/work', timeout: '3s' });
+  });
+
+  it('parses conformance with its own -a and -t', () => {
+    const options = parseSubcommand([
+      'conformance',
+      '-a',
+      'rust',
+      '-t',
+      '1m',
+      '--feature',
+      'step-in',
+    ]);
+    expect(options).toMatchObject({ adapter: 'rust', timeout: '1m', feature: ['step-in'] });
+  });
+
+  it("parses diagnose-sources --verbose as its own, not the main command's -v", () => {
+    expect(parseSubcommand(['diagnose-sources', 'dist', '--verbose'])).toMatchObject({
+      verbose: true,
+    });
+    expect(parseSubcommand(['diagnose-sources', 'dist'])).toMatchObject({ verbose: false });
+  });
+});
+
+describe('configOptionValue', () => {
+  const cli = createCli();
+
+  it('maps config values onto option values', () => {
+    expect(configOptionValue(cli, 'timeout', '2m')).toEqual({ attribute: 'timeout', value: '2m' });
+    expect(configOptionValue(cli, 'stack-limit', 5)).toEqual({ attribute: 'stackLimit', value: 5 });
+    expect(configOptionValue(cli, 'eval', ['order', 'total'])).toEqual({
+      attribute: 'eval',
+      value: ['order', 'total'],
+    });
+    expect(configOptionValue(cli, 'pretty', true)).toEqual({ attribute: 'pretty', value: true });
+  });
+
+  it('reads false as the --no- form or "off"', () => {
+    expect(configOptionValue(cli, 'skip-files', false)).toEqual({
+      attribute: 'skipFiles',
+      value: false,
+    });
+    expect(configOptionValue(cli, 'capture-locals', false)).toEqual({
+      attribute: 'captureLocals',
+      value: false,
+    });
+    expect(configOptionValue(cli, 'just-my-code', false)).toEqual({
+      attribute: 'justMyCode',
+      value: 'off',
+    });
+  });
+
+  it('rejects unknown options and values that do not fit', () => {
+    expect(() => configOptionValue(cli, 'timeuot', '2m')).toThrow('unknown option "timeuot"');
+    expect(() => configOptionValue(cli, 'adapter', 'node')).toThrow("can't be set");
+    expect(() => configOptionValue(cli, 'pretty', 'yes')).toThrow('expected true or false');
+    expect(() => configOptionValue(cli, 'timeout', ['1s', '2s'])).toThrow('takes one value');
+    expect(() => configOptionValue(cli, 'just-my-code', 'maybe')).toThrow('"just-my-code"');
+  });
+});
+
+describe('debuggeeExitCode', () => {
+  const termination = {
+    reason: 'exited' as const,
+    exitCode: 3,
+    timedOut: false,
+    killed: false,
+    wallTimeMs: 10,
+  };
+
+  it("mirrors the program's exit code", () => {
+    expect(debuggeeExitCode(termination)).toBe(3);
+    expect(debuggeeExitCode({ ...termination, exitCode: 0 })).toBe(0);
+  });
+
+  it('uses 128 + the signal number when a signal killed the program', () => {
+    expect(
+      debuggeeExitCode({ ...termination, reason: 'crashed', exitCode: null, signal: 'SIGSEGV' })
+    ).toBe(139);
+  });
+
+  it('has no exit code when the program never exited', () => {
+    expect(debuggeeExitCode({ ...termination, reason: 'max_hits', exitCode: null })).toBeUndefined();
+    expect(debuggeeExitCode(undefined)).toBeUndefined();
+  });
 });
@@ -0,0 +1,97 @@
+/**
+ * Unit tests for path translation in DAP traffic
+ */
+
+import { describe, it, expect } from 'vitest';
+import { mapIncomingPaths, mapOutgoingPaths, type PathMapper } from '../../src/dap/path-mapper.js';
+import type { Event, Request, Response } from '../../src/dap/protocol.js';
+
+const mapper: PathMapper = {
+  toAdapter: (path) => path.replace(/^C:\\/, '/mnt/c/').replace(/\\/g, '/'),
+  toLocal: (path) => path.replace(/^\/mnt\/c\//, 'C:\\').replace(/\//g, '\\'),
+};
+
+describe('mapOutgoingPaths', () => {
+  it('translates paths anywhere in request arguments', () => {
+    const request: Request = {
+      seq: 3,
+      type: 'request',
+      command: 'launch',
+      arguments: {
+        program: 'C:\\src\\app.py',
+        cwd: 'C:\\src',
+        args: ['C:\\data\\in.csv', '-v'],
+      },
+    };
+
+    expect(mapOutgoingPaths(request, mapper)).toEqual({
+      ...request,
+      arguments: {
+        program: '/mnt/c/src/app.py',
+        cwd: '/mnt/c/src',
+        args: ['/mnt/c/data/in.csv', '-v'],
+      },
+    });
+    expect((request.arguments as { program: string }).program).toBe('C:\\src\\app.py');
+  });
+
+  it('leaves conditions, log messages and evaluations as written', () => {
+    const breakpoints: Request = {
+      seq: 4,
+      type: 'request',
+      command: 'setBreakpoints',
+      arguments: {
+        source: { path: 'C:\\src\\app.py' },
+        breakpoints: [{ line: 3, condition: "p == 'C:\\\\x'", logMessage: 'C:\\x' }],
+      },
+    };
+    const evaluate: Request = {
+      seq: 5,
+      type: 'request',
+      command: 'evaluate',
+      arguments: { expression: 'C:\\x' },
+    };
+
+    expect(mapOutgoingPaths(breakpoints, mapper)).toEqual({
+      ...breakpoints,
+      arguments: {
+        source: { path: '/mnt/c/src/app.py' },
+        breakpoints: [{ line: 3, condition: "p == 'C:\\\\x'", logMessage: 'C:\\x' }],
+      },
+    });
+    expect(mapOutgoingPaths(evaluate, mapper)).toBe(evaluate);
+  });
+});
+
+describe('mapIncomingPaths', () => {
+  it('translates the path of reported sources', () => {
+    const frame = { id: 1, name: 'main', line: 3, column: 1 };
+    const response: Response = {
+      seq: 9,
+      type: 'response',
+      request_seq: 6,
+      success: true,
+      command: 'stackTrace',
+      body: {
+        stackFrames: [{ ...frame, source: { name: 'app.py', path: '/mnt/c/src/app.py' } }],
+      },
+    };
+    const event: Event = {
+      seq: 10,
+      type: 'event',
+      event: 'output',
+      body: { output: 'see /mnt/c/src/app.py\n', source: { path: '/mnt/c/src/app.py' } },
+    };
+
+    expect(mapIncomingPaths(response, mapper)).toEqual({
+      ...response,
+      body: {
+        stackFrames: [{ ...frame, source: { name: 'app.py', path: 'C:\\src\\app.py' } }],
+      },
+    });
+    expect(mapIncomingPaths(event, mapper)).toEqual({
+      ...event,
+      body: { output: 'see /mnt/c/src/app.py\n', source: { path: 'C:\\src\\app.py' } },
+    });
+  });
+});
@@ -0,0 +1,184 @@
+/**
+ * Unit tests for runInTerminal reverse request handling
+ */
+
+import { describe, it, expect } from 'vitest';
+import {
+  buildTerminalCommand,
+  buildTerminalEnv,
+  quoteShellArg,
+  reportPidArgs,
+  TerminalLauncher,
+  wrapTerminalCommand,
+} from '../../src/dap/terminal.js';
+
+describe('quoteShellArg', () => {
+  it('quotes only arguments that need it', () => {
+    expect(quoteShellArg('/usr/bin/node')).toBe('/usr/bin/node');
+    expect(quoteShellArg('--inspect=9229')).toBe('--inspect=9229');
+    expect(quoteShellArg('hello world')).toBe("'hello world'");
+    expect(quoteShellArg("it's")).toBe("'it'\\''s'");
+  });
+});
+
+describe('buildTerminalCommand', () => {
+  const request = { args: ['node', 'app.js', 'a b'] };
+
+  it('runs the command under script on Linux', () => {
+    expect(buildTerminalCommand(request, true, 'linux')).toEqual({
+      command: 'script',
+      args: ['-qfec', "node app.js 'a b'", '/dev/null'],
+      shell: false,
+    });
+  });
+
+  it('uses the BSD script syntax on macOS', () => {
+    expect(buildTerminalCommand(request, true, 'darwin')).toEqual({
+      command: 'script',
+      args: ['-q', '/dev/null', 'node', 'app.js', 'a b'],
+      shell: false,
+    });
+    const shellRequest = { args: ['node app.js > out'], argsCanBeInterpretedByShell: true };
+    expect(buildTerminalCommand(shellRequest, true, 'darwin')).toEqual({
+      command: 'script',
+      args: ['-q', '/dev/null', 'sh', '-c', 'node app.js > out'],
+      shell: false,
+    });
+  });
+
+  it('spawns the command directly without a pseudo-terminal', () => {
+    expect(buildTerminalCommand(request, false, 'win32')).toEqual({
+      command: 'node',
+      args: ['app.js', 'a b'],
+      shell: false,
+    });
+    expect(
+      buildTerminalCommand({ args: ['node', 'app.js'], argsCanBeInterpretedByShell: true }, false)
+    ).toEqual({ command: 'node app.js', args: [], shell: true });
+  });
+
+  it('rejects an empty command', () => {
+    expect(() => buildTerminalCommand({ args: [] }, false)).toThrow(
+      'runInTerminal request has no command'
+    );
+  });
+});
+
+describe('reportPidArgs', () => {
+  it('runs the command through sh, which writes its PID first', () => {
+    expect(reportPidArgs(['node', 'app.js'], '/tmp/x.pid')).toEqual([
+      'sh',
+      '-c',
+      'echo $$ >"$0"; exec "$@"',
+      '/tmp/x.pid',
+      'node',
+      'app.js',
+    ]);
+  });
+});
+
+describe('wrapTerminalCommand', () => {
+  const wrap = (command: string, args: string[]) => ({ command: 'jail', args: [command, ...args] });
+
+  it('runs the command inside the wrapper, shell command lines through sh -c', () => {
+    const direct = { command: 'node', args: ['app.js'], shell: false };
+    expect(wrapTerminalCommand(direct, wrap)).toEqual({
+      command: 'jail',
+      args: ['node', 'app.js'],
+      shell: false,
+    });
+    const shell = { command: 'node app.js > out', args: [], shell: true };
+    expect(wrapTerminalCommand(shell, wrap)).toEqual({
+      command: 'jail',
+      args: ['sh', '-c', 'node app.js > out'],
+      shell: false,
+    });
+  });
+
+  it("passes the request's environment to the wrapper", () => {
+    const envWrap = (command: string, args: string[], env?: Record<string, string | null>) => ({
+      command: 'env',
+      args: [...Object.entries(env ?? {}).map(([name, value]) => `${name}=${value}`), command],
+    });
+    const direct = { command: 'node', args: [], shell: false };
+    expect(wrapTerminalCommand(direct, envWrap, { NODE_OPTIONS: '-r x' }).args).toEqual([
+      'NODE_OPTIONS=-r x',
+      'node',
+    ]);
+  });
+});
+
+describe('buildTerminalEnv', () => {
+  it('adds variables and removes the ones set to null', () => {
+    process.env.DEBUG_RUN_TERMINAL_TEST = 'set';
+    try {
+      const env = buildTerminalEnv({ NODE_OPTIONS: '--inspect', DEBUG_RUN_TERMINAL_TEST: null });
+      expect(env.NODE_OPTIONS).toBe('--inspect');
+      expect(env).not.toHaveProperty('DEBUG_RUN_TERMINAL_TEST');
+      expect(env.PATH).toBe(process.env.PATH);
+    } finally {
+      delete process.env.DEBUG_RUN_TERMINAL_TEST;
+    }
+  });
+});
+
+describe('TerminalLauncher', () => {
+  it("reports the program's PID and forwards the output", async () => {
+    const output: string[] = [];
+    const launcher = new TerminalLauncher((body) => output.push(body.output));
+
+    const exited = new Promise<void>((resolve) => {
+      const check = setInterval(() => {
+        if (output.join('').includes('done')) {
+          clearInterval(check);
+          resolve();
+        }
+      }, 10);
+    });
+    const body = await launcher.launch({
+      cwd: process.cwd(),
+      args: [process.execPath, '-e', 'console.log(`${process.env.GREETING} ${process.pid} done`)'],
+      env: { GREETING: 'hello' },
+    });
+    await exited;
+    await launcher.reap();
+
+    expect(body.processId).toEqual(expect.any(Number));
+    expect(output.join('')).toContain(`hello ${body.processId} done\n`);
+    expect(output.join('')).not.toContain('\r');
+  });
+
+  it.skipIf(process.platform === 'win32')('keeps stderr apart from stdout', async () => {
+    const output: { category?: string; output: string }[] = [];
+    // The wrapper's stderr isn't a terminal even when the command runs under script(1)
+    const launcher = new TerminalLauncher(
+      (body) => output.push(body),
+      (command, args) => ({
+        command: 'sh',
+        args: ['-c', 'echo warning >&2; exec "$@"', 'sh', command, ...args],
+      })
+    );
+
+    const body = await launcher.launch({ cwd: process.cwd(), args: ['echo', 'ok'] });
+    await new Promise<void>((resolve) => {
+      const check = setInterval(() => {
+        if (output.some((o) => o.output.includes('ok')) && output.length > 1) {
+          clearInterval(check);
+          resolve();
+        }
+      }, 10);
+    });
+    await launcher.reap();
+
+    expect(body).toEqual({ shellProcessId: expect.any(Number) });
+    expect(output).toContainEqual({ category: 'stderr', output: 'warning\n' });
+    expect(output.find((o) => o.output.includes('ok'))?.category).toBe('stdout');
+  });
+
+  it('fails when the command cannot be started', async () => {
+    const launcher = new TerminalLauncher(() => {});
+    await expect(
+      launcher.launch({ cwd: '/nonexistent-debug-run-dir', args: ['node'] })
+    ).rejects.toThrow();
+  });
+});
@@ -0,0 +1,83 @@
+/**
+ * Unit tests for session artifact bundles (--artifacts)
+ */
+
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import { afterEach, beforeEach, describe, it, expect } from 'vitest';
+import { SessionArtifacts } from '../../src/output/artifacts.js';
+import type { DebugEvent } from '../../src/output/events.js';
+
+describe('SessionArtifacts', () => {
+  let parent: string;
+  const now = new Date(2026, 9, 14, 9, 30, 15);
+  const timestamp = now.toISOString();
+
+  beforeEach(() => {
+    parent = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-artifacts-'));
+  });
+
+  afterEach(() => {
+    fs.rmSync(parent, { recursive: true, force: true });
+  });
+
+  it('creates a session-stamped folder, suffixed when it already exists', () => {
+    const first = SessionArtifacts.create(parent, 'checkout bug', now);
+    const second = SessionArtifacts.create(parent, 'checkout bug', now);
+
+    expect(path.basename(first.dir)).toBe('checkout-bug-20261014-093015');
+    expect(path.basename(second.dir)).toBe('checkout-bug-20261014-093015-2');
+  });
+
+  it('splits events, output, DAP traffic and stops into separate files', async () => {
+    const artifacts = SessionArtifacts.create(parent, 'app', now);
+    const events: DebugEvent[] = [
+      { type: 'program_output', timestamp, category: 'stdout', output: '{"order":1}\n' },
+      { type: 'program_output', timestamp, category: 'stderr', output: 'warn\n' },
+      {
+        type: 'breakpoint_hit',
+        timestamp,
+        id: 1,
+        threadId: 1,
+        location: { file: 'src/cart.ts', line: 42 },
+        stackTrace: [],
+        locals: {},
+      },
+    ];
+    for (const event of events) {
+      artifacts.observe(event);
+    }
+    artifacts.protocolMessage('send', { seq: 1, type: 'request' });
+    artifacts.protocolMessage('receive', { seq: 2, type: 'event' }, 'child');
+    artifacts.adapterStderr('adapter warning\n');
+    artifacts.adapterStdout('Debug server listening at 127.0.0.1:8123\n');
+    await artifacts.close();
+
+    const read = (file: string) => fs.readFileSync(path.join(artifacts.dir, file), 'utf-8');
+    expect(read('events.ndjson').trim().split('\n')).toHaveLength(3);
+    expect(read('program.stdout.log')).toBe('{"order":1}\n');
+    expect(read('program.stderr.log')).toBe('warn\n');
+    expect(read('adapter.stderr.log')).toBe('adapter warning\n');
+    expect(read('adapter.stdout.log')).toBe('Debug server listening at 127.0.0.1:8123\n');
+    expect(
+      read('dap.ndjson')
+        .trim()
+        .split('\n')
+        .map((line) => JSON.parse(line))
+    ).toEqual([
+      { ts: expect.any(String), direction: 'send', message: { seq: 1, type: 'request' } },
+      {
+        ts: expect.any(String),
+        direction: 'receive',
+        session: 'child',
+        message: { seq: 2, type: 'event' },
+      },
+    ]);
+    expect(JSON.parse(read('stops/001-breakpoint_hit.json'))).toMatchObject({
+      type: 'breakpoint_hit',
+      location: { line: 42 },
+    });
+    expect(fs.existsSync(path.join(artifacts.dir, 'debug-console.log'))).toBe(false);
+  });
+});
@@ -0,0 +1,150 @@
+/**
+ * Unit tests for expected-value baselines (--expect)
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import {
+  BaselineRecorder,
+  compareToBaseline,
+  formatBaselineDiff,
+  readBaseline,
+  valuesMatch,
+  writeBaseline,
+  type BaselineStop,
+} from '../../src/output/baseline.js';
+import type { DebugEvent } from '../../src/output/events.js';
+
+function stop(locals: Record<string, string>, evaluations: Record<string, string> = {}) {
+  return { event: 'breakpoint_hit', location: 'src/cart.ts:42', locals, evaluations };
+}
+
+describe('BaselineRecorder', () => {
+  it('records each stop with its location relative to the working directory', () => {
+    const recorder = new BaselineRecorder('/app');
+    recorder.observe({ type: 'session_start', timestamp: '', adapter: 'node' } as DebugEvent);
+    recorder.observe({
+      type: 'breakpoint_hit',
+      timestamp: '2026-10-14T10:00:01.000Z',
+      label: 'checkout',
+      threadId: 1,
+      location: { file: '/app/src/cart.ts', line: 42 },
+      stackTrace: [],
+      locals: {
+        order: { type: 'Order', value: { total: { type: 'number', value: 42.5 } } },
+      },
+      evaluations: { 'cart.items.length': { result: '3', type: 'number' } },
+    } as DebugEvent);
+
+    expect(recorder.stops).toEqual([
+      {
+        event: 'breakpoint_hit',
+        location: 'src/cart.ts:42',
+        label: 'checkout',
+        locals: { 'order.total': '42.5' },
+        evaluations: { 'cart.items.length': '3' },
+      },
+    ]);
+  });
+});
+
+describe('valuesMatch', () => {
+  it('compares text exactly but numbers up to rounding', () => {
+    expect(valuesMatch('"ok"', '"ok"')).toBe(true);
+    expect(valuesMatch('0.3', '0.30000000000000004')).toBe(true);
+    expect(valuesMatch('0.3', '0.31')).toBe(false);
+    expect(valuesMatch('ok', 'OK')).toBe(false);
+  });
+
+  it('applies tolerance rules', () => {
+    expect(valuesMatch('9.99', '10.004', { within: 0.02 })).toBe(true);
+    expect(valuesMatch('1000', '1011', { relative: 0.01 })).toBe(false);
+    expect(valuesMatch('2026-10-14T10:00:01Z', '2026-10-15 08:30:00', 'timestamp')).toBe(true);
+    expect(valuesMatch('2026-10-14T10:00:01Z', 'soon', 'timestamp')).toBe(false);
+    expect(valuesMatch('a', 'b', 'ignore')).toBe(true);
+  });
+});
+
+describe('compareToBaseline', () => {
+  it('reports changed, missing and new values with rules applied by name', () => {
+    const baseline = {
+      rules: { '*.price': { within: 0.01 }, 'request.id': 'ignore' as const },
+      stops: [stop({ total: '42.5', 'items[0].price': '9.99', 'request.id': '7', gone: '1' })],
+    };
+    const differences = compareToBaseline(baseline, [
+      stop({ total: '43.5', 'items[0].price': '9.995', 'request.id': '8', added: '2' }),
+    ]);
+    const at = { stop: 1, location: 'src/cart.ts:42', kind: 'local' };
+    expect(differences).toEqual([
+      { ...at, name: 'total', expected: '42.5', actual: '43.5' },
+      { ...at, name: 'gone', expected: '1' },
+      { ...at, name: 'added', actual: '2' },
+    ]);
+  });
+
+  it('reports stops that moved, went missing or are new', () => {
+    const moved: BaselineStop = { ...stop({}), location: 'src/cart.ts:50' };
+    const differences = compareToBaseline({ stops: [stop({}), stop({})] }, [moved]);
+    expect(differences).toEqual([
+      {
+        stop: 1,
+        location: 'src/cart.ts:50',
+        kind: 'stop',
+        expected: 'breakpoint_hit at src/cart.ts:42',
+        actual: 'breakpoint_hit at src/cart.ts:50',
+      },
+      {
+        stop: 2,
+        location: 'src/cart.ts:42',
+        kind: 'stop',
+        expected: 'breakpoint_hit at src/cart.ts:42',
+      },
+    ]);
+  });
+
+  it('suggests a timestamp rule for times that changed', () => {
+    const [difference] = compareToBaseline(
+      { stops: [stop({}, { 'order.createdAt': '2026-10-14T10:00:01Z' })] },
+      [stop({}, { 'order.createdAt': '2026-10-15T11:12:13Z' })]
+    );
+    expect(difference.hint).toBe('add the rule "order.createdAt": "timestamp" to accept any time');
+    expect(formatBaselineDiff('baseline.json', [difference]).split('\n')).toEqual([
+      'Expectation failed: 1 difference from baseline.json',
+      '  stop 1 (src/cart.ts:42)',
+      '    eval order.createdAt',
+      '      expected: 2026-10-14T10:00:01Z',
+      '      actual:   2026-10-15T11:12:13Z',
+      '      hint: add the rule "order.createdAt": "timestamp" to accept any time',
+    ]);
+  });
+});
+
+describe('baseline files', () => {
+  let dir: string;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-baseline-'));
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  it('writes a baseline that reads back with its rules', () => {
+    const file = path.join(dir, 'baseline.json');
+    writeBaseline(file, [stop({ total: '42.5' })], { total: { within: 1 } });
+    expect(readBaseline(file)).toEqual({
+      rules: { total: { within: 1 } },
+      stops: [stop({ total: '42.5' })],
+    });
+  });
+
+  it('rejects missing files and unknown rules', () => {
+    const file = path.join(dir, 'baseline.json');
+    expect(() => readBaseline(file)).toThrow('not found (create it with --expect-update)');
+    fs.writeFileSync(file, JSON.stringify({ rules: { total: 'close' }, stops: [] }));
+    expect(() => readBaseline(file)).toThrow('Invalid baseline rule "total"');
+  });
+});
@@ -4,6 +4,7 @@
 
 import { describe, it, expect } from 'vitest';
 import { OutputFormatter } from '../../src/output/formatter.js';
+import { Redactor } from '../../src/output/redaction.js';
 import { Writable } from 'stream';
 import type { BreakpointHitEvent, StackFrameInfo, VariableValue } from '../../src/output/events.js';
 
@@ -305,3 +306,61 @@ describe('OutputFormatter compact mode', () => {
     });
   });
 });
+
+describe('OutputFormatter event listeners', () => {
+  it('notifies listeners of events excluded from the output stream', () => {
+    const { stream, getOutput } = createCaptureStream();
+    const formatter = new OutputFormatter({ stream, include: ['session_end'] });
+    const seen: string[] = [];
+    formatter.onEvent((event) => seen.push(event.type));
+
+    formatter.sessionStart('node', 'app.js');
+    formatter.programOutput('stdout', 'hello\n');
+
+    expect(seen).toEqual(['session_start', 'program_output']);
+    expect(getOutput()).toHaveLength(0);
+  });
+});
+
+describe('OutputFormatter program streams', () => {
+  it('writes program output as-is instead of as events', () => {
+    const events = createCaptureStream();
+    const stdout = createCaptureStream();
+    const stderr = createCaptureStream();
+    const formatter = new OutputFormatter({
+      stream: events.stream,
+      programStreams: { stdout: stdout.stream, stderr: stderr.stream },
+    });
+    const seen: string[] = [];
+    formatter.onEvent((event) => seen.push(event.type));
+
+    formatter.sessionStart('node', 'app.js');
+    formatter.programOutput('stdout', '{"order":1}\n');
+    formatter.programOutput('stderr', 'warning\n');
+    formatter.programOutput('console', 'Debugger attached.\n');
+
+    expect(events.getOutput().map((line) => JSON.parse(line).type)).toEqual(['session_start']);
+    expect(stdout.getOutput()).toEqual(['{"order":1}']);
+    expect(stderr.getOutput()).toEqual(['warning', 'Debugger attached.']);
+    expect(seen).toEqual(['session_start', 'program_output', 'program_output', 'program_output']);
+  });
+
+  it('redacts program output and events before writing them', () => {
+    const events = createCaptureStream();
+    const stdout = createCaptureStream();
+    const formatter = new OutputFormatter({
+      stream: events.stream,
+      programStreams: { stdout: stdout.stream, stderr: createCaptureStream().stream },
+      redactor: new Redactor({ names: [], envNames: ['API_TOKEN'] }, { API_TOKEN: 'tok-42' }),
+    });
+    const seen: unknown[] = [];
+    formatter.onEvent((event) => seen.push(event.type === 'program_output' && event.output));
+
+    formatter.programOutput('stdout', 'token is tok-42\n');
+    formatter.error('login failed for tok-42');
+
+    expect(stdout.getOutput()).toEqual(['token is [REDACTED]']);
+    expect(JSON.parse(events.getOutput()[0]).message).toBe('login failed for [REDACTED]');
+    expect(seen).toEqual(['token is [REDACTED]\n', false]);
+  });
+});
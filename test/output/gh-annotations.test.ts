@@ -0,0 +1,120 @@
+/**
+ * Unit tests for GitHub Actions annotations (--output-format gh-annotations)
+ */
+
+import { describe, it, expect } from 'vitest';
+import { GitHubAnnotations, workflowCommand } from '../../src/output/gh-annotations.js';
+import type { DebugEvent } from '../../src/output/events.js';
+
+const timestamp = '2026-10-14T09:30:15.123Z';
+
+describe('workflowCommand', () => {
+  it('escapes the message and property values', () => {
+    expect(
+      workflowCommand('error', '100% wrong\nsee logs', { title: 'a: b, c', file: 'src/a.ts' })
+    ).toBe('::error title=a%3A b%2C c,file=src/a.ts::100%25 wrong%0Asee logs');
+    expect(workflowCommand('notice', 'done')).toBe('::notice::done');
+  });
+});
+
+describe('GitHubAnnotations', () => {
+  const annotations = () => new GitHubAnnotations('/repo');
+
+  it('annotates failed assertions at their location', () => {
+    const event: DebugEvent = {
+      type: 'assertion_failed',
+      timestamp,
+      threadId: 1,
+      assertion: 'total >= 0',
+      actualValue: 'false',
+      location: { file: '/repo/src/cart.ts', line: 42, column: 5 },
+      stackTrace: [],
+      locals: {},
+    };
+
+    expect(annotations().annotate(event)).toBe(
+      '::error title=Assertion failed,file=src/cart.ts,line=42,col=5::total >= 0 was false'
+    );
+    expect(annotations().annotate({ ...event, label: 'after-totals' })).toBe(
+      '::error title=Assertion failed at after-totals,file=src/cart.ts,line=42,col=5::total >= 0 was false'
+    );
+  });
+
+  it('warns about unverified breakpoints with the suggestions', () => {
+    const unverified: DebugEvent = {
+      type: 'breakpoint_set',
+      timestamp,
+      id: 1,
+      file: '/repo/src/cart.ts',
+      line: 80,
+      verified: false,
+      message: 'No code at this line',
+      diagnostics: {
+        requestedFile: 'src/cart.ts',
+        requestedLine: 80,
+        suggestions: ['Try line 82'],
+      },
+    };
+
+    expect(annotations().annotate(unverified)).toBe(
+      '::warning title=Breakpoint not verified,file=src/cart.ts,line=80::No code at this line%0ATry line 82'
+    );
+    expect(annotations().annotate({ ...unverified, verified: true })).toBeUndefined();
+  });
+
+  it('annotates crashes, leaving out files outside the workspace', () => {
+    const crash: DebugEvent = {
+      type: 'signal_received',
+      timestamp,
+      threadId: 1,
+      signal: { name: 'SIGSEGV', description: 'invalid address (fault address: 0x0)' },
+      location: { file: '/usr/include/c++/13/bits/vector.h', line: 1125 },
+      locals: {},
+      threads: [],
+    };
+
+    expect(annotations().annotate(crash)).toBe(
+      '::error title=Crash%3A SIGSEGV::invalid address (fault address: 0x0)'
+    );
+  });
+
+  it('sums up the session in a notice', () => {
+    const tracker = annotations();
+    tracker.annotate({
+      type: 'assertion_failed',
+      timestamp,
+      threadId: 1,
+      assertion: 'ok',
+      actualValue: 'false',
+      location: { file: '/repo/a.py', line: 1 },
+      stackTrace: [],
+      locals: {},
+    });
+
+    expect(
+      tracker.annotate({
+        type: 'session_end',
+        timestamp,
+        summary: {
+          durationMs: 900,
+          exitCode: null,
+          breakpointsHit: 1,
+          exceptionsCaught: 0,
+          stepsExecuted: 0,
+          termination: {
+            reason: 'assertion_failed',
+            exitCode: null,
+            timedOut: false,
+            killed: true,
+            wallTimeMs: 900,
+          },
+        },
+      })
+    ).toBe(
+      '::notice title=debug-run::Session ended: assertion failed (1 breakpoint hit, 0 exceptions, 1 failed assertion)'
+    );
+    expect(
+      tracker.annotate({ type: 'program_output', timestamp, category: 'stdout', output: 'x' })
+    ).toBeUndefined();
+  });
+});
@@ -0,0 +1,99 @@
+/**
+ * Unit tests for the HTML report (--output-format html)
+ */
+
+import { describe, it, expect } from 'vitest';
+import { HtmlReport } from '../../src/output/html-report.js';
+import type { DebugEvent } from '../../src/output/events.js';
+
+const timestamp = '2026-10-14T09:30:15.123Z';
+
+const EVENTS: DebugEvent[] = [
+  { type: 'session_start', timestamp, adapter: 'node', program: '/app/dist/cart.js' },
+  { type: 'program_output', timestamp, category: 'stdout', output: 'loading <cart>\n' },
+  {
+    type: 'breakpoint_hit',
+    timestamp,
+    threadId: 1,
+    location: { file: '/app/src/cart.ts', line: 42, function: 'total' },
+    stackTrace: [{ frameId: 1, function: 'total', file: '/app/src/cart.ts', line: 42 }],
+    locals: {
+      count: { type: 'number', value: 3 },
+      cart: {
+        type: 'Cart',
+        value: { owner: { type: 'string', value: 'Ada & Co' } },
+        expandable: true,
+      },
+    },
+    evaluations: { 'cart.items.length': { result: '3' } },
+    source: { startLine: 41, endLine: 43, lines: ['  41 | let sum = 0;', '> 42 | sum += x;'] },
+  },
+  { type: 'program_output', timestamp, category: 'stdout', output: 'done\n' },
+  {
+    type: 'session_end',
+    timestamp,
+    summary: {
+      durationMs: 1200,
+      exitCode: 0,
+      breakpointsHit: 1,
+      exceptionsCaught: 0,
+      stepsExecuted: 0,
+      termination: {
+        reason: 'exited',
+        exitCode: 0,
+        timedOut: false,
+        killed: false,
+        wallTimeMs: 1200,
+      },
+    },
+  },
+];
+
+function render(events: DebugEvent[]): string {
+  const report = new HtmlReport();
+  for (const event of events) report.observe(event);
+  return report.render();
+}
+
+describe('HtmlReport', () => {
+  it('renders a standalone page with the summary and each stop', () => {
+    const html = render(EVENTS);
+
+    expect(html.startsWith('<!DOCTYPE html>')).toBe(true);
+    expect(html).not.toMatch(/<script|<link/);
+    expect(html).toContain('<title>debug-run: cart.js</title>');
+    expect(html).toContain('<td>Ended</td><td>exited (exit code 0)</td>');
+    expect(html).toContain('#1 breakpoint_hit — total (cart.ts:42)');
+    expect(html).toContain('<span class="current">&gt; 42 | sum += x;</span>');
+    expect(html).toContain('<code>total</code> /app/src/cart.ts:42');
+  });
+
+  it('shows expanded variables as trees and escapes values', () => {
+    const html = render(EVENTS);
+
+    expect(html).toContain(
+      '<div><span class="name">count</span> <span class="type">number</span> = ' +
+        '<span class="value">3</span></div>'
+    );
+    expect(html).toContain(
+      '<details><summary><span class="name">cart</span> <span class="type">Cart</span></summary>'
+    );
+    expect(html).toContain('<span class="value">Ada &amp; Co</span>');
+    expect(html).toContain('<span class="name">cart.items.length</span> = ');
+  });
+
+  it('attaches program output to the stop it preceded', () => {
+    const html = render(EVENTS);
+
+    expect(html).toContain('<h3>Program output</h3><pre>loading &lt;cart&gt;\n</pre>');
+    expect(html).toContain('<h3>Output after the last stop</h3><pre>done\n</pre>');
+  });
+
+  it('says so when the program never stopped', () => {
+    const html = render([EVENTS[0], EVENTS[3], EVENTS[4]]);
+
+    expect(html).toContain('<h2>Stops (0)</h2>');
+    expect(html).toContain('<p>The program did not stop.</p>');
+    expect(html).toContain('<h3>Program output</h3><pre>done\n</pre>');
+  });
+});
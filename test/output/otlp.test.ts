@@ -0,0 +1,185 @@
+/**
+ * Tests for the OTLP trace exporter
+ */
+
+import { describe, it, expect, vi, afterEach } from 'vitest';
+import {
+  OtlpExporter,
+  otlpOptionsFromEnv,
+  parseKeyValueList,
+  parseTraceParent,
+  toOtlpAttributes,
+} from '../../src/output/otlp.js';
+import { Logger } from '../../src/util/logger.js';
+
+const TRACE_ID = '4bf92f3577b34da6a3ce929d0e0e4736';
+const PARENT_ID = '00f067aa0ba902b7';
+
+interface PayloadSpan {
+  traceId: string;
+  spanId: string;
+  parentSpanId?: string;
+  name: string;
+  attributes: Array<{ key: string; value: Record<string, unknown> }>;
+  events: Array<{ name: string; attributes: Array<{ key: string; value: unknown }> }>;
+  status?: { code: number; message?: string };
+}
+
+function payloadSpans(exporter: OtlpExporter): PayloadSpan[] {
+  const payload = exporter.buildPayload() as {
+    resourceSpans: Array<{ scopeSpans: Array<{ spans: PayloadSpan[] }> }>;
+  };
+  return payload.resourceSpans[0].scopeSpans[0].spans;
+}
+
+describe('parseTraceParent', () => {
+  it('extracts the trace and parent span IDs', () => {
+    expect(parseTraceParent(`00-${TRACE_ID}-${PARENT_ID}-01`)).toEqual({
+      traceId: TRACE_ID,
+      spanId: PARENT_ID,
+    });
+  });
+
+  it('rejects malformed or all-zero values', () => {
+    expect(parseTraceParent(undefined)).toBeNull();
+    expect(parseTraceParent('garbage')).toBeNull();
+    expect(parseTraceParent(`00-${'0'.repeat(32)}-${PARENT_ID}-01`)).toBeNull();
+  });
+});
+
+describe('otlpOptionsFromEnv', () => {
+  it('appends /v1/traces to base endpoints', () => {
+    expect(otlpOptionsFromEnv('http://collector:4318/', {}).endpoint).toBe(
+      'http://collector:4318/v1/traces'
+    );
+    expect(otlpOptionsFromEnv(undefined, {}).endpoint).toBe('http://localhost:4318/v1/traces');
+    expect(
+      otlpOptionsFromEnv(undefined, { OTEL_EXPORTER_OTLP_ENDPOINT: 'https://otel.example.com' })
+        .endpoint
+    ).toBe('https://otel.example.com/v1/traces');
+  });
+
+  it('uses the traces endpoint as-is and reads headers, service name and traceparent', () => {
+    const options = otlpOptionsFromEnv(undefined, {
+      OTEL_EXPORTER_OTLP_TRACES_ENDPOINT: 'https://otel.example.com/custom',
+      OTEL_EXPORTER_OTLP_HEADERS: 'authorization=Bearer%20abc,x-team=ci',
+      OTEL_SERVICE_NAME: 'checkout-ci',
+      TRACEPARENT: `00-${TRACE_ID}-${PARENT_ID}-01`,
+    });
+
+    expect(options.endpoint).toBe('https://otel.example.com/custom');
+    expect(options.headers).toEqual({ authorization: 'Bearer abc', 'x-team': 'ci' });
+    expect(options.resourceAttributes?.['service.name']).toBe('checkout-ci');
+    expect(options.traceParent).toBe(`00-${TRACE_ID}-${PARENT_ID}-01`);
+  });
+});
+
+describe('parseKeyValueList', () => {
+  it('skips entries without a key', () => {
+    expect(parseKeyValueList('a=1,=2,b')).toEqual({ a: '1' });
+  });
+});
+
+describe('toOtlpAttributes', () => {
+  it('maps value types and drops undefined', () => {
+    expect(toOtlpAttributes({ s: 'x', i: 3, d: 1.5, b: true, o: [1], u: undefined })).toEqual([
+      { key: 's', value: { stringValue: 'x' } },
+      { key: 'i', value: { intValue: '3' } },
+      { key: 'd', value: { doubleValue: 1.5 } },
+      { key: 'b', value: { boolValue: true } },
+      { key: 'o', value: { stringValue: '[1]' } },
+    ]);
+  });
+});
+
+describe('OtlpExporter', () => {
+  afterEach(() => {
+    vi.unstubAllGlobals();
+  });
+
+  function runSession(exporter: OtlpExporter): void {
+    const logger = new Logger();
+    logger.observeSpans(exporter);
+    const timestamp = new Date().toISOString();
+
+    const session = logger.span('session', { adapter: 'node' });
+    exporter.recordEvent({ type: 'session_start', timestamp, adapter: 'node', program: 'app.js' });
+    logger.span('launching').end();
+    const running = logger.span('running');
+    exporter.recordEvent({
+      type: 'breakpoint_hit',
+      timestamp,
+      id: 1,
+      threadId: 1,
+      location: { file: 'src/app.ts', line: 10, function: 'main' },
+      stackTrace: [],
+      locals: {},
+    });
+    running.end();
+    session.end({ error: 'timed out' });
+    exporter.recordEvent({
+      type: 'session_end',
+      timestamp,
+      summary: {
+        durationMs: 5,
+        exitCode: 0,
+        breakpointsHit: 1,
+        exceptionsCaught: 0,
+        stepsExecuted: 0,
+      },
+    });
+  }
+
+  it('exports phase spans under the session span with stop events', () => {
+    const exporter = new OtlpExporter({ endpoint: 'http://localhost:4318/v1/traces' });
+    runSession(exporter);
+
+    const spans = payloadSpans(exporter);
+    const session = spans.find((s) => s.name === 'session')!;
+    const running = spans.find((s) => s.name === 'running')!;
+
+    expect(spans.map((s) => s.name).sort()).toEqual(['launching', 'running', 'session']);
+    expect(new Set(spans.map((s) => s.traceId)).size).toBe(1);
+    expect(session.parentSpanId).toBeUndefined();
+    expect(running.parentSpanId).toBe(session.spanId);
+    expect(running.events.map((e) => e.name)).toEqual(['breakpoint_hit']);
+    expect(session.status).toEqual({ code: 2, message: 'timed out' });
+    expect(session.attributes).toContainEqual({
+      key: 'debug_run.breakpoints_hit',
+      value: { intValue: '1' },
+    });
+  });
+
+  it('joins the trace from TRACEPARENT', () => {
+    const exporter = new OtlpExporter({
+      endpoint: 'http://localhost:4318/v1/traces',
+      traceParent: `00-${TRACE_ID}-${PARENT_ID}-01`,
+    });
+    runSession(exporter);
+
+    const spans = payloadSpans(exporter);
+    expect(spans.every((s) => s.traceId === TRACE_ID)).toBe(true);
+    expect(spans.find((s) => s.name === 'session')!.parentSpanId).toBe(PARENT_ID);
+  });
+
+  it('posts the payload once and never throws on failure', async () => {
+    const fetchMock = vi.fn().mockRejectedValue(new Error('connection refused'));
+    vi.stubGlobal('fetch', fetchMock);
+    const exporter = new OtlpExporter({
+      endpoint: 'http://collector/v1/traces',
+      headers: { authorization: 'token' },
+    });
+    runSession(exporter);
+
+    await expect(exporter.flush()).resolves.toBeUndefined();
+    await exporter.flush();
+
+    expect(fetchMock).toHaveBeenCalledTimes(1);
+    const [url, init] = fetchMock.mock.calls[0];
+    expect(url).toBe('http://collector/v1/traces');
+    expect(init.headers).toMatchObject({
+      'Content-Type': 'application/json',
+      authorization: 'token',
+    });
+  });
+});
@@ -0,0 +1,102 @@
+/**
+ * Unit tests for secrets redaction (--redact)
+ */
+
+import { describe, it, expect } from 'vitest';
+import { DEFAULT_REDACT_NAMES, REDACTED, Redactor } from '../../src/output/redaction.js';
+import type { DebugEvent } from '../../src/output/events.js';
+import type { Response } from '../../src/dap/protocol.js';
+
+const timestamp = '2026-10-14T09:30:15.123Z';
+
+describe('Redactor', () => {
+  const redactor = new Redactor(
+    { names: DEFAULT_REDACT_NAMES, envNames: ['DATABASE_URL'], values: ['ghp_[A-Za-z0-9]{8}'] },
+    {
+      API_TOKEN: 's3cr3t-token',
+      DATABASE_URL: 'postgres://app:pw@db/prod',
+      DEBUG: '1',
+      HOME: '/home/me',
+    }
+  );
+
+  it('masks named locals, their nested children and evaluations', () => {
+    const event: DebugEvent = {
+      type: 'breakpoint_hit',
+      timestamp,
+      threadId: 1,
+      location: { file: '/app/src/auth.ts', line: 30 },
+      stackTrace: [],
+      locals: {
+        user: { type: 'string', value: 'alice' },
+        password: { type: 'string', value: 'hunter2' },
+        config: {
+          type: 'Object',
+          value: {
+            apiKey: { type: 'string', value: 'k-123' },
+            retries: { type: 'number', value: 3 },
+          },
+          expandable: true,
+          variablesReference: 12,
+        },
+      },
+      evaluations: { 'session.token': { result: '"abc"', type: 'string', variablesReference: 0 } },
+    };
+
+    expect(redactor.redactEvent(event)).toEqual({
+      ...event,
+      locals: {
+        user: { type: 'string', value: 'alice' },
+        password: { type: 'string', value: REDACTED },
+        config: {
+          type: 'Object',
+          value: {
+            apiKey: { type: 'string', value: REDACTED },
+            retries: { type: 'number', value: 3 },
+          },
+          expandable: true,
+          variablesReference: 12,
+        },
+      },
+      evaluations: { 'session.token': { result: REDACTED, type: 'string' } },
+    });
+  });
+
+  it('masks secret environment values and value patterns in any text', () => {
+    const text = 'connecting to postgres://app:pw@db/prod with s3cr3t-token, ghp_abcd1234';
+
+    expect(redactor.redactText(text)).toBe(
+      `connecting to ${REDACTED} with ${REDACTED}, ${REDACTED}`
+    );
+    // Short values and unrelated variables are left alone
+    expect(redactor.redactText('DEBUG=1 HOME=/home/me')).toBe('DEBUG=1 HOME=/home/me');
+  });
+
+  it('masks variables in DAP responses', () => {
+    const response: Response = {
+      seq: 20,
+      type: 'response',
+      request_seq: 19,
+      success: true,
+      command: 'variables',
+      body: {
+        variables: [
+          { name: 'secret', value: "'x'", variablesReference: 0 },
+          { name: 'count', value: '2', variablesReference: 0 },
+        ],
+      },
+    };
+
+    expect(redactor.redactMessage(response).body).toEqual({
+      variables: [
+        { name: 'secret', value: REDACTED, variablesReference: 0 },
+        { name: 'count', value: '2', variablesReference: 0 },
+      ],
+    });
+  });
+
+  it('rejects patterns that are invalid or match everything', () => {
+    expect(() => new Redactor({ names: ['(password'] }, {})).toThrow('--redact-name "(password"');
+    expect(() => new Redactor({ names: [], values: ['x*'] }, {})).toThrow('matches empty text');
+  });
+});
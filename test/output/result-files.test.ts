@@ -0,0 +1,97 @@
+/**
+ * Unit tests for named result files (--name, --out-dir)
+ */
+
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import { afterEach, beforeEach, describe, it, expect } from 'vitest';
+import {
+  formatRunTimestamp,
+  readResultIndex,
+  ResultRun,
+  sanitizeRunName,
+} from '../../src/output/result-files.js';
+import type { DebugEvent } from '../../src/output/events.js';
+
+describe('sanitizeRunName', () => {
+  it('replaces characters that are unsafe in file names', () => {
+    expect(sanitizeRunName('checkout-bug')).toBe('checkout-bug');
+    expect(sanitizeRunName(' fix cart/total ')).toBe('fix-cart-total');
+    expect(sanitizeRunName('../..')).toBe('session');
+  });
+});
+
+describe('formatRunTimestamp', () => {
+  it('formats local time as YYYYMMDD-HHMMSS', () => {
+    expect(formatRunTimestamp(new Date(2026, 9, 4, 9, 3, 5))).toBe('20261004-090305');
+  });
+});
+
+describe('ResultRun', () => {
+  let dir: string;
+  const now = new Date(2026, 9, 14, 9, 30, 15);
+
+  beforeEach(() => {
+    dir = path.join(fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-results-')), 'results');
+  });
+
+  afterEach(() => {
+    fs.rmSync(path.dirname(dir), { recursive: true, force: true });
+  });
+
+  it('creates a named file, an index entry and the latest link', () => {
+    const run = ResultRun.start(dir, 'checkout-bug', now);
+
+    expect(path.basename(run.file)).toBe('checkout-bug-20261014-093015.ndjson');
+    expect(fs.readlinkSync(path.join(dir, 'latest'))).toBe('checkout-bug-20261014-093015.ndjson');
+    expect(readResultIndex(dir).runs).toEqual([
+      {
+        name: 'checkout-bug',
+        file: 'checkout-bug-20261014-093015.ndjson',
+        startedAt: now.toISOString(),
+        status: 'running',
+      },
+    ]);
+    run.stream.destroy();
+  });
+
+  it('adds a suffix when a run with the same name and time exists', async () => {
+    const first = ResultRun.start(dir, 'checkout-bug', now);
+    await first.finish(false);
+    const second = ResultRun.start(dir, 'checkout-bug', now);
+
+    expect(path.basename(second.file)).toBe('checkout-bug-20261014-093015-2.ndjson');
+    expect(fs.readlinkSync(path.join(dir, 'latest'))).toBe(path.basename(second.file));
+    expect(readResultIndex(dir).runs.map((r) => r.status)).toEqual(['completed', 'running']);
+    second.stream.destroy();
+  });
+
+  it('records the outcome when finished', async () => {
+    const run = ResultRun.start(dir, 'checkout-bug', now);
+    run.stream.write('{"type":"session_start"}\n');
+    run.observe({
+      type: 'session_start',
+      timestamp: now.toISOString(),
+      adapter: 'node',
+      program: 'dist/app.js',
+    } as DebugEvent);
+    run.observe({
+      type: 'session_end',
+      timestamp: now.toISOString(),
+      summary: { exitCode: 3, breakpointsHit: 2, exceptionsCaught: 1 },
+    } as DebugEvent);
+    await run.finish(true);
+
+    expect(fs.readFileSync(run.file, 'utf-8')).toBe('{"type":"session_start"}\n');
+    expect(readResultIndex(dir).runs[0]).toMatchObject({
+      status: 'failed',
+      adapter: 'node',
+      program: 'dist/app.js',
+      exitCode: 3,
+      breakpointsHit: 2,
+      exceptionsCaught: 1,
+      endedAt: expect.any(String),
+    });
+  });
+});
@@ -0,0 +1,154 @@
+/**
+ * Tests for output sections (--include/--exclude section names)
+ */
+
+import { describe, it, expect } from 'vitest';
+import { Writable } from 'stream';
+import { OutputFormatter } from '../../src/output/formatter.js';
+import {
+  omitSections,
+  parseOutputFilters,
+  type OutputSection,
+} from '../../src/output/sections.js';
+import type {
+  BreakpointHitEvent,
+  CrashReportEvent,
+  DebugEvent,
+  SessionEndEvent,
+} from '../../src/output/events.js';
+
+const HIT: BreakpointHitEvent = {
+  type: 'breakpoint_hit',
+  timestamp: '2025-01-15T10:00:00Z',
+  threadId: 1,
+  location: { file: '/app/src/cart.ts', line: 42, function: 'checkout' },
+  stackTrace: [{ frameId: 1, function: 'checkout', file: '/app/src/cart.ts', line: 42 }],
+  locals: { total: { type: 'number', value: 150 } },
+  evaluations: { 'cart.items.length': { result: '3' } },
+  source: { startLine: 41, endLine: 43, lines: ['  41 | a', '> 42 | b', '  43 | c'] },
+};
+
+function capture(include?: string[], exclude?: string[]) {
+  const lines: string[] = [];
+  const stream = new Writable({
+    write(chunk, _encoding, callback) {
+      lines.push(...chunk.toString().split('\n').filter(Boolean));
+      callback();
+    },
+  });
+  const formatter = new OutputFormatter({ stream, include, exclude });
+  return { formatter, events: () => lines.map((line) => JSON.parse(line)) };
+}
+
+describe('parseOutputFilters', () => {
+  it('sorts comma-separated names into event types and sections', () => {
+    const filters = parseOutputFilters(['breakpoint_hit,variables', 'error'], ['modules,threads']);
+    expect([...(filters.includeTypes ?? [])]).toEqual(['breakpoint_hit', 'error']);
+    expect(filters.omittedSections).toEqual(
+      new Set(['stacks', 'evaluations', 'source', 'output', 'threads', 'modules'])
+    );
+  });
+
+  it('keeps every event type when only sections are included', () => {
+    const filters = parseOutputFilters(['stacks,variables,evaluations,output']);
+    expect(filters.includeTypes).toBeUndefined();
+    expect(filters.omittedSections).toEqual(new Set(['source', 'threads', 'modules']));
+  });
+});
+
+describe('omitSections', () => {
+  it('drops the omitted fields and returns the event itself when nothing is omitted', () => {
+    expect(omitSections(HIT, new Set<OutputSection>())).toBe(HIT);
+
+    const omitted = new Set<OutputSection>(['stacks', 'source']);
+    const trimmed = omitSections(HIT, omitted) as Partial<BreakpointHitEvent>;
+    expect(trimmed.stackTrace).toBeUndefined();
+    expect(trimmed.source).toBeUndefined();
+    expect(trimmed.locals).toEqual(HIT.locals);
+    expect(HIT.stackTrace).toHaveLength(1);
+  });
+
+  it('keeps crash threads without their stacks when only stacks are omitted', () => {
+    const crash: CrashReportEvent = {
+      type: 'crash_report',
+      timestamp: '2025-01-15T10:00:00Z',
+      reason: 'signal',
+      threadId: 1,
+      exception: { type: 'SIGSEGV', message: 'Segmentation fault' },
+      location: { file: 'src/main.rs', line: 7 },
+      locals: {},
+      threads: [{ id: 1, name: 'main', faulting: true, stackTrace: HIT.stackTrace }],
+      recentOutput: ['starting'],
+    };
+
+    const noStacks = omitSections(crash, new Set<OutputSection>(['stacks'])) as CrashReportEvent;
+    expect(noStacks.threads).toEqual([{ id: 1, name: 'main', faulting: true }]);
+
+    const noThreads = omitSections(
+      crash,
+      new Set<OutputSection>(['threads', 'output'])
+    ) as Partial<CrashReportEvent>;
+    expect(noThreads.threads).toBeUndefined();
+    expect(noThreads.recentOutput).toBeUndefined();
+  });
+
+  it('drops threads and modules from the session summary', () => {
+    const end: SessionEndEvent = {
+      type: 'session_end',
+      timestamp: '2025-01-15T10:00:01Z',
+      summary: {
+        durationMs: 1000,
+        exitCode: 0,
+        breakpointsHit: 1,
+        exceptionsCaught: 0,
+        stepsExecuted: 0,
+        threads: [{ id: 1, name: 'main' }],
+        modules: [{ name: 'app' }],
+      },
+    };
+
+    const omitted = new Set<OutputSection>(['modules', 'threads']);
+    const trimmed = omitSections(end, omitted) as SessionEndEvent;
+    expect(trimmed.summary).toEqual({
+      durationMs: 1000,
+      exitCode: 0,
+      breakpointsHit: 1,
+      exceptionsCaught: 0,
+      stepsExecuted: 0,
+    });
+  });
+});
+
+describe('OutputFormatter sections', () => {
+  it('writes only the included sections but notifies listeners of the full event', () => {
+    const { formatter, events } = capture(['variables,evaluations']);
+    const observed: DebugEvent[] = [];
+    formatter.onEvent((event) => observed.push(event));
+
+    formatter.emit(HIT);
+
+    const [written] = events();
+    expect(Object.keys(written)).toEqual([
+      'type',
+      'timestamp',
+      'threadId',
+      'location',
+      'locals',
+      'evaluations',
+    ]);
+    expect(observed[0]).toBe(HIT);
+  });
+
+  it('treats program_output events as the output section', () => {
+    const { formatter, events } = capture(undefined, ['output']);
+    formatter.emit({
+      type: 'program_output',
+      timestamp: '2025-01-15T10:00:00Z',
+      category: 'stdout',
+      output: 'hello\n',
+    });
+    formatter.emit(HIT);
+
+    expect(events().map((event) => event.type)).toEqual(['breakpoint_hit']);
+  });
+});
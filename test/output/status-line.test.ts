@@ -0,0 +1,49 @@
+/**
+ * Unit tests for the live status line
+ */
+
+import { describe, it, expect } from 'vitest';
+import { StatusLine } from '../../src/output/status-line.js';
+
+function createStream(columns: number) {
+  const writes: string[] = [];
+  const stream = {
+    columns,
+    write: (data: string) => {
+      writes.push(data);
+      return true;
+    },
+  } as unknown as NodeJS.WriteStream;
+  return { stream, writes };
+}
+
+describe('StatusLine', () => {
+  it('rewrites the line in place', () => {
+    const { stream, writes } = createStream(80);
+    const status = new StatusLine(stream);
+
+    status.update('connecting...');
+    status.update('launching...');
+
+    expect(writes).toEqual(['\r\x1b[2Kconnecting...', '\r\x1b[2Klaunching...']);
+  });
+
+  it('truncates to the terminal width', () => {
+    const { stream, writes } = createStream(11);
+    new StatusLine(stream).update('Loading source maps');
+
+    expect(writes).toEqual(['\r\x1b[2KLoading s…']);
+  });
+
+  it('only clears a line it has shown', () => {
+    const { stream, writes } = createStream(80);
+    const status = new StatusLine(stream);
+
+    status.clear();
+    status.update('launching...');
+    status.clear();
+    status.clear();
+
+    expect(writes).toEqual(['\r\x1b[2Klaunching...', '\r\x1b[2K']);
+  });
+});
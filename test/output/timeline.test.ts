@@ -0,0 +1,145 @@
+/**
+ * Unit tests for the session timeline (--timeline)
+ */
+
+import { describe, it, expect } from 'vitest';
+import { SessionTimeline, type TraceEvent } from '../../src/output/timeline.js';
+import type { DebugEvent } from '../../src/output/events.js';
+
+const start = Date.parse('2026-10-14T09:30:00.000Z');
+
+function at(ms: number): string {
+  return new Date(start + ms).toISOString();
+}
+
+function slices(timeline: SessionTimeline): TraceEvent[] {
+  return timeline.build().traceEvents.filter((event) => event.ph !== 'M');
+}
+
+function placed(timeline: SessionTimeline) {
+  return slices(timeline).map(({ name, ph, ts, dur, tid, args }) => ({
+    name,
+    ph,
+    ts,
+    dur,
+    tid,
+    args,
+  }));
+}
+
+describe('SessionTimeline', () => {
+  it('names a track for each kind of event', () => {
+    const names = new SessionTimeline()
+      .build()
+      .traceEvents.filter((event) => event.ph === 'M')
+      .map((event) => event.args?.name);
+
+    expect(names).toEqual(['session', 'program', 'breakpoints', 'stops', 'output']);
+  });
+
+  it('records phases as slices in microseconds from the first event', () => {
+    const timeline = new SessionTimeline();
+    timeline.spanEnded(
+      { id: 2, parentId: 1, name: 'connecting', startTime: start + 5 },
+      start + 45
+    );
+    timeline.spanEnded({ id: 1, name: 'session', startTime: start }, start + 100, { exitCode: 0 });
+
+    expect(slices(timeline)).toEqual([
+      {
+        name: 'session',
+        cat: 'session',
+        ph: 'X',
+        dur: 100000,
+        ts: 0,
+        pid: 1,
+        tid: 1,
+        args: { exitCode: 0 },
+      },
+      { name: 'connecting', cat: 'session', ph: 'X', dur: 40000, ts: 5000, pid: 1, tid: 1 },
+    ]);
+  });
+
+  it('places a pause from the stopped event to the request that resumed it', () => {
+    const timeline = new SessionTimeline();
+    timeline.protocolMessage(
+      'receive',
+      { seq: 7, type: 'event', event: 'stopped', body: { reason: 'breakpoint', threadId: 1 } },
+      undefined,
+      start
+    );
+    timeline.protocolMessage(
+      'send',
+      { seq: 8, type: 'request', command: 'stackTrace', arguments: { threadId: 1 } },
+      undefined,
+      start + 10
+    );
+    timeline.protocolMessage(
+      'send',
+      { seq: 9, type: 'request', command: 'continue', arguments: { threadId: 1 } },
+      undefined,
+      start + 250
+    );
+
+    expect(slices(timeline)).toEqual([
+      {
+        name: 'paused (breakpoint)',
+        cat: 'program',
+        ph: 'X',
+        dur: 250000,
+        ts: 0,
+        pid: 1,
+        tid: 2,
+        args: { reason: 'breakpoint', resumedBy: 'continue', threadId: 1 },
+      },
+    ]);
+  });
+
+  it('merges output into bursts and marks stops and breakpoints', () => {
+    const timeline = new SessionTimeline();
+    const events: DebugEvent[] = [
+      {
+        type: 'breakpoint_set',
+        timestamp: at(0),
+        id: 1,
+        file: '/app/src/cart.ts',
+        line: 42,
+        verified: true,
+      },
+      { type: 'program_output', timestamp: at(10), category: 'stdout', output: 'one\n' },
+      { type: 'program_output', timestamp: at(60), category: 'stdout', output: 'two\n' },
+      { type: 'program_output', timestamp: at(500), category: 'stderr', output: 'three\n' },
+      {
+        type: 'breakpoint_hit',
+        timestamp: at(600),
+        id: 1,
+        threadId: 1,
+        location: { file: '/app/src/cart.ts', line: 42, function: 'total' },
+        stackTrace: [],
+        locals: {},
+      },
+    ];
+    for (const event of events) timeline.recordEvent(event);
+
+    expect(placed(timeline)).toEqual([
+      {
+        name: 'verified cart.ts:42',
+        ph: 'i',
+        ts: 0,
+        dur: undefined,
+        tid: 3,
+        args: { id: 1, file: '/app/src/cart.ts', line: 42, verified: true },
+      },
+      { name: 'output', ph: 'X', ts: 10000, dur: 50000, tid: 5, args: { bytes: 8, writes: 2 } },
+      { name: 'output', ph: 'X', ts: 500000, dur: 0, tid: 5, args: { bytes: 6, writes: 1 } },
+      {
+        name: 'breakpoint_hit',
+        ph: 'i',
+        ts: 600000,
+        dur: undefined,
+        tid: 4,
+        args: { location: 'cart.ts:42', function: 'total', threadId: 1 },
+      },
+    ]);
+  });
+});
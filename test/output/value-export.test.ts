@@ -0,0 +1,195 @@
+/**
+ * Unit tests for captured-value export (--export-values)
+ */
+
+import { describe, it, expect } from 'vitest';
+import { ValueExport, flattenValue, valueExportFormat } from '../../src/output/value-export.js';
+import { writeParquet } from '../../src/output/parquet.js';
+import type { DebugEvent, VariableValue } from '../../src/output/events.js';
+
+const SESSION_START = {
+  type: 'session_start',
+  timestamp: '2026-10-14T10:00:00.000Z',
+  adapter: 'node',
+  program: 'app.js',
+} as DebugEvent;
+
+const ORDER: VariableValue = {
+  type: 'Order',
+  value: {
+    id: { type: 'string', value: 'A-1' },
+    items: {
+      type: 'Array',
+      value: { type: 'Array', count: 1, items: [{ type: 'number', value: 3 }] },
+    },
+  },
+};
+
+const BREAKPOINT_HIT = {
+  type: 'breakpoint_hit',
+  timestamp: '2026-10-14T10:00:01.000Z',
+  id: 1,
+  label: 'checkout',
+  threadId: 1,
+  location: { file: 'src/cart.ts', line: 42 },
+  stackTrace: [],
+  locals: {
+    total: { type: 'number', value: 150 },
+    note: { type: 'string', value: 'a, "quoted"' },
+    order: ORDER,
+  },
+  evaluations: {
+    'cart.size': { result: '1', type: 'number' },
+    'missing()': { result: '', error: 'ReferenceError: missing is not defined' },
+  },
+} as DebugEvent;
+
+const EXCEPTION_THROWN = {
+  type: 'exception_thrown',
+  timestamp: '2026-10-14T10:00:02.000Z',
+  threadId: 1,
+  exception: { type: 'Error', message: 'out of stock' },
+  location: { file: 'src/cart.ts', line: 50 },
+  locals: { sku: { type: 'string', value: 'S-9' } },
+} as DebugEvent;
+
+function exportOf(...events: DebugEvent[]): ValueExport {
+  const values = new ValueExport('checkout-run');
+  for (const event of events) values.observe(event);
+  return values;
+}
+
+describe('valueExportFormat', () => {
+  it('picks the format by extension', () => {
+    expect(valueExportFormat('values.csv')).toBe('csv');
+    expect(valueExportFormat('out/Values.PARQUET')).toBe('parquet');
+    expect(() => valueExportFormat('values.json')).toThrow(
+      '--export-values "values.json": use a .csv or .parquet file'
+    );
+  });
+});
+
+describe('flattenValue', () => {
+  it('flattens object members and collection items', () => {
+    expect(flattenValue('order', ORDER)).toEqual([
+      { name: 'order.id', type: 'string', value: 'A-1' },
+      { name: 'order.items[0]', type: 'number', value: '3' },
+    ]);
+  });
+
+  it('keeps empty containers and nulls as a single row', () => {
+    const list = { type: 'Array', value: { type: 'Array', count: 0, items: [] } };
+    expect(flattenValue('list', list)).toEqual([{ name: 'list', type: 'Array', value: '[]' }]);
+    expect(flattenValue('options', { type: 'Object', value: {} })).toEqual([
+      { name: 'options', type: 'Object', value: '{}' },
+    ]);
+    expect(flattenValue('next', { type: 'object', value: null })).toEqual([
+      { name: 'next', type: 'object', value: 'null' },
+    ]);
+  });
+});
+
+describe('ValueExport', () => {
+  it('records a row per value with the stop it came from', () => {
+    const values = exportOf(SESSION_START, BREAKPOINT_HIT, EXCEPTION_THROWN);
+    expect(values.rows.map((row) => `${row.hit} ${row.kind} ${row.name}`)).toEqual([
+      '1 local total',
+      '1 local note',
+      '1 local order.id',
+      '1 local order.items[0]',
+      '1 evaluation cart.size',
+      '1 evaluation missing()',
+      '2 local sku',
+    ]);
+    expect(values.rows[0]).toEqual({
+      session: 'checkout-run',
+      sessionStart: '2026-10-14T10:00:00.000Z',
+      hit: 1,
+      event: 'breakpoint_hit',
+      breakpoint: 1,
+      label: 'checkout',
+      location: 'src/cart.ts:42',
+      timestamp: '2026-10-14T10:00:01.000Z',
+      kind: 'local',
+      name: 'total',
+      type: 'number',
+      value: '150',
+    });
+    expect(values.rows[5]).toMatchObject({
+      type: 'error',
+      value: 'ReferenceError: missing is not defined',
+    });
+    expect(values.rows[6].breakpoint).toBeUndefined();
+  });
+
+  it('writes CSV with quoted fields and empty nulls', () => {
+    const lines = exportOf(SESSION_START, BREAKPOINT_HIT, EXCEPTION_THROWN).toCsv().split('\n');
+    expect(lines[0]).toBe(
+      'session,session_start,hit,event,breakpoint,label,location,timestamp,kind,name,type,value'
+    );
+    expect(lines[2]).toContain(',local,note,string,"a, ""quoted"""');
+    expect(lines[7]).toBe(
+      'checkout-run,2026-10-14T10:00:00.000Z,2,exception_thrown,,,src/cart.ts:50,2026-10-14T10:00:02.000Z,local,sku,string,S-9'
+    );
+  });
+
+  it('writes a Parquet file with its footer at the end', () => {
+    const file = exportOf(SESSION_START, BREAKPOINT_HIT).toParquet();
+    expect(file.subarray(0, 4).toString()).toBe('PAR1');
+    expect(file.subarray(-4).toString()).toBe('PAR1');
+    const footerLength = file.readUInt32LE(file.length - 8);
+    expect(footerLength).toBeLessThan(file.length - 12);
+    const footer = file.subarray(file.length - 8 - footerLength, file.length - 8).toString();
+    expect(footer).toContain('session_start');
+    expect(footer).toContain('debug-run');
+  });
+});
+
+describe('writeParquet', () => {
+  it('writes the bytes the Parquet format specifies', () => {
+    const file = writeParquet(
+      [{ name: 'a', type: 'string', optional: true, values: ['x', null] }],
+      'dr'
+    );
+    const expected = [
+      '50415231',
+      // Page header: DATA_PAGE of 11 bytes; 2 values, PLAIN, RLE definition/repetition levels
+      '1500' + '1516' + '1516' + '2c' + '1504' + '1500' + '1506' + '1506' + '00' + '00',
+      // Definition levels: 2 bytes, one bit-packed group (0b01); then "x"
+      '02000000' + '0301' + '01000000' + '78',
+      // File metadata: version 1
+      '1502',
+      // Schema: the root with 1 child, then optional UTF-8 byte array "a"
+      '192c' + '48' + '06736368656d61' + '1502' + '00' + '150c' + '2502' + '180161' + '2500' + '00',
+      // 2 rows
+      '1604',
+      // One row group with one column chunk at offset 4: byte array, PLAIN and RLE,
+      // path "a", uncompressed, 2 values, 28 bytes, data page at offset 4
+      '191c' + '191c' + '2608' + '1c' + '150c' + '19250006' + '19180161' + '1500' + '1604',
+      '1638' + '1638' + '2608' + '00' + '00',
+      // Row group: 28 bytes, 2 rows
+      '1638' + '1604' + '00',
+      // created_by "dr"
+      '28026472' + '00',
+      // Metadata length, magic
+      '42000000',
+      '50415231',
+    ].join('');
+    expect(file.toString('hex')).toBe(expected);
+  });
+
+  it('rejects mismatched columns and nulls in required ones', () => {
+    expect(() =>
+      writeParquet([
+        { name: 'a', type: 'int64', values: [1, 2] },
+        { name: 'b', type: 'string', values: ['x'] },
+      ])
+    ).toThrow('Parquet column b has 1 values, not 2');
+    expect(() => writeParquet([{ name: 'a', type: 'string', values: ['x', null] }])).toThrow(
+      'Parquet column a is required but has nulls'
+    );
+    expect(() =>
+      writeParquet([{ name: 'a', type: 'string', optional: true, values: ['x', null] }])
+    ).not.toThrow();
+  });
+});
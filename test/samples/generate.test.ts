@@ -0,0 +1,90 @@
+/**
+ * Unit tests for sample projects (samples generate)
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import { getAdapter } from '../../src/adapters/index.js';
+import { configDefaultsFor, readConfigFile } from '../../src/util/config.js';
+import {
+  SAMPLE_PROJECTS,
+  generateSample,
+  getSampleProject,
+  sampleBreakpoints,
+  sampleConfig,
+} from '../../src/samples/generate.js';
+
+describe('sampleBreakpoints', () => {
+  it('finds the line after each marker comment', () => {
+    const project = SAMPLE_PROJECTS.python;
+    const lines = project.files[project.source].split('\n');
+
+    const breakpoints = sampleBreakpoints(project);
+    expect(breakpoints.map((bp) => bp.description)).toEqual([
+      'an invalid order is rejected',
+      "the discount for the customer's tier",
+      "an order's totals",
+    ]);
+    for (const bp of breakpoints) {
+      const line = Number(bp.spec.split(':').pop());
+      expect(lines[line - 1]).not.toMatch(/^\s*(#|$)/);
+      expect(lines[line - 2]).toContain(`Good breakpoint: ${bp.description}`);
+    }
+  });
+
+  it('marks the default breakpoint in every sample', () => {
+    for (const project of Object.values(SAMPLE_PROJECTS)) {
+      const descriptions = sampleBreakpoints(project).map((bp) => bp.description);
+      expect(descriptions).toContain(project.defaultBreakpoint);
+      expect(getAdapter(project.adapter)).toBeDefined();
+    }
+  });
+});
+
+describe('getSampleProject', () => {
+  it('accepts language aliases', () => {
+    expect(getSampleProject('JS').language).toBe('javascript');
+    expect(getSampleProject('dotnet').language).toBe('csharp');
+    expect(() => getSampleProject('cobol')).toThrow('No sample for "cobol" (available: ');
+  });
+});
+
+describe('generateSample', () => {
+  let dir: string;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-sample-'));
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  it('writes the sample with a config file debug-run reads', () => {
+    const sample = generateSample('rust', dir);
+
+    expect(sample.files).toEqual(['src/main.rs', 'Cargo.toml', '.debug-run.toml']);
+    expect(fs.readFileSync(path.join(dir, 'src/main.rs'), 'utf-8')).toBe(
+      SAMPLE_PROJECTS.rust.files['src/main.rs']
+    );
+    const config = readConfigFile(path.join(dir, '.debug-run.toml'));
+    const defaults = configDefaultsFor([config!], getAdapter('rust')!);
+    const totals = sampleBreakpoints(SAMPLE_PROJECTS.rust).find(
+      (bp) => bp.description === "an order's totals"
+    );
+    expect(defaults.get('breakpoint')?.value).toEqual([totals?.spec]);
+    expect(defaults.get('eval')?.value).toEqual(['order.id', 'total']);
+    expect(sampleConfig(SAMPLE_PROJECTS.rust)).toContain('# Build:  cargo build\n');
+  });
+
+  it('refuses to overwrite existing files unless forced', () => {
+    fs.writeFileSync(path.join(dir, 'sample_app.py'), 'print("mine")\n');
+
+    expect(() => generateSample('python', dir)).toThrow('already exists; use --force');
+    expect(fs.existsSync(path.join(dir, '.debug-run.toml'))).toBe(false);
+    generateSample('python', dir, { force: true });
+    expect(fs.readFileSync(path.join(dir, 'sample_app.py'), 'utf-8')).toContain('ORD-001');
+  });
+});
@@ -0,0 +1,74 @@
+/**
+ * Tests for serve-mode Prometheus metrics
+ */
+
+import { describe, it, expect } from 'vitest';
+import { ServerMetrics } from '../../src/server/metrics.js';
+import type { DebugEvent } from '../../src/output/events.js';
+
+function event(type: string, fields: Record<string, unknown> = {}): DebugEvent {
+  return { type, timestamp: '', ...fields } as DebugEvent;
+}
+
+function samples(metrics: ServerMetrics): string[] {
+  const lines = metrics.render().split('\n');
+  return lines.filter((line) => line && !line.startsWith('#'));
+}
+
+describe('ServerMetrics', () => {
+  it('counts sessions by adapter and outcome', () => {
+    const metrics = new ServerMetrics();
+    metrics.sessionStarted('a', 'node', 0);
+    metrics.sessionStarted('b', 'node', 0);
+    metrics.sessionStarted('c', 'python', 0);
+    metrics.observe('a', event('process_launched', { pid: 10 }));
+    metrics.observe('a', event('error', { message: 'Failed to get locals' }));
+    metrics.sessionFinished('a', 'failed');
+    metrics.sessionFinished('c', 'failed');
+
+    expect(samples(metrics)).toEqual(
+      expect.arrayContaining([
+        'debug_run_sessions_started_total{adapter="node"} 2',
+        'debug_run_sessions_started_total{adapter="python"} 1',
+        'debug_run_sessions_finished_total{adapter="node",status="failed"} 1',
+        'debug_run_sessions_finished_total{adapter="python",status="failed"} 1',
+        'debug_run_sessions_active{adapter="node"} 1',
+        'debug_run_adapter_launch_failures_total{adapter="python"} 1',
+        'debug_run_session_errors_total{adapter="node"} 1',
+      ])
+    );
+    expect(samples(metrics)).not.toContain(
+      'debug_run_adapter_launch_failures_total{adapter="node"} 1'
+    );
+  });
+
+  it('times breakpoint binding and the first stop from the session start', () => {
+    const metrics = new ServerMetrics();
+    metrics.sessionStarted('a', 'dotnet', 1000);
+    metrics.observe('a', event('breakpoint_set', { id: 1, verified: true }), 1500);
+    metrics.observe('a', event('breakpoint_set', { id: 2, verified: false }), 1500);
+    metrics.observe('a', event('breakpoint_verified', { id: 2 }), 3500);
+    metrics.observe('a', event('breakpoint_hit', { threadId: 1 }), 4000);
+    metrics.observe('a', event('breakpoint_hit', { threadId: 1 }), 9000);
+
+    expect(samples(metrics)).toEqual(
+      expect.arrayContaining([
+        'debug_run_breakpoint_bind_seconds_sum{adapter="dotnet"} 3',
+        'debug_run_breakpoint_bind_seconds_count{adapter="dotnet"} 2',
+        'debug_run_first_stop_seconds_sum{adapter="dotnet"} 3',
+        'debug_run_first_stop_seconds_count{adapter="dotnet"} 1',
+      ])
+    );
+  });
+
+  it('declares each metric family and escapes label values', () => {
+    const metrics = new ServerMetrics();
+    metrics.sessionStarted('a', 'stdio:"my adapter"', 0);
+    const text = metrics.render();
+
+    expect(text).toContain('# TYPE debug_run_sessions_started_total counter\n');
+    expect(text).toContain('# TYPE debug_run_first_stop_seconds summary\n');
+    expect(text).toContain('debug_run_sessions_started_total{adapter="stdio:\\"my adapter\\""} 1');
+    expect(text.endsWith('\n')).toBe(true);
+  });
+});
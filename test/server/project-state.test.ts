@@ -0,0 +1,72 @@
+/**
+ * Unit tests for serve-mode project state (--state-file)
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import { PROJECT_STATE_VERSION, ProjectStateStore } from '../../src/server/project-state.js';
+
+describe('ProjectStateStore', () => {
+  let dir: string;
+  let file: string;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-state-'));
+    file = path.join(dir, 'state.json');
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  it('starts a project empty', () => {
+    expect(new ProjectStateStore().get('/app')).toEqual({
+      project: path.resolve('/app'),
+      breakpoints: [],
+      logpoints: [],
+      evaluations: [],
+      profiles: {},
+    });
+  });
+
+  it('keeps state in the file across stores', () => {
+    new ProjectStateStore(file).update('/app', {
+      breakpoints: ['src/app.ts:10'],
+      evaluations: ['order.total'],
+    });
+
+    const state = new ProjectStateStore(file).get('/app/');
+    expect(state.breakpoints).toEqual(['src/app.ts:10']);
+    expect(state.evaluations).toEqual(['order.total']);
+    expect(state.updatedAt).toBeDefined();
+    expect(JSON.parse(fs.readFileSync(file, 'utf-8')).version).toBe(PROJECT_STATE_VERSION);
+    expect(fs.readdirSync(dir)).toEqual(['state.json']);
+  });
+
+  it('replaces lists and merges profiles by name', () => {
+    const store = new ProjectStateStore();
+    store.update('/app', {
+      logpoints: ['src/app.ts:5|{x}'],
+      profiles: { api: { adapter: 'node', program: 'server.js' }, cli: { adapter: 'node' } },
+    });
+    const state = store.update('/app', {
+      logpoints: [],
+      profiles: { api: { adapter: 'node', program: 'api.js' }, cli: null },
+    });
+
+    expect(state.logpoints).toEqual([]);
+    expect(state.profiles).toEqual({ api: { adapter: 'node', program: 'api.js' } });
+    expect(store.get('/other').profiles).toEqual({});
+  });
+
+  it('rejects files that are not project state', () => {
+    fs.writeFileSync(file, '{"version": 99, "projects": {}}');
+    expect(() => new ProjectStateStore(file)).toThrow('Unsupported state file version');
+    fs.writeFileSync(file, '[]');
+    expect(() => new ProjectStateStore(file)).toThrow('expected a "projects" object');
+    fs.writeFileSync(file, '{');
+    expect(() => new ProjectStateStore(file)).toThrow('Invalid state file');
+  });
+});
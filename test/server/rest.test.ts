@@ -0,0 +1,156 @@
+/**
+ * Tests for the serve-mode REST API
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import { DebugServer } from '../../src/server/server.js';
+import { REST_ROUTES, buildOpenApiSpec, matchRoute } from '../../src/server/rest.js';
+
+describe('matchRoute', () => {
+  it('extracts path parameters', () => {
+    expect(matchRoute('/sessions/{sessionId}/eval', '/sessions/abc/eval')).toEqual({
+      sessionId: 'abc',
+    });
+    expect(matchRoute('/sessions', '/sessions/')).toEqual({});
+  });
+
+  it('rejects paths with different segments', () => {
+    expect(matchRoute('/sessions/{sessionId}', '/sessions')).toBeNull();
+    expect(matchRoute('/sessions/{sessionId}/eval', '/sessions/abc/continue')).toBeNull();
+  });
+});
+
+describe('buildOpenApiSpec', () => {
+  it('documents every route with its parameters and schemas', () => {
+    const spec = buildOpenApiSpec('1.2.3') as {
+      openapi: string;
+      info: { version: string };
+      paths: Record<string, Record<string, { parameters?: Array<{ name: string }> }>>;
+      components: { schemas: Record<string, unknown> };
+    };
+
+    expect(spec.openapi).toBe('3.0.3');
+    expect(spec.info.version).toBe('1.2.3');
+    for (const route of REST_ROUTES) {
+      expect(spec.paths[route.path][route.method.toLowerCase()]).toBeDefined();
+    }
+    expect(spec.paths['/sessions/{sessionId}/eval'].post.parameters).toEqual([
+      expect.objectContaining({ name: 'sessionId', in: 'path', required: true }),
+    ]);
+    expect(spec.components.schemas.SessionParams).toBeDefined();
+  });
+});
+
+describe('REST API', () => {
+  const token = 'test-token';
+  const authorization = { Authorization: `Bearer ${token}` };
+  let server: DebugServer;
+  let baseUrl: string;
+
+  beforeEach(async () => {
+    server = new DebugServer('1.2.3', { token });
+    const address = await server.listen(0, '127.0.0.1');
+    baseUrl = `http://127.0.0.1:${address.port}`;
+  });
+
+  afterEach(async () => {
+    await server.close();
+  });
+
+  async function request(method: string, path: string, body?: unknown) {
+    const response = await fetch(baseUrl + path, {
+      method,
+      headers: { 'Content-Type': 'application/json', ...authorization },
+      body: body === undefined ? undefined : JSON.stringify(body),
+    });
+    return { status: response.status, body: await response.json() };
+  }
+
+  it('lists sessions and serves the OpenAPI document', async () => {
+    expect(await request('GET', '/sessions')).toEqual({ status: 200, body: [] });
+
+    const spec = await request('GET', '/openapi.json');
+    expect(spec.status).toBe(200);
+    expect(spec.body.info.version).toBe('1.2.3');
+  });
+
+  it('serves Prometheus metrics', async () => {
+    const response = await fetch(`${baseUrl}/metrics`, { headers: authorization });
+    expect(response.status).toBe(200);
+    expect(response.headers.get('content-type')).toContain('text/plain; version=0.0.4');
+    expect(await response.text()).toContain('# TYPE debug_run_sessions_started_total counter');
+  });
+
+  it('returns 400 for invalid session parameters and bodies', async () => {
+    const unknownAdapter = await request('POST', '/sessions', { adapter: 'cobol' });
+    expect(unknownAdapter.status).toBe(400);
+    expect(unknownAdapter.body.error).toContain('Unknown adapter');
+
+    const badContext = await request('POST', '/sessions', {
+      adapter: 'node',
+      program: 'app.js',
+      evalContext: 'console',
+    });
+    expect(badContext.status).toBe(400);
+    expect(badContext.body.error).toContain('"evalContext" must be one of');
+
+    const response = await fetch(`${baseUrl}/sessions`, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json; charset=utf-8', ...authorization },
+      body: '{nope',
+    });
+    expect(response.status).toBe(400);
+  });
+
+  it('returns 403 without the token or from another origin', async () => {
+    const anonymous = await fetch(`${baseUrl}/sessions`);
+    expect(anonymous.status).toBe(403);
+    expect((await anonymous.json()).error).toContain('Missing or invalid token');
+    const wrongToken = await fetch(`${baseUrl}/metrics`, {
+      headers: { Authorization: 'Bearer guess' },
+    });
+    expect(wrongToken.status).toBe(403);
+
+    const crossOrigin = await fetch(`${baseUrl}/sessions`, {
+      method: 'POST',
+      headers: {
+        'Content-Type': 'application/json',
+        Origin: 'https://evil.example',
+        ...authorization,
+      },
+      body: JSON.stringify({ adapter: 'node', program: 'app.js' }),
+    });
+    expect(crossOrigin.status).toBe(403);
+    expect(server.sessions.list()).toEqual([]);
+
+    const query = await fetch(`${baseUrl}/sessions?token=${token}`, {
+      headers: { Origin: 'http://127.0.0.1:5173' },
+    });
+    expect(query.status).toBe(200);
+  });
+
+  it('returns 415 for request bodies that are not application/json', async () => {
+    const response = await fetch(`${baseUrl}/sessions`, {
+      method: 'POST',
+      headers: { 'Content-Type': 'text/plain', ...authorization },
+      body: JSON.stringify({ adapter: 'node', program: 'app.js' }),
+    });
+    expect(response.status).toBe(415);
+    expect(server.sessions.list()).toEqual([]);
+  });
+
+  it('returns 404 for unknown sessions and routes, 405 for wrong methods', async () => {
+    expect((await request('GET', '/sessions/nope')).status).toBe(404);
+    expect((await request('POST', '/sessions/nope/continue')).status).toBe(404);
+    expect((await request('POST', '/sessions/nope/eval', { expression: 'x' })).status).toBe(404);
+    expect((await request('GET', '/nowhere')).status).toBe(404);
+    expect((await request('PUT', '/sessions')).status).toBe(405);
+    expect((await request('GET', '/sessions/nope/state')).status).toBe(404);
+  });
+
+  it('returns 400 when resuming invalid saved state', async () => {
+    const response = await request('POST', '/sessions/resume', { sessions: [] });
+    expect(response.status).toBe(400);
+    expect(response.body.error).toContain('Unsupported saved session version');
+  });
+});
@@ -0,0 +1,137 @@
+/**
+ * Tests for the serve-mode JSON-RPC endpoint
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import * as http from 'node:http';
+import type { Duplex } from 'node:stream';
+import { DebugServer } from '../../src/server/server.js';
+import { decodeFrame } from '../../src/server/websocket.js';
+
+const TOKEN = 'test-token';
+
+interface TestClient {
+  call(method: string, params?: unknown, token?: string): Promise<Record<string, unknown>>;
+  close(): void;
+}
+
+function connect(
+  port: number,
+  options: { path?: string; headers?: Record<string, string> } = {}
+): Promise<TestClient> {
+  return new Promise((resolve, reject) => {
+    const request = http.request({
+      port,
+      host: '127.0.0.1',
+      path: options.path ?? '/ws',
+      headers: {
+        Connection: 'Upgrade',
+        Upgrade: 'websocket',
+        'Sec-WebSocket-Key': 'dGhlIHNhbXBsZSBub25jZQ==',
+        'Sec-WebSocket-Version': '13',
+        Authorization: `Bearer ${TOKEN}`,
+        ...options.headers,
+      },
+    });
+    request.on('error', reject);
+    request.on('response', (response) => reject(new Error(`HTTP ${response.statusCode}`)));
+    request.on('upgrade', (_response, socket: Duplex) => {
+      let buffer = Buffer.alloc(0);
+      const pending: Array<(message: Record<string, unknown>) => void> = [];
+      socket.on('data', (chunk: Buffer) => {
+        buffer = Buffer.concat([buffer, chunk]);
+        let frame;
+        while ((frame = decodeFrame(buffer))) {
+          buffer = buffer.subarray(frame.length);
+          if (frame.opcode === 0x1) {
+            pending.shift()?.(JSON.parse(frame.payload.toString()));
+          }
+        }
+      });
+
+      let nextId = 1;
+      resolve({
+        call(method, params, token = TOKEN) {
+          const payload = Buffer.from(
+            JSON.stringify({ jsonrpc: '2.0', id: nextId++, method, params, token })
+          );
+          const mask = Buffer.from([1, 2, 3, 4]);
+          const header =
+            payload.length < 126
+              ? Buffer.from([0x81, 0x80 | payload.length])
+              : Buffer.from([0x81, 0x80 | 126, payload.length >> 8, payload.length & 0xff]);
+          socket.write(
+            Buffer.concat([header, mask, Buffer.from(payload.map((b, i) => b ^ mask[i % 4]))])
+          );
+          return new Promise((resolveCall) => pending.push(resolveCall));
+        },
+        close() {
+          socket.destroy();
+        },
+      });
+    });
+    request.end();
+  });
+}
+
+describe('DebugServer', () => {
+  let server: DebugServer;
+  let client: TestClient;
+  let port: number;
+
+  beforeEach(async () => {
+    server = new DebugServer('dev', { token: TOKEN });
+    const address = await server.listen(0, '127.0.0.1');
+    port = address.port;
+    client = await connect(port);
+  });
+
+  afterEach(async () => {
+    client.close();
+    await server.close();
+  });
+
+  it('lists sessions', async () => {
+    const response = await client.call('session.list');
+
+    expect(response).toEqual({ jsonrpc: '2.0', id: 1, result: [] });
+  });
+
+  it('rejects unknown methods', async () => {
+    const response = await client.call('session.explode');
+
+    expect(response.error).toMatchObject({ code: -32601 });
+  });
+
+  it('reports invalid session parameters', async () => {
+    const unknownAdapter = await client.call('session.start', { adapter: 'cobol', program: 'x' });
+    const missingProgram = await client.call('session.start', { adapter: 'node' });
+    const unknownSession = await client.call('session.subscribe', { sessionId: 'nope' });
+
+    expect(unknownAdapter.error).toMatchObject({ code: -32602 });
+    expect((unknownAdapter.error as { message: string }).message).toContain('Unknown adapter');
+    expect((missingProgram.error as { message: string }).message).toContain('"program"');
+    expect((unknownSession.error as { message: string }).message).toContain('Unknown session');
+  });
+
+  it('requires the token on every request', async () => {
+    const missing = await client.call('session.list', undefined, '');
+    const wrong = await client.call('session.list', undefined, 'guess');
+
+    expect(missing.error).toMatchObject({ code: -32001 });
+    expect(wrong.error).toMatchObject({ code: -32001 });
+  });
+
+  it('refuses upgrades without the token or from other origins', async () => {
+    await expect(connect(port, { headers: { Authorization: '' } })).rejects.toThrow('HTTP 403');
+    await expect(
+      connect(port, { headers: { Origin: 'https://evil.example' } })
+    ).rejects.toThrow('HTTP 403');
+    const fromQuery = await connect(port, {
+      path: `/ws?token=${TOKEN}`,
+      headers: { Authorization: '', Origin: 'http://localhost:3000' },
+    });
+    expect((await fromQuery.call('session.list')).result).toEqual([]);
+    fromQuery.close();
+  });
+});
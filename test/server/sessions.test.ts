@@ -0,0 +1,229 @@
+/**
+ * Unit tests for saving, resuming, reloading, restarting and retaining serve-mode sessions,
+ * and for project state
+ */
+
+import { describe, it, expect, vi } from 'vitest';
+import {
+  InvalidParamsError,
+  SAVED_SESSION_VERSION,
+  SessionRegistry,
+  SessionStateError,
+  parseProjectStateUpdate,
+  parseSavedSessionState,
+  type SessionParams,
+} from '../../src/server/sessions.js';
+
+function addSession(
+  registry: SessionRegistry,
+  id: string,
+  params: SessionParams,
+  status: 'running' | 'ended' = 'running'
+) {
+  // @ts-expect-error accessing private field for testing
+  registry.sessions.set(id, {
+    id,
+    params,
+    session: { isPaused: () => true },
+    status,
+    startedAt: new Date().toISOString(),
+    events: [],
+    eventCount: 0,
+    listeners: new Set(),
+    done: Promise.resolve(),
+  });
+}
+
+const attachParams: SessionParams = {
+  adapter: 'node',
+  attach: true,
+  pid: 4242,
+  breakpoints: ['src/app.ts:10'],
+  evaluations: ['order.total'],
+  pauseOnHit: true,
+};
+
+describe('SessionRegistry save/resume', () => {
+  it('saves an attach session with its breakpoints and watches', () => {
+    const registry = new SessionRegistry();
+    addSession(registry, 'a', attachParams);
+
+    const state = registry.save('a');
+    expect(state.version).toBe(SAVED_SESSION_VERSION);
+    expect(state.sessions).toEqual([{ sessionId: 'a', status: 'paused', params: attachParams }]);
+  });
+
+  it('refuses to save launched or ended sessions', () => {
+    const registry = new SessionRegistry();
+    addSession(registry, 'launched', { adapter: 'node', program: 'app.js' });
+    addSession(registry, 'ended', attachParams, 'ended');
+
+    expect(() => registry.save('launched')).toThrow(SessionStateError);
+    expect(() => registry.save('ended')).toThrow('has ended');
+  });
+
+  it('saveAll only includes running attach sessions', () => {
+    const registry = new SessionRegistry();
+    addSession(registry, 'a', attachParams);
+    addSession(registry, 'launched', { adapter: 'node', program: 'app.js' });
+    addSession(registry, 'ended', attachParams, 'ended');
+
+    expect(registry.saveAll().sessions.map((session) => session.sessionId)).toEqual(['a']);
+  });
+
+  it('resumes each saved session with its original parameters', async () => {
+    const registry = new SessionRegistry();
+    addSession(registry, 'a', attachParams);
+    const state = registry.save('a');
+
+    const start = vi.spyOn(registry, 'start').mockResolvedValue({
+      sessionId: 'b',
+      adapter: 'node',
+      status: 'running',
+      startedAt: '',
+      eventCount: 0,
+    });
+
+    const summaries = await registry.resume(JSON.parse(JSON.stringify(state)));
+    expect(start).toHaveBeenCalledWith(attachParams);
+    expect(summaries.map((summary) => summary.sessionId)).toEqual(['b']);
+  });
+});
+
+describe('SessionRegistry retention', () => {
+  it('forgets the longest-ended sessions beyond keepSessions', () => {
+    const registry = new SessionRegistry({ keepSessions: 2 });
+    for (const id of ['a', 'b', 'c', 'd']) {
+      addSession(registry, id, { adapter: 'node', program: 'app.js' });
+    }
+    // @ts-expect-error accessing private field for testing
+    const finish = (id: string) => registry.finish(registry.sessions.get(id));
+
+    vi.useFakeTimers();
+    try {
+      for (const [id, time] of [
+        ['b', 1000],
+        ['a', 2000],
+        ['c', 3000],
+      ] as const) {
+        vi.setSystemTime(time);
+        finish(id);
+      }
+    } finally {
+      vi.useRealTimers();
+    }
+
+    expect(registry.list().map((summary) => summary.sessionId)).toEqual(['a', 'c', 'd']);
+    expect(registry.get('b')).toBeUndefined();
+  });
+});
+
+describe('SessionRegistry reload', () => {
+  it('validates the files to reload', async () => {
+    const registry = new SessionRegistry();
+    addSession(registry, 'a', attachParams);
+
+    await expect(registry.reload('a', [])).rejects.toThrow(InvalidParamsError);
+    await expect(registry.reload('a', 'src/app.js')).rejects.toThrow('"files" must be');
+  });
+
+  it('reports sessions that cannot reload as a state error', async () => {
+    const registry = new SessionRegistry();
+    addSession(registry, 'ended', attachParams, 'ended');
+    await expect(registry.reload('ended', ['app.js'])).rejects.toThrow(SessionStateError);
+  });
+});
+
+describe('SessionRegistry restart', () => {
+  it('reports sessions that cannot restart as a state error', async () => {
+    const registry = new SessionRegistry();
+    addSession(registry, 'ended', attachParams, 'ended');
+    await expect(registry.restart('ended')).rejects.toThrow(SessionStateError);
+
+    addSession(registry, 'attached', attachParams);
+    // @ts-expect-error accessing private field for testing
+    registry.sessions.get('attached').session.restart = vi.fn(async () => {
+      throw new Error("An attached program can't be relaunched");
+    });
+    await expect(registry.restart('attached')).rejects.toThrow("can't be relaunched");
+  });
+});
+
+describe('parseSavedSessionState', () => {
+  it('rejects malformed or non-attach state', () => {
+    expect(() => parseSavedSessionState(null)).toThrow(InvalidParamsError);
+    expect(() => parseSavedSessionState({ version: 99, sessions: [] })).toThrow(
+      'Unsupported saved session version'
+    );
+    expect(() =>
+      parseSavedSessionState({
+        version: SAVED_SESSION_VERSION,
+        sessions: [{ sessionId: 'x', params: { adapter: 'node', program: 'app.js' } }],
+      })
+    ).toThrow('not an attach session');
+  });
+});
+
+describe('SessionRegistry start', () => {
+  it('refuses stdio: adapters', async () => {
+    const registry = new SessionRegistry();
+    await expect(
+      registry.start({ adapter: 'stdio:/bin/sh -c "touch /tmp/owned"', program: 'app.js' })
+    ).rejects.toThrow('stdio: adapters are only available on the command line');
+  });
+
+  // PID 1 belongs to root
+  it.skipIf(process.platform === 'win32' || process.getuid?.() === 0)(
+    "refuses to attach to another user's process",
+    async () => {
+      const registry = new SessionRegistry();
+      await expect(registry.start({ adapter: 'node', attach: true, pid: 1 })).rejects.toThrow(
+        'Refusing to attach: PID 1 belongs to'
+      );
+    }
+  );
+});
+
+describe('SessionRegistry project state', () => {
+  it('rejects an unknown profile before starting', async () => {
+    const registry = new SessionRegistry();
+    await expect(
+      registry.start({ adapter: 'node', program: 'app.js', project: '/app', profile: 'api' })
+    ).rejects.toThrow(InvalidParamsError);
+  });
+
+  it('rejects defines that are not strings before starting', async () => {
+    const registry = new SessionRegistry();
+    await expect(
+      registry.start({
+        adapter: 'node',
+        program: 'app.js',
+        defines: { ORDER_ID: 2 } as unknown as Record<string, string>,
+      })
+    ).rejects.toThrow('"defines" must be an object of string values');
+  });
+
+  it('updates and returns a project state', () => {
+    const registry = new SessionRegistry();
+    registry.updateProject('/app', { breakpoints: ['app.js:3'], profiles: { api: {} } });
+    expect(registry.project('/app')).toMatchObject({
+      breakpoints: ['app.js:3'],
+      profiles: { api: {} },
+    });
+  });
+});
+
+describe('parseProjectStateUpdate', () => {
+  it('rejects fields of the wrong type', () => {
+    expect(() => parseProjectStateUpdate([])).toThrow(InvalidParamsError);
+    expect(() => parseProjectStateUpdate({ breakpoints: 'app.js:3' })).toThrow(
+      '"breakpoints" must be an array of strings'
+    );
+    expect(() => parseProjectStateUpdate({ profiles: { api: 'node' } })).toThrow(
+      'Profile "api" must be an object of parameters or null'
+    );
+    expect(parseProjectStateUpdate({ profiles: { api: null } })).toEqual({
+      profiles: { api: null },
+    });
+  });
+});
@@ -0,0 +1,58 @@
+/**
+ * Tests for the minimal WebSocket implementation used by serve mode
+ */
+
+import { describe, it, expect } from 'vitest';
+import { computeAcceptKey, decodeFrame, encodeFrame } from '../../src/server/websocket.js';
+
+function maskedFrame(opcode: number, payload: Buffer, fin: boolean = true): Buffer {
+  const mask = Buffer.from([0x12, 0x34, 0x56, 0x78]);
+  const masked = Buffer.from(payload.map((byte, i) => byte ^ mask[i % 4]));
+  const header =
+    payload.length < 126
+      ? Buffer.from([(fin ? 0x80 : 0) | opcode, 0x80 | payload.length])
+      : Buffer.from([(fin ? 0x80 : 0) | opcode, 0x80 | 126, payload.length >> 8, payload.length]);
+  return Buffer.concat([header, mask, masked]);
+}
+
+describe('computeAcceptKey', () => {
+  it('matches the RFC 6455 example', () => {
+    expect(computeAcceptKey('dGhlIHNhbXBsZSBub25jZQ==')).toBe('s3pPLMBiTxaQ9kYGzzhZRbK+xOo=');
+  });
+});
+
+describe('decodeFrame', () => {
+  it('unmasks client text frames', () => {
+    const frame = decodeFrame(maskedFrame(0x1, Buffer.from('hello')));
+
+    expect(frame).toMatchObject({ fin: true, opcode: 0x1 });
+    expect(frame!.payload.toString()).toBe('hello');
+  });
+
+  it('handles 16-bit payload lengths', () => {
+    const payload = Buffer.alloc(300, 'a');
+    const frame = decodeFrame(maskedFrame(0x1, payload));
+
+    expect(frame!.payload.equals(payload)).toBe(true);
+    expect(frame!.length).toBe(4 + 4 + 300);
+  });
+
+  it('returns null until the whole frame has arrived', () => {
+    const bytes = maskedFrame(0x1, Buffer.from('partial'));
+
+    expect(decodeFrame(bytes.subarray(0, 1))).toBeNull();
+    expect(decodeFrame(bytes.subarray(0, bytes.length - 1))).toBeNull();
+  });
+});
+
+describe('encodeFrame', () => {
+  it('round-trips through decodeFrame at each length encoding', () => {
+    for (const size of [5, 200, 70000]) {
+      const payload = Buffer.alloc(size, 'x');
+      const frame = decodeFrame(encodeFrame(0x1, payload));
+
+      expect(frame!.fin).toBe(true);
+      expect(frame!.payload.length).toBe(size);
+    }
+  });
+});
@@ -0,0 +1,78 @@
+/**
+ * Unit tests for hit aggregation (--aggregate)
+ */
+
+import { describe, it, expect } from 'vitest';
+import {
+  HitAggregator,
+  parseAggregateSpec,
+  parseNumericValue,
+} from '../../src/session/aggregate.js';
+
+describe('parseAggregateSpec', () => {
+  it('splits the expression from the stats', () => {
+    expect(parseAggregateSpec('item.unit_price:min,max,mean')).toEqual({
+      expression: 'item.unit_price',
+      stats: ['min', 'max', 'mean'],
+    });
+    expect(parseAggregateSpec('order.total : sum')).toEqual({
+      expression: 'order.total',
+      stats: ['sum'],
+    });
+  });
+
+  it('uses the default stats when none are listed', () => {
+    expect(parseAggregateSpec('item.unit_price')).toEqual({
+      expression: 'item.unit_price',
+      stats: ['min', 'max', 'mean'],
+    });
+    expect(parseAggregateSpec('a ? b : c').expression).toBe('a ? b : c');
+    expect(parseAggregateSpec('xs[1:3]').expression).toBe('xs[1:3]');
+  });
+
+  it('rejects unknown stats and empty expressions', () => {
+    expect(() => parseAggregateSpec('x:min,median')).toThrow('unknown stat "median"');
+    expect(() => parseAggregateSpec(':max')).toThrow('missing expression');
+  });
+});
+
+describe('parseNumericValue', () => {
+  it('parses numbers and rejects other values', () => {
+    expect(parseNumericValue('12.5')).toBe(12.5);
+    expect(parseNumericValue(' -3 ')).toBe(-3);
+    expect(parseNumericValue('1e3')).toBe(1000);
+    expect(parseNumericValue('"12"')).toBeUndefined();
+    expect(parseNumericValue('null')).toBeUndefined();
+  });
+});
+
+describe('HitAggregator', () => {
+  it('reports the requested stats for each expression', () => {
+    const aggregator = new HitAggregator([
+      parseAggregateSpec('price:min,max,mean,sum'),
+      parseAggregateSpec('sku:distinct'),
+    ]);
+    expect(aggregator.expressions()).toEqual(['price', 'sku']);
+
+    for (const [price, sku] of [
+      ['2', '"A"'],
+      ['6', '"B"'],
+      ['undefined', '"A"'],
+    ]) {
+      aggregator.record(0, { result: price });
+      aggregator.record(1, { result: sku });
+    }
+    aggregator.record(0, { error: 'price is not defined' });
+
+    expect(aggregator.list()).toEqual([
+      { expression: 'price', count: 3, errors: 1, nonNumeric: 1, min: 2, max: 6, mean: 4, sum: 8 },
+      { expression: 'sku', count: 3, distinct: 2 },
+    ]);
+  });
+
+  it('leaves numeric stats out when no value was a number', () => {
+    const aggregator = new HitAggregator([parseAggregateSpec('name')]);
+    aggregator.record(0, { result: '"Ada"' });
+    expect(aggregator.list()).toEqual([{ expression: 'name', count: 1, nonNumeric: 1 }]);
+  });
+});
@@ -0,0 +1,177 @@
+/**
+ * Unit tests for structural breakpoints (--break-ast)
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import {
+  isParserAvailable,
+  parseStructuralQuery,
+  resolveStructuralBreakpoints,
+  structuralBreakpointLines,
+} from '../../src/session/ast-breakpoints.js';
+
+const PRICING_PY = `import math
+
+def calculate_discount(order, rate=0.1):
+    """Discount for an order.
+
+    Loyal customers get more.
+    """
+    if order.total < 10:
+        return 0
+    def round_down(x):
+        return math.floor(x)
+    for item in order.items:
+        if item.free:
+            continue
+    return round_down(order.total * rate)
+`;
+
+const ORDERS_TS = `export function processOrder(order: Order): number {
+  // A return in a comment doesn't count
+  const label = "not { a brace";
+  if (order.items.length === 0) return 0;
+  const prices = order.items.map((item) => {
+    return item.price;
+  });
+  for (const item of order.items) {
+    total += item.price;
+  }
+  order.items.forEach((item) => {
+    audit(item);
+  });
+  return total;
+}
+
+export const formatTotal = (total: number) => total.toFixed(2);
+`;
+
+const PARSERS = isParserAvailable('pricing.py') && isParserAvailable('orders.ts');
+
+function lines(source: string, spec: string, file: string): { functions: number; lines: number[] } {
+  return structuralBreakpointLines(source, parseStructuralQuery(spec), file);
+}
+
+describe('parseStructuralQuery', () => {
+  it('parses functions, points and an optional file', () => {
+    expect(parseStructuralQuery('fn:calculate_discount:first-return')).toEqual({
+      spec: 'fn:calculate_discount:first-return',
+      function: 'calculate_discount',
+      point: 'first-return',
+    });
+    expect(parseStructuralQuery('C:\\app\\Orders.cs:loop-body:Process')).toEqual({
+      spec: 'C:\\app\\Orders.cs:loop-body:Process',
+      file: 'C:\\app\\Orders.cs',
+      function: 'Process',
+      point: 'loop-body',
+    });
+    expect(parseStructuralQuery('fn:main').point).toBe('start');
+  });
+
+  it('rejects unknown points and malformed queries', () => {
+    expect(() => parseStructuralQuery('fn:main:last-line')).toThrow('unknown point "last-line"');
+    expect(() => parseStructuralQuery('loop-body:main:start')).toThrow('takes no point');
+    expect(() => parseStructuralQuery('main')).toThrow('expected [file:]fn:<function>');
+  });
+});
+
+describe('structuralBreakpointLines', () => {
+  it.skipIf(PARSERS)('asks for the tree-sitter packages when they are missing', () => {
+    expect(() => lines(ORDERS_TS, 'fn:processOrder', 'orders.ts')).toThrow(
+      'npm install tree-sitter tree-sitter-typescript'
+    );
+  });
+
+  it('rejects languages without a grammar', () => {
+    expect(() => lines('', 'fn:main', 'Main.fs')).toThrow('No tree-sitter grammar for .fs files');
+  });
+});
+
+describe.skipIf(!PARSERS)('structuralBreakpointLines (parsed)', () => {
+  it('finds points in Python functions by indentation', () => {
+    expect(lines(PRICING_PY, 'fn:calculate_discount', 'pricing.py')).toEqual({
+      functions: 1,
+      lines: [8],
+    });
+    const firstReturn = lines(PRICING_PY, 'fn:calculate_discount:first-return', 'pricing.py');
+    expect(firstReturn.lines).toEqual([9]);
+    // The nested function's return belongs to it
+    expect(lines(PRICING_PY, 'fn:calculate_discount:returns', 'pricing.py').lines).toEqual([9, 15]);
+    expect(lines(PRICING_PY, 'loop-body:calculate_discount', 'pricing.py').lines).toEqual([13]);
+  });
+
+  it('finds points in brace languages, skipping callbacks, strings and comments', () => {
+    expect(lines(ORDERS_TS, 'fn:processOrder', 'orders.ts').lines).toEqual([3]);
+    expect(lines(ORDERS_TS, 'fn:processOrder:returns', 'orders.ts').lines).toEqual([4, 14]);
+    expect(lines(ORDERS_TS, 'loop-body:processOrder', 'orders.ts').lines).toEqual([9, 12]);
+    expect(lines(ORDERS_TS, 'fn:formatTotal:first-return', 'orders.ts').lines).toEqual([17]);
+  });
+
+  it('finds statements on the same line as the opening brace', () => {
+    const source = 'function f(x: number) { return x; }\n';
+    expect(lines(source, 'fn:f', 'inline.ts').lines).toEqual([1]);
+    expect(lines(source, 'fn:f:first-return', 'inline.ts').lines).toEqual([1]);
+  });
+
+  it('matches function names with wildcards', () => {
+    expect(lines(PRICING_PY, 'fn:*_d*:first-return', 'pricing.py')).toEqual({
+      functions: 2,
+      lines: [9, 11],
+    });
+    expect(lines(ORDERS_TS, 'fn:process?rder', 'orders.ts').lines).toEqual([3]);
+  });
+
+  it('reports when the file has no such function', () => {
+    expect(lines(ORDERS_TS, 'fn:calculate_discount', 'orders.ts')).toEqual({
+      functions: 0,
+      lines: [],
+    });
+  });
+});
+
+describe.skipIf(!PARSERS)('resolveStructuralBreakpoints', () => {
+  let dir: string;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-ast-'));
+    fs.mkdirSync(path.join(dir, 'src'));
+    fs.mkdirSync(path.join(dir, 'venv'));
+    fs.writeFileSync(path.join(dir, 'src', 'pricing.py'), PRICING_PY);
+    fs.writeFileSync(path.join(dir, 'venv', 'pricing.py'), PRICING_PY);
+    fs.writeFileSync(path.join(dir, 'src', 'orders.ts'), ORDERS_TS);
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  it('searches the adapter languages under cwd', () => {
+    const queries = ['fn:calculate_discount:first-return'].map((q) => parseStructuralQuery(q));
+    expect(resolveStructuralBreakpoints(queries, { cwd: dir, adapter: 'debugpy' })).toEqual([
+      `${path.join(dir, 'src', 'pricing.py')}:9`,
+    ]);
+  });
+
+  it('looks only in the file a query names', () => {
+    const queries = ['src/orders.ts:loop-body:processOrder'].map((q) => parseStructuralQuery(q));
+    expect(resolveStructuralBreakpoints(queries, { cwd: dir })).toEqual([
+      `${path.join(dir, 'src', 'orders.ts')}:9`,
+      `${path.join(dir, 'src', 'orders.ts')}:12`,
+    ]);
+  });
+
+  it('fails when a function is missing or has nothing to break on', () => {
+    expect(() =>
+      resolveStructuralBreakpoints([parseStructuralQuery('fn:refund')], { cwd: dir })
+    ).toThrow('no function named refund');
+    expect(() =>
+      resolveStructuralBreakpoints([parseStructuralQuery('fn:refund_*')], { cwd: dir })
+    ).toThrow('no function matching refund_*');
+    expect(() =>
+      resolveStructuralBreakpoints([parseStructuralQuery('loop-body:formatTotal')], { cwd: dir })
+    ).toThrow('formatTotal has no loops');
+  });
+});
@@ -0,0 +1,123 @@
+/**
+ * Unit tests for breakable line discovery
+ */
+
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import { describe, it, expect, vi } from 'vitest';
+import {
+  findBreakableLines,
+  formatBreakableLinesReport,
+  formatLineList,
+  parseLineRange,
+} from '../../src/session/breakpoint-locations.js';
+import type { IDapClient } from '../../src/dap/client-interface.js';
+
+describe('parseLineRange', () => {
+  it('parses file:start-end and file:line', () => {
+    expect(parseLineRange('src/main.rs:140-260', '/repo')).toEqual({
+      file: path.resolve('/repo', 'src/main.rs'),
+      startLine: 140,
+      endLine: 260,
+    });
+    expect(parseLineRange('app.py:7', '/repo')).toMatchObject({ startLine: 7, endLine: 7 });
+  });
+
+  it('rejects malformed and empty ranges', () => {
+    expect(() => parseLineRange('src/main.rs')).toThrow('Expected "file:start-end"');
+    expect(() => parseLineRange('a.rs:0-3')).toThrow('Lines start at 1');
+    expect(() => parseLineRange('a.rs:20-10')).toThrow('end line is before start line');
+  });
+});
+
+describe('findBreakableLines', () => {
+  const range = { file: '/src/main.rs', startLine: 10, endLine: 14 };
+
+  it('uses breakpointLocations when supported', async () => {
+    const client = {
+      getCapabilities: () => ({ supportsBreakpointLocationsRequest: true }),
+      breakpointLocations: vi.fn(async () => ({
+        breakpoints: [
+          { line: 13, column: 9 },
+          { line: 11 },
+          { line: 13, column: 5 },
+          { line: 13, column: 9 },
+          { line: 20, column: 1 },
+        ],
+      })),
+    };
+
+    const result = await findBreakableLines(client as unknown as IDapClient, range);
+
+    expect(client.breakpointLocations).toHaveBeenCalledWith({
+      source: { path: '/src/main.rs' },
+      line: 10,
+      endLine: 14,
+    });
+    expect(result).toEqual({
+      method: 'breakpointLocations',
+      lines: [{ line: 11 }, { line: 13, columns: [5, 9] }],
+    });
+  });
+
+  it('probes with breakpoints otherwise and clears them', async () => {
+    const setBreakpoints = vi.fn(async () => ({
+      breakpoints: [
+        { verified: false },
+        { verified: true, line: 11 },
+        { verified: true, line: 13 },
+        { verified: true, line: 13 },
+        { verified: true },
+      ],
+    }));
+    const client = { getCapabilities: () => ({}), setBreakpoints };
+
+    const result = await findBreakableLines(client as unknown as IDapClient, range);
+
+    expect(result).toEqual({
+      method: 'setBreakpoints',
+      lines: [{ line: 11 }, { line: 13 }, { line: 14 }],
+    });
+    expect(setBreakpoints).toHaveBeenLastCalledWith({
+      source: { path: '/src/main.rs' },
+      breakpoints: [],
+    });
+  });
+});
+
+describe('formatLineList', () => {
+  it('collapses consecutive lines', () => {
+    expect(formatLineList([140, 142, 143, 144, 145, 150])).toBe('140, 142-145, 150');
+  });
+});
+
+describe('formatBreakableLinesReport', () => {
+  it('marks breakable lines in the source', () => {
+    const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-locations-'));
+    const file = path.join(dir, 'app.js');
+    fs.writeFileSync(file, 'function f() {\n  // note\n  return 1;\n}\n');
+
+    const report = formatBreakableLinesReport(
+      {
+        file,
+        startLine: 1,
+        endLine: 3,
+        adapter: 'node',
+        method: 'breakpointLocations',
+        lines: [{ line: 3 }],
+      },
+      dir
+    );
+
+    expect(report.split('\n')).toEqual([
+      'app.js:1-3 (node, via breakpointLocations)',
+      'Breakable lines: 3',
+      '',
+      '  1 │ function f() {',
+      '  2 │   // note',
+      '● 3 │   return 1;',
+    ]);
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+});
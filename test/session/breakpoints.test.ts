@@ -2,16 +2,24 @@
  * Unit tests for breakpoint parsing functionality
  */
 
+import * as fs from 'node:fs';
+import * as os from 'node:os';
 import * as path from 'node:path';
-import { describe, it, expect } from 'vitest';
+import { describe, it, expect, vi } from 'vitest';
 import {
+  BreakpointManager,
   parseBreakpointSpec,
+  parseInstructionBreakpointSpec,
   parseLogpointSpec,
+  parseScopedEvaluation,
   validateBreakpointSpec,
+  validateInstructionBreakpointSpec,
   validateLogpointSpec,
   validateAllBreakpoints,
   getBreakpointSuggestions,
 } from '../../src/session/breakpoints.js';
+import type { IDapClient } from '../../src/dap/client-interface.js';
+import type { OutputFormatter } from '../../src/output/formatter.js';
 
 /**
  * Helper to create a platform-independent absolute path for testing.
@@ -114,6 +122,61 @@ describe('parseBreakpointSpec', () => {
     });
   });
 
+  describe('column breakpoints', () => {
+    it('parses file:line:column', () => {
+      const result = parseBreakpointSpec('dist/bundle.js:1:34567');
+      expect(result.file).toBe(path.resolve('dist/bundle.js'));
+      expect(result.line).toBe(1);
+      expect(result.column).toBe(34567);
+    });
+
+    it('combines a column with a condition or hit count', () => {
+      expect(parseBreakpointSpec('app.js:3:14?x > 1:2')).toMatchObject({
+        line: 3,
+        column: 14,
+        condition: 'x > 1:2',
+      });
+      expect(parseBreakpointSpec('app.js:3:14#2')).toMatchObject({ column: 14, hitCondition: '2' });
+    });
+
+    it('leaves the column out when not given', () => {
+      expect(parseBreakpointSpec('app.js:3?a:1')).not.toHaveProperty('column');
+    });
+
+    it('rejects column 0', () => {
+      expect(validateBreakpointSpec('app.js:1:0').error).toContain('Columns start at 1');
+      expect(() => parseBreakpointSpec('app.js:1:0')).toThrow('Invalid column number');
+    });
+  });
+
+  describe('labeled breakpoints', () => {
+    it('parses file:line#label', () => {
+      expect(parseBreakpointSpec('src/main.rs:150#after-totals')).toMatchObject({
+        file: path.resolve('src/main.rs'),
+        line: 150,
+        label: 'after-totals',
+      });
+    });
+
+    it('combines a label with a condition or hit count', () => {
+      expect(parseBreakpointSpec('app.js:3#hot?x > 1')).toMatchObject({
+        label: 'hot',
+        condition: 'x > 1',
+      });
+      expect(parseBreakpointSpec('app.js:3:14#hot#2')).toMatchObject({
+        column: 14,
+        label: 'hot',
+        hitCondition: '2',
+      });
+    });
+
+    it('keeps #N a hit count', () => {
+      const result = parseBreakpointSpec('app.js:3#2');
+      expect(result.hitCondition).toBe('2');
+      expect(result).not.toHaveProperty('label');
+    });
+  });
+
   describe('Windows drive letter handling', () => {
     it('correctly parses Windows-style absolute paths', () => {
       // This tests that the regex correctly handles the colon in drive letters
@@ -285,6 +348,285 @@ describe('validateAllBreakpoints', () => {
     const errors = validateAllBreakpoints(['invalid'], ['also_invalid']);
     expect(errors.length).toBe(2);
   });
+
+  it('rejects a label used twice', () => {
+    expect(validateAllBreakpoints(['a.ts:1#totals', 'b.ts:9#totals?x', 'c.ts:3#other'])).toEqual([
+      'Breakpoint label "totals" is used by more than one breakpoint',
+    ]);
+  });
+
+  it('validates instruction breakpoints', () => {
+    expect(validateAllBreakpoints([], [], ['0x401a2c', 'main+4'])).toEqual([
+      'Invalid instruction address "main+4". Expected a hex address (e.g., "0x55aa113e")',
+    ]);
+  });
+});
+
+describe('BreakpointManager columns', () => {
+  function createManager(supportsBreakpointLocationsRequest: boolean) {
+    const formatter = { breakpointSet: vi.fn() };
+    const client = {
+      getCapabilities: () => ({ supportsBreakpointLocationsRequest }),
+      breakpointLocations: vi.fn(async () => ({
+        breakpoints: [
+          { line: 1, column: 900 },
+          { line: 1, column: 120 },
+          { line: 2, column: 5 },
+        ],
+      })),
+      setBreakpoints: vi.fn(async () => ({ breakpoints: [{ id: 1, verified: true, line: 1 }] })),
+    };
+    const manager = new BreakpointManager(
+      client as unknown as IDapClient,
+      formatter as unknown as OutputFormatter
+    );
+    return { manager, client, formatter };
+  }
+
+  it('snaps columns to the next valid breakpoint location', async () => {
+    const { manager, client, formatter } = createManager(true);
+    manager.addBreakpoint('/dist/bundle.js:1:500');
+    await manager.setAllBreakpoints();
+
+    expect(client.breakpointLocations).toHaveBeenCalledWith({
+      source: { path: '/dist/bundle.js' },
+      line: 1,
+    });
+    expect(client.setBreakpoints.mock.calls[0][0].breakpoints[0].column).toBe(900);
+    expect(formatter.breakpointSet.mock.calls[0][7]).toBe(900);
+  });
+
+  it('falls back to the last location before the column', async () => {
+    const { manager, client } = createManager(true);
+    manager.addBreakpoint('/dist/bundle.js:1:5000');
+    await manager.setAllBreakpoints();
+    expect(client.setBreakpoints.mock.calls[0][0].breakpoints[0].column).toBe(900);
+  });
+
+  it('passes the column through when locations are unavailable', async () => {
+    const { manager, client } = createManager(false);
+    manager.addBreakpoint('/dist/bundle.js:1:500');
+    await manager.setAllBreakpoints();
+
+    expect(client.breakpointLocations).not.toHaveBeenCalled();
+    expect(client.setBreakpoints.mock.calls[0][0].breakpoints[0].column).toBe(500);
+  });
+});
+
+describe('BreakpointManager labels', () => {
+  async function setLabeled() {
+    const formatter = { breakpointSet: vi.fn() };
+    const client = {
+      getCapabilities: () => ({}),
+      setBreakpoints: vi.fn(async () => ({ breakpoints: [{ id: 7, verified: true, line: 151 }] })),
+    };
+    const manager = new BreakpointManager(
+      client as unknown as IDapClient,
+      formatter as unknown as OutputFormatter
+    );
+    manager.addBreakpoint('/src/main.rs:150#after-totals');
+    await manager.setAllBreakpoints();
+    return { manager, formatter };
+  }
+
+  it('reports the label in breakpoint_set', async () => {
+    const { formatter } = await setLabeled();
+    expect(formatter.breakpointSet.mock.calls[0][8]).toBe('after-totals');
+  });
+
+  it('finds the label of a stop by hit breakpoint or by line', async () => {
+    const { manager } = await setLabeled();
+    const location = { file: '/src/main.rs', line: 151 };
+
+    expect(manager.labelFor(location, [7])).toBe('after-totals');
+    expect(manager.labelFor(location, [8])).toBeUndefined();
+    expect(manager.labelFor(location)).toBe('after-totals');
+    expect(manager.labelFor({ file: '/src/main.rs', line: 150 })).toBe('after-totals');
+    expect(manager.labelFor({ file: '/src/main.rs', line: 20 })).toBeUndefined();
+  });
+});
+
+describe('BreakpointManager verification feedback', () => {
+  function createManager(
+    setBreakpoints: (args: { source: { path: string } }) => Promise<{ breakpoints: object[] }>
+  ) {
+    const formatter = {
+      breakpointSet: vi.fn(),
+      emit: vi.fn(),
+      createEvent: vi.fn((type: string, data: object) => ({ type, ...data })),
+    };
+    const client = { getCapabilities: () => ({}), setBreakpoints: vi.fn(setBreakpoints) };
+    const manager = new BreakpointManager(
+      client as unknown as IDapClient,
+      formatter as unknown as OutputFormatter,
+      { adapterType: 'node' }
+    );
+    return { manager, client, formatter };
+  }
+
+  it('sends every file at once and reports each as its response arrives', async () => {
+    let releaseSlow: () => void = () => {};
+    const slow = new Promise<void>((resolve) => {
+      releaseSlow = resolve;
+    });
+    const { manager, client, formatter } = createManager(async ({ source }) => {
+      if (source.path === '/src/slow.ts') await slow;
+      return { breakpoints: [{ id: source.path === '/src/slow.ts' ? 1 : 2, verified: true }] };
+    });
+    manager.addBreakpoint('/src/slow.ts:10');
+    manager.addBreakpoint('/src/fast.ts:20');
+
+    const done = manager.setAllBreakpoints();
+    await new Promise((resolve) => setImmediate(resolve));
+    expect(client.setBreakpoints).toHaveBeenCalledTimes(2);
+    expect(formatter.breakpointSet.mock.calls.map((call) => call[1])).toEqual(['/src/fast.ts']);
+
+    releaseSlow();
+    await done;
+    expect(formatter.breakpointSet.mock.calls.map((call) => call[1])).toEqual([
+      '/src/fast.ts',
+      '/src/slow.ts',
+    ]);
+    expect(formatter.emit).not.toHaveBeenCalled();
+  });
+
+  it('warns about a file with unverified breakpoints', async () => {
+    const { manager, formatter } = createManager(async () => ({
+      breakpoints: [
+        { id: 1, verified: false, message: 'Could not resolve source location' },
+        { id: 2, verified: true, line: 31 },
+      ],
+    }));
+    manager.addBreakpoint('/src/handler.ts:12');
+    manager.addBreakpoint('/src/handler.ts:30');
+    await manager.setAllBreakpoints();
+
+    expect(formatter.emit).toHaveBeenCalledTimes(1);
+    const warning = formatter.emit.mock.calls[0][0];
+    expect(warning).toMatchObject({
+      type: 'breakpoint_warning',
+      file: '/src/handler.ts',
+      breakpoints: [{ id: 1, line: 12, message: 'Could not resolve source location' }],
+      message: '1 breakpoint in handler.ts not verified: Could not resolve source location',
+    });
+    expect(warning.suggestions.length).toBeGreaterThan(0);
+  });
+
+  it('reports a breakpoint the adapter verifies later', async () => {
+    const { manager, formatter } = createManager(async () => ({
+      breakpoints: [{ id: 4, verified: false, message: 'Unbound breakpoint' }],
+    }));
+    manager.addBreakpoint('/src/lazy.ts:8#after-load');
+    await manager.setAllBreakpoints();
+    formatter.emit.mockClear();
+
+    const bound = { id: 4, verified: true, line: 9 };
+    manager.handleBreakpointEvent({ reason: 'changed', breakpoint: bound });
+    manager.handleBreakpointEvent({ reason: 'changed', breakpoint: bound });
+    manager.handleBreakpointEvent({ reason: 'changed', breakpoint: { id: 99, verified: true } });
+
+    expect(formatter.emit).toHaveBeenCalledTimes(1);
+    expect(formatter.emit.mock.calls[0][0]).toEqual({
+      type: 'breakpoint_verified',
+      id: 4,
+      file: '/src/lazy.ts',
+      line: 9,
+      label: 'after-load',
+    });
+    expect(manager.findBreakpointById(4)).toMatchObject({ verified: true, message: undefined });
+  });
+
+  it('clears every file in the adapter and keeps the breakpoints to set again', async () => {
+    const { manager, client } = createManager(async () => ({
+      breakpoints: [{ id: 1, verified: true }],
+    }));
+    manager.addBreakpoint('/src/server.ts:40');
+    await manager.setAllBreakpoints();
+    await manager.clearAllBreakpoints();
+
+    expect(client.setBreakpoints).toHaveBeenLastCalledWith({
+      source: { path: '/src/server.ts' },
+      breakpoints: [],
+    });
+    expect(manager.findBreakpointById(1)).toMatchObject({ verified: false });
+  });
+});
+
+describe('instruction breakpoints', () => {
+  it('parses addresses with conditions and hit counts', () => {
+    expect(parseInstructionBreakpointSpec('0x55AA113E')).toEqual({
+      address: '0x55aa113e',
+      condition: undefined,
+      hitCondition: undefined,
+    });
+    expect(parseInstructionBreakpointSpec('0x401a2c?$rdi == 0')).toMatchObject({
+      condition: '$rdi == 0',
+    });
+    expect(parseInstructionBreakpointSpec('0x401a2c#3')).toMatchObject({ hitCondition: '3' });
+  });
+
+  it('rejects non-hex addresses', () => {
+    expect(validateInstructionBreakpointSpec('401a2c').valid).toBe(false);
+    expect(validateInstructionBreakpointSpec('0x').valid).toBe(false);
+    expect(() => parseInstructionBreakpointSpec('0xzz')).toThrow('Invalid instruction address');
+  });
+
+  function createManager(supportsInstructionBreakpoints: boolean) {
+    const formatter = {
+      emit: vi.fn(),
+      createEvent: vi.fn((type: string, data: object) => ({ type, ...data })),
+    };
+    const client = {
+      getCapabilities: () => ({ supportsInstructionBreakpoints }),
+      setInstructionBreakpoints: vi.fn(async () => ({
+        breakpoints: [
+          { id: 7, verified: true, source: { path: '/src/server.c' }, line: 88 },
+          { verified: false, message: 'Address not mapped' },
+        ],
+      })),
+    };
+    const manager = new BreakpointManager(
+      client as unknown as IDapClient,
+      formatter as unknown as OutputFormatter,
+      { adapterType: 'lldb' }
+    );
+    manager.addInstructionBreakpoint('0x401a2c#2');
+    manager.addInstructionBreakpoint('0xdeadbeef');
+    return { manager, client, formatter };
+  }
+
+  it('sets them on the adapter and reports each one', async () => {
+    const { manager, client, formatter } = createManager(true);
+    await manager.setAllBreakpoints();
+
+    expect(client.setInstructionBreakpoints).toHaveBeenCalledWith({
+      breakpoints: [
+        { instructionReference: '0x401a2c', condition: undefined, hitCondition: '2' },
+        { instructionReference: '0xdeadbeef', condition: undefined, hitCondition: undefined },
+      ],
+    });
+    const events = formatter.emit.mock.calls.map(([event]) => event);
+    expect(events[0]).toMatchObject({
+      type: 'instruction_breakpoint_set',
+      id: 7,
+      address: '0x401a2c',
+      verified: true,
+      location: '/src/server.c:88',
+    });
+    expect(events[1]).toMatchObject({ verified: false, message: 'Address not mapped' });
+    expect(manager.getInstructionBreakpoints().map((bp) => bp.verified)).toEqual([true, false]);
+  });
+
+  it('reports them as unverified when the adapter lacks support', async () => {
+    const { manager, client, formatter } = createManager(false);
+    await manager.setAllBreakpoints();
+
+    expect(client.setInstructionBreakpoints).not.toHaveBeenCalled();
+    expect(formatter.emit.mock.calls[0][0]).toMatchObject({
+      verified: false,
+      message: "The lldb adapter doesn't support instruction breakpoints",
+    });
+  });
 });
 
 describe('parseLogpointSpec', () => {
@@ -438,3 +780,138 @@ describe('getBreakpointSuggestions', () => {
     });
   });
 });
+
+describe('scoped evaluations', () => {
+  it('parses file:line:expression', () => {
+    expect(parseScopedEvaluation('pricing.rs:100:discount_rate', { cwd: '/repo' })).toEqual({
+      expression: 'discount_rate',
+      file: path.resolve('/repo', 'pricing.rs'),
+      line: 100,
+    });
+    expect(parseScopedEvaluation('src/a.ts:5:repl:order').expression).toBe('repl:order');
+  });
+
+  it('leaves plain expressions unscoped', () => {
+    for (const spec of ['total', 'arr[1:2:3]', 'std::mem::size_of::<u8>()', 'a ? b:1:c']) {
+      expect(parseScopedEvaluation(spec)).toEqual({ expression: spec });
+    }
+  });
+
+  function createManager() {
+    const client = {
+      setBreakpoints: vi.fn(async () => ({
+        // The adapter moved the pricing.rs breakpoint to the next executable line
+        breakpoints: [{ id: 7, verified: true, line: 101 }],
+      })),
+    };
+    const manager = new BreakpointManager(
+      client as unknown as IDapClient,
+      { breakpointSet: vi.fn() } as unknown as OutputFormatter,
+      { cwd: '/repo' }
+    );
+    manager.addBreakpoint('pricing.rs:100');
+    return manager;
+  }
+
+  it('selects expressions for the stop location or hit breakpoint', async () => {
+    const manager = createManager();
+    await manager.setAllBreakpoints();
+    const specs = ['total', 'pricing.rs:100:discount_rate', 'cart.rs:20:items'];
+    const file = path.resolve('/repo', 'pricing.rs');
+
+    expect(manager.selectEvaluations(specs, { file, line: 101 }, [7])).toEqual([
+      'total',
+      'discount_rate',
+    ]);
+    expect(manager.selectEvaluations(specs, { file, line: 100 })).toEqual([
+      'total',
+      'discount_rate',
+    ]);
+    expect(manager.selectEvaluations(specs, { file, line: 140 })).toEqual(['total']);
+  });
+
+  it('finds scoped expressions without a breakpoint', () => {
+    const manager = createManager();
+    expect(
+      manager.findUnmatchedEvaluations(['total', 'pricing.rs:100:rate', 'cart.rs:20:items'])
+    ).toEqual(['cart.rs:20:items']);
+  });
+});
+
+describe('stale sources', () => {
+  function createManager(file: string, checksum: string) {
+    const formatter = {
+      breakpointSet: vi.fn(),
+      emit: vi.fn(),
+      createEvent: vi.fn((type: string, data: object) => ({ type, ...data })),
+    };
+    const client = {
+      setBreakpoints: vi.fn(async () => ({
+        breakpoints: [
+          {
+            id: 3,
+            verified: true,
+            line: 12,
+            source: { path: file, checksums: [{ algorithm: 'SHA256', checksum }] },
+          },
+        ],
+      })),
+      source: vi.fn(async () => ({ content: 'const total = 1;\n' })),
+    };
+    const manager = new BreakpointManager(
+      client as unknown as IDapClient,
+      formatter as unknown as OutputFormatter
+    );
+    return { manager, client, formatter };
+  }
+
+  it('warns for each breakpoint in a file the adapter has a different checksum for', async () => {
+    const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-stale-'));
+    const file = path.join(dir, 'cart.ts');
+    fs.writeFileSync(file, 'const total = 2;\n');
+    try {
+      const { manager, client, formatter } = createManager(file, 'ab'.repeat(32));
+      manager.addBreakpoint(`${file}:12`);
+      await manager.setAllBreakpoints();
+      await manager.checkSource({ path: file, sourceReference: 4 });
+
+      expect(formatter.emit).toHaveBeenCalledTimes(1);
+      expect(formatter.emit.mock.calls[0][0]).toMatchObject({
+        type: 'stale_source',
+        file,
+        breakpointId: 3,
+        line: 12,
+        reason: 'checksum',
+        expected: 'ab'.repeat(32),
+      });
+      // Already checked when the breakpoints were set
+      expect(client.source).not.toHaveBeenCalled();
+      expect(manager.getStaleSources()).toEqual([file]);
+    } finally {
+      fs.rmSync(dir, { recursive: true, force: true });
+    }
+  });
+
+  it('compares the content behind a source reference at stops', async () => {
+    const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-stale-'));
+    const file = path.join(dir, 'cart.ts');
+    fs.writeFileSync(file, 'const total = 2;\n');
+    try {
+      const { manager, client, formatter } = createManager(file, 'ab'.repeat(32));
+      await manager.checkSource({ path: file, sourceReference: 4 });
+
+      expect(client.source).toHaveBeenCalledWith({
+        source: { path: file, sourceReference: 4 },
+        sourceReference: 4,
+      });
+      expect(formatter.emit.mock.calls[0][0]).toMatchObject({
+        type: 'stale_source',
+        file,
+        reason: 'content',
+      });
+      expect(formatter.emit.mock.calls[0][0]).not.toHaveProperty('breakpointId');
+    } finally {
+      fs.rmSync(dir, { recursive: true, force: true });
+    }
+  });
+});
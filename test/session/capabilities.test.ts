@@ -0,0 +1,67 @@
+/**
+ * Unit tests for adapter capability reports (capabilities)
+ */
+
+import { describe, it, expect } from 'vitest';
+import { describeCapabilities, formatCapabilitiesReport } from '../../src/session/capabilities.js';
+import type { Capabilities } from '../../src/dap/protocol.js';
+
+const NETCOREDBG: Capabilities & Record<string, unknown> = {
+  supportsConfigurationDoneRequest: true,
+  supportsConditionalBreakpoints: true,
+  supportsHitConditionalBreakpoints: true,
+  supportsSetVariable: true,
+  supportsExceptionFilterOptions: true,
+  supportsTerminateRequest: true,
+  supportsCancelRequest: true,
+  exceptionBreakpointFilters: [
+    { filter: 'all', label: 'All exceptions', supportsCondition: true },
+    { filter: 'user-unhandled', label: 'User-Unhandled Exceptions', default: true },
+  ],
+};
+
+describe('describeCapabilities', () => {
+  it('marks each known capability and collects the rest', () => {
+    const report = describeCapabilities('netcoredbg', NETCOREDBG);
+    const entries = report.groups.flatMap((group) => group.capabilities);
+    const entry = (flag: string) => entries.find((e) => e.flag === flag);
+
+    expect(report.groups.map((group) => group.title)).toEqual([
+      'Breakpoints',
+      'Execution',
+      'Inspection',
+    ]);
+    expect(entry('supportsConditionalBreakpoints')).toEqual({
+      flag: 'supportsConditionalBreakpoints',
+      name: 'Conditional breakpoints',
+      supported: true,
+      usedBy: '-b file:line?cond',
+    });
+    expect(entry('supportsDataBreakpoints')?.supported).toBe(false);
+    expect(entry('supportsStepBack')?.supported).toBe(false);
+    expect(report.other).toEqual(['supportsCancelRequest', 'supportsConfigurationDoneRequest']);
+    expect(report.exceptionFilters).toHaveLength(2);
+    expect(report.raw).toBe(NETCOREDBG);
+  });
+});
+
+describe('formatCapabilitiesReport', () => {
+  it('prints a table per group with the options that need each capability', () => {
+    const text = formatCapabilitiesReport(describeCapabilities('netcoredbg', NETCOREDBG));
+    const lines = text.split('\n');
+
+    expect(lines[0]).toBe('netcoredbg capabilities');
+    expect(lines).toContain('Breakpoints');
+    expect(lines).toContainEqual(expect.stringMatching(/^ {2}✓ Conditional breakpoints +-b file/));
+    expect(lines).toContainEqual(expect.stringMatching(/^ {2}✗ Data breakpoints +--watch-memory/));
+    expect(lines).toContain('  ✗ Step back');
+    expect(lines).toContain('Exception filters (--break-on-exception)');
+    expect(lines).toContain('  all             All exceptions (conditions)');
+    expect(lines).toContain('  user-unhandled  User-Unhandled Exceptions (default)');
+    expect(lines.slice(-3)).toEqual([
+      'Also supported',
+      '  supportsCancelRequest',
+      '  supportsConfigurationDoneRequest',
+    ]);
+  });
+});
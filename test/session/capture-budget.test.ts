@@ -0,0 +1,33 @@
+/**
+ * Unit tests for time-boxed capture (--capture-budget)
+ */
+
+import { describe, it, expect, afterEach, vi } from 'vitest';
+import { CaptureBudget } from '../../src/session/capture-budget.js';
+
+describe('CaptureBudget', () => {
+  afterEach(() => {
+    vi.useRealTimers();
+  });
+
+  it('passes through requests that finish in time', async () => {
+    const budget = new CaptureBudget(1000);
+
+    await expect(budget.within(Promise.resolve('locals'))).resolves.toBe('locals');
+    await expect(budget.within(Promise.reject(new Error('no frame')))).rejects.toThrow('no frame');
+    expect(budget.expired()).toBe(false);
+  });
+
+  it('gives up on a request when the budget runs out', async () => {
+    vi.useFakeTimers();
+    const budget = new CaptureBudget(2000);
+    const slow = new Promise<string>((resolve) => setTimeout(() => resolve('late'), 5000));
+
+    const result = budget.within(slow);
+    await vi.advanceTimersByTimeAsync(2000);
+
+    await expect(result).resolves.toBeUndefined();
+    expect(budget.expired()).toBe(true);
+    await expect(budget.within(Promise.resolve('next'))).resolves.toBeUndefined();
+  });
+});
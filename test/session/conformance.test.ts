@@ -0,0 +1,95 @@
+/**
+ * Unit tests for adapter conformance checks (conformance)
+ */
+
+import { describe, it, expect } from 'vitest';
+import {
+  CONFORMANCE_CHECKS,
+  CONFORMANCE_PROGRAMS,
+  caughtExceptionFilter,
+  conformanceLanguage,
+  formatConformanceReport,
+  markerLines,
+} from '../../src/session/conformance.js';
+
+describe('conformance samples', () => {
+  it('marks the lines the checks use in every sample', () => {
+    const markers = new Set(['add', 'loop', 'result']);
+    for (const program of Object.values(CONFORMANCE_PROGRAMS)) {
+      const lines = markerLines(program.files[program.source]);
+      for (const marker of markers) expect(lines[marker]).toBeGreaterThan(0);
+      expect(lines.loop).toBeLessThan(lines.result);
+    }
+    expect(markerLines(CONFORMANCE_PROGRAMS.python.files['conformance.py'])).toEqual({
+      add: 2,
+      loop: 9,
+      result: 10,
+      throw: 12,
+    });
+    expect(markerLines(CONFORMANCE_PROGRAMS.c.files['conformance.c']).throw).toBeUndefined();
+  });
+
+  it('picks the sample for each built-in adapter', () => {
+    expect(conformanceLanguage('node')).toBe('javascript');
+    expect(conformanceLanguage('netcoredbg')).toBe('csharp');
+    expect(conformanceLanguage('rust-gdb')).toBe('rust');
+    expect(conformanceLanguage('stdio:my-adapter')).toBeUndefined();
+  });
+
+  it('only needs markers every sample has, except where a check says so', () => {
+    const lines = markerLines(CONFORMANCE_PROGRAMS.rust.files['main.rs']);
+    for (const check of CONFORMANCE_CHECKS.filter((c) => !c.marker)) {
+      for (const breakpoint of check.breakpoints?.(lines) ?? []) {
+        expect(breakpoint.line).toBeGreaterThan(0);
+      }
+    }
+  });
+});
+
+describe('caughtExceptionFilter', () => {
+  it('prefers a filter for every exception over uncaught-only ones', () => {
+    const filters = (...names: string[]) => ({
+      exceptionBreakpointFilters: names.map((filter) => ({ filter, label: filter })),
+    });
+    expect(caughtExceptionFilter(filters('uncaught', 'all'))).toBe('all');
+    expect(caughtExceptionFilter(filters('uncaught', 'raised'))).toBe('raised');
+    expect(caughtExceptionFilter(filters('user-unhandled', 'cpp_throw'))).toBe('cpp_throw');
+    expect(caughtExceptionFilter(filters('uncaught'))).toBeUndefined();
+    expect(caughtExceptionFilter({})).toBeUndefined();
+  });
+});
+
+describe('formatConformanceReport', () => {
+  it('prints a line per feature with what was seen', () => {
+    const text = formatConformanceReport({
+      adapter: 'lldb',
+      language: 'c',
+      capabilities: {},
+      results: [
+        {
+          feature: 'breakpoint',
+          description: 'Stop',
+          status: 'pass',
+          detail: 'line 13',
+          durationMs: 1,
+        },
+        {
+          feature: 'step-in',
+          description: 'Step in',
+          status: 'fail',
+          detail: 'in main',
+          durationMs: 1,
+        },
+        { feature: 'logpoint', description: 'Log', status: 'unsupported', durationMs: 0 },
+      ],
+    });
+
+    expect(text.split('\n')).toEqual([
+      'lldb conformance (c sample): 1 passed, 1 failed, 1 not run',
+      '',
+      '  ✓ breakpoint  Stop: line 13',
+      '  ✗ step-in     Step in: in main',
+      '  - logpoint    Log (unsupported)',
+    ]);
+  });
+});
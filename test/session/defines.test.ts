@@ -0,0 +1,44 @@
+/**
+ * Unit tests for run variables (--define)
+ */
+
+import { describe, it, expect } from 'vitest';
+import { expandDefines, parseDefines, undefinedPlaceholders } from '../../src/session/defines.js';
+
+describe('parseDefines', () => {
+  it('splits on the first "=" and lets a later definition win', () => {
+    expect(parseDefines(['ORDER_ID=ORD-002', 'FILTER=a=b', 'ORDER_ID=ORD-003', 'EMPTY='])).toEqual({
+      ORDER_ID: 'ORD-003',
+      FILTER: 'a=b',
+      EMPTY: '',
+    });
+  });
+
+  it('rejects definitions without a valid name', () => {
+    expect(() => parseDefines(['ORDER_ID'])).toThrow(
+      'Invalid --define "ORDER_ID": expected NAME=VALUE (e.g., ORDER_ID=ORD-002)'
+    );
+    expect(() => parseDefines(['=ORD-002'])).toThrow('Invalid --define "=ORD-002"');
+    expect(() => parseDefines(['2FA=on'])).toThrow('Invalid --define "2FA=on"');
+  });
+});
+
+describe('expandDefines', () => {
+  it('fills defined placeholders and leaves the others', () => {
+    const defines = { ORDER_ID: 'ORD-002' };
+    expect(expandDefines('orders.ts:42?order.id == "${ORDER_ID}"', defines)).toBe(
+      'orders.ts:42?order.id == "ORD-002"'
+    );
+    expect(expandDefines('`${user}` == "${ORDER_ID}"', defines)).toBe('`${user}` == "ORD-002"');
+  });
+});
+
+describe('undefinedPlaceholders', () => {
+  it('lists each missing name once', () => {
+    expect(
+      undefinedPlaceholders(['${ORDER_ID} ${TENANT}', 'user.tenant == "${TENANT}"'], {
+        ORDER_ID: 'ORD-002',
+      })
+    ).toEqual(['TENANT']);
+  });
+});
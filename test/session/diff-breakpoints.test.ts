@@ -0,0 +1,174 @@
+/**
+ * Unit tests for breakpoints derived from a git diff
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import { execFileSync } from 'node:child_process';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import {
+  breakpointsFromDiff,
+  isExecutableLine,
+  parseUnifiedDiff,
+  pickBreakpointLines,
+} from '../../src/session/diff-breakpoints.js';
+
+const ORDERS_JS = `import fs from 'node:fs';
+
+export function total(items) {
+  let sum = 0;
+  for (const item of items) {
+    sum += item.price;
+  }
+  return sum;
+}
+
+function discount(order) {
+  // Loyal customers get 10%
+  return order.total * 0.9;
+}
+`;
+
+function hasGit(): boolean {
+  try {
+    execFileSync('git', ['--version'], { stdio: 'ignore' });
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+describe('parseUnifiedDiff', () => {
+  it('returns the changed ranges of the new files', () => {
+    const diff = `diff --git a/src/a.ts b/src/a.ts
+--- a/src/a.ts
++++ b/src/a.ts
+@@ -3 +3 @@ function a() {
+-  old();
++  updated();
+@@ -10,2 +10,3 @@ function b() {
++  added();
+@@ -20,2 +21,0 @@ function c() {
+-  removed();
+diff --git a/gone.ts b/gone.ts
+--- a/gone.ts
++++ /dev/null
+@@ -1,2 +0,0 @@
+-export {};
+`;
+    expect(parseUnifiedDiff(diff)).toEqual([
+      { file: 'src/a.ts', start: 3, count: 1 },
+      { file: 'src/a.ts', start: 10, count: 3 },
+      { file: 'src/a.ts', start: 21, count: 0 },
+    ]);
+  });
+});
+
+describe('isExecutableLine', () => {
+  it('accepts statements', () => {
+    expect(isExecutableLine('  return sum;')).toBe(true);
+    expect(isExecutableLine('  if (x) {')).toBe(true);
+    expect(isExecutableLine('    total = checkout(cart)')).toBe(true);
+  });
+
+  it('rejects declarations, comments and punctuation', () => {
+    expect(isExecutableLine('')).toBe(false);
+    expect(isExecutableLine('  // note')).toBe(false);
+    expect(isExecutableLine('  }')).toBe(false);
+    expect(isExecutableLine('  } else {')).toBe(false);
+    expect(isExecutableLine("import fs from 'node:fs';")).toBe(false);
+    expect(isExecutableLine('using System.Linq;')).toBe(false);
+    expect(isExecutableLine('@app.route("/")')).toBe(false);
+    expect(isExecutableLine('export function total(items) {')).toBe(false);
+    expect(isExecutableLine('    def checkout(self, cart):')).toBe(false);
+    expect(isExecutableLine('pub fn parse(input: &str) -> Result<Ast> {')).toBe(false);
+    expect(isExecutableLine('    public static int Latest(List<Order> orders)')).toBe(false);
+  });
+});
+
+describe('pickBreakpointLines', () => {
+  const lines = ORDERS_JS.split('\n');
+
+  it('picks one executable line per changed function', () => {
+    // Lines 4-6 of total() and 13 of discount() changed
+    expect(
+      pickBreakpointLines(lines, [
+        { file: 'orders.js', start: 4, count: 3 },
+        { file: 'orders.js', start: 13, count: 1 },
+      ])
+    ).toEqual([4, 13]);
+  });
+
+  it('skips non-executable changed lines', () => {
+    expect(pickBreakpointLines(lines, [{ file: 'orders.js', start: 12, count: 2 }])).toEqual([13]);
+  });
+
+  it('breaks on the body when only the signature changed', () => {
+    expect(pickBreakpointLines(lines, [{ file: 'orders.js', start: 11, count: 1 }])).toEqual([13]);
+  });
+
+  it('ignores top-level changes without statements', () => {
+    expect(pickBreakpointLines(lines, [{ file: 'orders.js', start: 1, count: 2 }])).toEqual([]);
+  });
+});
+
+describe.skipIf(!hasGit())('breakpointsFromDiff', () => {
+  let root: string;
+  const git = (...args: string[]) =>
+    execFileSync('git', ['-c', 'user.name=test', '-c', 'user.email=test@example.com', ...args], {
+      cwd: root,
+      stdio: 'ignore',
+    });
+
+  beforeEach(() => {
+    root = fs.realpathSync(fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-diff-')));
+    fs.mkdirSync(path.join(root, 'src'));
+    fs.writeFileSync(path.join(root, 'src/orders.js'), ORDERS_JS);
+    fs.writeFileSync(path.join(root, 'notes.md'), '# Notes\n');
+    git('init', '-q');
+    git('add', '.');
+    git('commit', '-q', '-m', 'init');
+  });
+
+  afterEach(() => {
+    fs.rmSync(root, { recursive: true, force: true });
+  });
+
+  it('returns absolute specs for working tree changes', () => {
+    fs.writeFileSync(
+      path.join(root, 'src/orders.js'),
+      ORDERS_JS.replace('item.price;', 'item.price * item.qty;')
+    );
+    fs.writeFileSync(path.join(root, 'notes.md'), '# Notes\n\nChanged\n');
+
+    expect(breakpointsFromDiff('HEAD', { cwd: path.join(root, 'src'), adapter: 'node' })).toEqual(
+      [`${path.join(root, 'src/orders.js')}:6`]
+    );
+  });
+
+  it('only considers files in the adapter languages', () => {
+    fs.writeFileSync(path.join(root, 'src/orders.js'), ORDERS_JS.replace('0.9', '0.8'));
+    expect(breakpointsFromDiff('HEAD', { cwd: root, adapter: 'debugpy' })).toEqual([]);
+  });
+
+  it('reports git errors', () => {
+    expect(() => breakpointsFromDiff('no-such-rev', { cwd: root })).toThrow('git diff failed');
+  });
+});
+
+// Checked before git runs, so it doesn't need git
+describe('breakpointsFromDiff revision check', () => {
+  it('rejects revisions that git would take as options', () => {
+    const root = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-diff-'));
+    try {
+      const output = path.join(root, 'diff.txt');
+      expect(() => breakpointsFromDiff(`--output=${output}`, { cwd: root })).toThrow(
+        `can't start with "-"`
+      );
+      expect(fs.existsSync(output)).toBe(false);
+    } finally {
+      fs.rmSync(root, { recursive: true, force: true });
+    }
+  });
+});
@@ -0,0 +1,175 @@
+/**
+ * Unit tests for symbol server downloads and SourceLink sources (--symbol-server,
+ * --source-link)
+ */
+
+import { describe, it, expect, vi, beforeEach, afterEach } from 'vitest';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import { deflateRawSync } from 'node:zlib';
+import {
+  SourceLinkMapper,
+  prefetchSymbols,
+  resolveSymbolServer,
+  sourceCachePath,
+} from '../../src/session/dotnet-symbols.js';
+
+const KEY = 'orders.pdb/33221100554477668899aabbccddeeffffffffff/orders.pdb';
+
+/**
+ * A minimal PE32 image with a portable PDB CodeView entry, and optionally an embedded PDB
+ */
+function assembly(pdbName: string, embedded?: string): Buffer {
+  const codeView = Buffer.alloc(24);
+  codeView.write('RSDS', 0, 'latin1');
+  Buffer.from('00112233445566778899aabbccddeeff', 'hex').copy(codeView, 4);
+  codeView.writeUInt32LE(1, 20);
+  const entries = [{ type: 2, data: Buffer.concat([codeView, Buffer.from(`${pdbName}\0`)]) }];
+  if (embedded !== undefined) {
+    const fixed = Buffer.alloc(8);
+    fixed.write('MPDB', 0, 'latin1');
+    entries.push({ type: 17, data: Buffer.concat([fixed, deflateRawSync(Buffer.from(embedded))]) });
+  }
+
+  const header = Buffer.alloc(0x200);
+  header.write('MZ', 0, 'latin1');
+  header.writeUInt32LE(0x80, 0x3c);
+  header.write('PE\0\0', 0x80, 'latin1');
+  header.writeUInt16LE(1, 0x86);
+  header.writeUInt16LE(0xe0, 0x94);
+  header.writeUInt16LE(0x10b, 0x98);
+  header.writeUInt32LE(16, 0x98 + 92);
+  header.writeUInt32LE(0x2000, 0x98 + 96 + 48);
+  header.writeUInt32LE(entries.length * 28, 0x98 + 96 + 52);
+  header.writeUInt32LE(0x1000, 0x178 + 8);
+  header.writeUInt32LE(0x2000, 0x178 + 12);
+  header.writeUInt32LE(0x200, 0x178 + 20);
+  const directory = Buffer.alloc(entries.length * 28);
+  let offset = header.length + directory.length;
+  entries.forEach((entry, index) => {
+    directory.writeUInt16LE(0x504d, index * 28 + 10);
+    directory.writeUInt32LE(entry.type, index * 28 + 12);
+    directory.writeUInt32LE(entry.data.length, index * 28 + 16);
+    directory.writeUInt32LE(offset, index * 28 + 24);
+    offset += entry.data.length;
+  });
+  return Buffer.concat([header, directory, ...entries.map((entry) => entry.data)]);
+}
+
+function fakeFetch(files: Record<string, string>) {
+  return vi.fn(async (url: string) =>
+    url in files ? new Response(files[url]) : new Response('', { status: 404 })
+  );
+}
+
+describe('resolveSymbolServer', () => {
+  it('accepts known names and http(s) URLs', () => {
+    expect(resolveSymbolServer('nuget')).toBe('https://symbols.nuget.org/download/symbols');
+    expect(resolveSymbolServer('https://symbols.example.com/')).toBe(
+      'https://symbols.example.com'
+    );
+  });
+
+  it('rejects anything else', () => {
+    expect(() => resolveSymbolServer('/srv/symbols')).toThrow(
+      'Invalid --symbol-server "/srv/symbols": expected an http(s) URL, microsoft or nuget'
+    );
+  });
+});
+
+describe('sourceCachePath', () => {
+  it('lays files out by host and path, one directory per URL segment', () => {
+    const url = 'https://raw.githubusercontent.com/acme/orders/4f2c9e1/src/Order%20Lines%2F..cs';
+    expect(sourceCachePath(url, '/cache')).toBe(
+      path.join('/cache', 'raw.githubusercontent.com', 'acme/orders/4f2c9e1/src/Order Lines_..cs')
+    );
+  });
+});
+
+describe('dotnet symbols', () => {
+  let dir: string;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-symbols-'));
+    fs.mkdirSync(path.join(dir, 'bin'));
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  describe('prefetchSymbols', () => {
+    it('copies PDBs from the first server that has them, then from the cache', async () => {
+      const bin = path.join(dir, 'bin');
+      const cache = path.join(dir, 'cache');
+      fs.writeFileSync(path.join(bin, 'Orders.dll'), assembly('/_/obj/Orders.pdb'));
+      fs.writeFileSync(path.join(bin, 'Embedded.dll'), assembly('Embedded.pdb', 'BSJB'));
+      fs.writeFileSync(path.join(bin, 'appsettings.json'), '{}');
+      const fetchImpl = fakeFetch({ [`https://second.example.com/${KEY}`]: 'PDB' });
+      const servers = ['https://first.example.com', 'https://second.example.com'];
+
+      const result = await prefetchSymbols(bin, servers, cache, fetchImpl as typeof fetch);
+      expect(result).toEqual({ loaded: [path.join(bin, 'Orders.pdb')], missing: [] });
+      expect(fs.readFileSync(path.join(bin, 'Orders.pdb'), 'utf8')).toBe('PDB');
+      expect(fetchImpl).toHaveBeenCalledTimes(2);
+
+      fs.rmSync(path.join(bin, 'Orders.pdb'));
+      await prefetchSymbols(bin, servers, cache, fetchImpl as typeof fetch);
+      expect(fs.existsSync(path.join(bin, 'Orders.pdb'))).toBe(true);
+      expect(fetchImpl).toHaveBeenCalledTimes(2);
+    });
+
+    it('reports assemblies no server has symbols for', async () => {
+      const bin = path.join(dir, 'bin');
+      fs.writeFileSync(path.join(bin, 'Orders.dll'), assembly('Orders.pdb'));
+      const result = await prefetchSymbols(
+        bin,
+        ['https://symbols.example.com'],
+        path.join(dir, 'cache'),
+        fakeFetch({}) as typeof fetch
+      );
+      expect(result).toEqual({ loaded: [], missing: [path.join(bin, 'Orders.dll')] });
+    });
+  });
+
+  describe('SourceLinkMapper', () => {
+    const sourceLink =
+      '{"documents":{"/_/*":"https://raw.githubusercontent.com/acme/orders/4f2c9e1/*"}}';
+
+    it('reports covered build paths as the cached download and maps them back', async () => {
+      const module = path.join(dir, 'bin', 'Orders.dll');
+      fs.writeFileSync(module, assembly('Orders.pdb', `BSJB\0${sourceLink}`));
+      const cache = path.join(dir, 'sources');
+      const url = 'https://raw.githubusercontent.com/acme/orders/4f2c9e1/src/Order.cs';
+      const fetchImpl = fakeFetch({ [url]: 'class Order {}' });
+      const mapper = new SourceLinkMapper(cache, fetchImpl as typeof fetch);
+      mapper.addModule(module);
+
+      const local = mapper.toLocal('/_/src/Order.cs');
+      expect(local).toBe(sourceCachePath(url, cache));
+      expect(mapper.toAdapter(local)).toBe('/_/src/Order.cs');
+      expect(mapper.toLocal(module)).toBe(module);
+      expect(mapper.toLocal('/build/elsewhere/Program.cs')).toBe('/build/elsewhere/Program.cs');
+
+      await mapper.fetchSources([local, null, local]);
+      expect(fs.readFileSync(local, 'utf8')).toBe('class Order {}');
+      expect(fetchImpl).toHaveBeenCalledTimes(1);
+    });
+
+    it('gives up on a source that failed to download', async () => {
+      const module = path.join(dir, 'bin', 'Orders.dll');
+      fs.writeFileSync(module, assembly('Orders.pdb'));
+      fs.writeFileSync(path.join(dir, 'bin', 'Orders.pdb'), `BSJB\0${sourceLink}`);
+      const fetchImpl = fakeFetch({});
+      const mapper = new SourceLinkMapper(path.join(dir, 'sources'), fetchImpl as typeof fetch);
+      mapper.addModule(module);
+
+      const local = mapper.toLocal('/_/src/Missing.cs');
+      await mapper.fetchSources([local]);
+      await mapper.fetchSources([local]);
+      expect(fs.existsSync(local)).toBe(false);
+      expect(fetchImpl).toHaveBeenCalledTimes(1);
+    });
+  });
+});
@@ -0,0 +1,111 @@
+/**
+ * Unit tests for exception breakpoint specs
+ */
+
+import { describe, it, expect } from 'vitest';
+import { nodeAdapter } from '../../src/adapters/node.js';
+import { debugpyAdapter } from '../../src/adapters/debugpy.js';
+import {
+  parseExceptionBreakpointSpec,
+  planExceptionBreakpoints,
+  validateExceptionBreakpointSpecs,
+} from '../../src/session/exception-breakpoints.js';
+
+describe('parseExceptionBreakpointSpec', () => {
+  it('parses filters, types and conditions', () => {
+    expect(parseExceptionBreakpointSpec('all')).toEqual({ filter: 'all', condition: undefined });
+    expect(parseExceptionBreakpointSpec('type:ValidationError')).toEqual({
+      type: 'ValidationError',
+      condition: undefined,
+    });
+    expect(parseExceptionBreakpointSpec('uncaught?error.status >= 500')).toEqual({
+      filter: 'uncaught',
+      condition: 'error.status >= 500',
+    });
+    // Only the first ? separates the condition
+    expect(parseExceptionBreakpointSpec('type:HttpError?error.retry ? 1 : 0')).toEqual({
+      type: 'HttpError',
+      condition: 'error.retry ? 1 : 0',
+    });
+  });
+
+  it('rejects empty parts', () => {
+    expect(() => parseExceptionBreakpointSpec('  ')).toThrow('Expected a filter or "type:Name"');
+    expect(() => parseExceptionBreakpointSpec('type:')).toThrow('Missing exception type');
+    expect(() => parseExceptionBreakpointSpec('all?')).toThrow('Empty condition');
+  });
+});
+
+describe('validateExceptionBreakpointSpecs', () => {
+  it('checks filters and type support against the adapter', () => {
+    expect(validateExceptionBreakpointSpecs(['all', 'type:TypeError'], nodeAdapter)).toEqual([]);
+    expect(validateExceptionBreakpointSpecs(['raised'], nodeAdapter)[0]).toContain(
+      'does not support exception filter "raised"'
+    );
+    expect(
+      validateExceptionBreakpointSpecs(['type:SIGSEGV'], { name: 'lldb', exceptionFilters: [] })[0]
+    ).toContain('does not support exception type filters');
+  });
+});
+
+describe('planExceptionBreakpoints', () => {
+  const conditionCapabilities = {
+    supportsExceptionFilterOptions: true,
+    exceptionBreakpointFilters: [
+      { filter: 'all', label: 'Caught Exceptions', supportsCondition: true },
+      { filter: 'uncaught', label: 'Uncaught Exceptions', supportsCondition: true },
+    ],
+  };
+
+  it('turns types into a filter condition for js-debug', () => {
+    const plan = planExceptionBreakpoints(
+      ['uncaught', 'type:ValidationError?error.field === "email"'],
+      nodeAdapter,
+      conditionCapabilities
+    );
+
+    expect(plan.args.filters).toEqual(['uncaught']);
+    expect(plan.args.filterOptions).toEqual([
+      {
+        filterId: 'all',
+        condition:
+          '(error?.name === "ValidationError" || error?.constructor?.name === "ValidationError")' +
+          ' && (error.field === "email")',
+      },
+    ]);
+    expect(plan.types).toEqual(['ValidationError']);
+    expect(plan.unsupported).toEqual([]);
+  });
+
+  it('uses exceptionOptions for debugpy', () => {
+    const plan = planExceptionBreakpoints(['type:KeyError'], debugpyAdapter, {
+      supportsExceptionOptions: true,
+    });
+
+    expect(plan.args).toEqual({
+      filters: [],
+      exceptionOptions: [
+        { path: [{ names: ['Python Exceptions'] }, { names: ['KeyError'] }], breakMode: 'always' },
+      ],
+    });
+  });
+
+  it('reports what the adapter cannot do', () => {
+    const plan = planExceptionBreakpoints(
+      ['all?error.code', 'type:KeyError?True', 'type:ValueError'],
+      debugpyAdapter,
+      {
+        supportsExceptionOptions: false,
+        supportsExceptionFilterOptions: true,
+        exceptionBreakpointFilters: [{ filter: 'all', label: 'All', supportsCondition: false }],
+      }
+    );
+
+    expect(plan.args).toEqual({ filters: [] });
+    expect(plan.unsupported).toEqual([
+      `all?error.code: the adapter doesn't support conditions on "all"`,
+      "type:KeyError?True: the adapter doesn't support exceptionOptions",
+      "type:ValueError: the adapter doesn't support exceptionOptions",
+    ]);
+  });
+});
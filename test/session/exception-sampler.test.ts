@@ -0,0 +1,81 @@
+/**
+ * Unit tests for exception sampling (--sample-exceptions)
+ */
+
+import { describe, it, expect } from 'vitest';
+import { ExceptionSampler, type SampledException } from '../../src/session/exception-sampler.js';
+
+function exception(
+  type: string,
+  line: number,
+  message: string = `${type} at ${line}`
+): SampledException {
+  return {
+    type,
+    message,
+    location: { file: '/srv/api/client.ts', line, function: 'fetchQuote' },
+    stackTrace: Array.from({ length: 15 }, (_, i) => ({
+      frameId: i,
+      function: `frame${i}`,
+      file: '/srv/api/client.ts',
+      line: line + i,
+    })),
+  };
+}
+
+const start = new Date('2026-01-01T00:00:00Z');
+const at = (seconds: number) => new Date(start.getTime() + seconds * 1000);
+
+describe('ExceptionSampler', () => {
+  it('groups exceptions by type and throw site, the most frequent first', () => {
+    const sampler = new ExceptionSampler(start);
+    sampler.record(exception('RangeError', 10), at(1));
+    sampler.record(exception('TimeoutError', 88, 'timed out after 3000ms'), at(2));
+    sampler.record(exception('TimeoutError', 88, 'timed out after 5000ms'), at(3));
+    sampler.record(exception('TimeoutError', 90), at(4));
+
+    const report = sampler.report(false, at(60));
+    expect(report).toMatchObject({
+      periodStart: start.toISOString(),
+      periodEnd: at(60).toISOString(),
+      count: 4,
+      total: 4,
+    });
+    expect(report.final).toBeUndefined();
+    expect(report.groups.map((group) => [group.type, group.location.line])).toEqual([
+      ['TimeoutError', 88],
+      ['RangeError', 10],
+      ['TimeoutError', 90],
+    ]);
+    expect(report.groups[0]).toMatchObject({
+      message: 'timed out after 5000ms',
+      distinctMessages: 2,
+      count: 2,
+      total: 2,
+      firstSeen: at(2).toISOString(),
+      lastSeen: at(3).toISOString(),
+    });
+    expect(report.groups[0].stackTrace).toHaveLength(10);
+    expect(report.groups[1].distinctMessages).toBeUndefined();
+  });
+
+  it('reports each period on its own and keeps running totals', () => {
+    const sampler = new ExceptionSampler(start);
+    sampler.record(exception('RangeError', 10), at(1));
+    sampler.record(exception('TimeoutError', 88), at(2));
+    sampler.report(false, at(60));
+
+    sampler.record(exception('TimeoutError', 88), at(61));
+    const report = sampler.report(true, at(90));
+    expect(report).toMatchObject({
+      periodStart: at(60).toISOString(),
+      count: 1,
+      total: 3,
+      final: true,
+    });
+    expect(report.groups).toHaveLength(1);
+    expect(report.groups[0]).toMatchObject({ type: 'TimeoutError', count: 1, total: 2 });
+
+    expect(sampler.report(false, at(120))).toMatchObject({ count: 0, total: 3, groups: [] });
+  });
+});
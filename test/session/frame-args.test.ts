@@ -0,0 +1,99 @@
+/**
+ * Unit tests for frame argument names (--capture-args)
+ */
+
+import { describe, it, expect } from 'vitest';
+import { parameterNames, shortFunctionName } from '../../src/session/frame-args.js';
+
+const INVENTORY_TS = `export class Inventory {
+  async reserve(sku: string, quantity = 1, ...tags: string[]): Promise<void> {
+    const stock = await this.stockOf(sku);
+    if (stock < quantity) {
+      this.reserve(sku, stock);
+    }
+  }
+}
+
+export const release = async ({ sku }: Item, force?: boolean) => {
+  log(sku);
+};
+`;
+
+const ORDERS_PY = `class Orders:
+    def place(self, order, /, *, retries: int = 3, **options) -> bool:
+        place(order)
+        return True
+`;
+
+const ORDER_SERVICE_CS = `public class OrderService
+{
+    public async Task<Order> Reserve(
+        [FromBody] Order order,
+        Dictionary<string, int> limits,
+        CancellationToken token = default)
+    {
+        return order;
+    }
+}
+`;
+
+describe('shortFunctionName', () => {
+  it('takes the last segment of qualified names', () => {
+    expect(shortFunctionName('Inventory.reserve')).toBe('reserve');
+    expect(shortFunctionName('app::orders::reserve<u64>')).toBe('reserve');
+    expect(shortFunctionName('MyApp.dll!MyApp.OrderService.Reserve(Order order)')).toBe(
+      'Reserve'
+    );
+    expect(shortFunctionName('async place')).toBe('place');
+  });
+
+  it('has no name for anonymous frames', () => {
+    expect(shortFunctionName('<anonymous>')).toBeUndefined();
+    expect(shortFunctionName('app::main::{{closure}}')).toBeUndefined();
+  });
+});
+
+describe('parameterNames', () => {
+  it('reads TypeScript methods and arrow functions, skipping calls', () => {
+    const lines = INVENTORY_TS.split('\n');
+    expect(parameterNames(lines, 5, 'reserve', 'inventory.ts')).toEqual([
+      'sku',
+      'quantity',
+      'tags',
+    ]);
+    expect(parameterNames(lines, 11, 'release', 'inventory.ts')).toEqual(['force']);
+  });
+
+  it('reads Python defs, ignoring the / and * markers', () => {
+    expect(parameterNames(ORDERS_PY.split('\n'), 3, 'place', 'orders.py')).toEqual([
+      'self',
+      'order',
+      'retries',
+      'options',
+    ]);
+  });
+
+  it('reads C# parameter lists across lines', () => {
+    expect(parameterNames(ORDER_SERVICE_CS.split('\n'), 8, 'Reserve', 'OrderService.cs')).toEqual([
+      'order',
+      'limits',
+      'token',
+    ]);
+  });
+
+  it('reads Rust and Go declarations', () => {
+    const rust = ['fn price<T: Into<u64>>(&mut self, item: T, mut qty: u32) -> u64 {', '  0'];
+    expect(parameterNames(rust, 2, 'price', 'pricing.rs')).toEqual(['self', 'item', 'qty']);
+    const go = ['func (r *Repo) Save(ctx context.Context, a, b int, rest ...string) error {'];
+    expect(parameterNames([...go, '\treturn nil'], 2, 'Save', 'repo.go')).toEqual([
+      'ctx',
+      'a',
+      'b',
+      'rest',
+    ]);
+  });
+
+  it('is undefined without a declaration above the line', () => {
+    expect(parameterNames(INVENTORY_TS.split('\n'), 5, 'checkout', 'inventory.ts')).toBeUndefined();
+  });
+});
@@ -0,0 +1,133 @@
+/**
+ * Unit tests for glob breakpoints (-b with a file glob)
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import { isParserAvailable } from '../../src/session/ast-breakpoints.js';
+import { validateAllBreakpoints } from '../../src/session/breakpoints.js';
+import {
+  breakpointGlob,
+  expandGlobBreakpoints,
+  globToRegExp,
+  matchFiles,
+} from '../../src/session/glob-breakpoints.js';
+
+const PRICING_RS = `pub fn calculate_total(items: &[Item]) -> u64 {
+    let total = items.iter().map(|i| i.price).sum();
+    total
+}
+
+pub fn calculate_tax(total: u64) -> u64 {
+    if total == 0 {
+        return 0;
+    }
+    total / 10
+}
+`;
+
+const PRICING_V1_RS = `fn calculate_total(items: &[Item]) -> u64 {
+    items.len() as u64
+}
+`;
+
+const RUST_PARSER = isParserAvailable('pricing.rs');
+
+describe('breakpointGlob', () => {
+  it('finds a glob in the file part only', () => {
+    expect(breakpointGlob('src/**/pricing*.rs:fn:calculate_*')).toBe('src/**/pricing*.rs');
+    expect(breakpointGlob('src/*.ts:45?x > 1')).toBe('src/*.ts');
+    expect(breakpointGlob('app.js:3?x * 2 > 1')).toBeUndefined();
+    expect(breakpointGlob('C:\\app\\Orders.cs:10')).toBeUndefined();
+  });
+});
+
+describe('globToRegExp', () => {
+  it('matches ** across directories and * within one', () => {
+    const pattern = globToRegExp('src/**/pricing*.rs');
+    expect(pattern.test('src/pricing.rs')).toBe(true);
+    expect(pattern.test('src/legacy/v1/pricing_v1.rs')).toBe(true);
+    expect(pattern.test('src/pricing/mod.rs')).toBe(false);
+    expect(globToRegExp('src/*.rs').test('src/a/b.rs')).toBe(false);
+    expect(globToRegExp('src/?.rs').test('src/a.rs')).toBe(true);
+  });
+});
+
+describe('expandGlobBreakpoints', () => {
+  let dir: string;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-glob-'));
+    fs.mkdirSync(path.join(dir, 'src', 'legacy'), { recursive: true });
+    fs.mkdirSync(path.join(dir, 'target'));
+    fs.writeFileSync(path.join(dir, 'src', 'pricing.rs'), PRICING_RS);
+    fs.writeFileSync(path.join(dir, 'src', 'legacy', 'pricing_v1.rs'), PRICING_V1_RS);
+    fs.writeFileSync(path.join(dir, 'src', 'orders.rs'), PRICING_V1_RS);
+    fs.writeFileSync(path.join(dir, 'target', 'pricing.rs'), PRICING_RS);
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  it('matches files under cwd, skipping build directories', () => {
+    expect(matchFiles('**/pricing*.rs', dir)).toEqual([
+      path.join(dir, 'src', 'legacy', 'pricing_v1.rs'),
+      path.join(dir, 'src', 'pricing.rs'),
+    ]);
+  });
+
+  it.skipIf(!RUST_PARSER)('expands structural points and keeps other breakpoints', () => {
+    const { breakpoints, expansions } = expandGlobBreakpoints(
+      ['src/**/pricing*.rs:fn:calculate_*', 'src/orders.rs:2'],
+      { cwd: dir }
+    );
+
+    const expanded = [
+      `${path.join(dir, 'src', 'legacy', 'pricing_v1.rs')}:2`,
+      `${path.join(dir, 'src', 'pricing.rs')}:2`,
+      `${path.join(dir, 'src', 'pricing.rs')}:7`,
+    ];
+    expect(breakpoints).toEqual([...expanded, 'src/orders.rs:2']);
+    expect(expansions).toEqual([
+      { pattern: 'src/**/pricing*.rs:fn:calculate_*', breakpoints: expanded },
+    ]);
+  });
+
+  it('copies a line and its condition to every matching file', () => {
+    const { breakpoints } = expandGlobBreakpoints(['src/*.rs:2?total > 0'], { cwd: dir });
+    expect(breakpoints).toEqual([
+      `${path.join(dir, 'src', 'orders.rs')}:2?total > 0`,
+      `${path.join(dir, 'src', 'pricing.rs')}:2?total > 0`,
+    ]);
+  });
+
+  it('numbers a label copied to several files', () => {
+    const { breakpoints } = expandGlobBreakpoints(['src/*.rs:2#total?total > 0'], { cwd: dir });
+    expect(breakpoints).toEqual([
+      `${path.join(dir, 'src', 'orders.rs')}:2#total-1?total > 0`,
+      `${path.join(dir, 'src', 'pricing.rs')}:2#total-2?total > 0`,
+    ]);
+    expect(validateAllBreakpoints(breakpoints, [])).toEqual([]);
+    expect(expandGlobBreakpoints(['src/legacy/*.rs:2#total'], { cwd: dir }).breakpoints).toEqual([
+      `${path.join(dir, 'src', 'legacy', 'pricing_v1.rs')}:2#total`,
+    ]);
+  });
+
+  it('fails when a glob matches no file or function', () => {
+    expect(() => expandGlobBreakpoints(['lib/**/*.rs:3'], { cwd: dir })).toThrow(
+      'no files match lib/**/*.rs'
+    );
+    expect(() => expandGlobBreakpoints(['src/*.rs:fn:calc_*:last'], { cwd: dir })).toThrow(
+      'Invalid -b "fn:calc_*:last"'
+    );
+  });
+
+  it.skipIf(!RUST_PARSER)('fails when no matching file declares the function', () => {
+    expect(() => expandGlobBreakpoints(['src/*.rs:fn:refund_*'], { cwd: dir })).toThrow(
+      'no function matching refund_* in src/*.rs'
+    );
+  });
+});
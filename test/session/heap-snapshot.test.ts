@@ -0,0 +1,143 @@
+/**
+ * Unit tests for heap snapshot capture and summaries
+ */
+
+import { describe, it, expect, vi, beforeEach, afterEach } from 'vitest';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import type { IDapClient } from '../../src/dap/client-interface.js';
+import {
+  readHeapSnapshotSummary,
+  summarizeHeapSnapshot,
+  takeHeapSnapshot,
+  type HeapSnapshotJson,
+} from '../../src/session/heap-snapshot.js';
+
+const NODE_FIELDS = [
+  'type',
+  'name',
+  'id',
+  'self_size',
+  'edge_count',
+  'trace_node_id',
+  'detachedness',
+];
+const NODE_TYPES = [
+  'hidden',
+  'array',
+  'string',
+  'object',
+  'code',
+  'closure',
+  'regexp',
+  'number',
+  'native',
+  'synthetic',
+];
+const EDGE_TYPES = ['context', 'element', 'property', 'internal', 'hidden', 'shortcut', 'weak'];
+
+/**
+ * root -> Cache -> Entry
+ * root -> Other -> (array) <- Cache, and a weak Entry -> Other edge
+ */
+function buildSnapshot(): HeapSnapshotJson {
+  const node = (type: string, name: number, id: number, size: number, edges: number) => [
+    NODE_TYPES.indexOf(type),
+    name,
+    id,
+    size,
+    edges,
+    0,
+    0,
+  ];
+  const edge = (type: string, to: number) => [
+    EDGE_TYPES.indexOf(type),
+    0,
+    to * NODE_FIELDS.length,
+  ];
+
+  return {
+    snapshot: {
+      meta: {
+        node_fields: NODE_FIELDS,
+        node_types: [NODE_TYPES, 'string', 'number', 'number', 'number', 'number', 'number'],
+        edge_fields: ['type', 'name_or_index', 'to_node'],
+        edge_types: [EDGE_TYPES, 'string_or_number', 'node'],
+      },
+    },
+    nodes: [
+      ...node('synthetic', 0, 1, 0, 2),
+      ...node('object', 1, 3, 10, 2),
+      ...node('object', 2, 5, 100, 1),
+      ...node('array', 3, 7, 50, 0),
+      ...node('object', 4, 9, 5, 1),
+    ],
+    edges: [
+      ...edge('property', 1),
+      ...edge('property', 4),
+      ...edge('property', 2),
+      ...edge('element', 3),
+      ...edge('weak', 4),
+      ...edge('property', 3),
+    ],
+    strings: ['', 'Cache', 'Entry', '', 'Other'],
+  };
+}
+
+describe('summarizeHeapSnapshot', () => {
+  it('ranks objects by retained size using the dominator tree', () => {
+    const summary = summarizeHeapSnapshot(buildSnapshot(), 3);
+
+    expect(summary.totalSize).toBe(165);
+    expect(summary.nodeCount).toBe(5);
+    // The array is also reachable through Other, so Cache doesn't retain it
+    expect(summary.topRetainers).toEqual([
+      { name: 'Cache', type: 'object', id: 3, selfSize: 10, retainedSize: 110 },
+      { name: 'Entry', type: 'object', id: 5, selfSize: 100, retainedSize: 100 },
+      { name: '', type: 'array', id: 7, selfSize: 50, retainedSize: 50 },
+    ]);
+  });
+
+  it('groups self size by constructor', () => {
+    expect(summarizeHeapSnapshot(buildSnapshot()).topConstructors).toEqual([
+      { name: 'Entry', count: 1, selfSize: 100 },
+      { name: '(array)', count: 1, selfSize: 50 },
+      { name: 'Cache', count: 1, selfSize: 10 },
+      { name: 'Other', count: 1, selfSize: 5 },
+    ]);
+  });
+});
+
+describe('takeHeapSnapshot', () => {
+  let dir: string;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-heap-'));
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  it('asks js-debug for a memory profile written to the file', async () => {
+    const file = path.join(dir, 'hit1.heapsnapshot');
+    const customRequest = vi.fn(async (command: string) => {
+      if (command === 'stopProfile') {
+        fs.writeFileSync(file, JSON.stringify(buildSnapshot()));
+      }
+    });
+
+    await takeHeapSnapshot({ customRequest } as unknown as IDapClient, file);
+
+    expect(customRequest).toHaveBeenNthCalledWith(1, 'startProfile', { type: 'memory', file });
+    expect(customRequest).toHaveBeenNthCalledWith(2, 'stopProfile');
+    expect(readHeapSnapshotSummary(file).totalSize).toBe(165);
+  });
+
+  it('fails when no snapshot was written', async () => {
+    const client = { customRequest: vi.fn() } as unknown as IDapClient;
+    const file = path.join(dir, 'missing.heapsnapshot');
+    await expect(takeHeapSnapshot(client, file)).rejects.toThrow('did not write the heap snapshot');
+  });
+});
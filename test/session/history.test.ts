@@ -0,0 +1,80 @@
+/**
+ * Unit tests for the serve mode stop history ($1, $2, ... in evaluate)
+ */
+
+import { describe, it, expect } from 'vitest';
+import { StopHistory, type StopSnapshot } from '../../src/session/history.js';
+
+function stop(line: number, subtotal: number, extra: Partial<StopSnapshot> = {}): StopSnapshot {
+  return {
+    type: 'breakpoint_hit',
+    timestamp: new Date(2026, 9, 14, 9, 30, line).toISOString(),
+    location: { file: 'src/cart.ts', line },
+    locals: {
+      subtotal: { type: 'number', value: subtotal },
+      label: { type: 'string', value: `line ${line}` },
+      order: {
+        type: 'Object',
+        value: {
+          items: {
+            type: 'Array',
+            value: { '[0]': { type: 'Object', value: { sku: { type: 'string', value: 'A-1' } } } },
+          },
+        },
+      },
+    },
+    ...extra,
+  };
+}
+
+describe('StopHistory', () => {
+  it('counts back from the current stop and keeps the last N earlier stops', () => {
+    const history = new StopHistory(2);
+    for (const line of [10, 20, 30, 40]) {
+      history.record(stop(line, line));
+    }
+
+    expect(history.get(0)).toBeUndefined();
+    expect(history.get(1)?.location.line).toBe(30);
+    expect(history.get(2)?.location.line).toBe(20);
+    expect(history.get(3)).toBeUndefined();
+  });
+
+  it('returns the captured value for a single reference', () => {
+    const history = new StopHistory(3);
+    history.record(stop(10, 99.5, { evaluations: { total: { result: '120' } } }));
+    history.record(stop(20, 80));
+
+    expect(history.expand('$1.subtotal').value).toMatchObject({ result: '99.5', type: 'number' });
+    expect(history.expand('$1.label').value).toMatchObject({ result: 'line 10' });
+    expect(history.expand('$1.total').value).toMatchObject({ result: '120' });
+    expect(history.expand('$1.order.items[0].sku').value).toMatchObject({ result: 'A-1' });
+    expect(history.expand('$1.order').value?.literal).toBeUndefined();
+  });
+
+  it('substitutes primitive values into a larger expression', () => {
+    const history = new StopHistory(3);
+    history.record(stop(10, 99.5));
+    history.record(stop(20, 80));
+    history.record(stop(30, 75));
+
+    expect(history.expand('subtotal - $1.subtotal')).toEqual({ expression: 'subtotal - 80' });
+    expect(history.expand('$2.label + $1.label').expression).toBe('"line 10" + "line 20"');
+  });
+
+  it('explains references it cannot resolve', () => {
+    const history = new StopHistory(3);
+    history.record(stop(10, 99.5));
+    history.record(stop(20, 80));
+
+    expect(() => history.expand('$2.subtotal')).toThrow('$2 is not in the history (1 earlier');
+    expect(() => history.expand('$1.missing')).toThrow('$1.missing was not captured');
+    expect(() => history.expand('total - $1.order')).toThrow('$1.order is an object');
+  });
+
+  it('detects expressions that refer to the history', () => {
+    expect(StopHistory.references('subtotal - $1.subtotal')).toBe(true);
+    expect(StopHistory.references('$price')).toBe(false);
+    expect(StopHistory.references('subtotal')).toBe(false);
+  });
+});
@@ -0,0 +1,59 @@
+/**
+ * Unit tests for breakpoint hit windows (--active-after / --active-until)
+ */
+
+import { describe, it, expect } from 'vitest';
+import {
+  HitWindowTracker,
+  hitWindowLabels,
+  parseHitWindows,
+} from '../../src/session/hit-windows.js';
+
+describe('parseHitWindows', () => {
+  it('combines the bounds given for each label', () => {
+    const windows = parseHitWindows(
+      ['parse=iteration@10', 'flush=flush@3'],
+      ['parse=iteration@20']
+    );
+    expect(windows).toEqual([
+      {
+        label: 'parse',
+        after: { counter: 'iteration', count: 10 },
+        until: { counter: 'iteration', count: 20 },
+      },
+      { label: 'flush', after: { counter: 'flush', count: 3 } },
+    ]);
+    expect(hitWindowLabels(windows)).toEqual(['parse', 'iteration', 'flush']);
+  });
+
+  it('rejects malformed, repeated and empty windows', () => {
+    expect(() => parseHitWindows(['parse@10'])).toThrow('expected label=counter@count');
+    expect(() => parseHitWindows(['parse=iteration@0'])).toThrow('must be 1 or more');
+    expect(() => parseHitWindows([], ['a=b@1', 'a=c@2'])).toThrow('more than once');
+    expect(() => parseHitWindows(['a=b@5'], ['a=b@4'])).toThrow('ends before it starts');
+  });
+});
+
+describe('HitWindowTracker', () => {
+  it('captures a label only while its counter is within the window', () => {
+    const windows = parseHitWindows(['parse=iteration@2'], ['parse=iteration@3']);
+    const tracker = new HitWindowTracker(windows);
+    const captured: number[] = [];
+    for (let iteration = 1; iteration <= 4; iteration++) {
+      tracker.record(['iteration']);
+      if (tracker.record(['parse'])) captured.push(iteration);
+    }
+    expect(captured).toEqual([2, 3]);
+    expect(tracker.hitCount('parse')).toBe(4);
+  });
+
+  it('counts a label that is never captured and starts again after a reset', () => {
+    const tracker = new HitWindowTracker(parseHitWindows([], ['iteration=iteration@0']));
+    expect(tracker.record(['iteration'])).toBe(false);
+    expect(tracker.record([])).toBe(true);
+    expect(tracker.record(['iteration', 'other'])).toBe(true);
+    expect(tracker.hitCount('iteration')).toBe(2);
+    tracker.reset();
+    expect(tracker.hitCount('iteration')).toBe(0);
+  });
+});
@@ -0,0 +1,64 @@
+/**
+ * Unit tests for hot code reload expressions
+ */
+
+import { describe, it, expect, vi } from 'vitest';
+import * as vm from 'node:vm';
+import * as path from 'node:path';
+import {
+  buildReloadExpression,
+  parseReloadResult,
+  resolveReloadFiles,
+} from '../../src/session/hot-reload.js';
+
+describe('buildReloadExpression', () => {
+  it('reloads cached CommonJS modules in js-debug sessions', () => {
+    const cache: Record<string, object> = { '/app/a.js': {} };
+    const load = Object.assign(
+      vi.fn((file: string) => {
+        cache[file] = {};
+      }),
+      { cache }
+    );
+    const expression = buildReloadExpression('js-debug', ['/app/a.js', '/app/b.mjs']);
+
+    expect(vm.runInNewContext(expression, { require: load })).toBe('10');
+    expect(load).toHaveBeenCalledOnce();
+    expect(load).toHaveBeenCalledWith('/app/a.js');
+  });
+
+  it("falls back to the main module's require outside CommonJS frames", () => {
+    const load = Object.assign(vi.fn(), { cache: { '/app/a.js': {} } });
+    const expression = buildReloadExpression('js-debug', ['/app/a.js']);
+
+    expect(vm.runInNewContext(expression, { process: { mainModule: { require: load } } })).toBe(
+      '1'
+    );
+  });
+
+  it('quotes paths as string literals for debugpy', () => {
+    const expression = buildReloadExpression('debugpy', ['/app/handlers.py', 'C:\\app\\x.py']);
+    expect(expression).toContain('importlib.reload(m)');
+    expect(expression).toContain('["/app/handlers.py", "C:\\\\app\\\\x.py"])');
+  });
+});
+
+describe('parseReloadResult', () => {
+  it('splits files by the returned flags, ignoring quotes', () => {
+    expect(parseReloadResult(['/a.py', '/b.py', '/c.py'], "'101'")).toEqual({
+      reloaded: ['/a.py', '/c.py'],
+      notLoaded: ['/b.py'],
+    });
+  });
+
+  it('treats missing flags as not loaded', () => {
+    expect(parseReloadResult(['/a.js'], '""')).toEqual({ reloaded: [], notLoaded: ['/a.js'] });
+  });
+});
+
+describe('resolveReloadFiles', () => {
+  it('resolves relative paths against the session cwd', () => {
+    const cwd = path.resolve('/no-such-dir');
+    expect(resolveReloadFiles(['src/a.js'], cwd)).toEqual([path.join(cwd, 'src/a.js')]);
+  });
+});
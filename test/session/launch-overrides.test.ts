@@ -0,0 +1,91 @@
+/**
+ * Unit tests for launch request overrides (--launch-arg, --launch-json)
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import {
+  buildLaunchOverrides,
+  mergeLaunchOverrides,
+  parseLaunchArg,
+} from '../../src/session/launch-overrides.js';
+
+describe('parseLaunchArg', () => {
+  it('parses JSON values and keeps anything else as a string', () => {
+    expect(parseLaunchArg('sourceMaps=true')).toEqual({ sourceMaps: true });
+    expect(parseLaunchArg('port=9229')).toEqual({ port: 9229 });
+    expect(parseLaunchArg('outFiles=["dist/**/*.js"]')).toEqual({ outFiles: ['dist/**/*.js'] });
+    expect(parseLaunchArg('console=integratedTerminal')).toEqual({ console: 'integratedTerminal' });
+    expect(parseLaunchArg('args=')).toEqual({ args: '' });
+  });
+
+  it('nests dotted keys and keeps "=" in the value', () => {
+    expect(parseLaunchArg('env.DEBUG=app:*')).toEqual({ env: { DEBUG: 'app:*' } });
+    expect(parseLaunchArg('env.OPTS=a=b')).toEqual({ env: { OPTS: 'a=b' } });
+  });
+
+  it('rejects missing keys and values', () => {
+    expect(() => parseLaunchArg('sourceMaps')).toThrow('expected key=value');
+    expect(() => parseLaunchArg('=true')).toThrow('expected key=value');
+    expect(() => parseLaunchArg('env..DEBUG=1')).toThrow('expected key=value');
+  });
+});
+
+describe('mergeLaunchOverrides', () => {
+  it('merges objects key by key and replaces arrays and scalars', () => {
+    const generated = {
+      type: 'pwa-node',
+      env: { NODE_ENV: 'test' },
+      skipFiles: ['<node_internals>/**'],
+      sourceMaps: false,
+    };
+
+    const merged = mergeLaunchOverrides(generated, {
+      env: { DEBUG: 'app:*' },
+      skipFiles: ['node_modules/**'],
+      sourceMaps: true,
+    });
+
+    expect(merged).toEqual({
+      type: 'pwa-node',
+      env: { NODE_ENV: 'test', DEBUG: 'app:*' },
+      skipFiles: ['node_modules/**'],
+      sourceMaps: true,
+    });
+    expect(generated.env).toEqual({ NODE_ENV: 'test' });
+  });
+});
+
+describe('buildLaunchOverrides', () => {
+  let dir: string;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-launch-'));
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  it('applies files in order, then --launch-arg pairs', () => {
+    const file = path.join(dir, 'extra.json');
+    fs.writeFileSync(file, JSON.stringify({ sourceMaps: false, env: { A: 'a', B: 'b' } }));
+
+    expect(buildLaunchOverrides([file], ['sourceMaps=true', 'env.B=beta'])).toEqual({
+      sourceMaps: true,
+      env: { A: 'a', B: 'beta' },
+    });
+  });
+
+  it('rejects files that are not a JSON object', () => {
+    const file = path.join(dir, 'extra.json');
+    fs.writeFileSync(file, '["sourceMaps"]');
+
+    expect(() => buildLaunchOverrides([file], [])).toThrow('expected a JSON object');
+    expect(() => buildLaunchOverrides([path.join(dir, 'missing.json')], [])).toThrow(
+      'Invalid --launch-json'
+    );
+  });
+});
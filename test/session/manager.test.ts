@@ -2,8 +2,15 @@
  * Unit tests for DebugSession manager
  */
 
-import { describe, it, expect, vi } from 'vitest';
-import { DebugSession } from '../../src/session/manager.js';
+import { describe, it, expect, vi, afterEach } from 'vitest';
+import { PassThrough } from 'node:stream';
+import {
+  DebugSession,
+  parseOnHitAction,
+  type SessionConfig,
+  type SigintMode,
+} from '../../src/session/manager.js';
+import { armTrigger } from '../../src/session/trigger.js';
 import type { AdapterConfig } from '../../src/adapters/base.js';
 import { OutputFormatter } from '../../src/output/formatter.js';
 
@@ -107,4 +114,1373 @@ describe('DebugSession', () => {
       expect(mockFormatter.sessionEnd).toHaveBeenCalledTimes(1);
     });
   });
+
+  describe('termination', () => {
+    function createSession(config: Partial<SessionConfig> = {}) {
+      const summaries: Array<Record<string, unknown>> = [];
+      const formatter = {
+        sessionStart: vi.fn(),
+        sessionEnd: vi.fn((summary) => summaries.push(summary)),
+        emit: vi.fn(),
+        createEvent: vi.fn(),
+        error: vi.fn(),
+      } as unknown as OutputFormatter;
+      const session = new DebugSession(
+        { adapter: mockAdapter, breakpoints: [], ...config },
+        formatter
+      );
+      // @ts-expect-error accessing private field for testing
+      session.startTime = Date.now();
+      return { session, summaries };
+    }
+
+    it('reports a program that exited on its own', () => {
+      const { session, summaries } = createSession();
+
+      // @ts-expect-error accessing private method for testing
+      session.handleExited({ exitCode: 2 });
+      // @ts-expect-error accessing private method for testing
+      session.handleTerminated();
+
+      expect(summaries[0].termination).toMatchObject({
+        reason: 'exited',
+        exitCode: 2,
+        timedOut: false,
+        killed: false,
+        wallTimeMs: expect.any(Number),
+      });
+    });
+
+    it('reports a timeout as killed', () => {
+      const { session, summaries } = createSession({ timeout: 5000 });
+
+      // @ts-expect-error accessing private method for testing
+      session.handleTimeout();
+
+      expect(summaries[0].termination).toMatchObject({
+        reason: 'timeout',
+        exitCode: null,
+        timedOut: true,
+        killed: true,
+      });
+    });
+
+    it('keeps the interrupt as the reason when the session is stopped for it', async () => {
+      const { session, summaries } = createSession();
+
+      await session.interrupt('SIGTERM');
+
+      expect(summaries[0].termination).toMatchObject({ reason: 'interrupted', killed: true });
+    });
+
+    it('never reports an attached program as killed', () => {
+      const { session, summaries } = createSession({ attach: true, pid: 1234 });
+
+      session.stop();
+
+      expect(summaries[0].termination).toMatchObject({ reason: 'stopped', killed: false });
+    });
+
+    it('fails a program that ran without its resource limits unless they are best effort', () => {
+      const limited = createSession({ resourceLimits: { cpuSeconds: 5 } });
+      // @ts-expect-error accessing private method for testing
+      limited.session.handleTerminated();
+      expect(limited.summaries[0].termination).toMatchObject({ reason: 'error' });
+      // @ts-expect-error accessing private field for testing
+      expect(limited.session.sessionError?.message).toContain('--limits-best-effort');
+
+      const bestEffort = createSession({
+        resourceLimits: { cpuSeconds: 5 },
+        limitsBestEffort: true,
+      });
+      // @ts-expect-error accessing private method for testing
+      bestEffort.session.handleTerminated();
+      expect(bestEffort.summaries[0].termination).toMatchObject({ reason: 'exited' });
+    });
+
+    it('fails when the resource limits cannot be applied', async () => {
+      const { session, summaries } = createSession({ resourceLimits: { cpuSeconds: 5 } });
+
+      // A PID far above any default pid_max, so never alive
+      // @ts-expect-error accessing private method for testing
+      await session.limitDebuggee(2 ** 30);
+
+      expect(summaries[0].termination).toMatchObject({ reason: 'error' });
+      // @ts-expect-error accessing private field for testing
+      expect(session.sessionError?.message).toMatch(/^Failed to apply resource limits: /);
+    });
+  });
+
+  describe('hit path and --max-hits', () => {
+    function createFormatter(sessionEndCalls: unknown[]): OutputFormatter {
+      return {
+        sessionStart: vi.fn(),
+        sessionStartAttach: vi.fn(),
+        sessionEnd: vi.fn((summary) => {
+          sessionEndCalls.push(summary);
+        }),
+        emit: vi.fn(),
+        createEvent: vi.fn(),
+        error: vi.fn(),
+        programOutput: vi.fn(),
+      } as unknown as OutputFormatter;
+    }
+
+    function hit(id: number, file: string, line: number, threadId: number = 1) {
+      return {
+        type: 'breakpoint_hit' as const,
+        timestamp: new Date().toISOString(),
+        id,
+        threadId,
+        location: { file, line, function: 'fn' },
+        stackTrace: [],
+        locals: {},
+      };
+    }
+
+    it('ends the session with the ordered hit path once max hits is reached', async () => {
+      const sessionEndCalls: Array<Record<string, unknown>> = [];
+      const session = new DebugSession(
+        { adapter: mockAdapter, breakpoints: [], maxHits: 2 },
+        createFormatter(sessionEndCalls)
+      );
+      const continueMock = vi.fn();
+      // @ts-expect-error accessing private field for testing
+      session.client = { continue: continueMock };
+
+      // @ts-expect-error accessing private field for testing
+      session.breakpointsHit = 1;
+      // @ts-expect-error accessing private method for testing
+      session.recordHit(hit(2, 'validate.ts', 10));
+      // @ts-expect-error accessing private method for testing
+      await session.resumeAfterHit(1);
+      expect(continueMock).toHaveBeenCalledTimes(1);
+      expect(sessionEndCalls).toHaveLength(0);
+
+      // @ts-expect-error accessing private field for testing
+      session.breakpointsHit = 2;
+      // @ts-expect-error accessing private method for testing
+      session.recordHit(hit(1, 'inventory.ts', 42, 3));
+      // @ts-expect-error accessing private method for testing
+      await session.resumeAfterHit(3);
+
+      expect(continueMock).toHaveBeenCalledTimes(1);
+      expect(sessionEndCalls).toHaveLength(1);
+      const summary = sessionEndCalls[0];
+      expect(summary.maxHitsReached).toBe(true);
+      expect(summary.path).toEqual([
+        expect.objectContaining({ hit: 1, breakpointId: 2, threadId: 1 }),
+        expect.objectContaining({ hit: 2, breakpointId: 1, threadId: 3 }),
+      ]);
+    });
+
+    it('omits the path when no breakpoint was hit', () => {
+      const sessionEndCalls: Array<Record<string, unknown>> = [];
+      const session = new DebugSession(
+        { adapter: mockAdapter, breakpoints: [] },
+        createFormatter(sessionEndCalls)
+      );
+
+      // @ts-expect-error accessing private method for testing
+      session.endSession();
+
+      expect(sessionEndCalls[0].path).toBeUndefined();
+      expect(sessionEndCalls[0].maxHitsReached).toBeUndefined();
+    });
+  });
+
+  describe('pauseOnHit', () => {
+    function createSession() {
+      const session = new DebugSession(
+        { adapter: mockAdapter, breakpoints: [], pauseOnHit: true },
+        {
+          sessionEnd: vi.fn(),
+          emit: vi.fn(),
+          createEvent: vi.fn(),
+          error: vi.fn(),
+        } as unknown as OutputFormatter
+      );
+      const client = {
+        continue: vi.fn(),
+        evaluate: vi.fn(async () => ({ result: '42', type: 'number', variablesReference: 0 })),
+      };
+      // @ts-expect-error accessing private field for testing
+      session.client = client;
+      return { session, client };
+    }
+
+    it('stays paused after a hit until continue() is called', async () => {
+      const { session, client } = createSession();
+      // @ts-expect-error accessing private field for testing
+      session.lastStop = { threadId: 4, frameId: 1000 };
+
+      // @ts-expect-error accessing private method for testing
+      await session.resumeAfterHit(4);
+      expect(session.isPaused()).toBe(true);
+      expect(client.continue).not.toHaveBeenCalled();
+
+      await expect(session.evaluate('total')).resolves.toEqual({
+        result: '42',
+        type: 'number',
+        variablesReference: 0,
+      });
+      expect(client.evaluate).toHaveBeenCalledWith({
+        expression: 'total',
+        frameId: 1000,
+        context: 'repl',
+      });
+
+      await session.continue();
+      expect(client.continue).toHaveBeenCalledWith({ threadId: 4 });
+      expect(session.isPaused()).toBe(false);
+    });
+
+    it('rejects continue() and evaluate() when not paused', async () => {
+      const { session } = createSession();
+
+      await expect(session.continue()).rejects.toThrow('not paused');
+      await expect(session.evaluate('x')).rejects.toThrow('not paused');
+    });
+  });
+
+  describe('interrupt', () => {
+    afterEach(() => {
+      vi.restoreAllMocks();
+    });
+
+    function createSession(sigintMode?: SigintMode) {
+      const formatter = {
+        sessionEnd: vi.fn(),
+        emit: vi.fn(),
+        createEvent: vi.fn(),
+        error: vi.fn(),
+      };
+      const session = new DebugSession(
+        { adapter: mockAdapter, breakpoints: [], sigintMode },
+        formatter as unknown as OutputFormatter
+      );
+      const client = {
+        threads: vi.fn(async () => ({ threads: [{ id: 7, name: 'main' }] })),
+        pause: vi.fn(),
+      };
+      // @ts-expect-error accessing private field for testing
+      session.client = client;
+      // @ts-expect-error accessing private field for testing
+      session.state = 'running';
+      // @ts-expect-error accessing private field for testing
+      session.debuggeePid = 4242;
+      const actions = () =>
+        formatter.emit.mock.calls
+          .map(([event]) => event)
+          .filter((event) => event.type === 'interrupted')
+          .map((event) => event.action);
+      return { session, client, formatter, actions };
+    }
+
+    it('snapshots, then forwards SIGINT, then tears down', async () => {
+      const kill = vi.spyOn(process, 'kill').mockImplementation(() => true);
+      const { session, client, formatter, actions } = createSession();
+
+      await expect(session.interrupt()).resolves.toBe('snapshot');
+      expect(client.pause).toHaveBeenCalledWith(7);
+
+      await expect(session.interrupt()).resolves.toBe('forward');
+      expect(kill).toHaveBeenCalledWith(4242, 'SIGINT');
+
+      await expect(session.interrupt()).resolves.toBe('stop');
+      expect(formatter.sessionEnd).toHaveBeenCalledTimes(1);
+
+      await expect(session.interrupt()).resolves.toBe('kill');
+      expect(actions()).toEqual(['snapshot', 'forward', 'stop']);
+    });
+
+    it('falls through to the next action when one is not possible', async () => {
+      const { session, client, actions } = createSession();
+      // @ts-expect-error accessing private field for testing
+      session.state = 'configuring';
+      // @ts-expect-error accessing private field for testing
+      session.debuggeePid = undefined;
+
+      await expect(session.interrupt()).resolves.toBe('stop');
+      expect(client.pause).not.toHaveBeenCalled();
+      expect(actions()).toEqual(['stop']);
+    });
+
+    it('tears down immediately in stop mode and on SIGTERM', async () => {
+      const stopMode = createSession('stop');
+      await expect(stopMode.session.interrupt()).resolves.toBe('stop');
+      expect(stopMode.client.pause).not.toHaveBeenCalled();
+
+      const sigterm = createSession('snapshot');
+      await expect(sigterm.session.interrupt('SIGTERM')).resolves.toBe('stop');
+      expect(sigterm.formatter.sessionEnd).toHaveBeenCalledTimes(1);
+    });
+
+    it('captures the pause stop as an interrupt_snapshot and resumes', async () => {
+      const { session, formatter } = createSession();
+      const client = {
+        threads: vi.fn(async () => ({ threads: [{ id: 7, name: 'main' }] })),
+        pause: vi.fn(),
+        continue: vi.fn(),
+        stackTrace: vi.fn(async () => ({
+          stackFrames: [{ id: 1000, name: 'loop', line: 12, column: 1, source: { path: 'app.js' } }],
+        })),
+      };
+      // @ts-expect-error accessing private field for testing
+      session.client = client;
+      // @ts-expect-error accessing private field for testing
+      session.config.captureLocals = false;
+
+      await session.interrupt();
+      // @ts-expect-error accessing private method for testing
+      await session.handleStopped({ reason: 'pause', threadId: 7 });
+
+      const snapshot = formatter.emit.mock.calls
+        .map(([event]) => event)
+        .find((event) => event.type === 'interrupt_snapshot');
+      expect(snapshot).toMatchObject({
+        threadId: 7,
+        location: { file: 'app.js', line: 12, function: 'loop' },
+        locals: {},
+      });
+      expect(client.continue).toHaveBeenCalledWith({ threadId: 7 });
+    });
+  });
+
+  describe('untilCrash', () => {
+    function createSession() {
+      const formatter = {
+        sessionEnd: vi.fn(),
+        emit: vi.fn(),
+        error: vi.fn(),
+        programOutput: vi.fn(),
+      };
+      const session = new DebugSession(
+        { adapter: mockAdapter, breakpoints: [], untilCrash: true, captureLocals: false },
+        formatter as unknown as OutputFormatter
+      );
+      const client = {
+        continue: vi.fn(),
+        threads: vi.fn(async () => ({
+          threads: [
+            { id: 7, name: 'main' },
+            { id: 8, name: 'worker' },
+          ],
+        })),
+        stackTrace: vi.fn(async ({ threadId }: { threadId: number }) => ({
+          stackFrames:
+            threadId === 7
+              ? [{ id: 1000, name: 'parse', line: 3, column: 9, source: { path: 'parse.js' } }]
+              : [{ id: 2000, name: 'poll', line: 20, column: 1, source: { path: 'queue.js' } }],
+        })),
+      };
+      // @ts-expect-error accessing private field for testing
+      session.client = client;
+      // @ts-expect-error accessing private field for testing
+      session.startTime = Date.now();
+      const report = () =>
+        formatter.emit.mock.calls
+          .map(([event]) => event)
+          .find((event) => event.type === 'crash_report');
+      return { session, client, formatter, report };
+    }
+
+    it('reports all threads and recent output, then ends the session', async () => {
+      const { session, client, formatter, report } = createSession();
+
+      // @ts-expect-error accessing private method for testing
+      session.handleOutput({ category: 'stdout', output: 'loading config\nparsing input\n' });
+      // @ts-expect-error accessing private method for testing
+      session.handleOutput({ category: 'console', output: 'Debugger attached.\n' });
+      // @ts-expect-error accessing private method for testing
+      await session.handleStopped({
+        reason: 'exception',
+        threadId: 7,
+        text: 'SyntaxError',
+        description: 'Unexpected token } in JSON',
+      });
+
+      expect(report()).toMatchObject({
+        reason: 'exception',
+        threadId: 7,
+        exception: { type: 'SyntaxError', message: 'Unexpected token } in JSON' },
+        location: { file: 'parse.js', line: 3, function: 'parse' },
+        threads: [
+          { id: 7, name: 'main', faulting: true, stackTrace: [{ function: 'parse' }] },
+          { id: 8, name: 'worker', stackTrace: [{ function: 'poll', file: 'queue.js' }] },
+        ],
+        recentOutput: ['loading config', 'parsing input'],
+      });
+      expect(client.continue).not.toHaveBeenCalled();
+      expect(formatter.sessionEnd).toHaveBeenCalledWith(
+        expect.objectContaining({ crashed: true, exceptionsCaught: 1 })
+      );
+    });
+
+    it('keeps only the most recent output lines', async () => {
+      const { session, report } = createSession();
+      for (let i = 1; i <= 60; i++) {
+        // @ts-expect-error accessing private method for testing
+        session.handleOutput({ category: 'stderr', output: `line ${i}\n` });
+      }
+      // @ts-expect-error accessing private method for testing
+      await session.handleStopped({ reason: 'signal', threadId: 7, description: 'SIGSEGV' });
+
+      const { recentOutput } = report();
+      expect(recentOutput).toHaveLength(50);
+      expect(recentOutput[0]).toBe('line 11');
+      expect(recentOutput[49]).toBe('line 60');
+    });
+  });
+
+  describe('continueIf', () => {
+    function createSession(orderId: () => string) {
+      const formatter = {
+        sessionEnd: vi.fn(),
+        emit: vi.fn(),
+        error: vi.fn(),
+      };
+      const session = new DebugSession(
+        {
+          adapter: mockAdapter,
+          breakpoints: [],
+          captureLocals: false,
+          continueIf: ['order.order_id != "ORD-002"'],
+        },
+        formatter as unknown as OutputFormatter
+      );
+      const client = {
+        continue: vi.fn(),
+        stackTrace: vi.fn(async () => ({
+          stackFrames: [{ id: 1, name: 'process', line: 88, source: { path: 'orders.rs' } }],
+        })),
+      };
+      // @ts-expect-error accessing private field for testing
+      session.client = client;
+      // @ts-expect-error accessing private field for testing
+      session.variableInspector = {
+        evaluate: vi.fn(async () => {
+          const id = orderId();
+          return { result: id === 'error' ? '' : String(id !== 'ORD-002') };
+        }),
+      };
+      // @ts-expect-error accessing private field for testing
+      session.startTime = Date.now();
+      const hits = () =>
+        formatter.emit.mock.calls
+          .map(([event]) => event)
+          .filter((event) => event.type === 'breakpoint_hit');
+      return { session, client, hits };
+    }
+
+    it('resumes without a snapshot while a rule holds', async () => {
+      const ids = ['ORD-001', 'ORD-002', 'ORD-003'];
+      const { session, client, hits } = createSession(() => ids.shift()!);
+
+      for (let i = 0; i < 3; i++) {
+        // @ts-expect-error accessing private method for testing
+        await session.handleStopped({ reason: 'breakpoint', threadId: 1 });
+      }
+
+      expect(hits()).toHaveLength(1);
+      expect(client.continue).toHaveBeenCalledTimes(3);
+      // @ts-expect-error accessing private method for testing
+      expect(session.buildSummary()).toMatchObject({ breakpointsHit: 1, breakpointsSkipped: 2 });
+    });
+
+    it('captures the stop when a rule fails to evaluate', async () => {
+      const { session, hits } = createSession(() => 'ORD-001');
+      // @ts-expect-error accessing private field for testing
+      session.variableInspector.evaluate = vi.fn(async () => {
+        throw new Error('no such variable: order');
+      });
+
+      // @ts-expect-error accessing private method for testing
+      await session.handleStopped({ reason: 'breakpoint', threadId: 1 });
+
+      expect(hits()).toHaveLength(1);
+    });
+  });
+
+  describe('evalAfterStep', () => {
+    it('steps over the breakpoint line before evaluating', async () => {
+      const formatter = { sessionEnd: vi.fn(), emit: vi.fn(), error: vi.fn() };
+      const session = new DebugSession(
+        {
+          adapter: mockAdapter,
+          breakpoints: [],
+          captureLocals: false,
+          evaluations: ['total'],
+          evalAfterStep: true,
+        },
+        formatter as unknown as OutputFormatter
+      );
+      const lines = [88, 89];
+      const client = {
+        continue: vi.fn(),
+        next: vi.fn(),
+        threads: vi.fn(async () => ({ threads: [] })),
+        stackTrace: vi.fn(async () => ({
+          stackFrames: [
+            { id: 1, name: 'process', line: lines.shift(), source: { path: 'orders.rs' } },
+          ],
+        })),
+      };
+      const evaluateExpressions = vi.fn(async () => ({ total: { result: '42' } }));
+      // @ts-expect-error accessing private field for testing
+      session.client = client;
+      // @ts-expect-error accessing private field for testing
+      session.variableInspector = { evaluateExpressions };
+      // @ts-expect-error accessing private field for testing
+      session.startTime = Date.now();
+
+      // @ts-expect-error accessing private method for testing
+      await session.handleStopped({ reason: 'breakpoint', threadId: 1, hitBreakpointIds: [1] });
+      expect(client.next).toHaveBeenCalledTimes(1);
+      expect(evaluateExpressions).not.toHaveBeenCalled();
+
+      // @ts-expect-error accessing private method for testing
+      await session.handleStopped({ reason: 'step', threadId: 1 });
+      const hits = formatter.emit.mock.calls
+        .map(([event]) => event)
+        .filter((event) => event.type === 'breakpoint_hit');
+      expect(hits).toHaveLength(1);
+      expect(hits[0].location.line).toBe(88);
+      expect(hits[0].evaluations).toEqual({ total: { result: '42' } });
+    });
+  });
+
+  describe('skipToHit', () => {
+    it('resumes earlier hits without a stack trace and keeps their numbering', async () => {
+      const formatter = { sessionEnd: vi.fn(), emit: vi.fn(), error: vi.fn() };
+      const session = new DebugSession(
+        { adapter: mockAdapter, breakpoints: [], captureLocals: false, skipToHit: 3 },
+        formatter as unknown as OutputFormatter
+      );
+      const client = {
+        continue: vi.fn(),
+        stackTrace: vi.fn(async () => ({
+          stackFrames: [{ id: 1, name: 'process', line: 88, source: { path: 'orders.rs' } }],
+        })),
+      };
+      // @ts-expect-error accessing private field for testing
+      session.client = client;
+      // @ts-expect-error accessing private field for testing
+      session.startTime = Date.now();
+
+      for (let i = 0; i < 4; i++) {
+        // @ts-expect-error accessing private method for testing
+        await session.handleStopped({ reason: 'breakpoint', threadId: 1 });
+      }
+
+      const hits = formatter.emit.mock.calls.filter(([event]) => event.type === 'breakpoint_hit');
+      expect(hits).toHaveLength(2);
+      expect(client.stackTrace).toHaveBeenCalledTimes(2);
+      expect(client.continue).toHaveBeenCalledTimes(4);
+      // @ts-expect-error accessing private method for testing
+      const summary = session.buildSummary();
+      expect(summary).toMatchObject({ breakpointsHit: 2, breakpointsSkipped: 2 });
+      expect(summary.path?.map((entry) => entry.hit)).toEqual([3, 4]);
+    });
+  });
+
+  describe('hitWindows', () => {
+    it('captures a breakpoint only within the hits of its counter', async () => {
+      const formatter = { sessionEnd: vi.fn(), emit: vi.fn(), error: vi.fn() };
+      const session = new DebugSession(
+        {
+          adapter: mockAdapter,
+          breakpoints: [],
+          captureLocals: false,
+          hitWindows: [
+            {
+              label: 'parse',
+              after: { counter: 'iteration', count: 2 },
+              until: { counter: 'iteration', count: 3 },
+            },
+            { label: 'iteration', until: { counter: 'iteration', count: 0 } },
+          ],
+        },
+        formatter as unknown as OutputFormatter
+      );
+      const client = {
+        continue: vi.fn(),
+        stackTrace: vi.fn(async () => ({
+          stackFrames: [{ id: 1, name: 'parse', line: 42, source: { path: 'parse.ts' } }],
+        })),
+      };
+      // @ts-expect-error accessing private field for testing
+      session.client = client;
+      // @ts-expect-error accessing private field for testing
+      session.breakpointManager = {
+        labelsFor: (_location: unknown, ids: number[]) => [ids[0] === 1 ? 'iteration' : 'parse'],
+        labelFor: () => 'parse',
+        checkSource: async () => {},
+      };
+      // @ts-expect-error accessing private field for testing
+      session.startTime = Date.now();
+
+      for (let i = 0; i < 4; i++) {
+        // @ts-expect-error accessing private method for testing
+        await session.handleStopped({ reason: 'breakpoint', threadId: 1, hitBreakpointIds: [1] });
+        // @ts-expect-error accessing private method for testing
+        await session.handleStopped({ reason: 'breakpoint', threadId: 1, hitBreakpointIds: [2] });
+      }
+
+      const hits = formatter.emit.mock.calls.filter(([event]) => event.type === 'breakpoint_hit');
+      expect(hits).toHaveLength(2);
+      expect(client.continue).toHaveBeenCalledTimes(8);
+      // @ts-expect-error accessing private method for testing
+      expect(session.buildSummary()).toMatchObject({ breakpointsHit: 2, breakpointsSkipped: 6 });
+    });
+  });
+
+  describe('armOnOutput', () => {
+    it('holds breakpoints back until the program prints the line', async () => {
+      const formatter = { emit: vi.fn(), programOutput: vi.fn() };
+      const session = new DebugSession(
+        { adapter: mockAdapter, breakpoints: [], armOnOutput: /listening on \d+/ },
+        formatter as unknown as OutputFormatter
+      );
+      const breakpointManager = { setAllBreakpoints: vi.fn(async () => {}) };
+      // @ts-expect-error accessing private field for testing
+      session.breakpointManager = breakpointManager;
+
+      // @ts-expect-error accessing private method for testing
+      await session.configureBreakpoints();
+      // @ts-expect-error accessing private method for testing
+      session.handleOutput({ category: 'stdout', output: 'warming up\nServer listening' });
+      expect(breakpointManager.setAllBreakpoints).not.toHaveBeenCalled();
+
+      // @ts-expect-error accessing private method for testing
+      session.handleOutput({ category: 'stdout', output: ' on 8080\n' });
+      // @ts-expect-error accessing private method for testing
+      session.handleOutput({ category: 'stdout', output: 'listening on 9090\n' });
+      expect(breakpointManager.setAllBreakpoints).toHaveBeenCalledTimes(1);
+      expect(formatter.emit).toHaveBeenCalledTimes(1);
+      expect(formatter.emit.mock.calls[0][0]).toMatchObject({
+        type: 'breakpoints_armed',
+        pattern: 'listening on \\d+',
+        line: 'Server listening on 8080',
+      });
+    });
+  });
+
+  describe('aggregate', () => {
+    it('folds hits into statistics without emitting breakpoint_hit', async () => {
+      const formatter = {
+        sessionEnd: vi.fn(),
+        emit: vi.fn(),
+        error: vi.fn(),
+      };
+      const session = new DebugSession(
+        {
+          adapter: mockAdapter,
+          breakpoints: [],
+          captureLocals: false,
+          aggregates: [{ expression: 'item.unit_price', stats: ['min', 'max', 'mean'] }],
+        },
+        formatter as unknown as OutputFormatter
+      );
+      const prices = ['4.5', '12', '1.5'];
+      const client = {
+        continue: vi.fn(),
+        stackTrace: vi.fn(async () => ({
+          stackFrames: [{ id: 1, name: 'price', line: 42, source: { path: 'pricing.ts' } }],
+        })),
+      };
+      // @ts-expect-error accessing private field for testing
+      session.client = client;
+      // @ts-expect-error accessing private field for testing
+      session.variableInspector = { evaluate: vi.fn(async () => ({ result: prices.shift()! })) };
+      // @ts-expect-error accessing private field for testing
+      session.startTime = Date.now();
+
+      for (let i = 0; i < 3; i++) {
+        // @ts-expect-error accessing private method for testing
+        await session.handleStopped({ reason: 'breakpoint', threadId: 1 });
+      }
+
+      expect(formatter.emit).not.toHaveBeenCalled();
+      expect(client.continue).toHaveBeenCalledTimes(3);
+      // @ts-expect-error accessing private method for testing
+      const summary = session.buildSummary();
+      expect(summary.breakpointsHit).toBe(3);
+      expect(summary.aggregates).toEqual([
+        { expression: 'item.unit_price', count: 3, min: 1.5, max: 12, mean: 6 },
+      ]);
+    });
+  });
+
+  describe('adapter startup failures', () => {
+    it("reports the adapter's recent output when it exits before the program runs", () => {
+      const formatter = {
+        sessionEnd: vi.fn(),
+        emit: vi.fn(),
+        error: vi.fn(),
+      };
+      const session = new DebugSession(
+        { adapter: mockAdapter, breakpoints: [] },
+        formatter as unknown as OutputFormatter
+      );
+      // @ts-expect-error accessing private field for testing
+      session.startTime = Date.now();
+      // @ts-expect-error accessing private field for testing
+      session.phase = { name: 'initializing', startedAt: Date.now() };
+
+      // @ts-expect-error accessing private method for testing
+      session.recordAdapterOutput('Traceback (most recent call last):\n');
+      // @ts-expect-error accessing private method for testing
+      session.recordAdapterOutput('ModuleNotFoundError: No module named debugpy\n');
+      // @ts-expect-error accessing private method for testing
+      session.handleAdapterExit();
+
+      expect(formatter.error).toHaveBeenCalledWith(
+        'Debug adapter failed to start',
+        'Debug adapter exited while initializing\nAdapter output:\n' +
+          'Traceback (most recent call last):\nModuleNotFoundError: No module named debugpy'
+      );
+      const [summary] = formatter.sessionEnd.mock.calls[0];
+      expect(summary.termination.reason).toBe('adapter_exited');
+      // @ts-expect-error accessing private field for testing
+      expect(session.sessionError?.message).toBe('Debug adapter exited while initializing');
+    });
+  });
+
+  describe('stepFilters', () => {
+    it('steps back out of filtered namespaces without counting the step', async () => {
+      const formatter = {
+        sessionEnd: vi.fn(),
+        emit: vi.fn(),
+        error: vi.fn(),
+      };
+      const session = new DebugSession(
+        {
+          adapter: mockAdapter,
+          breakpoints: [],
+          captureLocals: false,
+          captureEachStep: true,
+          stepFilters: ['Serilog'],
+        },
+        formatter as unknown as OutputFormatter
+      );
+      const frames = ['Serilog.Core.Logger.Write()', 'MyApp.Orders.Checkout()'];
+      const client = {
+        next: vi.fn(),
+        stepOut: vi.fn(),
+        continue: vi.fn(),
+        threads: vi.fn(async () => ({ threads: [] })),
+        stackTrace: vi.fn(async () => ({
+          stackFrames: [{ id: 1, name: frames.shift()!, line: 12, source: { path: 'a.cs' } }],
+        })),
+      };
+      // @ts-expect-error accessing private field for testing
+      session.client = client;
+      // @ts-expect-error accessing private field for testing
+      session.isStepping = true;
+      // @ts-expect-error accessing private field for testing
+      session.remainingSteps = 1;
+
+      // @ts-expect-error accessing private method for testing
+      await session.handleStopped({ reason: 'step', threadId: 1 });
+      expect(client.stepOut).toHaveBeenCalledWith({ threadId: 1 });
+      expect(formatter.emit).not.toHaveBeenCalled();
+
+      // @ts-expect-error accessing private method for testing
+      await session.handleStopped({ reason: 'step', threadId: 1 });
+      expect(formatter.emit).toHaveBeenCalledWith(
+        expect.objectContaining({ type: 'step_completed', location: expect.anything() })
+      );
+      expect(client.continue).toHaveBeenCalledOnce();
+    });
+  });
+
+  describe('skipFiles', () => {
+    it('leaves skipped frames out of stacks and reports the globs in the summary', async () => {
+      const formatter = {
+        sessionEnd: vi.fn(),
+        emit: vi.fn(),
+        error: vi.fn(),
+      };
+      const session = new DebugSession(
+        { adapter: mockAdapter, breakpoints: [], captureLocals: false },
+        formatter as unknown as OutputFormatter
+      );
+      const skipped = { path: '/app/node_modules/express/lib/router.js' };
+      const client = {
+        continue: vi.fn(),
+        threads: vi.fn(async () => ({ threads: [] })),
+        stackTrace: vi.fn(async () => ({
+          stackFrames: [
+            { id: 1, name: 'handler', line: 12, column: 1, source: { path: '/app/src/a.ts' } },
+            {
+              id: 2,
+              name: 'handle',
+              line: 95,
+              column: 1,
+              source: { ...skipped, presentationHint: 'deemphasize' },
+            },
+            { id: 3, name: 'main', line: 4, column: 1, source: { path: '/app/src/main.ts' } },
+          ],
+        })),
+      };
+      // @ts-expect-error accessing private field for testing
+      session.client = client;
+      // @ts-expect-error accessing private field for testing
+      session.startTime = Date.now();
+      const skipFiles = ['<node_internals>/**', '**/node_modules/**'];
+      // @ts-expect-error accessing private field for testing
+      session.launchArgs = { skipFiles };
+
+      // @ts-expect-error accessing private method for testing
+      await session.handleStopped({ reason: 'breakpoint', threadId: 1 });
+
+      const hit = formatter.emit.mock.calls
+        .map(([event]) => event)
+        .find((event) => event.type === 'breakpoint_hit');
+      expect(hit.stackTrace.map((frame: { function: string }) => frame.function)).toEqual([
+        'handler',
+        'main',
+      ]);
+      // @ts-expect-error accessing private method for testing
+      expect(session.buildSummary().skipFiles).toEqual(skipFiles);
+    });
+  });
+
+  describe('onHit restart-frame', () => {
+    function createSession(supportsRestartFrame = true) {
+      const formatter = {
+        sessionEnd: vi.fn(),
+        emit: vi.fn(),
+        error: vi.fn(),
+      };
+      const session = new DebugSession(
+        {
+          adapter: mockAdapter,
+          breakpoints: [],
+          captureLocals: false,
+          assignments: ['order.coupon="SPRING"'],
+          onHit: { action: 'restart-frame' },
+        },
+        formatter as unknown as OutputFormatter
+      );
+      let line = 27;
+      const client = {
+        continue: vi.fn(),
+        customRequest: vi.fn(async () => ({})),
+        evaluate: vi.fn(async () => ({ result: '"SPRING"', variablesReference: 0 })),
+        stackTrace: vi.fn(async () => ({
+          stackFrames: [
+            { id: 1, name: 'price', line, source: { path: 'pricing.js' } },
+            { id: 2, name: 'main', line: 3, source: { path: 'app.js' } },
+          ],
+        })),
+      };
+      // @ts-expect-error accessing private field for testing
+      session.client = client;
+      // @ts-expect-error accessing private field for testing
+      session.capabilities = { supportsRestartFrame };
+      // @ts-expect-error accessing private field for testing
+      session.startTime = Date.now();
+      const events = () => formatter.emit.mock.calls.map(([event]) => event);
+      const stopAt = async (reason: string, at: number) => {
+        line = at;
+        // @ts-expect-error accessing private method for testing
+        await session.handleStopped({ reason, threadId: 1 });
+      };
+      return { client, formatter, events, stopAt };
+    }
+
+    it('restarts once, applies --set at the frame start and captures the re-run', async () => {
+      const { client, events, stopAt } = createSession();
+
+      await stopAt('breakpoint', 27);
+      expect(client.customRequest).toHaveBeenCalledWith('restartFrame', { frameId: 1 });
+      expect(client.continue).not.toHaveBeenCalled();
+
+      await stopAt('restart', 19);
+      expect(client.evaluate).toHaveBeenCalledWith({
+        expression: 'order.coupon = "SPRING"',
+        frameId: 1,
+        context: 'repl',
+      });
+
+      await stopAt('breakpoint', 27);
+      expect(client.customRequest).toHaveBeenCalledTimes(1);
+      expect(client.continue).toHaveBeenCalledTimes(2);
+      expect(events().map((event) => [event.type, event.rerun])).toEqual([
+        ['breakpoint_hit', undefined],
+        ['frame_restarted', undefined],
+        ['breakpoint_hit', true],
+      ]);
+      expect(events()[0].assignments).toBeUndefined();
+      expect(events()[1]).toMatchObject({
+        location: { line: 19 },
+        assignments: { 'order.coupon': { value: '"SPRING"' } },
+      });
+    });
+
+    it('reports adapters without restartFrame once and carries on', async () => {
+      const { client, formatter, stopAt } = createSession(false);
+
+      await stopAt('breakpoint', 27);
+      await stopAt('breakpoint', 27);
+
+      expect(client.customRequest).not.toHaveBeenCalled();
+      expect(client.continue).toHaveBeenCalledTimes(2);
+      expect(formatter.error).toHaveBeenCalledTimes(1);
+    });
+  });
+
+  describe('onHit goto', () => {
+    function createSession(supportsGotoTargetsRequest = true) {
+      const formatter = {
+        sessionEnd: vi.fn(),
+        emit: vi.fn(),
+        error: vi.fn(),
+      };
+      const session = new DebugSession(
+        {
+          adapter: mockAdapter,
+          breakpoints: [],
+          captureLocals: false,
+          cwd: '/app',
+          onHit: { action: 'goto', file: 'src/main.rs', line: 260 },
+        },
+        formatter as unknown as OutputFormatter
+      );
+      const client = {
+        continue: vi.fn(),
+        customRequest: vi.fn(async (command: string) =>
+          command === 'gotoTargets' ? { targets: [{ id: 9, label: 'main.rs:260', line: 260 }] } : {}
+        ),
+        stackTrace: vi.fn(async () => ({
+          stackFrames: [{ id: 1, name: 'main', line: 250, source: { path: '/app/src/main.rs' } }],
+        })),
+      };
+      // @ts-expect-error accessing private field for testing
+      session.client = client;
+      // @ts-expect-error accessing private field for testing
+      session.capabilities = { supportsGotoTargetsRequest };
+      // @ts-expect-error accessing private field for testing
+      session.startTime = Date.now();
+      const events = () => formatter.emit.mock.calls.map(([event]) => event);
+      return { session, client, formatter, events };
+    }
+
+    it('captures the hit, jumps to the target line and continues from there', async () => {
+      const { session, client, events } = createSession();
+
+      // @ts-expect-error accessing private method for testing
+      await session.handleStopped({ reason: 'breakpoint', threadId: 1 });
+      expect(client.customRequest).toHaveBeenCalledWith('gotoTargets', {
+        source: { path: '/app/src/main.rs' },
+        line: 260,
+      });
+      expect(client.customRequest).toHaveBeenCalledWith('goto', { threadId: 1, targetId: 9 });
+      expect(client.continue).not.toHaveBeenCalled();
+
+      // @ts-expect-error accessing private method for testing
+      await session.handleStopped({ reason: 'goto', threadId: 1 });
+      expect(client.continue).toHaveBeenCalledTimes(1);
+      expect(events().map((event) => event.type)).toEqual(['breakpoint_hit', 'execution_moved']);
+      expect(events()[1]).toMatchObject({
+        from: { line: 250 },
+        to: { file: 'src/main.rs', line: 260 },
+      });
+    });
+
+    it('reports adapters without goto support once and carries on', async () => {
+      const { session, client, formatter } = createSession(false);
+
+      // @ts-expect-error accessing private method for testing
+      await session.handleStopped({ reason: 'breakpoint', threadId: 1 });
+      // @ts-expect-error accessing private method for testing
+      await session.handleStopped({ reason: 'breakpoint', threadId: 1 });
+
+      expect(client.customRequest).not.toHaveBeenCalled();
+      expect(client.continue).toHaveBeenCalledTimes(2);
+      expect(formatter.error).toHaveBeenCalledTimes(1);
+    });
+  });
+
+  describe('watchMemory', () => {
+    it('logs changes seen at stops and through the write watchpoint', async () => {
+      const formatter = {
+        sessionEnd: vi.fn(),
+        emit: vi.fn(),
+        error: vi.fn(),
+      };
+      const session = new DebugSession(
+        {
+          adapter: mockAdapter,
+          breakpoints: [],
+          captureLocals: false,
+          watchMemory: [{ expression: '&counter', size: 4 }],
+        },
+        formatter as unknown as OutputFormatter
+      );
+      const reads = ['2a000000', '2a000000', '00000000'];
+      const client = {
+        continue: vi.fn(),
+        evaluate: vi.fn(async () => ({
+          result: '(int *) 0x1000',
+          memoryReference: '0x1000',
+          variablesReference: 0,
+        })),
+        customRequest: vi.fn(async (command: string) => {
+          if (command === 'readMemory') {
+            const data = Buffer.from(reads.shift()!, 'hex').toString('base64');
+            return { address: '0x1000', data };
+          }
+          if (command === 'dataBreakpointInfo') return { dataId: 'counter', description: '' };
+          return { breakpoints: [{ verified: true }] };
+        }),
+        stackTrace: vi.fn(async () => ({
+          stackFrames: [{ id: 1, name: 'copy_out', line: 41, source: { path: '/app/ffi.c' } }],
+        })),
+      };
+      // @ts-expect-error accessing private field for testing
+      session.client = client;
+      // @ts-expect-error accessing private field for testing
+      session.capabilities = {
+        supportsReadMemoryRequest: true,
+        supportsDataBreakpoints: true,
+        supportsDataBreakpointBytes: true,
+      };
+      // @ts-expect-error accessing private field for testing
+      session.startTime = Date.now();
+
+      // @ts-expect-error accessing private method for testing
+      await session.handleStopped({ reason: 'breakpoint', threadId: 1 });
+      // @ts-expect-error accessing private method for testing
+      await session.handleStopped({ reason: 'breakpoint', threadId: 1 });
+      // @ts-expect-error accessing private method for testing
+      await session.handleStopped({ reason: 'data breakpoint', threadId: 1 });
+
+      expect(client.customRequest).toHaveBeenCalledWith('dataBreakpointInfo', {
+        name: '0x1000',
+        asAddress: true,
+        bytes: 4,
+      });
+      expect(client.customRequest).toHaveBeenCalledWith('setDataBreakpoints', {
+        breakpoints: [{ dataId: 'counter', accessType: 'write' }],
+      });
+      const commands = client.customRequest.mock.calls.map(([command]) => command);
+      expect(commands.filter((command) => command === 'dataBreakpointInfo')).toHaveLength(1);
+      expect(client.continue).toHaveBeenCalledTimes(3);
+      const events = formatter.emit.mock.calls.map(([event]) => event);
+      expect(events.map((event) => event.type)).toEqual([
+        'breakpoint_hit',
+        'breakpoint_hit',
+        'memory_changed',
+      ]);
+      expect(events[2]).toMatchObject({
+        expression: '&counter',
+        address: '0x1000',
+        before: '2a000000',
+        after: '00000000',
+        reason: 'write',
+        location: { file: '/app/ffi.c', line: 41 },
+      });
+      // @ts-expect-error accessing private method for testing
+      expect(session.buildSummary().memory).toEqual([
+        {
+          expression: '&counter',
+          size: 4,
+          address: '0x1000',
+          initial: '2a000000',
+          value: '00000000',
+          changes: 1,
+          watchpoint: true,
+        },
+      ]);
+    });
+  });
+
+  describe('thread timeline', () => {
+    it('names the stopped thread and lists every thread in session_end', async () => {
+      const formatter = {
+        sessionEnd: vi.fn(),
+        emit: vi.fn(),
+        error: vi.fn(),
+      };
+      const session = new DebugSession(
+        { adapter: mockAdapter, breakpoints: [], captureLocals: false },
+        formatter as unknown as OutputFormatter
+      );
+      const client = {
+        continue: vi.fn(),
+        threads: vi.fn(async () => ({
+          threads: [
+            { id: 1, name: 'main' },
+            { id: 4, name: 'Worker 2' },
+          ],
+        })),
+        stackTrace: vi.fn(async () => ({
+          stackFrames: [{ id: 1, name: 'work', line: 5, source: { path: 'worker.js' } }],
+        })),
+      };
+      // @ts-expect-error accessing private field for testing
+      session.client = client;
+      // @ts-expect-error accessing private field for testing
+      session.startTime = Date.now();
+      // @ts-expect-error accessing private field for testing
+      const timeline = session.threadTimeline;
+      timeline.update({ reason: 'started', threadId: 4 });
+
+      // @ts-expect-error accessing private method for testing
+      await session.handleStopped({ reason: 'breakpoint', threadId: 4 });
+      // @ts-expect-error accessing private method for testing
+      await session.handleStopped({ reason: 'breakpoint', threadId: 4 });
+      timeline.update({ reason: 'exited', threadId: 4 });
+      session.stop();
+
+      expect(client.threads).toHaveBeenCalledTimes(1);
+      expect(formatter.emit.mock.calls[0][0]).toMatchObject({
+        threadId: 4,
+        threadName: 'Worker 2',
+      });
+      expect(formatter.sessionEnd.mock.calls[0][0].threads).toEqual([
+        { id: 4, name: 'Worker 2', startedAt: expect.any(String), exitedAt: expect.any(String) },
+        { id: 1, name: 'main' },
+      ]);
+    });
+  });
+
+  describe('onExit restart', () => {
+    function createSession(supportsRestartRequest = true) {
+      const formatter = {
+        sessionEnd: vi.fn(),
+        emit: vi.fn(),
+        createEvent: vi.fn((type, data) => ({ type, ...data })),
+        error: vi.fn(),
+      };
+      const session = new DebugSession(
+        { adapter: mockAdapter, program: 'job.js', breakpoints: [], onExit: 'restart' },
+        formatter as unknown as OutputFormatter
+      );
+      const client = { customRequest: vi.fn(async () => ({})) };
+      // @ts-expect-error accessing private field for testing
+      session.client = client;
+      // @ts-expect-error accessing private field for testing
+      session.capabilities = { supportsRestartRequest };
+      // @ts-expect-error accessing private field for testing
+      session.launchArgs = { program: 'job.js' };
+      // @ts-expect-error accessing private field for testing
+      session.startTime = Date.now();
+      const exit = async (exitCode: number) => {
+        // @ts-expect-error accessing private method for testing
+        session.handleExited({ exitCode });
+        // @ts-expect-error accessing private method for testing
+        session.handleTerminated();
+        await new Promise((resolve) => setImmediate(resolve));
+      };
+      return { session, client, formatter, exit };
+    }
+
+    it('relaunches the program each time it exits', async () => {
+      const { session, client, formatter, exit } = createSession();
+
+      await exit(1);
+      await exit(1);
+
+      expect(client.customRequest).toHaveBeenCalledWith('restart', {
+        arguments: { program: 'job.js' },
+      });
+      expect(formatter.emit).toHaveBeenCalledWith(
+        expect.objectContaining({ type: 'process_restarted', restart: 2, reason: 'exited' })
+      );
+      expect(formatter.sessionEnd).not.toHaveBeenCalled();
+
+      session.stop();
+      expect(formatter.sessionEnd).toHaveBeenCalledWith(
+        expect.objectContaining({ restarts: 2, exitCode: null })
+      );
+    });
+
+    it('ends the session when the adapter cannot restart', async () => {
+      const { client, formatter, exit } = createSession(false);
+
+      await exit(0);
+
+      expect(client.customRequest).not.toHaveBeenCalled();
+      expect(formatter.error).toHaveBeenCalledWith(
+        'Failed to restart the program',
+        "The test adapter doesn't support restart requests"
+      );
+      expect(formatter.sessionEnd).toHaveBeenCalledWith(
+        expect.objectContaining({
+          exitCode: 0,
+          termination: expect.objectContaining({ reason: 'exited' }),
+        })
+      );
+    });
+  });
+
+  describe('reloadCode', () => {
+    function createSession(hotReload: AdapterConfig['hotReload'], paused: boolean) {
+      const formatter = { emit: vi.fn(), error: vi.fn() };
+      const session = new DebugSession(
+        { adapter: { ...mockAdapter, hotReload }, breakpoints: [], cwd: '/app' },
+        formatter as unknown as OutputFormatter
+      );
+      const client = {
+        evaluate: vi.fn(async () => ({ result: "'10'", variablesReference: 0 })),
+        customRequest: vi.fn(),
+      };
+      const breakpointManager = {
+        reverifyBreakpoints: vi.fn(async () => [
+          { file: '/app/src/a.js', line: 12, verified: true, id: 3 },
+        ]),
+      };
+      // @ts-expect-error accessing private field for testing
+      session.client = client;
+      // @ts-expect-error accessing private field for testing
+      session.breakpointManager = breakpointManager;
+      // @ts-expect-error accessing private field for testing
+      session.capabilities = { supportsRestartFrame: true };
+      if (paused) {
+        // @ts-expect-error accessing private field for testing
+        session.paused = { threadId: 1, frameId: 1000 };
+      }
+      return { session, client, breakpointManager, formatter };
+    }
+
+    it('reloads files, restarts the frame and re-verifies breakpoints', async () => {
+      const { session, client, breakpointManager, formatter } = createSession('js-debug', true);
+
+      const event = await session.reloadCode(['src/a.js', 'src/b.mjs'], { restartFrame: true });
+
+      expect(client.evaluate).toHaveBeenCalledWith(
+        expect.objectContaining({ frameId: 1000, context: 'repl' })
+      );
+      expect(client.customRequest).toHaveBeenCalledWith('restartFrame', { frameId: 1000 });
+      expect(breakpointManager.reverifyBreakpoints).toHaveBeenCalledWith(
+        new Set(['/app/src/a.js', '/app/src/b.mjs'])
+      );
+      expect(event).toMatchObject({
+        type: 'code_reloaded',
+        mechanism: 'js-debug',
+        reloaded: ['/app/src/a.js'],
+        notLoaded: ['/app/src/b.mjs'],
+        frameRestarted: true,
+        breakpoints: [{ id: 3, file: '/app/src/a.js', line: 12, verified: true }],
+      });
+      expect(formatter.emit).toHaveBeenCalledWith(event);
+    });
+
+    it('stays paused at the stop that follows a frame restart', async () => {
+      const { session } = createSession('js-debug', true);
+      await session.reloadCode(['src/a.js'], { restartFrame: true });
+
+      // @ts-expect-error accessing private field for testing
+      session.client.stackTrace = vi.fn(async () => ({
+        stackFrames: [{ id: 2000, name: 'price', line: 10, source: { path: '/app/src/a.js' } }],
+      }));
+      // @ts-expect-error accessing private method for testing
+      await session.handleStopped({ reason: 'restart', threadId: 1 });
+
+      expect(session.isPaused()).toBe(true);
+      // @ts-expect-error accessing private field for testing
+      expect(session.paused).toEqual({ threadId: 1, frameId: 2000 });
+    });
+
+    it('rejects adapters and states that cannot reload', async () => {
+      await expect(createSession(undefined, true).session.reloadCode(['a.js'])).rejects.toThrow(
+        "doesn't support hot code reload"
+      );
+      await expect(createSession('debugpy', false).session.reloadCode(['a.py'])).rejects.toThrow(
+        'only be reloaded while paused'
+      );
+      await expect(
+        createSession('js-debug', false).session.reloadCode(['a.js'], { restartFrame: true })
+      ).rejects.toThrow('restartFrame needs a session paused');
+    });
+  });
+
+  describe('trigger', () => {
+    it('holds the entry stop until the trigger fires, then runs', async () => {
+      const formatter = new OutputFormatter({ stream: new PassThrough() });
+      const emitted: string[] = [];
+      formatter.onEvent((event) => emitted.push(event.type));
+      const session = new DebugSession(
+        { adapter: mockAdapter, breakpoints: [], trigger: { file: '/tmp/go' } },
+        formatter
+      );
+      let fire: (source: 'file') => void = () => {};
+      const client = {
+        continue: vi.fn(),
+        stackTrace: vi.fn(async () => ({
+          stackFrames: [{ id: 1, name: 'main', line: 1, source: { path: 'server.js' } }],
+        })),
+      };
+      // @ts-expect-error accessing private field for testing
+      session.client = client;
+      // @ts-expect-error accessing private field for testing
+      session.trigger = { fired: new Promise((resolve) => (fire = resolve)), cancel: vi.fn() };
+
+      // @ts-expect-error accessing private method for testing
+      const held = session.handleStopped({ reason: 'entry', threadId: 1 });
+      await new Promise((resolve) => setTimeout(resolve, 0));
+      expect(emitted).toEqual(['trigger_waiting']);
+      expect(client.continue).not.toHaveBeenCalled();
+
+      fire('file');
+      await held;
+      expect(emitted).toEqual(['trigger_waiting', 'trigger_fired']);
+      expect(client.continue).toHaveBeenCalledWith({ threadId: 1 });
+      // @ts-expect-error accessing private field for testing
+      expect(session.trigger).toBeNull();
+    });
+
+    it('cancels a held trigger when the session ends', async () => {
+      const session = new DebugSession(
+        { adapter: mockAdapter, breakpoints: [], trigger: { file: '/tmp/never' } },
+        new OutputFormatter({ stream: new PassThrough() })
+      );
+      const client = {
+        continue: vi.fn(),
+        isConnected: () => false,
+        reapProcessTree: vi.fn(),
+        stackTrace: vi.fn(async () => ({
+          stackFrames: [{ id: 1, name: 'main', line: 1, source: { path: 'server.js' } }],
+        })),
+      };
+      // @ts-expect-error accessing private field for testing
+      session.client = client;
+      const trigger = armTrigger({ file: '/tmp/never' });
+      const cancel = vi.spyOn(trigger, 'cancel');
+      // @ts-expect-error accessing private field for testing
+      session.trigger = trigger;
+
+      // @ts-expect-error accessing private method for testing
+      const held = session.handleStopped({ reason: 'entry', threadId: 1 });
+      await new Promise((resolve) => setTimeout(resolve, 0));
+      // @ts-expect-error accessing private method for testing
+      await session.cleanup();
+      await held;
+
+      expect(cancel).toHaveBeenCalled();
+      expect(await trigger.fired).toBeNull();
+      expect(client.continue).not.toHaveBeenCalled();
+    });
+  });
+});
+
+describe('parseOnHitAction', () => {
+  it('parses restart-frame and goto targets', () => {
+    expect(parseOnHitAction('restart-frame')).toEqual({ action: 'restart-frame' });
+    expect(parseOnHitAction('goto:src/main.rs:260')).toEqual({
+      action: 'goto',
+      file: 'src/main.rs',
+      line: 260,
+    });
+    expect(parseOnHitAction('goto:C:\\app\\main.rs:7')).toMatchObject({ file: 'C:\\app\\main.rs' });
+  });
+
+  it('rejects unknown actions', () => {
+    expect(() => parseOnHitAction('goto:src/main.rs')).toThrow('Invalid --on-hit');
+    expect(() => parseOnHitAction('skip')).toThrow('Invalid --on-hit');
+  });
 });
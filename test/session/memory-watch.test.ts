@@ -0,0 +1,86 @@
+/**
+ * Unit tests for memory watches (--watch-memory)
+ */
+
+import { describe, it, expect } from 'vitest';
+import {
+  MemoryWatchLog,
+  decodeMemory,
+  parseAddress,
+  parseMemoryWatch,
+} from '../../src/session/memory-watch.js';
+
+describe('parseMemoryWatch', () => {
+  it('splits the address expression from the size', () => {
+    expect(parseMemoryWatch('&counter:8')).toEqual({ expression: '&counter', size: 8 });
+    expect(parseMemoryWatch('(char *)buf + 4:16')).toEqual({
+      expression: '(char *)buf + 4',
+      size: 16,
+    });
+    expect(parseMemoryWatch('&ns::total:4')).toEqual({ expression: '&ns::total', size: 4 });
+  });
+
+  it('rejects specs without a valid size', () => {
+    expect(() => parseMemoryWatch('&counter')).toThrow('Invalid --watch-memory');
+    expect(() => parseMemoryWatch('&counter:0')).toThrow('Invalid --watch-memory');
+    expect(() => parseMemoryWatch(':8')).toThrow('Invalid --watch-memory');
+    expect(() => parseMemoryWatch('&buf:100000')).toThrow('Invalid --watch-memory');
+  });
+});
+
+describe('parseAddress', () => {
+  it('finds the address in an evaluate result', () => {
+    expect(parseAddress('(int *) 0x00007ffd5e0c')).toBe('0x00007ffd5e0c');
+    expect(parseAddress('0x1000 <buf>')).toBe('0x1000');
+    expect(parseAddress('42')).toBeUndefined();
+  });
+});
+
+describe('decodeMemory', () => {
+  it('converts base64 to hex', () => {
+    expect(decodeMemory(Buffer.from([0x2a, 0, 0, 0xff]).toString('base64'))).toBe('2a0000ff');
+  });
+});
+
+describe('MemoryWatchLog', () => {
+  it('reports changes after the first sample', () => {
+    const log = new MemoryWatchLog([{ expression: '&counter', size: 4 }]);
+
+    expect(log.record('&counter', '0x1000', '2a000000', 'stop')).toBeUndefined();
+    expect(log.record('&counter', '0x1000', '2a000000', 'stop')).toBeUndefined();
+    expect(log.record('&counter', '0x1000', '2b000000', 'write')).toEqual({
+      expression: '&counter',
+      address: '0x1000',
+      size: 4,
+      before: '2a000000',
+      after: '2b000000',
+      reason: 'write',
+    });
+    expect(log.list()).toEqual([
+      {
+        expression: '&counter',
+        size: 4,
+        address: '0x1000',
+        initial: '2a000000',
+        value: '2b000000',
+        changes: 1,
+      },
+    ]);
+  });
+
+  it('starts over when the address moves', () => {
+    const log = new MemoryWatchLog([{ expression: '&local', size: 2 }]);
+    log.record('&local', '0x1000', '0100', 'stop');
+
+    expect(log.record('&local', '0x2000', '0200', 'stop')).toBeUndefined();
+    expect(log.list()[0]).toMatchObject({ address: '0x2000', changes: 0 });
+  });
+
+  it('reports each failing watch once', () => {
+    const log = new MemoryWatchLog([{ expression: '&gone', size: 4 }]);
+
+    expect(log.fail('&gone', 'no such variable')).toBe(true);
+    expect(log.fail('&gone', 'no such variable')).toBe(false);
+    expect(log.list()[0].error).toBe('no such variable');
+  });
+});
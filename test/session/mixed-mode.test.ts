@@ -0,0 +1,108 @@
+/**
+ * Unit tests for mixed-mode sessions (--native-break)
+ */
+
+import { describe, it, expect, vi } from 'vitest';
+import {
+  NativeCompanion,
+  lldbStringResult,
+  parsePythonStack,
+} from '../../src/session/mixed-mode.js';
+import { lldbAdapter } from '../../src/adapters/lldb.js';
+import type { OutputFormatter } from '../../src/output/formatter.js';
+import type { BreakpointHitEvent } from '../../src/output/events.js';
+
+const FORMAT_STACK =
+  '  File "/app/main.py", line 12, in <module>\n    main()\n' +
+  '  File "/app/main.py", line 8, in main\n    total = fastsum.total(values)\n';
+
+describe('lldbStringResult', () => {
+  it('unescapes the string of a char pointer result', () => {
+    expect(lldbStringResult('0x00007f31a8c04000 "  File \\"a.py\\", line 1\\n"')).toBe(
+      '  File "a.py", line 1\n'
+    );
+  });
+
+  it('is undefined for a null pointer', () => {
+    expect(lldbStringResult('0x0000000000000000')).toBeUndefined();
+  });
+});
+
+describe('parsePythonStack', () => {
+  it('lists the frames innermost first', () => {
+    expect(parsePythonStack(FORMAT_STACK)).toEqual([
+      { file: '/app/main.py', line: 8, function: 'main' },
+      { file: '/app/main.py', line: 12, function: '<module>' },
+    ]);
+  });
+});
+
+describe('NativeCompanion', () => {
+  function createCompanion(evaluate: () => Promise<{ result: string }>) {
+    const hits: BreakpointHitEvent[] = [];
+    const companion = new NativeCompanion(
+      { adapter: lldbAdapter, breakpoints: ['fastsum.c:42'], captureLocals: false },
+      {} as OutputFormatter,
+      (event) => hits.push(event)
+    );
+    const client = {
+      continue: vi.fn(),
+      evaluate: vi.fn(evaluate),
+      stackTrace: vi.fn(async () => ({
+        stackFrames: [
+          { id: 7, name: 'fastsum_total', line: 42, source: { path: '/ext/fastsum.c' } },
+          { id: 8, name: '_PyEval_EvalFrameDefault', line: 0 },
+        ],
+      })),
+    };
+    // @ts-expect-error accessing private field for testing
+    companion.client = client;
+    return { companion, client, hits };
+  }
+
+  it('reports a native hit with the Python stack that called into it', async () => {
+    const escaped = FORMAT_STACK.replace(/"/g, '\\"').replace(/\n/g, '\\n');
+    const { companion, client, hits } = createCompanion(async () => ({
+      result: `(const char *) $0 = 0x00007f31a8c04000 "${escaped}"`,
+    }));
+
+    // @ts-expect-error accessing private method for testing
+    await companion.handleStopped({ reason: 'breakpoint', threadId: 3, hitBreakpointIds: [1] });
+
+    expect(hits).toHaveLength(1);
+    expect(hits[0]).toMatchObject({
+      id: 1,
+      native: true,
+      location: { file: '/ext/fastsum.c', line: 42, function: 'fastsum_total' },
+      pythonStack: [
+        { file: '/app/main.py', line: 8, function: 'main' },
+        { file: '/app/main.py', line: 12, function: '<module>' },
+      ],
+    });
+    expect(hits[0].stackTrace).toHaveLength(2);
+    expect(client.evaluate.mock.calls[0][0]).toMatchObject({ frameId: 7, context: 'repl' });
+    expect(client.continue).toHaveBeenCalledWith({ threadId: 3 });
+  });
+
+  it('explains a Python stack it could not read', async () => {
+    const { companion, hits } = createCompanion(async () => ({
+      result: '(const char *) $0 = 0x0000000000000000',
+    }));
+
+    // @ts-expect-error accessing private method for testing
+    await companion.handleStopped({ reason: 'breakpoint', threadId: 3 });
+
+    expect(hits[0].pythonStack).toBeUndefined();
+    expect(hits[0].pythonStackError).toMatch(/released the GIL/);
+  });
+
+  it('resumes stops other than breakpoints without reporting them', async () => {
+    const { companion, client, hits } = createCompanion(async () => ({ result: '' }));
+
+    // @ts-expect-error accessing private method for testing
+    await companion.handleStopped({ reason: 'signal', threadId: 1 });
+
+    expect(hits).toHaveLength(0);
+    expect(client.continue).toHaveBeenCalledWith({ threadId: 1 });
+  });
+});
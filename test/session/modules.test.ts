@@ -0,0 +1,59 @@
+/**
+ * Unit tests for loaded module tracking (--modules)
+ */
+
+import { describe, it, expect } from 'vitest';
+import { ModuleTracker, symbolsLoaded, toModuleInfo } from '../../src/session/modules.js';
+
+describe('symbolsLoaded', () => {
+  it('reads adapter symbol status text', () => {
+    expect(symbolsLoaded('Symbols loaded.')).toBe(true);
+    expect(symbolsLoaded('Skipped loading symbols.')).toBe(false);
+    expect(symbolsLoaded('Cannot find or open the PDB file.')).toBe(false);
+    expect(symbolsLoaded('Symbols not loaded')).toBe(false);
+    expect(symbolsLoaded(undefined)).toBeUndefined();
+  });
+});
+
+describe('toModuleInfo', () => {
+  it('keeps the fields that explain unverified breakpoints', () => {
+    expect(
+      toModuleInfo({
+        id: 3,
+        name: 'App.dll',
+        path: '/app/App.dll',
+        version: '1.0.0.0',
+        symbolStatus: 'Symbols loaded.',
+        isUserCode: true,
+        addressRange: '0x7f00',
+      })
+    ).toEqual({
+      name: 'App.dll',
+      path: '/app/App.dll',
+      version: '1.0.0.0',
+      symbolsLoaded: true,
+      symbolStatus: 'Symbols loaded.',
+      isUserCode: true,
+    });
+  });
+});
+
+describe('ModuleTracker', () => {
+  it('applies module events and merges the modules request in load order', () => {
+    const tracker = new ModuleTracker();
+    tracker.update({ reason: 'new', module: { id: 1, name: 'libc.so.6' } });
+    tracker.update({ reason: 'new', module: { id: 2, name: 'libplugin.so' } });
+    tracker.update({ reason: 'new', module: { id: 3, name: 'libtmp.so' } });
+    tracker.update({ reason: 'removed', module: { id: 3, name: 'libtmp.so' } });
+    tracker.merge([
+      { id: 0, name: 'app' },
+      { id: 2, name: 'libplugin.so', symbolStatus: 'Symbols loaded.' },
+    ]);
+
+    expect(tracker.list()).toEqual([
+      { name: 'libc.so.6' },
+      { name: 'libplugin.so', symbolsLoaded: true, symbolStatus: 'Symbols loaded.' },
+      { name: 'app' },
+    ]);
+  });
+});
@@ -0,0 +1,40 @@
+/**
+ * Unit tests for arming breakpoints on program output (--arm-on-output)
+ */
+
+import { describe, it, expect } from 'vitest';
+import { OutputArming, parseArmPattern } from '../../src/session/output-arming.js';
+
+describe('parseArmPattern', () => {
+  it('compiles the pattern as a regular expression', () => {
+    expect(parseArmPattern('listening on :\\d+').test('Server listening on :8080')).toBe(true);
+  });
+
+  it('rejects an invalid regular expression', () => {
+    expect(() => parseArmPattern('ready (')).toThrow('Invalid --arm-on-output pattern "ready ("');
+  });
+});
+
+describe('OutputArming', () => {
+  it('matches lines split across chunks, per stream', () => {
+    const arming = new OutputArming(/^Server listening on \d+$/);
+    expect(arming.feed('stdout', 'booting\nServer list')).toBeUndefined();
+    expect(arming.feed('stderr', 'ening on 80\n')).toBeUndefined();
+    expect(arming.feed('stdout', 'ening on 8080\r\nnext')).toBe('Server listening on 8080');
+    expect(arming.armed).toBe(true);
+  });
+
+  it('matches an unfinished line such as a prompt', () => {
+    const arming = new OutputArming(/ready>/);
+    expect(arming.feed('stdout', 'ready> ')).toBe('ready> ');
+  });
+
+  it('reports the line once, until disarmed', () => {
+    const arming = new OutputArming(/ready/);
+    expect(arming.feed('stdout', 'ready\n')).toBe('ready');
+    expect(arming.feed('stdout', 'ready\n')).toBeUndefined();
+    arming.disarm();
+    expect(arming.armed).toBe(false);
+    expect(arming.feed('stdout', 'still ready\n')).toBe('still ready');
+  });
+});
@@ -0,0 +1,46 @@
+/**
+ * Unit tests for adapter progress tracking
+ */
+
+import { describe, it, expect } from 'vitest';
+import { ProgressTracker } from '../../src/session/progress.js';
+
+describe('ProgressTracker', () => {
+  it('describes the latest report in progress', () => {
+    const tracker = new ProgressTracker();
+    expect(tracker.describe()).toBeUndefined();
+
+    tracker.start({ progressId: 'maps', title: 'Loading source maps' }, 1000);
+    expect(tracker.describe()).toBe('Loading source maps');
+
+    tracker.update({ progressId: 'maps', message: '12/40', percentage: 30.4 });
+    expect(tracker.describe()).toBe('Loading source maps: 12/40 (30%)');
+
+    tracker.start({ progressId: 'scan', title: 'Scanning scripts' }, 1100);
+    expect(tracker.describe()).toBe('Scanning scripts');
+
+    tracker.end({ progressId: 'scan' }, 1200);
+    expect(tracker.describe()).toBe('Loading source maps: 12/40 (30%)');
+  });
+
+  it('lists finished reports with their durations', () => {
+    const tracker = new ProgressTracker();
+    tracker.start({ progressId: 'maps', title: 'Loading source maps', message: '0/40' }, 1000);
+    tracker.update({ progressId: 'unknown', message: 'ignored' });
+    tracker.end({ progressId: 'maps' }, 3500);
+    tracker.end({ progressId: 'maps' }, 4000);
+
+    expect(tracker.list()).toEqual([
+      {
+        title: 'Loading source maps',
+        message: '0/40',
+        startedAt: new Date(1000).toISOString(),
+        durationMs: 2500,
+      },
+    ]);
+    expect(tracker.describe()).toBeUndefined();
+
+    tracker.clear();
+    expect(tracker.list()).toEqual([]);
+  });
+});
@@ -0,0 +1,48 @@
+/**
+ * Unit tests for DAP round-trip statistics (--request-stats)
+ */
+
+import { describe, it, expect } from 'vitest';
+import { RequestStats } from '../../src/session/request-stats.js';
+import type { Request, Response } from '../../src/dap/protocol.js';
+
+function request(seq: number, command: string): Request {
+  return { seq, type: 'request', command };
+}
+
+function response(requestSeq: number, command: string, success: boolean = true): Response {
+  return { seq: 100 + requestSeq, type: 'response', request_seq: requestSeq, command, success };
+}
+
+describe('RequestStats', () => {
+  it('totals round trips per command, the most time-consuming first', () => {
+    const stats = new RequestStats();
+    stats.protocolMessage('send', request(1, 'variables'), undefined, 0);
+    stats.protocolMessage('send', request(2, 'variables'), undefined, 1);
+    stats.protocolMessage('receive', response(1, 'variables'), undefined, 10);
+    stats.protocolMessage('receive', response(2, 'variables'), undefined, 31);
+    stats.protocolMessage('send', request(3, 'evaluate'), undefined, 40);
+    stats.protocolMessage('receive', response(3, 'evaluate', false), undefined, 52.25);
+
+    expect(stats.list()).toEqual([
+      { command: 'variables', count: 2, totalMs: 40, meanMs: 20, maxMs: 30 },
+      { command: 'evaluate', count: 1, failed: 1, totalMs: 12.3, meanMs: 12.3, maxMs: 12.3 },
+    ]);
+  });
+
+  it('keeps child session requests apart and ignores events and reverse requests', () => {
+    const stats = new RequestStats();
+    stats.protocolMessage('send', request(1, 'launch'), undefined, 0);
+    stats.protocolMessage('send', request(1, 'setBreakpoints'), 'child', 5);
+    stats.protocolMessage('receive', request(7, 'runInTerminal'), undefined, 6);
+    stats.protocolMessage('send', response(7, 'runInTerminal'), undefined, 7);
+    stats.protocolMessage('receive', { seq: 8, type: 'event' }, undefined, 8);
+    stats.protocolMessage('receive', response(1, 'setBreakpoints'), 'child', 9);
+    stats.protocolMessage('receive', response(1, 'launch'), undefined, 20);
+
+    expect(stats.list()).toEqual([
+      { command: 'launch', count: 1, totalMs: 20, meanMs: 20, maxMs: 20 },
+      { command: 'setBreakpoints', count: 1, totalMs: 4, meanMs: 4, maxMs: 4 },
+    ]);
+  });
+});
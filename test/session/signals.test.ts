@@ -0,0 +1,43 @@
+/**
+ * Unit tests for native signal stop parsing
+ */
+
+import { describe, it, expect } from 'vitest';
+import { parseFatalSignal } from '../../src/session/signals.js';
+
+describe('parseFatalSignal', () => {
+  it('parses lldb-dap signal stops with the fault address', () => {
+    expect(
+      parseFatalSignal({
+        reason: 'exception',
+        description: 'signal SIGSEGV: invalid address (fault address: 0x0)',
+      })
+    ).toEqual({
+      name: 'SIGSEGV',
+      number: expect.any(Number),
+      description: 'signal SIGSEGV: invalid address (fault address: 0x0)',
+      faultAddress: '0x0',
+    });
+  });
+
+  it('parses signal stops reported with reason "signal"', () => {
+    expect(parseFatalSignal({ reason: 'signal', text: 'SIGABRT' })).toMatchObject({
+      name: 'SIGABRT',
+    });
+  });
+
+  it('maps Mach exceptions to signals', () => {
+    expect(
+      parseFatalSignal({
+        reason: 'exception',
+        description: 'EXC_BAD_ACCESS (code=1, address=0x10)',
+      })
+    ).toMatchObject({ name: 'SIGSEGV', faultAddress: '0x10' });
+  });
+
+  it('ignores breakpoints, language exceptions and signals the program survives', () => {
+    expect(parseFatalSignal({ reason: 'breakpoint', description: 'SIGSEGV' })).toBeUndefined();
+    expect(parseFatalSignal({ reason: 'exception', text: 'C++ Throw' })).toBeUndefined();
+    expect(parseFatalSignal({ reason: 'signal', text: 'SIGUSR1' })).toBeUndefined();
+  });
+});
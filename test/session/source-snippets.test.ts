@@ -0,0 +1,58 @@
+/**
+ * Unit tests for source snippet extraction
+ */
+
+import { describe, it, expect, beforeAll, afterAll } from 'vitest';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import { SourceSnippetReader, formatSnippetLines } from '../../src/session/source-snippets.js';
+
+describe('formatSnippetLines', () => {
+  it('marks the current line and aligns line numbers', () => {
+    expect(formatSnippetLines(['a', 'b', 'c'], 9, 10)).toEqual([
+      '   9 | a',
+      '> 10 | b',
+      '  11 | c',
+    ]);
+  });
+});
+
+describe('SourceSnippetReader', () => {
+  let dir: string;
+  let file: string;
+
+  beforeAll(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-src-'));
+    file = path.join(dir, 'app.ts');
+    fs.writeFileSync(file, ['line 1', 'line 2', 'line 3', 'line 4', 'line 5'].join('\n'));
+  });
+
+  afterAll(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  it('returns context lines around the stop line', () => {
+    const snippet = new SourceSnippetReader().read(file, 3, 1);
+
+    expect(snippet).toEqual({
+      startLine: 2,
+      endLine: 4,
+      lines: ['  2 | line 2', '> 3 | line 3', '  4 | line 4'],
+    });
+  });
+
+  it('clamps the window at the start and end of the file', () => {
+    const reader = new SourceSnippetReader();
+
+    expect(reader.read(file, 1, 2)?.startLine).toBe(1);
+    expect(reader.read(file, 5, 2)?.endLine).toBe(5);
+  });
+
+  it('returns undefined for unreadable files or out-of-range lines', () => {
+    const reader = new SourceSnippetReader();
+
+    expect(reader.read(path.join(dir, 'missing.ts'), 1, 2)).toBeUndefined();
+    expect(reader.read(file, 99, 2)).toBeUndefined();
+  });
+});
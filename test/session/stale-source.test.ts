@@ -0,0 +1,84 @@
+/**
+ * Unit tests for stale source detection
+ */
+
+import { createHash } from 'node:crypto';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import { afterEach, beforeEach, describe, it, expect } from 'vitest';
+import {
+  StaleSourceChecker,
+  compareChecksums,
+  compareContent,
+} from '../../src/session/stale-source.js';
+
+const sha256 = (text: string) => createHash('sha256').update(text).digest('hex');
+
+describe('compareChecksums', () => {
+  const content = Buffer.from('let total = 0;\nreturn total;\n');
+
+  it('accepts a matching checksum, whatever the line endings', () => {
+    expect(
+      compareChecksums(content, [{ algorithm: 'SHA256', checksum: sha256(content.toString()) }])
+    ).toBeUndefined();
+    const crlf = 'let total = 0;\r\nreturn total;\r\n';
+    expect(
+      compareChecksums(content, [{ algorithm: 'SHA256', checksum: sha256(crlf).toUpperCase() }])
+    ).toBeUndefined();
+  });
+
+  it('reports a checksum that matches no variant of the file', () => {
+    const running = sha256('let total = 1;\n');
+    expect(compareChecksums(content, [{ algorithm: 'SHA256', checksum: running }])).toEqual({
+      reason: 'checksum',
+      algorithm: 'SHA256',
+      expected: running,
+      actual: sha256(content.toString()),
+    });
+  });
+
+  it('ignores checksums it cannot compare', () => {
+    expect(
+      compareChecksums(content, [{ algorithm: 'timestamp', checksum: '1700000000' }])
+    ).toBeUndefined();
+  });
+});
+
+describe('compareContent', () => {
+  it('ignores line endings and trailing newlines', () => {
+    expect(compareContent(Buffer.from('a\nb\n'), 'a\r\nb')).toBeUndefined();
+    expect(compareContent(Buffer.from('a\nb\n'), 'a\nc\n')).toEqual({ reason: 'content' });
+  });
+});
+
+describe('StaleSourceChecker', () => {
+  let dir: string;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-stale-'));
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  it('checks each file once', () => {
+    const file = path.join(dir, 'cart.ts');
+    fs.writeFileSync(file, 'export const total = 2;\n');
+    const checker = new StaleSourceChecker();
+
+    expect(checker.shouldCheck(file)).toBe(true);
+    expect(checker.check(file, {})).toBeUndefined();
+    expect(checker.shouldCheck(file)).toBe(true);
+    expect(checker.check(file, { loadedContent: 'export const total = 1;\n' })).toEqual({
+      reason: 'content',
+    });
+    expect(checker.shouldCheck(file)).toBe(false);
+    expect(checker.check(file, { loadedContent: 'export const total = 1;\n' })).toBeUndefined();
+  });
+
+  it('skips files that are not on disk', () => {
+    expect(new StaleSourceChecker().shouldCheck(path.join(dir, 'missing.ts'))).toBe(false);
+  });
+});
@@ -0,0 +1,31 @@
+/**
+ * Unit tests for step filters (--step-filter)
+ */
+
+import { describe, it, expect } from 'vitest';
+import { matchesStepFilter, qualifiedFunctionName } from '../../src/session/step-filter.js';
+
+describe('qualifiedFunctionName', () => {
+  it('strips the module prefix and parameter list', () => {
+    expect(qualifiedFunctionName('MyApp.dll!MyApp.Orders.Checkout(int id)')).toBe(
+      'MyApp.Orders.Checkout'
+    );
+    expect(qualifiedFunctionName('Serilog.Log.Information()')).toBe('Serilog.Log.Information');
+    expect(qualifiedFunctionName('Main')).toBe('Main');
+  });
+});
+
+describe('matchesStepFilter', () => {
+  it('matches functions in a namespace or type', () => {
+    expect(matchesStepFilter('Serilog.Core.Logger.Write()', ['Serilog'])).toBe(true);
+    expect(matchesStepFilter('Npgsql.dll!Npgsql.NpgsqlCommand.Execute()', ['Npgsql.*'])).toBe(
+      true
+    );
+    expect(matchesStepFilter('MyApp.Cache.Get()', ['MyApp.Cache'])).toBe(true);
+  });
+
+  it('does not match namespaces that only share a prefix', () => {
+    expect(matchesStepFilter('SerilogHelpers.Format()', ['Serilog'])).toBe(false);
+    expect(matchesStepFilter('MyApp.Orders.Checkout()', ['Serilog', 'Npgsql'])).toBe(false);
+  });
+});
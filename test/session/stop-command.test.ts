@@ -0,0 +1,126 @@
+/**
+ * Unit tests for --on-stop-exec commands
+ */
+
+import { describe, it, expect } from 'vitest';
+import type { VariableValue } from '../../src/output/events.js';
+import {
+  capturedValue,
+  evaluationText,
+  fillStopCommand,
+  parseStopCommand,
+  MAX_STOP_COMMAND_OUTPUT,
+  runStopCommand,
+  stopCommandPlaceholders,
+} from '../../src/session/stop-command.js';
+
+const LOCALS: Record<string, VariableValue> = {
+  order_id: { type: 'string', value: 'abc-123' },
+  quantity: { type: 'int', value: 3 },
+  order: {
+    type: 'OrderDto',
+    value: {
+      Id: { type: 'string', value: 'abc-123' },
+      Total: { type: 'decimal', value: 150 },
+    },
+    expandable: true,
+    variablesReference: 7,
+  },
+  items: {
+    type: 'List<string>',
+    value: {
+      type: 'List<string>',
+      count: 2,
+      items: [
+        { type: 'string', value: 'widget' },
+        { type: 'string', value: 'gadget' },
+      ],
+    },
+  },
+};
+
+describe('parseStopCommand', () => {
+  it('splits the command line and finds its placeholders', () => {
+    const words = parseStopCommand('./scripts/capture.sh {order_id} "--label={ order.Id }" {x}');
+    expect(words).toEqual(['./scripts/capture.sh', '{order_id}', '--label={ order.Id }', '{x}']);
+    expect(stopCommandPlaceholders(words)).toEqual(['order_id', 'order.Id', 'x']);
+    expect(() => parseStopCommand('  ')).toThrow('--on-stop-exec needs a command');
+  });
+});
+
+describe('capturedValue', () => {
+  it('reads locals, paths into them and --eval results', () => {
+    expect(capturedValue('order_id', LOCALS)).toBe('abc-123');
+    expect(capturedValue('quantity', LOCALS)).toBe('3');
+    expect(capturedValue('order.Total', LOCALS)).toBe('150');
+    expect(capturedValue('items.1', LOCALS)).toBe('gadget');
+    expect(capturedValue('order', LOCALS)).toBe('{"Id":"abc-123","Total":150}');
+    expect(capturedValue('items', LOCALS)).toBe('["widget","gadget"]');
+    expect(capturedValue('queue.depth()', LOCALS, { 'queue.depth()': { result: '42' } })).toBe(
+      '42'
+    );
+  });
+
+  it('leaves names the stop did not capture unresolved', () => {
+    expect(capturedValue('customer', LOCALS)).toBeUndefined();
+    expect(capturedValue('order_id.length', LOCALS)).toBeUndefined();
+    expect(capturedValue('x', LOCALS, { x: { result: '', error: 'not defined' } })).toBeUndefined();
+  });
+
+  it('strips the quotes adapters put around string results', () => {
+    expect(evaluationText({ result: '"orders"' })).toBe('orders');
+    expect(evaluationText({ result: "'orders'" })).toBe('orders');
+    expect(evaluationText({ result: '42' })).toBe('42');
+  });
+});
+
+describe('fillStopCommand', () => {
+  it('fills each word without splitting values', () => {
+    expect(
+      fillStopCommand(['./capture.sh', '{name}', '--id={ id }', '{missing}'], {
+        name: 'a b; rm -rf /',
+        id: '7',
+      })
+    ).toEqual(['./capture.sh', 'a b; rm -rf /', '--id=7', '{missing}']);
+  });
+
+  it('reads {{ and }} as literal braces', () => {
+    const words = parseStopCommand("jq '{{id: .orders[{index}]}}' {{x}}");
+    expect(stopCommandPlaceholders(words)).toEqual(['index']);
+    expect(fillStopCommand(words, { index: '2' })).toEqual(['jq', '{id: .orders[2]}', '{x}']);
+  });
+});
+
+describe('runStopCommand', () => {
+  it('collects output and the exit code', async () => {
+    const result = await runStopCommand([
+      process.execPath,
+      '-e',
+      'console.log(process.argv[1]); console.error("warn"); process.exit(3)',
+      'abc-123',
+    ]);
+    expect(result).toMatchObject({ exitCode: 3, stdout: 'abc-123\n', stderr: 'warn\n' });
+    expect(result.error).toBeUndefined();
+  });
+
+  it('cuts long output off by characters', async () => {
+    const result = await runStopCommand([
+      process.execPath,
+      '-e',
+      `process.stdout.write('é'.repeat(${MAX_STOP_COMMAND_OUTPUT + 10}))`,
+    ]);
+    expect(result.stdout).toBe('é'.repeat(MAX_STOP_COMMAND_OUTPUT));
+    expect(result.truncated).toBe(true);
+  });
+
+  it('reports a command that cannot run or times out', async () => {
+    const missing = await runStopCommand(['./no-such-stop-command']);
+    expect(missing.exitCode).toBeNull();
+    expect(missing.error).toContain('Could not run ./no-such-stop-command');
+
+    const slow = await runStopCommand([process.execPath, '-e', 'setTimeout(() => {}, 10000)'], {
+      timeoutMs: 100,
+    });
+    expect(slow).toMatchObject({ exitCode: null, error: 'Timed out after 100ms' });
+  });
+});
@@ -0,0 +1,30 @@
+/**
+ * Unit tests for the thread timeline
+ */
+
+import { describe, it, expect } from 'vitest';
+import { ThreadTimeline } from '../../src/session/threads.js';
+
+describe('ThreadTimeline', () => {
+  it('records starts, exits and names in the order threads appear', () => {
+    const timeline = new ThreadTimeline();
+    const at = (seconds: number) => new Date(Date.UTC(2026, 9, 14, 9, 0, seconds));
+
+    timeline.nameThreads([{ id: 1, name: 'main' }]);
+    timeline.update({ reason: 'started', threadId: 7 }, at(1));
+    timeline.update({ reason: 'started', threadId: 8 }, at(2));
+    timeline.nameThreads([
+      { id: 1, name: 'main' },
+      { id: 7, name: 'worker-1' },
+      { id: 8, name: 'worker-2' },
+    ]);
+    timeline.update({ reason: 'exited', threadId: 7 }, at(5));
+
+    expect(timeline.nameOf(8)).toBe('worker-2');
+    expect(timeline.list()).toEqual([
+      { id: 1, name: 'main' },
+      { id: 7, name: 'worker-1', startedAt: at(1).toISOString(), exitedAt: at(5).toISOString() },
+      { id: 8, name: 'worker-2', startedAt: at(2).toISOString() },
+    ]);
+  });
+});
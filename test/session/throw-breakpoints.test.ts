@@ -0,0 +1,107 @@
+/**
+ * Unit tests for throw-site breakpoints (--break-throws)
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import { resolveThrowBreakpoints, throwSiteLines } from '../../src/session/throw-breakpoints.js';
+
+const ORDERS_TS = `export function reserve(order: Order): void {
+  if (!order.items.length) throw new Error('empty order');
+  // throw here once reservations are async
+  log('will throw if stock is short');
+  const glob = 'src/**/*.ts';
+  if (stock < order.quantity) {
+    throw new StockError(order.id);
+  }
+  /* a block comment
+     throw new Error('not code'); */
+}
+`;
+
+const BILLING_PY = `def charge(invoice):
+    """Charge an invoice.
+
+    raise ValueError if it's already paid
+    """
+    if invoice.paid: raise ValueError("paid")
+    message = "raise the alarm"  # raise later
+    raise RuntimeError(message)
+`;
+
+const PRICING_RS = `fn price<'a>(name: &'a str) -> u64 {
+    let label = "panic!(never)";
+    match name {
+        "free" => 0,
+        _ => panic!("unknown {}", name),
+    }
+}
+
+fn later() -> u64 { todo!() }
+`;
+
+describe('throwSiteLines', () => {
+  it('finds throw statements outside strings and comments', () => {
+    expect(throwSiteLines(ORDERS_TS.split('\n'), 'orders.ts')).toEqual([2, 7]);
+  });
+
+  it('finds raise in Python, skipping docstrings', () => {
+    expect(throwSiteLines(BILLING_PY.split('\n'), 'billing.py')).toEqual([6, 8]);
+  });
+
+  it('finds panicking macros in Rust', () => {
+    expect(throwSiteLines(PRICING_RS.split('\n'), 'pricing.rs')).toEqual([5, 9]);
+  });
+
+  it('finds C# throw expressions and Go panics', () => {
+    const cs = ['var name = input ?? throw new ArgumentNullException(nameof(input));'];
+    expect(throwSiteLines(cs, 'Orders.cs')).toEqual([1]);
+    expect(throwSiteLines(['\tpanic(err)', '\tlog.panic(x)'], 'main.go')).toEqual([1]);
+  });
+});
+
+describe('resolveThrowBreakpoints', () => {
+  let dir: string;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-throws-'));
+    fs.mkdirSync(path.join(dir, 'src', 'services'), { recursive: true });
+    fs.mkdirSync(path.join(dir, 'node_modules'));
+    fs.writeFileSync(path.join(dir, 'src', 'services', 'orders.ts'), ORDERS_TS);
+    fs.writeFileSync(path.join(dir, 'src', 'services', 'billing.py'), BILLING_PY);
+    fs.writeFileSync(path.join(dir, 'src', 'util.ts'), 'export const x = 1;\n');
+    fs.writeFileSync(path.join(dir, 'node_modules', 'lib.ts'), 'throw new Error();\n');
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  it("searches directories for the adapter's languages", () => {
+    const orders = path.join(dir, 'src', 'services', 'orders.ts');
+    expect(resolveThrowBreakpoints(['src/services/'], { cwd: dir, adapter: 'node' })).toEqual([
+      `${orders}:2`,
+      `${orders}:7`,
+    ]);
+    expect(resolveThrowBreakpoints(['.'], { cwd: dir })).toHaveLength(4);
+  });
+
+  it('takes files as given', () => {
+    const billing = path.join(dir, 'src', 'services', 'billing.py');
+    expect(resolveThrowBreakpoints(['src/services/billing.py'], { cwd: dir })).toEqual([
+      `${billing}:6`,
+      `${billing}:8`,
+    ]);
+  });
+
+  it('fails for a missing path or one without throw statements', () => {
+    expect(() => resolveThrowBreakpoints(['src/missing'], { cwd: dir })).toThrow(
+      '--break-throws "src/missing": no such file or directory'
+    );
+    expect(() => resolveThrowBreakpoints(['src/util.ts'], { cwd: dir })).toThrow(
+      '--break-throws: no throw statements in src/util.ts'
+    );
+  });
+});
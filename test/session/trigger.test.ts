@@ -0,0 +1,73 @@
+/**
+ * Unit tests for start triggers (--trigger-file, --trigger-signal)
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import { armTrigger, parseTriggerSignal, triggerHint } from '../../src/session/trigger.js';
+
+describe('parseTriggerSignal', () => {
+  it('accepts names with or without SIG', () => {
+    expect(parseTriggerSignal('USR1')).toBe('SIGUSR1');
+    expect(parseTriggerSignal('sigusr2')).toBe('SIGUSR2');
+    expect(parseTriggerSignal('HUP')).toBe('SIGHUP');
+  });
+
+  it('rejects signals that already interrupt the session or are not signals', () => {
+    expect(() => parseTriggerSignal('INT')).toThrow(
+      '--trigger-signal "INT": use one of USR1, USR2, HUP'
+    );
+    expect(() => parseTriggerSignal('go')).toThrow('--trigger-signal "go"');
+  });
+});
+
+describe('triggerHint', () => {
+  it('gives the commands that start the program', () => {
+    expect(triggerHint({ file: '/tmp/go' })).toBe('touch /tmp/go');
+    expect(triggerHint({ file: '/tmp/go', signal: 'SIGUSR1' }, 4242)).toBe(
+      'touch /tmp/go or kill -USR1 4242'
+    );
+  });
+});
+
+describe('armTrigger', () => {
+  let dir: string;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-trigger-'));
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  it('fires once the file appears', async () => {
+    const file = path.join(dir, 'go');
+    const trigger = armTrigger({ file }, 5);
+    setTimeout(() => fs.writeFileSync(file, ''), 20);
+    expect(await trigger.fired).toBe('file');
+  });
+
+  it('fires at once for a file that already exists', async () => {
+    const file = path.join(dir, 'go');
+    fs.writeFileSync(file, '');
+    expect(await armTrigger({ file }).fired).toBe('file');
+  });
+
+  it('fires on the signal and stops listening', async () => {
+    const listeners = process.listenerCount('SIGUSR2');
+    const trigger = armTrigger({ signal: 'SIGUSR2' });
+    expect(process.listenerCount('SIGUSR2')).toBe(listeners + 1);
+    process.emit('SIGUSR2', 'SIGUSR2');
+    expect(await trigger.fired).toBe('signal');
+    expect(process.listenerCount('SIGUSR2')).toBe(listeners);
+  });
+
+  it('resolves with null when cancelled', async () => {
+    const trigger = armTrigger({ file: path.join(dir, 'never'), signal: 'SIGUSR2' }, 5);
+    trigger.cancel();
+    expect(await trigger.fired).toBeNull();
+  });
+});
@@ -2,8 +2,14 @@
  * Unit tests for VariableInspector diffing functionality
  */
 
-import { describe, it, expect, beforeEach } from 'vitest';
-import { VariableInspector } from '../../src/session/variables.js';
+import { describe, it, expect, beforeEach, vi } from 'vitest';
+import {
+  VariableInspector,
+  hasEvaluationContexts,
+  parseAssignment,
+  parseEvaluationSpec,
+} from '../../src/session/variables.js';
+import { CaptureBudget } from '../../src/session/capture-budget.js';
 import type { VariableValue } from '../../src/output/events.js';
 import type { IDapClient } from '../../src/dap/client-interface.js';
 import type {
@@ -260,4 +266,419 @@ describe('VariableInspector', () => {
       ]);
     });
   });
+
+  describe('evaluateExpressions with safeEvaluation', () => {
+    const safeEvaluation = {
+      context: 'hover' as const,
+      rejected: /^EvalError: Possible side-effect/,
+    };
+
+    it('evaluates in the safe context and flags side-effect rejections', async () => {
+      const evaluate = vi.fn(async ({ expression }: { expression: string }) => {
+        if (expression === 'cart.clear()') {
+          throw new Error('EvalError: Possible side-effect in debug-evaluate');
+        }
+        return { result: '3', type: 'number', variablesReference: 0 };
+      });
+      const safeInspector = new VariableInspector({ evaluate } as unknown as IDapClient, {
+        safeEvaluation,
+      });
+
+      const results = await safeInspector.evaluateExpressions(1, ['cart.size', 'cart.clear()']);
+
+      expect(evaluate).toHaveBeenCalledWith({
+        expression: 'cart.size',
+        frameId: 1,
+        context: 'hover',
+      });
+      expect(results['cart.size']).toEqual({ result: '3', type: 'number' });
+      expect(results['cart.clear()']).toEqual({
+        result: '',
+        error: 'EvalError: Possible side-effect in debug-evaluate',
+        sideEffect: true,
+      });
+    });
+
+    it('treats a rejection reported as the result as an error', async () => {
+      const client = {
+        evaluate: async () => ({
+          result: 'EvalError: Possible side-effect in debug-evaluate',
+          variablesReference: 0,
+        }),
+      };
+      const safeInspector = new VariableInspector(client as unknown as IDapClient, {
+        safeEvaluation,
+      });
+
+      const results = await safeInspector.evaluateExpressions(1, ['counter++']);
+      expect(results['counter++'].sideEffect).toBe(true);
+    });
+
+    it('refuses expressions the adapter would still evaluate', async () => {
+      const evaluate = vi.fn();
+      const safeInspector = new VariableInspector({ evaluate } as unknown as IDapClient, {
+        safeEvaluation: { ...safeEvaluation, mutates: (expression) => expression.includes('=') },
+      });
+
+      const results = await safeInspector.evaluateExpressions(1, ['total = 0']);
+
+      expect(evaluate).not.toHaveBeenCalled();
+      expect(results['total = 0']).toEqual({
+        result: '',
+        error: 'total = 0 changes program state',
+        sideEffect: true,
+      });
+    });
+
+    it('rewrites expressions and parses results', async () => {
+      const evaluate = vi.fn(async () => ({ result: '(int) $0 = 7', variablesReference: 0 }));
+      const safeInspector = new VariableInspector({ evaluate } as unknown as IDapClient, {
+        safeEvaluation: {
+          context: 'repl',
+          expression: (expression) => `expr -- ${expression}`,
+          rejected: /JIT/,
+          parseResult: (output) => ({ result: output.split(' = ')[1], type: 'int' }),
+        },
+      });
+
+      const results = await safeInspector.evaluateExpressions(2, ['n']);
+
+      expect(evaluate).toHaveBeenCalledWith({
+        expression: 'expr -- n',
+        frameId: 2,
+        context: 'repl',
+      });
+      expect(results.n).toEqual({ result: '7', type: 'int' });
+    });
+  });
+
+  describe('evaluateExpressions result metadata', () => {
+    const client = {
+      evaluate: async () => ({
+        result: 'Order {id: 7}',
+        type: 'Order',
+        variablesReference: 12,
+        namedVariables: 2,
+        memoryReference: '0x7ffe1000',
+        presentationHint: { kind: 'class', attributes: ['readOnly'] },
+      }),
+      variables: async ({ variablesReference }: { variablesReference: number }) => ({
+        variables:
+          variablesReference === 12
+            ? [
+                { name: 'id', value: '7', type: 'int', variablesReference: 0 },
+                { name: 'customer', value: 'Customer', type: 'Customer', variablesReference: 13 },
+              ]
+            : [{ name: 'name', value: '"Ada"', type: 'string', variablesReference: 0 }],
+      }),
+    };
+
+    it('keeps the type, reference, presentation hint and memory reference', async () => {
+      const metadataInspector = new VariableInspector(client as unknown as IDapClient);
+
+      const results = await metadataInspector.evaluateExpressions(1, ['order']);
+
+      expect(results.order).toEqual({
+        result: 'Order {id: 7}',
+        type: 'Order',
+        variablesReference: 12,
+        namedVariables: 2,
+        memoryReference: '0x7ffe1000',
+        presentationHint: { kind: 'class', attributes: ['readOnly'] },
+      });
+    });
+
+    it('expands structured results to evalExpandDepth levels', async () => {
+      const expandInspector = new VariableInspector(client as unknown as IDapClient, {
+        evalExpandDepth: 1,
+      });
+
+      const results = await expandInspector.evaluateExpressions(1, ['order']);
+
+      expect(results.order.result).toBe('Order {id: 7}');
+      expect(results.order.value).toEqual({
+        id: { type: 'int', value: 7, expandable: false },
+        customer: { type: 'Customer', value: 'Customer', expandable: true, variablesReference: 13 },
+      });
+    });
+  });
+
+  describe('capture budget', () => {
+    const children: Record<number, { name: string; value: string; variablesReference: number }[]> =
+      {
+        1: [
+          { name: 'order', value: 'Order', variablesReference: 10 },
+          { name: 'cache', value: 'Count = 250000', variablesReference: 20 },
+          { name: 'count', value: '3', variablesReference: 0 },
+        ],
+        10: [{ name: 'customer', value: 'Customer', variablesReference: 11 }],
+        11: [{ name: 'name', value: '"Ada"', variablesReference: 0 }],
+      };
+
+    function budgetClient(stalled: number[] = []) {
+      const requested: number[] = [];
+      const client = {
+        scopes: async () => ({ scopes: [{ name: 'Locals', variablesReference: 1 }] }),
+        variables: ({ variablesReference }: { variablesReference: number }) => {
+          requested.push(variablesReference);
+          if (stalled.includes(variablesReference)) return new Promise(() => {});
+          return Promise.resolve({ variables: children[variablesReference] ?? [] });
+        },
+        evaluate: () => new Promise(() => {}),
+      };
+      return { client: client as unknown as IDapClient, requested };
+    }
+
+    it('fetches every local before any of their children', async () => {
+      const { client, requested } = budgetClient();
+      const budgeted = new VariableInspector(client, { maxDepth: 2 });
+
+      const locals = await budgeted.getLocals(1, new CaptureBudget(1000));
+
+      expect(requested).toEqual([1, 10, 20, 11]);
+      // With time to spare, the result matches an unbudgeted capture
+      expect(locals).toEqual(await budgeted.getLocals(1));
+    });
+
+    it('marks values whose children were not fetched in time as truncated', async () => {
+      const { client } = budgetClient([20]);
+      const budgeted = new VariableInspector(client, { maxDepth: 2 });
+
+      const locals = await budgeted.getLocals(1, new CaptureBudget(50));
+
+      expect(locals.cache).toMatchObject({ value: 'Count = 250000', truncated: true });
+      // Queued for the next level when the budget ran out
+      expect(locals.order.value).toEqual({
+        customer: expect.objectContaining({ value: 'Customer', truncated: true }),
+      });
+      expect(locals.count).toEqual({ type: 'unknown', value: '3', expandable: false });
+    });
+
+    it('marks evaluations the budget ran out on as truncated', async () => {
+      const { client } = budgetClient();
+      const budgeted = new VariableInspector(client);
+
+      const results = await budgeted.evaluateExpressions(1, ['order'], new CaptureBudget(20));
+
+      expect(results.order).toEqual({ result: '', truncated: true });
+    });
+  });
+
+  describe('provenance', () => {
+    const variables: Record<number, Record<string, unknown>[]> = {
+      1: [
+        { name: 'order', value: 'Order', variablesReference: 10, evaluateName: 'order' },
+        { name: 'count', value: '3', variablesReference: 0 },
+      ],
+      10: [
+        {
+          name: 'customer',
+          value: 'Customer',
+          variablesReference: 11,
+          evaluateName: 'order.customer',
+        },
+      ],
+      11: [
+        { name: 'name', value: '"Ada"', variablesReference: 0, evaluateName: 'order.customer.name' },
+      ],
+    };
+    const client = {
+      scopes: async () => ({ scopes: [{ name: 'Local: checkout', variablesReference: 1 }] }),
+      variables: async ({ variablesReference }: { variablesReference: number }) => ({
+        variables: variables[variablesReference] ?? [],
+      }),
+      evaluate: async () => ({ result: '', variablesReference: 0 }),
+    } as unknown as IDapClient;
+
+    it('tags locals and their children with frame, scope and reference path', async () => {
+      const tagging = new VariableInspector(client, { provenance: true });
+      const locals = await tagging.getLocals(5, undefined, 2);
+
+      expect(locals.order.provenance).toEqual({
+        frameIndex: 2,
+        scope: 'Local: checkout',
+        referencePath: [1],
+        evaluateName: 'order',
+      });
+      const customer = (locals.order.value as Record<string, VariableValue>).customer;
+      expect(customer.provenance).toEqual({
+        frameIndex: 2,
+        scope: 'Local: checkout',
+        referencePath: [1, 10],
+        evaluateName: 'order.customer',
+      });
+      expect(locals.count.provenance).toEqual({
+        frameIndex: 2,
+        scope: 'Local: checkout',
+        referencePath: [1],
+      });
+      // Breadth-first capture tags values the same way
+      expect(await tagging.getLocals(5, new CaptureBudget(1000), 2)).toEqual(locals);
+    });
+
+    it('leaves provenance out unless asked for', async () => {
+      const locals = await new VariableInspector(client).getLocals(5);
+      expect(locals.order.provenance).toBeUndefined();
+    });
+
+    it('ignores provenance when comparing values', () => {
+      const at = (reference: number) => ({
+        frameIndex: 0,
+        scope: 'Locals',
+        referencePath: [reference],
+      });
+      const a: VariableValue = {
+        type: 'Order',
+        value: { id: { type: 'int', value: 7, provenance: at(1) } },
+      };
+      const b: VariableValue = {
+        type: 'Order',
+        value: { id: { type: 'int', value: 7, provenance: at(2) } },
+      };
+      expect(inspector.valuesEqual(a, b)).toBe(true);
+    });
+  });
+
+  describe('evaluation contexts', () => {
+    it('parses context prefixes', () => {
+      expect(parseEvaluationSpec('repl:order')).toEqual({ expression: 'order', context: 'repl' });
+      expect(parseEvaluationSpec('clipboard: list')).toEqual({
+        expression: 'list',
+        context: 'clipboard',
+      });
+      // Anything else is part of the expression
+      expect(parseEvaluationSpec('std::mem::size_of::<u8>()')).toEqual({
+        expression: 'std::mem::size_of::<u8>()',
+      });
+      expect(parseEvaluationSpec('ok ? a : b')).toEqual({ expression: 'ok ? a : b' });
+    });
+
+    it('detects specs that pick a context', () => {
+      expect(hasEvaluationContexts(['a', 'b'])).toBe(false);
+      expect(hasEvaluationContexts(['a', 'hover:b'])).toBe(true);
+      expect(hasEvaluationContexts(['a'], 'repl')).toBe(true);
+    });
+
+    it('evaluates in the default or prefixed context, keyed by spec', async () => {
+      const evaluate = vi.fn(async () => ({ result: '1', variablesReference: 0 }));
+      const contextInspector = new VariableInspector({ evaluate } as unknown as IDapClient, {
+        evalContext: 'repl',
+      });
+
+      const results = await contextInspector.evaluateExpressions(3, ['order', 'clipboard:order']);
+
+      expect(evaluate.mock.calls.map((call) => (call as unknown[])[0])).toEqual([
+        { expression: 'order', frameId: 3, context: 'repl' },
+        { expression: 'order', frameId: 3, context: 'clipboard' },
+      ]);
+      expect(Object.keys(results)).toEqual(['order', 'clipboard:order']);
+    });
+  });
+});
+
+describe('VariableInspector.getRegisters', () => {
+  type RegisterVariable = { name: string; value: string; ref?: number };
+
+  function inspectorWith(variables: Record<number, RegisterVariable[]>) {
+    const client = {
+      scopes: async () => ({
+        scopes: [
+          { name: 'Locals', variablesReference: 1, expensive: false },
+          { name: 'Registers', variablesReference: 2, expensive: false },
+        ],
+      }),
+      variables: async ({ variablesReference }: { variablesReference: number }) => ({
+        variables: (variables[variablesReference] ?? []).map((v) => ({
+          name: v.name,
+          value: v.value,
+          variablesReference: v.ref ?? 0,
+        })),
+      }),
+    };
+    return new VariableInspector(client as unknown as IDapClient);
+  }
+
+  it("reads lldb's general-purpose register set", async () => {
+    const inspector = inspectorWith({
+      2: [
+        { name: 'General Purpose Registers', value: '', ref: 3 },
+        { name: 'Floating Point Registers', value: '', ref: 4 },
+      ],
+      3: [
+        { name: 'rip', value: '0x0000555555555189' },
+        { name: 'rsp', value: '0x00007fffffffe3d0' },
+      ],
+    });
+
+    expect(await inspector.getRegisters(1)).toEqual({
+      rip: '0x0000555555555189',
+      rsp: '0x00007fffffffe3d0',
+    });
+  });
+
+  it('reads flat register lists', async () => {
+    const inspector = inspectorWith({ 2: [{ name: 'pc', value: '0x401000' }] });
+    expect(await inspector.getRegisters(1)).toEqual({ pc: '0x401000' });
+  });
+
+  it('returns undefined without a register scope', async () => {
+    const inspector = new VariableInspector(mockClient as unknown as IDapClient);
+    expect(await inspector.getRegisters(1)).toBeUndefined();
+  });
+});
+
+describe('VariableInspector.getFrameArguments', () => {
+  function inspectorWith(scopes: string[], variables: Record<string, string>) {
+    const client = {
+      scopes: async () => ({
+        scopes: scopes.map((name, index) => ({
+          name,
+          variablesReference: index + 1,
+          expensive: false,
+        })),
+      }),
+      variables: async () => ({
+        variables: Object.entries(variables).map(([name, value]) => ({
+          name,
+          value,
+          variablesReference: 0,
+        })),
+      }),
+    };
+    return new VariableInspector(client as unknown as IDapClient);
+  }
+
+  it('picks the named parameters out of the locals, in parameter order', async () => {
+    const inspector = inspectorWith(['Local: reserve', 'Closure'], {
+      total: '0',
+      quantity: '3',
+      sku: "'SKU-42'",
+    });
+    expect(await inspector.getFrameArguments(1, ['sku', 'quantity'])).toEqual({
+      sku: "'SKU-42'",
+      quantity: '3',
+    });
+    expect(await inspector.getFrameArguments(1)).toBeUndefined();
+    expect(await inspector.getFrameArguments(1, ['missing'])).toBeUndefined();
+  });
+
+  it('takes an Arguments scope as-is and shortens long values', async () => {
+    const inspector = inspectorWith(['Locals', 'Arguments'], { payload: 'x'.repeat(300) });
+    const args = await inspector.getFrameArguments(1);
+    expect(args?.payload).toBe(`${'x'.repeat(200)}…`);
+  });
+});
+
+describe('parseAssignment', () => {
+  it('splits on the first assignment "="', () => {
+    expect(parseAssignment('order.total=0')).toEqual({ target: 'order.total', value: '0' });
+    expect(parseAssignment(' retry = a == b ')).toEqual({ target: 'retry', value: 'a == b' });
+    expect(parseAssignment('items[i>=0]=null')).toEqual({ target: 'items[i>=0]', value: 'null' });
+  });
+
+  it('rejects specs without an assignment', () => {
+    expect(() => parseAssignment('order.total')).toThrow('expected target=value');
+    expect(() => parseAssignment('a==b')).toThrow('expected target=value');
+    expect(() => parseAssignment('total=')).toThrow('expected target=value');
+  });
 });
@@ -0,0 +1,198 @@
+/**
+ * Unit tests for breakpoint verification (verify)
+ */
+
+import { EventEmitter } from 'node:events';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import { afterEach, beforeEach, describe, it, expect, vi } from 'vitest';
+import {
+  awaitBinding,
+  candidateReasons,
+  formatVerifyReport,
+  indexSourceMaps,
+  parseVerifySpecs,
+  setPendingBreakpoints,
+} from '../../src/session/verify.js';
+import type { IDapClient } from '../../src/dap/client-interface.js';
+
+type SetBreakpointsArgs = { source: { path?: string }; breakpoints?: { line: number }[] };
+
+function fakeClient(setBreakpoints: (args: SetBreakpointsArgs) => unknown) {
+  const client = new EventEmitter() as EventEmitter & {
+    setBreakpoints: ReturnType<typeof vi.fn>;
+    continue: ReturnType<typeof vi.fn>;
+  };
+  client.setBreakpoints = vi.fn(async (args: SetBreakpointsArgs) => setBreakpoints(args));
+  client.continue = vi.fn(async () => ({}));
+  return client;
+}
+
+describe('parseVerifySpecs', () => {
+  it('resolves files against cwd and keeps the spec', () => {
+    expect(parseVerifySpecs(['src/app.ts:10?x > 1'], { cwd: '/repo' })).toEqual([
+      {
+        spec: 'src/app.ts:10?x > 1',
+        file: path.resolve('/repo', 'src/app.ts'),
+        line: 10,
+        condition: 'x > 1',
+        hitCondition: undefined,
+        verified: false,
+      },
+    ]);
+  });
+});
+
+describe('setPendingBreakpoints and awaitBinding', () => {
+  it('records bindings as they arrive and resumes after stops', async () => {
+    const client = fakeClient(({ breakpoints }) => ({
+      breakpoints: breakpoints!.map((bp, i) => ({
+        id: bp.line,
+        verified: i === 0,
+        line: bp.line,
+      })),
+    }));
+    const breakpoints = parseVerifySpecs(['/app/a.ts:10', '/app/a.ts:20', '/app/b.ts:5']);
+
+    await setPendingBreakpoints(client as unknown as IDapClient, breakpoints);
+    expect(client.setBreakpoints).toHaveBeenCalledTimes(2);
+    expect(breakpoints.map((bp) => bp.verified)).toEqual([true, false, true]);
+
+    const ended = awaitBinding(client as unknown as IDapClient, breakpoints, 5000);
+    client.emit('stopped', { reason: 'breakpoint', threadId: 3, hitBreakpointIds: [10] });
+    expect(client.continue).toHaveBeenCalledWith({ threadId: 3 });
+    client.emit('breakpoint', {
+      reason: 'changed',
+      breakpoint: { id: 20, verified: true, line: 21 },
+    });
+
+    await expect(ended).resolves.toBe('all_verified');
+    expect(breakpoints[1]).toMatchObject({ verified: true, boundLine: 21 });
+  });
+
+  it('gives up when the program ends or the timeout passes', async () => {
+    const client = fakeClient(() => ({ breakpoints: [{ id: 1, verified: false }] }));
+    const breakpoints = parseVerifySpecs(['/app/a.ts:10']);
+    await setPendingBreakpoints(client as unknown as IDapClient, breakpoints);
+
+    const terminated = awaitBinding(client as unknown as IDapClient, breakpoints, 5000);
+    client.emit('terminated', {});
+    await expect(terminated).resolves.toBe('terminated');
+
+    vi.useFakeTimers();
+    try {
+      const timedOut = awaitBinding(client as unknown as IDapClient, breakpoints, 100);
+      vi.advanceTimersByTime(100);
+      await expect(timedOut).resolves.toBe('timeout');
+    } finally {
+      vi.useRealTimers();
+    }
+    expect(client.listenerCount('stopped')).toBe(0);
+  });
+});
+
+describe('indexSourceMaps and candidateReasons', () => {
+  let dir: string;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-verify-'));
+    fs.mkdirSync(path.join(dir, 'src'));
+    fs.mkdirSync(path.join(dir, 'dist'));
+    fs.writeFileSync(path.join(dir, 'src', 'app.ts'), 'const a = 1;\nconsole.log(a);\n');
+    fs.writeFileSync(path.join(dir, 'dist', 'app.js'), 'console.log(1);\n');
+    fs.writeFileSync(
+      path.join(dir, 'dist', 'app.js.map'),
+      JSON.stringify({ version: 3, file: 'app.js', sources: ['../src/app.ts'], mappings: '' })
+    );
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  it('links original sources to their maps and generated files', () => {
+    const index = indexSourceMaps(dir);
+    expect(index.get(path.join(dir, 'src', 'app.ts'))).toEqual([
+      { map: path.join(dir, 'dist', 'app.js.map'), generated: path.join(dir, 'dist', 'app.js') },
+    ]);
+  });
+
+  it('explains why a breakpoint did not bind', () => {
+    const file = path.join(dir, 'src', 'app.ts');
+    const later = new Date(Date.now() + 60_000);
+    fs.utimesSync(file, later, later);
+    const [bp] = parseVerifySpecs([`${file}:40`]);
+    bp.message = 'Unbound breakpoint';
+
+    const reasons = candidateReasons('node', bp, {
+      ended: 'timeout',
+      sourceMaps: indexSourceMaps(dir).get(file),
+    });
+
+    expect(reasons.slice(0, 4)).toEqual([
+      'Line 40 is past the end of the file (2 lines)',
+      'Adapter: Unbound breakpoint',
+      'app.ts changed after app.js was built; rebuild',
+      expect.stringContaining('had not loaded this file by the timeout'),
+    ]);
+    expect(reasons).toContain('Ensure "sourceMap": true in tsconfig.json');
+  });
+
+  it('reports missing files, missing maps and moved breakpoints', () => {
+    const [missing] = parseVerifySpecs([path.join(dir, 'src', 'gone.ts:3')]);
+    expect(candidateReasons('node', missing, { ended: 'all_verified', sourceMaps: [] })).toEqual(
+      expect.arrayContaining([
+        `${path.join(dir, 'src', 'gone.ts')} does not exist`,
+        'No source map lists this file; build with source maps enabled',
+      ])
+    );
+
+    const [moved] = parseVerifySpecs([path.join(dir, 'src', 'app.ts:1')]);
+    moved.verified = true;
+    moved.boundLine = 2;
+    expect(candidateReasons('node', moved, { ended: 'all_verified' })).toEqual([
+      'Line 1 has no code; the adapter moved the breakpoint to line 2',
+    ]);
+  });
+});
+
+describe('formatVerifyReport', () => {
+  it('lists each breakpoint with its maps and reasons', () => {
+    const text = formatVerifyReport(
+      {
+        adapter: 'node',
+        program: '/repo/dist/index.js',
+        ended: 'timeout',
+        breakpoints: [
+          {
+            spec: 'src/a.ts:3',
+            file: '/repo/src/a.ts',
+            line: 3,
+            verified: true,
+            bound: { line: 4 },
+            sourceMaps: [{ map: '/repo/dist/a.js.map', generated: '/repo/dist/a.js' }],
+          },
+          {
+            spec: 'src/b.ts:9',
+            file: '/repo/src/b.ts',
+            line: 9,
+            verified: false,
+            reasons: ['Adapter: Unbound breakpoint'],
+          },
+        ],
+      },
+      '/repo'
+    );
+
+    expect(text.split('\n')).toEqual([
+      '1/2 breakpoints bound (node, timeout)',
+      '',
+      '✓ src/a.ts:3 (bound at line 4)',
+      '    source map: dist/a.js.map → dist/a.js',
+      '',
+      '✗ src/b.ts:9',
+      '    - Adapter: Unbound breakpoint',
+    ]);
+  });
+});
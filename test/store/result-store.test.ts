@@ -0,0 +1,111 @@
+/**
+ * Tests for the SQLite result store (--store / query)
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import { ResultStore, isSqliteAvailable } from '../../src/store/result-store.js';
+import type { DebugEvent } from '../../src/output/events.js';
+
+function run(value: number, exitCode: number, evaluation: string): DebugEvent[] {
+  const timestamp = new Date().toISOString();
+  return [
+    { type: 'session_start', timestamp, adapter: 'node', program: 'app.js' },
+    {
+      type: 'breakpoint_hit',
+      timestamp,
+      id: 1,
+      threadId: 1,
+      location: { file: 'src/cart.ts', line: 42, function: 'total' },
+      stackTrace: [],
+      locals: {
+        total: { type: 'number', value },
+        items: { type: 'Array', value: [{ sku: 'a' }] },
+      },
+      evaluations: { 'total * 2': { result: evaluation, type: 'number' } },
+    },
+    {
+      type: 'exception_thrown',
+      timestamp,
+      threadId: 1,
+      exception: { type: 'RangeError', message: 'bad total' },
+      location: { file: 'src/cart.ts', line: 50 },
+      locals: {},
+    },
+    {
+      type: 'session_end',
+      timestamp,
+      summary: {
+        durationMs: 120,
+        exitCode,
+        breakpointsHit: 1,
+        exceptionsCaught: 1,
+        stepsExecuted: 0,
+      },
+    },
+  ];
+}
+
+describe.skipIf(!isSqliteAvailable())('ResultStore', () => {
+  let store: ResultStore;
+
+  beforeEach(() => {
+    store = ResultStore.open(':memory:');
+    for (const [value, exitCode] of [
+      [10, 0],
+      [20, 1],
+      [30, 0],
+    ]) {
+      for (const event of run(value, exitCode, String(value * 2))) {
+        store.record(event);
+      }
+    }
+  });
+
+  afterEach(() => {
+    store.close();
+  });
+
+  it('records one run per session with its summary', () => {
+    const runs = store.query('runs', { last: 10 });
+
+    expect(runs).toHaveLength(3);
+    expect(runs.map((r) => r.exitCode)).toEqual([0, 1, 0]);
+    expect(runs[0]).toMatchObject({ adapter: 'node', program: 'app.js', breakpointsHit: 1 });
+  });
+
+  it('returns a local value across the last N runs', () => {
+    const rows = store.query('value', { expression: 'total', last: 2 });
+
+    expect(rows.map((r) => r.run)).toEqual([2, 3]);
+    expect(rows.map((r) => r.value)).toEqual([20, 30]);
+    expect(rows[0]).toMatchObject({ source: 'local', file: 'src/cart.ts', line: 42 });
+  });
+
+  it('keeps structured locals as JSON', () => {
+    const rows = store.query('value', { expression: 'items', last: 1 });
+
+    expect(rows[0].value).toEqual([{ sku: 'a' }]);
+  });
+
+  it('returns evaluation results for --eval expressions', () => {
+    const rows = store.query('value', { expression: 'total * 2', last: 10 });
+
+    expect(rows.map((r) => r.value)).toEqual(['20', '40', '60']);
+    expect(rows[0].source).toBe('eval');
+  });
+
+  it('aggregates hits and exceptions by location and type', () => {
+    expect(store.query('hits', { last: 10 })).toEqual([
+      { file: 'src/cart.ts', line: 42, function: 'total', hits: 3, runs: 3 },
+    ]);
+    expect(store.query('exceptions', { last: 10 })[0]).toMatchObject({
+      type: 'RangeError',
+      count: 3,
+    });
+  });
+
+  it('rejects unknown queries and a value query without an expression', () => {
+    expect(() => store.query('nope', { last: 10 })).toThrow('Unknown query: nope');
+    expect(() => store.query('value', { last: 10 })).toThrow('requires an expression');
+  });
+});
@@ -0,0 +1,244 @@
+/**
+ * Unit tests for breakpoint suggestions from stack traces
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import {
+  formatSuggestReport,
+  isLibraryFrame,
+  parseException,
+  parseStackTrace,
+  resolveFramePath,
+  suggestBreakpoints,
+} from '../../src/suggest/from-trace.js';
+
+const NODE_TRACE = `/app/src/orders.js:42
+    return order.customer.id;
+                          ^
+
+TypeError: Cannot read properties of undefined (reading 'id')
+    at processOrder (/app/src/orders.js:42:27)
+    at Array.map (<anonymous>)
+    at handle (/app/src/routes/orders.js:17:22)
+    at Layer.handle (/app/node_modules/express/lib/router/layer.js:95:5)
+    at main (/app/src/index.js:8:3)
+    at Module._compile (node:internal/modules/cjs/loader:1256:14)
+`;
+
+const PYTHON_TRACE = `Traceback (most recent call last):
+  File "/srv/app/main.py", line 12, in <module>
+    main()
+  File "/srv/app/main.py", line 8, in main
+    total = checkout(cart)
+  File "/srv/app/shop/cart.py", line 30, in checkout
+    return sum(prices) / len(items)
+  File "/usr/lib/python3.12/statistics.py", line 300, in mean
+    raise StatisticsError('mean requires at least one data point')
+ZeroDivisionError: division by zero
+`;
+
+const DOTNET_TRACE = `Unhandled exception. System.InvalidOperationException: Sequence contains no elements
+   at System.Linq.ThrowHelper.ThrowNoElementsException()
+   at Shop.Orders.OrderService.Latest() in C:\\build\\Shop\\Orders\\OrderService.cs:line 27
+   at Shop.Program.Main(String[] args) in C:\\build\\Shop\\Program.cs:line 9
+`;
+
+const RUST_TRACE = `thread 'main' panicked at src/parser.rs:14:9:
+called \`Option::unwrap()\` on a \`None\` value
+stack backtrace:
+   0: rust_begin_unwind
+             at /rustc/abc123/library/std/src/panicking.rs:645:5
+   1: myapp::parser::parse
+             at ./src/parser.rs:14:9
+   2: myapp::main
+             at ./src/main.rs:5:5
+`;
+
+describe('parseStackTrace', () => {
+  it('parses Node.js frames innermost first', () => {
+    const frames = parseStackTrace(NODE_TRACE);
+    expect(frames[0]).toEqual({
+      file: '/app/src/orders.js',
+      line: 42,
+      column: 27,
+      function: 'processOrder',
+      language: 'node',
+    });
+    expect(frames.map((f) => f.line)).toEqual([42, 17, 95, 8, 1256]);
+  });
+
+  it('reverses Python tracebacks so the innermost frame comes first', () => {
+    const frames = parseStackTrace(PYTHON_TRACE);
+    expect(frames.map((f) => `${path.basename(f.file)}:${f.line}`)).toEqual([
+      'statistics.py:300',
+      'cart.py:30',
+      'main.py:8',
+      'main.py:12',
+    ]);
+    expect(frames[1].function).toBe('checkout');
+  });
+
+  it('parses .NET frames with source locations', () => {
+    const frames = parseStackTrace(DOTNET_TRACE);
+    expect(frames).toHaveLength(2);
+    expect(frames[0]).toMatchObject({
+      file: 'C:\\build\\Shop\\Orders\\OrderService.cs',
+      line: 27,
+      function: 'Shop.Orders.OrderService.Latest()',
+      language: 'dotnet',
+    });
+  });
+
+  it('parses Rust panics and backtraces without duplicating the panic site', () => {
+    const frames = parseStackTrace(RUST_TRACE);
+    expect(frames.map((f) => `${f.file}:${f.line}`)).toEqual([
+      'src/parser.rs:14',
+      '/rustc/abc123/library/std/src/panicking.rs:645',
+      './src/main.rs:5',
+    ]);
+    expect(frames[2].function).toBe('myapp::main');
+  });
+
+  it('parses Go and Java frames', () => {
+    const go = parseStackTrace('main.process(0x0?)\n\t/home/me/app/main.go:12 +0x1d\n');
+    expect(go).toEqual([
+      { file: '/home/me/app/main.go', line: 12, function: 'main.process', language: 'go' },
+    ]);
+
+    const java = parseStackTrace('\tat com.example.Orders.process(Orders.java:42)\n');
+    expect(java[0]).toMatchObject({ file: 'Orders.java', line: 42, language: 'java' });
+  });
+});
+
+describe('parseException', () => {
+  it('finds the exception before the frames', () => {
+    expect(parseException(NODE_TRACE, 'node')).toEqual({
+      type: 'TypeError',
+      message: "Cannot read properties of undefined (reading 'id')",
+    });
+    expect(parseException(DOTNET_TRACE, 'dotnet')).toEqual({
+      type: 'System.InvalidOperationException',
+      message: 'Sequence contains no elements',
+    });
+  });
+
+  it('uses the last exception line for Python', () => {
+    expect(parseException(PYTHON_TRACE, 'python')).toEqual({
+      type: 'ZeroDivisionError',
+      message: 'division by zero',
+    });
+  });
+
+  it('reports Rust panics', () => {
+    expect(parseException(RUST_TRACE, 'rust')).toEqual({
+      type: 'panic',
+      message: 'called `Option::unwrap()` on a `None` value',
+    });
+  });
+});
+
+describe('isLibraryFrame', () => {
+  it('recognizes runtime and dependency frames', () => {
+    expect(isLibraryFrame('/app/node_modules/express/lib/router/layer.js')).toBe(true);
+    expect(isLibraryFrame('node:internal/modules/cjs/loader')).toBe(true);
+    expect(isLibraryFrame('<anonymous>')).toBe(true);
+    expect(isLibraryFrame('/usr/lib/python3.12/statistics.py')).toBe(true);
+    expect(isLibraryFrame('/venv/lib/python3.12/site-packages/flask/app.py')).toBe(true);
+    expect(isLibraryFrame('/rustc/abc123/library/std/src/panicking.rs')).toBe(true);
+    expect(isLibraryFrame('/app/src/orders.js')).toBe(false);
+  });
+});
+
+describe('with a repository', () => {
+  let root: string;
+
+  function writeFile(relativePath: string): void {
+    const file = path.join(root, relativePath);
+    fs.mkdirSync(path.dirname(file), { recursive: true });
+    fs.writeFileSync(file, '');
+  }
+
+  beforeEach(() => {
+    root = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-suggest-'));
+  });
+
+  afterEach(() => {
+    fs.rmSync(root, { recursive: true, force: true });
+  });
+
+  describe('resolveFramePath', () => {
+    it('maps paths from another machine by their trailing segments', () => {
+      writeFile('src/routes/orders.js');
+      writeFile('src/orders.js');
+      const index = new Map([['orders.js', ['src/routes/orders.js', 'src/orders.js']]]);
+
+      expect(resolveFramePath('/app/src/routes/orders.js', root, index)).toEqual({
+        path: 'src/routes/orders.js',
+      });
+      expect(resolveFramePath('/app/src/orders.js', root, index)).toEqual({
+        path: 'src/orders.js',
+      });
+    });
+
+    it('flags equally good matches as ambiguous', () => {
+      const index = new Map([['Program.cs', ['Api/Program.cs', 'Worker/Program.cs']]]);
+      expect(resolveFramePath('C:\\build\\Program.cs', root, index)).toEqual({
+        path: 'Api/Program.cs',
+        ambiguous: true,
+      });
+    });
+
+    it('returns null when nothing matches', () => {
+      expect(resolveFramePath('/app/src/missing.js', root, new Map())).toBeNull();
+    });
+  });
+
+  describe('suggestBreakpoints', () => {
+    it('suggests the innermost application frames and a command', () => {
+      writeFile('src/orders.js');
+      writeFile('src/routes/orders.js');
+      writeFile('src/index.js');
+      writeFile('node_modules/express/lib/router/layer.js');
+
+      const result = suggestBreakpoints(NODE_TRACE, { root, limit: 2 });
+      expect(result.language).toBe('node');
+      expect(result.applicationFrames.map((f) => f.path)).toEqual([
+        'src/orders.js',
+        'src/routes/orders.js',
+        'src/index.js',
+      ]);
+      expect(result.breakpoints).toEqual(['src/orders.js:42', 'src/routes/orders.js:17']);
+      expect(result.command).toBe(
+        'npx debug-run "src/index.js" -a node -b "src/orders.js:42" -b "src/routes/orders.js:17"'
+      );
+    });
+
+    it('uses a program placeholder for compiled languages', () => {
+      writeFile('Shop/Orders/OrderService.cs');
+      writeFile('Shop/Program.cs');
+
+      const result = suggestBreakpoints(DOTNET_TRACE, { root });
+      expect(result.breakpoints).toEqual(['Shop/Orders/OrderService.cs:27', 'Shop/Program.cs:9']);
+      expect(result.command).toMatch(/^npx debug-run <program> -a dotnet /);
+    });
+
+    it('omits the command for languages without an adapter', () => {
+      writeFile('src/main/java/com/example/Orders.java');
+
+      const result = suggestBreakpoints('\tat com.example.Orders.process(Orders.java:42)\n', {
+        root,
+      });
+      expect(result.breakpoints).toEqual(['src/main/java/com/example/Orders.java:42']);
+      expect(result.command).toBeUndefined();
+    });
+
+    it('explains when no frames map to the repository', () => {
+      const result = suggestBreakpoints(PYTHON_TRACE, { root });
+      expect(result.breakpoints).toEqual([]);
+      expect(formatSuggestReport(result)).toContain('none map to files under this directory');
+    });
+  });
+});
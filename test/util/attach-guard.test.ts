@@ -0,0 +1,125 @@
+/**
+ * Unit tests for the attach guardrails
+ */
+
+import { describe, it, expect } from 'vitest';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import {
+  attachRefusals,
+  describeAttachTarget,
+  linuxExecutableName,
+  matchesPattern,
+  parsePsOwner,
+  remoteAttachHost,
+  type AttachTarget,
+} from '../../src/util/attach-guard.js';
+import type { AttachDenyRule } from '../../src/util/config.js';
+
+const source = '/work/.debug-run.toml';
+
+describe('parsePsOwner', () => {
+  it('reads the uid, user and executable name', () => {
+    expect(parsePsOwner('  501 alice    /usr/local/bin/node\n')).toEqual({
+      uid: 501,
+      user: 'alice',
+      name: 'node',
+    });
+    expect(parsePsOwner('')).toBeUndefined();
+  });
+});
+
+describe('linuxExecutableName', () => {
+  it('reads the whole name from the exe link, else from the command line', () => {
+    const proc = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-proc-'));
+    try {
+      fs.mkdirSync(path.join(proc, '42'));
+      fs.symlinkSync('/opt/app/bin/payment-reconciler-worker', path.join(proc, '42', 'exe'));
+      fs.mkdirSync(path.join(proc, '43'));
+      fs.writeFileSync(path.join(proc, '43', 'cmdline'), '/usr/bin/python3.12\0-m\0app\0');
+
+      expect(linuxExecutableName(42, proc)).toBe('payment-reconciler-worker');
+      expect(linuxExecutableName(43, proc)).toBe('python3.12');
+      expect(linuxExecutableName(44, proc)).toBeUndefined();
+    } finally {
+      fs.rmSync(proc, { recursive: true, force: true });
+    }
+  });
+});
+
+describe('matchesPattern', () => {
+  it('matches whole names with * and ? wildcards, ignoring case', () => {
+    expect(matchesPattern('prod-web-01', 'prod-*')).toBe(true);
+    expect(matchesPattern('Postgres', 'postgres')).toBe(true);
+    expect(matchesPattern('java17', 'java??')).toBe(true);
+    expect(matchesPattern('my-postgres', 'postgres')).toBe(false);
+    expect(matchesPattern('db.prod.example.com', '*.prod.example.com')).toBe(true);
+    expect(matchesPattern('dbXprod', 'db.prod')).toBe(false);
+  });
+});
+
+describe('remoteAttachHost', () => {
+  it('finds the host an override connects to', () => {
+    expect(remoteAttachHost({ address: 'api-01.internal' })).toBe('api-01.internal');
+    expect(remoteAttachHost({ connect: { host: 'worker-3', port: 5678 } })).toBe('worker-3');
+    expect(remoteAttachHost({ address: 'localhost' })).toBeUndefined();
+    expect(remoteAttachHost()).toBeUndefined();
+  });
+});
+
+describe('attachRefusals', () => {
+  const target: AttachTarget = {
+    pid: 4312,
+    name: 'postgres',
+    uid: 999,
+    user: 'postgres',
+    hosts: ['prod-db-01'],
+  };
+
+  it('refuses processes owned by another user unless allowed', () => {
+    expect(attachRefusals(target, { denylist: [], uid: 1000 })).toEqual([
+      'PID 4312 belongs to postgres, not the current user; pass --allow-other-user to attach anyway',
+    ]);
+    expect(attachRefusals(target, { denylist: [], uid: 1000, allowOtherUser: true })).toEqual([]);
+    expect(attachRefusals(target, { denylist: [], uid: 999 })).toEqual([]);
+  });
+
+  it('refuses processes whose owner could not be read unless allowed', () => {
+    const unknown: AttachTarget = { pid: 4312, hosts: ['laptop'] };
+    expect(attachRefusals(unknown, { denylist: [], uid: 1000 })).toEqual([
+      "can't tell who owns PID 4312 (is it running?); pass --allow-other-user to attach anyway",
+    ]);
+    expect(attachRefusals(unknown, { denylist: [], uid: 1000, allowOtherUser: true })).toEqual([]);
+    // Windows: owners aren't read or compared
+    expect(attachRefusals(unknown, { denylist: [] })).toEqual([]);
+  });
+
+  it('refuses denylisted process names and hosts', () => {
+    const denylist: AttachDenyRule[] = [
+      { kind: 'process', pattern: 'postgres', source },
+      { kind: 'host', pattern: 'prod-*', source },
+      { kind: 'host', pattern: 'staging-*', source },
+    ];
+
+    expect(attachRefusals(target, { denylist, uid: 999 })).toEqual([
+      `postgres (PID 4312) matches "postgres" in the attach denylist (${source})`,
+      `host prod-db-01 matches "prod-*" in the attach denylist (${source})`,
+    ]);
+    expect(
+      attachRefusals(
+        { pid: 77, name: 'node', uid: 999, hosts: ['laptop'] },
+        { denylist, uid: 999 }
+      )
+    ).toEqual([]);
+  });
+});
+
+describe('describeAttachTarget', () => {
+  it('names the process, its owner and the host', () => {
+    expect(
+      describeAttachTarget({ pid: 4312, name: 'node', user: 'alice', hosts: ['laptop'] })
+    ).toBe('PID 4312 (node, user alice) on laptop');
+    expect(describeAttachTarget({ hosts: ['laptop', 'api-01'] })).toBe('the process on api-01');
+  });
+});
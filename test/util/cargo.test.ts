@@ -0,0 +1,68 @@
+/**
+ * Unit tests for building cargo targets (--cargo-bin, --cargo-example)
+ */
+
+import { describe, it, expect } from 'vitest';
+import { cargoBuildArgs, parseCargoArtifact } from '../../src/util/cargo.js';
+
+function artifact(name: string, kind: string[], executable: string | null) {
+  return JSON.stringify({
+    reason: 'compiler-artifact',
+    package_id: `${name} 0.1.0`,
+    target: { name, kind, crate_types: kind },
+    filenames: executable ? [executable] : [],
+    executable,
+    fresh: false,
+  });
+}
+
+describe('cargoBuildArgs', () => {
+  it('builds the named target with its features', () => {
+    expect(cargoBuildArgs({ kind: 'bin', name: 'server', features: ['tls', 'metrics'] })).toEqual([
+      'build',
+      '--message-format=json-render-diagnostics',
+      '--bin',
+      'server',
+      '--features',
+      'tls,metrics',
+    ]);
+    expect(cargoBuildArgs({ kind: 'example', name: 'replay' })).toEqual([
+      'build',
+      '--message-format=json-render-diagnostics',
+      '--example',
+      'replay',
+    ]);
+  });
+});
+
+describe('parseCargoArtifact', () => {
+  it("finds the target's executable among cargo's messages", () => {
+    const output = [
+      artifact('serde', ['lib'], null),
+      artifact('shop', ['lib'], null),
+      artifact('server', ['bin'], '/work/shop/target/debug/server'),
+      JSON.stringify({ reason: 'build-finished', success: true }),
+    ].join('\n');
+
+    expect(parseCargoArtifact(output, { kind: 'bin', name: 'server' })).toBe(
+      '/work/shop/target/debug/server'
+    );
+  });
+
+  it('matches the target kind, so a bin and an example can share a name', () => {
+    const output = [
+      artifact('replay', ['bin'], '/work/target/debug/replay'),
+      artifact('replay', ['example'], '/work/target/debug/examples/replay'),
+    ].join('\n');
+
+    expect(parseCargoArtifact(output, { kind: 'example', name: 'replay' })).toBe(
+      '/work/target/debug/examples/replay'
+    );
+    expect(parseCargoArtifact(output, { kind: 'bin', name: 'other' })).toBeUndefined();
+  });
+
+  it('skips lines that are not JSON messages', () => {
+    const output = `warning: unused import\n{not json\n${artifact('server', ['bin'], '/t/server')}`;
+    expect(parseCargoArtifact(output, { kind: 'bin', name: 'server' })).toBe('/t/server');
+  });
+});
@@ -0,0 +1,144 @@
+/**
+ * Unit tests for config files (~/.config/debug-run/config.toml, .debug-run.toml)
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import { getAdapter } from '../../src/adapters/index.js';
+import { createStdioAdapter } from '../../src/adapters/stdio.js';
+import {
+  attachDenyRules,
+  configDefaultsFor,
+  readConfigFile,
+  userConfigPath,
+  type ConfigFile,
+} from '../../src/util/config.js';
+
+describe('userConfigPath', () => {
+  it('uses XDG_CONFIG_HOME when set', () => {
+    expect(userConfigPath({ XDG_CONFIG_HOME: '/xdg' })).toBe(
+      path.join('/xdg', 'debug-run', 'config.toml')
+    );
+    expect(userConfigPath({})).toBe(path.join(os.homedir(), '.config', 'debug-run', 'config.toml'));
+  });
+});
+
+describe('readConfigFile', () => {
+  let dir: string;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-config-'));
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  function write(text: string): string {
+    const file = path.join(dir, 'config.toml');
+    fs.writeFileSync(file, text);
+    return file;
+  }
+
+  it('reads [defaults] and [adapters.<name>] tables', () => {
+    const file = write('[defaults]\ntimeout = "2m"\n\n[adapters.python]\njust-my-code = false\n');
+
+    expect(readConfigFile(file)).toEqual({
+      path: file,
+      defaults: { timeout: '2m' },
+      adapters: { python: { 'just-my-code': false } },
+    });
+  });
+
+  it('reads the [attach] denylist', () => {
+    const file = write('[attach]\ndeny-processes = ["postgres"]\ndeny-hosts = ["prod-*"]\n');
+
+    expect(readConfigFile(file)?.attach).toEqual({ processes: ['postgres'], hosts: ['prod-*'] });
+    expect(() => readConfigFile(write('[attach]\ndeny-users = ["root"]'))).toThrow(
+      'unknown key "deny-users" in [attach]'
+    );
+    expect(() => readConfigFile(write('[attach]\ndeny-hosts = "prod-*"'))).toThrow(
+      '"attach.deny-hosts" must be an array of strings'
+    );
+  });
+
+  it('returns undefined for a missing file', () => {
+    expect(readConfigFile(path.join(dir, 'missing.toml'))).toBeUndefined();
+  });
+
+  it('names the file in errors', () => {
+    expect(() => readConfigFile(write('timeout = "2m"'))).toThrow(
+      /config\.toml: unexpected "timeout"; put options under \[defaults\]/
+    );
+    expect(() => readConfigFile(write('[adapters.cobol]\ntimeout = "2m"'))).toThrow(
+      'unknown adapter "cobol"'
+    );
+    expect(() => readConfigFile(write('[defaults]\ntimeout = '))).toThrow(
+      /config\.toml: line 2: expected a value/
+    );
+  });
+});
+
+describe('configDefaultsFor', () => {
+  const user: ConfigFile = {
+    path: '/home/me/.config/debug-run/config.toml',
+    defaults: { timeout: '2m', pretty: true },
+    adapters: { python: { 'just-my-code': false, timeout: '5m' }, node: { 'skip-files': false } },
+  };
+  const project: ConfigFile = {
+    path: '/work/app/.debug-run.toml',
+    defaults: { timeout: '30s' },
+    adapters: {},
+  };
+
+  it('layers adapter tables over defaults and later files over earlier ones', () => {
+    const defaults = configDefaultsFor([user, project], getAdapter('debugpy')!);
+
+    expect(Object.fromEntries(defaults)).toEqual({
+      timeout: { value: '30s', source: project.path },
+      pretty: { value: true, source: user.path },
+      'just-my-code': { value: false, source: user.path },
+    });
+  });
+
+  it('applies only the tables for the adapter in use', () => {
+    const defaults = configDefaultsFor([user], getAdapter('node')!);
+
+    expect(defaults.get('timeout')?.value).toBe('2m');
+    expect(defaults.get('skip-files')?.value).toBe(false);
+    expect(defaults.has('just-my-code')).toBe(false);
+  });
+
+  it('applies a stdio: table to that command line', () => {
+    const file: ConfigFile = {
+      path: '/work/app/.debug-run.toml',
+      defaults: {},
+      adapters: { 'stdio:./my-adapter': { timeout: '1m' } },
+    };
+
+    const defaults = configDefaultsFor([file], createStdioAdapter('stdio:./my-adapter')!);
+    expect(defaults.get('timeout')?.value).toBe('1m');
+    expect(configDefaultsFor([file], getAdapter('node')!).has('timeout')).toBe(false);
+  });
+});
+
+describe('attachDenyRules', () => {
+  it('collects the patterns of every file', () => {
+    const files: ConfigFile[] = [
+      { path: '/home/me/config.toml', defaults: {}, adapters: {} },
+      {
+        path: '/work/.debug-run.toml',
+        defaults: {},
+        adapters: {},
+        attach: { processes: ['postgres'], hosts: ['prod-*'] },
+      },
+    ];
+
+    expect(attachDenyRules(files)).toEqual([
+      { kind: 'process', pattern: 'postgres', source: '/work/.debug-run.toml' },
+      { kind: 'host', pattern: 'prod-*', source: '/work/.debug-run.toml' },
+    ]);
+  });
+});
@@ -0,0 +1,201 @@
+/**
+ * Unit tests for the diagnostic logger
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import { Writable } from 'node:stream';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import { Logger, formatText, type LogRecord } from '../../src/util/logger.js';
+
+function createCapture(): { stream: Writable; lines: string[] } {
+  const lines: string[] = [];
+  const stream = new Writable({
+    write(chunk: Buffer, _encoding, callback) {
+      lines.push(...chunk.toString().split('\n').filter(Boolean));
+      callback();
+    },
+  });
+  return { stream, lines };
+}
+
+describe('Logger', () => {
+  let savedDebugDap: string | undefined;
+
+  beforeEach(() => {
+    savedDebugDap = process.env.DEBUG_DAP;
+    delete process.env.DEBUG_DAP;
+  });
+
+  afterEach(() => {
+    if (savedDebugDap === undefined) {
+      delete process.env.DEBUG_DAP;
+    } else {
+      process.env.DEBUG_DAP = savedDebugDap;
+    }
+  });
+
+  describe('verbosity', () => {
+    it('only writes warnings and errors by default', () => {
+      const { stream, lines } = createCapture();
+      const logger = new Logger({ stream, format: 'json' });
+
+      logger.error('test', 'e');
+      logger.warn('test', 'w');
+      logger.info('test', 'i');
+      logger.debug('test', 'd');
+      logger.trace('test', 't');
+
+      expect(lines.map((l) => JSON.parse(l).msg)).toEqual(['e', 'w']);
+    });
+
+    it('enables more levels as verbosity increases', () => {
+      const logger = new Logger({ verbosity: 2, stream: createCapture().stream });
+
+      expect(logger.enabled('info')).toBe(true);
+      expect(logger.enabled('debug')).toBe(true);
+      expect(logger.enabled('trace')).toBe(false);
+    });
+
+    it('treats DEBUG_DAP as maximum verbosity', () => {
+      process.env.DEBUG_DAP = '1';
+      const logger = new Logger({ stream: createCapture().stream });
+
+      expect(logger.enabled('trace')).toBe(true);
+    });
+  });
+
+  describe('json format', () => {
+    it('writes one record per line with fields', () => {
+      const { stream, lines } = createCapture();
+      const logger = new Logger({ verbosity: 2, stream, format: 'json' });
+
+      logger.debug('dap', 'TX', { command: 'launch' });
+
+      expect(lines).toHaveLength(1);
+      const record = JSON.parse(lines[0]);
+      expect(record.level).toBe('debug');
+      expect(record.target).toBe('dap');
+      expect(record.msg).toBe('TX');
+      expect(record.command).toBe('launch');
+      expect(typeof record.ts).toBe('string');
+    });
+  });
+
+  describe('spans', () => {
+    it('tags records with the active span path', () => {
+      const { stream, lines } = createCapture();
+      const logger = new Logger({ verbosity: 1, stream, format: 'json' });
+
+      const session = logger.span('session');
+      const phase = logger.span('configuring');
+      logger.info('test', 'inside');
+      phase.end();
+      logger.info('test', 'outside');
+      session.end();
+
+      const records = lines.map((l) => JSON.parse(l));
+      expect(records.find((r) => r.msg === 'inside').span).toBe('session/configuring');
+      expect(records.find((r) => r.msg === 'outside').span).toBe('session');
+    });
+
+    it('logs span duration on exit', () => {
+      const { stream, lines } = createCapture();
+      const logger = new Logger({ verbosity: 1, stream, format: 'json' });
+
+      const span = logger.span('launching');
+      span.end({ ok: true });
+      span.end();
+
+      const exits = lines.map((l) => JSON.parse(l)).filter((r) => r.msg === 'exit launching');
+      expect(exits).toHaveLength(1);
+      expect(exits[0].ok).toBe(true);
+      expect(typeof exits[0].durationMs).toBe('number');
+    });
+
+    it('closes the span when inSpan throws', async () => {
+      const { stream, lines } = createCapture();
+      const logger = new Logger({ verbosity: 1, stream, format: 'json' });
+
+      await expect(
+        logger.inSpan('connecting', async () => {
+          throw new Error('refused');
+        })
+      ).rejects.toThrow('refused');
+      logger.info('test', 'after');
+
+      const records = lines.map((l) => JSON.parse(l));
+      expect(records.find((r) => r.msg === 'exit connecting').error).toBe('refused');
+      expect(records.find((r) => r.msg === 'after').span).toBeUndefined();
+    });
+
+    it('notifies span observers with parent IDs even when verbosity is 0', () => {
+      const { stream, lines } = createCapture();
+      const logger = new Logger({ stream });
+      const started: Array<{ name: string; id: number; parentId?: number }> = [];
+      const ended: Array<Record<string, unknown> | undefined> = [];
+      const unobserve = logger.observeSpans({
+        spanStarted: (span) => started.push(span),
+        spanEnded: (_span, _endTime, fields) => ended.push(fields),
+      });
+
+      const session = logger.span('session');
+      logger.span('running').end();
+      session.end({ exitCode: 0 });
+      unobserve();
+      logger.span('ignored').end();
+
+      expect(lines).toHaveLength(0);
+      expect(started.map((s) => s.name)).toEqual(['session', 'running']);
+      expect(started[1].parentId).toBe(started[0].id);
+      expect(ended).toEqual([undefined, { exitCode: 0 }]);
+    });
+  });
+
+  describe('log file', () => {
+    it('appends records to the file', () => {
+      const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-log-'));
+      const file = path.join(dir, 'debug.log');
+      try {
+        const logger = new Logger({ file });
+        logger.warn('test', 'to file');
+        logger.close();
+
+        expect(fs.readFileSync(file, 'utf-8')).toContain('to file');
+      } finally {
+        fs.rmSync(dir, { recursive: true, force: true });
+      }
+    });
+  });
+});
+
+describe('formatText', () => {
+  it('renders level, span, target, message and fields', () => {
+    const record: LogRecord = {
+      ts: '2024-01-01T00:00:00.000Z',
+      level: 'info',
+      target: 'session',
+      msg: 'stopped',
+      span: 'session/running',
+      reason: 'breakpoint',
+      threadId: 1,
+    };
+
+    expect(formatText(record)).toBe(
+      '2024-01-01T00:00:00.000Z INFO  session/running session: stopped reason=breakpoint threadId=1'
+    );
+  });
+
+  it('omits undefined fields', () => {
+    const record: LogRecord = {
+      ts: 't',
+      level: 'warn',
+      target: 'dap',
+      msg: 'm',
+      extra: undefined,
+    };
+
+    expect(formatText(record)).toBe('t WARN  dap: m');
+  });
+});
@@ -0,0 +1,147 @@
+/**
+ * Unit tests for Node inspector discovery (--discover)
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import * as fs from 'node:fs';
+import * as http from 'node:http';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import type { AddressInfo } from 'node:net';
+import {
+  formatInspectorTargets,
+  listeningSockets,
+  matchInspectors,
+  parseInspectorList,
+  probeInspector,
+  type InspectorTarget,
+} from '../../src/util/node-inspector.js';
+
+const API: InspectorTarget = {
+  host: '127.0.0.1',
+  port: 9229,
+  pid: 4312,
+  script: '/srv/api/server.js',
+  title: '/srv/api/server.js',
+  webSocketDebuggerUrl: 'ws://127.0.0.1:9229/1b45c707',
+};
+const WORKER: InspectorTarget = {
+  host: '127.0.0.1',
+  port: 40511,
+  script: '/srv/jobs/worker.js',
+  title: 'worker',
+  webSocketDebuggerUrl: 'ws://127.0.0.1:40511/9f0e8d2a',
+};
+
+describe('parseInspectorList', () => {
+  it('takes the entry script from the file URL and skips non-node targets', () => {
+    const list = [
+      {
+        type: 'node',
+        title: 'server.js',
+        url: 'file:///srv/api/server.js',
+        webSocketDebuggerUrl: 'ws://127.0.0.1:9229/1b45c707',
+      },
+      { type: 'page', title: 'chrome tab', webSocketDebuggerUrl: 'ws://127.0.0.1:9229/aa' },
+    ];
+
+    expect(parseInspectorList('127.0.0.1', 9229, list)).toEqual([
+      {
+        host: '127.0.0.1',
+        port: 9229,
+        script: '/srv/api/server.js',
+        title: 'server.js',
+        webSocketDebuggerUrl: 'ws://127.0.0.1:9229/1b45c707',
+      },
+    ]);
+    expect(parseInspectorList('127.0.0.1', 9229, { error: 'not a list' })).toEqual([]);
+  });
+});
+
+describe('listeningSockets', () => {
+  let dir: string;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-proc-'));
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  it('reads local listening sockets from /proc/net', () => {
+    const header = 'sl local_address rem_address st queues timers retrnsmt uid timeout inode';
+    fs.writeFileSync(
+      path.join(dir, 'tcp'),
+      [
+        header,
+        // 127.0.0.1:9229 listening, a connection to it, and a socket on another address
+        '0: 0100007F:240D 00000000:0000 0A 00000000:00000000 00:00000000 00000000 1000 0 51234',
+        '1: 0100007F:240D 0100007F:C350 01 00000000:00000000 00:00000000 00000000 1000 0 51238',
+        '2: 0501A8C0:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000 1000 0 51240',
+      ].join('\n')
+    );
+
+    expect(listeningSockets(dir)).toEqual([{ host: '127.0.0.1', port: 9229, inode: '51234' }]);
+  });
+});
+
+describe('probeInspector', () => {
+  let server: http.Server;
+  let port: number;
+
+  beforeEach(async () => {
+    server = http.createServer((request, response) => {
+      response.end(
+        request.url === '/json/list'
+          ? JSON.stringify([
+              {
+                type: 'node',
+                title: 'app.js',
+                url: 'file:///work/app.js',
+                webSocketDebuggerUrl: 'ws://x',
+              },
+            ])
+          : 'not found'
+      );
+    });
+    await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+    port = (server.address() as AddressInfo).port;
+  });
+
+  afterEach(async () => {
+    await new Promise((resolve) => server.close(resolve));
+  });
+
+  it('reads the targets an inspector lists', async () => {
+    const targets = await probeInspector('127.0.0.1', port);
+
+    expect(targets.map((target) => target.script)).toEqual(['/work/app.js']);
+  });
+
+  it('finds nothing on ports without an inspector', async () => {
+    await new Promise((resolve) => server.close(resolve));
+    server = http.createServer();
+
+    expect(await probeInspector('127.0.0.1', port, 200)).toEqual([]);
+  });
+});
+
+describe('matchInspectors', () => {
+  it('matches a port, pid or part of the script or title', () => {
+    expect(matchInspectors([API, WORKER], '40511')).toEqual([WORKER]);
+    expect(matchInspectors([API, WORKER], '4312')).toEqual([API]);
+    expect(matchInspectors([API, WORKER], 'api/')).toEqual([API]);
+    expect(matchInspectors([API, WORKER], 'worker')).toEqual([WORKER]);
+    expect(matchInspectors([API, WORKER], 'billing')).toEqual([]);
+  });
+});
+
+describe('formatInspectorTargets', () => {
+  it('numbers the targets with their address, pid and script', () => {
+    expect(formatInspectorTargets([API, WORKER]).split('\n')).toEqual([
+      '  1. 127.0.0.1:9229   pid 4312  /srv/api/server.js',
+      '  2. 127.0.0.1:40511  /srv/jobs/worker.js',
+    ]);
+  });
+});
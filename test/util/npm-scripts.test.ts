@@ -0,0 +1,134 @@
+/**
+ * Unit tests for npm script resolution
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import {
+  detectPackageManager,
+  expandScriptCommand,
+  resolveNpmScript,
+  splitCommandLine,
+} from '../../src/util/npm-scripts.js';
+
+describe('splitCommandLine', () => {
+  it('splits on whitespace and honors quotes', () => {
+    expect(splitCommandLine(`node app.js --name "my app" --tag 'a b'`)).toEqual([
+      'node',
+      'app.js',
+      '--name',
+      'my app',
+      '--tag',
+      'a b',
+    ]);
+  });
+});
+
+describe('expandScriptCommand', () => {
+  it('expands a plain node invocation', () => {
+    expect(expandScriptCommand('node --enable-source-maps dist/server.js --port 3000')).toEqual({
+      program: 'dist/server.js',
+      runtimeArgs: ['--enable-source-maps'],
+      args: ['--port', '3000'],
+      env: undefined,
+    });
+  });
+
+  it('keeps values of runtime flags that take an argument', () => {
+    expect(expandScriptCommand('node -r dotenv/config src/main.js')?.runtimeArgs).toEqual([
+      '-r',
+      'dotenv/config',
+    ]);
+  });
+
+  it('turns tsx into an in-process loader', () => {
+    expect(expandScriptCommand('tsx src/index.ts')).toEqual({
+      program: 'src/index.ts',
+      runtimeArgs: ['--import', 'tsx'],
+      args: undefined,
+      env: undefined,
+    });
+  });
+
+  it('does not expand tsx or ts-node subcommands', () => {
+    expect(expandScriptCommand('tsx watch src/index.ts')).toBeNull();
+    expect(expandScriptCommand('tsx --env-file .env watch src/index.ts')).toBeNull();
+    expect(expandScriptCommand('ts-node help')).toBeNull();
+    expect(expandScriptCommand('ts-node src/main.ts')?.program).toBe('src/main.ts');
+  });
+
+  it('extracts leading environment assignments', () => {
+    expect(expandScriptCommand('NODE_ENV=development node app.js')?.env).toEqual({
+      NODE_ENV: 'development',
+    });
+  });
+
+  it('does not expand shell pipelines or other tools', () => {
+    expect(expandScriptCommand('npm run build && node dist/app.js')).toBeNull();
+    expect(expandScriptCommand('next dev')).toBeNull();
+    expect(expandScriptCommand('node')).toBeNull();
+  });
+});
+
+describe('resolveNpmScript', () => {
+  let dir: string;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-npm-'));
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  function writePackageJson(content: Record<string, unknown>): void {
+    fs.writeFileSync(path.join(dir, 'package.json'), JSON.stringify(content));
+  }
+
+  it('detects the package manager from lockfiles', () => {
+    fs.writeFileSync(path.join(dir, 'pnpm-lock.yaml'), '');
+    expect(detectPackageManager(dir)).toBe('pnpm');
+  });
+
+  it('prefers the packageManager field', () => {
+    fs.writeFileSync(path.join(dir, 'package-lock.json'), '{}');
+    expect(detectPackageManager(dir, { packageManager: 'yarn@4.1.0' })).toBe('yarn');
+  });
+
+  it('resolves an expandable script to an absolute program', () => {
+    writePackageJson({ scripts: { 'start:server': 'node server.js' } });
+
+    const launch = resolveNpmScript(dir, 'start:server');
+
+    expect(launch.program).toBe(path.join(dir, 'server.js'));
+    expect(launch.runtimeExecutable).toBeUndefined();
+    expect(launch.cwd).toBe(dir);
+  });
+
+  it('runs other scripts through the package manager', () => {
+    writePackageJson({ scripts: { dev: 'nodemon src/app.js' } });
+    fs.writeFileSync(path.join(dir, 'yarn.lock'), '');
+
+    const launch = resolveNpmScript(dir, 'dev');
+
+    expect(launch.runtimeExecutable).toBe('yarn');
+    expect(launch.runtimeArgs).toEqual(['run', 'dev']);
+    expect(launch.args).toEqual(['--']);
+    expect(launch.program).toBeUndefined();
+  });
+
+  it("runs the package manager's .cmd shim on Windows", () => {
+    writePackageJson({ scripts: { dev: 'nodemon src/app.js' } });
+
+    expect(resolveNpmScript(dir, 'dev', 'win32').runtimeExecutable).toBe('npm.cmd');
+    expect(resolveNpmScript(dir, 'dev', 'linux').runtimeExecutable).toBe('npm');
+  });
+
+  it('lists available scripts when the script is missing', () => {
+    writePackageJson({ scripts: { start: 'node index.js', test: 'vitest' } });
+
+    expect(() => resolveNpmScript(dir, 'serve')).toThrow('Available scripts: start, test');
+  });
+});
@@ -0,0 +1,140 @@
+/**
+ * Unit tests for reading .NET assembly debug information and SourceLink maps
+ */
+
+import { describe, it, expect } from 'vitest';
+import { deflateRawSync } from 'node:zlib';
+import {
+  readAssemblyDebugInfo,
+  sourceLinkDocuments,
+  sourceLinkUrl,
+  symbolServerKey,
+} from '../../src/util/pdb.js';
+
+const PORTABLE = 0x504d;
+
+/**
+ * A minimal PE32 image whose only section holds a debug directory with these entries
+ */
+function peImage(entries: { type: number; minor?: number; data: Buffer }[]): Buffer {
+  const header = Buffer.alloc(0x200);
+  header.write('MZ', 0, 'latin1');
+  header.writeUInt32LE(0x80, 0x3c);
+  header.write('PE\0\0', 0x80, 'latin1');
+  header.writeUInt16LE(1, 0x86);
+  header.writeUInt16LE(0xe0, 0x94);
+  header.writeUInt16LE(0x10b, 0x98);
+  header.writeUInt32LE(16, 0x98 + 92);
+  header.writeUInt32LE(0x2000, 0x98 + 96 + 6 * 8);
+  header.writeUInt32LE(entries.length * 28, 0x98 + 96 + 6 * 8 + 4);
+  header.writeUInt32LE(0x1000, 0x178 + 8);
+  header.writeUInt32LE(0x2000, 0x178 + 12);
+  header.writeUInt32LE(0x200, 0x178 + 20);
+
+  const directory = Buffer.alloc(entries.length * 28);
+  let offset = header.length + directory.length;
+  entries.forEach((entry, index) => {
+    directory.writeUInt16LE(entry.minor ?? 0, index * 28 + 10);
+    directory.writeUInt32LE(entry.type, index * 28 + 12);
+    directory.writeUInt32LE(entry.data.length, index * 28 + 16);
+    directory.writeUInt32LE(offset, index * 28 + 24);
+    offset += entry.data.length;
+  });
+  return Buffer.concat([header, directory, ...entries.map((entry) => entry.data)]);
+}
+
+function codeView(pdbPath: string, age = 1): Buffer {
+  const fixed = Buffer.alloc(24);
+  fixed.write('RSDS', 0, 'latin1');
+  Buffer.from('00112233445566778899aabbccddeeff', 'hex').copy(fixed, 4);
+  fixed.writeUInt32LE(age, 20);
+  return Buffer.concat([fixed, Buffer.from(`${pdbPath}\0`)]);
+}
+
+function embeddedPdb(pdb: Buffer): Buffer {
+  const fixed = Buffer.alloc(8);
+  fixed.write('MPDB', 0, 'latin1');
+  fixed.writeUInt32LE(pdb.length, 4);
+  return Buffer.concat([fixed, deflateRawSync(pdb)]);
+}
+
+const SOURCE_LINK =
+  '{"documents":{"/_/*":"https://raw.githubusercontent.com/acme/orders/4f2c9e1/*"}}';
+
+describe('readAssemblyDebugInfo', () => {
+  it('reads the CodeView entry and the embedded PDB', () => {
+    const pdb = Buffer.from(`BSJB\0\x01${SOURCE_LINK}\0`, 'latin1');
+    const info = readAssemblyDebugInfo(
+      peImage([
+        { type: 2, minor: PORTABLE, data: codeView('/_/src/Orders/obj/Release/Orders.pdb') },
+        { type: 17, data: embeddedPdb(pdb) },
+      ])
+    );
+    expect(info.codeView).toEqual({
+      pdbPath: '/_/src/Orders/obj/Release/Orders.pdb',
+      guid: '33221100554477668899AABBCCDDEEFF',
+      age: 1,
+      portable: true,
+    });
+    expect(info.embeddedPdb).toEqual(pdb);
+  });
+
+  it('has nothing for an image without a debug directory', () => {
+    expect(readAssemblyDebugInfo(peImage([]))).toEqual({});
+  });
+
+  it('rejects files that are not PE images', () => {
+    expect(() => readAssemblyDebugInfo(Buffer.from('#!/bin/sh\necho hi\n'))).toThrow(
+      'Not a PE image'
+    );
+  });
+});
+
+describe('symbolServerKey', () => {
+  it('files portable PDBs under FFFFFFFF and Windows PDBs under their age', () => {
+    const guid = '33221100554477668899AABBCCDDEEFF';
+    expect(
+      symbolServerKey({ pdbPath: 'C:\\build\\Orders.pdb', guid, age: 1, portable: true })
+    ).toBe('orders.pdb/33221100554477668899aabbccddeeffffffffff/orders.pdb');
+    expect(symbolServerKey({ pdbPath: 'Native.pdb', guid, age: 26, portable: false })).toBe(
+      'native.pdb/33221100554477668899aabbccddeeff1a/native.pdb'
+    );
+  });
+});
+
+describe('sourceLinkDocuments', () => {
+  it('finds the map among other blobs', () => {
+    const pdb = Buffer.from(`\x08{"documents": oops}\x50${SOURCE_LINK}\x10more`, 'latin1');
+    expect(sourceLinkDocuments(pdb)).toEqual({
+      '/_/*': 'https://raw.githubusercontent.com/acme/orders/4f2c9e1/*',
+    });
+  });
+
+  it('is undefined for a PDB without SourceLink', () => {
+    expect(sourceLinkDocuments(Buffer.from('BSJB\0{"other":1}'))).toBeUndefined();
+  });
+});
+
+describe('sourceLinkUrl', () => {
+  const documents = {
+    'C:\\src\\*': 'https://example.com/app/*',
+    'C:\\src\\vendor\\*': 'https://example.com/vendor/*',
+    'C:\\src\\Generated.cs': 'https://example.com/generated.cs',
+  };
+
+  it('uses exact entries, then the longest matching prefix', () => {
+    expect(sourceLinkUrl(documents, 'C:\\src\\Generated.cs')).toBe(
+      'https://example.com/generated.cs'
+    );
+    expect(sourceLinkUrl(documents, 'c:\\SRC\\vendor\\Json\\Reader.cs')).toBe(
+      'https://example.com/vendor/Json/Reader.cs'
+    );
+    expect(sourceLinkUrl(documents, 'C:\\src\\Orders.cs')).toBe(
+      'https://example.com/app/Orders.cs'
+    );
+  });
+
+  it('is undefined for paths the map does not cover', () => {
+    expect(sourceLinkUrl(documents, '/home/me/Orders.cs')).toBeUndefined();
+  });
+});
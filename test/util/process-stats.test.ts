@@ -0,0 +1,35 @@
+/**
+ * Unit tests for debuggee CPU time sampling
+ */
+
+import { describe, it, expect } from 'vitest';
+import { parseProcStat, parsePsCpuTime, readCpuTimeMs } from '../../src/util/process-stats.js';
+
+describe('parseProcStat', () => {
+  it('adds utime and stime, even when the command name has spaces', () => {
+    const stat = '4242 (my app) S 1 4242 4242 0 -1 4194560 1234 0 0 0 250 75 0 0 20 0 4 0';
+    expect(parseProcStat(stat)).toBe(3250);
+  });
+
+  it('returns undefined for truncated input', () => {
+    expect(parseProcStat('4242 (node) S 1')).toBeUndefined();
+  });
+});
+
+describe('parsePsCpuTime', () => {
+  it('parses minutes, hours and days', () => {
+    expect(parsePsCpuTime('0:01.25\n')).toBe(1250);
+    expect(parsePsCpuTime('1:02:03')).toBe(3723000);
+    expect(parsePsCpuTime('2-00:00:01')).toBe(172801000);
+    expect(parsePsCpuTime('')).toBeUndefined();
+  });
+});
+
+describe('readCpuTimeMs', () => {
+  it.skipIf(process.platform !== 'linux' && process.platform !== 'darwin')(
+    'reads the CPU time of a running process',
+    () => {
+      expect(readCpuTimeMs(process.pid)).toBeGreaterThanOrEqual(0);
+    }
+  );
+});
@@ -0,0 +1,184 @@
+/**
+ * Unit tests for process tree cleanup
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import { spawn } from 'node:child_process';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import {
+  findDescendants,
+  getPidDir,
+  isProcessAlive,
+  killProcessTree,
+  parseProcessTable,
+  processGroupSpawnOptions,
+  processStartTime,
+  sweepOrphanedProcesses,
+  trackProcess,
+  untrackProcess,
+} from '../../src/util/process-tree.js';
+
+/** A PID far above any default pid_max, so never alive */
+const DEAD_PID = 2 ** 30;
+
+describe('parseProcessTable', () => {
+  it('maps parents to their children', () => {
+    const table = parseProcessTable('    1     0\n  100     1\n  101   100\n  102   100\n');
+    expect(table.get(1)).toEqual([100]);
+    expect(table.get(100)).toEqual([101, 102]);
+    expect(table.get(101)).toBeUndefined();
+  });
+
+  it('ignores blank and malformed lines', () => {
+    const table = parseProcessTable('\n  garbage\n  200   1\n');
+    expect([...table.entries()]).toEqual([[1, [200]]]);
+  });
+});
+
+describe('isProcessAlive', () => {
+  it('detects the current process', () => {
+    expect(isProcessAlive(process.pid)).toBe(true);
+  });
+
+  it('reports missing processes as dead', () => {
+    expect(isProcessAlive(DEAD_PID)).toBe(false);
+  });
+});
+
+describe.skipIf(process.platform === 'win32')('processStartTime', () => {
+  it('reads the same start time until the process is gone', () => {
+    const started = processStartTime(process.pid);
+    expect(started).toMatch(/\S/);
+    expect(processStartTime(process.pid)).toBe(started);
+    expect(processStartTime(DEAD_PID)).toBeUndefined();
+  });
+});
+
+describe.skipIf(process.platform === 'win32')('killProcessTree', () => {
+  it('kills a process and its children', async () => {
+    // Parent that spawns a long-lived child
+    const script = [
+      "const { spawn } = require('node:child_process');",
+      "spawn(process.execPath, ['-e', 'setInterval(() => {}, 1000)'], { stdio: 'ignore' });",
+      'setInterval(() => {}, 1000);',
+    ].join('\n');
+    const parent = spawn(process.execPath, ['-e', script], {
+      stdio: 'ignore',
+      ...processGroupSpawnOptions(),
+    });
+    const pid = parent.pid!;
+
+    let descendants: number[] = [];
+    for (let i = 0; i < 100 && descendants.length === 0; i++) {
+      await new Promise((resolve) => setTimeout(resolve, 50));
+      descendants = findDescendants(pid);
+    }
+    expect(descendants.length).toBeGreaterThan(0);
+
+    await killProcessTree(pid, { graceMs: 2000 });
+    // Let the OS reap the parent
+    await new Promise((resolve) => setTimeout(resolve, 100));
+
+    expect(isProcessAlive(pid)).toBe(false);
+    for (const child of descendants) {
+      expect(isProcessAlive(child)).toBe(false);
+    }
+  });
+});
+
+describe('PID tracking', () => {
+  let tempDir: string;
+  let originalHome: string | undefined;
+
+  beforeEach(() => {
+    tempDir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-pids-'));
+    originalHome = process.env.DEBUG_RUN_HOME;
+    process.env.DEBUG_RUN_HOME = tempDir;
+  });
+
+  afterEach(() => {
+    if (originalHome === undefined) {
+      delete process.env.DEBUG_RUN_HOME;
+    } else {
+      process.env.DEBUG_RUN_HOME = originalHome;
+    }
+    fs.rmSync(tempDir, { recursive: true, force: true });
+  });
+
+  it('writes and removes PID files', () => {
+    trackProcess(DEAD_PID, 'node');
+    const files = fs.readdirSync(getPidDir());
+    expect(files).toEqual([`${process.pid}-${DEAD_PID}.json`]);
+
+    const record = JSON.parse(fs.readFileSync(path.join(getPidDir(), files[0]), 'utf-8'));
+    expect(record).toMatchObject({ owner: process.pid, pid: DEAD_PID, command: 'node' });
+
+    untrackProcess(DEAD_PID);
+    expect(fs.readdirSync(getPidDir())).toEqual([]);
+  });
+
+  it('leaves PID files owned by a running debug-run alone', async () => {
+    trackProcess(DEAD_PID, 'node');
+    expect(await sweepOrphanedProcesses()).toBe(0);
+    expect(fs.readdirSync(getPidDir())).toHaveLength(1);
+    untrackProcess(DEAD_PID);
+  });
+
+  it('removes stale PID files from dead owners', async () => {
+    fs.mkdirSync(getPidDir(), { recursive: true });
+    const file = path.join(getPidDir(), `${DEAD_PID}-${DEAD_PID - 1}.json`);
+    fs.writeFileSync(
+      file,
+      JSON.stringify({ owner: DEAD_PID, pid: DEAD_PID - 1, command: 'node', startedAt: '' })
+    );
+    fs.writeFileSync(path.join(getPidDir(), 'corrupt.json'), '{');
+
+    expect(await sweepOrphanedProcesses()).toBe(0);
+    expect(fs.readdirSync(getPidDir())).toEqual([]);
+  });
+
+  it.skipIf(process.platform === 'win32')(
+    'sweeps an orphan only while its PID has the recorded start time',
+    async () => {
+      const child = spawn(process.execPath, ['-e', 'setInterval(() => {}, 1000)'], {
+        stdio: 'ignore',
+        ...processGroupSpawnOptions(),
+      });
+      const pid = child.pid!;
+      const exited = new Promise((resolve) => child.once('exit', resolve));
+      const record = (processStart: string | undefined) => {
+        fs.mkdirSync(getPidDir(), { recursive: true });
+        fs.writeFileSync(
+          path.join(getPidDir(), `${DEAD_PID}-${pid}.json`),
+          JSON.stringify({
+            owner: DEAD_PID,
+            pid,
+            command: process.execPath,
+            startedAt: '',
+            processStart,
+          })
+        );
+      };
+
+      try {
+        // The PID now belongs to a process started at another time
+        record('0');
+        expect(await sweepOrphanedProcesses()).toBe(0);
+        expect(isProcessAlive(pid)).toBe(true);
+
+        record(processStartTime(pid));
+        expect(await sweepOrphanedProcesses()).toBe(1);
+        await exited;
+        expect(fs.readdirSync(getPidDir())).toEqual([]);
+      } finally {
+        child.kill('SIGKILL');
+      }
+    }
+  );
+
+  it('returns 0 when there is no PID directory', async () => {
+    expect(await sweepOrphanedProcesses()).toBe(0);
+  });
+});
@@ -0,0 +1,119 @@
+/**
+ * Unit tests for Python environment detection
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import { detectPythonInterpreter, pythonInPrefix } from '../../src/util/python-env.js';
+
+function createPython(prefix: string): string {
+  const python = pythonInPrefix(prefix);
+  fs.mkdirSync(path.dirname(python), { recursive: true });
+  fs.writeFileSync(python, '');
+  return python;
+}
+
+describe('pythonInPrefix', () => {
+  it('uses bin/python on POSIX', () => {
+    expect(pythonInPrefix('/env', 'linux')).toBe(path.join('/env', 'bin', 'python'));
+  });
+
+  it('uses Scripts/python.exe on Windows', () => {
+    expect(pythonInPrefix('C:\\env', 'win32')).toBe(path.join('C:\\env', 'Scripts', 'python.exe'));
+  });
+});
+
+describe('detectPythonInterpreter', () => {
+  let root: string;
+
+  beforeEach(() => {
+    root = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-pyenv-'));
+  });
+
+  afterEach(() => {
+    fs.rmSync(root, { recursive: true, force: true });
+  });
+
+  it('prefers an activated virtualenv', () => {
+    const active = createPython(path.join(root, 'active'));
+    createPython(path.join(root, '.venv'));
+
+    expect(detectPythonInterpreter([root], { VIRTUAL_ENV: path.join(root, 'active') })).toEqual({
+      path: active,
+      source: 'virtualenv',
+    });
+  });
+
+  it('finds a project-local .venv from a subdirectory', () => {
+    const python = createPython(path.join(root, '.venv'));
+    const sub = path.join(root, 'src', 'pkg');
+    fs.mkdirSync(sub, { recursive: true });
+
+    expect(detectPythonInterpreter([sub], {})).toEqual({ path: python, source: 'venv' });
+  });
+
+  it("finds the program's venv before the working directory's", () => {
+    createPython(path.join(root, 'cwd', '.venv'));
+    const python = createPython(path.join(root, 'tool', '.venv'));
+    const programDir = path.join(root, 'tool', 'scripts');
+    fs.mkdirSync(programDir, { recursive: true });
+
+    expect(detectPythonInterpreter([programDir, path.join(root, 'cwd')], {})).toEqual({
+      path: python,
+      source: 'venv',
+    });
+  });
+
+  it('does not search above the pyproject.toml root', () => {
+    createPython(path.join(root, '.venv'));
+    const project = path.join(root, 'project');
+    fs.mkdirSync(project);
+    fs.writeFileSync(path.join(project, 'pyproject.toml'), '[project]\nname = "x"\n');
+
+    expect(detectPythonInterpreter([project], {})).toBeNull();
+  });
+
+  it.skipIf(process.platform === 'win32')(
+    "asks Poetry for the environment of a subdirectory's project",
+    () => {
+      const python = createPython(path.join(root, 'poetry-env'));
+      const project = path.join(root, 'project');
+      const sub = path.join(project, 'src', 'pkg');
+      fs.mkdirSync(sub, { recursive: true });
+      fs.writeFileSync(path.join(project, 'pyproject.toml'), '[tool.poetry]\nname = "x"\n');
+      // A stand-in poetry that only answers from the project root
+      const bin = path.join(root, 'bin');
+      fs.mkdirSync(bin);
+      fs.writeFileSync(
+        path.join(bin, 'poetry'),
+        `#!/bin/sh\n[ -f pyproject.toml ] && echo '${path.join(root, 'poetry-env')}'\n`,
+        { mode: 0o755 }
+      );
+
+      const originalPath = process.env.PATH;
+      process.env.PATH = `${bin}${path.delimiter}${originalPath}`;
+      try {
+        expect(detectPythonInterpreter([sub], {})).toEqual({ path: python, source: 'poetry' });
+      } finally {
+        process.env.PATH = originalPath;
+      }
+    }
+  );
+
+  it('falls back to an activated conda environment', () => {
+    const condaPrefix = path.join(root, 'conda');
+    const python =
+      process.platform === 'win32'
+        ? path.join(condaPrefix, 'python.exe')
+        : path.join(condaPrefix, 'bin', 'python');
+    fs.mkdirSync(path.dirname(python), { recursive: true });
+    fs.writeFileSync(python, '');
+
+    expect(detectPythonInterpreter([root], { CONDA_PREFIX: condaPrefix })).toEqual({
+      path: python,
+      source: 'conda',
+    });
+  });
+});
@@ -0,0 +1,122 @@
+/**
+ * Unit tests for debuggee resource limits
+ */
+
+import { describe, it, expect } from 'vitest';
+import { execFileSync, spawn } from 'node:child_process';
+import * as fs from 'node:fs';
+import {
+  applyResourceLimits,
+  jobObjectScript,
+  parseCpuLimit,
+  parseFdLimit,
+  parseMemoryLimit,
+  prlimitArgs,
+  unsupportedResourceLimits,
+} from '../../src/util/resource-limits.js';
+
+function hasPrlimit(): boolean {
+  try {
+    execFileSync('prlimit', ['--version'], { stdio: 'ignore' });
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+describe('parseMemoryLimit', () => {
+  it('parses binary units', () => {
+    expect(parseMemoryLimit('512M')).toBe(512 * 1024 ** 2);
+    expect(parseMemoryLimit('2G')).toBe(2 * 1024 ** 3);
+    expect(parseMemoryLimit('1.5GiB')).toBe(1.5 * 1024 ** 3);
+    expect(parseMemoryLimit('64kb')).toBe(64 * 1024);
+  });
+
+  it('treats bare numbers as bytes', () => {
+    expect(parseMemoryLimit('4096')).toBe(4096);
+  });
+
+  it('rejects invalid sizes', () => {
+    expect(() => parseMemoryLimit('lots')).toThrow('Invalid memory limit');
+    expect(() => parseMemoryLimit('0G')).toThrow('greater than zero');
+  });
+});
+
+describe('parseCpuLimit', () => {
+  it('parses durations in seconds', () => {
+    expect(parseCpuLimit('120s')).toBe(120);
+    expect(parseCpuLimit('5m')).toBe(300);
+    expect(parseCpuLimit('1h')).toBe(3600);
+    expect(parseCpuLimit('30')).toBe(30);
+  });
+
+  it('rejects invalid durations', () => {
+    expect(() => parseCpuLimit('2 minutes')).toThrow('Invalid CPU limit');
+    expect(() => parseCpuLimit('0s')).toThrow('Invalid CPU limit');
+  });
+});
+
+describe('parseFdLimit', () => {
+  it('parses a positive integer', () => {
+    expect(parseFdLimit('1024')).toBe(1024);
+  });
+
+  it('rejects anything else', () => {
+    expect(() => parseFdLimit('-1')).toThrow('Invalid file descriptor limit');
+    expect(() => parseFdLimit('0')).toThrow('Invalid file descriptor limit');
+  });
+});
+
+describe('unsupportedResourceLimits', () => {
+  const all = { memoryBytes: 1024, cpuSeconds: 10, fileDescriptors: 64 };
+
+  it('supports everything on Linux', () => {
+    expect(unsupportedResourceLimits(all, 'linux')).toEqual([]);
+  });
+
+  it('cannot limit file descriptors on Windows', () => {
+    expect(unsupportedResourceLimits(all, 'win32')).toEqual(['--limit-fds']);
+  });
+
+  it('reports every requested limit elsewhere', () => {
+    expect(unsupportedResourceLimits({ cpuSeconds: 10 }, 'darwin')).toEqual(['--limit-cpu']);
+  });
+});
+
+describe('prlimitArgs', () => {
+  it('only includes requested limits', () => {
+    expect(prlimitArgs(42, { memoryBytes: 2048, fileDescriptors: 256 })).toEqual([
+      '--pid',
+      '42',
+      '--data=2048',
+      '--nofile=256',
+    ]);
+  });
+});
+
+describe('jobObjectScript', () => {
+  it('passes the PID, memory and CPU time (in 100ns ticks)', () => {
+    const script = jobObjectScript(1234, { memoryBytes: 2048, cpuSeconds: 2 });
+    expect(script).toContain('[DebugRunJob]::Apply(1234, 2048, 20000000)');
+  });
+});
+
+describe.skipIf(process.platform !== 'linux' || !hasPrlimit())('applyResourceLimits', () => {
+  it('applies rlimits to a running process', async () => {
+    const child = spawn('sleep', ['30'], { stdio: 'ignore' });
+    try {
+      await applyResourceLimits(child.pid!, {
+        memoryBytes: 1024 ** 3,
+        cpuSeconds: 60,
+        fileDescriptors: 128,
+      });
+
+      const limits = fs.readFileSync(`/proc/${child.pid}/limits`, 'utf-8');
+      expect(limits).toMatch(/Max cpu time\s+60\s+60/);
+      expect(limits).toMatch(/Max data size\s+1073741824\s+1073741824/);
+      expect(limits).toMatch(/Max open files\s+128\s+128/);
+    } finally {
+      child.kill();
+    }
+  });
+});
@@ -0,0 +1,169 @@
+/**
+ * Unit tests for the debuggee sandbox (--sandbox)
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import * as fs from 'node:fs';
+import * as os from 'node:os';
+import * as path from 'node:path';
+import {
+  bwrapArgs,
+  defaultHiddenPaths,
+  sandboxEnv,
+  sandboxExecProfile,
+  sandboxScope,
+  sandboxSpawn,
+  seccompFilter,
+  type SandboxPolicy,
+} from '../../src/util/sandbox.js';
+
+const policy: SandboxPolicy = { writable: [], hidden: [], network: false };
+
+describe('sandboxScope', () => {
+  it('confines only the program when the adapter can start it through runInTerminal', () => {
+    expect(sandboxScope({ terminalLaunch: 'pipe' }, policy, 'linux')).toBe('program');
+    expect(sandboxScope({ terminalLaunch: 'loopback' }, policy, 'darwin')).toBe('program');
+    expect(
+      sandboxScope({ terminalLaunch: 'loopback' }, { ...policy, network: true }, 'linux')
+    ).toBe('program');
+  });
+
+  it('confines the adapter when the program alone could not be', () => {
+    expect(sandboxScope({}, policy, 'linux')).toBe('adapter');
+    expect(sandboxScope({ terminalLaunch: 'loopback' }, policy, 'linux')).toBe('adapter');
+    expect(sandboxScope({ terminalLaunch: 'pipe' }, { ...policy, adapter: true }, 'linux')).toBe(
+      'adapter'
+    );
+  });
+});
+
+describe('defaultHiddenPaths', () => {
+  it('lists credential stores in the home directory', () => {
+    const hidden = defaultHiddenPaths('/home/me');
+    expect(hidden).toContain(path.join('/home/me', '.ssh'));
+    expect(hidden).toContain(path.join('/home/me', '.config/gh'));
+  });
+});
+
+describe('sandboxEnv', () => {
+  it('keeps only allowed variables of ours, then the given ones', () => {
+    const base = { PATH: '/usr/bin', LC_ALL: 'C', AWS_SECRET_ACCESS_KEY: 'x', GITHUB_TOKEN: 'y' };
+
+    expect(sandboxEnv({ ORDER_ID: '7', PATH: null }, base)).toEqual({ LC_ALL: 'C', ORDER_ID: '7' });
+  });
+});
+
+describe('seccompFilter', () => {
+  it('builds a filter of whole instructions for known architectures', () => {
+    for (const arch of ['x64', 'arm64']) {
+      const filter = seccompFilter(arch)!;
+      expect(filter.length % 8).toBe(0);
+      // Starts by checking the architecture and ends with the EPERM and ENOSYS returns
+      expect(filter.readUInt16LE(0)).toBe(0x20);
+      expect(filter.readUInt32LE(4)).toBe(4);
+      expect(filter.readUInt32LE(filter.length - 12)).toBe(0x00050001);
+      expect(filter.readUInt32LE(filter.length - 4)).toBe(0x00050026);
+    }
+    expect(seccompFilter('ia32')).toBeUndefined();
+  });
+});
+
+describe('sandbox command lines', () => {
+  let dir: string;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'debug-run-sandbox-'));
+    fs.mkdirSync(path.join(dir, 'out'));
+    fs.mkdirSync(path.join(dir, '.ssh'));
+    fs.writeFileSync(path.join(dir, '.netrc'), 'machine example.com');
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  it('mounts everything read-only but the writable paths and hides paths that exist', () => {
+    const args = bwrapArgs(
+      {
+        writable: [path.join(dir, 'out'), path.join(dir, 'missing')],
+        hidden: [path.join(dir, '.ssh'), path.join(dir, '.netrc'), path.join(dir, '.aws')],
+        network: false,
+      },
+      '/tmp'
+    );
+    const run = fs.existsSync('/run') ? ['--tmpfs', '/run'] : [];
+    expect(args).toEqual([
+      '--unshare-all',
+      '--new-session',
+      '--die-with-parent',
+      '--cap-drop',
+      'ALL',
+      '--clearenv',
+      '--args',
+      '4',
+      '--ro-bind',
+      '/',
+      '/',
+      '--dev-bind',
+      '/dev',
+      '/dev',
+      ...run,
+      '--bind',
+      '/tmp',
+      '/tmp',
+      '--bind',
+      path.join(dir, 'out'),
+      path.join(dir, 'out'),
+      '--tmpfs',
+      path.join(dir, '.ssh'),
+      '--ro-bind',
+      '/dev/null',
+      path.join(dir, '.netrc'),
+      '--',
+    ]);
+  });
+
+  it('shares the network only when allowed, and hides the sandbox files', () => {
+    const args = bwrapArgs({ ...policy, network: true }, '/tmp', {
+      filesDir: path.join(dir, 'files'),
+      seccomp: true,
+    });
+    expect(args.slice(0, 2)).toEqual(['--unshare-all', '--share-net']);
+    expect(args.slice(-5)).toEqual(['--tmpfs', path.join(dir, 'files'), '--seccomp', '3', '--']);
+    expect(bwrapArgs(policy, '/tmp')).not.toContain('--share-net');
+  });
+
+  it('writes a sandbox-exec profile that denies writes, hidden paths and remote network', () => {
+    const out = fs.realpathSync(path.join(dir, 'out'));
+    const ssh = fs.realpathSync(path.join(dir, '.ssh'));
+    const profile = sandboxExecProfile(
+      { writable: [path.join(dir, 'out')], hidden: [path.join(dir, '.ssh')], network: false },
+      dir
+    );
+    expect(profile.split('\n')).toEqual([
+      '(version 1)',
+      '(allow default)',
+      '(deny file-write*)',
+      `(allow file-write* (subpath "/dev") (subpath "/private/tmp") (subpath "${fs.realpathSync(dir)}") (subpath "${out}"))`,
+      `(deny file-read* file-write* (subpath "${ssh}"))`,
+      '(deny network*)',
+      '(allow network* (remote ip "localhost:*") (local ip "localhost:*") (remote unix-socket))',
+    ]);
+  });
+
+  it('wraps the command for the platform', () => {
+    const linux = sandboxSpawn('python3', ['app.py'], policy, { SECRET: 'x' }, 'linux');
+    expect(linux.command).toBe('sh');
+    expect(linux.args[1]).toMatch(/; exec bwrap "\$@"$/);
+    expect(linux.args.slice(-3)).toEqual(['--', 'python3', 'app.py']);
+    expect(linux.args).not.toContain('x');
+    expect(fs.readFileSync(linux.args[3], 'utf-8')).toContain('--setenv\0SECRET\0x\0');
+    const macos = sandboxSpawn('python3', ['app.py'], policy, {}, 'darwin');
+    expect(macos.command).toBe('sandbox-exec');
+    expect(macos.args[0]).toBe('-p');
+    expect(macos.args.slice(2)).toEqual(['python3', 'app.py']);
+    expect(() => sandboxSpawn('app.exe', [], policy, {}, 'win32')).toThrow(
+      '--sandbox is not supported on win32'
+    );
+  });
+});
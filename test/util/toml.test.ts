@@ -0,0 +1,52 @@
+/**
+ * Unit tests for the TOML subset config files use
+ */
+
+import { describe, it, expect } from 'vitest';
+import { parseToml } from '../../src/util/toml.js';
+
+describe('parseToml', () => {
+  it('parses tables, dotted keys and values', () => {
+    const doc = [
+      '# personal defaults',
+      '[defaults]',
+      'timeout = "2m"  # slow machine',
+      'pretty = true',
+      '',
+      '[adapters.node]',
+      'skip-files = [',
+      '  "<node_internals>/**",',
+      "  '**/node_modules/**',",
+      ']',
+      '"stack-limit" = 5',
+      'limits.max = 1_000',
+      'ratio = -1.5e3',
+      'label = "tab\\there \\u00e9"',
+    ].join('\n');
+
+    expect(parseToml(doc)).toEqual({
+      defaults: { timeout: '2m', pretty: true },
+      adapters: {
+        node: {
+          'skip-files': ['<node_internals>/**', '**/node_modules/**'],
+          'stack-limit': 5,
+          limits: { max: 1000 },
+          ratio: -1500,
+          label: 'tab\there é',
+        },
+      },
+    });
+  });
+
+  it('names the line of an error', () => {
+    expect(() => parseToml('a = 1\na = 2')).toThrow('line 2: "a" is defined twice');
+    expect(() => parseToml('a = 1 b')).toThrow('line 1: unexpected "b" after value');
+    expect(() => parseToml('\n\na = "open')).toThrow('line 3: unterminated string');
+  });
+
+  it('rejects the parts of TOML it does not support', () => {
+    expect(() => parseToml('env = { DEBUG = "1" }')).toThrow('inline tables are not supported');
+    expect(() => parseToml('[[runs]]')).toThrow('arrays of tables are not supported');
+    expect(() => parseToml('since = 1979-05-27')).toThrow('dates and times are not supported');
+  });
+});
@@ -0,0 +1,111 @@
+/**
+ * Unit tests for WSL path translation and adapter command lines (--wsl)
+ */
+
+import { describe, it, expect } from 'vitest';
+import {
+  windowsToWslPath,
+  wslPathMapper,
+  wslSpawn,
+  wslToWindowsPath,
+} from '../../src/util/wsl.js';
+
+describe('windowsToWslPath', () => {
+  it('moves drive paths under /mnt', () => {
+    expect(windowsToWslPath('C:\\src\\app\\main.py')).toBe('/mnt/c/src/app/main.py');
+    expect(windowsToWslPath('d:/work/index.ts')).toBe('/mnt/d/work/index.ts');
+    expect(windowsToWslPath('C:\\')).toBe('/mnt/c/');
+  });
+
+  it('maps the distribution share to its root', () => {
+    expect(windowsToWslPath('\\\\wsl.localhost\\Ubuntu\\home\\me\\app.py')).toBe(
+      '/home/me/app.py'
+    );
+    expect(windowsToWslPath('\\\\wsl$\\Ubuntu')).toBe('/');
+  });
+
+  it('leaves other strings alone', () => {
+    expect(windowsToWslPath('--interpreter=vscode')).toBe('--interpreter=vscode');
+    expect(windowsToWslPath('C:')).toBe('C:');
+    expect(windowsToWslPath('src\\app.py')).toBe('src\\app.py');
+    expect(windowsToWslPath('/home/me/app.py')).toBe('/home/me/app.py');
+  });
+});
+
+describe('wslToWindowsPath', () => {
+  it('maps /mnt paths back to their drive', () => {
+    expect(wslToWindowsPath('/mnt/c/src/app/main.py', 'Ubuntu')).toBe('C:\\src\\app\\main.py');
+    expect(wslToWindowsPath('/mnt/d', 'Ubuntu')).toBe('D:\\');
+  });
+
+  it('puts other absolute paths on the distribution share', () => {
+    expect(wslToWindowsPath('/usr/lib/python3.12/json/decoder.py', 'Ubuntu')).toBe(
+      '\\\\wsl.localhost\\Ubuntu\\usr\\lib\\python3.12\\json\\decoder.py'
+    );
+    expect(wslToWindowsPath('/home/me/app.py')).toBe('/home/me/app.py');
+  });
+
+  it('leaves relative and virtual paths alone', () => {
+    expect(wslToWindowsPath('<node_internals>/fs', 'Ubuntu')).toBe('<node_internals>/fs');
+    expect(wslToWindowsPath('app.py', 'Ubuntu')).toBe('app.py');
+  });
+
+  it('round-trips through the path mapper', () => {
+    const mapper = wslPathMapper('Ubuntu');
+    for (const local of ['C:\\src\\app.py', '\\\\wsl.localhost\\Ubuntu\\home\\me\\app.py']) {
+      expect(mapper.toLocal(mapper.toAdapter(local))).toBe(local);
+    }
+  });
+});
+
+describe('wslSpawn', () => {
+  it('runs the adapter through wsl.exe with translated arguments', () => {
+    const spawn = wslSpawn(
+      'node',
+      ['C:\\Users\\me\\.debug-run\\js-debug\\src\\dapDebugServer.js', '8177'],
+      {},
+      'Ubuntu',
+      {}
+    );
+
+    expect(spawn.command).toBe('wsl.exe');
+    expect(spawn.args).toEqual([
+      '--distribution',
+      'Ubuntu',
+      '--exec',
+      'sh',
+      '-lc',
+      'exec "$0" "$@"',
+      'node',
+      '/mnt/c/Users/me/.debug-run/js-debug/src/dapDebugServer.js',
+      '8177',
+    ]);
+  });
+
+  it('uses the distribution command for a Windows executable', () => {
+    const spawn = wslSpawn(
+      'C:\\tools\\netcoredbg\\netcoredbg.exe',
+      ['--interpreter=vscode'],
+      {},
+      undefined,
+      {}
+    );
+    expect(spawn.args.slice(-2)).toEqual(['netcoredbg', '--interpreter=vscode']);
+    expect(spawn.args).not.toContain('--distribution');
+  });
+
+  it('shares the environment through WSLENV, translating paths', () => {
+    const spawn = wslSpawn(
+      'python3',
+      ['-m', 'debugpy.adapter'],
+      { DEBUG: '1', DATA_DIR: 'C:\\data' },
+      undefined,
+      { WSLENV: 'USERPROFILE/p' }
+    );
+    expect(spawn.env).toEqual({
+      DEBUG: '1',
+      DATA_DIR: 'C:\\data',
+      WSLENV: 'USERPROFILE/p:DEBUG:DATA_DIR/p',
+    });
+  });
+});